@@ -128,6 +128,98 @@ impl DiagnosticsBundle {
         crate::event::EventStats::from_events(&self.events)
     }
 
+    /// Summary manifest describing this bundle's contents
+    pub fn manifest(&self) -> BundleManifest {
+        let mut entries: Vec<String> = [
+            "manifest.json",
+            "app_info.json",
+            "system_info.json",
+            "events.json",
+            "logs.json",
+            "extensions.json",
+            "permissions.json",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        if self.crash_report.is_some() {
+            entries.push("crash_report.json".to_string());
+        }
+        entries.push("metadata.json".to_string());
+
+        BundleManifest {
+            schema_version: BUNDLE_MANIFEST_VERSION.to_string(),
+            bundle_id: self.id,
+            bundle_version: self.version.clone(),
+            created_at: self.created_at,
+            app_name: self.app_info.name.clone(),
+            app_version: self.app_info.version.clone(),
+            event_count: self.events.len(),
+            log_count: self.logs.len(),
+            extension_count: self.extensions.len(),
+            has_crash_report: self.crash_report.is_some(),
+            redaction_applied: self.metadata.redaction_applied,
+            entries,
+        }
+    }
+
+    /// Write the bundle as a zip archive for attaching to support tickets.
+    ///
+    /// Entries are written in a fixed order with fixed timestamps and sorted
+    /// JSON keys, so the same bundle always produces the same archive.
+    #[cfg(feature = "bundle-export")]
+    pub fn write_zip(&self, path: &std::path::Path) -> Result<BundleManifest, std::io::Error> {
+        use std::io::Write;
+        use zip::write::SimpleFileOptions;
+        use zip::ZipWriter;
+
+        let manifest = self.manifest();
+        let file = std::fs::File::create(path)?;
+        let mut zip = ZipWriter::new(file);
+
+        let options = SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated)
+            .last_modified_time(zip::DateTime::default())
+            .unix_permissions(0o644);
+
+        for entry in &manifest.entries {
+            let content = match entry.as_str() {
+                "manifest.json" => to_sorted_json(&manifest)?,
+                "app_info.json" => to_sorted_json(&self.app_info)?,
+                "system_info.json" => to_sorted_json(&self.system_info)?,
+                "events.json" => to_sorted_json(&self.events)?,
+                "logs.json" => to_sorted_json(&self.logs)?,
+                "extensions.json" => to_sorted_json(&self.extensions)?,
+                "permissions.json" => to_sorted_json(&self.permissions)?,
+                "crash_report.json" => to_sorted_json(&self.crash_report)?,
+                "metadata.json" => to_sorted_json(&self.metadata)?,
+                _ => continue,
+            };
+
+            zip.start_file(entry.as_str(), options)
+                .map_err(std::io::Error::other)?;
+            zip.write_all(content.as_bytes())?;
+        }
+
+        zip.finish().map_err(std::io::Error::other)?;
+        Ok(manifest)
+    }
+
+    /// Read the manifest entry from a bundle zip
+    #[cfg(feature = "bundle-export")]
+    pub fn read_zip_manifest(path: &std::path::Path) -> Result<BundleManifest, std::io::Error> {
+        let file = std::fs::File::open(path)?;
+        let mut archive = zip::ZipArchive::new(file).map_err(|e| {
+            std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string())
+        })?;
+        let entry = archive.by_name("manifest.json").map_err(|e| {
+            std::io::Error::new(std::io::ErrorKind::NotFound, e.to_string())
+        })?;
+        serde_json::from_reader(entry).map_err(|e| {
+            std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string())
+        })
+    }
+
     /// Filter events by severity
     pub fn filter_events_by_severity(&self, min_severity: crate::Severity) -> Vec<&DiagnosticEvent> {
         self.events
@@ -137,6 +229,57 @@ impl DiagnosticsBundle {
     }
 }
 
+/// Bundle manifest schema version
+pub const BUNDLE_MANIFEST_VERSION: &str = "1";
+
+/// Manifest stored as the first entry of a bundle zip
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BundleManifest {
+    /// Manifest schema version
+    pub schema_version: String,
+
+    /// Bundle ID
+    pub bundle_id: Uuid,
+
+    /// Bundle format version
+    pub bundle_version: String,
+
+    /// Bundle creation timestamp
+    pub created_at: DateTime<Utc>,
+
+    /// Application name
+    pub app_name: String,
+
+    /// Application version
+    pub app_version: String,
+
+    /// Number of events
+    pub event_count: usize,
+
+    /// Number of log entries
+    pub log_count: usize,
+
+    /// Number of extensions
+    pub extension_count: usize,
+
+    /// Whether a crash report is included
+    pub has_crash_report: bool,
+
+    /// Whether redaction was applied to the bundle contents
+    pub redaction_applied: bool,
+
+    /// Archive entries, in write order
+    pub entries: Vec<String>,
+}
+
+/// Serialize via `serde_json::Value` so map keys come out sorted
+#[cfg(feature = "bundle-export")]
+fn to_sorted_json<T: Serialize>(value: &T) -> Result<String, std::io::Error> {
+    serde_json::to_value(value)
+        .and_then(|v| serde_json::to_string_pretty(&v))
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))
+}
+
 /// System information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SystemInfo {
@@ -312,7 +455,8 @@ impl BundleCrashReport {
 /// Stack frame (minimal, for bundle crash reports)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BundleStackFrame {
-    /// Instruction pointer (hex)
+    /// Instruction pointer (hex), empty when unknown
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub ip: String,
 
     /// Symbol name (if available)
@@ -339,6 +483,10 @@ pub struct BundleMetadata {
     #[serde(default)]
     pub auto_report_consent: bool,
 
+    /// Whether logs were passed through redaction rules
+    #[serde(default)]
+    pub redaction_applied: bool,
+
     /// Custom fields
     #[serde(default, flatten)]
     pub custom: HashMap<String, serde_json::Value>,
@@ -378,4 +526,36 @@ mod tests {
         assert!(!info.os.is_empty());
         assert!(!info.arch.is_empty());
     }
+
+    #[cfg(feature = "bundle-export")]
+    #[test]
+    fn test_write_zip_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bundle.zip");
+
+        let app_info = AppInfo::from_env("TestApp", "1.0.0", "abc123");
+        let events = vec![
+            DiagnosticEvent::new(ErrorCode::UI_INVALID_PROP, Severity::Warning, "test")
+                .with_context("b", 2)
+                .with_context("a", 1),
+        ];
+        let logs = vec![LogEntry::new(crate::LogLevel::Info, "ui", "hello")];
+        let bundle = DiagnosticsBundle::new(app_info, events, logs)
+            .with_crash_report(BundleCrashReport::new("boom"));
+
+        let written = bundle.write_zip(&path).unwrap();
+        let manifest = DiagnosticsBundle::read_zip_manifest(&path).unwrap();
+
+        assert_eq!(manifest, written);
+        assert_eq!(manifest.schema_version, BUNDLE_MANIFEST_VERSION);
+        assert_eq!(manifest.event_count, 1);
+        assert_eq!(manifest.log_count, 1);
+        assert!(manifest.has_crash_report);
+        assert_eq!(manifest.entries[0], "manifest.json");
+
+        // Same bundle, same bytes
+        let again = dir.path().join("again.zip");
+        bundle.write_zip(&again).unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), std::fs::read(&again).unwrap());
+    }
 }
@@ -2,16 +2,22 @@
 //!
 //! Catches panics and writes crash reports to local files.
 
-use crate::{AppInfo, RedactionRules, redact_string};
+use crate::{
+    AppInfo, BundleCrashReport, BundleStackFrame, DiagnosticEvent, DiagnosticsCollector,
+    DiagnosticsConfig, ErrorCode, RedactionRules, Severity, redact_string,
+};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::panic::{self, PanicHookInfo};
 use std::path::{Path, PathBuf};
-use std::sync::OnceLock;
+use std::sync::{Arc, OnceLock};
 
 /// Global crash handler configuration
 static CRASH_CONFIG: OnceLock<CrashConfig> = OnceLock::new();
 
+/// Collector that receives crash reports, if one was installed
+static CRASH_COLLECTOR: OnceLock<Arc<DiagnosticsCollector>> = OnceLock::new();
+
 /// Crash handler configuration
 #[derive(Debug, Clone)]
 pub struct CrashConfig {
@@ -29,6 +35,9 @@ pub struct CrashConfig {
 
     /// Whether to include stack traces
     pub include_stack_trace: bool,
+
+    /// Keep full file paths in stack frames (otherwise only the file name)
+    pub include_full_paths: bool,
 }
 
 impl Default for CrashConfig {
@@ -39,6 +48,20 @@ impl Default for CrashConfig {
             redaction_rules: RedactionRules::default(),
             max_crash_files: 10,
             include_stack_trace: true,
+            include_full_paths: false,
+        }
+    }
+}
+
+impl CrashConfig {
+    /// Derive crash settings from the diagnostics configuration
+    pub fn from_diagnostics_config(app_info: AppInfo, config: &DiagnosticsConfig) -> Self {
+        Self {
+            app_info,
+            redaction_rules: config.redaction_rules.clone(),
+            include_stack_trace: config.include_stack_traces,
+            include_full_paths: config.include_full_paths,
+            ..Default::default()
         }
     }
 }
@@ -77,6 +100,10 @@ pub struct StackFrame {
     /// Symbol name (demangled if possible)
     pub symbol: Option<String>,
 
+    /// Module (crate) the symbol belongs to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub module: Option<String>,
+
     /// File path (redacted)
     pub file: Option<String>,
 
@@ -128,7 +155,7 @@ impl CrashReport {
 
         // Capture stack trace
         let stack_trace = if config.include_stack_trace {
-            Some(capture_stack_trace(&config.redaction_rules))
+            Some(capture_stack_trace(config))
         } else {
             None
         };
@@ -179,50 +206,176 @@ impl CrashReport {
     pub fn to_json(&self) -> Result<String, serde_json::Error> {
         serde_json::to_string_pretty(self)
     }
+
+    /// Convert to the lightweight crash report embedded in diagnostics bundles
+    pub fn to_bundle_report(&self) -> BundleCrashReport {
+        let mut report = BundleCrashReport::new(self.message.clone());
+        report.id = self.id;
+        report.timestamp = self.timestamp;
+        report.error_code = Some(ErrorCode::RUNTIME_PANIC);
+        report.thread_id = Some(self.system_info.thread_id.clone());
+        report.stack_trace = self.stack_trace.as_ref().map(|frames| {
+            frames.iter().map(StackFrame::to_bundle_frame).collect()
+        });
+        report
+    }
+
+    /// Convert to a fatal diagnostic event carrying the structured stack trace
+    pub fn to_event(&self) -> DiagnosticEvent {
+        let mut event = DiagnosticEvent::new(ErrorCode::RUNTIME_PANIC, Severity::Fatal, &self.message)
+            .with_context("crash_id", self.id.to_string());
+
+        if let Some(ref location) = self.location {
+            event = event.with_context("location", location);
+        }
+        if let Some(ref frames) = self.stack_trace {
+            event = event.with_context("stack_trace", frames);
+        }
+
+        event
+    }
 }
 
+impl StackFrame {
+    /// Convert to a bundle stack frame.
+    ///
+    /// `std::backtrace` does not expose instruction addresses, so `ip` is left
+    /// empty and `offset` holds the source location when it is known.
+    pub fn to_bundle_frame(&self) -> BundleStackFrame {
+        let offset = self.file.as_ref().map(|file| match (self.line, self.column) {
+            (Some(line), Some(column)) => format!("{}:{}:{}", file, line, column),
+            (Some(line), None) => format!("{}:{}", file, line),
+            _ => file.clone(),
+        });
+
+        BundleStackFrame {
+            ip: String::new(),
+            symbol: self.symbol.clone(),
+            module: self.module.clone(),
+            offset,
+        }
+    }
+}
+
+/// Maximum number of frames kept in a crash report
+const MAX_FRAMES: usize = 50;
+
 /// Capture the current stack trace
-fn capture_stack_trace(rules: &RedactionRules) -> Vec<StackFrame> {
-    let mut frames = Vec::new();
+fn capture_stack_trace(config: &CrashConfig) -> Vec<StackFrame> {
+    // Crash reports always want a trace, regardless of RUST_BACKTRACE
+    let bt = std::backtrace::Backtrace::force_capture();
+    symbolicate(&bt.to_string(), &config.redaction_rules, config.include_full_paths)
+}
 
-    // Use backtrace crate if available, otherwise return empty
-    // For now, we'll capture a simple representation
-    let bt = std::backtrace::Backtrace::capture();
-    let bt_str = format!("{}", bt);
+/// Best-effort symbolication of a rendered `std::backtrace::Backtrace`.
+///
+/// The standard format is a `N: symbol` line optionally followed by an
+/// `at file:line:column` line. Paths are reduced to the file name unless
+/// `include_full_paths` is set, and are always passed through redaction.
+pub fn symbolicate(backtrace: &str, rules: &RedactionRules, include_full_paths: bool) -> Vec<StackFrame> {
+    let mut frames: Vec<StackFrame> = Vec::new();
+
+    for line in backtrace.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
 
-    // Parse the backtrace string into frames
-    for (index, line) in bt_str.lines().enumerate() {
-        if line.trim().is_empty() {
+        if let Some(location) = trimmed.strip_prefix("at ") {
+            if let Some(frame) = frames.last_mut() {
+                let (file, line, column) = parse_location(location);
+                let file = if include_full_paths {
+                    file.to_string()
+                } else {
+                    file.rsplit(['/', '\\']).next().unwrap_or(file).to_string()
+                };
+                frame.file = Some(redact_string(&file, rules));
+                frame.line = line;
+                frame.column = column;
+            }
             continue;
         }
 
-        // Redact the line
-        let redacted_line = redact_string(line, rules);
+        let Some((index, symbol)) = trimmed.split_once(": ") else {
+            continue;
+        };
+        let Ok(index) = index.parse::<usize>() else {
+            continue;
+        };
 
+        if frames.len() >= MAX_FRAMES {
+            break;
+        }
+
+        let symbol = symbol.trim();
         frames.push(StackFrame {
             index,
-            symbol: Some(redacted_line),
+            symbol: Some(symbol.to_string()),
+            module: symbol_module(symbol),
             file: None,
             line: None,
             column: None,
         });
-
-        // Limit to 50 frames
-        if frames.len() >= 50 {
-            break;
-        }
     }
 
     frames
 }
 
+/// Split `file:line:column` into its parts
+fn parse_location(location: &str) -> (&str, Option<u32>, Option<u32>) {
+    let mut parts = location.rsplitn(3, ':');
+    let last = parts.next();
+    let middle = parts.next();
+    let rest = parts.next();
+
+    match (rest, middle, last) {
+        (Some(file), Some(line), Some(column)) => match (line.parse(), column.parse()) {
+            (Ok(line), Ok(column)) => (file, Some(line), Some(column)),
+            _ => (location, None, None),
+        },
+        (None, Some(file), Some(line)) => match line.parse() {
+            Ok(line) => (file, Some(line), None),
+            Err(_) => (location, None, None),
+        },
+        _ => (location, None, None),
+    }
+}
+
+/// Derive the crate name from a demangled symbol path
+fn symbol_module(symbol: &str) -> Option<String> {
+    let symbol = symbol.trim_start_matches('<');
+    let module = symbol.split("::").next()?;
+    if module.is_empty() || module == symbol || module.contains(' ') {
+        return None;
+    }
+    Some(module.to_string())
+}
+
 /// Install the crash handler
 ///
 /// This sets up a panic hook that captures crash reports and writes them to files.
 /// Call this early in your application's startup.
 pub fn install_crash_handler(config: CrashConfig) {
+    install(config, None);
+}
+
+/// Install the crash handler, also recording crashes in `collector`
+///
+/// Each crash becomes a fatal event with the structured stack trace, and the
+/// report is attached to the collector's next exported bundle.
+pub fn install_crash_handler_with_collector(
+    config: CrashConfig,
+    collector: Arc<DiagnosticsCollector>,
+) {
+    install(config, Some(collector));
+}
+
+fn install(config: CrashConfig, collector: Option<Arc<DiagnosticsCollector>>) {
     // Store config for later use
     let _ = CRASH_CONFIG.set(config.clone());
+    if let Some(collector) = collector {
+        let _ = CRASH_COLLECTOR.set(collector);
+    }
 
     // Get the previous panic hook (for chaining)
     let prev_hook = panic::take_hook();
@@ -232,6 +385,9 @@ pub fn install_crash_handler(config: CrashConfig) {
         if let Some(config) = CRASH_CONFIG.get() {
             // Create crash report
             let report = CrashReport::from_panic(info, config);
+            if let Some(collector) = CRASH_COLLECTOR.get() {
+                collector.record_crash(&report);
+            }
 
             // Try to save it
             match report.save_to_file(&config.crash_dir) {
@@ -361,6 +517,7 @@ mod tests {
             stack_trace: Some(vec![StackFrame {
                 index: 0,
                 symbol: Some("test_function".to_string()),
+                module: None,
                 file: Some("test.rs".to_string()),
                 line: Some(10),
                 column: Some(1),
@@ -400,4 +557,96 @@ mod tests {
         assert!(!snapshot.arch.is_empty());
         assert!(snapshot.num_cpus > 0);
     }
+
+    const SAMPLE_BACKTRACE: &str = "   0: std::backtrace::Backtrace::force_capture
+             at /rustc/abc/library/std/src/backtrace.rs:312:13
+   1: my_app::ui::on_click
+             at /home/alice/projects/my_app/src/ui.rs:42:9
+   2: __rust_begin_short_backtrace
+";
+
+    #[test]
+    fn test_symbolicate_frames() {
+        let frames = symbolicate(SAMPLE_BACKTRACE, &RedactionRules::default(), false);
+
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[1].symbol.as_deref(), Some("my_app::ui::on_click"));
+        assert_eq!(frames[1].module.as_deref(), Some("my_app"));
+        assert_eq!(frames[1].file.as_deref(), Some("ui.rs"));
+        assert_eq!(frames[1].line, Some(42));
+        assert_eq!(frames[1].column, Some(9));
+        assert!(frames[2].file.is_none());
+    }
+
+    #[test]
+    fn test_symbolicate_full_paths_are_redacted() {
+        let frames = symbolicate(SAMPLE_BACKTRACE, &RedactionRules::default(), true);
+        let file = frames[1].file.as_deref().unwrap();
+
+        assert!(file.starts_with("/home/[USER]/"));
+        assert!(!file.contains("alice"));
+    }
+
+    #[test]
+    fn test_captured_frames_convert_to_bundle() {
+        let config = CrashConfig::default();
+        let frames = capture_stack_trace(&config);
+        assert!(!frames.is_empty());
+        assert!(frames.iter().any(|f| f.symbol.is_some()));
+
+        let report = CrashReport {
+            id: uuid::Uuid::new_v4(),
+            timestamp: chrono::Utc::now(),
+            app_info: config.app_info.clone(),
+            message: "Test panic".to_string(),
+            location: None,
+            stack_trace: Some(symbolicate(SAMPLE_BACKTRACE, &RedactionRules::default(), false)),
+            system_info: SystemSnapshot {
+                os: "linux".to_string(),
+                arch: "x86_64".to_string(),
+                num_cpus: 8,
+                thread_name: None,
+                thread_id: "ThreadId(3)".to_string(),
+            },
+        };
+
+        let bundle_report = report.to_bundle_report();
+        assert_eq!(bundle_report.error_code, Some(ErrorCode::RUNTIME_PANIC));
+        let bundle_frames = bundle_report.stack_trace.unwrap();
+        assert_eq!(bundle_frames.len(), 3);
+        assert!(bundle_frames.iter().all(|f| f.ip.is_empty()));
+        assert_eq!(bundle_frames[1].offset.as_deref(), Some("ui.rs:42:9"));
+
+        let event = report.to_event();
+        assert_eq!(event.severity, Severity::Fatal);
+        assert!(event.context.contains_key("stack_trace"));
+    }
+
+    #[test]
+    fn test_panic_hook_records_crash() {
+        let dir = tempfile::tempdir().unwrap();
+        let collector = Arc::new(DiagnosticsCollector::new(
+            AppInfo::from_env("TestApp", "1.0.0", "test"),
+            DiagnosticsConfig::default(),
+        ));
+        let config = CrashConfig {
+            crash_dir: dir.path().to_path_buf(),
+            ..Default::default()
+        };
+        install_crash_handler_with_collector(config, collector.clone());
+
+        let result = panic::catch_unwind(|| panic!("hook test panic"));
+        assert!(result.is_err());
+
+        let crash = collector.last_crash().unwrap();
+        assert_eq!(crash.reason, "hook test panic");
+        assert!(crash.stack_trace.is_some_and(|frames| !frames.is_empty()));
+        assert!(collector.get_events().iter().any(|e| e.severity == Severity::Fatal));
+
+        let reports = list_crash_reports(dir.path()).unwrap();
+        assert_eq!(reports.len(), 1);
+        let report = load_crash_report(&reports[0]).unwrap();
+        assert_eq!(report.message, "hook test panic");
+        assert!(report.location.is_some_and(|location| location.contains("crash.rs")));
+    }
 }
@@ -4,6 +4,7 @@
 
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::sync::{OnceLock, RwLock};
 
 /// Error domain categories
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -281,7 +282,7 @@ pub struct ErrorRegistry {
 }
 
 /// Description of an error code
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ErrorDescription {
     pub code: ErrorCode,
     pub title: String,
@@ -290,53 +291,425 @@ pub struct ErrorDescription {
     pub fixes: Vec<String>,
 }
 
+impl ErrorDescription {
+    /// Create a new error description with the standard documentation URL
+    pub fn new(code: ErrorCode, title: impl Into<String>, description: impl Into<String>) -> Self {
+        Self {
+            code,
+            title: title.into(),
+            description: description.into(),
+            documentation_url: Some(format!(
+                "https://oxidekit.com/errors/{}-{:04}",
+                code.domain.prefix().to_lowercase(),
+                code.code()
+            )),
+            fixes: Vec::new(),
+        }
+    }
+
+    /// Fallback description for codes that are not in the catalog
+    pub fn unknown(code: ErrorCode) -> Self {
+        Self {
+            code,
+            title: "Unknown Error".into(),
+            description: format!("No description is available for {}", code),
+            documentation_url: None,
+            fixes: vec!["Export a diagnostics bundle and include it in your report".into()],
+        }
+    }
+
+    pub fn with_fix(mut self, fix: impl Into<String>) -> Self {
+        self.fixes.push(fix.into());
+        self
+    }
+
+    pub fn with_documentation_url(mut self, url: impl Into<String>) -> Self {
+        self.documentation_url = Some(url.into());
+        self
+    }
+
+    /// The primary suggested action, if any
+    pub fn suggested_action(&self) -> Option<&str> {
+        self.fixes.first().map(|s| s.as_str())
+    }
+}
+
+/// Process-wide catalog used by `ErrorCode::describe()`
+static GLOBAL_REGISTRY: OnceLock<RwLock<ErrorRegistry>> = OnceLock::new();
+
+fn global_registry() -> &'static RwLock<ErrorRegistry> {
+    GLOBAL_REGISTRY.get_or_init(|| RwLock::new(ErrorRegistry::new()))
+}
+
 impl ErrorRegistry {
     /// Create a new error registry with built-in errors
     pub fn new() -> Self {
-        let mut entries = std::collections::HashMap::new();
-
-        // Register UI errors
-        entries.insert(ErrorCode::UI_UNKNOWN_COMPONENT, ErrorDescription {
-            code: ErrorCode::UI_UNKNOWN_COMPONENT,
-            title: "Unknown Component".into(),
-            description: "The specified component was not found in the registry".into(),
-            documentation_url: Some("https://oxidekit.com/errors/ui-0101".into()),
-            fixes: vec![
-                "Check for typos in the component name".into(),
-                "Ensure the component pack is installed".into(),
-            ],
-        });
-
-        entries.insert(ErrorCode::UI_INVALID_PROP, ErrorDescription {
-            code: ErrorCode::UI_INVALID_PROP,
-            title: "Invalid Property".into(),
-            description: "The property is not valid for this component".into(),
-            documentation_url: Some("https://oxidekit.com/errors/ui-0102".into()),
-            fixes: vec![
-                "Check the component documentation for valid properties".into(),
-                "Use 'oxide export ai-schema' to see all component specs".into(),
-            ],
-        });
-
-        entries.insert(ErrorCode::UI_MISSING_REQUIRED_PROP, ErrorDescription {
-            code: ErrorCode::UI_MISSING_REQUIRED_PROP,
-            title: "Missing Required Property".into(),
-            description: "A required property was not provided".into(),
-            documentation_url: Some("https://oxidekit.com/errors/ui-0103".into()),
-            fixes: vec![
-                "Add the missing required property".into(),
-            ],
-        });
+        let mut registry = Self::empty();
+
+        // UI errors
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::UI_UNKNOWN_COMPONENT,
+                "Unknown Component",
+                "The specified component was not found in the registry",
+            )
+            .with_fix("Check for typos in the component name")
+            .with_fix("Ensure the component pack is installed"),
+        );
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::UI_INVALID_PROP,
+                "Invalid Property",
+                "The property is not valid for this component",
+            )
+            .with_fix("Check the component documentation for valid properties")
+            .with_fix("Use 'oxide export ai-schema' to see all component specs"),
+        );
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::UI_MISSING_REQUIRED_PROP,
+                "Missing Required Property",
+                "A required property was not provided",
+            )
+            .with_fix("Add the missing required property"),
+        );
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::UI_INVALID_CHILD,
+                "Invalid Child",
+                "The component does not accept this kind of child",
+            )
+            .with_fix("Move the child into a container that accepts it"),
+        );
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::UI_DEPRECATED_COMPONENT,
+                "Deprecated Component",
+                "The component is deprecated and will be removed in a future release",
+            )
+            .with_fix("Migrate to the replacement component listed in the changelog"),
+        );
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::UI_ACCESSIBILITY_VIOLATION,
+                "Accessibility Violation",
+                "The component does not meet accessibility requirements",
+            )
+            .with_fix("Add a label or accessible name to the component")
+            .with_fix("Run 'oxide a11y check' for a full report"),
+        );
+
+        // Layout errors
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::LAYOUT_OVERFLOW,
+                "Layout Overflow",
+                "Content exceeds the bounds of its container",
+            )
+            .with_fix("Enable scrolling on the container or constrain the content size"),
+        );
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::LAYOUT_INVALID_SIZE,
+                "Invalid Size",
+                "A size value is negative, NaN, or otherwise unusable",
+            )
+            .with_fix("Check width, height, and min/max constraints"),
+        );
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::LAYOUT_CYCLE_DETECTED,
+                "Layout Cycle",
+                "Layout did not converge because sizes depend on each other",
+            )
+            .with_fix("Remove circular size dependencies between parent and children"),
+        );
+
+        // Render errors
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::RENDER_GPU_ERROR,
+                "GPU Error",
+                "The GPU device reported an error",
+            )
+            .with_fix("Update your graphics drivers"),
+        );
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::RENDER_SHADER_COMPILE,
+                "Shader Compilation Failed",
+                "A shader could not be compiled for the current GPU backend",
+            )
+            .with_fix("Update your graphics drivers")
+            .with_fix("Report the issue with a diagnostics bundle"),
+        );
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::RENDER_OUT_OF_MEMORY,
+                "GPU Out of Memory",
+                "The GPU ran out of memory while rendering",
+            )
+            .with_fix("Reduce image sizes or the number of offscreen surfaces"),
+        );
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::RENDER_TEXTURE_ERROR,
+                "Texture Error",
+                "A texture could not be created or uploaded",
+            )
+            .with_fix("Check that image assets are valid and within size limits"),
+        );
+
+        // Extension errors
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::EXT_NOT_FOUND,
+                "Extension Not Found",
+                "The requested extension is not installed",
+            )
+            .with_fix("Install the extension with 'oxide add'"),
+        );
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::EXT_VERSION_MISMATCH,
+                "Extension Version Mismatch",
+                "The installed extension is not compatible with this OxideKit version",
+            )
+            .with_fix("Update the extension to a compatible version"),
+        );
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::EXT_PERMISSION_DENIED,
+                "Extension Permission Denied",
+                "The extension attempted an operation it has no permission for",
+            )
+            .with_fix("Grant the permission in oxide.toml if the access is expected"),
+        );
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::EXT_LOAD_FAILED,
+                "Extension Load Failed",
+                "The extension could not be loaded",
+            )
+            .with_fix("Reinstall the extension"),
+        );
+
+        // Network errors
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::NET_CONNECTION_FAILED,
+                "Connection Failed",
+                "A network connection could not be established",
+            )
+            .with_fix("Check your internet connection")
+            .with_fix("Verify the host is in the network allowlist"),
+        );
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::NET_TIMEOUT,
+                "Network Timeout",
+                "The request did not complete in time",
+            )
+            .with_fix("Retry the request or increase the timeout"),
+        );
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::NET_TLS_ERROR,
+                "TLS Error",
+                "The secure connection could not be verified",
+            )
+            .with_fix("Check the server certificate and system clock"),
+        );
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::NET_INVALID_RESPONSE,
+                "Invalid Response",
+                "The server returned a response that could not be understood",
+            )
+            .with_fix("Check that the API endpoint and version are correct"),
+        );
+
+        // File system errors
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::FS_NOT_FOUND,
+                "File Not Found",
+                "The file or directory does not exist",
+            )
+            .with_fix("Check the path for typos"),
+        );
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::FS_PERMISSION_DENIED,
+                "File Permission Denied",
+                "The application is not allowed to access this path",
+            )
+            .with_fix("Grant filesystem access in oxide.toml or choose another location"),
+        );
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::FS_READ_ERROR,
+                "File Read Error",
+                "The file could not be read",
+            )
+            .with_fix("Check that the file is not locked or corrupted"),
+        );
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::FS_WRITE_ERROR,
+                "File Write Error",
+                "The file could not be written",
+            )
+            .with_fix("Check available disk space and write permissions"),
+        );
+
+        // Config errors
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::CONFIG_INVALID_FORMAT,
+                "Invalid Configuration Format",
+                "The configuration file could not be parsed",
+            )
+            .with_fix("Validate the file syntax (TOML/JSON)"),
+        );
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::CONFIG_MISSING_FIELD,
+                "Missing Configuration Field",
+                "A required configuration field is missing",
+            )
+            .with_fix("Add the missing field to oxide.toml"),
+        );
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::CONFIG_INVALID_VALUE,
+                "Invalid Configuration Value",
+                "A configuration field has an invalid value",
+            )
+            .with_fix("Check the documentation for the allowed values"),
+        );
+
+        // Compiler errors
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::COMPILER_SYNTAX_ERROR,
+                "Syntax Error",
+                "The source file contains invalid syntax",
+            )
+            .with_fix("Fix the syntax at the reported location"),
+        );
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::COMPILER_PARSE_ERROR,
+                "Parse Error",
+                "The source file could not be parsed",
+            )
+            .with_fix("Check for unbalanced braces or quotes"),
+        );
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::COMPILER_VALIDATION_ERROR,
+                "Validation Error",
+                "The source file parsed but failed validation",
+            )
+            .with_fix("Run 'oxide check' for detailed validation output"),
+        );
+
+        // Runtime errors
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::RUNTIME_PANIC,
+                "Runtime Panic",
+                "The application encountered an unrecoverable error",
+            )
+            .with_fix("Report the issue with the crash report attached"),
+        );
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::RUNTIME_INITIALIZATION,
+                "Initialization Failed",
+                "The runtime could not be initialized",
+            )
+            .with_fix("Check the application manifest and required resources"),
+        );
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::RUNTIME_STATE_ERROR,
+                "State Error",
+                "The application state is inconsistent",
+            )
+            .with_fix("Restart the application"),
+        );
+
+        // System errors
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::SYSTEM_OUT_OF_MEMORY,
+                "Out of Memory",
+                "The system ran out of memory",
+            )
+            .with_fix("Close other applications and try again"),
+        );
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::SYSTEM_THREAD_PANIC,
+                "Thread Panic",
+                "A background thread terminated unexpectedly",
+            )
+            .with_fix("Report the issue with a diagnostics bundle"),
+        );
+        registry.register(
+            ErrorDescription::new(
+                ErrorCode::SYSTEM_RESOURCE_EXHAUSTED,
+                "Resource Exhausted",
+                "A system resource limit was reached",
+            )
+            .with_fix("Close other applications or raise the resource limit"),
+        );
+
+        registry
+    }
 
-        // Add more error descriptions...
+    /// Create a registry without any built-in errors
+    pub fn empty() -> Self {
+        Self {
+            entries: std::collections::HashMap::new(),
+        }
+    }
 
-        Self { entries }
+    /// Register (or replace) an error description
+    pub fn register(&mut self, description: ErrorDescription) {
+        self.entries.insert(description.code, description);
     }
 
     /// Look up error description
     pub fn get(&self, code: ErrorCode) -> Option<&ErrorDescription> {
         self.entries.get(&code)
     }
+
+    /// Look up error description, falling back to a generic entry
+    pub fn describe(&self, code: ErrorCode) -> ErrorDescription {
+        self.get(code)
+            .cloned()
+            .unwrap_or_else(|| ErrorDescription::unknown(code))
+    }
+
+    /// Number of registered error codes
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if the registry is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Register a description in the global catalog used by `ErrorCode::describe()`
+    pub fn register_global(description: ErrorDescription) {
+        if let Ok(mut registry) = global_registry().write() {
+            registry.register(description);
+        }
+    }
 }
 
 impl Default for ErrorRegistry {
@@ -345,6 +718,16 @@ impl Default for ErrorRegistry {
     }
 }
 
+impl ErrorCode {
+    /// Describe this error code using the global catalog
+    pub fn describe(&self) -> ErrorDescription {
+        global_registry()
+            .read()
+            .map(|registry| registry.describe(*self))
+            .unwrap_or_else(|_| ErrorDescription::unknown(*self))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -391,4 +774,55 @@ mod tests {
         assert!(desc.is_some());
         assert_eq!(desc.unwrap().title, "Unknown Component");
     }
+
+    #[test]
+    fn test_builtin_codes_have_descriptions() {
+        let registry = ErrorRegistry::new();
+        let desc = registry.describe(ErrorCode::NET_TIMEOUT);
+
+        assert_eq!(desc.title, "Network Timeout");
+        assert!(desc.suggested_action().is_some());
+        assert_eq!(
+            desc.documentation_url.as_deref(),
+            Some("https://oxidekit.com/errors/net-0502")
+        );
+    }
+
+    #[test]
+    fn test_registry_register_custom() {
+        let mut registry = ErrorRegistry::empty();
+        let code = ErrorCode::new(ErrorDomain::Ext, 42);
+        registry.register(
+            ErrorDescription::new(code, "Quota Exceeded", "The extension exceeded its quota")
+                .with_fix("Reduce extension workload"),
+        );
+
+        let desc = registry.describe(code);
+        assert_eq!(desc.title, "Quota Exceeded");
+        assert_eq!(desc.suggested_action(), Some("Reduce extension workload"));
+        assert_eq!(registry.len(), 1);
+    }
+
+    #[test]
+    fn test_describe_unknown_fallback() {
+        let code = ErrorCode::new(ErrorDomain::System, 99);
+        let desc = code.describe();
+
+        assert_eq!(desc.title, "Unknown Error");
+        assert!(desc.description.contains("OXD-SYSTEM-1099"));
+        assert!(desc.documentation_url.is_none());
+    }
+
+    #[test]
+    fn test_describe_global_registration() {
+        let code = ErrorCode::new(ErrorDomain::Ui, 77);
+        ErrorRegistry::register_global(ErrorDescription::new(
+            code,
+            "Custom Widget Error",
+            "A custom widget failed",
+        ));
+
+        assert_eq!(code.describe().title, "Custom Widget Error");
+        assert_eq!(ErrorCode::UI_INVALID_PROP.describe().title, "Invalid Property");
+    }
 }
@@ -22,6 +22,8 @@ mod error;
 mod event;
 mod bundle;
 mod redact;
+mod stream;
+mod trace;
 
 #[cfg(feature = "crash-handler")]
 mod crash;
@@ -33,6 +35,8 @@ pub use error::*;
 pub use event::*;
 pub use bundle::*;
 pub use redact::*;
+pub use stream::*;
+pub use trace::*;
 
 #[cfg(feature = "crash-handler")]
 pub use crash::*;
@@ -41,6 +45,7 @@ pub use crash::*;
 pub use report::*;
 
 use std::sync::RwLock;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::collections::VecDeque;
 
 /// Maximum number of events to retain in memory
@@ -62,6 +67,18 @@ pub struct DiagnosticsCollector {
 
     /// Configuration
     config: DiagnosticsConfig,
+
+    /// Live event subscribers
+    subscribers: RwLock<Vec<SubscriberSlot>>,
+
+    /// Most recent crash, attached to exported bundles
+    last_crash: RwLock<Option<BundleCrashReport>>,
+
+    /// Per-severity counters driving the sampler (info, warning, error, fatal)
+    sample_counters: [AtomicU64; 4],
+
+    /// Events discarded by sampling
+    dropped_events: AtomicU64,
 }
 
 impl DiagnosticsCollector {
@@ -72,11 +89,23 @@ impl DiagnosticsCollector {
             events: RwLock::new(VecDeque::with_capacity(MAX_EVENTS)),
             logs: RwLock::new(VecDeque::with_capacity(MAX_LOGS)),
             config,
+            subscribers: RwLock::new(Vec::new()),
+            last_crash: RwLock::new(None),
+            sample_counters: Default::default(),
+            dropped_events: AtomicU64::new(0),
         }
     }
 
     /// Record a diagnostic event
+    ///
+    /// Events are subject to the per-severity sampling rates in the config;
+    /// sampled-out events only increment the dropped counter.
     pub fn record_event(&self, event: DiagnosticEvent) {
+        if !self.sample(event.severity) {
+            self.dropped_events.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+
         if let Ok(mut events) = self.events.write() {
             if events.len() >= MAX_EVENTS {
                 events.pop_front();
@@ -88,10 +117,66 @@ impl DiagnosticsCollector {
                 "Diagnostic event recorded"
             );
 
+            self.publish(&event);
             events.push_back(event);
         }
     }
 
+    /// Number of events discarded by sampling
+    pub fn dropped_count(&self) -> u64 {
+        self.dropped_events.load(Ordering::Relaxed)
+    }
+
+    /// Decide whether to keep an event of the given severity.
+    ///
+    /// Uses a per-severity counter so a rate of 0.1 keeps exactly every tenth
+    /// event, which keeps sampling reproducible across runs.
+    fn sample(&self, severity: Severity) -> bool {
+        let rate = self.config.sampling.rate_for(severity);
+        if rate >= 1.0 {
+            return true;
+        }
+        if rate <= 0.0 {
+            return false;
+        }
+
+        let counter = match severity {
+            Severity::Info => &self.sample_counters[0],
+            Severity::Warning => &self.sample_counters[1],
+            Severity::Error => &self.sample_counters[2],
+            Severity::Fatal => &self.sample_counters[3],
+        };
+        let n = counter.fetch_add(1, Ordering::Relaxed) as f64;
+        ((n + 1.0) * rate).floor() > (n * rate).floor()
+    }
+
+    /// Subscribe to events as they are recorded
+    pub fn subscribe(&self) -> EventSubscriber {
+        self.subscribe_with_capacity(DEFAULT_SUBSCRIBER_CAPACITY)
+    }
+
+    /// Subscribe with a custom buffer size. Events beyond the buffer are
+    /// dropped and counted in `EventSubscriber::lagged()`.
+    pub fn subscribe_with_capacity(&self, capacity: usize) -> EventSubscriber {
+        let (slot, subscriber) = SubscriberSlot::channel(capacity);
+        if let Ok(mut subscribers) = self.subscribers.write() {
+            subscribers.push(slot);
+        }
+        subscriber
+    }
+
+    /// Number of active subscribers
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.read().map(|s| s.len()).unwrap_or(0)
+    }
+
+    /// Push an event to all live subscribers, pruning disconnected ones
+    fn publish(&self, event: &DiagnosticEvent) {
+        if let Ok(mut subscribers) = self.subscribers.write() {
+            subscribers.retain(|slot| slot.deliver(event));
+        }
+    }
+
     /// Record a log entry
     pub fn record_log(&self, entry: LogEntry) {
         if let Ok(mut logs) = self.logs.write() {
@@ -132,6 +217,21 @@ impl DiagnosticsCollector {
         }
     }
 
+    /// Record a crash: emits a fatal event with the structured stack trace and
+    /// keeps the report for inclusion in the next exported bundle
+    #[cfg(feature = "crash-handler")]
+    pub fn record_crash(&self, report: &CrashReport) {
+        self.record_event(report.to_event());
+        if let Ok(mut last_crash) = self.last_crash.write() {
+            *last_crash = Some(report.to_bundle_report());
+        }
+    }
+
+    /// Get the most recently recorded crash
+    pub fn last_crash(&self) -> Option<BundleCrashReport> {
+        self.last_crash.read().ok().and_then(|c| c.clone())
+    }
+
     /// Get app info
     pub fn app_info(&self) -> &AppInfo {
         &self.app_info
@@ -154,11 +254,17 @@ impl DiagnosticsCollector {
             .map(|l| redact_log_entry(l, &self.config.redaction_rules))
             .collect();
 
-        DiagnosticsBundle::new(
+        let mut bundle = DiagnosticsBundle::new(
             self.app_info.clone(),
             events,
             redacted_logs,
-        )
+        );
+        bundle.metadata.redaction_applied = true;
+
+        match self.last_crash() {
+            Some(crash) => bundle.with_crash_report(crash),
+            None => bundle,
+        }
     }
 }
 
@@ -240,6 +346,56 @@ impl BuildProfile {
     pub fn diagnostics_available(&self) -> bool {
         !matches!(self, BuildProfile::Release)
     }
+
+    /// Whether the `devtools` feature was compiled into this build
+    pub const fn devtools_compiled() -> bool {
+        cfg!(feature = "devtools")
+    }
+
+    /// Whether devtools were compiled into an optimized (non-debug) build
+    pub const fn devtools_compiled_in_release() -> bool {
+        cfg!(feature = "devtools") && !cfg!(debug_assertions)
+    }
+
+    /// Check that devtools code is absent for this profile
+    pub fn check_no_devtools(&self, devtools_compiled: bool) -> Result<(), String> {
+        if devtools_compiled && !self.devtools_available() {
+            return Err(format!(
+                "devtools code is compiled into a {:?} build; disable the `devtools` feature for release",
+                self
+            ));
+        }
+        Ok(())
+    }
+
+    /// Fail loudly if devtools functionality is reachable in a non-dev build.
+    ///
+    /// Call this at startup of release binaries to catch feature-flag
+    /// misconfigurations. See also `assert_devtools_stripped!` for a
+    /// compile-time variant.
+    pub fn assert_no_devtools(&self) {
+        self.assert_no_devtools_with(Self::devtools_compiled());
+    }
+
+    fn assert_no_devtools_with(&self, devtools_compiled: bool) {
+        if let Err(msg) = self.check_no_devtools(devtools_compiled) {
+            tracing::error!(profile = ?self, "{}", msg);
+            panic!("{}", msg);
+        }
+    }
+}
+
+/// Compile-time check that devtools are not built into an optimized build.
+///
+/// Place at module level in a release binary: `oxide_diagnostics::assert_devtools_stripped!();`
+#[macro_export]
+macro_rules! assert_devtools_stripped {
+    () => {
+        const _: () = assert!(
+            !$crate::BuildProfile::devtools_compiled_in_release(),
+            "oxide-diagnostics: the `devtools` feature must not be enabled in release builds"
+        );
+    };
 }
 
 /// Diagnostics configuration
@@ -264,6 +420,10 @@ pub struct DiagnosticsConfig {
     /// Include full file paths (privacy concern)
     #[serde(default)]
     pub include_full_paths: bool,
+
+    /// Per-severity event sampling rates
+    #[serde(default)]
+    pub sampling: SamplingRates,
 }
 
 impl Default for DiagnosticsConfig {
@@ -274,6 +434,62 @@ impl Default for DiagnosticsConfig {
             redaction_rules: RedactionRules::default(),
             include_stack_traces: false,
             include_full_paths: false,
+            sampling: SamplingRates::default(),
+        }
+    }
+}
+
+/// Fraction of events kept per severity (0.0 - 1.0)
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct SamplingRates {
+    #[serde(default = "default_rate")]
+    pub info: f64,
+
+    #[serde(default = "default_rate")]
+    pub warning: f64,
+
+    #[serde(default = "default_rate")]
+    pub error: f64,
+
+    #[serde(default = "default_rate")]
+    pub fatal: f64,
+}
+
+fn default_rate() -> f64 {
+    1.0
+}
+
+impl Default for SamplingRates {
+    fn default() -> Self {
+        Self {
+            info: 1.0,
+            warning: 1.0,
+            error: 1.0,
+            fatal: 1.0,
+        }
+    }
+}
+
+impl SamplingRates {
+    /// Set the rate for one severity
+    pub fn with_rate(mut self, severity: Severity, rate: f64) -> Self {
+        let rate = rate.clamp(0.0, 1.0);
+        match severity {
+            Severity::Info => self.info = rate,
+            Severity::Warning => self.warning = rate,
+            Severity::Error => self.error = rate,
+            Severity::Fatal => self.fatal = rate,
+        }
+        self
+    }
+
+    /// Get the rate for a severity
+    pub fn rate_for(&self, severity: Severity) -> f64 {
+        match severity {
+            Severity::Info => self.info,
+            Severity::Warning => self.warning,
+            Severity::Error => self.error,
+            Severity::Fatal => self.fatal,
         }
     }
 }
@@ -296,6 +512,14 @@ pub struct LogEntry {
     /// Additional fields
     #[serde(default)]
     pub fields: std::collections::HashMap<String, serde_json::Value>,
+
+    /// Trace ID for cross-boundary correlation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
+
+    /// Span ID for cross-boundary correlation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span_id: Option<String>,
 }
 
 /// Log levels
@@ -317,6 +541,23 @@ impl LogEntry {
             category: category.to_string(),
             message: message.to_string(),
             fields: std::collections::HashMap::new(),
+            trace_id: None,
+            span_id: None,
+        }
+    }
+
+    /// Attach trace/span IDs from a trace context
+    pub fn with_trace(mut self, context: &TraceContext) -> Self {
+        self.trace_id = Some(context.trace_id.clone());
+        self.span_id = Some(context.span_id.clone());
+        self
+    }
+
+    /// Attach the current thread's trace context, if any
+    pub fn with_current_trace(self) -> Self {
+        match TraceContext::current() {
+            Some(context) => self.with_trace(&context),
+            None => self,
         }
     }
 
@@ -333,7 +574,7 @@ pub mod prelude {
     pub use crate::{
         DiagnosticsCollector, DiagnosticsConfig, AppInfo, BuildProfile,
         DiagnosticEvent, ErrorCode, ErrorDomain, Severity,
-        LogEntry, LogLevel,
+        LogEntry, LogLevel, EventSubscriber, TraceContext,
     };
 
     #[cfg(feature = "bundle-export")]
@@ -381,4 +622,125 @@ mod tests {
         collector.record_event(event);
         assert_eq!(collector.get_events().len(), 1);
     }
+
+    #[test]
+    fn test_subscribers_receive_events() {
+        let app_info = AppInfo::from_env("Test", "1.0.0", "test");
+        let collector = DiagnosticsCollector::new(app_info, DiagnosticsConfig::default());
+
+        let first = collector.subscribe();
+        let second = collector.subscribe();
+        assert_eq!(collector.subscriber_count(), 2);
+
+        collector.record_event(DiagnosticEvent::new(
+            ErrorCode::LAYOUT_OVERFLOW,
+            Severity::Warning,
+            "Overflow",
+        ));
+
+        assert_eq!(first.try_recv().unwrap().message, "Overflow");
+        assert_eq!(second.try_recv().unwrap().message, "Overflow");
+    }
+
+    #[test]
+    fn test_subscriber_lag_and_cleanup() {
+        let app_info = AppInfo::from_env("Test", "1.0.0", "test");
+        let collector = DiagnosticsCollector::new(app_info, DiagnosticsConfig::default());
+
+        let slow = collector.subscribe_with_capacity(1);
+        let dropped = collector.subscribe();
+        drop(dropped);
+
+        for _ in 0..3 {
+            collector.record_event(DiagnosticEvent::new(
+                ErrorCode::UI_INVALID_PROP,
+                Severity::Info,
+                "tick",
+            ));
+        }
+
+        assert_eq!(slow.drain().len(), 1);
+        assert_eq!(slow.lagged(), 2);
+        assert_eq!(collector.subscriber_count(), 1);
+    }
+
+    #[test]
+    fn test_sampling_by_severity() {
+        let app_info = AppInfo::from_env("Test", "1.0.0", "test");
+        let config = DiagnosticsConfig {
+            sampling: SamplingRates::default().with_rate(Severity::Info, 0.1),
+            ..Default::default()
+        };
+        let collector = DiagnosticsCollector::new(app_info, config);
+
+        for _ in 0..200 {
+            collector.record_event(DiagnosticEvent::new(
+                ErrorCode::UI_INVALID_PROP,
+                Severity::Info,
+                "noise",
+            ));
+        }
+        for _ in 0..20 {
+            collector.record_event(DiagnosticEvent::new(
+                ErrorCode::RENDER_GPU_ERROR,
+                Severity::Error,
+                "gpu",
+            ));
+        }
+
+        let events = collector.get_events();
+        let info = events.iter().filter(|e| e.severity == Severity::Info).count();
+        let errors = events.iter().filter(|e| e.severity == Severity::Error).count();
+
+        assert!((15..=25).contains(&info), "kept {} info events", info);
+        assert_eq!(errors, 20);
+        assert_eq!(collector.dropped_count(), (200 - info) as u64);
+    }
+
+    #[test]
+    fn test_no_devtools_in_release() {
+        assert!(BuildProfile::Release.check_no_devtools(false).is_ok());
+        assert!(BuildProfile::ReleaseDiagnostics.check_no_devtools(false).is_ok());
+        assert!(BuildProfile::Dev.check_no_devtools(true).is_ok());
+
+        assert!(BuildProfile::Release.check_no_devtools(true).is_err());
+        BuildProfile::Release.assert_no_devtools_with(false);
+    }
+
+    #[test]
+    #[should_panic(expected = "devtools code is compiled into a Release build")]
+    fn test_devtools_in_release_trips() {
+        BuildProfile::Release.assert_no_devtools_with(true);
+    }
+
+    // Debug test builds must always pass the compile-time check
+    assert_devtools_stripped!();
+
+    #[test]
+    fn test_log_entry_inherits_trace_context() {
+        let context = TraceContext::from_ids("trace-1", "span-1");
+        let _guard = context.enter();
+
+        let entry = LogEntry::new(LogLevel::Info, "ui", "clicked").with_current_trace();
+        assert_eq!(entry.trace_id.as_deref(), Some("trace-1"));
+        assert_eq!(entry.span_id.as_deref(), Some("span-1"));
+
+        let json = serde_json::to_string(&entry).unwrap();
+        let parsed: LogEntry = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.trace_id.as_deref(), Some("trace-1"));
+    }
+
+    #[cfg(feature = "bundle-export")]
+    #[test]
+    fn test_bundle_preserves_trace_ids() {
+        let app_info = AppInfo::from_env("Test", "1.0.0", "test");
+        let collector = DiagnosticsCollector::new(app_info, DiagnosticsConfig::default());
+        let context = TraceContext::new();
+
+        collector.record_log(LogEntry::new(LogLevel::Warn, "network", "slow").with_trace(&context));
+
+        let bundle = collector.export_bundle();
+        assert_eq!(bundle.logs[0].trace_id, Some(context.trace_id));
+        assert_eq!(bundle.logs[0].span_id, Some(context.span_id));
+    }
 }
@@ -5,6 +5,9 @@
 
 use crate::{DiagnosticsBundle, DiagnosticsConfig};
 use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicI64, Ordering};
 
 /// Report status
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -56,6 +59,7 @@ impl Default for ReportConfig {
 pub struct AutoReporter {
     config: ReportConfig,
     client: reqwest::Client,
+    spool: Option<ReportSpool>,
 }
 
 impl AutoReporter {
@@ -66,7 +70,22 @@ impl AutoReporter {
             .build()
             .unwrap_or_else(|_| reqwest::Client::new());
 
-        Self { config, client }
+        Self {
+            config,
+            client,
+            spool: None,
+        }
+    }
+
+    /// Persist queued reports to an on-disk spool so they survive being offline
+    pub fn with_spool(mut self, spool: ReportSpool) -> Self {
+        self.spool = Some(spool);
+        self
+    }
+
+    /// Get the report spool (if configured)
+    pub fn spool(&self) -> Option<&ReportSpool> {
+        self.spool.as_ref()
     }
 
     /// Create from diagnostics config
@@ -158,6 +177,9 @@ impl AutoReporter {
     }
 
     /// Queue a bundle for later sending (non-blocking)
+    ///
+    /// With a spool configured the bundle is written to disk and uploaded by
+    /// the next `flush_spool`; without one it is only logged.
     pub fn queue_bundle(&self, bundle: DiagnosticsBundle) -> ReportStatus {
         if !self.is_enabled() {
             return ReportStatus::Disabled;
@@ -167,8 +189,12 @@ impl AutoReporter {
             return ReportStatus::NoConsent;
         }
 
-        // In a real implementation, this would queue to a background task
-        // For now, we just mark as pending
+        if let Some(ref spool) = self.spool {
+            if let Err(e) = spool.enqueue(&bundle) {
+                return ReportStatus::Failed { error: e.to_string() };
+            }
+        }
+
         tracing::info!(
             bundle_id = %bundle.id,
             "Queued diagnostics bundle for sending"
@@ -176,6 +202,260 @@ impl AutoReporter {
 
         ReportStatus::Pending
     }
+
+    /// Upload spooled reports in batches, oldest first
+    ///
+    /// This crate has no view of network state, so nothing flushes the spool
+    /// automatically; call this when the app regains connectivity.
+    pub async fn flush_spool(&self) -> std::io::Result<SpoolFlush> {
+        match self.spool {
+            Some(ref spool) if self.is_enabled() => spool.flush(self).await,
+            Some(ref spool) => Ok(SpoolFlush {
+                uploaded: 0,
+                remaining: spool.len(),
+                error: None,
+            }),
+            None => Ok(SpoolFlush::default()),
+        }
+    }
+
+    /// Send several bundles in a single request
+    async fn try_send_batch(&self, bundles: &[DiagnosticsBundle]) -> Result<(), reqwest::Error> {
+        let mut request = self.client.post(&self.config.endpoint).json(bundles);
+
+        if let Some(ref api_key) = self.config.api_key {
+            request = request.header("X-API-Key", api_key);
+        }
+
+        request.send().await?.error_for_status()?;
+        Ok(())
+    }
+}
+
+/// Destination for batched report uploads
+pub trait ReportTransport {
+    /// Upload a batch of bundles. The batch either succeeds or fails as a whole.
+    fn send_batch(&self, bundles: &[DiagnosticsBundle]) -> impl Future<Output = Result<(), String>> + Send;
+}
+
+impl ReportTransport for AutoReporter {
+    async fn send_batch(&self, bundles: &[DiagnosticsBundle]) -> Result<(), String> {
+        self.try_send_batch(bundles).await.map_err(|e| e.to_string())
+    }
+}
+
+/// Result of flushing the spool
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SpoolFlush {
+    /// Number of reports uploaded
+    pub uploaded: usize,
+
+    /// Number of reports still waiting in the spool
+    pub remaining: usize,
+
+    /// Last upload error (if the flush stopped early)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// On-disk queue of pending reports
+///
+/// Files are named `report_<stamp>_<id>.json` with a monotonic stamp, so
+/// lexical order is enqueue order.
+pub struct ReportSpool {
+    dir: PathBuf,
+    max_entries: usize,
+    batch_size: usize,
+    retry_count: u32,
+    backoff_ms: u64,
+    last_stamp: AtomicI64,
+}
+
+impl ReportSpool {
+    /// Create a spool in the given directory
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            max_entries: 100,
+            batch_size: 10,
+            retry_count: 3,
+            backoff_ms: 100,
+            last_stamp: AtomicI64::new(0),
+        }
+    }
+
+    /// Maximum number of spooled reports; the oldest are dropped beyond this
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries.max(1);
+        self
+    }
+
+    /// Number of reports uploaded per request
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Retry count and base backoff delay for failed batches
+    pub fn with_retry(mut self, retry_count: u32, backoff_ms: u64) -> Self {
+        self.retry_count = retry_count;
+        self.backoff_ms = backoff_ms;
+        self
+    }
+
+    /// Spool directory
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Persist a bundle, evicting the oldest reports if the spool is full
+    pub fn enqueue(&self, bundle: &DiagnosticsBundle) -> std::io::Result<PathBuf> {
+        std::fs::create_dir_all(&self.dir)?;
+
+        let now = chrono::Utc::now().timestamp_micros();
+        let stamp = match self.last_stamp.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |last| {
+            Some(now.max(last + 1))
+        }) {
+            Ok(last) | Err(last) => now.max(last + 1),
+        };
+
+        let path = self.dir.join(format!("report_{:020}_{}.json", stamp, bundle.id));
+        let json = bundle
+            .to_json_compact()
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        std::fs::write(&path, json)?;
+
+        let pending = self.pending()?;
+        if pending.len() > self.max_entries {
+            for old in &pending[..pending.len() - self.max_entries] {
+                tracing::warn!(path = %old.display(), "Report spool full, dropping oldest report");
+                let _ = std::fs::remove_file(old);
+            }
+        }
+
+        Ok(path)
+    }
+
+    /// Spooled report files, oldest first
+    pub fn pending(&self) -> std::io::Result<Vec<PathBuf>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut reports: Vec<_> = std::fs::read_dir(&self.dir)?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.file_name()
+                    .map(|n| n.to_string_lossy())
+                    .is_some_and(|n| n.starts_with("report_") && n.ends_with(".json"))
+            })
+            .collect();
+
+        reports.sort();
+        Ok(reports)
+    }
+
+    /// Number of spooled reports
+    pub fn len(&self) -> usize {
+        self.pending().map(|p| p.len()).unwrap_or(0)
+    }
+
+    /// Check if the spool is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Upload spooled reports in batches, oldest first.
+    ///
+    /// Each batch is retried with exponential backoff; if it still fails the
+    /// flush stops and the remaining reports stay on disk for the next attempt.
+    /// A report that cannot be removed from the spool fails the flush, so it
+    /// is never sent twice in one call.
+    pub async fn flush<T: ReportTransport>(&self, transport: &T) -> std::io::Result<SpoolFlush> {
+        let mut result = SpoolFlush::default();
+
+        loop {
+            let pending = self.pending()?;
+            if pending.is_empty() {
+                break;
+            }
+
+            let batch_paths: Vec<PathBuf> = pending.into_iter().take(self.batch_size).collect();
+            let mut batch = Vec::with_capacity(batch_paths.len());
+            let mut sent_paths = Vec::with_capacity(batch_paths.len());
+            for path in batch_paths {
+                let loaded = std::fs::read_to_string(&path).ok().and_then(|content| {
+                    serde_json::from_str::<DiagnosticsBundle>(&content).ok()
+                });
+                match loaded {
+                    Some(bundle) => {
+                        batch.push(bundle);
+                        sent_paths.push(path);
+                    }
+                    None => {
+                        tracing::warn!(path = %path.display(), "Discarding unreadable spooled report");
+                        remove_spooled(&path)?;
+                    }
+                }
+            }
+
+            if batch.is_empty() {
+                continue;
+            }
+
+            if let Err(error) = self.send_with_retry(transport, &batch).await {
+                result.error = Some(error);
+                break;
+            }
+
+            for path in &sent_paths {
+                remove_spooled(path)?;
+                result.uploaded += 1;
+            }
+        }
+
+        result.remaining = self.len();
+        Ok(result)
+    }
+
+    async fn send_with_retry<T: ReportTransport>(
+        &self,
+        transport: &T,
+        batch: &[DiagnosticsBundle],
+    ) -> Result<(), String> {
+        let mut last_error = String::new();
+
+        for attempt in 0..=self.retry_count {
+            match transport.send_batch(batch).await {
+                Ok(()) => return Ok(()),
+                Err(e) => {
+                    tracing::warn!(
+                        attempt = attempt + 1,
+                        batch_size = batch.len(),
+                        error = %e,
+                        "Failed to upload spooled reports"
+                    );
+                    last_error = e;
+
+                    if attempt < self.retry_count {
+                        let delay = std::time::Duration::from_millis(self.backoff_ms * 2u64.pow(attempt));
+                        tokio::time::sleep(delay).await;
+                    }
+                }
+            }
+        }
+
+        Err(last_error)
+    }
+}
+
+/// Remove a spooled report; one already gone counts as removed
+fn remove_spooled(path: &Path) -> std::io::Result<()> {
+    match std::fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
 }
 
 #[cfg(test)]
@@ -208,4 +488,120 @@ mod tests {
         let reporter = AutoReporter::new(config);
         assert!(reporter.is_enabled());
     }
+
+    struct FakeTransport {
+        online: std::sync::atomic::AtomicBool,
+        received: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl FakeTransport {
+        fn new(online: bool) -> Self {
+            Self {
+                online: std::sync::atomic::AtomicBool::new(online),
+                received: std::sync::Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    impl ReportTransport for FakeTransport {
+        fn send_batch(&self, bundles: &[DiagnosticsBundle]) -> impl Future<Output = Result<(), String>> + Send {
+            let result = if self.online.load(Ordering::SeqCst) {
+                let mut received = self.received.lock().unwrap();
+                received.extend(
+                    bundles
+                        .iter()
+                        .map(|b| b.metadata.description.clone().unwrap_or_default()),
+                );
+                Ok(())
+            } else {
+                Err("offline".to_string())
+            };
+            async move { result }
+        }
+    }
+
+    fn bundle(label: &str) -> DiagnosticsBundle {
+        let mut bundle = DiagnosticsBundle::new(
+            crate::AppInfo::from_env("TestApp", "1.0.0", "test"),
+            vec![],
+            vec![],
+        );
+        bundle.metadata.description = Some(label.to_string());
+        bundle.metadata.auto_report_consent = true;
+        bundle
+    }
+
+    #[tokio::test]
+    async fn test_spool_uploads_oldest_first_when_online() {
+        let dir = tempfile::tempdir().unwrap();
+        let spool = ReportSpool::new(dir.path()).with_batch_size(2).with_retry(0, 0);
+        let transport = FakeTransport::new(false);
+
+        for label in ["first", "second", "third"] {
+            spool.enqueue(&bundle(label)).unwrap();
+        }
+
+        let offline = spool.flush(&transport).await.unwrap();
+        assert_eq!(offline.uploaded, 0);
+        assert_eq!(offline.remaining, 3);
+        assert!(offline.error.is_some());
+
+        transport.online.store(true, Ordering::SeqCst);
+        let online = spool.flush(&transport).await.unwrap();
+        assert_eq!(online.uploaded, 3);
+        assert_eq!(online.remaining, 0);
+        assert_eq!(
+            *transport.received.lock().unwrap(),
+            vec!["first", "second", "third"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_spool_flush_fails_on_unremovable_report() {
+        let dir = tempfile::tempdir().unwrap();
+        let spool = ReportSpool::new(dir.path()).with_retry(0, 0);
+        std::fs::create_dir(dir.path().join("report_0.json")).unwrap();
+
+        let transport = FakeTransport::new(true);
+        let flush = spool.flush(&transport);
+        let result = tokio::time::timeout(std::time::Duration::from_secs(5), flush).await.unwrap();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_spool_drops_oldest_when_full() {
+        let dir = tempfile::tempdir().unwrap();
+        let spool = ReportSpool::new(dir.path()).with_max_entries(2);
+
+        for label in ["a", "b", "c"] {
+            spool.enqueue(&bundle(label)).unwrap();
+        }
+
+        let pending = spool.pending().unwrap();
+        assert_eq!(pending.len(), 2);
+        let oldest: DiagnosticsBundle =
+            serde_json::from_str(&std::fs::read_to_string(&pending[0]).unwrap()).unwrap();
+        assert_eq!(oldest.metadata.description.as_deref(), Some("b"));
+    }
+
+    #[test]
+    fn test_queue_bundle_respects_opt_in() {
+        let dir = tempfile::tempdir().unwrap();
+        let disabled = AutoReporter::new(ReportConfig::default())
+            .with_spool(ReportSpool::new(dir.path()));
+        assert!(matches!(disabled.queue_bundle(bundle("x")), ReportStatus::Disabled));
+
+        let enabled = AutoReporter::new(ReportConfig {
+            enabled: true,
+            endpoint: "https://example.com/report".to_string(),
+            ..Default::default()
+        })
+        .with_spool(ReportSpool::new(dir.path()));
+
+        let mut no_consent = bundle("y");
+        no_consent.metadata.auto_report_consent = false;
+        assert!(matches!(enabled.queue_bundle(no_consent), ReportStatus::NoConsent));
+        assert!(matches!(enabled.queue_bundle(bundle("z")), ReportStatus::Pending));
+        assert_eq!(enabled.spool().unwrap().len(), 1);
+    }
 }
@@ -0,0 +1,132 @@
+//! Live Event Stream
+//!
+//! Push-based delivery of diagnostic events to subscribers (e.g. a live dev panel).
+//! Each subscriber has a bounded buffer; slow consumers drop events instead of
+//! blocking the recorder, and can query how many events they missed.
+
+use crate::DiagnosticEvent;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, SyncSender, TryRecvError, TrySendError};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Default number of buffered events per subscriber
+pub const DEFAULT_SUBSCRIBER_CAPACITY: usize = 256;
+
+/// Receiving end of a diagnostic event subscription
+pub struct EventSubscriber {
+    receiver: Receiver<DiagnosticEvent>,
+    lagged: Arc<AtomicUsize>,
+}
+
+impl EventSubscriber {
+    /// Receive the next event without blocking
+    pub fn try_recv(&self) -> Option<DiagnosticEvent> {
+        match self.receiver.try_recv() {
+            Ok(event) => Some(event),
+            Err(TryRecvError::Empty) | Err(TryRecvError::Disconnected) => None,
+        }
+    }
+
+    /// Wait up to `timeout` for the next event
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<DiagnosticEvent> {
+        match self.receiver.recv_timeout(timeout) {
+            Ok(event) => Some(event),
+            Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Disconnected) => None,
+        }
+    }
+
+    /// Drain all currently buffered events
+    pub fn drain(&self) -> Vec<DiagnosticEvent> {
+        self.receiver.try_iter().collect()
+    }
+
+    /// Number of events dropped because this subscriber fell behind
+    pub fn lagged(&self) -> usize {
+        self.lagged.load(Ordering::Relaxed)
+    }
+
+    /// Return the number of dropped events and reset the counter
+    pub fn take_lagged(&self) -> usize {
+        self.lagged.swap(0, Ordering::Relaxed)
+    }
+
+    /// Check if the subscriber has missed any events
+    pub fn is_lagged(&self) -> bool {
+        self.lagged() > 0
+    }
+}
+
+/// Sending half held by the collector for each subscriber
+pub(crate) struct SubscriberSlot {
+    sender: SyncSender<DiagnosticEvent>,
+    lagged: Arc<AtomicUsize>,
+}
+
+impl SubscriberSlot {
+    /// Create a linked slot/subscriber pair
+    pub(crate) fn channel(capacity: usize) -> (Self, EventSubscriber) {
+        let (sender, receiver) = mpsc::sync_channel(capacity.max(1));
+        let lagged = Arc::new(AtomicUsize::new(0));
+
+        (
+            Self {
+                sender,
+                lagged: lagged.clone(),
+            },
+            EventSubscriber { receiver, lagged },
+        )
+    }
+
+    /// Deliver an event. Returns `false` if the subscriber has been dropped.
+    pub(crate) fn deliver(&self, event: &DiagnosticEvent) -> bool {
+        match self.sender.try_send(event.clone()) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_)) => {
+                self.lagged.fetch_add(1, Ordering::Relaxed);
+                true
+            }
+            Err(TrySendError::Disconnected(_)) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ErrorCode, Severity};
+
+    fn event(message: &str) -> DiagnosticEvent {
+        DiagnosticEvent::new(ErrorCode::UI_INVALID_PROP, Severity::Warning, message)
+    }
+
+    #[test]
+    fn test_slot_delivers_to_subscriber() {
+        let (slot, subscriber) = SubscriberSlot::channel(4);
+
+        assert!(slot.deliver(&event("a")));
+        assert_eq!(subscriber.try_recv().unwrap().message, "a");
+        assert!(subscriber.try_recv().is_none());
+    }
+
+    #[test]
+    fn test_slow_subscriber_lags() {
+        let (slot, subscriber) = SubscriberSlot::channel(2);
+
+        for i in 0..5 {
+            slot.deliver(&event(&i.to_string()));
+        }
+
+        assert_eq!(subscriber.drain().len(), 2);
+        assert_eq!(subscriber.take_lagged(), 3);
+        assert!(!subscriber.is_lagged());
+    }
+
+    #[test]
+    fn test_dropped_subscriber_detected() {
+        let (slot, subscriber) = SubscriberSlot::channel(2);
+        drop(subscriber);
+
+        assert!(!slot.deliver(&event("a")));
+    }
+}
@@ -0,0 +1,108 @@
+//! Trace Context
+//!
+//! Trace/span correlation IDs for linking log entries across the UI/backend
+//! boundary. The current context is tracked per thread.
+
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+
+thread_local! {
+    static CURRENT_TRACE: RefCell<Option<TraceContext>> = const { RefCell::new(None) };
+}
+
+/// Trace and span identifiers
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceContext {
+    /// Trace ID shared by every span of one logical operation
+    pub trace_id: String,
+
+    /// Span ID for the current unit of work
+    pub span_id: String,
+}
+
+impl TraceContext {
+    /// Start a new trace with a root span
+    pub fn new() -> Self {
+        Self {
+            trace_id: uuid::Uuid::new_v4().simple().to_string(),
+            span_id: new_span_id(),
+        }
+    }
+
+    /// Create a context from existing IDs (e.g. received from a backend)
+    pub fn from_ids(trace_id: impl Into<String>, span_id: impl Into<String>) -> Self {
+        Self {
+            trace_id: trace_id.into(),
+            span_id: span_id.into(),
+        }
+    }
+
+    /// Create a child span within the same trace
+    pub fn child(&self) -> Self {
+        Self {
+            trace_id: self.trace_id.clone(),
+            span_id: new_span_id(),
+        }
+    }
+
+    /// Get the current thread's trace context
+    pub fn current() -> Option<Self> {
+        CURRENT_TRACE.with(|current| current.borrow().clone())
+    }
+
+    /// Make this the current thread's context until the guard is dropped
+    pub fn enter(self) -> TraceGuard {
+        let previous = CURRENT_TRACE.with(|current| current.borrow_mut().replace(self));
+        TraceGuard { previous }
+    }
+}
+
+impl Default for TraceContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Restores the previous trace context when dropped
+pub struct TraceGuard {
+    previous: Option<TraceContext>,
+}
+
+impl Drop for TraceGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        CURRENT_TRACE.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+fn new_span_id() -> String {
+    uuid::Uuid::new_v4().simple().to_string()[..16].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_enter_and_restore() {
+        assert!(TraceContext::current().is_none());
+
+        let root = TraceContext::new();
+        {
+            let _outer = root.clone().enter();
+            assert_eq!(TraceContext::current(), Some(root.clone()));
+
+            let child = root.child();
+            {
+                let _inner = child.clone().enter();
+                let current = TraceContext::current().unwrap();
+                assert_eq!(current.trace_id, root.trace_id);
+                assert_ne!(current.span_id, root.span_id);
+            }
+
+            assert_eq!(TraceContext::current(), Some(root));
+        }
+
+        assert!(TraceContext::current().is_none());
+    }
+}
@@ -8,6 +8,7 @@
 //! - [`responsive`]: Breakpoint-based responsive layouts
 //! - [`safe_area`]: Safe area handling for notches and home indicators
 //! - [`adaptive`]: Adaptive navigation patterns
+//! - [`snapshot`]: Serializable layout snapshots for golden-file testing
 //!
 //! # Example
 //!
@@ -27,13 +28,17 @@
 pub mod adaptive;
 pub mod responsive;
 pub mod safe_area;
+pub mod snapshot;
 
 pub use taffy;
 pub use taffy::prelude::*;
 pub use taffy::{Overflow, Point};
 
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::snapshot::{LayoutSnapshot, SnapshotStyle};
+
 /// Prelude module for convenient imports
 ///
 /// Import everything commonly needed with:
@@ -60,10 +65,13 @@ pub mod prelude {
         AdaptiveLayout, AdaptiveNavigation, BottomNavConfig, DynamicAdaptiveLayout, Layout,
         SidebarConfig, SimpleLayout, SplitViewConfig, SplitViewWidth,
     };
+
+    // Snapshot types
+    pub use crate::snapshot::{DeltaKind, LayoutSnapshot, NodeDelta, SnapshotStyle};
 }
 
 /// Computed rectangle from layout
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
 pub struct ComputedRect {
     pub x: f32,
     pub y: f32,
@@ -133,6 +141,8 @@ pub struct NodeVisual {
     pub clips_children: bool,
     /// Scroll offset (x, y) for scrollable containers
     pub scroll_offset: (f32, f32),
+    /// Paint order among siblings; higher values paint on top
+    pub z_index: i32,
 }
 
 impl Default for NodeVisual {
@@ -144,6 +154,7 @@ impl Default for NodeVisual {
             corner_radius: 0.0,
             clips_children: false,
             scroll_offset: (0.0, 0.0),
+            z_index: 0,
         }
     }
 }
@@ -174,6 +185,11 @@ impl NodeVisual {
         self.scroll_offset = (x, y);
         self
     }
+
+    pub fn with_z_index(mut self, z_index: i32) -> Self {
+        self.z_index = z_index;
+        self
+    }
 }
 
 /// A UI node with layout and visual properties
@@ -184,10 +200,18 @@ pub struct UINode {
     pub children: Vec<UINode>,
 }
 
+/// Intrinsic size callback for a leaf node
+///
+/// Receives the known or definite available size on each axis and returns
+/// the content size, e.g. the height of text wrapped to the given width.
+type MeasureFn = Box<dyn Fn(Size<Option<f32>>) -> Size<f32> + Send + Sync>;
+
 /// A layout tree that can compute positions for UI elements
 pub struct LayoutTree {
-    taffy: TaffyTree,
+    taffy: TaffyTree<MeasureFn>,
     visuals: HashMap<NodeId, NodeVisual>,
+    /// Root and available space of the last layout pass
+    last_layout: Option<(NodeId, Size<AvailableSpace>)>,
 }
 
 impl LayoutTree {
@@ -196,6 +220,7 @@ impl LayoutTree {
         Self {
             taffy: TaffyTree::new(),
             visuals: HashMap::new(),
+            last_layout: None,
         }
     }
 
@@ -204,6 +229,20 @@ impl LayoutTree {
         self.taffy.new_leaf(style).expect("Failed to create node")
     }
 
+    /// Create a leaf node sized by its content
+    ///
+    /// `measure_fn` is called during layout with the width and height the
+    /// node is constrained to (`None` when unknown) and returns its content
+    /// size, so wrapping text can report its height for the final width.
+    pub fn new_measured_node<F>(&mut self, style: Style, measure_fn: F) -> NodeId
+    where
+        F: Fn(Size<Option<f32>>) -> Size<f32> + Send + Sync + 'static,
+    {
+        self.taffy
+            .new_leaf_with_context(style, Box::new(measure_fn))
+            .expect("Failed to create node")
+    }
+
     /// Create a new node with visual properties
     pub fn new_visual_node(&mut self, style: Style, visual: NodeVisual) -> NodeId {
         let id = self.taffy.new_leaf(style).expect("Failed to create node");
@@ -238,16 +277,104 @@ impl LayoutTree {
         self.visuals.insert(node, visual);
     }
 
+    /// Replace the style of an existing node (marks it dirty for the next layout)
+    pub fn set_style(&mut self, node: NodeId, style: Style) {
+        self.taffy.set_style(node, style).expect("Node not found");
+    }
+
     /// Get visual properties for a node
     pub fn get_visual(&self, node: NodeId) -> Option<&NodeVisual> {
         self.visuals.get(&node)
     }
 
+    /// Mark a node as needing layout (e.g. after its content changed)
+    ///
+    /// Its ancestors are invalidated too; siblings keep their cached layout.
+    pub fn mark_dirty(&mut self, node: NodeId) {
+        self.taffy.mark_dirty(node).expect("Node not found");
+    }
+
     /// Compute layout for the tree starting from root
     pub fn compute_layout(&mut self, root: NodeId, available_space: Size<AvailableSpace>) {
         self.taffy
-            .compute_layout(root, available_space)
+            .compute_layout_with_measure(root, available_space, measure_leaf)
             .expect("Failed to compute layout");
+        self.last_layout = Some((root, available_space));
+    }
+
+    /// Compute layout into a fixed-size viewport
+    pub fn compute_layout_fixed(&mut self, root: NodeId, width: f32, height: f32) {
+        self.compute_layout(
+            root,
+            Size {
+                width: AvailableSpace::Definite(width),
+                height: AvailableSpace::Definite(height),
+            },
+        );
+    }
+
+    /// Compute layout at the narrowest size the content allows
+    pub fn compute_min_content(&mut self, root: NodeId) {
+        self.compute_layout(root, Size::MIN_CONTENT);
+    }
+
+    /// Compute layout at the size the content takes without wrapping
+    pub fn compute_max_content(&mut self, root: NodeId) {
+        self.compute_layout(root, Size::MAX_CONTENT);
+    }
+
+    /// Lay out the tree in `space` on both axes and return the root's size
+    ///
+    /// Handy for auto-sizing a window to its content. The last committed
+    /// layout is restored afterwards, so rects read later are unaffected.
+    pub fn measure_root(&mut self, root: NodeId, space: AvailableSpace) -> Size<f32> {
+        let previous = self.last_layout;
+        self.compute_layout(root, Size { width: space, height: space });
+        let size = self.get_layout(root).size;
+
+        if let Some((previous_root, previous_space)) = previous {
+            self.compute_layout(previous_root, previous_space);
+        }
+        self.last_layout = previous;
+        size
+    }
+
+    /// Compute layout, only redoing the nodes invalidated since the last pass
+    ///
+    /// Returns the number of nodes laid out again: the dirty nodes (changed
+    /// nodes and their ancestors), or the whole subtree when the root or
+    /// available space differs from the last pass. Clean subtrees are served
+    /// from Taffy's cache.
+    pub fn compute_layout_incremental(
+        &mut self,
+        root: NodeId,
+        available_space: Size<AvailableSpace>,
+    ) -> usize {
+        let recomputed = if self.last_layout == Some((root, available_space)) {
+            self.count_dirty(root)
+        } else {
+            self.count_nodes(root)
+        };
+
+        if recomputed > 0 {
+            self.compute_layout(root, available_space);
+        }
+        recomputed
+    }
+
+    /// Count the dirty nodes in a subtree
+    ///
+    /// Invalidation propagates to ancestors, so clean subtrees are skipped.
+    fn count_dirty(&self, node: NodeId) -> usize {
+        if !self.taffy.dirty(node).unwrap_or(false) {
+            return 0;
+        }
+        1 + self.children(node).into_iter().map(|child| self.count_dirty(child)).sum::<usize>()
+    }
+
+    /// Count the nodes in a subtree
+    fn count_nodes(&self, node: NodeId) -> usize {
+        1 + self.children(node).into_iter().map(|child| self.count_nodes(child)).sum::<usize>()
     }
 
     /// Get the computed layout for a node
@@ -266,6 +393,19 @@ impl LayoutTree {
         }
     }
 
+    /// Get the computed margin of a node
+    ///
+    /// Percentages are resolved against the containing block's width and
+    /// auto margins to the space they took up.
+    pub fn resolved_margin(&self, node: NodeId) -> Rect<f32> {
+        self.get_layout(node).margin
+    }
+
+    /// Get the computed padding of a node, with percentages resolved
+    pub fn resolved_padding(&self, node: NodeId) -> Rect<f32> {
+        self.get_layout(node).padding
+    }
+
     /// Get computed rectangle with absolute position by traversing parents
     pub fn get_absolute_rect(&self, node: NodeId, parent_offset: (f32, f32)) -> ComputedRect {
         let layout = self.get_layout(node);
@@ -277,11 +417,31 @@ impl LayoutTree {
         }
     }
 
+    /// Capture the computed layout of a subtree for golden-file testing
+    pub fn snapshot(&self, root: NodeId) -> LayoutSnapshot {
+        let style = self.get_style(root).map(|style| SnapshotStyle {
+            display: format!("{:?}", style.display),
+            position: format!("{:?}", style.position),
+            flex_direction: format!("{:?}", style.flex_direction),
+        });
+        LayoutSnapshot {
+            id: u64::from(root),
+            rect: self.get_rect(root),
+            style: style.unwrap_or_default(),
+            children: self.children(root).into_iter().map(|child| self.snapshot(child)).collect(),
+        }
+    }
+
     /// Get children of a node
     pub fn children(&self, node: NodeId) -> Vec<NodeId> {
         self.taffy.children(node).unwrap_or_default()
     }
 
+    /// Get the parent of a node (`None` for roots)
+    pub fn parent(&self, node: NodeId) -> Option<NodeId> {
+        self.taffy.parent(node)
+    }
+
     /// Iterate over all nodes depth-first, computing absolute positions
     pub fn traverse<F>(&self, root: NodeId, mut callback: F)
     where
@@ -313,7 +473,24 @@ impl LayoutTree {
             clip_rect: None,
             scroll_offset: (0.0, 0.0),
         };
-        self.traverse_with_clip_recursive(root, (0.0, 0.0), initial_clip, &mut callback);
+        self.traverse_with_clip_recursive(root, (0.0, 0.0), initial_clip, false, &mut callback);
+    }
+
+    /// Iterate over all nodes with clipping context in the order they paint
+    ///
+    /// Like [`traverse_with_clip`](Self::traverse_with_clip), but each node's
+    /// children are visited by ascending z-index, with absolutely positioned
+    /// children after in-flow ones at the same z-index. Every node forms its
+    /// own stacking context, so z-indices only order siblings.
+    pub fn traverse_painters_order<F>(&self, root: NodeId, mut callback: F)
+    where
+        F: FnMut(NodeId, ComputedRect, Option<&NodeVisual>, ClipContext),
+    {
+        let initial_clip = ClipContext {
+            clip_rect: None,
+            scroll_offset: (0.0, 0.0),
+        };
+        self.traverse_with_clip_recursive(root, (0.0, 0.0), initial_clip, true, &mut callback);
     }
 
     fn traverse_with_clip_recursive<F>(
@@ -321,6 +498,7 @@ impl LayoutTree {
         node: NodeId,
         parent_offset: (f32, f32),
         clip_ctx: ClipContext,
+        painter_order: bool,
         callback: &mut F,
     ) where
         F: FnMut(NodeId, ComputedRect, Option<&NodeVisual>, ClipContext),
@@ -356,11 +534,102 @@ impl LayoutTree {
         }
 
         let new_offset = (rect.x, rect.y);
-        for child in self.children(node) {
-            self.traverse_with_clip_recursive(child, new_offset, child_clip, callback);
+        let children = if painter_order {
+            self.paint_order(node)
+        } else {
+            self.children(node)
+        };
+        for child in children {
+            self.traverse_with_clip_recursive(
+                child,
+                new_offset,
+                child_clip,
+                painter_order,
+                callback,
+            );
         }
     }
 
+    /// Find the topmost node containing a point
+    ///
+    /// Walks front-to-back in paint order, so the deepest node on top wins.
+    /// Siblings are ordered by z-index, absolutely positioned children paint
+    /// above their in-flow siblings, and a point outside a clipping ancestor
+    /// can't hit its descendants.
+    /// Geometry matches [`traverse_with_clip`](Self::traverse_with_clip).
+    pub fn hit_test(&self, x: f32, y: f32, root: NodeId) -> Option<NodeId> {
+        let initial_clip = ClipContext {
+            clip_rect: None,
+            scroll_offset: (0.0, 0.0),
+        };
+        self.hit_test_recursive(root, (0.0, 0.0), initial_clip, (x, y))
+    }
+
+    fn hit_test_recursive(
+        &self,
+        node: NodeId,
+        parent_offset: (f32, f32),
+        clip_ctx: ClipContext,
+        point: (f32, f32),
+    ) -> Option<NodeId> {
+        let visual = self.visuals.get(&node);
+        let adjusted_offset = (
+            parent_offset.0 - clip_ctx.scroll_offset.0,
+            parent_offset.1 - clip_ctx.scroll_offset.1,
+        );
+        let rect = self.get_absolute_rect(node, adjusted_offset);
+
+        let mut child_clip = clip_ctx;
+        let mut children_reachable = true;
+        if let Some(vis) = visual {
+            if vis.clips_children {
+                child_clip.clip_rect = match clip_ctx.clip_rect {
+                    Some(parent_clip) => rect.intersection(&parent_clip),
+                    None => Some(rect),
+                };
+                children_reachable = child_clip.clip_rect.is_some();
+            }
+            child_clip.scroll_offset = (
+                clip_ctx.scroll_offset.0 + vis.scroll_offset.0,
+                clip_ctx.scroll_offset.1 + vis.scroll_offset.1,
+            );
+        }
+
+        if children_reachable && clip_allows(child_clip.clip_rect, point) {
+            let new_offset = (rect.x, rect.y);
+            for child in self.paint_order(node).into_iter().rev() {
+                let hit = self.hit_test_recursive(child, new_offset, child_clip, point);
+                if hit.is_some() {
+                    return hit;
+                }
+            }
+        }
+
+        (clip_allows(clip_ctx.clip_rect, point) && rect.contains_point(point.0, point.1))
+            .then_some(node)
+    }
+
+    /// Children of a node in the order they paint, back to front
+    ///
+    /// Children are ordered by z-index; at equal z-index in-flow children
+    /// paint in tree order, followed by absolutely positioned ones.
+    fn paint_order(&self, node: NodeId) -> Vec<NodeId> {
+        let mut children = self.children(node);
+        children.sort_by_key(|&child| (self.z_index(child), self.is_absolute(child)));
+        children
+    }
+
+    fn z_index(&self, node: NodeId) -> i32 {
+        self.visuals.get(&node).map(|v| v.z_index).unwrap_or(0)
+    }
+
+    fn is_absolute(&self, node: NodeId) -> bool {
+        self.taffy
+            .style(node)
+            .map(|style| style.position == Position::Absolute)
+            .unwrap_or(false)
+    }
+
     /// Set scroll offset for a scrollable node
     pub fn set_scroll_offset(&mut self, node: NodeId, x: f32, y: f32) {
         if let Some(visual) = self.visuals.get_mut(&node) {
@@ -392,6 +661,36 @@ impl LayoutTree {
     }
 }
 
+/// Taffy measure function dispatching to a node's [`MeasureFn`]
+fn measure_leaf(
+    known: Size<Option<f32>>,
+    available: Size<AvailableSpace>,
+    _node: NodeId,
+    measure_fn: Option<&mut MeasureFn>,
+    _style: &Style,
+) -> Size<f32> {
+    let Some(measure_fn) = measure_fn else {
+        return Size::ZERO;
+    };
+    let constraint = Size {
+        width: known.width.or(available.width.into_option()),
+        height: known.height.or(available.height.into_option()),
+    };
+    let measured = measure_fn(constraint);
+    Size {
+        width: known.width.unwrap_or(measured.width),
+        height: known.height.unwrap_or(measured.height),
+    }
+}
+
+/// Check that a point lies inside an optional clip rect
+fn clip_allows(clip: Option<ComputedRect>, (x, y): (f32, f32)) -> bool {
+    match clip {
+        Some(clip_rect) => clip_rect.contains_point(x, y),
+        None => true,
+    }
+}
+
 impl Default for LayoutTree {
     fn default() -> Self {
         Self::new()
@@ -540,6 +839,24 @@ impl StyleBuilder {
         self
     }
 
+    pub fn gap_xy(mut self, column_gap: f32, row_gap: f32) -> Self {
+        self.style.gap = Size {
+            width: LengthPercentage::Length(column_gap),
+            height: LengthPercentage::Length(row_gap),
+        };
+        self
+    }
+
+    pub fn row_gap(mut self, value: f32) -> Self {
+        self.style.gap.height = LengthPercentage::Length(value);
+        self
+    }
+
+    pub fn column_gap(mut self, value: f32) -> Self {
+        self.style.gap.width = LengthPercentage::Length(value);
+        self
+    }
+
     pub fn flex_grow(mut self, value: f32) -> Self {
         self.style.flex_grow = value;
         self
@@ -654,6 +971,32 @@ impl StyleBuilder {
         self
     }
 
+    // Grid
+    pub fn grid(mut self) -> Self {
+        self.style.display = Display::Grid;
+        self
+    }
+
+    pub fn grid_template_columns(mut self, tracks: Vec<TrackSizingFunction>) -> Self {
+        self.style.grid_template_columns = tracks;
+        self
+    }
+
+    pub fn grid_template_rows(mut self, tracks: Vec<TrackSizingFunction>) -> Self {
+        self.style.grid_template_rows = tracks;
+        self
+    }
+
+    pub fn grid_column(mut self, placement: Line<GridPlacement>) -> Self {
+        self.style.grid_column = placement;
+        self
+    }
+
+    pub fn grid_row(mut self, placement: Line<GridPlacement>) -> Self {
+        self.style.grid_row = placement;
+        self
+    }
+
     // Position
     pub fn position_absolute(mut self) -> Self {
         self.style.position = Position::Absolute;
@@ -811,4 +1154,293 @@ mod tests {
 
         assert_eq!(visited.len(), 3);
     }
+
+    #[test]
+    fn test_grid_style_builder() {
+        let style = StyleBuilder::new()
+            .grid()
+            .size_full()
+            .grid_template_columns(vec![fr(1.0), fr(1.0)])
+            .grid_template_rows(vec![length(100.0), length(100.0)])
+            .build();
+
+        assert_eq!(style.display, Display::Grid);
+        assert_eq!(style.grid_template_columns.len(), 2);
+        assert_eq!(style.grid_template_rows.len(), 2);
+
+        let cell = StyleBuilder::new()
+            .grid_column(Line { start: line(1), end: span(2) })
+            .grid_row(Line { start: line(2), end: auto() })
+            .build();
+        assert_eq!(cell.grid_column.end, GridPlacement::Span(2));
+
+        let mut tree = LayoutTree::new();
+        let wide = tree.new_node(cell);
+        let root = tree.new_node_with_children(style, &[wide]);
+        tree.compute_layout(
+            root,
+            Size {
+                width: AvailableSpace::Definite(400.0),
+                height: AvailableSpace::Definite(200.0),
+            },
+        );
+        let rect = tree.get_rect(wide);
+        assert_eq!((rect.x, rect.y, rect.width, rect.height), (0.0, 100.0, 400.0, 100.0));
+    }
+
+    #[test]
+    fn test_incremental_layout() {
+        let mut tree = LayoutTree::new();
+        let leaves: Vec<_> = (0..3)
+            .map(|_| tree.new_node(StyleBuilder::new().size(50.0, 50.0).build()))
+            .collect();
+        let row = tree.new_node_with_children(StyleBuilder::new().flex_row().build(), &leaves);
+        let root = tree.new_node_with_children(StyleBuilder::new().size_full().build(), &[row]);
+        let space = Size {
+            width: AvailableSpace::Definite(400.0),
+            height: AvailableSpace::Definite(300.0),
+        };
+
+        assert_eq!(tree.compute_layout_incremental(root, space), 5);
+        assert_eq!(tree.compute_layout_incremental(root, space), 0);
+
+        // Only the leaf and its ancestors are laid out again
+        tree.set_style(leaves[0], StyleBuilder::new().size(80.0, 50.0).build());
+        assert!(!tree.taffy.dirty(leaves[1]).unwrap());
+        assert_eq!(tree.compute_layout_incremental(root, space), 3);
+        assert_eq!(tree.get_rect(leaves[1]).x, 80.0);
+        assert_eq!(tree.get_rect(leaves[2]).x, 130.0);
+
+        tree.mark_dirty(leaves[2]);
+        assert_eq!(tree.compute_layout_incremental(root, space), 3);
+
+        let resized = Size {
+            width: AvailableSpace::Definite(200.0),
+            height: AvailableSpace::Definite(300.0),
+        };
+        assert_eq!(tree.compute_layout_incremental(root, resized), 5);
+        assert_eq!(tree.get_rect(root).width, 200.0);
+    }
+
+    fn absolute(x: f32, y: f32, size: f32) -> Style {
+        StyleBuilder::new()
+            .position_absolute()
+            .left(x)
+            .top(y)
+            .size(size, size)
+            .build()
+    }
+
+    #[test]
+    fn test_hit_test_overlapping_absolute() {
+        let mut tree = LayoutTree::new();
+        let inner = tree.new_node(absolute(150.0, 150.0, 100.0));
+        let first = tree.new_visual_node_with_children(
+            absolute(0.0, 0.0, 200.0),
+            NodeVisual::default().with_clips_children(true),
+            &[inner],
+        );
+        let second = tree.new_node(absolute(100.0, 100.0, 200.0));
+        // In-flow content later in tree order still paints below absolutes
+        let content = tree.new_node(StyleBuilder::new().size(300.0, 300.0).build());
+        let root = tree.new_node_with_children(
+            StyleBuilder::new().size(400.0, 400.0).build(),
+            &[first, second, content],
+        );
+        tree.compute_layout(
+            root,
+            Size {
+                width: AvailableSpace::Definite(400.0),
+                height: AvailableSpace::Definite(400.0),
+            },
+        );
+
+        assert_eq!(tree.hit_test(50.0, 50.0, root), Some(first));
+        assert_eq!(tree.hit_test(150.0, 150.0, root), Some(second));
+        assert_eq!(tree.hit_test(250.0, 50.0, root), Some(content));
+        assert_eq!(tree.hit_test(350.0, 350.0, root), Some(root));
+        assert_eq!(tree.hit_test(450.0, 50.0, root), None);
+
+        // The clipped child is only reachable inside its ancestor
+        tree.set_style(second, absolute(300.0, 300.0, 50.0));
+        tree.compute_layout(
+            root,
+            Size {
+                width: AvailableSpace::Definite(400.0),
+                height: AvailableSpace::Definite(400.0),
+            },
+        );
+        assert_eq!(tree.hit_test(175.0, 175.0, root), Some(inner));
+        assert_eq!(tree.hit_test(225.0, 225.0, root), Some(content));
+    }
+
+    #[test]
+    fn test_painters_order_by_z_index() {
+        let mut tree = LayoutTree::new();
+        let modal = tree.new_visual_node(
+            absolute(50.0, 50.0, 100.0),
+            NodeVisual::default().with_z_index(10),
+        );
+        let content = tree.new_visual_node(
+            absolute(0.0, 0.0, 200.0),
+            NodeVisual::default().with_z_index(0),
+        );
+        let backdrop = tree.new_visual_node(
+            StyleBuilder::new().size(200.0, 200.0).build(),
+            NodeVisual::default().with_z_index(-1),
+        );
+        let root = tree.new_node_with_children(
+            StyleBuilder::new().size(200.0, 200.0).build(),
+            &[modal, content, backdrop],
+        );
+        tree.compute_layout(
+            root,
+            Size {
+                width: AvailableSpace::Definite(200.0),
+                height: AvailableSpace::Definite(200.0),
+            },
+        );
+
+        let mut order = Vec::new();
+        tree.traverse_painters_order(root, |node, _, _, _| order.push(node));
+        assert_eq!(order, vec![root, backdrop, content, modal]);
+
+        // Tree order is untouched by plain traversal
+        let mut order = Vec::new();
+        tree.traverse_with_clip(root, |node, _, _, _| order.push(node));
+        assert_eq!(order, vec![root, modal, content, backdrop]);
+
+        assert_eq!(tree.hit_test(100.0, 100.0, root), Some(modal));
+        assert_eq!(tree.hit_test(10.0, 10.0, root), Some(content));
+    }
+
+    #[test]
+    fn test_resolved_percent_margin_and_padding() {
+        let mut tree = LayoutTree::new();
+        let mut style = StyleBuilder::new().size(100.0, 50.0).build();
+        style.margin = Rect {
+            left: LengthPercentageAuto::Percent(0.1),
+            right: LengthPercentageAuto::Auto,
+            top: LengthPercentageAuto::Percent(0.1),
+            bottom: LengthPercentageAuto::Length(8.0),
+        };
+        style.padding = Rect {
+            left: LengthPercentage::Percent(0.05),
+            right: LengthPercentage::Length(4.0),
+            top: zero(),
+            bottom: zero(),
+        };
+        let child = tree.new_node(style);
+        let root = tree.new_node_with_children(
+            StyleBuilder::new().flex_column().size(400.0, 300.0).build(),
+            &[child],
+        );
+        tree.compute_layout(
+            root,
+            Size {
+                width: AvailableSpace::Definite(400.0),
+                height: AvailableSpace::Definite(300.0),
+            },
+        );
+
+        // Percentages resolve against the 400px parent width on both axes
+        let margin = tree.resolved_margin(child);
+        assert_eq!((margin.left, margin.top, margin.bottom), (40.0, 40.0, 8.0));
+        // The auto margin takes the remaining row space
+        assert_eq!(margin.right, 260.0);
+        assert_eq!(tree.get_rect(child).x, 40.0);
+
+        let padding = tree.resolved_padding(child);
+        assert_eq!((padding.left, padding.right), (20.0, 4.0));
+    }
+
+    #[test]
+    fn test_measured_node_wraps_to_width() {
+        let mut tree = LayoutTree::new();
+        // Text covering 10000 px² of area: narrower means taller
+        let text = tree.new_measured_node(Style::default(), |constraint: Size<Option<f32>>| {
+            let width = constraint.width.unwrap_or(1000.0);
+            Size { width, height: 10000.0 / width }
+        });
+        let root = tree.new_node_with_children(
+            StyleBuilder::new().flex_column().width(400.0).build(),
+            &[text],
+        );
+
+        let space = Size {
+            width: AvailableSpace::Definite(400.0),
+            height: AvailableSpace::MaxContent,
+        };
+        tree.compute_layout(root, space);
+        assert_eq!(tree.get_rect(text).width, 400.0);
+        assert_eq!(tree.get_rect(text).height, 25.0);
+        assert_eq!(tree.get_rect(root).height, 25.0);
+
+        tree.set_style(root, StyleBuilder::new().flex_column().width(200.0).build());
+        tree.compute_layout(root, space);
+        assert_eq!(tree.get_rect(text).height, 50.0);
+        assert_eq!(tree.get_rect(root).height, 50.0);
+    }
+
+    #[test]
+    fn test_per_axis_gap() {
+        let style = StyleBuilder::new().gap_xy(16.0, 8.0).build();
+        assert_eq!(style.gap.width, LengthPercentage::Length(16.0));
+        assert_eq!(style.gap.height, LengthPercentage::Length(8.0));
+
+        let style = StyleBuilder::new().gap(4.0).row_gap(12.0).build();
+        assert_eq!(style.gap.width, LengthPercentage::Length(4.0));
+        assert_eq!(style.gap.height, LengthPercentage::Length(12.0));
+
+        let mut tree = LayoutTree::new();
+        let cards: Vec<NodeId> =
+            (0..4).map(|_| tree.new_node(StyleBuilder::new().size(50.0, 30.0).build())).collect();
+        let root = tree.new_node_with_children(
+            StyleBuilder::new()
+                .grid()
+                .grid_template_columns(vec![length(50.0), length(50.0)])
+                .column_gap(20.0)
+                .row_gap(10.0)
+                .build(),
+            &cards,
+        );
+        tree.compute_layout(root, Size::MAX_CONTENT);
+        assert_eq!(tree.get_rect(cards[1]).x, 70.0);
+        assert_eq!(tree.get_rect(cards[2]).y, 40.0);
+    }
+
+    #[test]
+    fn test_min_and_max_content_of_wrapping_row() {
+        let mut tree = LayoutTree::new();
+        let items: Vec<NodeId> =
+            (0..3).map(|_| tree.new_node(StyleBuilder::new().size(100.0, 20.0).build())).collect();
+        let root =
+            tree.new_node_with_children(StyleBuilder::new().flex_row().flex_wrap().build(), &items);
+
+        // Min-content wraps every item onto its own line
+        tree.compute_min_content(root);
+        assert_eq!(tree.get_rect(root).width, 100.0);
+        assert_eq!(tree.get_rect(root).height, 60.0);
+
+        tree.compute_max_content(root);
+        assert_eq!(tree.get_rect(root).width, 300.0);
+        assert_eq!(tree.get_rect(root).height, 20.0);
+
+        tree.compute_layout_fixed(root, 250.0, 400.0);
+        assert_eq!(tree.get_rect(items[2]).y, 20.0);
+
+        let natural = tree.measure_root(root, AvailableSpace::MinContent);
+        assert_eq!((natural.width, natural.height), (100.0, 60.0));
+        let natural = tree.measure_root(root, AvailableSpace::MaxContent);
+        assert_eq!((natural.width, natural.height), (300.0, 20.0));
+
+        // Measuring leaves the committed 250px layout in place
+        assert_eq!(tree.get_rect(root).width, 250.0);
+        assert_eq!(tree.get_rect(items[2]).y, 20.0);
+        let fixed = Size {
+            width: AvailableSpace::Definite(250.0),
+            height: AvailableSpace::Definite(400.0),
+        };
+        assert_eq!(tree.compute_layout_incremental(root, fixed), 0);
+    }
 }
@@ -261,6 +261,27 @@ impl BreakpointContext {
     pub fn resolve<'a, T: Clone>(&self, responsive: &'a Responsive<T>) -> &'a T {
         responsive.resolve(self.current)
     }
+
+    /// Resolve a responsive `f32` by interpolating between breakpoints
+    ///
+    /// The base value sits at 0px and each override at its breakpoint's
+    /// minimum width. Widths between two defined values blend linearly;
+    /// past the last defined breakpoint the value holds.
+    pub fn resolve_interpolated(&self, responsive: &Responsive<f32>, width: f32) -> f32 {
+        let mut lower = (0.0, responsive.base);
+        for breakpoint in Breakpoint::all() {
+            let Some(&value) = responsive.overrides.get(&breakpoint) else {
+                continue;
+            };
+            let stop = breakpoint.min_width();
+            if width < stop {
+                let t = (width - lower.0) / (stop - lower.0);
+                return lower.1 + (value - lower.1) * t.clamp(0.0, 1.0);
+            }
+            lower = (stop, value);
+        }
+        lower.1
+    }
 }
 
 impl Default for BreakpointContext {
@@ -371,4 +392,21 @@ mod tests {
         let desktop_ctx = BreakpointContext::from_size(1280.0, 720.0, 1.0);
         assert_eq!(*desktop_ctx.resolve(&columns), 4);
     }
+
+    #[test]
+    fn test_resolve_interpolated() {
+        let padding = Responsive::new(8.0)
+            .at(Breakpoint::Md, 16.0)
+            .at(Breakpoint::Lg, 24.0);
+        let ctx = BreakpointContext::default();
+
+        // Halfway between Md (768px) and Lg (992px)
+        assert_eq!(ctx.resolve_interpolated(&padding, 880.0), 20.0);
+        assert_eq!(ctx.resolve_interpolated(&padding, 768.0), 16.0);
+        assert_eq!(ctx.resolve_interpolated(&padding, 384.0), 12.0);
+        // Holds past the last defined breakpoint
+        assert_eq!(ctx.resolve_interpolated(&padding, 1920.0), 24.0);
+        // Stepped resolution is unchanged
+        assert_eq!(*ctx.resolve(&padding), 24.0);
+    }
 }
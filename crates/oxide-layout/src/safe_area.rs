@@ -4,6 +4,9 @@
 //! and status bars commonly found on modern mobile devices.
 
 use bitflags::bitflags;
+use taffy::{LengthPercentage, NodeId, Rect};
+
+use crate::LayoutTree;
 
 /// Safe area insets representing the spacing needed to avoid device-specific UI elements.
 ///
@@ -209,6 +212,16 @@ pub struct SafeAreaContainer {
     edges: SafeAreaEdges,
     /// Additional minimum padding beyond safe area
     minimum_padding: SafeAreaInsets,
+    /// Root of the last `apply`, with its author padding and the padding written
+    applied: Option<AppliedPadding>,
+}
+
+/// Padding bookkeeping for the root a container was last applied to
+#[derive(Debug, Clone, Copy)]
+struct AppliedPadding {
+    root: NodeId,
+    base: Rect<LengthPercentage>,
+    written: Rect<LengthPercentage>,
 }
 
 impl SafeAreaContainer {
@@ -218,6 +231,7 @@ impl SafeAreaContainer {
             insets: SafeAreaInsets::zero(),
             edges: SafeAreaEdges::ALL,
             minimum_padding: SafeAreaInsets::zero(),
+            applied: None,
         }
     }
 
@@ -260,6 +274,45 @@ impl SafeAreaContainer {
         self.insets = insets;
     }
 
+    /// Inject the effective insets as padding on a layout root
+    ///
+    /// Updates the container's insets and adds the effective insets to the
+    /// root's own pixel padding, replacing whatever a previous call added, so
+    /// calling this again with the same insets leaves the tree untouched.
+    /// Percentage padding on an inset edge is replaced while the inset is
+    /// non-zero and restored afterwards. If the root's style was rebuilt since
+    /// the last call, its new padding is taken as the author padding.
+    pub fn apply(&mut self, tree: &mut LayoutTree, root: NodeId, insets: SafeAreaInsets) {
+        self.insets = insets;
+        let effective = self.effective_insets();
+        let Some(style) = tree.get_style(root) else {
+            return;
+        };
+
+        let mut style = style.clone();
+        let base = match self.applied {
+            Some(applied) if applied.root == root && applied.written == style.padding => {
+                applied.base
+            }
+            _ => style.padding,
+        };
+        style.padding = Rect {
+            top: inset_padding(base.top, effective.top),
+            bottom: inset_padding(base.bottom, effective.bottom),
+            left: inset_padding(base.left, effective.left),
+            right: inset_padding(base.right, effective.right),
+        };
+        self.applied = Some(AppliedPadding {
+            root,
+            base,
+            written: style.padding,
+        });
+
+        if tree.get_style(root) != Some(&style) {
+            tree.set_style(root, style);
+        }
+    }
+
     /// Check if top edge is active
     pub fn has_top(&self) -> bool {
         self.edges.contains(SafeAreaEdges::TOP)
@@ -287,6 +340,15 @@ impl Default for SafeAreaContainer {
     }
 }
 
+/// Add an inset to an author padding edge
+fn inset_padding(value: LengthPercentage, inset: f32) -> LengthPercentage {
+    match value {
+        LengthPercentage::Length(length) => LengthPercentage::Length(length + inset),
+        LengthPercentage::Percent(_) if inset == 0.0 => value,
+        LengthPercentage::Percent(_) => LengthPercentage::Length(inset),
+    }
+}
+
 /// Common device safe area presets
 pub mod presets {
     use super::SafeAreaInsets;
@@ -459,4 +521,68 @@ mod tests {
         assert!(container.has_left());
         assert!(!container.has_right());
     }
+
+    #[test]
+    fn test_apply_to_layout_root() {
+        use crate::StyleBuilder;
+        use taffy::{AvailableSpace, Size};
+
+        let mut tree = LayoutTree::new();
+        let content = tree.new_node(StyleBuilder::new().width(100.0).height(20.0).build());
+        let root = tree.new_node_with_children(
+            StyleBuilder::new().flex_column().padding(8.0).size(390.0, 844.0).build(),
+            &[content],
+        );
+        let space = Size {
+            width: AvailableSpace::Definite(390.0),
+            height: AvailableSpace::Definite(844.0),
+        };
+        tree.compute_layout(root, space);
+        assert_eq!(tree.get_rect(content).y, 8.0);
+
+        let mut container = SafeAreaContainer::new().with_edges(SafeAreaEdges::VERTICAL);
+        let insets = SafeAreaInsets::new(44.0, 0.0, 20.0, 0.0);
+        container.apply(&mut tree, root, insets);
+        tree.compute_layout(root, space);
+        // Content origin shifts down by the top inset; the left edge is opted out
+        assert_eq!(tree.get_rect(content).y, 52.0);
+        assert_eq!(tree.get_rect(content).x, 8.0);
+
+        // Re-applying the same insets is a no-op
+        container.apply(&mut tree, root, insets);
+        assert_eq!(tree.compute_layout_incremental(root, space), 0);
+        assert_eq!(tree.get_rect(content).y, 52.0);
+
+        // Removing the inset restores the original padding
+        container.apply(&mut tree, root, SafeAreaInsets::zero());
+        tree.compute_layout(root, space);
+        assert_eq!(tree.get_rect(content).y, 8.0);
+    }
+
+    #[test]
+    fn test_apply_after_style_rebuild() {
+        use crate::StyleBuilder;
+
+        let mut tree = LayoutTree::new();
+        let root = tree.new_node(StyleBuilder::new().padding(8.0).build());
+        let mut container = SafeAreaContainer::new();
+        let insets = SafeAreaInsets::new(44.0, 0.0, 0.0, 0.0);
+        container.apply(&mut tree, root, insets);
+        assert_eq!(tree.get_style(root).unwrap().padding.top, LengthPercentage::Length(52.0));
+
+        // The author rebuilds the style; the inset is added once, not subtracted
+        tree.set_style(root, StyleBuilder::new().padding(12.0).build());
+        container.apply(&mut tree, root, insets);
+        assert_eq!(tree.get_style(root).unwrap().padding.top, LengthPercentage::Length(56.0));
+
+        // Percentage padding comes back once its edge has no inset
+        let mut style = tree.get_style(root).unwrap().clone();
+        style.padding.top = LengthPercentage::Percent(0.1);
+        tree.set_style(root, style);
+        container.apply(&mut tree, root, insets);
+        assert_eq!(tree.get_style(root).unwrap().padding.top, LengthPercentage::Length(44.0));
+        container.apply(&mut tree, root, SafeAreaInsets::zero());
+        assert_eq!(tree.get_style(root).unwrap().padding.top, LengthPercentage::Percent(0.1));
+        assert_eq!(tree.get_style(root).unwrap().padding.left, LengthPercentage::Length(12.0));
+    }
 }
@@ -0,0 +1,222 @@
+//! Layout Snapshots
+//!
+//! Serializable dumps of a computed layout tree for golden-file testing.
+//! Take a snapshot after `compute_layout`, store it as JSON, and diff later
+//! snapshots against it to catch layout regressions.
+
+use serde::{Deserialize, Serialize};
+
+use crate::ComputedRect;
+
+/// Rect differences smaller than this are treated as equal
+const SNAPSHOT_EPSILON: f32 = 0.01;
+
+/// Key style fields recorded alongside each node's rect
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct SnapshotStyle {
+    /// Display mode (`Flex`, `Grid`, `Block`, `None`)
+    pub display: String,
+    /// Positioning scheme (`Relative`, `Absolute`)
+    pub position: String,
+    /// Main axis of a flex container
+    pub flex_direction: String,
+}
+
+/// A computed layout subtree
+///
+/// Rects are relative to the parent node, matching [`crate::LayoutTree::get_rect`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayoutSnapshot {
+    /// Node id at the time the snapshot was taken
+    pub id: u64,
+    /// Computed rect relative to the parent
+    pub rect: ComputedRect,
+    /// Key style fields
+    pub style: SnapshotStyle,
+    /// Child snapshots in document order
+    pub children: Vec<LayoutSnapshot>,
+}
+
+/// How a node differs from the expected snapshot
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeltaKind {
+    /// The node is not in the expected snapshot
+    Added,
+    /// The expected node is missing
+    Removed,
+    /// The node moved, resized, or changed style
+    Changed {
+        /// Rect in the expected snapshot
+        expected: ComputedRect,
+        /// Rect in this snapshot
+        actual: ComputedRect,
+        /// Whether the recorded style fields differ
+        style_changed: bool,
+    },
+}
+
+/// A per-node difference between two snapshots
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeDelta {
+    /// Child indices from the root to the node
+    pub path: Vec<usize>,
+    /// Node id (from this snapshot, or the expected one for removals)
+    pub id: u64,
+    /// What changed
+    pub kind: DeltaKind,
+}
+
+impl NodeDelta {
+    /// Position change as (dx, dy), zero unless the node changed
+    pub fn offset(&self) -> (f32, f32) {
+        match &self.kind {
+            DeltaKind::Changed {
+                expected, actual, ..
+            } => (actual.x - expected.x, actual.y - expected.y),
+            _ => (0.0, 0.0),
+        }
+    }
+
+    /// Size change as (dwidth, dheight), zero unless the node changed
+    pub fn size_change(&self) -> (f32, f32) {
+        match &self.kind {
+            DeltaKind::Changed {
+                expected, actual, ..
+            } => (actual.width - expected.width, actual.height - expected.height),
+            _ => (0.0, 0.0),
+        }
+    }
+}
+
+impl LayoutSnapshot {
+    /// Diff this snapshot against a known-good one
+    ///
+    /// Nodes are matched by their position in the tree rather than by id, so
+    /// snapshots from separately built trees compare cleanly.
+    pub fn diff(&self, expected: &LayoutSnapshot) -> Vec<NodeDelta> {
+        let mut deltas = Vec::new();
+        let mut path = Vec::new();
+        self.diff_recursive(expected, &mut path, &mut deltas);
+        deltas
+    }
+
+    fn diff_recursive(
+        &self,
+        expected: &LayoutSnapshot,
+        path: &mut Vec<usize>,
+        deltas: &mut Vec<NodeDelta>,
+    ) {
+        let style_changed = self.style != expected.style;
+        if style_changed || !rects_match(&self.rect, &expected.rect) {
+            deltas.push(NodeDelta {
+                path: path.clone(),
+                id: self.id,
+                kind: DeltaKind::Changed {
+                    expected: expected.rect,
+                    actual: self.rect,
+                    style_changed,
+                },
+            });
+        }
+
+        let shared = self.children.len().min(expected.children.len());
+        for index in 0..shared {
+            path.push(index);
+            self.children[index].diff_recursive(&expected.children[index], path, deltas);
+            path.pop();
+        }
+        for (index, child) in self.children.iter().enumerate().skip(shared) {
+            path.push(index);
+            child.collect(DeltaKind::Added, path, deltas);
+            path.pop();
+        }
+        for (index, child) in expected.children.iter().enumerate().skip(shared) {
+            path.push(index);
+            child.collect(DeltaKind::Removed, path, deltas);
+            path.pop();
+        }
+    }
+
+    /// Record a whole subtree as added or removed
+    fn collect(&self, kind: DeltaKind, path: &mut Vec<usize>, deltas: &mut Vec<NodeDelta>) {
+        deltas.push(NodeDelta {
+            path: path.clone(),
+            id: self.id,
+            kind: kind.clone(),
+        });
+        for (index, child) in self.children.iter().enumerate() {
+            path.push(index);
+            child.collect(kind.clone(), path, deltas);
+            path.pop();
+        }
+    }
+}
+
+fn rects_match(a: &ComputedRect, b: &ComputedRect) -> bool {
+    (a.x - b.x).abs() < SNAPSHOT_EPSILON
+        && (a.y - b.y).abs() < SNAPSHOT_EPSILON
+        && (a.width - b.width).abs() < SNAPSHOT_EPSILON
+        && (a.height - b.height).abs() < SNAPSHOT_EPSILON
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LayoutTree, StyleBuilder};
+    use taffy::{AvailableSpace, NodeId, Size};
+
+    fn build(sidebar_width: f32, with_footer: bool) -> (LayoutTree, NodeId) {
+        let mut tree = LayoutTree::new();
+        let sidebar = tree.new_node(StyleBuilder::new().width(sidebar_width).build());
+        let content = tree.new_node(StyleBuilder::new().flex_grow(1.0).build());
+        let mut children = vec![sidebar, content];
+        if with_footer {
+            children.push(tree.new_node(StyleBuilder::new().size(40.0, 40.0).build()));
+        }
+        let root = tree.new_node_with_children(
+            StyleBuilder::new().flex_row().size(800.0, 600.0).build(),
+            &children,
+        );
+        tree.compute_layout(
+            root,
+            Size {
+                width: AvailableSpace::Definite(800.0),
+                height: AvailableSpace::Definite(600.0),
+            },
+        );
+        (tree, root)
+    }
+
+    #[test]
+    fn test_snapshot_json_round_trip() {
+        let (tree, root) = build(200.0, false);
+        let snapshot = tree.snapshot(root);
+        assert_eq!(snapshot.children.len(), 2);
+        assert_eq!(snapshot.style.flex_direction, "Row");
+        assert_eq!(snapshot.children[1].rect.x, 200.0);
+        assert_eq!(snapshot.children[1].rect.width, 600.0);
+
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let restored: LayoutSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, snapshot);
+        assert!(snapshot.diff(&restored).is_empty());
+    }
+
+    #[test]
+    fn test_snapshot_diff() {
+        let (tree, root) = build(200.0, false);
+        let golden = tree.snapshot(root);
+        let (tree, root) = build(250.0, true);
+        let deltas = tree.snapshot(root).diff(&golden);
+
+        // Sidebar grew, content shifted and shrank, footer appeared
+        assert_eq!(deltas.len(), 3);
+        assert_eq!(deltas[0].path, vec![0]);
+        assert_eq!(deltas[0].size_change(), (50.0, 0.0));
+        assert_eq!(deltas[1].path, vec![1]);
+        assert_eq!(deltas[1].offset(), (50.0, 0.0));
+        assert_eq!(deltas[1].size_change(), (-90.0, 0.0));
+        assert_eq!(deltas[2].path, vec![2]);
+        assert_eq!(deltas[2].kind, DeltaKind::Added);
+    }
+}
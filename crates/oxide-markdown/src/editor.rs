@@ -1,7 +1,16 @@
 //! Markdown editor component.
 
+use crate::parser::MarkdownParser;
+use crate::paste::{html_to_markdown, looks_like_html};
+use crate::renderer::{IncrementalRenderer, RenderedBlock};
+use crate::toolbar::{Toolbar, ToolbarAction, ToolbarItem};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 /// Editor mode
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
@@ -20,8 +29,15 @@ pub enum EditorMode {
 pub struct AutoSaveConfig {
     /// Enable auto-save
     pub enabled: bool,
-    /// Interval
+    /// Idle time after the last change before saving
     pub interval: Duration,
+    /// Snapshots to keep; older ones are pruned
+    #[serde(default = "default_max_snapshots")]
+    pub max_snapshots: usize,
+}
+
+fn default_max_snapshots() -> usize {
+    20
 }
 
 impl Default for AutoSaveConfig {
@@ -29,10 +45,25 @@ impl Default for AutoSaveConfig {
         Self {
             enabled: false,
             interval: Duration::from_secs(30),
+            max_snapshots: default_max_snapshots(),
         }
     }
 }
 
+/// A saved copy of the editor content
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EditorSnapshot {
+    /// Snapshot id, increasing with each save
+    pub id: u64,
+    /// When the snapshot was taken
+    pub timestamp: DateTime<Utc>,
+    /// Saved content
+    pub content: String,
+}
+
+/// Callback invoked with the content on each autosave
+pub type ChangeCallback = Arc<dyn Fn(&str) + Send + Sync>;
+
 /// Keyboard shortcut
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KeyboardShortcut {
@@ -97,6 +128,40 @@ impl EditorState {
         self
     }
 
+    /// Ordered selection bounds, or the cursor when nothing is selected
+    pub fn selection_range(&self) -> (usize, usize) {
+        match (self.selection_start, self.selection_end) {
+            (Some(start), Some(end)) => (start.min(end), start.max(end)),
+            _ => (self.cursor, self.cursor),
+        }
+    }
+
+    /// Select a range and move the cursor to its end
+    pub fn select(&mut self, start: usize, end: usize) {
+        if start == end {
+            self.selection_start = None;
+            self.selection_end = None;
+        } else {
+            self.selection_start = Some(start);
+            self.selection_end = Some(end);
+        }
+        self.cursor = end;
+    }
+
+    /// Selection bounds clamped to the content and moved back to char
+    /// boundaries, so they are always safe to slice and edit with
+    fn edit_range(&self) -> (usize, usize) {
+        let floor = |mut offset: usize| {
+            offset = offset.min(self.content.len());
+            while !self.content.is_char_boundary(offset) {
+                offset -= 1;
+            }
+            offset
+        };
+        let (start, end) = self.selection_range();
+        (floor(start), floor(end))
+    }
+
     /// Get selected text
     pub fn selected_text(&self) -> Option<&str> {
         match (self.selection_start, self.selection_end) {
@@ -136,26 +201,60 @@ impl MarkdownEditorConfig {
 }
 
 /// Markdown editor component
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct MarkdownEditor {
     /// State
     pub state: EditorState,
     /// Configuration
     pub config: MarkdownEditorConfig,
+    /// Live preview, re-rendered block by block
+    preview: IncrementalRenderer,
+    /// Autosave snapshots, oldest first
+    snapshots: Vec<EditorSnapshot>,
+    /// Id for the next snapshot
+    next_snapshot_id: u64,
+    /// Hash of the last saved (or initial) content
+    saved_hash: u64,
+    /// Hash of the content at the last tick
+    seen_hash: u64,
+    /// When the content last changed, if not saved since
+    changed_at: Option<Instant>,
+    /// Autosave callback
+    on_change: Option<ChangeCallback>,
+}
+
+impl fmt::Debug for MarkdownEditor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MarkdownEditor")
+            .field("state", &self.state)
+            .field("config", &self.config)
+            .field("snapshots", &self.snapshots.len())
+            .finish()
+    }
 }
 
 impl MarkdownEditor {
     /// Create new editor
     pub fn new() -> Self {
+        let empty = content_hash("");
         Self {
             state: EditorState::new(),
             config: MarkdownEditorConfig::new(),
+            preview: IncrementalRenderer::default(),
+            snapshots: Vec::new(),
+            next_snapshot_id: 1,
+            saved_hash: empty,
+            seen_hash: empty,
+            changed_at: None,
+            on_change: None,
         }
     }
 
     /// Set value
     pub fn value(mut self, content: impl Into<String>) -> Self {
         self.state.content = content.into();
+        self.saved_hash = content_hash(&self.state.content);
+        self.seen_hash = self.saved_hash;
         self
     }
 
@@ -178,6 +277,81 @@ impl MarkdownEditor {
         self
     }
 
+    /// Keep at most `max` autosave snapshots
+    pub fn max_snapshots(mut self, max: usize) -> Self {
+        self.config.auto_save.max_snapshots = max;
+        self
+    }
+
+    /// Call `callback` with the content whenever it is autosaved
+    pub fn on_change(mut self, callback: impl Fn(&str) + Send + Sync + 'static) -> Self {
+        self.on_change = Some(Arc::new(callback));
+        self
+    }
+
+    /// Advance autosave; call periodically (e.g. once per frame)
+    ///
+    /// Saves a snapshot once the content has been idle for the configured
+    /// interval, and only if it differs from the last save. Returns the id
+    /// of the new snapshot.
+    pub fn tick(&mut self, now: Instant) -> Option<u64> {
+        if !self.config.auto_save.enabled {
+            return None;
+        }
+        let hash = content_hash(&self.state.content);
+        if hash != self.seen_hash {
+            self.seen_hash = hash;
+            self.changed_at = Some(now);
+            return None;
+        }
+        let changed_at = self.changed_at?;
+        if now.duration_since(changed_at) < self.config.auto_save.interval {
+            return None;
+        }
+        self.changed_at = None;
+        self.save_snapshot()
+    }
+
+    /// Save a snapshot now unless the content matches the last save
+    pub fn save_snapshot(&mut self) -> Option<u64> {
+        let hash = content_hash(&self.state.content);
+        if hash == self.saved_hash {
+            return None;
+        }
+        self.saved_hash = hash;
+
+        let id = self.next_snapshot_id;
+        self.next_snapshot_id += 1;
+        self.snapshots.push(EditorSnapshot {
+            id,
+            timestamp: Utc::now(),
+            content: self.state.content.clone(),
+        });
+        let excess = self.snapshots.len().saturating_sub(self.config.auto_save.max_snapshots.max(1));
+        self.snapshots.drain(..excess);
+
+        if let Some(callback) = &self.on_change {
+            callback(&self.state.content);
+        }
+        Some(id)
+    }
+
+    /// Retained snapshots, oldest first
+    pub fn snapshots(&self) -> &[EditorSnapshot] {
+        &self.snapshots
+    }
+
+    /// Replace the content with a snapshot; `false` if it was pruned or never existed
+    pub fn restore_snapshot(&mut self, id: u64) -> bool {
+        let Some(snapshot) = self.snapshots.iter().find(|snapshot| snapshot.id == id) else {
+            return false;
+        };
+        self.state.content = snapshot.content.clone();
+        self.state.select(self.state.content.len(), self.state.content.len());
+        self.state.modified = true;
+        true
+    }
+
     /// Insert text at cursor
     pub fn insert(&mut self, text: &str) {
         self.state.content.insert_str(self.state.cursor, text);
@@ -185,10 +359,194 @@ impl MarkdownEditor {
         self.state.modified = true;
     }
 
+    /// Paste clipboard content over the selection
+    ///
+    /// HTML (e.g. copied from a browser) is converted to markdown; if there
+    /// is none, or it converts to nothing, the plain text is pasted instead.
+    pub fn paste(&mut self, text: &str, html: Option<&str>) {
+        let markdown = html
+            .filter(|html| looks_like_html(html))
+            .map(html_to_markdown)
+            .filter(|markdown| !markdown.is_empty());
+        let pasted = markdown.as_deref().unwrap_or(text);
+
+        let (start, end) = self.state.edit_range();
+        self.state.content.replace_range(start..end, pasted);
+        self.state.select(start + pasted.len(), start + pasted.len());
+        self.state.modified = true;
+    }
+
     /// Get content
     pub fn content(&self) -> &str {
         &self.state.content
     }
+
+    /// Render the live preview, reusing output for blocks the edit didn't touch
+    pub fn render_preview(&mut self) -> Vec<RenderedBlock> {
+        self.preview.update(&self.state.content)
+    }
+
+    /// Get the preview renderer (e.g. to see which blocks were re-rendered)
+    pub fn preview(&self) -> &IncrementalRenderer {
+        &self.preview
+    }
+
+    /// Apply a toolbar item to the current selection
+    pub fn apply_toolbar(&mut self, item: ToolbarItem) -> bool {
+        let action = Toolbar::new().action_for(item);
+        self.apply_action(&action)
+    }
+
+    /// Apply a toolbar action to the current selection
+    ///
+    /// Returns `false` for actions the editor doesn't handle itself (undo/redo).
+    pub fn apply_action(&mut self, action: &ToolbarAction) -> bool {
+        match action {
+            ToolbarAction::Insert(text) => {
+                let (start, end) = self.state.edit_range();
+                self.state.content.replace_range(start..end, text);
+                self.state.select(start + text.len(), start + text.len());
+            }
+            ToolbarAction::Wrap {
+                prefix,
+                suffix,
+                placeholder,
+            } => self.wrap_selection(prefix, suffix, placeholder),
+            ToolbarAction::PrefixLines(prefix) => {
+                self.prefix_lines(|_| prefix.clone(), |line| line.strip_prefix(prefix.as_str()))
+            }
+            ToolbarAction::NumberLines => self.prefix_lines(|i| format!("{}. ", i + 1), strip_number),
+            ToolbarAction::TogglePreview => {
+                self.config.mode = match self.config.mode {
+                    EditorMode::Preview => EditorMode::Edit,
+                    _ => EditorMode::Preview,
+                };
+                return true;
+            }
+            ToolbarAction::Undo | ToolbarAction::Redo => return false,
+        }
+        self.state.modified = true;
+        true
+    }
+
+    /// Wrap the selection in markers, or remove them if already wrapped
+    fn wrap_selection(&mut self, prefix: &str, suffix: &str, placeholder: &str) {
+        let (start, end) = self.state.edit_range();
+        let content = &mut self.state.content;
+        let selected = content.get(start..end).unwrap_or_default();
+
+        // Selection includes the markers
+        if !selected.is_empty()
+            && selected.len() >= prefix.len() + suffix.len()
+            && selected.starts_with(prefix)
+            && selected.ends_with(suffix)
+        {
+            let inner = selected[prefix.len()..selected.len() - suffix.len()].to_string();
+            content.replace_range(start..end, &inner);
+            self.state.select(start, start + inner.len());
+            return;
+        }
+
+        // Markers surround the selection
+        if content[..start].ends_with(prefix) && content[end..].starts_with(suffix) {
+            content.replace_range(end..end + suffix.len(), "");
+            content.replace_range(start - prefix.len()..start, "");
+            self.state.select(start - prefix.len(), end - prefix.len());
+            return;
+        }
+
+        let inner = if start == end { placeholder.to_string() } else { selected.to_string() };
+        content.replace_range(start..end, &format!("{}{}{}", prefix, inner, suffix));
+        let inner_start = start + prefix.len();
+        self.state.select(inner_start, inner_start + inner.len());
+    }
+
+    /// Prefix every line touched by the selection, or strip the prefix if
+    /// every line already has it
+    fn prefix_lines(&mut self, prefix: impl Fn(usize) -> String, strip: impl Fn(&str) -> Option<&str>) {
+        let (start, end) = self.state.edit_range();
+        let content = &self.state.content;
+
+        let line_start = content[..start].rfind('\n').map_or(0, |i| i + 1);
+        // A selection ending at the start of a line doesn't include that line
+        let end = if end > start && content[..end].ends_with('\n') { end - 1 } else { end };
+        let line_end = content[end..].find('\n').map_or(content.len(), |i| end + i);
+
+        let lines: Vec<&str> = content[line_start..line_end].split('\n').collect();
+        let remove = lines.iter().all(|line| strip(line).is_some());
+        let block = lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| match strip(line) {
+                Some(rest) if remove => rest.to_string(),
+                _ => format!("{}{}", prefix(i), line),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        self.state.content.replace_range(line_start..line_end, &block);
+        self.state.select(line_start, line_start + block.len());
+    }
+
+    /// Handle a key press; returns `true` if the editor consumed it
+    pub fn handle_key(&mut self, key: &KeyboardShortcut) -> bool {
+        match key.key.as_str() {
+            "Enter" if !key.ctrl && !key.shift && !key.alt => {
+                self.newline();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Insert a line break, continuing the list item or blockquote at the cursor
+    ///
+    /// On an empty item the marker is outdented one level if nested, and
+    /// removed otherwise, ending the list or quote.
+    pub fn newline(&mut self) {
+        let (start, end) = self.state.edit_range();
+        let content = &mut self.state.content;
+        content.replace_range(start..end, "");
+
+        let line_start = content[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = content[start..].find('\n').map_or(content.len(), |i| start + i);
+        let before = &content[line_start..start];
+
+        let text = match continuation(before) {
+            Some((marker_end, _)) if marker_end == before.len() && content[start..line_end].trim().is_empty() => {
+                let indent = before.len() - before.trim_start_matches([' ', '\t']).len();
+                let removed = if indent > 0 {
+                    line_start + parent_indent(&content[..line_start], indent)..line_start + indent
+                } else {
+                    line_start..start
+                };
+                let cursor = start - removed.len();
+                content.replace_range(removed, "");
+                self.state.select(cursor, cursor);
+                self.state.modified = true;
+                return;
+            }
+            Some((_, next)) => format!("\n{}", next),
+            None => "\n".to_string(),
+        };
+        content.insert_str(start, &text);
+        self.state.select(start + text.len(), start + text.len());
+        self.state.modified = true;
+    }
+
+    /// Toggle the `index`th task list checkbox in the source
+    ///
+    /// Returns the new checked state, or `None` if there is no such task.
+    pub fn toggle_task(&mut self, index: usize) -> Option<bool> {
+        let marker = MarkdownParser::new()
+            .task_markers(&self.state.content)
+            .into_iter()
+            .nth(index)?;
+        let mark = if marker.checked { " " } else { "x" };
+        self.state.content.replace_range(marker.offset..marker.offset + 1, mark);
+        self.state.modified = true;
+        Some(!marker.checked)
+    }
 }
 
 impl Default for MarkdownEditor {
@@ -196,3 +554,340 @@ impl Default for MarkdownEditor {
         Self::new()
     }
 }
+
+fn content_hash(content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// List or blockquote markup that continues `line`: the end of its marker
+/// and the marker for the next line
+fn continuation(line: &str) -> Option<(usize, String)> {
+    let indent = line.len() - line.trim_start_matches([' ', '\t']).len();
+    let mut pos = indent;
+    while line[pos..].starts_with('>') {
+        pos += 1;
+        if line[pos..].starts_with(' ') {
+            pos += 1;
+        }
+    }
+
+    let rest = &line[pos..];
+    let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let (len, next) = if let Some(bullet) = ["- ", "* ", "+ "].into_iter().find(|b| rest.starts_with(b)) {
+        if ["[ ] ", "[x] ", "[X] "].iter().any(|task| rest[2..].starts_with(task)) {
+            (6, format!("{}[ ] ", bullet))
+        } else {
+            (2, bullet.to_string())
+        }
+    } else if digits > 0 && (rest[digits..].starts_with(". ") || rest[digits..].starts_with(") ")) {
+        let number: u64 = rest[..digits].parse().ok()?;
+        (digits + 2, format!("{}{}", number + 1, &rest[digits..digits + 2]))
+    } else if pos > indent {
+        (0, String::new())
+    } else {
+        return None;
+    };
+    Some((pos + len, format!("{}{}", &line[..pos], next)))
+}
+
+/// Indent of the closest list item above `before` that is less indented than
+/// `indent`, or 0 if there is none
+fn parent_indent(before: &str, indent: usize) -> usize {
+    before
+        .lines()
+        .rev()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| (line, line.len() - line.trim_start_matches([' ', '\t']).len()))
+        .find(|&(line, line_indent)| line_indent < indent && continuation(line).is_some())
+        .map_or(0, |(_, line_indent)| line_indent)
+}
+
+/// Strip a `1. ` style list number from the start of a line
+fn strip_number(line: &str) -> Option<&str> {
+    let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    if digits == 0 {
+        return None;
+    }
+    line[digits..].strip_prefix(". ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toggle_task() {
+        let mut editor = MarkdownEditor::new().value("# Todo\n\n- [ ] write\n- [X] test\n");
+
+        assert_eq!(editor.toggle_task(0), Some(true));
+        assert_eq!(editor.content(), "# Todo\n\n- [x] write\n- [X] test\n");
+        assert!(editor.state.modified);
+
+        assert_eq!(editor.toggle_task(1), Some(false));
+        assert_eq!(editor.toggle_task(0), Some(false));
+        assert_eq!(editor.content(), "# Todo\n\n- [ ] write\n- [ ] test\n");
+
+        assert_eq!(editor.toggle_task(2), None);
+    }
+
+    #[test]
+    fn test_preview_rerenders_edited_block() {
+        let content: String = (0..200).map(|i| format!("Paragraph number {}.\n\n", i)).collect();
+        let mut editor = MarkdownEditor::new().value(content);
+
+        let before = editor.render_preview();
+        assert_eq!(before.len(), 200);
+        assert_eq!(editor.preview().changed().len(), 200);
+
+        // Type into paragraph 120
+        editor.state.cursor = editor.content().find("number 120").unwrap() + "number 120".len();
+        editor.insert(" edited");
+        let after = editor.render_preview();
+
+        assert_eq!(editor.preview().changed(), [120]);
+        let differing: Vec<_> = (0..200).filter(|i| before[*i] != after[*i]).collect();
+        assert_eq!(differing, [120]);
+        assert_eq!(
+            after[120],
+            RenderedBlock::Paragraph(vec![crate::RenderedInline::Text("Paragraph number 120 edited.".into())])
+        );
+
+        // Nothing changed: nothing re-rendered
+        editor.render_preview();
+        assert!(editor.preview().changed().is_empty());
+    }
+
+    fn select(editor: &mut MarkdownEditor, text: &str) {
+        let start = editor.content().find(text).unwrap();
+        editor.state.select(start, start + text.len());
+    }
+
+    #[test]
+    fn test_bold_toggles() {
+        let mut editor = MarkdownEditor::new().value("make this loud");
+        select(&mut editor, "this");
+
+        assert!(editor.apply_toolbar(ToolbarItem::Bold));
+        assert_eq!(editor.content(), "make **this** loud");
+        assert_eq!(editor.state.selected_text(), Some("this"));
+
+        // Applying again with the inner text selected removes the markers
+        editor.apply_toolbar(ToolbarItem::Bold);
+        assert_eq!(editor.content(), "make this loud");
+        assert_eq!(editor.state.selected_text(), Some("this"));
+
+        // So does selecting the markers themselves
+        let mut editor = MarkdownEditor::new().value("`code` here");
+        select(&mut editor, "`code`");
+        editor.apply_toolbar(ToolbarItem::Code);
+        assert_eq!(editor.content(), "code here");
+    }
+
+    #[test]
+    fn test_empty_selection_inserts_placeholder() {
+        let mut editor = MarkdownEditor::new().value("Hi ");
+        editor.state.cursor = 3;
+
+        editor.apply_toolbar(ToolbarItem::Italic);
+        assert_eq!(editor.content(), "Hi _italic text_");
+        assert_eq!(editor.state.selected_text(), Some("italic text"));
+    }
+
+    #[test]
+    fn test_list_prefix_lines() {
+        let mut editor = MarkdownEditor::new().value("intro\napples\npears\nplums\n");
+        let start = editor.content().find("apples").unwrap();
+        let end = editor.content().find("plums").unwrap();
+        editor.state.select(start + 2, end);
+
+        editor.apply_toolbar(ToolbarItem::BulletList);
+        assert_eq!(editor.content(), "intro\n- apples\n- pears\nplums\n");
+
+        editor.apply_toolbar(ToolbarItem::BulletList);
+        assert_eq!(editor.content(), "intro\napples\npears\nplums\n");
+
+        select(&mut editor, "apples\npears\nplums");
+        editor.apply_toolbar(ToolbarItem::NumberedList);
+        assert_eq!(editor.content(), "intro\n1. apples\n2. pears\n3. plums\n");
+
+        select(&mut editor, "intro");
+        editor.apply_toolbar(ToolbarItem::Quote);
+        assert_eq!(editor.content(), "> intro\n1. apples\n2. pears\n3. plums\n");
+    }
+
+    fn enter_at_end(editor: &mut MarkdownEditor) {
+        editor.state.cursor = editor.content().len();
+        assert!(editor.handle_key(&KeyboardShortcut::new("Enter")));
+    }
+
+    #[test]
+    fn test_enter_continues_ordered_list() {
+        let mut editor = MarkdownEditor::new().value("1. one\n2. two");
+        enter_at_end(&mut editor);
+        assert_eq!(editor.content(), "1. one\n2. two\n3. ");
+        assert_eq!(editor.state.cursor, editor.content().len());
+
+        let mut editor = MarkdownEditor::new().value("- [x] done");
+        enter_at_end(&mut editor);
+        assert_eq!(editor.content(), "- [x] done\n- [ ] ");
+
+        // Shift+Enter is left to the caller
+        assert!(!editor.handle_key(&KeyboardShortcut::new("Enter").shift()));
+    }
+
+    #[test]
+    fn test_enter_continues_blockquote() {
+        let mut editor = MarkdownEditor::new().value("> quoted");
+        enter_at_end(&mut editor);
+        assert_eq!(editor.content(), "> quoted\n> ");
+
+        let mut editor = MarkdownEditor::new().value("> - item");
+        enter_at_end(&mut editor);
+        assert_eq!(editor.content(), "> - item\n> - ");
+
+        let mut editor = MarkdownEditor::new().value("plain");
+        enter_at_end(&mut editor);
+        assert_eq!(editor.content(), "plain\n");
+    }
+
+    #[test]
+    fn test_enter_on_empty_item_ends_list() {
+        let mut editor = MarkdownEditor::new().value("- a\n- ");
+        enter_at_end(&mut editor);
+        assert_eq!(editor.content(), "- a\n");
+        assert_eq!(editor.state.cursor, 4);
+
+        // Nested items outdent first
+        let mut editor = MarkdownEditor::new().value("- a\n  - ");
+        enter_at_end(&mut editor);
+        assert_eq!(editor.content(), "- a\n- ");
+        enter_at_end(&mut editor);
+        assert_eq!(editor.content(), "- a\n");
+
+        // One level at a time, by the parent marker's width
+        let mut editor = MarkdownEditor::new().value("1. a\n   - b\n     - ");
+        enter_at_end(&mut editor);
+        assert_eq!(editor.content(), "1. a\n   - b\n   - ");
+        enter_at_end(&mut editor);
+        assert_eq!(editor.content(), "1. a\n   - b\n- ");
+
+        let mut editor = MarkdownEditor::new().value("- é");
+        editor.state.select(2, 3);
+        editor.newline();
+        assert_eq!(editor.content(), "- \n- é");
+
+        let mut editor = MarkdownEditor::new().value("> quote\n> ");
+        enter_at_end(&mut editor);
+        assert_eq!(editor.content(), "> quote\n");
+    }
+
+    #[test]
+    fn test_autosave_after_idle_interval() {
+        let saved = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = saved.clone();
+        let mut editor = MarkdownEditor::new()
+            .value("Draft")
+            .auto_save(Duration::from_secs(5))
+            .on_change(move |content| sink.lock().unwrap().push(content.to_string()));
+        let start = Instant::now();
+
+        // Nothing changed yet
+        assert_eq!(editor.tick(start + Duration::from_secs(60)), None);
+
+        editor.state.cursor = 5;
+        editor.insert(" one");
+        assert_eq!(editor.tick(start), None);
+        assert_eq!(editor.tick(start + Duration::from_secs(4)), None);
+
+        // Typing again restarts the idle timer
+        editor.insert(" two");
+        assert_eq!(editor.tick(start + Duration::from_secs(4)), None);
+        assert_eq!(editor.tick(start + Duration::from_secs(8)), None);
+
+        let id = editor.tick(start + Duration::from_secs(9));
+        assert!(id.is_some());
+        assert_eq!(*saved.lock().unwrap(), ["Draft one two"]);
+        assert_eq!(editor.snapshots()[0].content, "Draft one two");
+
+        // Saved once per change
+        assert_eq!(editor.tick(start + Duration::from_secs(30)), None);
+    }
+
+    #[test]
+    fn test_autosave_skips_noop_edits() {
+        let mut editor = MarkdownEditor::new().value("Same").auto_save(Duration::from_secs(1));
+        let start = Instant::now();
+
+        editor.state.content.push('!');
+        editor.tick(start);
+        editor.state.content.pop();
+        editor.tick(start + Duration::from_millis(500));
+
+        assert_eq!(editor.tick(start + Duration::from_secs(10)), None);
+        assert!(editor.snapshots().is_empty());
+    }
+
+    #[test]
+    fn test_snapshots_pruned_and_restored() {
+        let mut editor = MarkdownEditor::new().auto_save(Duration::from_secs(1)).max_snapshots(2);
+        let start = Instant::now();
+
+        let mut ids = Vec::new();
+        for (i, text) in ["a", "b", "c"].into_iter().enumerate() {
+            let at = start + Duration::from_secs(10 * i as u64);
+            editor.insert(text);
+            editor.tick(at);
+            ids.extend(editor.tick(at + Duration::from_secs(1)));
+        }
+
+        assert_eq!(ids.len(), 3);
+        let kept: Vec<_> = editor.snapshots().iter().map(|s| s.id).collect();
+        assert_eq!(kept, ids[1..]);
+
+        assert!(!editor.restore_snapshot(ids[0]));
+        assert!(editor.restore_snapshot(ids[1]));
+        assert_eq!(editor.content(), "ab");
+    }
+
+    #[test]
+    fn test_paste_converts_html() {
+        let mut editor = MarkdownEditor::new().value("Links: ");
+        editor.state.cursor = 7;
+        editor.paste("Docs", Some("<a href=\"https://example.com\">Docs</a>"));
+        assert_eq!(editor.content(), "Links: [Docs](https://example.com)");
+        assert_eq!(editor.state.cursor, editor.content().len());
+
+        // No HTML on the clipboard, or none worth converting
+        let mut editor = MarkdownEditor::new();
+        editor.paste("a < b", None);
+        editor.paste(" plain", Some("<span></span>"));
+        assert_eq!(editor.content(), "a < b plain");
+
+        // Offsets past the end or inside a character are clamped
+        let mut editor = MarkdownEditor::new().value("über");
+        editor.state.select(1, 99);
+        editor.paste("x", None);
+        assert_eq!(editor.content(), "x");
+    }
+
+    #[test]
+    fn test_toolbar_with_multibyte_and_stale_selection() {
+        let mut editor = MarkdownEditor::new().value("café au lait");
+        // Ends inside the two-byte `é`
+        editor.state.select(0, 4);
+        editor.apply_toolbar(ToolbarItem::Bold);
+        assert_eq!(editor.content(), "**caf**é au lait");
+
+        // A selection left over from longer content is clamped to the end
+        let mut editor = MarkdownEditor::new().value("naïve");
+        editor.state.select(2, 40);
+        editor.apply_toolbar(ToolbarItem::Italic);
+        assert_eq!(editor.content(), "na_ïve_");
+
+        editor.state.select(50, 60);
+        editor.apply_toolbar(ToolbarItem::BulletList);
+        assert_eq!(editor.content(), "- na_ïve_");
+    }
+}
@@ -1,5 +1,7 @@
-//! Markdown extensions (footnotes, math, diagrams).
+//! Markdown extensions (footnotes, math, diagrams, emoji).
 
+use crate::parser::{slugify, BlockElement, InlineElement, MarkdownDocument};
+use crate::{MarkdownError, MarkdownResult};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -60,13 +62,134 @@ impl FootnoteDefinition {
     }
 }
 
+/// A footnote resolved against its references
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResolvedFootnote {
+    /// Number shown at references (1-based, in order of first reference)
+    pub number: usize,
+    /// Label from the source (`[^label]`)
+    pub label: String,
+    /// Plain text of the definition
+    pub content: String,
+    /// Anchor id of the footnote (`fn-label`)
+    pub id: String,
+    /// Anchor id of each reference in order, for back-links (`fnref-label`,
+    /// `fnref-label-2`, ...)
+    pub back_references: Vec<String>,
+}
+
+/// Problem found while resolving footnotes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FootnoteWarning {
+    /// Referenced but never defined
+    Undefined(String),
+    /// Defined but never referenced
+    Unreferenced(String),
+}
+
+/// Footnotes of a document, numbered and linked to their references
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FootnoteList {
+    /// Referenced, defined footnotes in number order
+    pub footnotes: Vec<ResolvedFootnote>,
+    /// Undefined and unreferenced footnotes
+    pub warnings: Vec<FootnoteWarning>,
+}
+
+impl FootnoteList {
+    /// Resolve the footnote references and definitions of a document
+    pub fn resolve(doc: &MarkdownDocument) -> Self {
+        let mut references = Vec::new();
+        collect_references(&doc.blocks, &mut references);
+
+        let mut list = Self::default();
+        for label in &references {
+            if list.get(label).is_some() || list.warnings.contains(&FootnoteWarning::Undefined(label.clone())) {
+                continue;
+            }
+            let Some(definition) = doc.footnotes.get(label) else {
+                list.warnings.push(FootnoteWarning::Undefined(label.clone()));
+                continue;
+            };
+
+            let mut content = String::new();
+            doc.extract_text(definition, &mut content);
+            let slug = slugify(label);
+            let count = references.iter().filter(|r| *r == label).count();
+            list.footnotes.push(ResolvedFootnote {
+                number: list.footnotes.len() + 1,
+                label: label.clone(),
+                content: content.trim_end().to_string(),
+                id: format!("fn-{}", slug),
+                back_references: (1..=count)
+                    .map(|n| if n == 1 { format!("fnref-{}", slug) } else { format!("fnref-{}-{}", slug, n) })
+                    .collect(),
+            });
+        }
+
+        for block in &doc.blocks {
+            if let BlockElement::FootnoteDefinition { label, .. } = block {
+                if !references.contains(label) {
+                    list.warnings.push(FootnoteWarning::Unreferenced(label.clone()));
+                }
+            }
+        }
+        list
+    }
+
+    /// Get a resolved footnote by label
+    pub fn get(&self, label: &str) -> Option<&ResolvedFootnote> {
+        self.footnotes.iter().find(|footnote| footnote.label == label)
+    }
+}
+
+/// Footnote labels in reference order (repeats included)
+fn collect_references(blocks: &[BlockElement], out: &mut Vec<String>) {
+    for block in blocks {
+        match block {
+            BlockElement::Heading { content, .. } | BlockElement::Paragraph(content) => inline_references(content, out),
+            BlockElement::Blockquote(inner) | BlockElement::FootnoteDefinition { content: inner, .. } => {
+                collect_references(inner, out)
+            }
+            BlockElement::List { items, .. } => {
+                for item in items {
+                    collect_references(&item.content, out);
+                    collect_references(item.nested_list.as_deref().map(std::slice::from_ref).unwrap_or_default(), out);
+                }
+            }
+            BlockElement::Table { header, rows, .. } => {
+                for cell in std::iter::once(header).chain(rows).flat_map(|row| &row.cells) {
+                    inline_references(&cell.content, out);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn inline_references(inlines: &[InlineElement], out: &mut Vec<String>) {
+    for inline in inlines {
+        match inline {
+            InlineElement::FootnoteReference(label) => out.push(label.clone()),
+            InlineElement::Strong(inner)
+            | InlineElement::Emphasis(inner)
+            | InlineElement::Strikethrough(inner)
+            | InlineElement::Link { content: inner, .. } => inline_references(inner, out),
+            _ => {}
+        }
+    }
+}
+
 /// Math block
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MathBlock {
     /// LaTeX content
     pub content: String,
     /// Display mode (block vs inline)
     pub display: bool,
+    /// Byte range of the math, delimiters included, in the source
+    #[serde(default)]
+    pub source_span: std::ops::Range<usize>,
 }
 
 impl MathBlock {
@@ -75,6 +198,7 @@ impl MathBlock {
         Self {
             content: content.into(),
             display: false,
+            source_span: 0..0,
         }
     }
 
@@ -83,6 +207,57 @@ impl MathBlock {
         Self {
             content: content.into(),
             display: true,
+            source_span: 0..0,
+        }
+    }
+
+    /// Set the source span
+    pub fn span(mut self, span: std::ops::Range<usize>) -> Self {
+        self.source_span = span;
+        self
+    }
+
+    /// Collect all math in a document, in source order
+    pub fn collect(doc: &MarkdownDocument) -> Vec<MathBlock> {
+        let mut out = Vec::new();
+        block_math(&doc.blocks, &mut out);
+        out
+    }
+}
+
+fn block_math(blocks: &[BlockElement], out: &mut Vec<MathBlock>) {
+    for block in blocks {
+        match block {
+            BlockElement::Math(math) => out.push(math.clone()),
+            BlockElement::Heading { content, .. } | BlockElement::Paragraph(content) => inline_math(content, out),
+            BlockElement::Blockquote(inner) | BlockElement::FootnoteDefinition { content: inner, .. } => {
+                block_math(inner, out)
+            }
+            BlockElement::List { items, .. } => {
+                for item in items {
+                    block_math(&item.content, out);
+                    block_math(item.nested_list.as_deref().map(std::slice::from_ref).unwrap_or_default(), out);
+                }
+            }
+            BlockElement::Table { header, rows, .. } => {
+                for cell in std::iter::once(header).chain(rows).flat_map(|row| &row.cells) {
+                    inline_math(&cell.content, out);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn inline_math(inlines: &[InlineElement], out: &mut Vec<MathBlock>) {
+    for inline in inlines {
+        match inline {
+            InlineElement::Math(math) => out.push(math.clone()),
+            InlineElement::Strong(inner)
+            | InlineElement::Emphasis(inner)
+            | InlineElement::Strikethrough(inner)
+            | InlineElement::Link { content: inner, .. } => inline_math(inner, out),
+            _ => {}
         }
     }
 }
@@ -110,6 +285,243 @@ pub trait DiagramRenderer: Send + Sync {
     fn render(&self, source: &str, diagram_type: DiagramType) -> Result<String, String>;
 }
 
+/// Flowchart layout direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FlowDirection {
+    /// Top to bottom (`TD` / `TB`)
+    TopDown,
+    /// Bottom to top (`BT`)
+    BottomUp,
+    /// Left to right (`LR`)
+    LeftRight,
+    /// Right to left (`RL`)
+    RightLeft,
+}
+
+/// Flowchart node shape
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NodeShape {
+    /// `A[label]` (also used for bare ids)
+    Rect,
+    /// `A(label)`
+    Round,
+    /// `A((label))`
+    Circle,
+    /// `A{label}`
+    Diamond,
+}
+
+/// Flowchart edge line style
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EdgeStyle {
+    /// `-->`
+    Arrow,
+    /// `---`
+    Line,
+    /// `-.->`
+    Dotted,
+    /// `==>`
+    Thick,
+}
+
+/// A flowchart node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FlowNode {
+    /// Node id used by edges
+    pub id: String,
+    /// Display label (the id when none is given)
+    pub label: String,
+    /// Shape
+    pub shape: NodeShape,
+}
+
+/// A flowchart edge
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FlowEdge {
+    /// Source node id
+    pub from: String,
+    /// Target node id
+    pub to: String,
+    /// Edge label
+    pub label: Option<String>,
+    /// Line style
+    pub style: EdgeStyle,
+}
+
+/// Node/edge model of a Mermaid flowchart
+///
+/// Supports `graph`/`flowchart` headers with a direction, node shapes
+/// (`[]`, `()`, `(())`, `{}`), chained edges (`A --> B --> C`), and edge
+/// labels (`-->|label|` or `-- label -->`). Subgraphs, styling, and other
+/// diagram types are reported as [`MarkdownError::DiagramError`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Flowchart {
+    /// Layout direction
+    pub direction: FlowDirection,
+    /// Nodes in order of first appearance
+    pub nodes: Vec<FlowNode>,
+    /// Edges in source order
+    pub edges: Vec<FlowEdge>,
+}
+
+impl Flowchart {
+    /// Parse Mermaid flowchart source
+    pub fn parse(source: &str) -> MarkdownResult<Self> {
+        let mut statements = source
+            .lines()
+            .map(|line| line.split("%%").next().unwrap_or_default())
+            .flat_map(|line| line.split(';'))
+            .map(str::trim)
+            .filter(|s| !s.is_empty());
+
+        let header = statements.next().ok_or_else(|| diagram_error("empty diagram"))?;
+        let mut chart = Self {
+            direction: parse_header(header)?,
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        };
+        for statement in statements {
+            chart.parse_statement(statement)?;
+        }
+        Ok(chart)
+    }
+
+    /// Get a node by id
+    pub fn node(&self, id: &str) -> Option<&FlowNode> {
+        self.nodes.iter().find(|node| node.id == id)
+    }
+
+    fn parse_statement(&mut self, statement: &str) -> MarkdownResult<()> {
+        let keyword = statement.split_whitespace().next().unwrap_or_default();
+        if matches!(keyword, "subgraph" | "end" | "style" | "classDef" | "class" | "click" | "linkStyle") {
+            return Err(diagram_error(format!("unsupported statement '{}'", keyword)));
+        }
+
+        let (mut from, mut rest) = self.parse_node(statement)?;
+        while !rest.is_empty() {
+            let (style, label, after_link) = parse_link(rest)?;
+            let (to, after_node) = self.parse_node(after_link)?;
+            self.edges.push(FlowEdge {
+                from,
+                to: to.clone(),
+                label,
+                style,
+            });
+            from = to;
+            rest = after_node;
+        }
+        Ok(())
+    }
+
+    /// Parse `id` with an optional shape, returning the id and the rest
+    fn parse_node<'a>(&mut self, input: &'a str) -> MarkdownResult<(String, &'a str)> {
+        let input = input.trim_start();
+        let id_len = input
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(input.len());
+        if id_len == 0 {
+            return Err(diagram_error(format!("expected node id at '{}'", input)));
+        }
+        let (id, rest) = input.split_at(id_len);
+
+        let shapes = [
+            ("((", "))", NodeShape::Circle),
+            ("(", ")", NodeShape::Round),
+            ("[", "]", NodeShape::Rect),
+            ("{", "}", NodeShape::Diamond),
+        ];
+        let mut shaped = None;
+        let mut rest = rest;
+        if let Some((open, close, shape)) = shapes.iter().find(|(open, _, _)| rest.starts_with(open)) {
+            let body = &rest[open.len()..];
+            let end = body
+                .find(close)
+                .ok_or_else(|| diagram_error(format!("unterminated '{}' for node '{}'", open, id)))?;
+            let label = body[..end].trim().trim_matches('"').to_string();
+            shaped = Some((label, *shape));
+            rest = &body[end + close.len()..];
+        }
+
+        match (self.nodes.iter_mut().find(|node| node.id == id), shaped) {
+            (Some(node), Some((label, shape))) => {
+                node.label = label;
+                node.shape = shape;
+            }
+            (Some(_), None) => {}
+            (None, shaped) => {
+                let (label, shape) = shaped.unwrap_or_else(|| (id.to_string(), NodeShape::Rect));
+                self.nodes.push(FlowNode {
+                    id: id.to_string(),
+                    label,
+                    shape,
+                });
+            }
+        }
+        Ok((id.to_string(), rest.trim_start()))
+    }
+}
+
+fn parse_header(header: &str) -> MarkdownResult<FlowDirection> {
+    let mut parts = header.split_whitespace();
+    match parts.next() {
+        Some("graph" | "flowchart") => {}
+        Some(other) => return Err(diagram_error(format!("unsupported diagram type '{}'", other))),
+        None => return Err(diagram_error("empty diagram")),
+    }
+    match parts.next() {
+        None | Some("TD" | "TB") => Ok(FlowDirection::TopDown),
+        Some("BT") => Ok(FlowDirection::BottomUp),
+        Some("LR") => Ok(FlowDirection::LeftRight),
+        Some("RL") => Ok(FlowDirection::RightLeft),
+        Some(other) => Err(diagram_error(format!("unknown direction '{}'", other))),
+    }
+}
+
+/// Parse a link like `-->`, `-->|label|`, or `-- label -->`
+fn parse_link(input: &str) -> MarkdownResult<(EdgeStyle, Option<String>, &str)> {
+    const ARROWS: [(&str, EdgeStyle); 6] = [
+        ("-.->", EdgeStyle::Dotted),
+        ("-.-", EdgeStyle::Dotted),
+        ("==>", EdgeStyle::Thick),
+        ("===", EdgeStyle::Thick),
+        ("-->", EdgeStyle::Arrow),
+        ("---", EdgeStyle::Line),
+    ];
+
+    let input = input.trim_start();
+    let plain = ARROWS.iter().find(|(arrow, _)| input.starts_with(arrow));
+    let (style, mut label, rest) = if let Some((arrow, style)) = plain {
+        (*style, None, &input[arrow.len()..])
+    } else if let Some(text) = input.strip_prefix("--").or_else(|| input.strip_prefix("==")) {
+        // Inline label: `-- text -->`
+        let (end, arrow, style) = ARROWS
+            .iter()
+            .filter_map(|(arrow, style)| text.find(arrow).map(|i| (i, arrow, *style)))
+            .min_by_key(|(i, _, _)| *i)
+            .ok_or_else(|| diagram_error(format!("unterminated edge label at '{}'", input)))?;
+        (style, Some(text[..end].trim().to_string()), &text[end + arrow.len()..])
+    } else {
+        return Err(diagram_error(format!("expected edge at '{}'", input)));
+    };
+
+    let mut rest = rest.trim_start();
+    if let Some(body) = rest.strip_prefix('|') {
+        let end = body
+            .find('|')
+            .ok_or_else(|| diagram_error("unterminated '|' edge label"))?;
+        label = Some(body[..end].trim().to_string());
+        rest = &body[end + 1..];
+    }
+    if rest.trim().is_empty() {
+        return Err(diagram_error("edge is missing a target node"));
+    }
+    Ok((style, label.filter(|l| !l.is_empty()), rest))
+}
+
+fn diagram_error(message: impl Into<String>) -> MarkdownError {
+    MarkdownError::DiagramError(message.into())
+}
+
 /// An abbreviation definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Abbreviation {
@@ -129,6 +541,108 @@ impl Abbreviation {
     }
 }
 
+/// Built-in shortcodes (GitHub names)
+const EMOJI: &[(&str, &str)] = &[
+    ("+1", "👍"),
+    ("-1", "👎"),
+    ("100", "💯"),
+    ("bug", "🐛"),
+    ("bulb", "💡"),
+    ("calendar", "📆"),
+    ("clap", "👏"),
+    ("construction", "🚧"),
+    ("coffee", "☕"),
+    ("memo", "📝"),
+    ("eyes", "👀"),
+    ("fire", "🔥"),
+    ("gear", "⚙️"),
+    ("heart", "❤️"),
+    ("hourglass", "⌛"),
+    ("information_source", "ℹ️"),
+    ("joy", "😂"),
+    ("key", "🔑"),
+    ("link", "🔗"),
+    ("lock", "🔒"),
+    ("mag", "🔍"),
+    ("package", "📦"),
+    ("pencil2", "✏️"),
+    ("point_right", "👉"),
+    ("pushpin", "📌"),
+    ("question", "❓"),
+    ("rocket", "🚀"),
+    ("smile", "😄"),
+    ("sparkles", "✨"),
+    ("star", "⭐"),
+    ("tada", "🎉"),
+    ("thinking", "🤔"),
+    ("thumbsdown", "👎"),
+    ("thumbsup", "👍"),
+    ("warning", "⚠️"),
+    ("wave", "👋"),
+    ("white_check_mark", "✅"),
+    ("wink", "😉"),
+    ("wrench", "🔧"),
+    ("x", "❌"),
+    ("zap", "⚡"),
+];
+
+/// Emoji shortcode table: the built-in set plus custom shortcodes
+#[derive(Debug, Clone, Default)]
+pub struct EmojiMap {
+    /// Custom shortcodes, checked before the built-in set
+    custom: HashMap<String, String>,
+}
+
+impl EmojiMap {
+    /// Create a map with the built-in shortcodes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a custom shortcode (without colons), overriding a built-in one
+    pub fn register(&mut self, shortcode: impl Into<String>, emoji: impl Into<String>) {
+        self.custom.insert(shortcode.into(), emoji.into());
+    }
+
+    /// Look up a shortcode (without colons)
+    pub fn get(&self, shortcode: &str) -> Option<&str> {
+        self.custom.get(shortcode).map(String::as_str).or_else(|| {
+            EMOJI
+                .iter()
+                .find(|(code, _)| *code == shortcode)
+                .map(|(_, emoji)| *emoji)
+        })
+    }
+
+    /// Replace known `:shortcode:`s in `text`; unknown ones are left as written
+    pub fn expand(&self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find(':') {
+            out.push_str(&rest[..start]);
+            rest = &rest[start..];
+            let code_len = rest[1..]
+                .find(|c: char| !(c.is_ascii_alphanumeric() || "_+-".contains(c)))
+                .unwrap_or(rest.len() - 1);
+            let emoji = Some(&rest[1..1 + code_len])
+                .filter(|code| !code.is_empty() && rest[1 + code_len..].starts_with(':'))
+                .and_then(|code| self.get(code));
+            match emoji {
+                Some(emoji) => {
+                    out.push_str(emoji);
+                    rest = &rest[code_len + 2..];
+                }
+                None => {
+                    out.push(':');
+                    rest = &rest[1..];
+                }
+            }
+        }
+        out.push_str(rest);
+        out
+    }
+}
+
 /// Extension registry
 #[derive(Debug, Clone, Default)]
 pub struct ExtensionRegistry {
@@ -171,3 +685,83 @@ impl ExtensionRegistry {
         self.footnotes.push(footnote);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    #[test]
+    fn test_emoji_shortcodes() {
+        let mut emoji = EmojiMap::new();
+        assert_eq!(emoji.expand("Launch :rocket: now"), "Launch 🚀 now");
+        assert_eq!(emoji.expand(":notreal: and 12:30:45"), ":notreal: and 12:30:45");
+        assert_eq!(emoji.expand("::rocket:::"), ":🚀::");
+
+        emoji.register("ferris", "🦀");
+        assert_eq!(emoji.expand(":ferris: :+1:"), "🦀 👍");
+    }
+
+    #[test]
+    fn test_resolve_footnotes() {
+        let doc = parse(concat!(
+            "Alpha[^b] beta[^a] gamma[^missing] and again[^b].\n\n",
+            "[^a]: First note.\n\n",
+            "[^b]: Second note.\n\n",
+            "[^unused]: Never cited.\n",
+        ))
+        .unwrap();
+        let list = FootnoteList::resolve(&doc);
+
+        // Numbered by first reference, not definition order
+        let labels: Vec<_> = list.footnotes.iter().map(|f| (f.number, f.label.as_str())).collect();
+        assert_eq!(labels, [(1, "b"), (2, "a")]);
+
+        let b = list.get("b").unwrap();
+        assert_eq!(b.content, "Second note.");
+        assert_eq!(b.id, "fn-b");
+        assert_eq!(b.back_references, ["fnref-b", "fnref-b-2"]);
+        assert_eq!(list.get("a").unwrap().back_references, ["fnref-a"]);
+
+        assert_eq!(
+            list.warnings,
+            [
+                FootnoteWarning::Undefined("missing".into()),
+                FootnoteWarning::Unreferenced("unused".into())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_flowchart() {
+        let chart = Flowchart::parse(
+            "graph TD; A[Start] -->|go| B{Decide?}\n  B -- yes --> C(Done)\n  B -.-> D --> A %% loop back",
+        )
+        .unwrap();
+
+        assert_eq!(chart.direction, FlowDirection::TopDown);
+        assert_eq!(chart.nodes.len(), 4);
+        assert_eq!(chart.edges.len(), 4);
+
+        assert_eq!(chart.node("A").unwrap().label, "Start");
+        assert_eq!(chart.node("B").unwrap().shape, NodeShape::Diamond);
+        assert_eq!(chart.node("C").unwrap().label, "Done");
+        assert_eq!(chart.node("D").unwrap().label, "D");
+
+        let labels: Vec<_> = chart.edges.iter().map(|e| e.label.as_deref()).collect();
+        assert_eq!(labels, [Some("go"), Some("yes"), None, None]);
+        assert_eq!(chart.edges[2].style, EdgeStyle::Dotted);
+        assert_eq!((chart.edges[3].from.as_str(), chart.edges[3].to.as_str()), ("D", "A"));
+    }
+
+    #[test]
+    fn test_unsupported_syntax() {
+        assert!(matches!(
+            Flowchart::parse("sequenceDiagram\nA->>B: hi"),
+            Err(MarkdownError::DiagramError(_))
+        ));
+        assert!(Flowchart::parse("graph LR\nsubgraph one\nA --> B\nend").is_err());
+        assert!(Flowchart::parse("graph LR\nA -->").is_err());
+        assert!(Flowchart::parse("graph LR\nA[oops --> B").is_err());
+    }
+}
@@ -0,0 +1,352 @@
+//! Front matter (YAML between `---`, TOML between `+++`) at the start of a document.
+
+use crate::{MarkdownError, MarkdownResult};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Front matter syntax
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FrontmatterFormat {
+    /// YAML between `---` lines
+    Yaml,
+    /// TOML between `+++` lines
+    Toml,
+}
+
+impl FrontmatterFormat {
+    fn delimiter(&self) -> &'static str {
+        match self {
+            FrontmatterFormat::Yaml => "---",
+            FrontmatterFormat::Toml => "+++",
+        }
+    }
+}
+
+/// Parsed front matter
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Frontmatter {
+    /// Syntax the front matter was written in
+    pub format: FrontmatterFormat,
+    /// Top-level keys and their values
+    pub fields: Map<String, Value>,
+    /// Source between the delimiters
+    pub raw: String,
+    /// Byte offset where the body starts
+    pub body_start: usize,
+}
+
+impl Frontmatter {
+    /// Parse front matter at the start of `content`
+    ///
+    /// Returns `None` if there is none, it is never closed, or it doesn't
+    /// parse; the caller then treats it as part of the body.
+    pub fn parse(content: &str) -> Option<Frontmatter> {
+        Self::try_parse(content).ok().flatten()
+    }
+
+    /// Parse front matter at the start of `content`, failing on a block that
+    /// looks like front matter but doesn't parse
+    ///
+    /// Returns `Ok(None)` if there is no closed block of `key: value` (or
+    /// TOML) lines, so a thematic break followed by prose isn't an error.
+    pub fn try_parse(content: &str) -> MarkdownResult<Option<Frontmatter>> {
+        let Some(first) = content.lines().next().map(str::trim_end) else {
+            return Ok(None);
+        };
+        let Some(format) = [FrontmatterFormat::Yaml, FrontmatterFormat::Toml]
+            .into_iter()
+            .find(|format| first == format.delimiter())
+        else {
+            return Ok(None);
+        };
+        let Some(newline) = content.find('\n') else {
+            return Ok(None);
+        };
+
+        let mut offset = newline + 1;
+        let raw_start = offset;
+        loop {
+            let line_end = content[offset..].find('\n').map_or(content.len(), |i| offset + i);
+            let line = content[offset..line_end].trim_end();
+            let closes = line == format.delimiter() || (format == FrontmatterFormat::Yaml && line == "...");
+            if closes {
+                // An empty block or one opened by a blank line is a thematic break, not front matter
+                let raw = &content[raw_start..offset];
+                let blank = raw.trim().is_empty() || raw.starts_with(['\n', '\r']);
+                if blank || !looks_like_fields(format, raw) {
+                    return Ok(None);
+                }
+                let fields = match format {
+                    FrontmatterFormat::Yaml => parse_yaml(raw),
+                    FrontmatterFormat::Toml => toml::from_str::<toml::Table>(raw)
+                        .ok()
+                        .and_then(|table| match serde_json::to_value(table) {
+                            Ok(Value::Object(fields)) => Some(fields),
+                            _ => None,
+                        }),
+                };
+                let Some(fields) = fields else {
+                    let message = format!("invalid {:?} front matter", format);
+                    return Err(MarkdownError::ParseError(message));
+                };
+                return Ok(Some(Frontmatter {
+                    format,
+                    fields,
+                    raw: raw.to_string(),
+                    body_start: (line_end + 1).min(content.len()),
+                }));
+            }
+            if line_end == content.len() {
+                return Ok(None);
+            }
+            offset = line_end + 1;
+        }
+    }
+
+    /// Get a field
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.fields.get(key)
+    }
+
+    /// Get a string field
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.get(key).and_then(Value::as_str)
+    }
+}
+
+/// Whether the first line of a block is a `key: value` (YAML) or
+/// `key = value` / `[table]` (TOML) line, rather than prose
+fn looks_like_fields(format: FrontmatterFormat, raw: &str) -> bool {
+    let mut lines = raw.lines().map(str::trim);
+    let Some(line) = lines.find(|line| !line.is_empty() && !line.starts_with('#')) else {
+        return false;
+    };
+    let bare_key = |key: &str| {
+        let key = unquote(key.trim());
+        !key.is_empty() && !key.contains(char::is_whitespace)
+    };
+    match format {
+        FrontmatterFormat::Yaml => line.split_once(':').is_some_and(|(key, value)| {
+            bare_key(key) && (value.is_empty() || value.starts_with(' '))
+        }),
+        FrontmatterFormat::Toml => {
+            line.starts_with('[') || line.split_once('=').is_some_and(|(key, _)| bare_key(key))
+        }
+    }
+}
+
+/// Parse the YAML subset used in front matter: nested maps, lists, scalars,
+/// and `|` / `>` block scalars. Anchors and flow maps are not supported.
+fn parse_yaml(source: &str) -> Option<Map<String, Value>> {
+    let lines: Vec<(usize, &str)> = source
+        .lines()
+        .map(|line| (line.len() - line.trim_start().len(), line.trim()))
+        .collect();
+    let mut pos = 0;
+    skip_insignificant(&lines, &mut pos);
+    let indent = lines.get(pos)?.0;
+    let map = yaml_map(&lines, &mut pos, indent)?;
+    skip_insignificant(&lines, &mut pos);
+    (pos == lines.len()).then_some(map)
+}
+
+/// Skip blank and comment lines
+fn skip_insignificant(lines: &[(usize, &str)], pos: &mut usize) {
+    while lines.get(*pos).is_some_and(|(_, line)| line.is_empty() || line.starts_with('#')) {
+        *pos += 1;
+    }
+}
+
+fn yaml_map(lines: &[(usize, &str)], pos: &mut usize, indent: usize) -> Option<Map<String, Value>> {
+    let mut map = Map::new();
+    loop {
+        skip_insignificant(lines, pos);
+        let Some(&(line_indent, line)) = lines.get(*pos) else {
+            break;
+        };
+        if line_indent < indent {
+            break;
+        }
+        if line_indent > indent || line.starts_with('-') {
+            return None;
+        }
+        let (key, value) = line.split_once(':')?;
+        if !value.is_empty() && !value.starts_with(' ') {
+            return None;
+        }
+        *pos += 1;
+
+        let value = value.trim();
+        let header = value.split(" #").next().unwrap_or(value).trim_end();
+        let chomp = header.strip_prefix(['|', '>']).filter(|chomp| ["", "-", "+"].contains(chomp));
+        if let Some(chomp) = chomp {
+            let text = yaml_block_scalar(lines, pos, indent, header.starts_with('>'));
+            let text = if chomp == "-" { text } else { text + "\n" };
+            map.insert(unquote(key.trim()).to_string(), Value::String(text));
+            continue;
+        }
+        skip_insignificant(lines, pos);
+        let value = match (value, lines.get(*pos)) {
+            ("", Some(&(next, item))) if item.starts_with('-') && next >= indent => yaml_list(lines, pos, next),
+            ("", Some(&(next, _))) if next > indent => Value::Object(yaml_map(lines, pos, next)?),
+            ("", _) => Value::Null,
+            (value, _) => yaml_scalar(value),
+        };
+        map.insert(unquote(key.trim()).to_string(), value);
+    }
+    Some(map)
+}
+
+/// Lines indented past `indent`, joined with newlines (literal) or spaces
+/// (folded, where a blank line becomes a newline); trailing blank lines are
+/// dropped
+fn yaml_block_scalar(
+    lines: &[(usize, &str)],
+    pos: &mut usize,
+    indent: usize,
+    folded: bool,
+) -> String {
+    let mut block: Vec<(usize, &str)> = Vec::new();
+    while let Some(&(line_indent, line)) = lines.get(*pos) {
+        if !line.is_empty() && line_indent <= indent {
+            break;
+        }
+        block.push((line_indent, line));
+        *pos += 1;
+    }
+    while block.last().is_some_and(|(_, line)| line.is_empty()) {
+        block.pop();
+    }
+
+    let base = block.iter().find(|(_, line)| !line.is_empty()).map_or(0, |&(i, _)| i);
+    let mut text = String::new();
+    for (i, &(line_indent, line)) in block.iter().enumerate() {
+        let previous = i.checked_sub(1).map(|i| block[i].1);
+        match previous {
+            Some(previous) if folded && (line.is_empty() || !previous.is_empty()) => {
+                text.push(if line.is_empty() { '\n' } else { ' ' })
+            }
+            Some(_) if !folded => text.push('\n'),
+            _ => {}
+        }
+        if !line.is_empty() {
+            text.push_str(&" ".repeat(line_indent.saturating_sub(base)));
+            text.push_str(line);
+        }
+    }
+    text
+}
+
+fn yaml_list(lines: &[(usize, &str)], pos: &mut usize, indent: usize) -> Value {
+    let mut items = Vec::new();
+    loop {
+        skip_insignificant(lines, pos);
+        let Some(&(line_indent, line)) = lines.get(*pos) else {
+            break;
+        };
+        let item = match line.strip_prefix('-') {
+            Some(item) if line_indent == indent && (item.is_empty() || item.starts_with(' ')) => item,
+            _ => break,
+        };
+        items.push(yaml_scalar(item.trim()));
+        *pos += 1;
+    }
+    Value::Array(items)
+}
+
+fn yaml_scalar(value: &str) -> Value {
+    if value.starts_with(['"', '\'']) {
+        return Value::String(unquote(value).to_string());
+    }
+    let value = value.split(" #").next().unwrap_or(value).trim();
+    if let Some(inner) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+        let items = inner.split(',').map(str::trim).filter(|item| !item.is_empty());
+        return Value::Array(items.map(yaml_scalar).collect());
+    }
+    match value {
+        "" | "~" | "null" => Value::Null,
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        _ => {
+            if let Ok(int) = value.parse::<i64>() {
+                Value::from(int)
+            } else if let Some(float) = value.parse::<f64>().ok().filter(|f| f.is_finite()) {
+                Value::from(float)
+            } else {
+                Value::String(value.to_string())
+            }
+        }
+    }
+}
+
+/// Strip matching quotes
+fn unquote(value: &str) -> &str {
+    for quote in ['"', '\''] {
+        if let Some(inner) = value.strip_prefix(quote).and_then(|v| v.strip_suffix(quote)) {
+            return inner;
+        }
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_yaml_front_matter() {
+        let content = "---\ntitle: \"Getting started\"\ndraft: false\nweight: 3\ntags: [intro, setup]\n\
+                       authors:\n  - ana\n  - li\nseo:\n  description: First steps # shown in search\n---\n# Body\n";
+        let front = Frontmatter::parse(content).unwrap();
+
+        assert_eq!(front.format, FrontmatterFormat::Yaml);
+        assert_eq!(front.get_str("title"), Some("Getting started"));
+        assert_eq!(front.get("draft"), Some(&Value::Bool(false)));
+        assert_eq!(front.get("weight"), Some(&Value::from(3)));
+        assert_eq!(front.get("tags"), Some(&serde_json::json!(["intro", "setup"])));
+        assert_eq!(front.get("authors"), Some(&serde_json::json!(["ana", "li"])));
+        assert_eq!(front.get("seo"), Some(&serde_json::json!({ "description": "First steps" })));
+        assert_eq!(&content[front.body_start..], "# Body\n");
+    }
+
+    #[test]
+    fn test_toml_front_matter() {
+        let front = Frontmatter::parse("+++\ntitle = \"Notes\"\n[extra]\ntoc = true\n+++\nBody").unwrap();
+
+        assert_eq!(front.format, FrontmatterFormat::Toml);
+        assert_eq!(front.get_str("title"), Some("Notes"));
+        assert_eq!(front.get("extra"), Some(&serde_json::json!({ "toc": true })));
+    }
+
+    #[test]
+    fn test_missing_or_invalid_front_matter() {
+        assert_eq!(Frontmatter::parse("# Just a heading\n"), None);
+        // Never closed
+        assert_eq!(Frontmatter::parse("---\ntitle: x\n\nBody\n"), None);
+        // Not key/value pairs
+        assert_eq!(Frontmatter::parse("---\nSome prose here\n---\n"), None);
+        assert_eq!(Frontmatter::parse("+++\nnot = = toml\n+++\n"), None);
+        // Horizontal rules
+        assert_eq!(Frontmatter::parse("---\n---\n"), None);
+
+        // Only blocks that look like front matter are errors
+        assert!(Frontmatter::try_parse("---\nSome prose here\n---\n").unwrap().is_none());
+        assert!(Frontmatter::try_parse("---\ntitle: [unclosed\nstill going\n---\n").is_err());
+        assert!(Frontmatter::try_parse("+++\nnot = = toml\n+++\n").is_err());
+    }
+
+    #[test]
+    fn test_yaml_block_scalars() {
+        let content = "---\ntitle: Notes\n\
+                       description: |\n  First line\n    indented # kept\n\n  Last line\n\
+                       summary: >-\n  Folded into\n  one line\n\n  New paragraph\n\
+                       draft: true\n---\nBody\n";
+        let front = Frontmatter::parse(content).unwrap();
+
+        let description = "First line\n  indented # kept\n\nLast line\n";
+        assert_eq!(front.get_str("description"), Some(description));
+        assert_eq!(front.get_str("summary"), Some("Folded into one line\nNew paragraph"));
+        assert_eq!(front.get("draft"), Some(&Value::Bool(true)));
+        assert_eq!(&content[front.body_start..], "Body\n");
+    }
+}
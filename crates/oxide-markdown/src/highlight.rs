@@ -1,6 +1,11 @@
 //! Syntax highlighting for code blocks.
 
+use crate::renderer::RenderedBlock;
 use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::ops::RangeInclusive;
 
 /// Token type for syntax highlighting
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -55,6 +60,12 @@ pub struct HighlightTheme {
     pub background: String,
     /// Default text color
     pub foreground: String,
+    /// Background of emphasized lines
+    pub line_highlight: String,
+    /// Background of added diff lines
+    pub diff_added: String,
+    /// Background of removed diff lines
+    pub diff_removed: String,
     /// Token styles
     pub styles: std::collections::HashMap<TokenType, TokenStyle>,
 }
@@ -65,6 +76,9 @@ impl Default for HighlightTheme {
             name: "default".to_string(),
             background: "#1E1E1E".to_string(),
             foreground: "#D4D4D4".to_string(),
+            line_highlight: "#2A2D2E".to_string(),
+            diff_added: "#1E3A29".to_string(),
+            diff_removed: "#4B1818".to_string(),
             styles: std::collections::HashMap::new(),
         }
     }
@@ -146,6 +160,136 @@ impl SyntaxHighlighter {
     }
 }
 
+/// Highlights code blocks on first request, e.g. when they scroll into view
+///
+/// Output is cached by language and a hash of the code, so repeated
+/// samples are only tokenized once.
+#[derive(Debug, Clone, Default)]
+pub struct LazyHighlighter {
+    /// Highlighter used on a cache miss
+    highlighter: SyntaxHighlighter,
+    /// Language and code of each registered block, indexed by block id
+    blocks: Vec<(String, String)>,
+    /// Output by (language, code hash)
+    cache: HashMap<(String, u64), String>,
+    /// Requests answered from the cache
+    cache_hits: usize,
+}
+
+impl LazyHighlighter {
+    /// Create a lazy highlighter
+    pub fn new(highlighter: SyntaxHighlighter) -> Self {
+        Self {
+            highlighter,
+            ..Default::default()
+        }
+    }
+
+    /// Register a code block and return its id
+    pub fn register(&mut self, language: impl Into<String>, code: impl Into<String>) -> usize {
+        self.blocks.push((language.into(), code.into()));
+        self.blocks.len() - 1
+    }
+
+    /// Register every code block with a language in `blocks`, in document order
+    pub fn register_blocks(&mut self, blocks: &[RenderedBlock]) -> Vec<usize> {
+        let mut ids = Vec::new();
+        for block in blocks {
+            match block {
+                RenderedBlock::CodeBlock {
+                    language: Some(language),
+                    code,
+                    ..
+                } => ids.push(self.register(language.as_str(), code.as_str())),
+                RenderedBlock::BlockQuote(inner) => ids.extend(self.register_blocks(inner)),
+                RenderedBlock::List { items, .. } => {
+                    for item in items {
+                        ids.extend(self.register_blocks(item));
+                    }
+                }
+                _ => {}
+            }
+        }
+        ids
+    }
+
+    /// Highlight a block, reusing cached output; `None` for an unknown id
+    pub fn request_highlight(&mut self, block_id: usize) -> Option<&str> {
+        let (language, code) = self.blocks.get(block_id)?;
+        let key = (language.clone(), code_hash(code));
+        if self.cache.contains_key(&key) {
+            self.cache_hits += 1;
+        }
+        let highlighter = &self.highlighter;
+        let output = self
+            .cache
+            .entry(key)
+            .or_insert_with(|| highlighter.highlight(code, language));
+        Some(output.as_str())
+    }
+
+    /// Output for a block if it was already highlighted, without tokenizing
+    pub fn highlighted(&self, block_id: usize) -> Option<&str> {
+        let (language, code) = self.blocks.get(block_id)?;
+        self.cache.get(&(language.clone(), code_hash(code))).map(String::as_str)
+    }
+
+    /// Number of requests answered from the cache
+    pub fn cache_hits(&self) -> usize {
+        self.cache_hits
+    }
+
+    /// Number of distinct highlighted (language, code) pairs
+    pub fn cache_len(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Forget registered blocks, keeping the cache for the next document
+    pub fn clear_blocks(&mut self) {
+        self.blocks.clear();
+    }
+}
+
+fn code_hash(code: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    code.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Diff classification of a code line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DiffKind {
+    /// `+` line
+    Added,
+    /// `-` line
+    Removed,
+}
+
+impl DiffKind {
+    /// Gutter marker for this kind
+    pub fn marker(&self) -> char {
+        match self {
+            DiffKind::Added => '+',
+            DiffKind::Removed => '-',
+        }
+    }
+}
+
+/// A styled line of a code block
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CodeLine {
+    /// Line number (1-based)
+    pub number: usize,
+    /// Line text (without the diff marker)
+    pub text: String,
+    /// Whether the line is emphasized
+    pub emphasized: bool,
+    /// Diff classification, if the block is a diff
+    pub diff: Option<DiffKind>,
+    /// Line background, if any
+    pub background: Option<String>,
+}
+
 /// Code block view
 #[derive(Debug, Clone, Default)]
 pub struct CodeBlockView {
@@ -159,6 +303,10 @@ pub struct CodeBlockView {
     pub copy_button: bool,
     /// Filename
     pub filename: Option<String>,
+    /// Emphasized line ranges (1-based, inclusive)
+    pub highlighted_lines: Vec<RangeInclusive<usize>>,
+    /// Treat `+`/`-` lines as diff additions/removals
+    pub diff: bool,
 }
 
 impl CodeBlockView {
@@ -196,4 +344,158 @@ impl CodeBlockView {
         self.filename = Some(name.into());
         self
     }
+
+    /// Emphasize a range of lines (1-based, inclusive)
+    pub fn highlight_lines(mut self, lines: RangeInclusive<usize>) -> Self {
+        self.highlighted_lines.push(lines);
+        self
+    }
+
+    /// Classify `+`/`-` lines as diff additions/removals
+    pub fn diff(mut self, diff: bool) -> Self {
+        self.diff = diff;
+        self
+    }
+
+    /// Apply a fence info string like `rust {1,3-5}`, `diff`, or `rust diff`
+    pub fn metadata(mut self, info: &str) -> Self {
+        for part in info.split_whitespace() {
+            if part == "diff" {
+                self.diff = true;
+                if self.language.is_empty() {
+                    self.language = part.to_string();
+                }
+            } else if let Some(spec) = part.strip_prefix('{').and_then(|p| p.strip_suffix('}')) {
+                self.highlighted_lines.extend(parse_line_spec(spec));
+            } else if self.language.is_empty() {
+                self.language = part.to_string();
+            }
+        }
+        self
+    }
+
+    /// Split the code into styled lines
+    pub fn lines(&self, theme: &HighlightTheme) -> Vec<CodeLine> {
+        let diff = self.diff || self.language == "diff";
+        self.code
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                let number = i + 1;
+                let kind = if diff { diff_kind(line) } else { None };
+                let text = if kind.is_some() { &line[1..] } else { line };
+                let emphasized = self.highlighted_lines.iter().any(|r| r.contains(&number));
+                let background = match kind {
+                    Some(DiffKind::Added) => Some(theme.diff_added.clone()),
+                    Some(DiffKind::Removed) => Some(theme.diff_removed.clone()),
+                    None if emphasized => Some(theme.line_highlight.clone()),
+                    None => None,
+                };
+                CodeLine {
+                    number,
+                    text: text.to_string(),
+                    emphasized,
+                    diff: kind,
+                    background,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Classify a diff line; `+++`/`---` file headers are context
+fn diff_kind(line: &str) -> Option<DiffKind> {
+    if line.starts_with("+++") || line.starts_with("---") {
+        None
+    } else if line.starts_with('+') {
+        Some(DiffKind::Added)
+    } else if line.starts_with('-') {
+        Some(DiffKind::Removed)
+    } else {
+        None
+    }
+}
+
+/// Parse `1,3-5` into line ranges, skipping malformed parts
+fn parse_line_spec(spec: &str) -> Vec<RangeInclusive<usize>> {
+    spec.split(',')
+        .filter_map(|part| {
+            let part = part.trim();
+            match part.split_once('-') {
+                Some((start, end)) => Some(start.trim().parse().ok()?..=end.trim().parse().ok()?),
+                None => part.parse().ok().map(|n| n..=n),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highlighted_lines() {
+        let code = "fn main() {\n    let a = 1;\n    let b = 2;\n    let c = a + b;\n    println!(\"{}\", c);\n}";
+        let theme = HighlightTheme::default();
+        let lines = CodeBlockView::new().language("rust").code(code).highlight_lines(3..=5).lines(&theme);
+
+        assert_eq!(lines.len(), 6);
+        let emphasized: Vec<_> = lines.iter().filter(|l| l.emphasized).map(|l| l.number).collect();
+        assert_eq!(emphasized, [3, 4, 5]);
+        assert_eq!(lines[2].background.as_deref(), Some(theme.line_highlight.as_str()));
+        assert_eq!(lines[1].background, None);
+        assert!(lines.iter().all(|l| l.diff.is_none()));
+
+        // Same via fence metadata
+        let from_info = CodeBlockView::new().metadata("rust {3-5}").code(code).lines(&theme);
+        assert_eq!(from_info, lines);
+
+        // Huge ranges are not expanded
+        let all = CodeBlockView::new()
+            .metadata("rust {2,1-18446744073709551615}")
+            .code(code)
+            .lines(&theme);
+        assert!(all.iter().all(|l| l.emphasized));
+    }
+
+    #[test]
+    fn test_lazy_highlight_on_request() {
+        let content = "```rust\nlet a = 1;\n```\n\n> ```rust\n> let a = 1;\n> ```\n\n```js\nx<y\n```\n";
+        let blocks = crate::Markdown::new(content).lazy_highlight(true).render();
+        assert!(matches!(&blocks[0], RenderedBlock::CodeBlock { highlighted: None, .. }));
+
+        let mut lazy = LazyHighlighter::new(SyntaxHighlighter::new());
+        let ids = lazy.register_blocks(&blocks);
+        assert_eq!(ids, [0, 1, 2]);
+        assert!(ids.iter().all(|id| lazy.highlighted(*id).is_none()));
+        assert_eq!(lazy.cache_len(), 0);
+
+        assert_eq!(lazy.request_highlight(2), Some("x&lt;y\n"));
+        assert!(lazy.highlighted(0).is_none());
+        assert_eq!(lazy.cache_hits(), 0);
+
+        // Block 1 has the same language and code as block 0
+        lazy.request_highlight(0);
+        assert!(lazy.highlighted(1).is_some());
+        lazy.request_highlight(1);
+        lazy.request_highlight(0);
+        assert_eq!(lazy.cache_hits(), 2);
+        assert_eq!(lazy.cache_len(), 2);
+
+        assert_eq!(lazy.request_highlight(3), None);
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        let code = "--- a/lib.rs\n+++ b/lib.rs\n fn keep() {}\n-fn old() {}\n+fn new() {}";
+        let theme = HighlightTheme::default();
+        let lines = CodeBlockView::new().metadata("diff").code(code).lines(&theme);
+
+        let kinds: Vec<_> = lines.iter().map(|l| l.diff).collect();
+        assert_eq!(kinds, [None, None, None, Some(DiffKind::Removed), Some(DiffKind::Added)]);
+        assert_eq!(lines[3].text, "fn old() {}");
+        assert_eq!(lines[4].background.as_deref(), Some(theme.diff_added.as_str()));
+        assert_eq!(lines[4].diff.map(|d| d.marker()), Some('+'));
+        assert_eq!(lines[2].background, None);
+    }
 }
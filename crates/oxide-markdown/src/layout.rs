@@ -0,0 +1,389 @@
+//! Markdown to layout tree conversion.
+//!
+//! Builds an `oxide-layout` subtree from a parsed [`MarkdownDocument`] so
+//! markdown can be dropped straight into a UI. Text is measured with the
+//! text system; colors and sizes come from the theme.
+
+use crate::parser::{BlockElement, InlineElement, ListType, MarkdownDocument, TableRow};
+use crate::theme::ThemeConfig;
+use crate::HeadingLevel;
+use oxide_layout::{Dimension, LayoutTree, NodeId, NodeVisual, Style, StyleBuilder};
+use oxide_render::Color;
+use oxide_text::TextSystem;
+use std::collections::HashMap;
+
+/// What a layout node represents
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkdownNodeKind {
+    /// Root of the document
+    Document,
+    /// Heading text
+    Heading(HeadingLevel),
+    /// Paragraph text
+    Paragraph,
+    /// Code block container
+    CodeBlock,
+    /// Blockquote container
+    Blockquote,
+    /// List container
+    List,
+    /// List item row (marker + content)
+    ListItem,
+    /// Bullet or number of a list item
+    ListMarker,
+    /// Checkbox of a task list item
+    Checkbox { checked: bool },
+    /// Table container
+    Table,
+    /// Table row
+    TableRow,
+    /// Table cell text
+    TableCell,
+    /// Horizontal rule
+    HorizontalRule,
+    /// Display math (TeX source)
+    Math,
+    /// Text inside a container (e.g. code block contents)
+    Text,
+}
+
+/// Measured text to draw at a layout node
+#[derive(Debug, Clone)]
+pub struct LayoutText {
+    /// Node the text belongs to
+    pub node: NodeId,
+    /// Plain text content
+    pub content: String,
+    /// Font size in pixels
+    pub font_size: f32,
+    /// Font family
+    pub font_family: String,
+    /// Text color (RGBA 0-1)
+    pub color: [f32; 4],
+}
+
+/// Result of laying out a document
+#[derive(Debug, Clone)]
+pub struct MarkdownLayout {
+    /// Root node of the markdown subtree
+    pub root: NodeId,
+    /// Kind of every node in the subtree
+    pub kinds: HashMap<NodeId, MarkdownNodeKind>,
+    /// Text runs, in document order
+    pub texts: Vec<LayoutText>,
+}
+
+impl MarkdownLayout {
+    /// Get the kind of a node
+    pub fn kind(&self, node: NodeId) -> Option<MarkdownNodeKind> {
+        self.kinds.get(&node).copied()
+    }
+
+    /// Get the text drawn at a node
+    pub fn text(&self, node: NodeId) -> Option<&LayoutText> {
+        self.texts.iter().find(|text| text.node == node)
+    }
+}
+
+/// Builds layout nodes for a document
+pub struct LayoutBuilder<'a> {
+    tree: &'a mut LayoutTree,
+    text_system: &'a mut TextSystem,
+    theme: ThemeConfig,
+    kinds: HashMap<NodeId, MarkdownNodeKind>,
+    texts: Vec<LayoutText>,
+}
+
+impl<'a> LayoutBuilder<'a> {
+    /// Create a builder that adds nodes to `tree`
+    pub fn new(tree: &'a mut LayoutTree, text_system: &'a mut TextSystem, theme: ThemeConfig) -> Self {
+        Self {
+            tree,
+            text_system,
+            theme,
+            kinds: HashMap::new(),
+            texts: Vec::new(),
+        }
+    }
+
+    /// Build the subtree for a document
+    pub fn build(mut self, doc: &MarkdownDocument) -> MarkdownLayout {
+        let children = self.blocks(doc, &doc.blocks);
+        let style = StyleBuilder::new().flex_column().gap(self.block_gap()).build();
+        let visual = NodeVisual::default().with_background(color(&self.theme.colors.background));
+        let root = self.tree.new_visual_node_with_children(style, visual, &children);
+        self.kinds.insert(root, MarkdownNodeKind::Document);
+
+        MarkdownLayout {
+            root,
+            kinds: self.kinds,
+            texts: self.texts,
+        }
+    }
+
+    fn blocks(&mut self, doc: &MarkdownDocument, blocks: &[BlockElement]) -> Vec<NodeId> {
+        blocks.iter().filter_map(|block| self.block(doc, block)).collect()
+    }
+
+    fn block(&mut self, doc: &MarkdownDocument, block: &BlockElement) -> Option<NodeId> {
+        let typography = &self.theme.typography;
+        let (base, body_font) = (typography.base_size, typography.body_font.clone());
+        let colors = self.theme.colors.clone();
+
+        let node = match block {
+            BlockElement::Heading { level, content, .. } => {
+                let size = heading_size(base, typography.heading_scale, *level);
+                let text = inline_text(doc, content);
+                self.text_node(text, size, body_font, &colors.heading, MarkdownNodeKind::Heading(*level))
+            }
+            BlockElement::Paragraph(content) => {
+                let text = inline_text(doc, content);
+                self.text_node(text, base, body_font, &colors.text, MarkdownNodeKind::Paragraph)
+            }
+            BlockElement::CodeBlock { content, .. } => {
+                let code_font = self.theme.typography.code_font.clone();
+                let code = content.trim_end_matches('\n').to_string();
+                let text = self.text_node(code, base * 0.875, code_font, &colors.code_text, MarkdownNodeKind::Text);
+                let style = StyleBuilder::new().flex_column().padding(base).build();
+                let visual = NodeVisual::default()
+                    .with_background(color(&colors.code_background))
+                    .with_radius(6.0);
+                self.container(style, visual, &[text], MarkdownNodeKind::CodeBlock)
+            }
+            BlockElement::Blockquote(inner) => {
+                let children = self.blocks(doc, inner);
+                let style = StyleBuilder::new()
+                    .flex_column()
+                    .gap(self.block_gap())
+                    .padding_xy(base, base * 0.25)
+                    .build();
+                let visual = NodeVisual::default().with_border(color(&colors.blockquote_border), 1.0);
+                self.container(style, visual, &children, MarkdownNodeKind::Blockquote)
+            }
+            BlockElement::List { list_type, items } => {
+                let rows: Vec<NodeId> = items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, item)| {
+                        let marker = match (item.checked, list_type) {
+                            (Some(checked), _) => self.checkbox(checked),
+                            (None, ListType::Unordered) => {
+                                let bullet = "•".to_string();
+                                self.text_node(bullet, base, body_font.clone(), &colors.text, MarkdownNodeKind::ListMarker)
+                            }
+                            (None, ListType::Ordered(start)) => {
+                                let number = format!("{}.", start + i as u64);
+                                self.text_node(number, base, body_font.clone(), &colors.text, MarkdownNodeKind::ListMarker)
+                            }
+                        };
+
+                        let mut content = self.blocks(doc, &item.content);
+                        if let Some(nested) = &item.nested_list {
+                            content.extend(self.block(doc, nested));
+                        }
+                        let style = StyleBuilder::new().flex_column().flex_grow(1.0).gap(base * 0.25).build();
+                        let body = self.tree.new_node_with_children(style, &content);
+
+                        let style = StyleBuilder::new().flex_row().gap(base * 0.5).build();
+                        self.container(style, NodeVisual::default(), &[marker, body], MarkdownNodeKind::ListItem)
+                    })
+                    .collect();
+                let style = StyleBuilder::new().flex_column().gap(base * 0.25).padding_xy(base, 0.0).build();
+                self.container(style, NodeVisual::default(), &rows, MarkdownNodeKind::List)
+            }
+            BlockElement::Table { header, rows, .. } => {
+                let rows: Vec<NodeId> = std::iter::once(header)
+                    .chain(rows)
+                    .map(|row| self.table_row(doc, row))
+                    .collect();
+                let style = StyleBuilder::new().flex_column().build();
+                let visual = NodeVisual::default().with_border(color(&colors.border), 1.0);
+                self.container(style, visual, &rows, MarkdownNodeKind::Table)
+            }
+            BlockElement::Math(math) => {
+                let code_font = self.theme.typography.code_font.clone();
+                let tex = math.content.trim().to_string();
+                self.text_node(tex, base, code_font, &colors.text, MarkdownNodeKind::Math)
+            }
+            BlockElement::HorizontalRule => {
+                let style = StyleBuilder::new().width_percent(1.0).height(1.0).build();
+                let visual = NodeVisual::default().with_background(color(&colors.border));
+                self.container(style, visual, &[], MarkdownNodeKind::HorizontalRule)
+            }
+            BlockElement::Html(_) | BlockElement::FootnoteDefinition { .. } | BlockElement::DefinitionList { .. } => {
+                return None
+            }
+        };
+        Some(node)
+    }
+
+    fn table_row(&mut self, doc: &MarkdownDocument, row: &TableRow) -> NodeId {
+        let base = self.theme.typography.base_size;
+        let font = self.theme.typography.body_font.clone();
+        let text_color = self.theme.colors.text.clone();
+
+        let cells: Vec<NodeId> = row
+            .cells
+            .iter()
+            .map(|cell| {
+                let text = inline_text(doc, &cell.content);
+                let node = self.text_node(text, base, font.clone(), &text_color, MarkdownNodeKind::TableCell);
+                let mut style = self.tree.get_style(node).cloned().unwrap_or_default();
+                style.flex_grow = 1.0;
+                style.flex_basis = Dimension::Length(0.0);
+                self.tree.set_style(node, style);
+                node
+            })
+            .collect();
+
+        let style = StyleBuilder::new().flex_row().gap(base).padding_xy(base * 0.5, base * 0.25).build();
+        self.container(style, NodeVisual::default(), &cells, MarkdownNodeKind::TableRow)
+    }
+
+    /// Create a leaf node sized to its measured text
+    fn text_node(
+        &mut self,
+        content: String,
+        font_size: f32,
+        font_family: String,
+        text_color: &str,
+        kind: MarkdownNodeKind,
+    ) -> NodeId {
+        let (width, height) = self.text_system.measure_text(&content, font_size);
+        let style = StyleBuilder::new().size(width.ceil(), height.ceil()).flex_shrink(0.0).build();
+        let node = self.tree.new_node(style);
+
+        self.kinds.insert(node, kind);
+        self.texts.push(LayoutText {
+            node,
+            content,
+            font_size,
+            font_family,
+            color: color(text_color),
+        });
+        node
+    }
+
+    /// Create a box sized to the body text, filled when checked
+    fn checkbox(&mut self, checked: bool) -> NodeId {
+        let size = self.theme.typography.base_size * 0.875;
+        let style = StyleBuilder::new().size(size, size).flex_shrink(0.0).build();
+        let mut visual = NodeVisual::default()
+            .with_border(color(&self.theme.colors.border), 1.0)
+            .with_radius(3.0);
+        if checked {
+            visual = visual.with_background(color(&self.theme.colors.link));
+        }
+        self.container(style, visual, &[], MarkdownNodeKind::Checkbox { checked })
+    }
+
+    fn container(
+        &mut self,
+        style: Style,
+        visual: NodeVisual,
+        children: &[NodeId],
+        kind: MarkdownNodeKind,
+    ) -> NodeId {
+        let node = self.tree.new_visual_node_with_children(style, visual, children);
+        self.kinds.insert(node, kind);
+        node
+    }
+
+    fn block_gap(&self) -> f32 {
+        self.theme.typography.base_size * 0.75
+    }
+}
+
+/// Font size for a heading: `base * scale^n`, larger for higher levels
+fn heading_size(base: f32, scale: f32, level: HeadingLevel) -> f32 {
+    base * scale.powi(4 - level.level().min(4) as i32)
+}
+
+fn inline_text(doc: &MarkdownDocument, inlines: &[InlineElement]) -> String {
+    let mut text = String::new();
+    doc.extract_inline_text(inlines, &mut text);
+    text
+}
+
+fn color(hex: &str) -> [f32; 4] {
+    Color::from_hex(hex).unwrap_or(Color::BLACK).to_array()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::Markdown;
+
+    #[test]
+    fn test_document_structure() {
+        let markdown = Markdown::new("# Title\n\nSome *intro* text.\n\n- one\n- two\n");
+        let mut tree = LayoutTree::new();
+        let mut text_system = TextSystem::new();
+        let layout = markdown.layout(&mut tree, &mut text_system);
+
+        assert_eq!(layout.kind(layout.root), Some(MarkdownNodeKind::Document));
+        let blocks = tree.children(layout.root);
+        let kinds: Vec<_> = blocks.iter().map(|n| layout.kind(*n).unwrap()).collect();
+        assert_eq!(
+            kinds,
+            [
+                MarkdownNodeKind::Heading(HeadingLevel::H1),
+                MarkdownNodeKind::Paragraph,
+                MarkdownNodeKind::List
+            ]
+        );
+
+        assert_eq!(layout.text(blocks[0]).unwrap().content, "Title");
+        assert_eq!(layout.text(blocks[1]).unwrap().content, "Some intro text.");
+        assert!(layout.text(blocks[0]).unwrap().font_size > layout.text(blocks[1]).unwrap().font_size);
+
+        let items = tree.children(blocks[2]);
+        assert_eq!(items.len(), 2);
+        assert_eq!(layout.kind(items[0]), Some(MarkdownNodeKind::ListItem));
+        let marker = tree.children(items[0])[0];
+        assert_eq!(layout.text(marker).unwrap().content, "•");
+        let body = tree.children(items[0])[1];
+        assert_eq!(layout.text(tree.children(body)[0]).unwrap().content, "one");
+    }
+
+    #[test]
+    fn test_task_list_checkboxes() {
+        let markdown = Markdown::new("- [x] done\n- [ ] todo\n");
+        let mut tree = LayoutTree::new();
+        let mut text_system = TextSystem::new();
+        let layout = markdown.layout(&mut tree, &mut text_system);
+
+        let list = tree.children(layout.root)[0];
+        let markers: Vec<_> = tree
+            .children(list)
+            .iter()
+            .map(|item| layout.kind(tree.children(*item)[0]).unwrap())
+            .collect();
+        assert_eq!(
+            markers,
+            [
+                MarkdownNodeKind::Checkbox { checked: true },
+                MarkdownNodeKind::Checkbox { checked: false }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_code_and_quote_styling() {
+        let markdown = Markdown::new("> quoted\n\n```rust\nfn main() {}\n```\n").theme(crate::MarkdownTheme::Dark);
+        let mut tree = LayoutTree::new();
+        let mut text_system = TextSystem::new();
+        let root = markdown.to_layout_tree(&mut tree, &mut text_system);
+
+        let blocks = tree.children(root);
+        assert_eq!(blocks.len(), 2);
+
+        let quote = tree.get_visual(blocks[0]).unwrap();
+        assert!(quote.border_color.is_some());
+
+        let code = tree.get_visual(blocks[1]).unwrap();
+        let dark = ThemeConfig::from_preset(crate::MarkdownTheme::Dark);
+        assert_eq!(code.background, Some(color(&dark.colors.code_background)));
+        assert_eq!(tree.children(blocks[1]).len(), 1);
+    }
+}
@@ -72,32 +72,42 @@
 
 pub mod editor;
 pub mod extensions;
+pub mod frontmatter;
 pub mod highlight;
+pub mod layout;
 pub mod parser;
+pub mod paste;
 pub mod renderer;
+pub mod sanitize;
 pub mod theme;
 pub mod toc;
 pub mod toolbar;
 
 // Re-export main types
 pub use editor::{
-    AutoSaveConfig, EditorMode, EditorState, KeyboardShortcut, MarkdownEditor, MarkdownEditorConfig,
+    AutoSaveConfig, ChangeCallback, EditorMode, EditorSnapshot, EditorState, KeyboardShortcut,
+    MarkdownEditor, MarkdownEditorConfig,
 };
 pub use extensions::{
-    Abbreviation, DiagramRenderer, DiagramType, Extension, ExtensionRegistry, Footnote,
-    FootnoteDefinition, MathBlock, MathRenderer,
+    Abbreviation, DiagramRenderer, DiagramType, EdgeStyle, EmojiMap, Extension, ExtensionRegistry,
+    FlowDirection, FlowEdge, FlowNode, Flowchart, Footnote, FootnoteDefinition, FootnoteList, FootnoteWarning,
+    MathBlock, MathRenderer, NodeShape, ResolvedFootnote,
 };
+pub use frontmatter::{Frontmatter, FrontmatterFormat};
 pub use highlight::{
-    CodeBlockView, HighlightTheme, LanguageDefinition, LanguageRegistry, SyntaxHighlighter,
-    TokenStyle, TokenType,
+    CodeBlockView, CodeLine, DiffKind, HighlightTheme, LanguageDefinition, LanguageRegistry,
+    LazyHighlighter, SyntaxHighlighter, TokenStyle, TokenType,
 };
+pub use layout::{LayoutText, MarkdownLayout, MarkdownNodeKind};
 pub use parser::{
-    BlockElement, InlineElement, ListItem, ListType, MarkdownDocument, MarkdownParser, ParseError,
-    TableAlignment, TableCell, TableRow,
+    BlockElement, DocStats, InlineElement, ListItem, ListType, MarkdownDocument, MarkdownParser,
+    ParseError, SlugGenerator, TableAlignment, TableCell, TableRow, TaskMarker,
 };
 pub use renderer::{
-    Markdown, MarkdownRenderer, RenderedBlock, RenderedElement, RenderedInline, RenderOptions,
+    IncrementalRenderer, LinkResolver, Markdown, MarkdownRenderer, RenderedBlock, RenderedElement,
+    RenderedInline, RenderOptions,
 };
+pub use sanitize::HtmlSanitizer;
 pub use theme::{MarkdownTheme, ThemeColors, ThemeConfig, TypographyTokens};
 pub use toc::{TocConfig, TocEntry, TocRenderer, TableOfContents};
 pub use toolbar::{Toolbar, ToolbarConfig, ToolbarItem, ToolbarAction};
@@ -148,24 +158,29 @@ pub type MarkdownResult<T> = Result<T, MarkdownError>;
 /// Convenient re-exports for common usage
 pub mod prelude {
     pub use crate::editor::{
-        AutoSaveConfig, EditorMode, EditorState, KeyboardShortcut, MarkdownEditor,
-        MarkdownEditorConfig,
+        AutoSaveConfig, ChangeCallback, EditorMode, EditorSnapshot, EditorState, KeyboardShortcut,
+        MarkdownEditor, MarkdownEditorConfig,
     };
     pub use crate::extensions::{
-        Abbreviation, DiagramRenderer, DiagramType, Extension, ExtensionRegistry, Footnote,
-        FootnoteDefinition, MathBlock, MathRenderer,
+        Abbreviation, DiagramRenderer, DiagramType, EdgeStyle, EmojiMap, Extension, ExtensionRegistry,
+        FlowDirection, FlowEdge, FlowNode, Flowchart, Footnote, FootnoteDefinition, FootnoteList,
+        FootnoteWarning, MathBlock, MathRenderer, NodeShape, ResolvedFootnote,
     };
+    pub use crate::frontmatter::{Frontmatter, FrontmatterFormat};
     pub use crate::highlight::{
-        CodeBlockView, HighlightTheme, LanguageDefinition, LanguageRegistry, SyntaxHighlighter,
-        TokenStyle, TokenType,
+        CodeBlockView, CodeLine, DiffKind, HighlightTheme, LanguageDefinition, LanguageRegistry,
+        LazyHighlighter, SyntaxHighlighter, TokenStyle, TokenType,
     };
+    pub use crate::layout::{LayoutText, MarkdownLayout, MarkdownNodeKind};
     pub use crate::parser::{
-        BlockElement, InlineElement, ListItem, ListType, MarkdownDocument, MarkdownParser,
-        ParseError, TableAlignment, TableCell, TableRow,
+        BlockElement, DocStats, InlineElement, ListItem, ListType, MarkdownDocument,
+        MarkdownParser, ParseError, SlugGenerator, TableAlignment, TableCell, TableRow, TaskMarker,
     };
     pub use crate::renderer::{
-        Markdown, MarkdownRenderer, RenderedBlock, RenderedElement, RenderedInline, RenderOptions,
+        IncrementalRenderer, LinkResolver, Markdown, MarkdownRenderer, RenderedBlock, RenderedElement,
+        RenderedInline, RenderOptions,
     };
+    pub use crate::sanitize::HtmlSanitizer;
     pub use crate::theme::{MarkdownTheme, ThemeColors, ThemeConfig, TypographyTokens};
     pub use crate::toc::{TocConfig, TocEntry, TocRenderer, TableOfContents};
     pub use crate::toolbar::{Toolbar, ToolbarAction, ToolbarConfig, ToolbarItem};
@@ -2,12 +2,16 @@
 //!
 //! Provides markdown parsing using pulldown-cmark with CommonMark and GFM support.
 
+use crate::extensions::{EmojiMap, MathBlock};
+use crate::frontmatter::Frontmatter;
 use crate::{HeadingLevel, MarkdownError, MarkdownResult, Position, Range};
 use pulldown_cmark::{
     Alignment, CodeBlockKind, Event, HeadingLevel as CmarkHeadingLevel, Options, Parser, Tag,
     TagEnd,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+use unicode_segmentation::UnicodeSegmentation;
 
 /// Error during parsing
 #[derive(Debug, Clone)]
@@ -86,11 +90,20 @@ pub struct ListItem {
     /// Item content (blocks)
     pub content: Vec<BlockElement>,
     /// Task checkbox state (None if not a task item)
-    pub task_checked: Option<bool>,
+    pub checked: Option<bool>,
     /// Nested list (if any)
     pub nested_list: Option<Box<BlockElement>>,
 }
 
+/// A task list checkbox in markdown source
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TaskMarker {
+    /// Byte offset of the character between the brackets (`' '` or `'x'`)
+    pub offset: usize,
+    /// Whether the box is checked
+    pub checked: bool,
+}
+
 /// Inline markdown elements
 #[derive(Debug, Clone)]
 pub enum InlineElement {
@@ -124,6 +137,8 @@ pub enum InlineElement {
     Html(String),
     /// Footnote reference
     FootnoteReference(String),
+    /// Inline (`$...$`) or display (`$$...$$`) math inside a paragraph
+    Math(MathBlock),
 }
 
 /// Block-level markdown elements
@@ -160,6 +175,8 @@ pub enum BlockElement {
     HorizontalRule,
     /// Raw HTML block
     Html(String),
+    /// Display math standing alone in its paragraph
+    Math(MathBlock),
     /// Footnote definition
     FootnoteDefinition {
         label: String,
@@ -189,6 +206,31 @@ pub struct MarkdownDocument {
     pub footnotes: HashMap<String, Vec<BlockElement>>,
     /// Link references
     pub link_references: HashMap<String, (String, Option<String>)>,
+    /// Front matter, kept out of the blocks
+    pub front_matter: Option<Frontmatter>,
+}
+
+/// Reading statistics for a document
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DocStats {
+    /// Words of prose (code blocks and math excluded)
+    pub words: usize,
+    /// Characters of prose, spaces included (code blocks and math excluded)
+    pub characters: usize,
+    /// Headings at any depth
+    pub headings: usize,
+    /// Estimated reading time
+    pub reading_time: Duration,
+}
+
+impl DocStats {
+    /// Default reading speed in words per minute
+    pub const DEFAULT_WPM: u32 = 200;
+
+    /// Reading time rounded up to whole minutes
+    pub fn reading_minutes(&self) -> u64 {
+        self.reading_time.as_secs().div_ceil(60)
+    }
 }
 
 impl MarkdownDocument {
@@ -197,6 +239,66 @@ impl MarkdownDocument {
         Self::default()
     }
 
+    /// Reading statistics at the default reading speed
+    pub fn stats(&self) -> DocStats {
+        self.stats_with_wpm(DocStats::DEFAULT_WPM)
+    }
+
+    /// Reading statistics at `wpm` words per minute
+    pub fn stats_with_wpm(&self, wpm: u32) -> DocStats {
+        let mut stats = DocStats::default();
+        self.collect_stats(&self.blocks, &mut stats);
+        let seconds = stats.words as f64 * 60.0 / wpm.max(1) as f64;
+        stats.reading_time = Duration::from_secs(seconds.ceil() as u64);
+        stats
+    }
+
+    fn collect_stats(&self, blocks: &[BlockElement], stats: &mut DocStats) {
+        let mut count = |inlines: &[InlineElement]| {
+            let mut text = String::new();
+            self.extract_inline_text(inlines, &mut text);
+            stats.words += text.unicode_words().count();
+            stats.characters += text.graphemes(true).count();
+        };
+        let mut nested = Vec::new();
+        for block in blocks {
+            match block {
+                BlockElement::Heading { content, .. } => {
+                    count(content);
+                    stats.headings += 1;
+                }
+                BlockElement::Paragraph(content) => count(content),
+                BlockElement::Blockquote(inner) | BlockElement::FootnoteDefinition { content: inner, .. } => {
+                    nested.push(inner.as_slice())
+                }
+                BlockElement::List { items, .. } => {
+                    for item in items {
+                        nested.push(item.content.as_slice());
+                        nested.extend(item.nested_list.as_deref().map(std::slice::from_ref));
+                    }
+                }
+                BlockElement::Table { header, rows, .. } => {
+                    for cell in std::iter::once(header).chain(rows).flat_map(|row| &row.cells) {
+                        count(&cell.content);
+                    }
+                }
+                BlockElement::DefinitionList { items } => {
+                    for item in items {
+                        count(&item.term);
+                        nested.extend(item.definitions.iter().map(Vec::as_slice));
+                    }
+                }
+                BlockElement::CodeBlock { .. }
+                | BlockElement::Html(_)
+                | BlockElement::Math(_)
+                | BlockElement::HorizontalRule => {}
+            }
+        }
+        for blocks in nested {
+            self.collect_stats(blocks, stats);
+        }
+    }
+
     /// Get all headings in the document
     pub fn headings(&self) -> Vec<(&HeadingLevel, &[InlineElement], Option<&str>)> {
         let mut headings = Vec::new();
@@ -229,7 +331,7 @@ impl MarkdownDocument {
         text
     }
 
-    fn extract_text(&self, blocks: &[BlockElement], text: &mut String) {
+    pub(crate) fn extract_text(&self, blocks: &[BlockElement], text: &mut String) {
         for block in blocks {
             match block {
                 BlockElement::Heading { content, .. } => {
@@ -271,7 +373,7 @@ impl MarkdownDocument {
         }
     }
 
-    fn extract_inline_text(&self, inlines: &[InlineElement], text: &mut String) {
+    pub(crate) fn extract_inline_text(&self, inlines: &[InlineElement], text: &mut String) {
         for inline in inlines {
             match inline {
                 InlineElement::Text(t) => text.push_str(t),
@@ -286,6 +388,7 @@ impl MarkdownDocument {
                 InlineElement::Image { alt, .. } => {
                     text.push_str(alt);
                 }
+                InlineElement::Math(math) => text.push_str(&math.content),
                 _ => {}
             }
         }
@@ -302,6 +405,12 @@ pub struct MarkdownParser {
     smart_punctuation: bool,
     /// Enable heading attributes
     heading_attributes: bool,
+    /// Enable `$`/`$$` math
+    math: bool,
+    /// Recognize YAML (`---`) and TOML (`+++`) front matter
+    front_matter: bool,
+    /// Shortcodes to expand, if emoji are enabled
+    emoji: Option<EmojiMap>,
 }
 
 impl Default for MarkdownParser {
@@ -318,6 +427,9 @@ impl MarkdownParser {
             footnotes: true,
             smart_punctuation: false,
             heading_attributes: true,
+            math: false,
+            front_matter: true,
+            emoji: None,
         }
     }
 
@@ -345,8 +457,46 @@ impl MarkdownParser {
         self
     }
 
+    /// Enable or disable `$...$` and `$$...$$` math
+    pub fn math(mut self, enable: bool) -> Self {
+        self.math = enable;
+        self
+    }
+
+    /// Enable or disable front matter, which is kept out of the content
+    pub fn front_matter(mut self, enable: bool) -> Self {
+        self.front_matter = enable;
+        self
+    }
+
+    /// Enable or disable `:shortcode:` emoji
+    pub fn emoji(mut self, enable: bool) -> Self {
+        self.emoji = enable.then(|| self.emoji.take().unwrap_or_default());
+        self
+    }
+
+    /// Add a custom emoji shortcode (without colons); enables emoji
+    pub fn custom_emoji(mut self, shortcode: impl Into<String>, emoji: impl Into<String>) -> Self {
+        self.emoji.get_or_insert_with(EmojiMap::new).register(shortcode, emoji);
+        self
+    }
+
+    /// Find task list checkboxes in source order
+    pub fn task_markers(&self, content: &str) -> Vec<TaskMarker> {
+        Parser::new_ext(content, self.options_for(content))
+            .into_offset_iter()
+            .filter_map(|(event, range)| match event {
+                Event::TaskListMarker(checked) => content[range.clone()].find('[').map(|i| TaskMarker {
+                    offset: range.start + i + 1,
+                    checked,
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
     /// Build parser options
-    fn build_options(&self) -> Options {
+    pub(crate) fn build_options(&self) -> Options {
         let mut options = Options::empty();
 
         if self.gfm {
@@ -367,25 +517,69 @@ impl MarkdownParser {
             options.insert(Options::ENABLE_HEADING_ATTRIBUTES);
         }
 
+        if self.math {
+            options.insert(Options::ENABLE_MATH);
+        }
+
+        if self.front_matter {
+            options.insert(Options::ENABLE_YAML_STYLE_METADATA_BLOCKS);
+            options.insert(Options::ENABLE_PLUSES_DELIMITED_METADATA_BLOCKS);
+        }
+
+        options
+    }
+
+    /// Parser options for `content`; a leading block that isn't front matter
+    /// stays in the body
+    pub(crate) fn options_for(&self, content: &str) -> Options {
+        let mut options = self.build_options();
+        if matches!(Frontmatter::try_parse(content), Ok(None)) {
+            options.remove(Options::ENABLE_YAML_STYLE_METADATA_BLOCKS);
+            options.remove(Options::ENABLE_PLUSES_DELIMITED_METADATA_BLOCKS);
+        }
         options
     }
 
     /// Parse markdown content into a document
+    ///
+    /// Fails if the document starts with front matter that doesn't parse.
     pub fn parse(&self, content: &str) -> MarkdownResult<MarkdownDocument> {
-        let options = self.build_options();
-        let parser = Parser::new_ext(content, options);
+        let parser = Parser::new_ext(content, self.options_for(content));
 
         let mut document = MarkdownDocument::new();
+        if self.front_matter {
+            document.front_matter = Frontmatter::try_parse(content)?;
+        }
         let mut block_stack: Vec<Vec<BlockElement>> = vec![Vec::new()];
         let mut inline_stack: Vec<Vec<InlineElement>> = Vec::new();
         let mut current_list_items: Vec<Vec<ListItem>> = Vec::new();
         let mut current_list_types: Vec<ListType> = Vec::new();
         let mut table_state: Option<TableParseState> = None;
         let mut current_footnote: Option<String> = None;
+        let mut in_code_block = false;
+        // Lines of the open HTML block, emitted as one element at its end
+        let mut html_block: Option<String> = None;
+        // Destination and title of each open link or image
+        let mut link_targets: Vec<(String, Option<String>)> = Vec::new();
+        // Checkbox state of each open list item
+        let mut item_tasks: Vec<Option<bool>> = Vec::new();
+        // End offset of the last undefined footnote reference turned into a reference
+        let mut footnote_end = 0;
 
-        for event in parser {
+        for (event, span) in parser.into_offset_iter() {
             match event {
                 Event::Start(tag) => {
+                    in_code_block |= matches!(tag, Tag::CodeBlock(_));
+                    if matches!(tag, Tag::HtmlBlock) {
+                        html_block = Some(String::new());
+                    }
+                    if matches!(tag, Tag::Item) {
+                        item_tasks.push(None);
+                    }
+                    if let Tag::Link { dest_url, title, .. } | Tag::Image { dest_url, title, .. } = &tag {
+                        let title = Some(title.to_string()).filter(|title| !title.is_empty());
+                        link_targets.push((dest_url.to_string(), title));
+                    }
                     self.handle_start_tag(
                         tag,
                         &mut block_stack,
@@ -397,6 +591,13 @@ impl MarkdownParser {
                     )?;
                 }
                 Event::End(tag_end) => {
+                    in_code_block &= !matches!(tag_end, TagEnd::CodeBlock);
+                    let is_paragraph = matches!(tag_end, TagEnd::Paragraph);
+                    let is_link = matches!(tag_end, TagEnd::Link | TagEnd::Image);
+                    if let (TagEnd::HtmlBlock, Some(html)) = (tag_end, html_block.take()) {
+                        push_html(html, &mut block_stack, &mut inline_stack);
+                    }
+                    let is_item = matches!(tag_end, TagEnd::Item);
                     self.handle_end_tag(
                         tag_end,
                         &mut document,
@@ -407,13 +608,49 @@ impl MarkdownParser {
                         &mut table_state,
                         &mut current_footnote,
                     )?;
+                    if is_paragraph {
+                        promote_display_math(&mut block_stack);
+                    }
+                    if let Some((dest, link_title)) = link_targets.pop().filter(|_| is_link) {
+                        if let Some(InlineElement::Link { url, title, .. } | InlineElement::Image { url, title, .. }) =
+                            inline_stack.last_mut().and_then(|inlines| inlines.last_mut())
+                        {
+                            *url = dest;
+                            *title = link_title;
+                        }
+                    }
+                    if is_item {
+                        let checked = item_tasks.pop().flatten();
+                        if let Some(item) = current_list_items.last_mut().and_then(|items| items.last_mut()) {
+                            item.checked = checked;
+                        }
+                    }
+                }
+                Event::Text(text) if in_code_block => {
+                    if let Some(BlockElement::CodeBlock { content, .. }) =
+                        block_stack.last_mut().and_then(|blocks| blocks.last_mut())
+                    {
+                        content.push_str(&text);
+                    }
                 }
                 Event::Text(text) => {
+                    // `^label` and `]` of a reference already emitted
+                    if span.end <= footnote_end {
+                        continue;
+                    }
+                    let footnote = undefined_footnote(content, &span).filter(|_| self.footnotes);
+                    let inline = match footnote {
+                        Some((label, end)) => {
+                            footnote_end = end;
+                            InlineElement::FootnoteReference(label)
+                        }
+                        None => InlineElement::Text(text.to_string()),
+                    };
                     if let Some(inlines) = inline_stack.last_mut() {
-                        inlines.push(InlineElement::Text(text.to_string()));
+                        inlines.push(inline);
                     } else if let Some(ref mut state) = table_state {
                         if let Some(cell) = state.current_row.cells.last_mut() {
-                            cell.content.push(InlineElement::Text(text.to_string()));
+                            cell.content.push(inline);
                         }
                     }
                 }
@@ -436,16 +673,10 @@ impl MarkdownParser {
                         inlines.push(InlineElement::HardBreak);
                     }
                 }
-                Event::Html(html) => {
-                    let html_str = html.to_string();
-                    if inline_stack.is_empty() {
-                        if let Some(blocks) = block_stack.last_mut() {
-                            blocks.push(BlockElement::Html(html_str));
-                        }
-                    } else if let Some(inlines) = inline_stack.last_mut() {
-                        inlines.push(InlineElement::Html(html_str));
-                    }
-                }
+                Event::Html(html) => match html_block.as_mut() {
+                    Some(lines) => lines.push_str(&html),
+                    None => push_html(html.to_string(), &mut block_stack, &mut inline_stack),
+                },
                 Event::InlineHtml(html) => {
                     if let Some(inlines) = inline_stack.last_mut() {
                         inlines.push(InlineElement::Html(html.to_string()));
@@ -462,26 +693,20 @@ impl MarkdownParser {
                     }
                 }
                 Event::TaskListMarker(checked) => {
-                    if let Some(items) = current_list_items.last_mut() {
-                        if let Some(item) = items.last_mut() {
-                            item.task_checked = Some(checked);
-                        }
+                    // The item is still open; it's stored when it ends
+                    if let Some(task) = item_tasks.last_mut() {
+                        *task = Some(checked);
                     }
                 }
                 Event::InlineMath(math) => {
-                    // Inline math (e.g., $x^2$)
                     if let Some(inlines) = inline_stack.last_mut() {
-                        inlines.push(InlineElement::Code(format!("${}$", math)));
+                        inlines.push(InlineElement::Math(MathBlock::inline(math.to_string()).span(span)));
                     }
                 }
                 Event::DisplayMath(math) => {
-                    // Display math (e.g., $$x^2$$)
-                    if let Some(blocks) = block_stack.last_mut() {
-                        blocks.push(BlockElement::CodeBlock {
-                            language: Some("math".to_string()),
-                            content: math.to_string(),
-                            filename: None,
-                        });
+                    // Always inside a paragraph; promoted to a block when it ends
+                    if let Some(inlines) = inline_stack.last_mut() {
+                        inlines.push(InlineElement::Math(MathBlock::display(math.to_string()).span(span)));
                     }
                 }
             }
@@ -492,6 +717,10 @@ impl MarkdownParser {
             document.blocks = blocks;
         }
 
+        if let Some(emoji) = &self.emoji {
+            visit_inlines(&mut document.blocks, &mut |inlines| expand_emoji(inlines, emoji));
+        }
+
         Ok(document)
     }
 
@@ -543,11 +772,17 @@ impl MarkdownParser {
                     Some(n) => ListType::Ordered(n),
                     None => ListType::Unordered,
                 };
+                // Text of a tight item comes before its nested list
+                if !current_list_items.is_empty() {
+                    flush_item_text(block_stack, inline_stack);
+                }
                 current_list_types.push(list_type);
                 current_list_items.push(Vec::new());
             }
             Tag::Item => {
                 block_stack.push(Vec::new());
+                // Tight items have text without a wrapping paragraph
+                inline_stack.push(Vec::new());
             }
             Tag::FootnoteDefinition(label) => {
                 *current_footnote = Some(label.to_string());
@@ -599,15 +834,9 @@ impl MarkdownParser {
             Tag::Strikethrough => {
                 inline_stack.push(Vec::new());
             }
-            Tag::Link { dest_url, title, .. } => {
+            // Destination and title are filled in by `parse` when the tag ends
+            Tag::Link { .. } | Tag::Image { .. } => {
                 inline_stack.push(Vec::new());
-                // Store link info for later
-                let _ = (dest_url, title);
-            }
-            Tag::Image { dest_url, title, .. } => {
-                inline_stack.push(Vec::new());
-                // Store image info for later
-                let _ = (dest_url, title);
             }
             _ => {}
         }
@@ -669,11 +898,13 @@ impl MarkdownParser {
                 }
             }
             TagEnd::Item => {
+                flush_item_text(block_stack, inline_stack);
+                inline_stack.pop();
                 let item_blocks = block_stack.pop().unwrap_or_default();
                 if let Some(items) = current_list_items.last_mut() {
                     items.push(ListItem {
                         content: item_blocks,
-                        task_checked: None,
+                        checked: None,
                         nested_list: None,
                     });
                 }
@@ -748,7 +979,6 @@ impl MarkdownParser {
             TagEnd::Link => {
                 let content = inline_stack.pop().unwrap_or_default();
                 if let Some(parent) = inline_stack.last_mut() {
-                    // Link URL/title would need to be tracked from start tag
                     parent.push(InlineElement::Link {
                         url: String::new(),
                         title: None,
@@ -767,7 +997,6 @@ impl MarkdownParser {
                     .collect::<Vec<_>>()
                     .join("");
                 if let Some(parent) = inline_stack.last_mut() {
-                    // Image URL/title would need to be tracked from start tag
                     parent.push(InlineElement::Image {
                         url: String::new(),
                         title: None,
@@ -790,30 +1019,167 @@ struct TableParseState {
     in_header: bool,
 }
 
+/// Turn the `[^label]` text left by undefined footnote references into
+/// references, so they can be reported instead of rendered literally
+fn visit_inlines(blocks: &mut [BlockElement], f: &mut dyn FnMut(&mut Vec<InlineElement>)) {
+    for block in blocks {
+        match block {
+            BlockElement::Heading { content, .. } | BlockElement::Paragraph(content) => f(content),
+            BlockElement::Blockquote(inner) | BlockElement::FootnoteDefinition { content: inner, .. } => {
+                visit_inlines(inner, f)
+            }
+            BlockElement::List { items, .. } => {
+                for item in items {
+                    visit_inlines(&mut item.content, f);
+                    if let Some(nested) = &mut item.nested_list {
+                        visit_inlines(std::slice::from_mut(nested.as_mut()), f);
+                    }
+                }
+            }
+            BlockElement::Table { header, rows, .. } => {
+                for row in std::iter::once(header).chain(rows) {
+                    for cell in &mut row.cells {
+                        f(&mut cell.content);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Merge adjacent text and expand emoji shortcodes in it; code spans are left alone
+fn expand_emoji(inlines: &mut Vec<InlineElement>, emoji: &EmojiMap) {
+    let mut result: Vec<InlineElement> = Vec::with_capacity(inlines.len());
+    for inline in inlines.drain(..) {
+        match (result.last_mut(), inline) {
+            (Some(InlineElement::Text(previous)), InlineElement::Text(text)) => previous.push_str(&text),
+            (_, mut other) => {
+                if let InlineElement::Strong(inner)
+                | InlineElement::Emphasis(inner)
+                | InlineElement::Strikethrough(inner)
+                | InlineElement::Link { content: inner, .. } = &mut other
+                {
+                    expand_emoji(inner, emoji);
+                }
+                result.push(other);
+            }
+        }
+    }
+    for inline in &mut result {
+        if let InlineElement::Text(text) = inline {
+            *text = emoji.expand(text);
+        }
+    }
+    *inlines = result;
+}
+
+/// Label and end offset of an undefined footnote reference, which pulldown-cmark
+/// leaves as text starting with an unescaped `[`
+fn undefined_footnote(content: &str, span: &std::ops::Range<usize>) -> Option<(String, usize)> {
+    if content.get(span.clone()) != Some("[") {
+        return None;
+    }
+    let backslashes = content[..span.start].bytes().rev().take_while(|&b| b == b'\\').count();
+    if backslashes % 2 == 1 {
+        return None;
+    }
+    let label = content[span.end..].strip_prefix('^')?;
+    let len = label.find(|c: char| matches!(c, ']' | '[' | '\\') || c.is_whitespace())?;
+    let closed = len > 0 && label[len..].starts_with(']');
+    closed.then(|| (label[..len].to_string(), span.end + len + 2))
+}
+
+/// Move text collected directly in a tight list item into a paragraph
+fn flush_item_text(block_stack: &mut [Vec<BlockElement>], inline_stack: &mut [Vec<InlineElement>]) {
+    let Some(inlines) = inline_stack.last_mut().filter(|inlines| !inlines.is_empty()) else {
+        return;
+    };
+    if let Some(blocks) = block_stack.last_mut() {
+        blocks.push(BlockElement::Paragraph(std::mem::take(inlines)));
+    }
+}
+
+/// Turn a just-closed paragraph holding only display math into a math block
+fn promote_display_math(block_stack: &mut [Vec<BlockElement>]) {
+    let Some(block) = block_stack.last_mut().and_then(|blocks| blocks.last_mut()) else {
+        return;
+    };
+    let BlockElement::Paragraph(inlines) = block else {
+        return;
+    };
+    let mut content = inlines.iter().filter(|inline| match inline {
+        InlineElement::SoftBreak => false,
+        InlineElement::Text(text) => !text.trim().is_empty(),
+        _ => true,
+    });
+    if let (Some(InlineElement::Math(math)), None) = (content.next(), content.next()) {
+        if math.display {
+            *block = BlockElement::Math(math.clone());
+        }
+    }
+}
+
+/// Push raw HTML as a block, or as an inline inside an open item
+fn push_html(html: String, block_stack: &mut [Vec<BlockElement>], inline_stack: &mut [Vec<InlineElement>]) {
+    if let Some(inlines) = inline_stack.last_mut() {
+        inlines.push(InlineElement::Html(html));
+    } else if let Some(blocks) = block_stack.last_mut() {
+        blocks.push(BlockElement::Html(html));
+    }
+}
+
 /// Parse markdown content with default options
 pub fn parse(content: &str) -> MarkdownResult<MarkdownDocument> {
     MarkdownParser::new().parse(content)
 }
 
 /// Generate a slug from heading text for anchor links
+///
+/// GitHub-style: lowercase, punctuation stripped, whitespace runs replaced
+/// with a single hyphen. Hyphens and underscores in the text are kept.
 pub fn slugify(text: &str) -> String {
     text.to_lowercase()
         .chars()
-        .map(|c| {
-            if c.is_alphanumeric() {
-                c
-            } else if c.is_whitespace() || c == '-' || c == '_' {
-                '-'
-            } else {
-                ' '
-            }
-        })
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace() || *c == '-' || *c == '_')
         .collect::<String>()
         .split_whitespace()
         .collect::<Vec<_>>()
         .join("-")
-        .trim_matches('-')
-        .to_string()
+}
+
+/// Generates unique slugs, suffixing duplicates with `-1`, `-2`, ...
+#[derive(Debug, Clone, Default)]
+pub struct SlugGenerator {
+    /// Slugs handed out so far
+    used: HashSet<String>,
+    /// Next suffix to try for each base slug
+    counts: HashMap<String, usize>,
+}
+
+impl SlugGenerator {
+    /// Create a generator with no slugs used
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get a unique slug for heading text
+    pub fn slug(&mut self, text: &str) -> String {
+        let base = slugify(text);
+        let mut slug = base.clone();
+        while self.used.contains(&slug) {
+            let count = self.counts.entry(base.clone()).or_insert(0);
+            *count += 1;
+            slug = format!("{}-{}", base, count);
+        }
+        self.used.insert(slug.clone());
+        slug
+    }
+
+    /// Reserve an explicit id (e.g. `{#custom}`) so generated slugs avoid it
+    pub fn reserve(&mut self, id: &str) {
+        self.used.insert(id.to_string());
+    }
 }
 
 #[cfg(test)]
@@ -850,8 +1216,9 @@ mod tests {
         let doc = parse("```rust\nfn main() {}\n```").unwrap();
         assert_eq!(doc.blocks.len(), 1);
         match &doc.blocks[0] {
-            BlockElement::CodeBlock { language, .. } => {
+            BlockElement::CodeBlock { language, content, .. } => {
                 assert_eq!(language.as_deref(), Some("rust"));
+                assert_eq!(content, "fn main() {}\n");
             }
             _ => panic!("Expected code block"),
         }
@@ -865,11 +1232,40 @@ mod tests {
             BlockElement::List { list_type, items } => {
                 assert_eq!(*list_type, ListType::Unordered);
                 assert_eq!(items.len(), 3);
+                assert!(matches!(
+                    &items[0].content[..],
+                    [BlockElement::Paragraph(text)] if matches!(&text[..], [InlineElement::Text(t)] if t == "Item 1")
+                ));
             }
             _ => panic!("Expected list"),
         }
     }
 
+    #[test]
+    fn test_parse_task_list() {
+        let doc = parse("- [ ] todo\n- [x] done\n- plain").unwrap();
+        match &doc.blocks[0] {
+            BlockElement::List { items, .. } => {
+                let checked: Vec<_> = items.iter().map(|item| item.checked).collect();
+                assert_eq!(checked, [Some(false), Some(true), None]);
+                assert!(matches!(
+                    &items[0].content[..],
+                    [BlockElement::Paragraph(text)] if matches!(&text[..], [InlineElement::Text(t)] if t == "todo")
+                ));
+            }
+            _ => panic!("Expected list"),
+        }
+
+        let markers = MarkdownParser::new().task_markers("- [ ] todo\n- [x] done");
+        assert_eq!(
+            markers,
+            [
+                TaskMarker { offset: 3, checked: false },
+                TaskMarker { offset: 14, checked: true }
+            ]
+        );
+    }
+
     #[test]
     fn test_parse_ordered_list() {
         let doc = parse("1. First\n2. Second\n3. Third").unwrap();
@@ -922,6 +1318,20 @@ mod tests {
         assert_eq!(slugify("CamelCase"), "camelcase");
     }
 
+    #[test]
+    fn test_unique_slugs() {
+        let mut slugs = SlugGenerator::new();
+        assert_eq!(slugs.slug("Overview"), "overview");
+        assert_eq!(slugs.slug("Overview"), "overview-1");
+        assert_eq!(slugs.slug("Overview!"), "overview-2");
+
+        // A heading that already looks like a suffixed slug is skipped over
+        let mut slugs = SlugGenerator::new();
+        assert_eq!(slugs.slug("Setup 1"), "setup-1");
+        assert_eq!(slugs.slug("Setup"), "setup");
+        assert_eq!(slugs.slug("Setup"), "setup-2");
+    }
+
     #[test]
     fn test_document_headings() {
         let doc = parse("# H1\n## H2\n### H3").unwrap();
@@ -947,4 +1357,125 @@ mod tests {
         assert_eq!(TableAlignment::from(Alignment::Right), TableAlignment::Right);
         assert_eq!(TableAlignment::from(Alignment::None), TableAlignment::None);
     }
+
+    #[test]
+    fn test_inline_and_block_math() {
+        let content = "Energy $E = mc^2$ and `$not math$`.\n\n$$\n\\int_0^1 x\\,dx\n$$\n";
+        let doc = MarkdownParser::new().math(true).parse(content).unwrap();
+
+        let BlockElement::Paragraph(inlines) = &doc.blocks[0] else {
+            panic!("expected paragraph, got {:?}", doc.blocks[0]);
+        };
+        let InlineElement::Math(inline) = &inlines[1] else {
+            panic!("expected inline math, got {:?}", inlines[1]);
+        };
+        assert_eq!(inline.content, "E = mc^2");
+        assert!(!inline.display);
+        assert_eq!(&content[inline.source_span.clone()], "$E = mc^2$");
+        // `$` inside a code span stays literal
+        assert!(matches!(&inlines[3], InlineElement::Code(code) if code == "$not math$"));
+
+        let BlockElement::Math(block) = &doc.blocks[1] else {
+            panic!("expected math block, got {:?}", doc.blocks[1]);
+        };
+        assert!(block.display);
+        assert_eq!(block.content.trim(), "\\int_0^1 x\\,dx");
+        assert!(content[block.source_span.clone()].starts_with("$$"));
+
+        assert_eq!(MathBlock::collect(&doc), [inline.clone(), block.clone()]);
+    }
+
+    #[test]
+    fn test_math_disabled_by_default() {
+        let doc = parse("Costs $5 or $10.").unwrap();
+        let BlockElement::Paragraph(inlines) = &doc.blocks[0] else {
+            panic!("expected paragraph");
+        };
+        assert!(!inlines.iter().any(|inline| matches!(inline, InlineElement::Math(_))));
+    }
+
+    #[test]
+    fn test_document_stats() {
+        let content = "# Release notes\n\n\
+                       The new build ships today.\n\n\
+                       ```rust\nfn these_words_do_not_count() {}\n```\n\n\
+                       ## Fixes\n\n\
+                       - Faster startup\n- Fewer crashes\n";
+        let stats = parse(content).unwrap().stats();
+
+        // "Release notes" + "The new build ships today" + "Fixes" + "Faster startup" + "Fewer crashes"
+        assert_eq!(stats.words, 2 + 5 + 1 + 2 + 2);
+        assert_eq!(stats.headings, 2);
+        assert_eq!(stats.characters, "Release notesThe new build ships today.FixesFaster startupFewer crashes".len());
+        assert_eq!(stats.reading_minutes(), 1);
+
+        // 12 words at 6 words per minute
+        let slow = parse(content).unwrap().stats_with_wpm(6);
+        assert_eq!(slow.reading_time, Duration::from_secs(120));
+    }
+
+    #[test]
+    fn test_front_matter_excluded_from_body() {
+        let doc = parse("---\ntitle: Guide\nauthor: Sam\n---\n# Guide\n\nWelcome.\n").unwrap();
+
+        let front = doc.front_matter.as_ref().unwrap();
+        assert_eq!(front.get_str("title"), Some("Guide"));
+        assert_eq!(front.get_str("author"), Some("Sam"));
+        assert_eq!(doc.blocks.len(), 2);
+        assert!(matches!(&doc.blocks[0], BlockElement::Heading { .. }));
+        assert_eq!(doc.plain_text(), "Guide\nWelcome.\n");
+        assert_eq!(doc.stats().words, 2);
+
+        // Invalid front matter is an error rather than leaking into the body
+        assert!(parse("---\nnot: [front matter\nstill going\n---\nText\n").is_err());
+
+        // A rule followed by prose is body text
+        let doc = parse("---\nstill going\n---\nText\n").unwrap();
+        assert!(doc.front_matter.is_none());
+        assert!(doc.plain_text().contains("still going"));
+    }
+
+    #[test]
+    fn test_emoji_expansion() {
+        let parser = MarkdownParser::new().emoji(true).custom_emoji("ferris", "🦀");
+        let doc = parser.parse("Ship it :rocket: :ferris: :notreal: `:rocket:`\n\n```\n:rocket:\n```\n").unwrap();
+
+        let BlockElement::Paragraph(inlines) = &doc.blocks[0] else {
+            panic!("expected paragraph");
+        };
+        assert!(matches!(&inlines[0], InlineElement::Text(text) if text == "Ship it 🚀 🦀 :notreal: "));
+        assert!(matches!(&inlines[1], InlineElement::Code(code) if code == ":rocket:"));
+        assert!(matches!(&doc.blocks[1], BlockElement::CodeBlock { content, .. } if content == ":rocket:\n"));
+
+        // Off by default
+        assert_eq!(parse(":rocket:").unwrap().plain_text(), ":rocket:\n");
+    }
+
+    #[test]
+    fn test_undefined_footnote_references() {
+        let source = concat!(
+            "Cited[^missing], escaped \\[^kept] and [^a b].\n\n",
+            "| Col |\n| --- |\n| x[^cell] |\n",
+        );
+        let doc = parse(source).unwrap();
+
+        let BlockElement::Paragraph(inlines) = &doc.blocks[0] else {
+            panic!("expected paragraph");
+        };
+        let refs: Vec<_> = inlines
+            .iter()
+            .filter_map(|inline| match inline {
+                InlineElement::FootnoteReference(label) => Some(label.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(refs, ["missing"]);
+        assert!(doc.plain_text().contains("escaped [^kept] and [^a b]."));
+
+        let BlockElement::Table { rows, .. } = &doc.blocks[1] else {
+            panic!("expected table");
+        };
+        let cell = &rows[0].cells[0].content;
+        assert!(matches!(&cell[1], InlineElement::FootnoteReference(label) if label == "cell"));
+    }
 }
@@ -0,0 +1,311 @@
+//! Conversion of pasted HTML (e.g. from a browser) to markdown.
+
+use crate::sanitize::{decode_entities, skip_past_closing, Tag};
+
+/// Tags whose content is never pasted
+const SKIPPED_TAGS: &[&str] = &["script", "style", "head", "title", "template", "noscript"];
+
+/// Whether clipboard text looks like HTML markup
+pub fn looks_like_html(text: &str) -> bool {
+    let mut rest = text;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start..];
+        if Tag::parse(rest).is_some() {
+            return true;
+        }
+        rest = &rest[1..];
+    }
+    false
+}
+
+/// Convert an HTML fragment to markdown
+///
+/// Handles headings, paragraphs, emphasis, code, links, images, lists,
+/// blockquotes, and tables; other tags are dropped and their text kept.
+pub fn html_to_markdown(html: &str) -> String {
+    let mut converter = Converter::default();
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        converter.text(&decode_entities(&rest[..start]));
+        rest = &rest[start..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        let Some(tag) = Tag::parse(rest) else {
+            converter.text("<");
+            rest = &rest[1..];
+            continue;
+        };
+        rest = &rest[tag.len..];
+
+        if SKIPPED_TAGS.contains(&tag.name.as_str()) {
+            if !tag.closing && !tag.self_closing {
+                rest = skip_past_closing(rest, &tag.name);
+            }
+            continue;
+        }
+        converter.tag(&tag);
+    }
+    converter.text(&decode_entities(rest));
+    converter.finish()
+}
+
+/// Streaming HTML-to-markdown state
+#[derive(Default)]
+struct Converter {
+    out: String,
+    /// Open lists; `Some(n)` is an ordered list at item `n`
+    lists: Vec<Option<u64>>,
+    /// Targets of open links
+    links: Vec<String>,
+    /// Depth of open blockquotes
+    quotes: usize,
+    /// Inside `<pre>`
+    pre: bool,
+    /// Inside inline `<code>`
+    code: bool,
+    /// Text of the open table cell
+    cell: Option<String>,
+    /// Cells of the open table row
+    row: Vec<String>,
+    /// Rows written for the open table
+    table_rows: usize,
+}
+
+impl Converter {
+    fn tag(&mut self, tag: &Tag) {
+        let open = !tag.closing;
+        match tag.name.as_str() {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                self.block();
+                if open {
+                    let level = tag.name[1..].parse().unwrap_or(1);
+                    self.write(&"#".repeat(level));
+                    self.write(" ");
+                }
+            }
+            // Paragraphs inside list items would make the list loose
+            "p" | "div" | "section" | "article" if self.lists.is_empty() => self.block(),
+            "br" => self.newline(),
+            "hr" if open => {
+                self.block();
+                self.write("---");
+                self.block();
+            }
+            "strong" | "b" => self.write("**"),
+            "em" | "i" => self.write("_"),
+            "del" | "s" | "strike" => self.write("~~"),
+            "code" if !self.pre => {
+                self.code = open;
+                self.write("`");
+            }
+            "pre" => {
+                self.block();
+                self.write("```");
+                self.pre = open;
+                self.newline();
+                if !open {
+                    self.block();
+                }
+            }
+            "blockquote" => {
+                self.block();
+                self.quotes = if open { self.quotes + 1 } else { self.quotes.saturating_sub(1) };
+            }
+            "a" if open => {
+                self.links.push(tag.attribute("href").unwrap_or_default().to_string());
+                self.write("[");
+            }
+            "a" => {
+                let href = self.links.pop().unwrap_or_default();
+                self.write(&format!("]({})", link_destination(&href)));
+            }
+            "img" => {
+                let alt = escape_markdown(tag.attribute("alt").unwrap_or_default());
+                let src = link_destination(tag.attribute("src").unwrap_or_default());
+                self.write(&format!("![{}]({})", alt, src));
+            }
+            "ul" | "ol" if open => {
+                if self.lists.is_empty() {
+                    self.block();
+                } else {
+                    self.newline();
+                }
+                let start = tag.attribute("start").and_then(|s| s.parse().ok()).unwrap_or(1);
+                self.lists.push((tag.name == "ol").then_some(start));
+            }
+            "ul" | "ol" => {
+                self.lists.pop();
+                if self.lists.is_empty() {
+                    self.block();
+                }
+            }
+            "li" if open => {
+                self.newline();
+                let indent = "  ".repeat(self.lists.len().saturating_sub(1));
+                let marker = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}. ", *number - 1)
+                    }
+                    _ => "- ".to_string(),
+                };
+                self.write(&format!("{}{}", indent, marker));
+            }
+            "table" => {
+                self.block();
+                self.table_rows = 0;
+            }
+            "tr" if open => self.row.clear(),
+            "tr" => {
+                let cells = std::mem::take(&mut self.row);
+                self.write(&format!("| {} |", cells.join(" | ")));
+                self.newline();
+                if self.table_rows == 0 {
+                    self.write(&format!("|{}", " --- |".repeat(cells.len())));
+                    self.newline();
+                }
+                self.table_rows += 1;
+            }
+            "td" | "th" if open => self.cell = Some(String::new()),
+            "td" | "th" => {
+                let cell = self.cell.take().unwrap_or_default();
+                self.row.push(cell.trim().replace('|', "\\|"));
+            }
+            _ => {}
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        if self.pre {
+            self.write(text);
+            return;
+        }
+        let mut collapsed = String::with_capacity(text.len());
+        for (i, word) in text.split_whitespace().enumerate() {
+            if i > 0 {
+                collapsed.push(' ');
+            }
+            collapsed.push_str(word);
+        }
+        let target = self.cell.as_ref().unwrap_or(&self.out);
+        let at_line_start = target.is_empty() || target.ends_with(['\n', ' ']);
+        if text.starts_with(char::is_whitespace) && !at_line_start {
+            collapsed.insert(0, ' ');
+        }
+        if text.ends_with(char::is_whitespace) && !collapsed.is_empty() {
+            collapsed.push(' ');
+        }
+        if !self.code {
+            collapsed = escape_markdown(&collapsed);
+        }
+        self.write(&collapsed);
+    }
+
+    fn write(&mut self, text: &str) {
+        if let Some(cell) = &mut self.cell {
+            cell.push_str(text);
+            return;
+        }
+        if self.quotes > 0 && (self.out.is_empty() || self.out.ends_with('\n')) && !text.is_empty() {
+            self.out.push_str(&"> ".repeat(self.quotes));
+        }
+        self.out.push_str(text);
+    }
+
+    /// End the current line
+    fn newline(&mut self) {
+        if self.cell.is_some() {
+            return;
+        }
+        let trimmed = self.out.trim_end_matches(' ').len();
+        self.out.truncate(trimmed);
+        if !self.out.is_empty() && !self.out.ends_with('\n') {
+            self.out.push('\n');
+        }
+    }
+
+    /// End the current block with a blank line
+    fn block(&mut self) {
+        self.newline();
+        if !self.out.is_empty() && !self.out.ends_with("\n\n") {
+            self.out.push('\n');
+        }
+    }
+
+    fn finish(self) -> String {
+        self.out.trim().to_string()
+    }
+}
+
+/// Backslash-escape characters that would turn pasted text into markup
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '#' | '~' | '<') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Format a link target, using `<...>` when it has spaces or parentheses
+fn link_destination(href: &str) -> String {
+    if !href.contains(|c: char| c.is_whitespace() || matches!(c, '(' | ')' | '<' | '>')) {
+        return href.to_string();
+    }
+    let href = href.replace('<', "%3C").replace('>', "%3E");
+    format!("<{}>", href.replace('\n', "%0A").replace('\r', "%0D"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_link_and_list() {
+        let html = "<meta charset=\"utf-8\"><p>See <a href=\"https://example.com/docs\">the docs</a> for:</p>\n\
+                    <ul>\n  <li>setup</li>\n  <li><b>usage</b> &amp; tips</li>\n</ul>";
+
+        assert_eq!(
+            html_to_markdown(html),
+            "See [the docs](https://example.com/docs) for:\n\n- setup\n- **usage** & tips"
+        );
+    }
+
+    #[test]
+    fn test_headings_nested_lists_and_tables() {
+        let html = "<h2>Plan</h2><ol><li>Design<ul><li>sketch</li></ul></li><li>Build</li></ol>\
+                    <table><tr><th>Name</th><th>Role</th></tr><tr><td>Ana</td><td>Dev</td></tr></table>\
+                    <script>ignored()</script>";
+
+        assert_eq!(
+            html_to_markdown(html),
+            "## Plan\n\n1. Design\n  - sketch\n2. Build\n\n| Name | Role |\n| --- | --- |\n| Ana | Dev |"
+        );
+    }
+
+    #[test]
+    fn test_detects_html() {
+        assert!(looks_like_html("<b>bold</b>"));
+        assert!(!looks_like_html("if a < b then"));
+    }
+
+    #[test]
+    fn test_escapes_pasted_text() {
+        let html = "<p>#1 *not* bold_text [x]</p>\
+                    <p><a href=\"https://example.com/a b)\">link</a> or <code>a*b</code></p>";
+
+        assert_eq!(
+            html_to_markdown(html),
+            "\\#1 \\*not\\* bold\\_text \\[x\\]\n\n[link](<https://example.com/a b)>) or `a*b`"
+        );
+    }
+}
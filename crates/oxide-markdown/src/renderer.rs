@@ -1,8 +1,18 @@
 //! Markdown rendering.
 
 use serde::{Deserialize, Serialize};
-use crate::parser::MarkdownDocument;
-use crate::theme::MarkdownTheme;
+use crate::highlight::SyntaxHighlighter;
+use crate::layout::{LayoutBuilder, MarkdownLayout};
+use crate::sanitize::HtmlSanitizer;
+use crate::parser::{BlockElement, InlineElement, ListType, MarkdownDocument, MarkdownParser, TableRow};
+use crate::theme::{MarkdownTheme, ThemeConfig};
+use oxide_layout::{LayoutTree, NodeId};
+use oxide_text::TextSystem;
+use pulldown_cmark::{Event, Parser};
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+use std::sync::Arc;
 
 /// Render options
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -15,6 +25,21 @@ pub struct RenderOptions {
     pub link_previews: bool,
     /// Sanitize HTML
     pub sanitize: bool,
+    /// Allowlist applied to raw HTML when `sanitize` is set
+    #[serde(default)]
+    pub sanitizer: HtmlSanitizer,
+    /// Base path or URL that relative link and image URLs resolve against
+    #[serde(default)]
+    pub base_url: Option<String>,
+    /// Leave code unhighlighted for a `LazyHighlighter` to fill in on demand
+    #[serde(default)]
+    pub lazy_highlight: bool,
+    /// Parse `$...$` and `$$...$$` as math
+    #[serde(default)]
+    pub math: bool,
+    /// Expand `:shortcode:` emoji
+    #[serde(default)]
+    pub emoji: bool,
 }
 
 impl RenderOptions {
@@ -25,12 +50,57 @@ impl RenderOptions {
             toc: false,
             link_previews: false,
             sanitize: true,
+            sanitizer: HtmlSanitizer::default(),
+            base_url: None,
+            lazy_highlight: false,
+            math: false,
+            emoji: false,
         }
     }
+
+    /// Set the base path or URL for relative links and images
+    pub fn base_url(mut self, base: impl Into<String>) -> Self {
+        self.base_url = Some(base.into());
+        self
+    }
+
+    /// Enable `$...$` and `$$...$$` math
+    pub fn math(mut self, enable: bool) -> Self {
+        self.math = enable;
+        self
+    }
+
+    /// Enable `:shortcode:` emoji
+    pub fn emoji(mut self, enable: bool) -> Self {
+        self.emoji = enable;
+        self
+    }
+
+    /// Parser configured for these options
+    pub(crate) fn parser(&self) -> MarkdownParser {
+        MarkdownParser::new().math(self.math).emoji(self.emoji)
+    }
+}
+
+/// Resolves link and image URLs with a scheme (e.g. `asset:logo.png`)
+///
+/// Returning `None` leaves the URL unchanged.
+pub trait LinkResolver: Send + Sync {
+    /// Resolve a URL
+    fn resolve(&self, url: &str) -> Option<String>;
+}
+
+impl<F> LinkResolver for F
+where
+    F: Fn(&str) -> Option<String> + Send + Sync,
+{
+    fn resolve(&self, url: &str) -> Option<String> {
+        self(url)
+    }
 }
 
 /// A rendered block element
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum RenderedBlock {
     /// Paragraph
     Paragraph(Vec<RenderedInline>),
@@ -60,10 +130,14 @@ pub enum RenderedBlock {
         headers: Vec<Vec<RenderedInline>>,
         rows: Vec<Vec<Vec<RenderedInline>>>,
     },
+    /// Raw HTML, sanitized unless disabled in the options
+    Html(String),
+    /// Display math (TeX source)
+    Math(String),
 }
 
 /// A rendered inline element
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum RenderedInline {
     /// Plain text
     Text(String),
@@ -89,18 +163,34 @@ pub enum RenderedInline {
     },
     /// Line break
     LineBreak,
+    /// Inline raw HTML, sanitized unless disabled in the options
+    Html(String),
+    /// Math (TeX source) within text
+    Math { tex: String, display: bool },
 }
 
 /// A rendered element (alias)
 pub type RenderedElement = RenderedBlock;
 
 /// Markdown renderer
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct MarkdownRenderer {
     /// Theme
     pub theme: MarkdownTheme,
     /// Options
     pub options: RenderOptions,
+    /// Resolver for URLs with a scheme
+    resolver: Option<Arc<dyn LinkResolver>>,
+}
+
+impl fmt::Debug for MarkdownRenderer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MarkdownRenderer")
+            .field("theme", &self.theme)
+            .field("options", &self.options)
+            .field("resolver", &self.resolver.is_some())
+            .finish()
+    }
 }
 
 impl MarkdownRenderer {
@@ -109,9 +199,16 @@ impl MarkdownRenderer {
         Self {
             theme: MarkdownTheme::default(),
             options: RenderOptions::new(),
+            resolver: None,
         }
     }
 
+    /// Set a resolver for URLs with a scheme, e.g. app asset URLs
+    pub fn link_resolver(mut self, resolver: impl LinkResolver + 'static) -> Self {
+        self.resolver = Some(Arc::new(resolver));
+        self
+    }
+
     /// Set theme
     pub fn theme(mut self, theme: MarkdownTheme) -> Self {
         self.theme = theme;
@@ -125,10 +222,260 @@ impl MarkdownRenderer {
     }
 
     /// Render document
-    pub fn render(&self, _doc: &MarkdownDocument) -> Vec<RenderedBlock> {
-        // Basic implementation
-        Vec::new()
+    pub fn render(&self, doc: &MarkdownDocument) -> Vec<RenderedBlock> {
+        self.render_blocks(&doc.blocks)
+    }
+
+    fn render_blocks(&self, blocks: &[BlockElement]) -> Vec<RenderedBlock> {
+        blocks.iter().filter_map(|block| self.render_block(block)).collect()
+    }
+
+    fn render_block(&self, block: &BlockElement) -> Option<RenderedBlock> {
+        let rendered = match block {
+            BlockElement::Heading { level, content, id } => RenderedBlock::Heading {
+                level: level.level() as u8,
+                content: self.render_inlines(content),
+                id: id.clone(),
+            },
+            BlockElement::Paragraph(content) => RenderedBlock::Paragraph(self.render_inlines(content)),
+            BlockElement::CodeBlock { language, content, .. } => RenderedBlock::CodeBlock {
+                language: language.clone(),
+                code: content.clone(),
+                highlighted: language
+                    .as_deref()
+                    .filter(|_| self.options.highlight_code && !self.options.lazy_highlight)
+                    .map(|lang| SyntaxHighlighter::new().highlight(content, lang)),
+            },
+            BlockElement::Blockquote(inner) => RenderedBlock::BlockQuote(self.render_blocks(inner)),
+            BlockElement::List { list_type, items } => RenderedBlock::List {
+                ordered: matches!(list_type, ListType::Ordered(_)),
+                items: items
+                    .iter()
+                    .map(|item| {
+                        let mut blocks = self.render_blocks(&item.content);
+                        blocks.extend(item.nested_list.as_deref().and_then(|list| self.render_block(list)));
+                        blocks
+                    })
+                    .collect(),
+            },
+            BlockElement::Table { header, rows, .. } => RenderedBlock::Table {
+                headers: self.render_row(header),
+                rows: rows.iter().map(|row| self.render_row(row)).collect(),
+            },
+            BlockElement::HorizontalRule => RenderedBlock::HorizontalRule,
+            BlockElement::Math(math) => RenderedBlock::Math(math.content.clone()),
+            BlockElement::Html(html) => RenderedBlock::Html(self.render_html(html)?),
+            BlockElement::FootnoteDefinition { .. } | BlockElement::DefinitionList { .. } => return None,
+        };
+        Some(rendered)
+    }
+
+    fn render_row(&self, row: &TableRow) -> Vec<Vec<RenderedInline>> {
+        row.cells.iter().map(|cell| self.render_inlines(&cell.content)).collect()
+    }
+
+    fn render_inlines(&self, inlines: &[InlineElement]) -> Vec<RenderedInline> {
+        inlines
+            .iter()
+            .filter_map(|inline| {
+                Some(match inline {
+                    InlineElement::Text(text) => RenderedInline::Text(text.clone()),
+                    InlineElement::SoftBreak => RenderedInline::Text(" ".to_string()),
+                    InlineElement::HardBreak => RenderedInline::LineBreak,
+                    InlineElement::Strong(inner) => RenderedInline::Strong(self.render_inlines(inner)),
+                    InlineElement::Emphasis(inner) => RenderedInline::Emphasis(self.render_inlines(inner)),
+                    InlineElement::Strikethrough(inner) => RenderedInline::Strikethrough(self.render_inlines(inner)),
+                    InlineElement::Code(code) => RenderedInline::Code(code.clone()),
+                    InlineElement::Link { url, title, content } => RenderedInline::Link {
+                        url: self.resolve_url(url),
+                        title: title.clone(),
+                        content: self.render_inlines(content),
+                    },
+                    InlineElement::Image { url, title, alt } => RenderedInline::Image {
+                        url: self.resolve_url(url),
+                        alt: alt.clone(),
+                        title: title.clone(),
+                    },
+                    InlineElement::FootnoteReference(label) => RenderedInline::Text(format!("[{}]", label)),
+                    InlineElement::Html(html) => RenderedInline::Html(self.render_html(html)?),
+                    InlineElement::Math(math) => RenderedInline::Math {
+                        tex: math.content.clone(),
+                        display: math.display,
+                    },
+                })
+            })
+            .collect()
+    }
+
+    /// Resolve a link or image URL
+    ///
+    /// URLs with a scheme go through the resolver; relative paths are joined
+    /// to the base. Anchors and absolute paths are left alone. When `sanitize`
+    /// is set, a result whose scheme the sanitizer doesn't allow becomes `#`.
+    fn resolve_url(&self, url: &str) -> String {
+        let resolved = if url.starts_with('#') || url.starts_with('/') {
+            url.to_string()
+        } else if has_scheme(url) {
+            self.resolver
+                .as_ref()
+                .and_then(|resolver| resolver.resolve(url))
+                .unwrap_or_else(|| url.to_string())
+        } else {
+            match &self.options.base_url {
+                Some(base) => join_url(base, url),
+                None => url.to_string(),
+            }
+        };
+        if self.options.sanitize && !self.options.sanitizer.url_allowed(&resolved) {
+            return "#".to_string();
+        }
+        resolved
+    }
+
+    /// Sanitize raw HTML per the options; `None` if nothing is left
+    fn render_html(&self, html: &str) -> Option<String> {
+        let html = if self.options.sanitize {
+            self.options.sanitizer.sanitize(html)
+        } else {
+            html.to_string()
+        };
+        Some(html).filter(|html| !html.trim().is_empty())
+    }
+}
+
+/// Whether `url` starts with a scheme like `https:` or `asset:`
+fn has_scheme(url: &str) -> bool {
+    match url.split_once(':') {
+        Some((scheme, _)) => {
+            scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme.chars().all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+        }
+        None => false,
+    }
+}
+
+/// Join a relative URL to a base directory, applying `./` and `../`
+///
+/// `../` never climbs above the host of a URL base or the root of an
+/// absolute path; above a relative base it is kept.
+fn join_url(base: &str, relative: &str) -> String {
+    let base = base.trim_end_matches('/');
+    let root = match base.find("://") {
+        Some(i) => base[i + 3..].find('/').map_or(base.len(), |j| i + 3 + j),
+        None => 0,
+    };
+    let relative_base = root == 0 && !base.starts_with('/');
+    let mut path = base.to_string();
+    let mut ups = 0;
+    let mut rest = relative;
+    loop {
+        if let Some(stripped) = rest.strip_prefix("./") {
+            rest = stripped;
+        } else if let Some(stripped) = rest.strip_prefix("../") {
+            if relative_base && path.is_empty() {
+                ups += 1;
+            }
+            path.truncate(path[root..].rfind('/').map_or(root, |i| root + i));
+            rest = stripped;
+        } else {
+            break;
+        }
+    }
+    if relative_base && path.is_empty() {
+        return format!("{}{}", "../".repeat(ups), rest);
+    }
+    format!("{}/{}", path, rest)
+}
+
+/// Renders a document block by block, reusing output for unchanged blocks
+///
+/// Top-level block boundaries come from a quick event scan of the source;
+/// only blocks whose source text changed are parsed and rendered again.
+/// Blocks are parsed on their own, so reference-style links whose
+/// definition lives in another block are not resolved.
+#[derive(Debug, Clone, Default)]
+pub struct IncrementalRenderer {
+    /// Renderer used for changed blocks
+    renderer: MarkdownRenderer,
+    /// Source and output of each top-level block, in order
+    blocks: Vec<(String, Vec<RenderedBlock>)>,
+    /// Indices of blocks rendered by the last update
+    changed: Vec<usize>,
+}
+
+impl IncrementalRenderer {
+    /// Create an incremental renderer
+    pub fn new(renderer: MarkdownRenderer) -> Self {
+        Self {
+            renderer,
+            ..Default::default()
+        }
+    }
+
+    /// Re-render `content`, reusing cached output for unchanged blocks
+    pub fn update(&mut self, content: &str) -> Vec<RenderedBlock> {
+        let mut cache: HashMap<String, Vec<RenderedBlock>> = self.blocks.drain(..).collect();
+        self.changed.clear();
+
+        let parser = self.renderer.options.parser();
+        for (index, range) in top_level_blocks(content, &parser).into_iter().enumerate() {
+            let source = &content[range];
+            let rendered = match cache.remove(source) {
+                Some(rendered) => rendered,
+                None => {
+                    self.changed.push(index);
+                    let doc = parser.parse(source).unwrap_or_default();
+                    self.renderer.render(&doc)
+                }
+            };
+            // Identical blocks later in the document can share the output
+            cache.entry(source.to_string()).or_insert_with(|| rendered.clone());
+            self.blocks.push((source.to_string(), rendered));
+        }
+
+        self.rendered()
+    }
+
+    /// Output of the last update
+    pub fn rendered(&self) -> Vec<RenderedBlock> {
+        self.blocks.iter().flat_map(|(_, rendered)| rendered.iter().cloned()).collect()
+    }
+
+    /// Number of top-level blocks
+    pub fn block_count(&self) -> usize {
+        self.blocks.len()
     }
+
+    /// Indices of the top-level blocks that the last update had to render
+    pub fn changed(&self) -> &[usize] {
+        &self.changed
+    }
+
+    /// Drop all cached output
+    pub fn clear(&mut self) {
+        self.blocks.clear();
+        self.changed.clear();
+    }
+}
+
+/// Byte ranges of the top-level blocks in `content`
+fn top_level_blocks(content: &str, parser: &MarkdownParser) -> Vec<Range<usize>> {
+    let mut depth = 0usize;
+    let mut ranges = Vec::new();
+    for (event, range) in Parser::new_ext(content, parser.options_for(content)).into_offset_iter() {
+        match event {
+            Event::Start(_) => {
+                if depth == 0 {
+                    ranges.push(range);
+                }
+                depth += 1;
+            }
+            Event::End(_) => depth = depth.saturating_sub(1),
+            Event::Rule | Event::Html(_) | Event::DisplayMath(_) if depth == 0 => ranges.push(range),
+            _ => {}
+        }
+    }
+    ranges
 }
 
 impl Default for MarkdownRenderer {
@@ -176,17 +523,199 @@ impl Markdown {
         self
     }
 
+    /// Defer code highlighting to a `LazyHighlighter`
+    pub fn lazy_highlight(mut self, enable: bool) -> Self {
+        self.options.lazy_highlight = enable;
+        self
+    }
+
+    /// Enable `$...$` and `$$...$$` math
+    pub fn math(mut self, enable: bool) -> Self {
+        self.options.math = enable;
+        self
+    }
+
+    /// Enable `:shortcode:` emoji
+    pub fn emoji(mut self, enable: bool) -> Self {
+        self.options.emoji = enable;
+        self
+    }
+
     /// Render to blocks
     pub fn render(&self) -> Vec<RenderedBlock> {
         let renderer = MarkdownRenderer::new()
             .theme(self.theme.clone())
             .options(self.options.clone());
-        let doc = MarkdownDocument::default();
-        renderer.render(&doc)
+        renderer.render(&self.document())
     }
 
     /// Get content
     pub fn content(&self) -> &str {
         &self.content
     }
+
+    /// Parse the content into a document
+    pub fn document(&self) -> MarkdownDocument {
+        self.options.parser().parse(&self.content).unwrap_or_default()
+    }
+
+    /// Build a layout subtree for the content and return its root
+    pub fn to_layout_tree(&self, tree: &mut LayoutTree, text_system: &mut TextSystem) -> NodeId {
+        self.layout(tree, text_system).root
+    }
+
+    /// Build a layout subtree, keeping each node's kind and text for painting
+    pub fn layout(&self, tree: &mut LayoutTree, text_system: &mut TextSystem) -> MarkdownLayout {
+        LayoutBuilder::new(tree, text_system, ThemeConfig::from_preset(self.theme)).build(&self.document())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_blocks() {
+        let blocks = Markdown::new("# Title\n\nSome **bold** text.\n\n- a\n- b\n\n```rust\nlet x = 1;\n```\n").render();
+
+        assert_eq!(blocks.len(), 4);
+        assert!(matches!(&blocks[0], RenderedBlock::Heading { level: 1, .. }));
+        assert_eq!(
+            blocks[1],
+            RenderedBlock::Paragraph(vec![
+                RenderedInline::Text("Some ".into()),
+                RenderedInline::Strong(vec![RenderedInline::Text("bold".into())]),
+                RenderedInline::Text(" text.".into()),
+            ])
+        );
+        assert!(matches!(&blocks[2], RenderedBlock::List { ordered: false, items } if items.len() == 2));
+        assert!(matches!(&blocks[3], RenderedBlock::CodeBlock { code, .. } if code == "let x = 1;\n"));
+    }
+
+    fn first_inline(renderer: &MarkdownRenderer, content: &str) -> RenderedInline {
+        let doc = MarkdownParser::new().parse(content).unwrap();
+        match renderer.render(&doc).remove(0) {
+            RenderedBlock::Paragraph(mut inlines) => inlines.remove(0),
+            block => panic!("expected paragraph, got {:?}", block),
+        }
+    }
+
+    fn rendered_url(inline: RenderedInline) -> String {
+        match inline {
+            RenderedInline::Link { url, .. } | RenderedInline::Image { url, .. } => url,
+            inline => panic!("expected link or image, got {:?}", inline),
+        }
+    }
+
+    #[test]
+    fn test_relative_urls_use_base() {
+        let renderer = MarkdownRenderer::new().options(RenderOptions::new().base_url("/home/me/docs/"));
+        let url = |content: &str| rendered_url(first_inline(&renderer, content));
+
+        assert_eq!(url("![logo](./img/logo.png)"), "/home/me/docs/img/logo.png");
+        assert_eq!(url("[up](../README.md)"), "/home/me/README.md");
+        assert_eq!(url("![cdn](https://cdn.example.com/a.png)"), "https://cdn.example.com/a.png");
+        assert_eq!(url("[abs](/etc/hosts)"), "/etc/hosts");
+        assert_eq!(url("[anchor](#usage)"), "#usage");
+
+        let renderer = MarkdownRenderer::new().options(RenderOptions::new().base_url("https://example.com/guide"));
+        assert_eq!(
+            rendered_url(first_inline(&renderer, "[x](../../../x.md)")),
+            "https://example.com/x.md"
+        );
+
+        assert_eq!(join_url("docs", "../img.png"), "img.png");
+        assert_eq!(join_url("docs/guide/", "../../img.png"), "img.png");
+        assert_eq!(join_url("docs", "../../img.png"), "../img.png");
+        assert_eq!(join_url("/docs", "../../img.png"), "/img.png");
+
+        // Without a base, relative URLs are kept as written
+        assert_eq!(rendered_url(first_inline(&MarkdownRenderer::new(), "[x](x.md)")), "x.md");
+    }
+
+    #[test]
+    fn test_link_resolver_handles_custom_schemes() {
+        let renderer = MarkdownRenderer::new()
+            .link_resolver(|url: &str| url.strip_prefix("asset:").map(|path| format!("/app/assets/{}", path)));
+
+        assert_eq!(rendered_url(first_inline(&renderer, "![i](asset:icon.svg)")), "/app/assets/icon.svg");
+        assert_eq!(rendered_url(first_inline(&renderer, "[w](https://x.dev)")), "https://x.dev");
+    }
+
+    #[test]
+    fn test_render_sanitizes_html() {
+        let content = "<div onclick=\"steal()\">\n<script>\nalert(1)\n</script>\n<em>kept</em>\n</div>\n\n\
+                       Text <a href=\"javascript:alert(1)\">link</a>\n";
+        let blocks = Markdown::new(content).render();
+
+        assert_eq!(blocks[0], RenderedBlock::Html("<div>\n\n<em>kept</em>\n</div>\n".into()));
+        assert_eq!(
+            blocks[1],
+            RenderedBlock::Paragraph(vec![
+                RenderedInline::Text("Text ".into()),
+                RenderedInline::Html("<a>".into()),
+                RenderedInline::Text("link".into()),
+                RenderedInline::Html("</a>".into()),
+            ])
+        );
+
+        // Script-only HTML disappears entirely
+        assert_eq!(Markdown::new("<script>alert(1)</script>\n").render(), []);
+    }
+
+    #[test]
+    fn test_render_sanitizes_urls() {
+        let renderer = MarkdownRenderer::new();
+        assert_eq!(rendered_url(first_inline(&renderer, "[x](javascript:alert(1))")), "#");
+        assert_eq!(rendered_url(first_inline(&renderer, "[x](JavaScript&#58;alert(1))")), "#");
+        assert_eq!(rendered_url(first_inline(&renderer, "![x](data:text/html,hi)")), "#");
+        assert_eq!(rendered_url(first_inline(&renderer, "[x](mailto:a@b.dev)")), "mailto:a@b.dev");
+
+        let options = RenderOptions { sanitize: false, ..Default::default() };
+        let renderer = MarkdownRenderer::new().options(options);
+        assert_eq!(rendered_url(first_inline(&renderer, "[x](javascript:f())")), "javascript:f()");
+    }
+
+    #[test]
+    fn test_incremental_block_boundaries() {
+        let mut renderer = IncrementalRenderer::default();
+        renderer.update("# A\n\n> quote\n> more\n\n---\n\n1. one\n2. two\n");
+        assert_eq!(renderer.block_count(), 4);
+
+        // Editing the quote only re-renders the quote
+        renderer.update("# A\n\n> quote\n> changed\n\n---\n\n1. one\n2. two\n");
+        assert_eq!(renderer.changed(), [1]);
+    }
+
+    #[test]
+    fn test_render_math_option() {
+        let content = "Energy $E = mc^2$.\n\n$$\nx^2\n$$\n";
+        let blocks = Markdown::new(content).math(true).render();
+        let RenderedBlock::Paragraph(inlines) = &blocks[0] else {
+            panic!("expected paragraph, got {:?}", blocks[0]);
+        };
+        assert_eq!(inlines[1], RenderedInline::Math { tex: "E = mc^2".into(), display: false });
+        assert!(matches!(&blocks[1], RenderedBlock::Math(tex) if tex.trim() == "x^2"));
+
+        // Off by default
+        let plain = Markdown::new(content).render();
+        assert!(!plain.iter().any(|block| matches!(block, RenderedBlock::Math(_))));
+
+        let options = RenderOptions::new().math(true);
+        let mut renderer = IncrementalRenderer::new(MarkdownRenderer::new().options(options));
+        renderer.update(content);
+        assert_eq!(renderer.block_count(), 2);
+        assert!(matches!(&renderer.rendered()[1], RenderedBlock::Math(_)));
+    }
+
+    #[test]
+    fn test_render_emoji_option() {
+        let text = |text: &str| RenderedBlock::Paragraph(vec![RenderedInline::Text(text.into())]);
+        assert_eq!(Markdown::new("Ship it :rocket:").emoji(true).render(), [text("Ship it 🚀")]);
+        assert_eq!(Markdown::new("Ship it :rocket:").render(), [text("Ship it :rocket:")]);
+
+        let options = RenderOptions::new().emoji(true);
+        let mut renderer = IncrementalRenderer::new(MarkdownRenderer::new().options(options));
+        assert_eq!(renderer.update(":tada:"), [text("🎉")]);
+    }
 }
@@ -0,0 +1,382 @@
+//! HTML sanitization for raw HTML embedded in markdown.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Tags removed together with their content
+const DROP_CONTENT_TAGS: &[&str] = &["script", "style", "iframe", "object", "embed", "template", "noscript"];
+
+/// Attributes holding URLs
+const URL_ATTRIBUTES: &[&str] = &["href", "src", "cite", "action", "formaction", "poster", "background"];
+
+/// Allowlist-based HTML sanitizer
+///
+/// Tags outside the allowlist are removed but their text is kept, except
+/// for script-like tags whose content is dropped too. Event handler
+/// attributes are never kept, and URL attributes must use an allowed scheme.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HtmlSanitizer {
+    /// Allowed tag names (lowercase)
+    pub allowed_tags: HashSet<String>,
+    /// Allowed attribute names (lowercase)
+    pub allowed_attributes: HashSet<String>,
+    /// Allowed URL schemes (lowercase)
+    pub allowed_schemes: HashSet<String>,
+}
+
+impl Default for HtmlSanitizer {
+    fn default() -> Self {
+        let set = |items: &[&str]| items.iter().map(|s| s.to_string()).collect();
+        Self {
+            allowed_tags: set(&[
+                "a", "abbr", "b", "blockquote", "br", "code", "dd", "del", "details", "div", "dl", "dt", "em",
+                "h1", "h2", "h3", "h4", "h5", "h6", "hr", "i", "img", "ins", "kbd", "li", "mark", "ol", "p",
+                "pre", "s", "samp", "span", "strong", "sub", "summary", "sup", "table", "tbody", "td", "tfoot",
+                "th", "thead", "tr", "u", "ul",
+            ]),
+            allowed_attributes: set(&[
+                "align", "alt", "colspan", "height", "href", "id", "lang", "rowspan", "src", "title", "width",
+            ]),
+            allowed_schemes: set(&["http", "https", "mailto"]),
+        }
+    }
+}
+
+impl HtmlSanitizer {
+    /// Create a sanitizer with the default safe allowlist
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow a tag
+    pub fn allow_tag(mut self, tag: impl Into<String>) -> Self {
+        self.allowed_tags.insert(tag.into().to_lowercase());
+        self
+    }
+
+    /// Remove a tag from the allowlist
+    pub fn deny_tag(mut self, tag: &str) -> Self {
+        self.allowed_tags.remove(&tag.to_lowercase());
+        self
+    }
+
+    /// Allow an attribute; `on*` event handlers stay stripped regardless
+    pub fn allow_attribute(mut self, attribute: impl Into<String>) -> Self {
+        self.allowed_attributes.insert(attribute.into().to_lowercase());
+        self
+    }
+
+    /// Allow a URL scheme
+    pub fn allow_scheme(mut self, scheme: impl Into<String>) -> Self {
+        self.allowed_schemes.insert(scheme.into().to_lowercase());
+        self
+    }
+
+    /// Sanitize an HTML fragment
+    pub fn sanitize(&self, html: &str) -> String {
+        let mut out = String::with_capacity(html.len());
+        let mut rest = html;
+
+        while let Some(start) = rest.find('<') {
+            out.push_str(&rest[..start]);
+            rest = &rest[start..];
+
+            if let Some(comment) = rest.strip_prefix("<!--") {
+                rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+                continue;
+            }
+
+            let Some(tag) = Tag::parse(rest) else {
+                out.push_str("&lt;");
+                rest = &rest[1..];
+                continue;
+            };
+            rest = &rest[tag.len..];
+
+            if DROP_CONTENT_TAGS.contains(&tag.name.as_str()) {
+                if !tag.closing && !tag.self_closing {
+                    rest = skip_past_closing(rest, &tag.name);
+                }
+                continue;
+            }
+            if !self.allowed_tags.contains(&tag.name) {
+                continue;
+            }
+
+            out.push('<');
+            if tag.closing {
+                out.push('/');
+            }
+            out.push_str(&tag.name);
+            if !tag.closing {
+                for (name, value) in &tag.attributes {
+                    if !self.attribute_allowed(name, value.as_deref()) {
+                        continue;
+                    }
+                    out.push(' ');
+                    out.push_str(name);
+                    if let Some(value) = value {
+                        out.push_str("=\"");
+                        out.push_str(&value.replace('"', "&quot;"));
+                        out.push('"');
+                    }
+                }
+            }
+            out.push_str(if tag.self_closing { " />" } else { ">" });
+        }
+
+        out.push_str(rest);
+        out
+    }
+
+    fn attribute_allowed(&self, name: &str, value: Option<&str>) -> bool {
+        if name.starts_with("on") || !self.allowed_attributes.contains(name) {
+            return false;
+        }
+        if !URL_ATTRIBUTES.contains(&name) {
+            return true;
+        }
+        match value {
+            Some(url) => self.url_allowed(url),
+            None => true,
+        }
+    }
+
+    /// Relative URLs are allowed; absolute ones need an allowed scheme
+    pub(crate) fn url_allowed(&self, url: &str) -> bool {
+        let url: String = decode_entities(url)
+            .chars()
+            .filter(|c| !c.is_whitespace() && !c.is_control())
+            .collect::<String>()
+            .to_lowercase();
+        match url.find(':') {
+            Some(colon) if !url[..colon].contains(['/', '?', '#']) => self.allowed_schemes.contains(&url[..colon]),
+            _ => true,
+        }
+    }
+}
+
+/// A parsed start or end tag
+pub(crate) struct Tag {
+    pub(crate) name: String,
+    pub(crate) attributes: Vec<(String, Option<String>)>,
+    pub(crate) closing: bool,
+    pub(crate) self_closing: bool,
+    /// Length of the tag in the source, including `<` and `>`
+    pub(crate) len: usize,
+}
+
+impl Tag {
+    /// Value of an attribute, if present with a value
+    pub(crate) fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(attr, _)| attr == name)
+            .and_then(|(_, value)| value.as_deref())
+    }
+
+    /// Parse a tag at the start of `source`, which begins with `<`
+    pub(crate) fn parse(source: &str) -> Option<Tag> {
+        let bytes = source.as_bytes();
+        let mut pos = 1;
+        let closing = bytes.get(pos) == Some(&b'/');
+        if closing {
+            pos += 1;
+        }
+        let name_start = pos;
+        while bytes.get(pos).is_some_and(|b| b.is_ascii_alphanumeric() || *b == b'-') {
+            pos += 1;
+        }
+        if pos == name_start || !bytes[name_start].is_ascii_alphabetic() {
+            return None;
+        }
+        let name = source[name_start..pos].to_ascii_lowercase();
+
+        let mut attributes = Vec::new();
+        let mut self_closing = false;
+        loop {
+            while bytes.get(pos).is_some_and(|b| b.is_ascii_whitespace()) {
+                pos += 1;
+            }
+            match bytes.get(pos)? {
+                b'>' => break,
+                b'/' => {
+                    self_closing = true;
+                    pos += 1;
+                    continue;
+                }
+                _ => {}
+            }
+
+            let attr_start = pos;
+            while bytes.get(pos).is_some_and(|b| !b.is_ascii_whitespace() && !b"=>/".contains(b)) {
+                pos += 1;
+            }
+            let attr_name = source[attr_start..pos].to_ascii_lowercase();
+            self_closing = false;
+
+            let mut value = None;
+            if bytes.get(pos) == Some(&b'=') {
+                pos += 1;
+                match bytes.get(pos)? {
+                    quote @ (b'"' | b'\'') => {
+                        let end = source[pos + 1..].find(*quote as char)? + pos + 1;
+                        value = Some(source[pos + 1..end].to_string());
+                        pos = end + 1;
+                    }
+                    _ => {
+                        let value_start = pos;
+                        while bytes.get(pos).is_some_and(|b| !b.is_ascii_whitespace() && *b != b'>') {
+                            pos += 1;
+                        }
+                        value = Some(source[value_start..pos].to_string());
+                    }
+                }
+            }
+            if !attr_name.is_empty() {
+                attributes.push((attr_name, value));
+            }
+        }
+
+        Some(Tag {
+            name,
+            attributes,
+            closing,
+            self_closing,
+            len: pos + 1,
+        })
+    }
+}
+
+/// Skip past `</name>`, or to the end if it never closes
+pub(crate) fn skip_past_closing<'a>(source: &'a str, name: &str) -> &'a str {
+    let lower = source.to_ascii_lowercase();
+    let needle = format!("</{}", name);
+    let Some(start) = lower.find(&needle) else {
+        return "";
+    };
+    match source[start..].find('>') {
+        Some(end) => &source[start + end + 1..],
+        None => "",
+    }
+}
+
+/// Decode numeric character references and common named ones
+///
+/// Numeric references follow the HTML spec: any number of digits, the
+/// trailing `;` is optional, and out-of-range values become U+FFFD.
+pub(crate) fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = if let Some(body) = rest.strip_prefix("&#") {
+            numeric_reference(body).map(|(c, len)| (c, len + 2))
+        } else {
+            rest.find(';')
+                .filter(|end| *end <= 12)
+                .and_then(|end| named_entity(&rest[1..end]).map(|c| (c, end + 1)))
+        };
+        match decoded {
+            Some((c, len)) => {
+                out.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Decode the body of a numeric reference (after `&#`), returning the
+/// character and the number of bytes consumed
+fn numeric_reference(body: &str) -> Option<(char, usize)> {
+    let (radix, start) = match body.as_bytes().first() {
+        Some(b'x' | b'X') => (16, 1),
+        _ => (10, 0),
+    };
+    let digits = body[start..]
+        .bytes()
+        .take_while(|b| (*b as char).is_digit(radix))
+        .count();
+    if digits == 0 {
+        return None;
+    }
+    let code = body[start..start + digits]
+        .chars()
+        .fold(0u32, |code, c| {
+            code.saturating_mul(radix).saturating_add(c.to_digit(radix).unwrap_or(0))
+        });
+    let c = match code {
+        0 | 0xD800..=0xDFFF => '\u{fffd}',
+        code => char::from_u32(code).unwrap_or('\u{fffd}'),
+    };
+    let mut len = start + digits;
+    if body[len..].starts_with(';') {
+        len += 1;
+    }
+    Some((c, len))
+}
+
+fn named_entity(name: &str) -> Option<char> {
+    Some(match name {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => '\u{a0}',
+        "colon" | "COLON" => ':',
+        "Colon" => '\u{2237}',
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strips_scripts_and_handlers() {
+        let sanitizer = HtmlSanitizer::new();
+        let html = "<div class=\"x\" onclick=\"steal()\"><script>alert(1)</script><b>bold</b></div>\
+                    <img src=\"a.png\" onerror=alert(1) alt='pic'>";
+
+        assert_eq!(
+            sanitizer.sanitize(html),
+            "<div><b>bold</b></div><img src=\"a.png\" alt=\"pic\">"
+        );
+        assert_eq!(sanitizer.sanitize("<SCRIPT src=x></SCRIPT >ok"), "ok");
+        assert_eq!(sanitizer.sanitize("<style>*{}</style><!-- note -->text"), "text");
+    }
+
+    #[test]
+    fn test_strips_dangerous_urls() {
+        let sanitizer = HtmlSanitizer::new();
+
+        assert_eq!(sanitizer.sanitize("<a href=\"javascript:alert(1)\">x</a>"), "<a>x</a>");
+        assert_eq!(sanitizer.sanitize("<a href=\"java\tscript&#58;alert(1)\">x</a>"), "<a>x</a>");
+        // Numeric references without `;` or padded with zeros still decode in browsers
+        assert_eq!(sanitizer.sanitize("<a href=\"javascript&#58alert(1)\">x</a>"), "<a>x</a>");
+        assert_eq!(sanitizer.sanitize("<a href=\"javascript&#x0000000003a;alert(1)\">x</a>"), "<a>x</a>");
+        assert_eq!(sanitizer.sanitize("<a href=\"javascript&#0000000000058;alert(1)\">x</a>"), "<a>x</a>");
+        assert_eq!(
+            sanitizer.sanitize("<a href=\"https://example.com\" title=\"t\">x</a>"),
+            "<a href=\"https://example.com\" title=\"t\">x</a>"
+        );
+        assert_eq!(sanitizer.sanitize("<a href=\"docs/intro.md\">x</a>"), "<a href=\"docs/intro.md\">x</a>");
+    }
+
+    #[test]
+    fn test_custom_allowlist() {
+        let sanitizer = HtmlSanitizer::new().deny_tag("img").allow_tag("video").allow_attribute("onload");
+
+        assert_eq!(sanitizer.sanitize("<img src=a.png><video onload=x></video>"), "<video></video>");
+        assert_eq!(sanitizer.sanitize("1 < 2 <custom>tag</custom>"), "1 &lt; 2 tag");
+    }
+}
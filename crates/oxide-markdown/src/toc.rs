@@ -1,6 +1,7 @@
 //! Table of contents generation.
 
 use serde::{Deserialize, Serialize};
+use crate::parser::{slugify, MarkdownParser, SlugGenerator};
 use crate::HeadingLevel;
 
 /// A table of contents entry
@@ -12,6 +13,9 @@ pub struct TocEntry {
     pub text: String,
     /// Heading level
     pub level: HeadingLevel,
+    /// Section number (`1`, `1.2`, ...) when numbering is enabled
+    #[serde(default)]
+    pub number: Option<String>,
     /// Child entries
     pub children: Vec<TocEntry>,
 }
@@ -23,6 +27,7 @@ impl TocEntry {
             id: id.into(),
             text: text.into(),
             level,
+            number: None,
             children: Vec::new(),
         }
     }
@@ -36,6 +41,29 @@ impl TocEntry {
     pub fn has_children(&self) -> bool {
         !self.children.is_empty()
     }
+
+    /// Anchor link to the heading (`#id`)
+    pub fn anchor(&self) -> String {
+        format!("#{}", self.id)
+    }
+
+    /// Add an entry under the last entry that is a level above it
+    fn insert(entries: &mut Vec<TocEntry>, entry: TocEntry) {
+        match entries.last_mut() {
+            Some(last) if last.level.level() < entry.level.level() => Self::insert(&mut last.children, entry),
+            _ => entries.push(entry),
+        }
+    }
+
+    /// Number entries by their position in the tree, so a skipped level
+    /// (h2 then h4) still reads as `1`, `1.1`
+    fn number(entries: &mut [TocEntry], prefix: &str) {
+        for (i, entry) in entries.iter_mut().enumerate() {
+            let number = format!("{}{}", prefix, i + 1);
+            Self::number(&mut entry.children, &format!("{}.", number));
+            entry.number = Some(number);
+        }
+    }
 }
 
 /// TOC configuration
@@ -49,6 +77,9 @@ pub struct TocConfig {
     pub generate_ids: bool,
     /// ID prefix
     pub id_prefix: String,
+    /// Compute hierarchical section numbers
+    #[serde(default)]
+    pub numbered: bool,
 }
 
 impl Default for TocConfig {
@@ -58,6 +89,7 @@ impl Default for TocConfig {
             max_level: 3,
             generate_ids: true,
             id_prefix: "heading-".to_string(),
+            numbered: false,
         }
     }
 }
@@ -74,6 +106,12 @@ impl TocConfig {
         self.max_level = max;
         self
     }
+
+    /// Enable section numbering
+    pub fn numbered(mut self, numbered: bool) -> Self {
+        self.numbered = numbered;
+        self
+    }
 }
 
 /// Table of contents
@@ -129,17 +167,109 @@ impl TocRenderer {
 
     /// Generate ID from text
     pub fn generate_id(&self, text: &str) -> String {
-        let slug: String = text
-            .to_lowercase()
-            .chars()
-            .map(|c| if c.is_alphanumeric() { c } else { '-' })
-            .collect();
-        format!("{}{}", self.config.id_prefix, slug.trim_matches('-'))
+        format!("{}{}", self.config.id_prefix, slugify(text))
     }
 
     /// Extract TOC from content
-    pub fn extract(&self, _content: &str) -> TableOfContents {
-        // Basic implementation
-        TableOfContents::new()
+    ///
+    /// Entry ids are unique, stable slugs: repeated headings get `-1`, `-2`,
+    /// ... suffixes in document order, and explicit `{#id}` attributes are
+    /// kept as-is. Headings outside `min_level..=max_level` are left out but
+    /// still count toward slug suffixes, so ids match the rendered anchors.
+    pub fn extract(&self, content: &str) -> TableOfContents {
+        let doc = MarkdownParser::new().parse(content).unwrap_or_default();
+        let headings = doc.headings();
+
+        let mut slugs = SlugGenerator::new();
+        for (_, _, id) in &headings {
+            if let Some(id) = id {
+                slugs.reserve(id);
+            }
+        }
+
+        let mut toc = TableOfContents::new();
+        for (level, inlines, id) in headings {
+            let mut text = String::new();
+            doc.extract_inline_text(inlines, &mut text);
+
+            let id = match id {
+                Some(id) => id.to_string(),
+                None if self.config.generate_ids => format!("{}{}", self.config.id_prefix, slugs.slug(&text)),
+                None => String::new(),
+            };
+            if (self.config.min_level..=self.config.max_level).contains(&(level.level() as u8)) {
+                TocEntry::insert(&mut toc.entries, TocEntry::new(id, text, *level));
+            }
+        }
+        if self.config.numbered {
+            TocEntry::number(&mut toc.entries, "");
+        }
+        toc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duplicate_headings_get_unique_slugs() {
+        let renderer = TocRenderer::new().config(TocConfig {
+            id_prefix: String::new(),
+            ..Default::default()
+        });
+        let content = "# Guide\n## Overview\n## Install\n### Overview\n# Reference\n## Overview\n";
+        let toc = renderer.extract(content);
+
+        assert_eq!(toc.count(), 6);
+        assert_eq!(toc.entries.len(), 2);
+        let guide = &toc.entries[0];
+        assert_eq!(guide.children[0].id, "overview");
+        assert_eq!(guide.children[1].children[0].id, "overview-1");
+        assert_eq!(toc.entries[1].children[0].id, "overview-2");
+        assert_eq!(toc.entries[1].children[0].anchor(), "#overview-2");
+
+        // Stable across runs
+        let again = renderer.extract(content);
+        assert_eq!(again.entries[1].children[0].id, "overview-2");
+    }
+
+    #[test]
+    fn test_level_filter_and_numbering() {
+        let content = "# Title\n## Intro\n### Setup\n### Usage\n## API\n#### Deep\n##### Too deep\n## FAQ\n";
+        let renderer = TocRenderer::new().config(TocConfig::new().levels(2, 4).numbered(true));
+        let toc = renderer.extract(content);
+
+        fn flatten(entries: &[TocEntry], out: &mut Vec<(String, String)>) {
+            for entry in entries {
+                out.push((entry.number.clone().unwrap(), entry.text.clone()));
+                flatten(&entry.children, out);
+            }
+        }
+        let mut flat = Vec::new();
+        flatten(&toc.entries, &mut flat);
+        let expected = [
+            ("1", "Intro"),
+            ("1.1", "Setup"),
+            ("1.2", "Usage"),
+            ("2", "API"),
+            ("2.1", "Deep"),
+            ("3", "FAQ"),
+        ];
+        assert_eq!(flat, expected.map(|(n, t)| (n.to_string(), t.to_string())));
+
+        // h2-h3 only, unnumbered
+        let toc = TocRenderer::new().config(TocConfig::new().levels(2, 3)).extract(content);
+        assert_eq!(toc.count(), 5);
+        assert_eq!(toc.entries[1].text, "API");
+        assert!(!toc.entries[1].has_children());
+        assert_eq!(toc.entries[0].number, None);
+    }
+
+    #[test]
+    fn test_default_prefix() {
+        let toc = TocRenderer::new().extract("## Getting Started!");
+        assert_eq!(toc.entries[0].id, "heading-getting-started");
+        assert_eq!(toc.entries[0].text, "Getting Started!");
     }
 }
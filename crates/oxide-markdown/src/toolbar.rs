@@ -98,8 +98,17 @@ impl ToolbarItem {
 pub enum ToolbarAction {
     /// Insert text at cursor
     Insert(String),
-    /// Wrap selection
-    Wrap { prefix: String, suffix: String },
+    /// Wrap selection (or toggle the wrapping off); an empty selection
+    /// inserts the placeholder between the markers
+    Wrap {
+        prefix: String,
+        suffix: String,
+        placeholder: String,
+    },
+    /// Prefix every selected line (or remove the prefix if all lines have it)
+    PrefixLines(String),
+    /// Number the selected lines `1. `, `2. `, ...
+    NumberLines,
     /// Toggle mode
     TogglePreview,
     /// Undo
@@ -108,6 +117,17 @@ pub enum ToolbarAction {
     Redo,
 }
 
+impl ToolbarAction {
+    /// Create a wrap action
+    pub fn wrap(prefix: impl Into<String>, suffix: impl Into<String>, placeholder: impl Into<String>) -> Self {
+        Self::Wrap {
+            prefix: prefix.into(),
+            suffix: suffix.into(),
+            placeholder: placeholder.into(),
+        }
+    }
+}
+
 /// Toolbar configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolbarConfig {
@@ -177,30 +197,26 @@ impl Toolbar {
     /// Get action for item
     pub fn action_for(&self, item: ToolbarItem) -> ToolbarAction {
         match item {
-            ToolbarItem::Bold => ToolbarAction::Wrap {
-                prefix: "**".to_string(),
-                suffix: "**".to_string(),
-            },
-            ToolbarItem::Italic => ToolbarAction::Wrap {
-                prefix: "_".to_string(),
-                suffix: "_".to_string(),
-            },
-            ToolbarItem::Strikethrough => ToolbarAction::Wrap {
-                prefix: "~~".to_string(),
-                suffix: "~~".to_string(),
-            },
-            ToolbarItem::Code => ToolbarAction::Wrap {
-                prefix: "`".to_string(),
-                suffix: "`".to_string(),
-            },
-            ToolbarItem::Link => ToolbarAction::Wrap {
-                prefix: "[".to_string(),
-                suffix: "](url)".to_string(),
-            },
+            ToolbarItem::Bold => ToolbarAction::wrap("**", "**", "bold text"),
+            ToolbarItem::Italic => ToolbarAction::wrap("_", "_", "italic text"),
+            ToolbarItem::Strikethrough => ToolbarAction::wrap("~~", "~~", "strikethrough"),
+            ToolbarItem::Code => ToolbarAction::wrap("`", "`", "code"),
+            ToolbarItem::CodeBlock => ToolbarAction::wrap("```\n", "\n```", "code"),
+            ToolbarItem::Link => ToolbarAction::wrap("[", "](url)", "link text"),
+            ToolbarItem::Image => ToolbarAction::wrap("![", "](url)", "alt text"),
+            ToolbarItem::Heading => ToolbarAction::PrefixLines("## ".to_string()),
+            ToolbarItem::Quote => ToolbarAction::PrefixLines("> ".to_string()),
+            ToolbarItem::BulletList => ToolbarAction::PrefixLines("- ".to_string()),
+            ToolbarItem::TaskList => ToolbarAction::PrefixLines("- [ ] ".to_string()),
+            ToolbarItem::NumberedList => ToolbarAction::NumberLines,
+            ToolbarItem::HorizontalRule => ToolbarAction::Insert("\n---\n".to_string()),
+            ToolbarItem::Table => {
+                ToolbarAction::Insert("\n| Column | Column |\n| ------ | ------ |\n|        |        |\n".to_string())
+            }
             ToolbarItem::Preview => ToolbarAction::TogglePreview,
             ToolbarItem::Undo => ToolbarAction::Undo,
             ToolbarItem::Redo => ToolbarAction::Redo,
-            _ => ToolbarAction::Insert(String::new()),
+            ToolbarItem::Separator => ToolbarAction::Insert(String::new()),
         }
     }
 }
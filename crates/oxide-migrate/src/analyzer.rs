@@ -4,12 +4,14 @@
 //! inventories components, and calculates migration confidence scores.
 
 use crate::error::{IssueCategory, MigrateError, MigrateResult, MigrationIssue, Severity};
+use crate::tailwind::TailwindTheme;
 use regex::Regex;
 use scraper::{Html, Selector};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::io::Read;
+use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
 /// CSS framework type
@@ -254,10 +256,24 @@ pub struct AnalysisResult {
     pub migration_confidence: f32,
     /// CSS custom properties (variables) found
     pub css_variables: HashMap<String, String>,
+    /// CSS custom properties declared in a dark-theme scope (`.dark`,
+    /// `[data-theme="dark"]`, `[data-bs-theme="dark"]`, `prefers-color-scheme: dark`)
+    #[serde(default)]
+    pub dark_css_variables: HashMap<String, String>,
+    /// Sass/SCSS (`$name`) and Less (`@name`) variables found, unresolved.
+    /// Map entries are flattened to `map-key`.
+    #[serde(default)]
+    pub preprocessor_variables: HashMap<String, String>,
+    /// Theme scale from `tailwind.config.*`, if one was found
+    #[serde(default)]
+    pub tailwind_theme: Option<TailwindTheme>,
     /// Color palette detected
     pub detected_colors: Vec<String>,
     /// Font families detected
     pub detected_fonts: Vec<String>,
+    /// `min-width` values (px) from media queries, one entry per occurrence
+    #[serde(default)]
+    pub media_breakpoints: Vec<f32>,
 }
 
 impl Default for AnalysisResult {
@@ -272,8 +288,12 @@ impl Default for AnalysisResult {
             issues: Vec::new(),
             migration_confidence: 0.0,
             css_variables: HashMap::new(),
+            dark_css_variables: HashMap::new(),
+            preprocessor_variables: HashMap::new(),
+            tailwind_theme: None,
             detected_colors: Vec::new(),
             detected_fonts: Vec::new(),
+            media_breakpoints: Vec::new(),
         }
     }
 }
@@ -320,6 +340,12 @@ pub struct Analyzer {
     component_patterns: HashMap<ComponentType, Vec<Regex>>,
     /// CSS variable pattern
     css_var_pattern: Regex,
+    /// Sass/Less variable declaration pattern
+    preprocessor_var_pattern: Regex,
+    /// `@media (min-width: ...)` pattern
+    media_min_width_pattern: Regex,
+    /// Selector/at-rule conventions that scope a dark palette
+    dark_scope_pattern: Regex,
     /// Font family pattern
     font_pattern: Regex,
 }
@@ -332,6 +358,15 @@ impl Analyzer {
             tailwind_patterns: Self::build_tailwind_patterns()?,
             component_patterns: Self::build_component_patterns()?,
             css_var_pattern: Regex::new(r"--([a-zA-Z0-9_-]+)\s*:\s*([^;]+);")?,
+            dark_scope_pattern: Regex::new(
+                r#"(?i)\.(?:theme-)?dark(?:[\s.:#>+~{,\[)]|$)|\[data-(?:bs-)?(?:theme|mode|color-scheme)\s*=\s*["']?dark["']?\s*\]|prefers-color-scheme\s*:\s*dark"#,
+            )?,
+            media_min_width_pattern: Regex::new(
+                r"@media[^{]*?min-width\s*:\s*(\d+(?:\.\d+)?)\s*(px|em|rem)",
+            )?,
+            preprocessor_var_pattern: Regex::new(
+                r"(?m)^\s*[$@]([a-zA-Z_][a-zA-Z0-9_-]*)\s*:\s*([^;]+);",
+            )?,
             font_pattern: Regex::new(r#"font-family\s*:\s*([^;]+)"#)?,
         })
     }
@@ -595,6 +630,8 @@ impl Analyzer {
 
     /// Analyze a directory
     fn analyze_directory(&self, path: &Path) -> MigrateResult<AnalysisResult> {
+        let root = find_template_root(path);
+        let path = root.as_path();
         let mut result = AnalysisResult::default();
         let mut all_classes = Vec::new();
         let mut html_content = String::new();
@@ -646,6 +683,7 @@ impl Analyzer {
                             css_content.push_str(&content);
 
                             self.extract_css_variables(&content, &mut result);
+                            self.extract_media_breakpoints(&content, &mut result);
                             self.extract_colors(&content, &mut result);
                             self.extract_fonts(&content, &mut result);
                         }
@@ -654,8 +692,30 @@ impl Analyzer {
                         }
                     }
                 }
-                "js" | "ts" | "jsx" | "tsx" => {
+                "scss" | "less" => {
+                    match fs::read_to_string(file_path) {
+                        Ok(content) => {
+                            result.files_analyzed.css_files += 1;
+                            result.files_analyzed.total_bytes += content.len() as u64;
+
+                            self.extract_preprocessor_variables(&content, &mut result);
+                            self.extract_media_breakpoints(&content, &mut result);
+                            self.extract_fonts(&content, &mut result);
+                        }
+                        Err(_) => {
+                            result.files_analyzed.parse_errors += 1;
+                        }
+                    }
+                }
+                "js" | "ts" | "jsx" | "tsx" | "cjs" | "mjs" => {
                     result.files_analyzed.js_files += 1;
+
+                    let file_name = file_path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+                    if TailwindTheme::is_config_file(file_name) {
+                        if let Ok(content) = fs::read_to_string(file_path) {
+                            self.extract_tailwind_config(&content, &relative_path, &mut result);
+                        }
+                    }
                 }
                 _ => {}
             }
@@ -674,67 +734,16 @@ impl Analyzer {
     }
 
     /// Analyze a zip file
+    ///
+    /// The archive is extracted to a temporary directory that is removed once
+    /// analysis finishes.
     fn analyze_zip(&self, path: &Path) -> MigrateResult<AnalysisResult> {
-        let file = fs::File::open(path)?;
-        let mut archive = zip::ZipArchive::new(file)?;
-        let mut result = AnalysisResult::default();
-        let mut all_classes = Vec::new();
-        let mut css_content = String::new();
-
-        for i in 0..archive.len() {
-            let mut file = archive.by_index(i)?;
-            let name = file.name().to_string();
-
-            if file.is_dir() {
-                continue;
-            }
-
-            let extension = Path::new(&name)
-                .extension()
-                .and_then(|e| e.to_str())
-                .unwrap_or("");
+        let temp_dir = tempfile::Builder::new()
+            .prefix("oxide-migrate-")
+            .tempdir()?;
+        extract_zip(path, temp_dir.path())?;
 
-            match extension {
-                "html" | "htm" => {
-                    let mut content = String::new();
-                    if std::io::Read::read_to_string(&mut file, &mut content).is_ok() {
-                        result.files_analyzed.html_files += 1;
-                        result.files_analyzed.total_bytes += content.len() as u64;
-
-                        let classes = self.extract_html_classes(&content);
-                        all_classes.extend(classes);
-
-                        self.detect_components(&content, &name, &mut result);
-                    } else {
-                        result.files_analyzed.parse_errors += 1;
-                    }
-                }
-                "css" => {
-                    let mut content = String::new();
-                    if std::io::Read::read_to_string(&mut file, &mut content).is_ok() {
-                        result.files_analyzed.css_files += 1;
-                        result.files_analyzed.total_bytes += content.len() as u64;
-                        css_content.push_str(&content);
-
-                        self.extract_css_variables(&content, &mut result);
-                        self.extract_colors(&content, &mut result);
-                        self.extract_fonts(&content, &mut result);
-                    } else {
-                        result.files_analyzed.parse_errors += 1;
-                    }
-                }
-                "js" | "ts" | "jsx" | "tsx" => {
-                    result.files_analyzed.js_files += 1;
-                }
-                _ => {}
-            }
-        }
-
-        self.detect_framework(&all_classes, &css_content, &mut result);
-        self.build_inventory(&mut result);
-        result.migration_confidence = self.calculate_migration_confidence(&result);
-
-        Ok(result)
+        self.analyze_directory(temp_dir.path())
     }
 
     /// Extract CSS class names from HTML content
@@ -861,11 +870,126 @@ impl Analyzer {
     }
 
     /// Extract CSS custom properties
+    ///
+    /// Variables inside a dark-theme scope are collected separately into
+    /// `dark_css_variables` so they do not override the default palette.
     fn extract_css_variables(&self, css: &str, result: &mut AnalysisResult) {
-        for caps in self.css_var_pattern.captures_iter(css) {
+        let css = strip_css_comments(css);
+        // Dark flag for each open block
+        let mut scopes: Vec<bool> = Vec::new();
+        let mut segment_start = 0;
+
+        for (i, c) in css.char_indices() {
+            match c {
+                '{' => {
+                    let segment = &css[segment_start..i];
+                    let (declarations, prelude) = match segment.rfind([';', '}']) {
+                        Some(pos) => segment.split_at(pos + 1),
+                        None => ("", segment),
+                    };
+                    let in_dark = scopes.last().copied().unwrap_or(false);
+                    self.collect_css_variables(declarations, in_dark, result);
+
+                    scopes.push(in_dark || self.dark_scope_pattern.is_match(prelude));
+                    segment_start = i + 1;
+                }
+                '}' => {
+                    let in_dark = scopes.pop().unwrap_or(false);
+                    self.collect_css_variables(&css[segment_start..i], in_dark, result);
+                    segment_start = i + 1;
+                }
+                _ => {}
+            }
+        }
+
+        let in_dark = scopes.last().copied().unwrap_or(false);
+        self.collect_css_variables(&css[segment_start..], in_dark, result);
+    }
+
+    /// Extract `min-width` breakpoints from media queries
+    fn extract_media_breakpoints(&self, css: &str, result: &mut AnalysisResult) {
+        for caps in self.media_min_width_pattern.captures_iter(css) {
+            let Ok(value) = caps[1].parse::<f32>() else {
+                continue;
+            };
+            let px = match &caps[2] {
+                "em" | "rem" => value * 16.0,
+                _ => value,
+            };
+            if px > 0.0 {
+                result.media_breakpoints.push(px);
+            }
+        }
+    }
+
+    /// Collect `--name: value` declarations from a block body
+    fn collect_css_variables(&self, declarations: &str, dark: bool, result: &mut AnalysisResult) {
+        if !declarations.contains("--") {
+            return;
+        }
+
+        let mut body = declarations.trim().to_string();
+        if !body.ends_with(';') {
+            body.push(';');
+        }
+
+        let target = if dark {
+            &mut result.dark_css_variables
+        } else {
+            &mut result.css_variables
+        };
+        for caps in self.css_var_pattern.captures_iter(&body) {
             let name = caps.get(1).map_or("", |m| m.as_str()).to_string();
             let value = caps.get(2).map_or("", |m| m.as_str()).trim().to_string();
-            result.css_variables.insert(name, value);
+            target.insert(name, value);
+        }
+    }
+
+    /// Extract Sass (`$name: value;`) and Less (`@name: value;`) variables.
+    ///
+    /// Values are stored unresolved; Sass maps such as
+    /// `$theme-colors: ("primary": $blue)` are flattened to `theme-colors-primary`.
+    fn extract_preprocessor_variables(&self, source: &str, result: &mut AnalysisResult) {
+        for caps in self.preprocessor_var_pattern.captures_iter(source) {
+            let name = caps.get(1).map_or("", |m| m.as_str());
+            let value = caps
+                .get(2)
+                .map_or("", |m| m.as_str())
+                .replace("!default", "")
+                .replace("!global", "")
+                .trim()
+                .to_string();
+
+            if let Some(entries) = value.strip_prefix('(').and_then(|v| v.strip_suffix(')')) {
+                for entry in split_top_level(entries) {
+                    if let Some((key, entry_value)) = entry.split_once(':') {
+                        let key = key.trim().trim_matches(|c| c == '"' || c == '\'');
+                        if !key.is_empty() {
+                            result.preprocessor_variables.insert(
+                                format!("{}-{}", name, key),
+                                entry_value.trim().to_string(),
+                            );
+                        }
+                    }
+                }
+            } else {
+                result.preprocessor_variables.insert(name.to_string(), value);
+            }
+        }
+    }
+
+    /// Parse the theme scale from a Tailwind config
+    fn extract_tailwind_config(&self, source: &str, file: &str, result: &mut AnalysisResult) {
+        match TailwindTheme::parse(source) {
+            Some(theme) if !theme.is_empty() => result.tailwind_theme = Some(theme),
+            Some(_) => {}
+            None => result.issues.push(
+                MigrationIssue::info(
+                    IssueCategory::General,
+                    "Tailwind config has no static theme scale, using Tailwind defaults",
+                )
+                .with_file(file),
+            ),
         }
     }
 
@@ -971,9 +1095,154 @@ impl Default for Analyzer {
     }
 }
 
+/// Remove `/* ... */` comments from CSS
+fn strip_css_comments(css: &str) -> String {
+    let mut out = String::with_capacity(css.len());
+    let mut rest = css;
+
+    while let Some(start) = rest.find("/*") {
+        out.push_str(&rest[..start]);
+        match rest[start + 2..].find("*/") {
+            Some(end) => rest = &rest[start + 2 + end + 2..],
+            None => {
+                rest = "";
+                break;
+            }
+        }
+    }
+    out.push_str(rest);
+
+    out
+}
+
+/// Directory entries ignored when locating the template root
+const IGNORED_ROOT_ENTRIES: &[&str] = &["__MACOSX", ".DS_Store", "Thumbs.db"];
+
+/// Maximum number of single-folder wrappers to descend through
+const MAX_ROOT_DEPTH: usize = 3;
+
+/// Maximum number of entries extracted from a template archive
+const MAX_ARCHIVE_ENTRIES: usize = 10_000;
+
+/// Maximum total uncompressed size extracted from a template archive
+const MAX_ARCHIVE_BYTES: u64 = 512 * 1024 * 1024;
+
+/// Extract a zip archive, rejecting entries that would escape `dest`
+fn extract_zip(archive_path: &Path, dest: &Path) -> MigrateResult<()> {
+    extract_zip_limited(archive_path, dest, MAX_ARCHIVE_ENTRIES, MAX_ARCHIVE_BYTES)
+}
+
+/// Extract a zip archive, failing once it exceeds `max_entries` entries or
+/// `max_bytes` of decompressed data
+fn extract_zip_limited(
+    archive_path: &Path,
+    dest: &Path,
+    max_entries: usize,
+    max_bytes: u64,
+) -> MigrateResult<()> {
+    let file = fs::File::open(archive_path)?;
+    let mut archive = zip::ZipArchive::new(file)?;
+
+    if archive.len() > max_entries {
+        return Err(MigrateError::Archive(format!(
+            "Archive has {} entries, limit is {}",
+            archive.len(),
+            max_entries
+        )));
+    }
+
+    let mut remaining = max_bytes;
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        let Some(relative) = entry.enclosed_name() else {
+            return Err(MigrateError::InvalidPath {
+                path: archive_path.to_path_buf(),
+                reason: format!("Archive entry escapes extraction directory: {}", entry.name()),
+            });
+        };
+        let out_path = dest.join(relative);
+
+        if entry.is_dir() {
+            fs::create_dir_all(&out_path)?;
+        } else {
+            if let Some(parent) = out_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let mut out = fs::File::create(&out_path)?;
+            // Count the bytes actually inflated; header sizes can lie
+            let written = std::io::copy(&mut (&mut entry).take(remaining + 1), &mut out)?;
+            if written > remaining {
+                return Err(MigrateError::Archive(format!(
+                    "Archive expands beyond {} bytes",
+                    max_bytes
+                )));
+            }
+            remaining -= written;
+        }
+    }
+
+    Ok(())
+}
+
+/// Find the real template root when it is wrapped in single folders
+/// (e.g. `theme.zip` containing `theme-v2/index.html`)
+fn find_template_root(path: &Path) -> PathBuf {
+    let mut root = path.to_path_buf();
+
+    for _ in 0..MAX_ROOT_DEPTH {
+        let Ok(entries) = fs::read_dir(&root) else {
+            break;
+        };
+
+        let mut dirs = Vec::new();
+        let mut has_files = false;
+        for entry in entries.filter_map(|e| e.ok()) {
+            let name = entry.file_name();
+            if IGNORED_ROOT_ENTRIES.iter().any(|ignored| name == *ignored) {
+                continue;
+            }
+            if entry.path().is_dir() {
+                dirs.push(entry.path());
+            } else {
+                has_files = true;
+            }
+        }
+
+        if has_files || dirs.len() != 1 {
+            break;
+        }
+        root = dirs.remove(0);
+    }
+
+    root
+}
+
+/// Split on commas that are not nested inside parentheses
+fn split_top_level(input: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+
+    for (i, c) in input.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parts.push(&input[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&input[start..]);
+
+    parts.into_iter().filter(|p| !p.trim().is_empty()).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::io::Write;
 
     #[test]
     fn test_framework_detection_bootstrap() {
@@ -1056,6 +1325,199 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_extract_dark_scoped_variables() {
+        let analyzer = Analyzer::new().unwrap();
+        let mut result = AnalysisResult::default();
+
+        let css = r#"
+            :root { --bg: #ffffff; --fg: #111111 }
+            /* .dark { --bg: #123456; } */
+            .dark { --bg: #000000; }
+            [data-bs-theme="dark"] { --fg: #eeeeee; }
+            .darken { --accent: #ff0000; }
+            .dark-text { --muted: #777777; }
+            .app.theme-dark:hover, .x { --link: #99ccff; }
+            @media (prefers-color-scheme: dark) {
+                :root { --border: #333333; }
+            }
+            @media (min-width: 768px) {
+                :root { --gutter: 24px; }
+            }
+        "#;
+
+        analyzer.extract_css_variables(css, &mut result);
+
+        assert_eq!(result.css_variables.get("bg"), Some(&"#ffffff".to_string()));
+        assert_eq!(result.css_variables.get("fg"), Some(&"#111111".to_string()));
+        assert_eq!(result.css_variables.get("accent"), Some(&"#ff0000".to_string()));
+        assert_eq!(result.css_variables.get("gutter"), Some(&"24px".to_string()));
+        assert_eq!(result.css_variables.get("muted"), Some(&"#777777".to_string()));
+
+        assert_eq!(result.dark_css_variables.get("bg"), Some(&"#000000".to_string()));
+        assert_eq!(result.dark_css_variables.get("fg"), Some(&"#eeeeee".to_string()));
+        assert_eq!(result.dark_css_variables.get("border"), Some(&"#333333".to_string()));
+        assert_eq!(result.dark_css_variables.get("link"), Some(&"#99ccff".to_string()));
+        assert_eq!(result.dark_css_variables.len(), 4);
+    }
+
+    #[test]
+    fn test_extract_media_breakpoints() {
+        let analyzer = Analyzer::new().unwrap();
+        let mut result = AnalysisResult::default();
+
+        let css = r#"
+            @media (min-width: 576px) { .a { color: red; } }
+            @media screen and (min-width: 48em) { .b { color: red; } }
+            @media (max-width: 767.98px) { .c { color: red; } }
+        "#;
+
+        analyzer.extract_media_breakpoints(css, &mut result);
+        assert_eq!(result.media_breakpoints, vec![576.0, 768.0]);
+    }
+
+    #[test]
+    fn test_extract_preprocessor_variables() {
+        let analyzer = Analyzer::new().unwrap();
+        let mut result = AnalysisResult::default();
+
+        let scss = r#"
+            // $commented: red;
+            $blue: #0d6efd !default;
+            $primary: $blue;
+            $theme-colors: (
+                "success": #198754,
+                "danger": rgba(220, 53, 69, 1)
+            );
+            @media (min-width: 768px) { .a { color: red; } }
+            @base-padding: 12px;
+        "#;
+
+        analyzer.extract_preprocessor_variables(scss, &mut result);
+        let vars = &result.preprocessor_variables;
+        assert_eq!(vars.get("blue"), Some(&"#0d6efd".to_string()));
+        assert_eq!(vars.get("primary"), Some(&"$blue".to_string()));
+        assert_eq!(vars.get("theme-colors-success"), Some(&"#198754".to_string()));
+        assert_eq!(
+            vars.get("theme-colors-danger"),
+            Some(&"rgba(220, 53, 69, 1)".to_string())
+        );
+        assert_eq!(vars.get("base-padding"), Some(&"12px".to_string()));
+        assert!(!vars.contains_key("commented"));
+        assert_eq!(vars.len(), 5);
+    }
+
+    fn write_template(root: &Path) {
+        fs::create_dir_all(root.join("assets/css")).unwrap();
+        fs::write(
+            root.join("index.html"),
+            r#"<div class="container"><nav class="navbar navbar-expand-lg"></nav>
+               <div class="card"><div class="card-body"></div></div>
+               <button class="btn btn-primary">Go</button></div>"#,
+        )
+        .unwrap();
+        fs::write(
+            root.join("assets/css/theme.css"),
+            ":root { --primary-color: #3B82F6; }\n.btn { color: #1F2937; }",
+        )
+        .unwrap();
+    }
+
+    fn zip_dir(src: &Path, zip_path: &Path, prefix: &str) {
+        let mut writer = zip::ZipWriter::new(fs::File::create(zip_path).unwrap());
+        let options = zip::write::SimpleFileOptions::default();
+
+        for entry in WalkDir::new(src).into_iter().filter_map(|e| e.ok()) {
+            if !entry.path().is_file() {
+                continue;
+            }
+            let relative = entry.path().strip_prefix(src).unwrap().to_string_lossy();
+            writer
+                .start_file(format!("{}{}", prefix, relative.replace('\\', "/")), options)
+                .unwrap();
+            writer.write_all(&fs::read(entry.path()).unwrap()).unwrap();
+        }
+        writer.finish().unwrap();
+    }
+
+    #[test]
+    fn test_analyze_zip_matches_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let template = dir.path().join("unzipped");
+        write_template(&template);
+
+        let zip_path = dir.path().join("template.zip");
+        zip_dir(&template, &zip_path, "admin-theme-v2/");
+
+        let analyzer = Analyzer::new().unwrap();
+        let from_dir = analyzer.analyze(&template).unwrap();
+        let from_zip = analyzer.analyze(&zip_path).unwrap();
+
+        assert_eq!(from_zip.framework, from_dir.framework);
+        assert_eq!(from_zip.css_variables, from_dir.css_variables);
+        assert_eq!(from_zip.files_analyzed.html_files, 1);
+        assert_eq!(from_zip.files_analyzed.css_files, 1);
+        assert_eq!(from_zip.components.len(), from_dir.components.len());
+        assert_eq!(
+            from_zip.components.iter().map(|c| c.source_file.clone()).collect::<Vec<_>>(),
+            from_dir.components.iter().map(|c| c.source_file.clone()).collect::<Vec<_>>()
+        );
+        assert_eq!(from_zip.migration_confidence, from_dir.migration_confidence);
+    }
+
+    #[test]
+    fn test_find_template_root_nested() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("theme/dist");
+        write_template(&nested);
+        fs::create_dir_all(dir.path().join("__MACOSX")).unwrap();
+
+        assert_eq!(find_template_root(dir.path()), nested);
+        assert_eq!(find_template_root(&nested), nested);
+    }
+
+    #[test]
+    fn test_zip_slip_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let zip_path = dir.path().join("evil.zip");
+
+        let mut writer = zip::ZipWriter::new(fs::File::create(&zip_path).unwrap());
+        writer
+            .start_file("../evil.html", zip::write::SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(b"<div class=\"btn\"></div>").unwrap();
+        writer.finish().unwrap();
+
+        let err = Analyzer::new().unwrap().analyze(&zip_path).unwrap_err();
+        assert!(matches!(err, MigrateError::InvalidPath { .. }));
+        assert!(!dir.path().join("evil.html").exists());
+    }
+
+    #[test]
+    fn test_zip_limits_enforced() {
+        let dir = tempfile::tempdir().unwrap();
+        let zip_path = dir.path().join("big.zip");
+
+        let mut writer = zip::ZipWriter::new(fs::File::create(&zip_path).unwrap());
+        for name in ["a.html", "b.html", "c.html"] {
+            writer
+                .start_file(name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(&[b' '; 100]).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let out = dir.path().join("out");
+        let err = extract_zip_limited(&zip_path, &out, 2, 1000).unwrap_err();
+        assert!(matches!(err, MigrateError::Archive(_)));
+
+        let err = extract_zip_limited(&zip_path, &out, 10, 250).unwrap_err();
+        assert!(matches!(err, MigrateError::Archive(_)));
+        assert!(fs::metadata(out.join("c.html")).unwrap().len() <= 51);
+
+        extract_zip_limited(&zip_path, &out, 3, 300).unwrap();
+    }
+
     #[test]
     fn test_framework_version_parse() {
         let v1 = FrameworkVersion::parse("5.3.2");
@@ -3,13 +3,26 @@
 //! Generates complete OxideKit project output from analysis, tokens, and mappings.
 //! Creates starter-compatible projects with extracted themes and placeholder pages.
 
-use crate::analyzer::AnalysisResult;
+use crate::analyzer::{AnalysisResult, ComponentType};
 use crate::error::{IssueCategory, MigrateError, MigrateResult, MigrationIssue};
-use crate::mapper::{MappingResult, LayoutPattern};
+use crate::mapper::{Breakpoint, ComponentMapper, LayoutMapping, LayoutPattern, MappingResult};
 use crate::tokens::ExtractedTokens;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// Manifest recording the files written by the migrator, relative to the output directory
+pub const MIGRATION_MANIFEST_FILE: &str = ".oxide-migrate.json";
+
+/// Suffix for newly generated content that conflicts with a user-edited file
+pub const CONFLICT_SUFFIX: &str = ".migrate-new";
+
+/// Media query widths closer than this (in px) are treated as one breakpoint
+const BREAKPOINT_MERGE_TOLERANCE: f32 = 8.0;
+
+/// Names assigned to extracted breakpoints, smallest first
+const BREAKPOINT_NAMES: &[&str] = &["sm", "md", "lg", "xl", "2xl", "3xl"];
 
 /// Migration output configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,6 +41,15 @@ pub struct MigrationConfig {
     pub include_todos: bool,
     /// Overwrite existing files
     pub overwrite: bool,
+    /// How to treat files that already exist from a previous migration
+    #[serde(default)]
+    pub merge_mode: MergeMode,
+    /// Only migrate these component types (empty = all)
+    #[serde(default)]
+    pub include_components: Vec<ComponentType>,
+    /// Never migrate these component types
+    #[serde(default)]
+    pub exclude_components: Vec<ComponentType>,
 }
 
 impl Default for MigrationConfig {
@@ -40,8 +62,117 @@ impl Default for MigrationConfig {
             generate_placeholders: true,
             include_todos: true,
             overwrite: false,
+            merge_mode: MergeMode::Overwrite,
+            include_components: Vec::new(),
+            exclude_components: Vec::new(),
+        }
+    }
+}
+
+impl MigrationConfig {
+    /// Check if a component type passes the include/exclude filter
+    pub fn includes_component(&self, component_type: &ComponentType) -> bool {
+        (self.include_components.is_empty() || self.include_components.contains(component_type))
+            && !self.exclude_components.contains(component_type)
+    }
+
+    /// Drop the sidebar and navbar from a layout when they are filtered out
+    pub fn filter_layout(&self, layout: &LayoutMapping) -> LayoutMapping {
+        let sidebar = self.includes_component(&ComponentType::Sidebar);
+        let navbar = self.includes_component(&ComponentType::Navbar);
+        let pattern = match layout.pattern {
+            LayoutPattern::FullDashboard => match (sidebar, navbar) {
+                (true, true) => LayoutPattern::FullDashboard,
+                (true, false) => LayoutPattern::SidebarLayout,
+                (false, true) => LayoutPattern::NavbarLayout,
+                (false, false) => LayoutPattern::SingleColumn,
+            },
+            LayoutPattern::SidebarLayout if !sidebar => LayoutPattern::SingleColumn,
+            LayoutPattern::NavbarLayout if !navbar => LayoutPattern::SingleColumn,
+            pattern => pattern,
+        };
+
+        LayoutMapping {
+            pattern,
+            sidebar: layout.sidebar.clone().filter(|_| sidebar),
+            navbar: layout.navbar.clone().filter(|_| navbar),
+            ..layout.clone()
+        }
+    }
+}
+
+/// Re-migration behavior for existing output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MergeMode {
+    /// Write every generated file (subject to `overwrite`)
+    #[default]
+    Overwrite,
+    /// Keep files edited since the last migration; conflicting regenerations
+    /// are written next to them with a `.migrate-new` suffix
+    PreserveEdits,
+}
+
+/// Hashes of the files written by the last migration run
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MigrationManifest {
+    /// Content hash per relative path (forward slashes)
+    pub files: BTreeMap<String, String>,
+}
+
+impl MigrationManifest {
+    /// Load the manifest from an output directory (empty if missing)
+    pub fn load(output_dir: &Path) -> MigrateResult<Self> {
+        let path = output_dir.join(MIGRATION_MANIFEST_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Save the manifest into an output directory
+    pub fn save(&self, output_dir: &Path) -> MigrateResult<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(output_dir.join(MIGRATION_MANIFEST_FILE), json)?;
+        Ok(())
+    }
+
+    /// Hash recorded for a path
+    pub fn hash_of(&self, path: &Path) -> Option<&str> {
+        self.files.get(&manifest_key(path)).map(|s| s.as_str())
+    }
+}
+
+/// Normalize a relative path for use as a manifest key
+fn manifest_key(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+/// Convert a component ID (`DataTable`) to a file stem (`data_table`)
+fn to_snake_case(id: &str) -> String {
+    let mut out = String::with_capacity(id.len() + 4);
+    for (i, c) in id.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
         }
     }
+    out
+}
+
+/// Stable FNV-1a content hash (independent of the Rust version)
+pub(crate) fn content_hash(content: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in content.as_bytes() {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", hash)
 }
 
 /// Available starter templates
@@ -105,6 +236,9 @@ pub struct MigrationOutput {
     pub issues: Vec<MigrationIssue>,
     /// Summary statistics
     pub summary: MigrationSummary,
+    /// Existing files left untouched because the user edited them
+    #[serde(default)]
+    pub preserved: Vec<PathBuf>,
 }
 
 /// A generated file
@@ -281,6 +415,7 @@ impl Converter {
             todos: Vec::new(),
             issues: Vec::new(),
             summary: MigrationSummary::default(),
+            preserved: Vec::new(),
         };
 
         // Create directory structure
@@ -290,7 +425,7 @@ impl Converter {
         self.generate_manifest(analysis, &mut output)?;
 
         // Generate theme files
-        self.generate_theme_files(tokens, &mut output)?;
+        self.generate_theme_files(analysis, tokens, &mut output)?;
 
         // Generate design pack parts
         self.generate_design_parts(mappings, &mut output)?;
@@ -298,6 +433,9 @@ impl Converter {
         // Generate layout files
         self.generate_layouts(mappings, &mut output)?;
 
+        // Generate .oui sources for mapped components
+        self.generate_components(mappings, &mut output)?;
+
         // Generate placeholder pages
         if self.config.generate_placeholders {
             self.generate_placeholder_pages(analysis, mappings, &mut output)?;
@@ -311,6 +449,11 @@ impl Converter {
         // Generate migration documentation
         self.generate_documentation(analysis, mappings, &mut output)?;
 
+        // Keep user edits from a previous run
+        if self.config.merge_mode == MergeMode::PreserveEdits {
+            self.reconcile_existing(&mut output)?;
+        }
+
         // Calculate summary
         self.calculate_summary(analysis, mappings, &mut output);
 
@@ -318,8 +461,14 @@ impl Converter {
     }
 
     /// Write migration output to filesystem
+    ///
+    /// Also records a manifest of content hashes so a later run in
+    /// `MergeMode::PreserveEdits` can tell which files the user modified.
     pub fn write_output(&self, output: &MigrationOutput) -> MigrateResult<()> {
         let base_path = &self.config.output_dir;
+        let preserve_edits = self.config.merge_mode == MergeMode::PreserveEdits;
+        let previous = MigrationManifest::load(base_path)?;
+        let mut manifest = MigrationManifest::default();
 
         // Create directories
         for dir in &output.directories {
@@ -333,8 +482,9 @@ impl Converter {
         for file in &output.files {
             let full_path = base_path.join(&file.path);
 
-            // Check if file exists and overwrite is disabled
-            if full_path.exists() && !self.config.overwrite {
+            // Check if file exists and overwrite is disabled. In merge mode the
+            // output has already been reconciled against the existing files.
+            if full_path.exists() && !self.config.overwrite && !preserve_edits {
                 return Err(MigrateError::OutputGeneration(format!(
                     "File already exists and overwrite is disabled: {}",
                     full_path.display()
@@ -347,6 +497,97 @@ impl Converter {
             }
 
             fs::write(&full_path, &file.content)?;
+
+            if !manifest_key(&file.path).ends_with(CONFLICT_SUFFIX) {
+                manifest
+                    .files
+                    .insert(manifest_key(&file.path), content_hash(&file.content));
+            }
+        }
+
+        // Preserved files keep their previous base hash so later runs still
+        // see them as edited
+        for path in &output.preserved {
+            if let Some(hash) = previous.hash_of(path) {
+                manifest.files.insert(manifest_key(path), hash.to_string());
+            }
+        }
+
+        fs::create_dir_all(base_path)?;
+        manifest.save(base_path)
+    }
+
+    /// Compare generated files against the output directory and drop any the
+    /// user has edited since the last migration.
+    ///
+    /// - Missing or unmodified managed files are (re)written.
+    /// - Edited files whose generated content did not change are kept as-is.
+    /// - Edited files whose generated content changed are kept, the new
+    ///   content is written alongside with a `.migrate-new` suffix, and a
+    ///   conflict TODO is recorded.
+    fn reconcile_existing(&self, output: &mut MigrationOutput) -> MigrateResult<()> {
+        let base_path = &self.config.output_dir;
+        let manifest = MigrationManifest::load(base_path)?;
+        let mut files = Vec::with_capacity(output.files.len());
+
+        for file in std::mem::take(&mut output.files) {
+            let Ok(on_disk) = fs::read_to_string(base_path.join(&file.path)) else {
+                files.push(file);
+                continue;
+            };
+
+            let disk_hash = content_hash(&on_disk);
+            match manifest.hash_of(&file.path) {
+                Some(base) if base == disk_hash => files.push(file),
+                Some(base) if base == content_hash(&file.content) => {
+                    output.preserved.push(file.path);
+                }
+                _ if disk_hash == content_hash(&file.content) => files.push(file),
+                base => {
+                    let mut conflict_path = file.path.clone().into_os_string();
+                    conflict_path.push(CONFLICT_SUFFIX);
+                    let conflict_path = PathBuf::from(conflict_path);
+
+                    output.todos.push(TodoItem {
+                        description: if base.is_some() {
+                            format!(
+                                "Merge conflict: {} was edited after migration; compare with {}",
+                                file.path.display(),
+                                conflict_path.display()
+                            )
+                        } else {
+                            format!(
+                                "Merge conflict: {} was not created by the migrator; compare with {}",
+                                file.path.display(),
+                                conflict_path.display()
+                            )
+                        },
+                        priority: 1,
+                        category: TodoCategory::Integration,
+                        file: Some(file.path.clone()),
+                        effort: Effort::Low,
+                    });
+
+                    output.preserved.push(file.path.clone());
+                    files.push(GeneratedFile {
+                        path: conflict_path,
+                        needs_review: true,
+                        ..file
+                    });
+                }
+            }
+        }
+
+        output.files = files;
+
+        // Keep the generated TODO list in sync with any conflicts found above
+        let todos_md = self.generate_todos_markdown(&output.todos);
+        if let Some(todos_file) = output
+            .files
+            .iter_mut()
+            .find(|f| f.path == Path::new("TODOS.md"))
+        {
+            todos_file.content = todos_md;
         }
 
         Ok(())
@@ -441,6 +682,7 @@ generated_at = "{}"
     /// Generate theme files
     fn generate_theme_files(
         &self,
+        analysis: &AnalysisResult,
         tokens: &ExtractedTokens,
         output: &mut MigrationOutput,
     ) -> MigrateResult<()> {
@@ -454,6 +696,29 @@ generated_at = "{}"
             needs_review: tokens.confidence.overall < 0.7,
         });
 
+        // Responsive breakpoints from source media queries
+        let breakpoints = self.extract_breakpoints(analysis);
+        if !breakpoints.is_empty() {
+            output.files.push(GeneratedFile {
+                path: PathBuf::from("theme/breakpoints.generated.toml"),
+                content: self.generate_breakpoints_toml(&breakpoints),
+                file_type: FileType::Theme,
+                confidence: 0.8,
+                needs_review: false,
+            });
+        }
+
+        // Dark variant, if the source ships one
+        if let Some(dark_theme) = &tokens.dark_theme {
+            output.files.push(GeneratedFile {
+                path: PathBuf::from("theme/theme.dark.generated.toml"),
+                content: toml::to_string_pretty(dark_theme)?,
+                file_type: FileType::Theme,
+                confidence: tokens.confidence.colors,
+                needs_review: true,
+            });
+        }
+
         // Typography file
         let typography_toml = toml::to_string_pretty(&tokens.typography)?;
         output.files.push(GeneratedFile {
@@ -477,13 +742,100 @@ generated_at = "{}"
         Ok(())
     }
 
+    /// Cluster media query widths into a breakpoint set
+    ///
+    /// Widths within `BREAKPOINT_MERGE_TOLERANCE` of each other (e.g. `768px`
+    /// and `767.98px`) are merged, keeping the most frequently used value.
+    fn extract_breakpoints(&self, analysis: &AnalysisResult) -> Vec<Breakpoint> {
+        let mut widths: Vec<f32> = analysis
+            .media_breakpoints
+            .iter()
+            .map(|w| (w * 100.0).round() / 100.0)
+            .collect();
+        widths.sort_by(|a, b| a.total_cmp(b));
+
+        let mut clusters: Vec<Vec<f32>> = Vec::new();
+        for width in widths {
+            match clusters.last_mut() {
+                Some(cluster) if width - cluster[0] <= BREAKPOINT_MERGE_TOLERANCE => cluster.push(width),
+                _ => clusters.push(vec![width]),
+            }
+        }
+
+        clusters
+            .into_iter()
+            .enumerate()
+            .map(|(i, cluster)| {
+                // Most used wins; ties prefer whole-pixel values over `.98` variants
+                let score = |candidate: f32| {
+                    let count = cluster.iter().filter(|&&w| w == candidate).count();
+                    (count, candidate.fract() == 0.0)
+                };
+                let mut best = cluster[0];
+                for &candidate in &cluster[1..] {
+                    if score(candidate) > score(best) {
+                        best = candidate;
+                    }
+                }
+
+                Breakpoint {
+                    name: BREAKPOINT_NAMES
+                        .get(i)
+                        .map(|n| n.to_string())
+                        .unwrap_or_else(|| format!("bp{}", i + 1)),
+                    min_width: best,
+                }
+            })
+            .collect()
+    }
+
+    fn generate_breakpoints_toml(&self, breakpoints: &[Breakpoint]) -> String {
+        let mut toml = String::from(
+            "# Responsive Breakpoints\n# Extracted from source media queries by oxide-migrate\n#\n# `oxide` is the nearest built-in OxideKit breakpoint (mobile/tablet/desktop/wide).\n",
+        );
+
+        for breakpoint in breakpoints {
+            let oxide = oxide_components::responsive::Breakpoint::from_width(breakpoint.min_width);
+            toml.push_str(&format!(
+                "\n[[breakpoints]]\nname = \"{}\"\nmin_width = {:?}\noxide = \"{}\"\n",
+                breakpoint.name,
+                breakpoint.min_width,
+                oxide.name()
+            ));
+        }
+
+        toml
+    }
+
+    /// Check if a design part comes from at least one included component type
+    fn includes_design_part(&self, tag: &str, mappings: &MappingResult) -> bool {
+        let sources: &[ComponentType] = match tag {
+            "part:sidebar" => return self.config.includes_component(&ComponentType::Sidebar),
+            "part:navbar" => return self.config.includes_component(&ComponentType::Navbar),
+            "part:datatable" => &[ComponentType::Table, ComponentType::DataTable],
+            "part:stat_cards" => &[ComponentType::Statistics, ComponentType::Card],
+            "part:login_form" => &[ComponentType::Form, ComponentType::Input],
+            _ => return true,
+        };
+        mappings.components.iter().any(|mapping| {
+            sources.contains(&mapping.source_type)
+                && self.config.includes_component(&mapping.source_type)
+        })
+    }
+
     /// Generate design pack parts
     fn generate_design_parts(
         &self,
         mappings: &MappingResult,
         output: &mut MigrationOutput,
     ) -> MigrateResult<()> {
-        for part in &mappings.design_parts {
+        let parts: Vec<_> = mappings
+            .design_parts
+            .iter()
+            .filter(|part| self.includes_design_part(&part.tag, mappings))
+            .collect();
+
+        for part in &parts {
             let file_name = part.tag.replace("part:", "") + ".toml";
             output.files.push(GeneratedFile {
                 path: PathBuf::from(format!("design/parts/{}", file_name)),
@@ -495,8 +847,7 @@ generated_at = "{}"
         }
 
         // Generate main design pack index
-        let parts_list: Vec<String> = mappings
-            .design_parts
+        let parts_list: Vec<String> = parts
             .iter()
             .map(|p| format!("\"parts/{}\"", p.tag.replace("part:", "") + ".toml"))
             .collect();
@@ -520,7 +871,7 @@ pattern = "{:?}"
 "#,
             self.config.project_name,
             parts_list.join(",\n    "),
-            mappings.layout.pattern,
+            self.config.filter_layout(&mappings.layout).pattern,
         );
 
         output.files.push(GeneratedFile {
@@ -540,7 +891,7 @@ pattern = "{:?}"
         mappings: &MappingResult,
         output: &mut MigrationOutput,
     ) -> MigrateResult<()> {
-        let layout = &mappings.layout;
+        let layout = &self.config.filter_layout(&mappings.layout);
 
         // Generate main layout based on pattern
         let main_layout = match layout.pattern {
@@ -565,6 +916,65 @@ pattern = "{:?}"
         Ok(())
     }
 
+    /// Generate `.oui` component files from component mappings
+    fn generate_components(
+        &self,
+        mappings: &MappingResult,
+        output: &mut MigrationOutput,
+    ) -> MigrateResult<()> {
+        let mapper = ComponentMapper::new();
+        let mut seen = HashSet::new();
+        let mut skipped = HashSet::new();
+
+        for mapping in &mappings.components {
+            if !self.config.includes_component(&mapping.source_type) {
+                if self.config.include_todos && skipped.insert(mapping.source_type.clone()) {
+                    output.todos.push(TodoItem {
+                        description: format!(
+                            "Skipped {} (excluded by migration filter)",
+                            mapping.source_type
+                        ),
+                        priority: 3,
+                        category: TodoCategory::Component,
+                        file: None,
+                        effort: Effort::Low,
+                    });
+                }
+                continue;
+            }
+
+            let component = mapper.generate_oui(mapping);
+            if component.id.is_empty() || !seen.insert(component.id.clone()) {
+                continue;
+            }
+
+            let path = PathBuf::from(format!("src/components/{}.oui", to_snake_case(&component.id)));
+
+            if component.placeholder && self.config.include_todos {
+                output.todos.push(TodoItem {
+                    description: format!(
+                        "Implement {} (no OxideKit equivalent, placeholder generated)",
+                        mapping.source_type
+                    ),
+                    priority: 2,
+                    category: TodoCategory::Component,
+                    file: Some(path.clone()),
+                    effort: Effort::Medium,
+                });
+            }
+
+            output.files.push(GeneratedFile {
+                path,
+                content: component.source,
+                file_type: FileType::Component,
+                confidence: if component.placeholder { 0.0 } else { mapping.confidence },
+                needs_review: mapping.needs_review || component.placeholder,
+            });
+        }
+
+        Ok(())
+    }
+
     fn generate_dashboard_layout(&self, layout: &crate::mapper::LayoutMapping) -> String {
         let sidebar_width = layout
             .sidebar
@@ -748,7 +1158,8 @@ pattern = "{:?}"
         let has_tables = analysis
             .components
             .iter()
-            .any(|c| c.component_type == crate::analyzer::ComponentType::Table);
+            .any(|c| c.component_type == ComponentType::Table)
+            && self.config.includes_component(&ComponentType::Table);
         if has_tables {
             let table_page = self.generate_table_page();
             output.files.push(GeneratedFile {
@@ -764,7 +1175,8 @@ pattern = "{:?}"
         let has_forms = analysis
             .components
             .iter()
-            .any(|c| c.component_type == crate::analyzer::ComponentType::Form);
+            .any(|c| c.component_type == ComponentType::Form)
+            && self.config.includes_component(&ComponentType::Form);
         if has_forms {
             let form_page = self.generate_form_page();
             output.files.push(GeneratedFile {
@@ -985,7 +1397,7 @@ pattern = "{:?}"
 
         // Add TODOs for components needing manual review
         for mapping in &mappings.components {
-            if mapping.needs_review {
+            if mapping.needs_review && self.config.includes_component(&mapping.source_type) {
                 output.todos.push(TodoItem {
                     description: format!(
                         "Review mapping for {:?} -> {}",
@@ -1258,7 +1670,7 @@ impl Default for Converter {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::analyzer::ComponentType;
+    use crate::tokens::ExtractedTokens;
 
     #[test]
     fn test_starter_template_from_str() {
@@ -1298,6 +1710,7 @@ mod tests {
             todos: Vec::new(),
             issues: Vec::new(),
             summary: MigrationSummary::default(),
+            preserved: Vec::new(),
         };
 
         converter.create_directories(&mut output).unwrap();
@@ -1334,4 +1747,256 @@ mod tests {
         assert!(md.contains("Priority 1"));
         assert!(md.contains("Priority 2"));
     }
+
+    fn test_tokens() -> ExtractedTokens {
+        ExtractedTokens {
+            theme: oxide_components::theme::Theme::dark(),
+            typography: Default::default(),
+            fonts: Default::default(),
+            name_mapping: Default::default(),
+            issues: Vec::new(),
+            confidence: Default::default(),
+            dark_theme: None,
+        }
+    }
+
+    fn test_mappings() -> MappingResult {
+        MappingResult {
+            layout: Default::default(),
+            components: Vec::new(),
+            design_parts: Vec::new(),
+            issues: Vec::new(),
+            confidence: 0.85,
+        }
+    }
+
+    fn run(converter: &Converter) -> MigrationOutput {
+        let output = converter
+            .convert(&AnalysisResult::default(), &test_tokens(), &test_mappings())
+            .unwrap();
+        converter.write_output(&output).unwrap();
+        output
+    }
+
+    #[test]
+    fn test_breakpoints_from_media_queries() {
+        let analysis = AnalysisResult {
+            // sm, md (twice + a near duplicate), lg, xl
+            media_breakpoints: vec![576.0, 768.0, 767.98, 768.0, 992.0, 1200.0, 1199.98],
+            ..Default::default()
+        };
+
+        let converter = Converter::with_defaults();
+        let breakpoints = converter.extract_breakpoints(&analysis);
+        let widths: Vec<(String, f32)> = breakpoints
+            .iter()
+            .map(|b| (b.name.clone(), b.min_width))
+            .collect();
+
+        assert_eq!(
+            widths,
+            vec![
+                ("sm".to_string(), 576.0),
+                ("md".to_string(), 768.0),
+                ("lg".to_string(), 992.0),
+                ("xl".to_string(), 1200.0),
+            ]
+        );
+
+        let output = converter
+            .convert(&analysis, &test_tokens(), &test_mappings())
+            .unwrap();
+        let file = output
+            .files
+            .iter()
+            .find(|f| f.path == Path::new("theme/breakpoints.generated.toml"))
+            .unwrap();
+        assert!(file.content.contains("name = \"md\"\nmin_width = 768.0\noxide = \"tablet\""));
+        assert!(file.content.contains("name = \"lg\"\nmin_width = 992.0\noxide = \"tablet\""));
+        assert!(file.content.contains("name = \"xl\"\nmin_width = 1200.0\noxide = \"desktop\""));
+    }
+
+    #[test]
+    fn test_convert_generates_oui_components() {
+        let mapper = ComponentMapper::new();
+        let analysis = AnalysisResult {
+            framework: crate::analyzer::Framework::Bootstrap,
+            components: vec![
+                crate::analyzer::DetectedComponent::new(ComponentType::Navbar)
+                    .with_classes(vec!["navbar".into(), "navbar-brand".into()]),
+                crate::analyzer::DetectedComponent::new(ComponentType::Custom("mega-menu".into())),
+            ],
+            ..Default::default()
+        };
+        let mappings = mapper.map(&analysis).unwrap();
+
+        let converter = Converter::with_defaults();
+        let output = converter
+            .convert(&analysis, &test_tokens(), &mappings)
+            .unwrap();
+
+        let navbar = output
+            .files
+            .iter()
+            .find(|f| f.path == Path::new("src/components/navbar.oui"))
+            .unwrap();
+        assert_eq!(navbar.file_type, FileType::Component);
+        assert!(navbar.content.contains("render: Row {"));
+
+        assert!(output
+            .files
+            .iter()
+            .any(|f| f.path == Path::new("src/components/mega_menu.oui") && f.needs_review));
+        assert!(output.todos.iter().any(|t| {
+            t.category == TodoCategory::Component
+                && t.file.as_deref() == Some(Path::new("src/components/mega_menu.oui"))
+        }));
+    }
+
+    #[test]
+    fn test_selective_migration_by_component_type() {
+        let mapper = ComponentMapper::new();
+        let analysis = AnalysisResult {
+            framework: crate::analyzer::Framework::Bootstrap,
+            components: vec![
+                crate::analyzer::DetectedComponent::new(ComponentType::Navbar),
+                crate::analyzer::DetectedComponent::new(ComponentType::Form),
+                crate::analyzer::DetectedComponent::new(ComponentType::Card),
+                crate::analyzer::DetectedComponent::new(ComponentType::Table),
+                crate::analyzer::DetectedComponent::new(ComponentType::Table),
+            ],
+            ..Default::default()
+        };
+        let mappings = mapper.map(&analysis).unwrap();
+
+        let converter = Converter::new(MigrationConfig {
+            include_components: vec![ComponentType::Navbar, ComponentType::Form],
+            ..Default::default()
+        });
+        let output = converter
+            .convert(&analysis, &test_tokens(), &mappings)
+            .unwrap();
+
+        let components: Vec<&Path> = output
+            .files
+            .iter()
+            .filter(|f| f.file_type == FileType::Component)
+            .map(|f| f.path.as_path())
+            .collect();
+        assert!(components.contains(&Path::new("src/components/navbar.oui")));
+        assert!(components.contains(&Path::new("src/components/form.oui")));
+        assert_eq!(components.len(), 2);
+
+        // Table page is tied to the skipped Table component
+        assert!(output.files.iter().any(|f| f.path == Path::new("src/pages/form-example.oxide")));
+        assert!(!output.files.iter().any(|f| f.path == Path::new("src/pages/data-list.oxide")));
+
+        let skipped: Vec<&str> = output
+            .todos
+            .iter()
+            .filter(|t| t.description.starts_with("Skipped "))
+            .map(|t| t.description.as_str())
+            .collect();
+        assert_eq!(skipped.len(), 2);
+        assert!(skipped.iter().any(|d| d.contains(&ComponentType::Card.to_string())));
+        assert!(skipped.iter().any(|d| d.contains(&ComponentType::Table.to_string())));
+    }
+
+    #[test]
+    fn test_excluded_layout_parts_are_not_generated() {
+        let mapper = ComponentMapper::new();
+        let analysis = AnalysisResult {
+            framework: crate::analyzer::Framework::Bootstrap,
+            components: vec![
+                crate::analyzer::DetectedComponent::new(ComponentType::Navbar),
+                crate::analyzer::DetectedComponent::new(ComponentType::Sidebar),
+                crate::analyzer::DetectedComponent::new(ComponentType::Table),
+            ],
+            ..Default::default()
+        };
+        let mappings = mapper.map(&analysis).unwrap();
+        assert_eq!(mappings.layout.pattern, LayoutPattern::FullDashboard);
+
+        let converter = Converter::new(MigrationConfig {
+            exclude_components: vec![ComponentType::Sidebar, ComponentType::Table],
+            ..Default::default()
+        });
+        let output = converter
+            .convert(&analysis, &test_tokens(), &mappings)
+            .unwrap();
+        let file = |path: &str| output.files.iter().find(|f| f.path == Path::new(path));
+
+        let layout = file("src/layouts/main.oxide").unwrap();
+        assert!(layout.content.contains("<layout name=\"navbar\">"));
+        assert!(!layout.content.contains("ui.Sidenav"));
+
+        assert!(file("design/parts/navbar.toml").is_some());
+        assert!(file("design/parts/sidebar.toml").is_none());
+        assert!(file("design/parts/datatable.toml").is_none());
+        let index = &file("design/design-pack.toml").unwrap().content;
+        assert!(!index.contains("parts/sidebar.toml"));
+        assert!(index.contains("pattern = \"NavbarLayout\""));
+    }
+
+    #[test]
+    fn test_component_filter_exclude_wins() {
+        let config = MigrationConfig {
+            include_components: vec![ComponentType::Navbar, ComponentType::Form],
+            exclude_components: vec![ComponentType::Form],
+            ..Default::default()
+        };
+        assert!(config.includes_component(&ComponentType::Navbar));
+        assert!(!config.includes_component(&ComponentType::Form));
+        assert!(!config.includes_component(&ComponentType::Card));
+        assert!(MigrationConfig::default().includes_component(&ComponentType::Card));
+    }
+
+    #[test]
+    fn test_remigration_preserves_edits() {
+        let dir = tempfile::tempdir().unwrap();
+        let converter = Converter::new(MigrationConfig {
+            output_dir: dir.path().to_path_buf(),
+            merge_mode: MergeMode::PreserveEdits,
+            ..Default::default()
+        });
+
+        run(&converter);
+        assert!(dir.path().join(MIGRATION_MANIFEST_FILE).exists());
+
+        let layout = dir.path().join("src/layouts/main.oxide");
+        fs::write(&layout, "// hand-edited layout\n").unwrap();
+
+        let second = run(&converter);
+        assert!(second.preserved.contains(&PathBuf::from("src/layouts/main.oxide")));
+        assert_eq!(fs::read_to_string(&layout).unwrap(), "// hand-edited layout\n");
+
+        // Still detected as edited on a third run
+        let third = run(&converter);
+        assert!(third.preserved.contains(&PathBuf::from("src/layouts/main.oxide")));
+    }
+
+    #[test]
+    fn test_remigration_records_conflict() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = MigrationConfig {
+            output_dir: dir.path().to_path_buf(),
+            merge_mode: MergeMode::PreserveEdits,
+            ..Default::default()
+        };
+
+        run(&Converter::new(config.clone()));
+        let manifest = dir.path().join("oxide.toml");
+        fs::write(&manifest, "# my own manifest\n").unwrap();
+
+        // A different project name changes the generated oxide.toml
+        let second = run(&Converter::new(config).project_name("renamed-app"));
+
+        assert_eq!(fs::read_to_string(&manifest).unwrap(), "# my own manifest\n");
+        let conflict = fs::read_to_string(dir.path().join("oxide.toml.migrate-new")).unwrap();
+        assert!(conflict.contains("renamed-app"));
+        assert!(second
+            .todos
+            .iter()
+            .any(|t| t.description.starts_with("Merge conflict: oxide.toml")));
+    }
 }
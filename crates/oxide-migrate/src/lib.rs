@@ -113,6 +113,7 @@ pub mod converter;
 pub mod error;
 pub mod mapper;
 pub mod report;
+pub mod tailwind;
 pub mod tokens;
 
 pub use analyzer::{
@@ -120,15 +121,16 @@ pub use analyzer::{
     FileAnalysisSummary, Framework, FrameworkVersion,
 };
 pub use converter::{
-    Converter, FileType, GeneratedFile, MigrationConfig, MigrationOutput, MigrationSummary,
-    StarterTemplate, TodoCategory, TodoItem,
+    Converter, FileType, GeneratedFile, MergeMode, MigrationConfig, MigrationManifest,
+    MigrationOutput, MigrationSummary, StarterTemplate, TodoCategory, TodoItem,
 };
 pub use error::{IssueCategory, MigrateError, MigrateResult, MigrationIssue, Severity};
 pub use mapper::{
     ComponentMapper, ComponentMapping, DesignPart, LayoutMapping, LayoutPattern, MappingResult,
-    NavbarConfig, SidebarConfig,
+    NavbarConfig, OuiComponent, SidebarConfig,
 };
-pub use report::{MigrationReport, ReportGenerator};
+pub use report::{JsonReport, MigrationReport, ReportGenerator, REPORT_SCHEMA_VERSION};
+pub use tailwind::TailwindTheme;
 pub use tokens::{
     ExtractedFonts, ExtractedTokens, ExtractedTypography, TokenConfidence, TokenExtractor,
 };
@@ -249,13 +251,15 @@ impl Migrator {
         result: &MigrationResult,
         source_path: &Path,
     ) -> MigrateResult<MigrationReport> {
-        self.report_generator.generate(
+        let report = self.report_generator.generate(
             &result.analysis,
             &result.tokens,
             &result.mappings,
             source_path,
             Some(&self.config.output_dir),
-        )
+        )?;
+
+        Ok(report.with_output(&result.output))
     }
 
     /// Get the configuration
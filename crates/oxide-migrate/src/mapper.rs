@@ -207,6 +207,66 @@ pub struct ComponentMapping {
     pub notes: Vec<String>,
 }
 
+/// Generated `.oui` source for a mapped component
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OuiComponent {
+    /// Component ID (e.g., "Navbar")
+    pub id: String,
+    /// `.oui` source code
+    pub source: String,
+    /// No OxideKit equivalent was found; the source is a placeholder
+    pub placeholder: bool,
+}
+
+/// A node in a generated `.oui` tree
+struct OuiNode {
+    name: String,
+    props: Vec<(String, String)>,
+    children: Vec<OuiNode>,
+}
+
+impl OuiNode {
+    fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            props: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    fn prop(mut self, key: &str, value: impl Into<String>) -> Self {
+        self.props.push((key.to_string(), value.into()));
+        self
+    }
+
+    fn token(self, key: &str, token: &str) -> Self {
+        self.prop(key, format!("token(\"{}\")", token))
+    }
+
+    fn text(self, key: &str, value: &str) -> Self {
+        self.prop(key, format!("\"{}\"", value))
+    }
+
+    fn child(mut self, child: OuiNode) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    fn render(&self, indent: usize, out: &mut String) {
+        let pad = "    ".repeat(indent);
+        out.push_str(&format!("{} {{\n", self.name));
+        for (key, value) in &self.props {
+            out.push_str(&format!("{}    {}: {}\n", pad, key, value));
+        }
+        for child in &self.children {
+            out.push('\n');
+            out.push_str(&format!("{}    ", pad));
+            child.render(indent + 1, out);
+        }
+        out.push_str(&format!("{}}}\n", pad));
+    }
+}
+
 /// Design pack part definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DesignPart {
@@ -730,6 +790,158 @@ impl ComponentMapper {
         }
     }
 
+    /// Generate `.oui` source for a component mapping
+    ///
+    /// Components without an OxideKit equivalent produce a placeholder that
+    /// renders a TODO message.
+    pub fn generate_oui(&self, mapping: &ComponentMapping) -> OuiComponent {
+        let id = match &mapping.source_type {
+            ComponentType::Custom(name) => {
+                let id: String = name
+                    .split(|c: char| !c.is_ascii_alphanumeric())
+                    .filter(|part| !part.is_empty())
+                    .map(|part| {
+                        let mut chars = part.chars();
+                        chars
+                            .next()
+                            .map(|c| c.to_ascii_uppercase().to_string() + chars.as_str())
+                            .unwrap_or_default()
+                    })
+                    .collect();
+                // Names without ASCII letters or digits get a stable hashed ID
+                if id.is_empty() {
+                    format!("Custom{}", &crate::converter::content_hash(name)[..8])
+                } else {
+                    id
+                }
+            }
+            other => format!("{:?}", other),
+        };
+
+        let has_class = |prefix: &str| mapping.source_classes.iter().any(|c| c.starts_with(prefix));
+        let variant = mapping.prop_mappings.values().find_map(|prop| {
+            prop.strip_prefix("variant=\"")
+                .and_then(|v| v.strip_suffix('"'))
+                .map(str::to_string)
+        });
+
+        let placeholder = mapping.target_component.starts_with("compat.");
+        let render = if placeholder {
+            OuiNode::new("Container")
+                .token("padding", "spacing.md")
+                .token("border", "color.border")
+                .child(
+                    OuiNode::new("Text")
+                        .text("content", &format!("TODO: migrate {}", mapping.source_type))
+                        .token("color", "color.text.secondary"),
+                )
+        } else {
+            match &mapping.source_type {
+                ComponentType::Navbar => {
+                    let mut bar = OuiNode::new("Row")
+                        .text("align", "center")
+                        .text("justify", "space-between")
+                        .token("padding", "spacing.md")
+                        .token("background", "color.surface")
+                        .child(OuiNode::new("Text").text("content", "Brand").text("weight", "bold"))
+                        .child(
+                            OuiNode::new("Row")
+                                .token("gap", "spacing.md")
+                                .child(OuiNode::new("Link").text("label", "Home"))
+                                .child(OuiNode::new("Link").text("label", "About")),
+                        );
+                    if has_class("navbar-toggler") {
+                        bar = bar.child(OuiNode::new("Button").text("icon", "menu"));
+                    }
+                    bar
+                }
+                ComponentType::Card => {
+                    let mut body = OuiNode::new("Column").token("gap", "spacing.sm");
+                    if has_class("card-header") || has_class("card-title") {
+                        body = body.child(
+                            OuiNode::new("Text")
+                                .text("content", "Title")
+                                .text("weight", "bold"),
+                        );
+                    }
+                    body = body.child(
+                        OuiNode::new("Text")
+                            .text("content", "Content")
+                            .token("color", "color.text.secondary"),
+                    );
+                    if has_class("card-footer") {
+                        body = body.child(OuiNode::new("Row").token("gap", "spacing.sm"));
+                    }
+                    OuiNode::new("Container")
+                        .token("padding", "spacing.md")
+                        .token("background", "color.surface")
+                        .token("border_radius", "radius.md")
+                        .token("border", "color.border")
+                        .child(body)
+                }
+                ComponentType::Button | ComponentType::IconButton => OuiNode::new("Button")
+                    .text("label", "Button")
+                    .text("variant", variant.as_deref().unwrap_or("primary")),
+                ComponentType::Sidebar | ComponentType::Menu | ComponentType::List => {
+                    OuiNode::new("Column")
+                        .token("gap", "spacing.xs")
+                        .token("padding", "spacing.md")
+                        .token("background", "color.surface")
+                        .child(OuiNode::new("Link").text("label", "Item"))
+                }
+                ComponentType::Alert | ComponentType::Toast => {
+                    let color = format!("color.{}", variant.as_deref().unwrap_or("info"));
+                    OuiNode::new("Container")
+                        .token("padding", "spacing.md")
+                        .token("border_radius", "radius.md")
+                        .token("background", &color)
+                        .child(OuiNode::new("Text").text("content", "Message"))
+                }
+                ComponentType::Row | ComponentType::ButtonGroup | ComponentType::Tabs => {
+                    OuiNode::new("Row").token("gap", "spacing.md")
+                }
+                ComponentType::Column | ComponentType::Stack | ComponentType::Form => {
+                    OuiNode::new("Column").token("gap", "spacing.md")
+                }
+                ComponentType::Container | ComponentType::Grid | ComponentType::Flex => {
+                    OuiNode::new("Container").token("padding", "spacing.md")
+                }
+                _ => {
+                    // Reference the mapped OxideKit component directly
+                    let target = mapping
+                        .target_component
+                        .rsplit('.')
+                        .next()
+                        .unwrap_or("Container");
+                    let mut node = OuiNode::new(target);
+                    if let Some(variant) = &variant {
+                        node = node.text("variant", variant);
+                    }
+                    node
+                }
+            }
+        };
+
+        let mut source = format!(
+            "// {}\n// Migrated from: {}\n// Generated by oxide-migrate\n\nComponent {{\n    id: \"{}\"\n\n    render: ",
+            id,
+            if mapping.source_classes.is_empty() {
+                mapping.source_type.to_string()
+            } else {
+                mapping.source_classes.join(" ")
+            },
+            id
+        );
+        render.render(1, &mut source);
+        source.push_str("}\n");
+
+        OuiComponent {
+            id,
+            source,
+            placeholder,
+        }
+    }
+
     /// Detect layout pattern from components
     fn detect_layout(
         &self,
@@ -1035,6 +1247,61 @@ mod tests {
         assert!(mapping.prop_mappings.contains_key("btn-primary"));
     }
 
+    #[test]
+    fn test_generate_navbar_oui() {
+        let mapper = ComponentMapper::new();
+        let rules = mapper.mapping_rules.get(&Framework::Bootstrap).unwrap();
+
+        let component = DetectedComponent {
+            component_type: ComponentType::Navbar,
+            classes: vec!["navbar".into(), "navbar-brand".into(), "navbar-nav".into()],
+            source_file: Some("index.html".into()),
+            occurrences: 1,
+            variant: None,
+            size: None,
+            mappable: true,
+            mapping_confidence: 0.9,
+        };
+
+        let mapping = mapper.map_component(&component, rules, &Framework::Bootstrap);
+        let oui = mapper.generate_oui(&mapping);
+
+        assert_eq!(oui.id, "Navbar");
+        assert!(!oui.placeholder);
+        assert!(oui.source.contains("id: \"Navbar\""));
+        assert!(oui.source.contains(
+            "    render: Row {\n        align: \"center\"\n        justify: \"space-between\"\n"
+        ));
+        assert!(oui.source.contains(
+            "        Text {\n            content: \"Brand\"\n            weight: \"bold\"\n        }\n"
+        ));
+        assert!(oui.source.contains("        Row {\n            gap: token(\"spacing.md\")\n"));
+        assert!(oui.source.contains("            Link {\n                label: \"Home\"\n"));
+        assert!(oui.source.ends_with("    }\n}\n"));
+    }
+
+    #[test]
+    fn test_generate_placeholder_oui() {
+        let mapper = ComponentMapper::new();
+        let rules = mapper.mapping_rules.get(&Framework::Custom).unwrap();
+
+        let component = DetectedComponent::new(ComponentType::Custom("mega-menu".into()));
+        let mapping = mapper.map_component(&component, rules, &Framework::Custom);
+        let oui = mapper.generate_oui(&mapping);
+
+        assert_eq!(oui.id, "MegaMenu");
+        assert!(oui.placeholder);
+        assert!(oui.source.contains("TODO: migrate Custom(mega-menu)"));
+
+        let component = DetectedComponent::new(ComponentType::Custom("メニュー".into()));
+        let mapping = mapper.map_component(&component, rules, &Framework::Custom);
+        let first = mapper.generate_oui(&mapping);
+        let second = mapper.generate_oui(&mapping);
+        assert!(first.id.starts_with("Custom"));
+        assert_eq!(first.id.len(), "Custom".len() + 8);
+        assert_eq!(first.id, second.id);
+    }
+
     #[test]
     fn test_layout_detection() {
         let mapper = ComponentMapper::new();
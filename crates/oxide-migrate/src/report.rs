@@ -4,6 +4,7 @@
 //! including analysis summaries, token mappings, and migration recommendations.
 
 use crate::analyzer::{AnalysisResult, Framework};
+use crate::converter::{FileType, MigrationOutput};
 use crate::error::{MigrateResult, MigrationIssue};
 use crate::mapper::MappingResult;
 use crate::tokens::ExtractedTokens;
@@ -12,6 +13,10 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::Path;
 
+/// Version of the machine-readable report schema produced by
+/// [`MigrationReport::to_json`]. Bumped on any breaking change to its keys.
+pub const REPORT_SCHEMA_VERSION: u32 = 1;
+
 /// Complete migration report
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MigrationReport {
@@ -31,6 +36,212 @@ pub struct MigrationReport {
     pub confidence: ConfidenceReport,
     /// Next steps/action items
     pub next_steps: Vec<ActionItem>,
+    /// Generated files (filled in by [`MigrationReport::with_output`])
+    #[serde(default)]
+    pub generated_files: Vec<GeneratedFileEntry>,
+    /// Number of TODO items created during conversion
+    #[serde(default)]
+    pub todo_count: usize,
+}
+
+impl MigrationReport {
+    /// Attach conversion results (generated files, TODOs, conversion confidence)
+    pub fn with_output(mut self, output: &MigrationOutput) -> Self {
+        self.generated_files = output
+            .files
+            .iter()
+            .map(|f| GeneratedFileEntry {
+                path: f.path.to_string_lossy().replace('\\', "/"),
+                file_type: file_type_name(f.file_type).to_string(),
+                confidence: f.confidence,
+                needs_review: f.needs_review,
+            })
+            .collect();
+        self.todo_count = output.todos.len();
+
+        if !output.files.is_empty() {
+            let total: f32 = output.files.iter().map(|f| f.confidence).sum();
+            self.confidence.conversion = Some(total / output.files.len() as f32);
+        }
+
+        self
+    }
+
+    /// Build the machine-readable summary used by [`MigrationReport::to_json`]
+    pub fn to_json_report(&self) -> JsonReport {
+        let count_issues = |severity: &str| {
+            self.issues
+                .iter()
+                .filter(|i| i.severity == severity)
+                .count()
+        };
+
+        let mappings = &self.mapping_summary.mappings;
+        let unmapped_count = mappings
+            .iter()
+            .filter(|m| m.target.starts_with("compat."))
+            .count();
+        let mut unmapped: Vec<String> = mappings
+            .iter()
+            .filter(|m| m.target.starts_with("compat."))
+            .map(|m| m.source.clone())
+            .collect();
+        unmapped.sort();
+        unmapped.dedup();
+
+        JsonReport {
+            schema_version: REPORT_SCHEMA_VERSION,
+            tool_version: self.metadata.tool_version.clone(),
+            generated_at: self.metadata.generated_at,
+            source: JsonSource {
+                path: self.metadata.source_path.clone(),
+                framework: self.metadata.source_framework.clone(),
+                version: self.metadata.source_version.clone(),
+            },
+            confidence: JsonConfidence {
+                overall: unit(self.confidence.overall),
+                framework: unit(self.confidence.framework),
+                analysis: unit(self.confidence.analysis),
+                tokens: unit(self.confidence.tokens),
+                mapping: unit(self.confidence.mapping),
+                conversion: self.confidence.conversion.map(unit),
+            },
+            components: JsonComponents {
+                total_types: self.analysis_summary.components.total_types,
+                total_instances: self.analysis_summary.components.total_instances,
+                mapped: mappings.len() - unmapped_count,
+                needs_review: mappings.iter().filter(|m| m.needs_review).count(),
+                unmapped,
+            },
+            todo_count: self.todo_count,
+            issues: JsonIssueCounts {
+                errors: count_issues("ERROR"),
+                warnings: count_issues("WARN"),
+                info: count_issues("INFO"),
+            },
+            generated_files: self.generated_files.clone(),
+        }
+    }
+
+    /// Export the versioned, machine-readable report (for CI gating)
+    ///
+    /// Unlike [`ReportGenerator::to_json`], which dumps the full report, the
+    /// keys here are stable within a [`REPORT_SCHEMA_VERSION`].
+    pub fn to_json(&self) -> MigrateResult<String> {
+        Ok(serde_json::to_string_pretty(&self.to_json_report())?)
+    }
+}
+
+/// Generated file entry for the report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeneratedFileEntry {
+    /// Path relative to the output directory (forward slashes)
+    pub path: String,
+    /// File type (e.g., "theme", "component", "page")
+    #[serde(rename = "type")]
+    pub file_type: String,
+    /// Generation confidence
+    pub confidence: f32,
+    /// Needs manual review
+    pub needs_review: bool,
+}
+
+/// Machine-readable report (schema [`REPORT_SCHEMA_VERSION`])
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonReport {
+    /// Schema version
+    pub schema_version: u32,
+    /// Tool version
+    pub tool_version: String,
+    /// Report generation timestamp
+    pub generated_at: DateTime<Utc>,
+    /// Migration source
+    pub source: JsonSource,
+    /// Per-phase confidence (0.0 to 1.0)
+    pub confidence: JsonConfidence,
+    /// Component mapping counts
+    pub components: JsonComponents,
+    /// TODO items created during conversion
+    pub todo_count: usize,
+    /// Issue counts by severity
+    pub issues: JsonIssueCounts,
+    /// Generated files
+    pub generated_files: Vec<GeneratedFileEntry>,
+}
+
+/// Migration source in the JSON report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonSource {
+    /// Source path analyzed
+    pub path: String,
+    /// Detected framework
+    pub framework: String,
+    /// Detected framework version
+    pub version: Option<String>,
+}
+
+/// Per-phase confidence in the JSON report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonConfidence {
+    /// Overall migration confidence
+    pub overall: f32,
+    /// Framework detection
+    pub framework: f32,
+    /// Analysis phase
+    pub analysis: f32,
+    /// Token extraction phase
+    pub tokens: f32,
+    /// Component mapping phase
+    pub mapping: f32,
+    /// Conversion phase (absent if no output was attached)
+    pub conversion: Option<f32>,
+}
+
+/// Component counts in the JSON report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonComponents {
+    /// Unique component types detected
+    pub total_types: usize,
+    /// Component instances detected
+    pub total_instances: usize,
+    /// Mappings with an OxideKit equivalent
+    pub mapped: usize,
+    /// Mappings needing manual review
+    pub needs_review: usize,
+    /// Component types without an OxideKit equivalent
+    pub unmapped: Vec<String>,
+}
+
+/// Issue counts in the JSON report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonIssueCounts {
+    pub errors: usize,
+    pub warnings: usize,
+    pub info: usize,
+}
+
+/// Clamp a confidence value into `0.0..=1.0`
+fn unit(value: f32) -> f32 {
+    if value.is_nan() {
+        0.0
+    } else {
+        value.clamp(0.0, 1.0)
+    }
+}
+
+/// Stable name for a generated file type
+fn file_type_name(file_type: FileType) -> &'static str {
+    match file_type {
+        FileType::Theme => "theme",
+        FileType::Typography => "typography",
+        FileType::Fonts => "fonts",
+        FileType::DesignPart => "design_part",
+        FileType::Component => "component",
+        FileType::Page => "page",
+        FileType::Layout => "layout",
+        FileType::Config => "config",
+        FileType::Documentation => "documentation",
+    }
 }
 
 /// Report metadata
@@ -262,10 +473,16 @@ pub struct ConfidenceReport {
     pub overall: f32,
     /// Framework detection confidence
     pub framework: f32,
+    /// Source analysis confidence
+    #[serde(default)]
+    pub analysis: f32,
     /// Token extraction confidence
     pub tokens: f32,
     /// Component mapping confidence
     pub mapping: f32,
+    /// Average generated file confidence (if output was attached)
+    #[serde(default)]
+    pub conversion: Option<f32>,
     /// Risk assessment
     pub risk_level: String,
 }
@@ -345,6 +562,8 @@ impl ReportGenerator {
             issues,
             confidence,
             next_steps,
+            generated_files: Vec::new(),
+            todo_count: 0,
         })
     }
 
@@ -672,8 +891,10 @@ impl ReportGenerator {
         ConfidenceReport {
             overall,
             framework: analysis.framework_confidence,
+            analysis: analysis.migration_confidence,
             tokens: tokens.confidence.overall,
             mapping: mappings.confidence,
+            conversion: None,
             risk_level: risk_level.into(),
         }
     }
@@ -987,6 +1208,7 @@ mod tests {
                 shadows: 0.75,
                 overall: 0.8,
             },
+            dark_theme: None,
         }
     }
 
@@ -1053,6 +1275,70 @@ mod tests {
         assert!(json.contains("Bootstrap"));
     }
 
+    #[test]
+    fn test_versioned_json_report() {
+        use crate::converter::{Converter, MigrationConfig};
+
+        let generator = ReportGenerator::new();
+        let analysis = create_test_analysis();
+        let tokens = create_test_tokens();
+        let mappings = create_test_mappings();
+
+        let output = Converter::new(MigrationConfig::default())
+            .convert(&analysis, &tokens, &mappings)
+            .unwrap();
+        let report = generator
+            .generate(&analysis, &tokens, &mappings, "/test/path", None)
+            .unwrap()
+            .with_output(&output);
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        assert_eq!(json["schema_version"], REPORT_SCHEMA_VERSION);
+
+        let keys: Vec<&str> = json.as_object().unwrap().keys().map(|k| k.as_str()).collect();
+        for key in [
+            "schema_version",
+            "tool_version",
+            "generated_at",
+            "source",
+            "confidence",
+            "components",
+            "todo_count",
+            "issues",
+            "generated_files",
+        ] {
+            assert!(keys.contains(&key), "missing key {}", key);
+        }
+
+        for phase in ["overall", "framework", "analysis", "tokens", "mapping", "conversion"] {
+            let value = json["confidence"][phase].as_f64().unwrap();
+            assert!((0.0..=1.0).contains(&value), "{} out of range", phase);
+        }
+
+        assert_eq!(json["todo_count"], output.todos.len());
+        let files = json["generated_files"].as_array().unwrap();
+        assert_eq!(files.len(), output.files.len());
+        assert!(files
+            .iter()
+            .any(|f| f["path"] == "theme/theme.generated.toml" && f["type"] == "theme"));
+    }
+
+    #[test]
+    fn test_json_confidence_clamped() {
+        let generator = ReportGenerator::new();
+        let mut analysis = create_test_analysis();
+        analysis.framework_confidence = 1.4;
+
+        let report = generator
+            .generate(&analysis, &create_test_tokens(), &create_test_mappings(), "/p", None)
+            .unwrap();
+        let json = report.to_json_report();
+
+        assert_eq!(json.confidence.framework, 1.0);
+        assert!(json.confidence.conversion.is_none());
+        assert!(json.components.unmapped.is_empty());
+    }
+
     #[test]
     fn test_confidence_calculation() {
         let generator = ReportGenerator::new();
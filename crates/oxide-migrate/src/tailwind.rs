@@ -0,0 +1,447 @@
+//! Tailwind Config Parsing
+//!
+//! Best-effort extraction of the design scale from `tailwind.config.{js,cjs,mjs,ts}`.
+//! Only static object literals are understood; spreads, function values and
+//! imported references are skipped.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// File names recognized as Tailwind configs
+pub const TAILWIND_CONFIG_FILES: &[&str] = &[
+    "tailwind.config.js",
+    "tailwind.config.cjs",
+    "tailwind.config.mjs",
+    "tailwind.config.ts",
+];
+
+/// Theme scale declared in a Tailwind config
+///
+/// Nested scales are flattened with `-` (`blue.500` becomes `blue-500`) and
+/// `DEFAULT` entries use the parent name.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TailwindTheme {
+    /// Colors from `theme.colors` and `theme.extend.colors`
+    pub colors: BTreeMap<String, String>,
+    /// Spacing scale from `theme.spacing` and `theme.extend.spacing`
+    pub spacing: BTreeMap<String, String>,
+    /// Font sizes from `theme.fontSize` and `theme.extend.fontSize`
+    pub font_size: BTreeMap<String, String>,
+}
+
+impl TailwindTheme {
+    /// Parse a Tailwind config source (CommonJS or ES module)
+    pub fn parse(source: &str) -> Option<Self> {
+        let source = strip_comments(source);
+        let root_start = find_config_object(&source)?;
+        let root = Parser::new(&source[root_start..]).value()?;
+
+        let theme = lookup(&root, "theme")?;
+        let extend = lookup(theme, "extend");
+
+        let mut result = Self::default();
+        for section in [Some(theme), extend].into_iter().flatten() {
+            if let Some(colors) = lookup(section, "colors") {
+                flatten_into(colors, "", &mut result.colors);
+            }
+            if let Some(spacing) = lookup(section, "spacing") {
+                flatten_into(spacing, "", &mut result.spacing);
+            }
+            if let Some(font_size) = lookup(section, "fontSize") {
+                flatten_into(font_size, "", &mut result.font_size);
+            }
+        }
+
+        Some(result)
+    }
+
+    /// Check if a file name is a Tailwind config
+    pub fn is_config_file(file_name: &str) -> bool {
+        TAILWIND_CONFIG_FILES.contains(&file_name)
+    }
+
+    /// Check if no scale values were found
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty() && self.spacing.is_empty() && self.font_size.is_empty()
+    }
+}
+
+/// Parsed JS literal (subset)
+#[derive(Debug, Clone)]
+enum Value {
+    Object(Vec<(String, Value)>),
+    Array(Vec<Value>),
+    String(String),
+    /// Anything else (numbers, identifiers, expressions), kept as raw text
+    Other(String),
+}
+
+fn lookup<'a>(value: &'a Value, key: &str) -> Option<&'a Value> {
+    match value {
+        Value::Object(entries) => entries.iter().rev().find(|(k, _)| k == key).map(|(_, v)| v),
+        _ => None,
+    }
+}
+
+fn flatten_into(value: &Value, prefix: &str, out: &mut BTreeMap<String, String>) {
+    let Value::Object(entries) = value else {
+        return;
+    };
+
+    for (key, value) in entries {
+        let name = match (prefix.is_empty(), key.as_str()) {
+            (true, _) => key.clone(),
+            (false, "DEFAULT") => prefix.to_string(),
+            (false, _) => format!("{}-{}", prefix, key),
+        };
+
+        match value {
+            Value::String(s) => {
+                out.insert(name, s.clone());
+            }
+            Value::Other(raw) if raw.parse::<f64>().is_ok() => {
+                out.insert(name, raw.clone());
+            }
+            // fontSize: ['1.25rem', { lineHeight: '1.75rem' }]
+            Value::Array(items) => {
+                if let Some(Value::String(s)) = items.first() {
+                    out.insert(name, s.clone());
+                }
+            }
+            Value::Object(_) => flatten_into(value, &name, out),
+            Value::Other(_) => {}
+        }
+    }
+}
+
+/// Locate the opening brace of the exported config object
+fn find_config_object(source: &str) -> Option<usize> {
+    for marker in ["module.exports", "export default"] {
+        if let Some(pos) = source.find(marker) {
+            let rest = &source[pos + marker.len()..];
+            let Some(brace) = rest.find('{') else {
+                continue;
+            };
+            // Allow `= {`, `defineConfig({` and `({`, but not `export default config`
+            let between = rest[..brace].trim_start_matches(|c: char| c == '=' || c.is_whitespace());
+            if between.is_empty() || between.ends_with('(') {
+                return Some(pos + marker.len() + brace);
+            }
+        }
+    }
+
+    // `const config = { ... }; export default config`
+    let assign = source.find("= {")?;
+    Some(assign + 2)
+}
+
+fn strip_comments(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    let mut quote: Option<char> = None;
+
+    while let Some(c) = chars.next() {
+        if let Some(q) = quote {
+            out.push(c);
+            if c == '\\' {
+                if let Some(next) = chars.next() {
+                    out.push(next);
+                }
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+
+        match (c, chars.peek()) {
+            ('/', Some('/')) => {
+                for next in chars.by_ref() {
+                    if next == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = ' ';
+                for next in chars.by_ref() {
+                    if prev == '*' && next == '/' {
+                        break;
+                    }
+                    prev = next;
+                }
+            }
+            ('"' | '\'' | '`', _) => {
+                quote = Some(c);
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+
+    out
+}
+
+struct Parser<'a> {
+    src: &'a [u8],
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            src: text.as_bytes(),
+            text,
+            pos: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.src.get(self.pos).copied()
+    }
+
+    fn skip_ws(&mut self) {
+        while self.peek().is_some_and(|c| c.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    /// Parse a value; `None` when the input is malformed (unbalanced
+    /// delimiters or unexpected end of input)
+    fn value(&mut self) -> Option<Value> {
+        self.skip_ws();
+        match self.peek() {
+            Some(b'{') => self.object(),
+            Some(b'[') => self.array(),
+            Some(b'"' | b'\'' | b'`') => Some(Value::String(self.string())),
+            _ => Some(Value::Other(self.raw())),
+        }
+    }
+
+    fn object(&mut self) -> Option<Value> {
+        self.pos += 1;
+        let mut entries = Vec::new();
+
+        loop {
+            self.skip_ws();
+            let start = self.pos;
+            match self.peek() {
+                None => return None,
+                Some(b'}') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some(b',') => {
+                    self.pos += 1;
+                    continue;
+                }
+                _ => {}
+            }
+
+            // Spread (`...defaultTheme.colors`) or other unsupported entry
+            if self.text[self.pos..].starts_with("...") {
+                self.raw();
+                continue;
+            }
+
+            // A stray `]` or `)` is never consumed by `raw`
+            if matches!(self.peek(), Some(b']' | b')')) {
+                return None;
+            }
+
+            let key = match self.peek() {
+                Some(b'"' | b'\'' | b'`') => self.string(),
+                _ => self.identifier(),
+            };
+            self.skip_ws();
+
+            if key.is_empty() || self.peek() != Some(b':') {
+                // Shorthand property or method; skip it
+                self.raw();
+                if self.pos == start {
+                    return None;
+                }
+                continue;
+            }
+            self.pos += 1;
+
+            let value = self.value()?;
+            entries.push((key, value));
+        }
+
+        Some(Value::Object(entries))
+    }
+
+    fn array(&mut self) -> Option<Value> {
+        self.pos += 1;
+        let mut items = Vec::new();
+
+        loop {
+            self.skip_ws();
+            match self.peek() {
+                None => return None,
+                Some(b']') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some(b',') => {
+                    self.pos += 1;
+                }
+                // Mismatched closer; `raw` would stop here without consuming it
+                Some(b'}' | b')') => return None,
+                _ => items.push(self.value()?),
+            }
+        }
+
+        Some(Value::Array(items))
+    }
+
+    fn string(&mut self) -> String {
+        let quote = self.src[self.pos];
+        self.pos += 1;
+        let start = self.pos;
+
+        while let Some(c) = self.peek() {
+            if c == b'\\' {
+                self.pos += 2;
+                continue;
+            }
+            if c == quote {
+                break;
+            }
+            self.pos += 1;
+        }
+
+        let end = self.pos.min(self.src.len());
+        self.pos = (self.pos + 1).min(self.src.len());
+        self.text[start..end].to_string()
+    }
+
+    fn identifier(&mut self) -> String {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == b'_' || c == b'$')
+        {
+            self.pos += 1;
+        }
+        self.text[start..self.pos].to_string()
+    }
+
+    /// Consume an unsupported expression up to the next `,`, `}` or `]` at depth 0
+    fn raw(&mut self) -> String {
+        let start = self.pos;
+        let mut depth = 0usize;
+
+        while let Some(c) = self.peek() {
+            match c {
+                b'"' | b'\'' | b'`' => {
+                    self.string();
+                    continue;
+                }
+                b'{' | b'[' | b'(' => depth += 1,
+                b'}' | b']' | b')' if depth == 0 => break,
+                b'}' | b']' | b')' => depth -= 1,
+                b',' if depth == 0 => break,
+                _ => {}
+            }
+            self.pos += 1;
+        }
+
+        self.text[start..self.pos].trim().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CJS_CONFIG: &str = r##"
+        const defaultTheme = require('tailwindcss/defaultTheme');
+
+        /** @type {import('tailwindcss').Config} */
+        module.exports = {
+          content: ['./src/**/*.{html,js}'],
+          theme: {
+            colors: {
+              transparent: 'transparent',
+              primary: { DEFAULT: '#1D4ED8', light: '#60A5FA' }, // brand
+              'brand-gray': { 100: '#F3F4F6', 900: '#111827' },
+            },
+            spacing: {
+              '1': '4px',
+              '2': '8px',
+              '4': '1rem',
+            },
+            extend: {
+              colors: { danger: "#DC2626" },
+              fontSize: {
+                base: ['1rem', { lineHeight: '1.5rem' }],
+                xl: '1.25rem',
+              },
+              fontFamily: { sans: ['Inter', ...defaultTheme.fontFamily.sans] },
+            },
+          },
+          plugins: [require('@tailwindcss/forms')],
+        }
+    "##;
+
+    #[test]
+    fn test_parse_cjs_config() {
+        let theme = TailwindTheme::parse(CJS_CONFIG).unwrap();
+
+        assert_eq!(theme.colors.get("primary"), Some(&"#1D4ED8".to_string()));
+        assert_eq!(theme.colors.get("primary-light"), Some(&"#60A5FA".to_string()));
+        assert_eq!(theme.colors.get("brand-gray-900"), Some(&"#111827".to_string()));
+        assert_eq!(theme.colors.get("danger"), Some(&"#DC2626".to_string()));
+        assert_eq!(theme.spacing.get("4"), Some(&"1rem".to_string()));
+        assert_eq!(theme.font_size.get("base"), Some(&"1rem".to_string()));
+        assert_eq!(theme.font_size.get("xl"), Some(&"1.25rem".to_string()));
+    }
+
+    #[test]
+    fn test_parse_esm_config() {
+        let source = r#"
+            import type { Config } from 'tailwindcss'
+
+            const config = {
+              theme: {
+                extend: {
+                  colors: { accent: '#F59E0B' },
+                  spacing: { '18': '4.5rem' },
+                },
+              },
+            } satisfies Config
+
+            export default config
+        "#;
+
+        let theme = TailwindTheme::parse(source).unwrap();
+        assert_eq!(theme.colors.get("accent"), Some(&"#F59E0B".to_string()));
+        assert_eq!(theme.spacing.get("18"), Some(&"4.5rem".to_string()));
+
+        let define = "export default defineConfig({ theme: { colors: { ink: '#000' } } })";
+        let theme = TailwindTheme::parse(define).unwrap();
+        assert_eq!(theme.colors.get("ink"), Some(&"#000".to_string()));
+    }
+
+    #[test]
+    fn test_parse_without_theme() {
+        assert!(TailwindTheme::parse("module.exports = { content: [] }").is_none());
+        assert!(TailwindTheme::parse("not a config").is_none());
+    }
+
+    #[test]
+    fn test_parse_malformed_terminates() {
+        let mismatched = "module.exports = { theme: { colors: { a: 'b' ) } } }";
+        assert!(TailwindTheme::parse(mismatched).is_none());
+
+        let unclosed_array = "module.exports = { content: ['x' }, theme: { colors: {} } }";
+        assert!(TailwindTheme::parse(unclosed_array).is_none());
+
+        let truncated = "module.exports = { theme: { colors: { a: 'b'";
+        assert!(TailwindTheme::parse(truncated).is_none());
+    }
+}
@@ -1,17 +1,53 @@
 //! Token Extraction
 //!
 //! Extracts design tokens (colors, typography, spacing, radii, shadows) from CSS
-//! variables and common CSS patterns, normalizing them to OxideKit token format.
+//! variables, Sass/Less variables and common CSS patterns, normalizing them to
+//! OxideKit token format.
 
 use crate::analyzer::{AnalysisResult, Framework};
 use crate::error::{IssueCategory, MigrateResult, MigrationIssue};
+use crate::tailwind::TailwindTheme;
 use oxide_components::theme::{
     ColorToken, ColorTokens, DesignTokens, RadiusTokens, ShadowToken, ShadowTokens, SpacingToken,
     SpacingTokens, Theme, ThemeMetadata, TypographyTokens,
 };
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+
+/// Confidence penalty applied (scaled by share) for tokens sourced from
+/// Sass/Less variables, whose values may depend on build-time functions
+const PREPROCESSOR_CONFIDENCE_PENALTY: f32 = 0.2;
+
+/// Minimum confidence for categories taken from a Tailwind config, which
+/// declares the design scale directly
+const TAILWIND_CONFIG_CONFIDENCE: f32 = 0.95;
+
+/// Semantic color names and the Tailwind color keys that map to them
+const TAILWIND_SEMANTIC_COLORS: &[(&str, &[&str])] = &[
+    ("primary", &["primary", "brand"]),
+    ("secondary", &["secondary"]),
+    ("success", &["success"]),
+    ("warning", &["warning"]),
+    ("danger", &["danger", "error"]),
+    ("info", &["info"]),
+    ("background", &["background"]),
+    ("surface", &["surface"]),
+    ("text", &["foreground", "text"]),
+    ("border", &["border"]),
+];
+
+/// Tailwind spacing keys used for the named spacing scale
+const TAILWIND_SPACING_SCALE: &[(&str, &str)] = &[
+    ("1", "xs"),
+    ("2", "sm"),
+    ("4", "md"),
+    ("6", "lg"),
+    ("8", "xl"),
+    ("12", "xxl"),
+];
 
 /// Token extraction result
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,6 +64,9 @@ pub struct ExtractedTokens {
     pub issues: Vec<MigrationIssue>,
     /// Confidence scores for each token category
     pub confidence: TokenConfidence,
+    /// Dark variant of the theme, when the source defines a paired dark palette
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dark_theme: Option<Theme>,
 }
 
 /// Typography extraction result
@@ -126,6 +165,8 @@ pub struct TokenExtractor {
     radius_pattern: Regex,
     /// Shadow value pattern
     shadow_pattern: Regex,
+    /// Sass/Less variable reference pattern (`$name` / `@name`)
+    variable_reference: Regex,
 }
 
 impl TokenExtractor {
@@ -140,6 +181,7 @@ impl TokenExtractor {
             shadow_pattern: Regex::new(
                 r"([\d.]+)(px|rem)?\s+([\d.]+)(px|rem)?\s+([\d.]+)(px|rem)?\s*(?:([\d.]+)(px|rem)?\s*)?(rgba?\([^)]+\)|#[0-9a-fA-F]+)"
             )?,
+            variable_reference: Regex::new(r"[$@]([a-zA-Z_][a-zA-Z0-9_-]*)")?,
         })
     }
 
@@ -148,6 +190,10 @@ impl TokenExtractor {
         let mut issues = Vec::new();
         let mut name_mapping = HashMap::new();
 
+        // Fold resolved Sass/Less variables in alongside CSS custom properties
+        let (analysis, preprocessor_share) = self.merge_preprocessor_variables(analysis, &mut issues);
+        let analysis = analysis.as_ref();
+
         // Extract colors
         let color_tokens = self.extract_colors(analysis, &mut issues, &mut name_mapping)?;
 
@@ -193,8 +239,18 @@ impl TokenExtractor {
             },
         };
 
+        // Build the dark variant from dark-scoped overrides
+        let dark_theme = if analysis.dark_css_variables.is_empty() {
+            None
+        } else {
+            Some(self.build_dark_theme(analysis, &theme)?)
+        };
+
         // Calculate confidence scores
-        let confidence = self.calculate_confidence(analysis, &theme);
+        let mut confidence = self.calculate_confidence(analysis, &theme, preprocessor_share);
+        if let Some(tailwind) = &analysis.tailwind_theme {
+            Self::apply_tailwind_confidence(tailwind, &mut confidence);
+        }
 
         Ok(ExtractedTokens {
             theme,
@@ -203,9 +259,128 @@ impl TokenExtractor {
             name_mapping,
             issues,
             confidence,
+            dark_theme,
         })
     }
 
+    /// Build a dark theme by overlaying dark-scoped variables on the defaults
+    fn build_dark_theme(&self, analysis: &AnalysisResult, light: &Theme) -> MigrateResult<Theme> {
+        let mut dark_analysis = analysis.clone();
+        dark_analysis
+            .css_variables
+            .extend(analysis.dark_css_variables.clone());
+
+        // Fallback warnings were already reported for the default palette
+        let mut scratch_issues = Vec::new();
+        let mut scratch_mapping = HashMap::new();
+        let color = self.extract_colors(&dark_analysis, &mut scratch_issues, &mut scratch_mapping)?;
+
+        let mut theme = light.clone();
+        theme.name = format!("{} (Dark)", light.name);
+        theme.tokens.color = color;
+        theme.metadata.is_dark = true;
+        Ok(theme)
+    }
+
+    /// Resolve Sass/Less variables and merge them into the CSS variable set.
+    ///
+    /// CSS custom properties take precedence on name clashes. Returns the
+    /// merged analysis and the share of variables that came from a preprocessor.
+    fn merge_preprocessor_variables<'a>(
+        &self,
+        analysis: &'a AnalysisResult,
+        issues: &mut Vec<MigrationIssue>,
+    ) -> (Cow<'a, AnalysisResult>, f32) {
+        if analysis.preprocessor_variables.is_empty() {
+            return (Cow::Borrowed(analysis), 0.0);
+        }
+
+        let resolved = self.resolve_preprocessor_variables(&analysis.preprocessor_variables);
+        let mut merged = analysis.clone();
+        let mut added = 0;
+        let mut unresolved = Vec::new();
+
+        for (name, value) in resolved {
+            if self.variable_reference.is_match(&value) {
+                unresolved.push(name.clone());
+            }
+            if let Entry::Vacant(entry) = merged.css_variables.entry(name) {
+                entry.insert(value);
+                added += 1;
+            }
+        }
+
+        if !unresolved.is_empty() {
+            unresolved.sort();
+            issues.push(
+                MigrationIssue::warning(
+                    IssueCategory::ColorToken,
+                    format!(
+                        "Could not fully resolve preprocessor variables: {}",
+                        unresolved.join(", ")
+                    ),
+                )
+                .with_suggestion("Check these values against the compiled CSS"),
+            );
+        }
+
+        let share = added as f32 / merged.css_variables.len().max(1) as f32;
+        (Cow::Owned(merged), share)
+    }
+
+    /// Substitute `$name` / `@name` references with their declared values.
+    ///
+    /// References to unknown or cyclic variables are left in place.
+    fn resolve_preprocessor_variables(
+        &self,
+        raw: &HashMap<String, String>,
+    ) -> HashMap<String, String> {
+        let mut resolved = HashMap::new();
+        for name in raw.keys() {
+            let mut visiting = HashSet::new();
+            let value = self.resolve_variable(name, raw, &mut resolved, &mut visiting);
+            resolved.insert(name.clone(), value);
+        }
+        resolved
+    }
+
+    fn resolve_variable(
+        &self,
+        name: &str,
+        raw: &HashMap<String, String>,
+        resolved: &mut HashMap<String, String>,
+        visiting: &mut HashSet<String>,
+    ) -> String {
+        if let Some(value) = resolved.get(name) {
+            return value.clone();
+        }
+        let Some(value) = raw.get(name) else {
+            return String::new();
+        };
+        if !visiting.insert(name.to_string()) {
+            return value.clone();
+        }
+
+        let mut output = String::with_capacity(value.len());
+        let mut last = 0;
+        for caps in self.variable_reference.captures_iter(value) {
+            let whole = caps.get(0).expect("match");
+            let reference = &caps[1];
+            output.push_str(&value[last..whole.start()]);
+            if raw.contains_key(reference) && !visiting.contains(reference) {
+                output.push_str(&self.resolve_variable(reference, raw, resolved, visiting));
+            } else {
+                output.push_str(whole.as_str());
+            }
+            last = whole.end();
+        }
+        output.push_str(&value[last..]);
+
+        visiting.remove(name);
+        resolved.insert(name.to_string(), output.clone());
+        output
+    }
+
     /// Extract color tokens from CSS variables and detected colors
     fn extract_colors(
         &self,
@@ -297,6 +472,11 @@ impl TokenExtractor {
             self.extract_tailwind_colors(vars, &mut colors, name_mapping);
         }
 
+        // A Tailwind config declares the palette directly and wins over guesses
+        if let Some(tailwind) = &analysis.tailwind_theme {
+            self.apply_tailwind_colors(tailwind, &mut colors, name_mapping);
+        }
+
         // Add any detected colors that weren't mapped
         for color in &analysis.detected_colors {
             if !name_mapping.values().any(|v| v == color) {
@@ -518,6 +698,39 @@ impl TokenExtractor {
         }
     }
 
+    /// Map Tailwind config colors to semantic colors; the rest become custom colors
+    fn apply_tailwind_colors(
+        &self,
+        tailwind: &TailwindTheme,
+        colors: &mut ColorTokens,
+        name_mapping: &mut HashMap<String, String>,
+    ) {
+        for (name, value) in &tailwind.colors {
+            if let Some(color_value) = self.parse_color_value(value) {
+                colors
+                    .custom
+                    .insert(format!("tw-{}", name), ColorToken::new(color_value));
+            }
+        }
+
+        for (semantic_name, keys) in TAILWIND_SEMANTIC_COLORS {
+            // Prefer the DEFAULT shade, then the mid shades of a scale
+            let found = keys.iter().find_map(|key| {
+                [key.to_string(), format!("{}-500", key), format!("{}-600", key)]
+                    .into_iter()
+                    .find_map(|name| {
+                        let value = self.parse_color_value(tailwind.colors.get(&name)?)?;
+                        Some((name, value))
+                    })
+            });
+
+            if let Some((name, value)) = found {
+                self.set_semantic_color(colors, semantic_name, &value);
+                name_mapping.insert(format!("tailwind.colors.{}", name), semantic_name.to_string());
+            }
+        }
+    }
+
     /// Extract spacing tokens
     fn extract_spacing(
         &self,
@@ -592,15 +805,62 @@ impl TokenExtractor {
             spacing.xl = SpacingToken::new(32.0); // space-8
             spacing.xxl = SpacingToken::new(48.0); // space-12
 
-            issues.push(MigrationIssue::info(
-                IssueCategory::Spacing,
-                "Using Tailwind default spacing scale (4px base)",
-            ));
+            if analysis
+                .tailwind_theme
+                .as_ref()
+                .is_none_or(|t| t.spacing.is_empty())
+            {
+                issues.push(MigrationIssue::info(
+                    IssueCategory::Spacing,
+                    "Using Tailwind default spacing scale (4px base)",
+                ));
+            }
+        }
+
+        if let Some(tailwind) = &analysis.tailwind_theme {
+            self.apply_tailwind_spacing(tailwind, &mut spacing);
         }
 
         Ok(spacing)
     }
 
+    /// Apply the spacing scale from a Tailwind config
+    fn apply_tailwind_spacing(&self, tailwind: &TailwindTheme, spacing: &mut SpacingTokens) {
+        for (key, value) in &tailwind.spacing {
+            let Some(px_value) = self.parse_spacing_value(value) else {
+                continue;
+            };
+
+            let size_name = TAILWIND_SPACING_SCALE
+                .iter()
+                .find(|(scale_key, _)| scale_key == key)
+                .map(|(_, size_name)| *size_name)
+                .or(match key.as_str() {
+                    "xs" | "sm" | "md" | "lg" | "xl" => Some(key.as_str()),
+                    "2xl" => Some("xxl"),
+                    _ => None,
+                });
+
+            match size_name {
+                Some("xs") => spacing.xs = SpacingToken::new(px_value),
+                Some("sm") => spacing.sm = SpacingToken::new(px_value),
+                Some("md") => spacing.md = SpacingToken::new(px_value),
+                Some("lg") => spacing.lg = SpacingToken::new(px_value),
+                Some("xl") => spacing.xl = SpacingToken::new(px_value),
+                Some("xxl") => spacing.xxl = SpacingToken::new(px_value),
+                _ => {
+                    spacing
+                        .custom
+                        .insert(format!("tw-{}", key), SpacingToken::new(px_value));
+                }
+            }
+
+            if key == "1" {
+                spacing.base = px_value;
+            }
+        }
+    }
+
     /// Parse a spacing value to pixels
     fn parse_spacing_value(&self, value: &str) -> Option<f32> {
         if let Some(caps) = self.spacing_pattern.captures(value.trim()) {
@@ -898,6 +1158,31 @@ impl TokenExtractor {
             }
         }
 
+        if let Some(tailwind) = &analysis.tailwind_theme {
+            for (key, value) in &tailwind.font_size {
+                let Some(px_value) = self.parse_spacing_value(value) else {
+                    continue;
+                };
+                match key.as_str() {
+                    "xs" => typography.font_size.xs = px_value,
+                    "sm" => typography.font_size.sm = px_value,
+                    "base" => typography.font_size.md = px_value,
+                    "lg" => typography.font_size.lg = px_value,
+                    "xl" => typography.font_size.xl = px_value,
+                    "2xl" => typography.font_size.xxl = px_value,
+                    "3xl" => typography.font_size.xxxl = px_value,
+                    _ => {
+                        typography.font_size.custom.insert(key.clone(), px_value);
+                    }
+                }
+                extracted.scale.push(TypographyScaleEntry {
+                    name: key.clone(),
+                    size_px: px_value,
+                    line_height: 1.5,
+                });
+            }
+        }
+
         // Add inferred typography roles
         extracted.roles = vec![
             InferredRole {
@@ -1033,8 +1318,17 @@ impl TokenExtractor {
     }
 
     /// Calculate confidence scores
-    fn calculate_confidence(&self, analysis: &AnalysisResult, theme: &Theme) -> TokenConfidence {
+    ///
+    /// `preprocessor_share` is the fraction of variables taken from Sass/Less
+    /// sources; color, spacing and radius scores are reduced accordingly.
+    fn calculate_confidence(
+        &self,
+        analysis: &AnalysisResult,
+        theme: &Theme,
+        preprocessor_share: f32,
+    ) -> TokenConfidence {
         let vars_count = analysis.css_variables.len();
+        let source_factor = 1.0 - PREPROCESSOR_CONFIDENCE_PENALTY * preprocessor_share.clamp(0.0, 1.0);
 
         // Color confidence based on how many semantic colors we extracted
         let colors = &theme.tokens.color;
@@ -1049,7 +1343,8 @@ impl TokenExtractor {
         .iter()
         .filter(|&&x| x)
         .count() as f32
-            / 6.0;
+            / 6.0
+            * source_factor;
 
         // Typography confidence
         let typo = &theme.tokens.typography;
@@ -1062,10 +1357,10 @@ impl TokenExtractor {
             0.8
         } else {
             0.5
-        };
+        } * source_factor;
 
         // Radius confidence
-        let radius_score = if theme.tokens.radius.md != 8.0 { 0.8 } else { 0.5 };
+        let radius_score = if theme.tokens.radius.md != 8.0 { 0.8 } else { 0.5 } * source_factor;
 
         // Shadow confidence
         let shadow_score = if theme.tokens.shadow.md.blur > 0.0 {
@@ -1086,6 +1381,26 @@ impl TokenExtractor {
         }
     }
 
+    /// Raise confidence for categories declared in a Tailwind config
+    fn apply_tailwind_confidence(tailwind: &TailwindTheme, confidence: &mut TokenConfidence) {
+        if !tailwind.colors.is_empty() {
+            confidence.colors = confidence.colors.max(TAILWIND_CONFIG_CONFIDENCE);
+        }
+        if !tailwind.spacing.is_empty() {
+            confidence.spacing = confidence.spacing.max(TAILWIND_CONFIG_CONFIDENCE);
+        }
+        if !tailwind.font_size.is_empty() {
+            confidence.typography = confidence.typography.max(TAILWIND_CONFIG_CONFIDENCE);
+        }
+
+        confidence.overall = (confidence.colors
+            + confidence.typography
+            + confidence.spacing
+            + confidence.radius
+            + confidence.shadows)
+            / 5.0;
+    }
+
     /// Export extracted tokens to TOML format
     pub fn to_theme_toml(&self, tokens: &ExtractedTokens) -> MigrateResult<String> {
         Ok(toml::to_string_pretty(&tokens.theme)?)
@@ -1193,6 +1508,157 @@ mod tests {
         assert!(!tokens.theme.metadata.is_dark);
     }
 
+    #[test]
+    fn test_extract_from_scss_variables() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("_variables.scss"),
+            r#"
+                $blue: #0d6efd !default;
+                $primary: $blue;
+                $spacing-base: 8px;
+                $spacing-md: $spacing-base;
+                $theme-colors: ("success": #198754, "danger": #dc3545);
+            "#,
+        )
+        .unwrap();
+
+        let analysis = crate::analyzer::Analyzer::new()
+            .unwrap()
+            .analyze(dir.path())
+            .unwrap();
+        let extractor = TokenExtractor::new().unwrap();
+        let tokens = extractor.extract(&analysis).unwrap();
+
+        let color = &tokens.theme.tokens.color;
+        assert_eq!(color.primary.value, "#0D6EFD");
+        assert_eq!(color.success.value, "#198754");
+        assert_eq!(color.danger.value, "#DC3545");
+        assert_eq!(tokens.theme.tokens.spacing.md.value, 8.0);
+
+        // Same values as plain CSS variables score higher
+        let css_analysis = AnalysisResult {
+            css_variables: extractor.resolve_preprocessor_variables(&analysis.preprocessor_variables),
+            ..Default::default()
+        };
+        let css_tokens = extractor.extract(&css_analysis).unwrap();
+        assert!(tokens.confidence.colors < css_tokens.confidence.colors);
+    }
+
+    #[test]
+    fn test_extract_from_tailwind_config() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("tailwind.config.js"),
+            r##"
+                module.exports = {
+                  content: ['./**/*.html'],
+                  theme: {
+                    extend: {
+                      colors: {
+                        primary: { DEFAULT: '#7C3AED', 700: '#6D28D9' },
+                        'brand-sand': '#F5E6C8',
+                      },
+                      spacing: { '1': '6px', '2': '12px', '4': '1.5rem', '18': '4.5rem' },
+                      fontSize: { base: ['15px', { lineHeight: '1.5' }], '2xl': '1.75rem' },
+                    },
+                  },
+                }
+            "##,
+        )
+        .unwrap();
+
+        let analysis = crate::analyzer::Analyzer::new()
+            .unwrap()
+            .analyze(dir.path())
+            .unwrap();
+        assert!(analysis.tailwind_theme.is_some());
+
+        let tokens = TokenExtractor::new().unwrap().extract(&analysis).unwrap();
+        let theme = &tokens.theme.tokens;
+
+        assert_eq!(theme.color.primary.value, "#7C3AED");
+        assert_eq!(theme.color.custom["tw-brand-sand"].value, "#F5E6C8");
+        assert_eq!(theme.color.custom["tw-primary-700"].value, "#6D28D9");
+
+        assert_eq!(theme.spacing.base, 6.0);
+        assert_eq!(theme.spacing.xs.value, 6.0);
+        assert_eq!(theme.spacing.sm.value, 12.0);
+        assert_eq!(theme.spacing.md.value, 24.0);
+        assert_eq!(theme.spacing.custom["tw-18"].value, 72.0);
+
+        assert_eq!(theme.typography.font_size.md, 15.0);
+        assert_eq!(theme.typography.font_size.xxl, 28.0);
+
+        assert!(tokens.confidence.colors >= 0.95);
+        assert!(tokens.confidence.spacing >= 0.95);
+    }
+
+    #[test]
+    fn test_extract_light_and_dark_palettes() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("theme.css"),
+            r#"
+                :root {
+                    --primary-color: #2563EB;
+                    --body-bg: #FFFFFF;
+                    --text-color: #111827;
+                }
+                [data-theme="dark"] {
+                    --body-bg: #0B1120;
+                    --text-color: #F3F4F6;
+                }
+                @media (prefers-color-scheme: dark) {
+                    :root { --primary-color: #60A5FA; }
+                }
+            "#,
+        )
+        .unwrap();
+
+        let analysis = crate::analyzer::Analyzer::new()
+            .unwrap()
+            .analyze(dir.path())
+            .unwrap();
+        let tokens = TokenExtractor::new().unwrap().extract(&analysis).unwrap();
+
+        let light = &tokens.theme;
+        assert!(!light.metadata.is_dark);
+        assert_eq!(light.tokens.color.primary.value, "#2563EB");
+        assert_eq!(light.tokens.color.background.value, "#FFFFFF");
+        assert_eq!(light.tokens.color.text.value, "#111827");
+
+        let dark = tokens.dark_theme.as_ref().expect("dark palette");
+        assert!(dark.metadata.is_dark);
+        assert_eq!(dark.tokens.color.primary.value, "#60A5FA");
+        assert_eq!(dark.tokens.color.background.value, "#0B1120");
+        assert_eq!(dark.tokens.color.text.value, "#F3F4F6");
+        // Non-color tokens are shared
+        assert_eq!(dark.tokens.spacing.md.value, light.tokens.spacing.md.value);
+    }
+
+    #[test]
+    fn test_resolve_variable_references() {
+        let extractor = TokenExtractor::new().unwrap();
+        let raw: HashMap<String, String> = [
+            ("a", "$b"),
+            ("b", "@c"),
+            ("c", "4px"),
+            ("border", "1px solid $a"),
+            ("loop", "$loop"),
+            ("missing", "$nope"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+        let resolved = extractor.resolve_preprocessor_variables(&raw);
+        assert_eq!(resolved["a"], "4px");
+        assert_eq!(resolved["border"], "1px solid 4px");
+        assert_eq!(resolved["loop"], "$loop");
+        assert_eq!(resolved["missing"], "$nope");
+    }
+
     #[test]
     fn test_hsl_to_rgb() {
         let extractor = TokenExtractor::new().unwrap();
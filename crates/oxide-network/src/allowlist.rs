@@ -5,6 +5,7 @@
 
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
 use tracing::{debug, warn};
 
 /// URL allowlist configuration.
@@ -64,6 +65,9 @@ pub struct AllowlistPattern {
     /// Specific paths allowed (empty = all paths).
     #[serde(default)]
     pub allowed_paths: Vec<String>,
+    /// Port the URL must use (None = any port).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
 }
 
 /// Type of allowlist pattern.
@@ -79,6 +83,8 @@ pub enum PatternType {
     UrlPrefix,
     /// Regular expression pattern.
     Regex,
+    /// IPv4 or IPv6 address range in CIDR notation (10.0.0.0/8, fd00::/8).
+    CidrRange,
 }
 
 impl Allowlist {
@@ -119,6 +125,7 @@ impl Allowlist {
             description: None,
             allow_websocket: false,
             allowed_paths: Vec::new(),
+            port: None,
         });
         self
     }
@@ -131,6 +138,7 @@ impl Allowlist {
             description: None,
             allow_websocket: false,
             allowed_paths: Vec::new(),
+            port: None,
         });
         self
     }
@@ -143,6 +151,7 @@ impl Allowlist {
             description: None,
             allow_websocket: false,
             allowed_paths: Vec::new(),
+            port: None,
         });
         self
     }
@@ -155,6 +164,23 @@ impl Allowlist {
             description: None,
             allow_websocket: false,
             allowed_paths: Vec::new(),
+            port: None,
+        });
+        self
+    }
+
+    /// Add an IP range in CIDR notation to the allowlist.
+    ///
+    /// A trailing port (`10.0.0.0/8:8080`) restricts the range to that port.
+    pub fn allow_cidr(mut self, range: impl Into<String>) -> Self {
+        let (range, port) = split_cidr_port(range.into());
+        self.patterns.push(AllowlistPattern {
+            pattern: range,
+            pattern_type: PatternType::CidrRange,
+            description: None,
+            allow_websocket: false,
+            allowed_paths: Vec::new(),
+            port,
         });
         self
     }
@@ -266,12 +292,24 @@ impl Allowlist {
                     Err(_) => false,
                 }
             }
+            PatternType::CidrRange => match url.host() {
+                Some(url::Host::Ipv4(ip)) => cidr_contains(&pattern.pattern, IpAddr::V4(ip)),
+                Some(url::Host::Ipv6(ip)) => cidr_contains(&pattern.pattern, IpAddr::V6(ip)),
+                _ => false,
+            },
         };
 
         if !domain_matches {
             return false;
         }
 
+        // Check port restriction if any
+        if let Some(port) = pattern.port {
+            if url.port_or_known_default() != Some(port) {
+                return false;
+            }
+        }
+
         // Check path restrictions if any
         if !pattern.allowed_paths.is_empty() {
             let path_allowed = pattern.allowed_paths.iter().any(|allowed_path| {
@@ -308,6 +346,70 @@ impl Allowlist {
     }
 }
 
+/// Split an optional trailing port off a CIDR range.
+///
+/// Accepts `10.0.0.0/8:8080`, `10.0.0.1:8080`, `fd00::/8:8080` and
+/// `[fd00::1]:8080`. Anything else is returned unchanged.
+fn split_cidr_port(range: String) -> (String, Option<u16>) {
+    let split = if let Some((network, prefix)) = range.split_once('/') {
+        prefix
+            .split_once(':')
+            .map(|(prefix, port)| (format!("{}/{}", network, prefix), port))
+    } else if let Some(rest) = range.strip_prefix('[') {
+        rest.split_once("]:")
+            .map(|(address, port)| (address.to_string(), port))
+    } else if range.matches(':').count() == 1 {
+        range
+            .split_once(':')
+            .map(|(address, port)| (address.to_string(), port))
+    } else {
+        None
+    };
+
+    match split.and_then(|(range, port)| Some((range, port.parse().ok()?))) {
+        Some((range, port)) => (range, Some(port)),
+        None => (range, None),
+    }
+}
+
+/// Check whether an address falls inside a CIDR range.
+///
+/// A bare address without a prefix length matches only itself. Ranges and
+/// addresses of different families never match.
+fn cidr_contains(range: &str, ip: IpAddr) -> bool {
+    let (network, prefix) = match range.split_once('/') {
+        Some((network, prefix)) => match prefix.parse::<u32>() {
+            Ok(prefix) => (network, Some(prefix)),
+            Err(_) => return false,
+        },
+        None => (range, None),
+    };
+    let network: IpAddr = match network.trim_matches(|c| c == '[' || c == ']').parse() {
+        Ok(network) => network,
+        Err(_) => return false,
+    };
+
+    match (network, ip) {
+        (IpAddr::V4(network), IpAddr::V4(ip)) => {
+            let prefix = prefix.unwrap_or(32);
+            if prefix > 32 {
+                return false;
+            }
+            let mask = u32::MAX.checked_shl(32 - prefix).unwrap_or(0);
+            u32::from(network) & mask == u32::from(ip) & mask
+        }
+        (IpAddr::V6(network), IpAddr::V6(ip)) => {
+            let prefix = prefix.unwrap_or(128);
+            if prefix > 128 {
+                return false;
+            }
+            let mask = u128::MAX.checked_shl(128 - prefix).unwrap_or(0);
+            u128::from(network) & mask == u128::from(ip) & mask
+        }
+        _ => false,
+    }
+}
+
 /// Builder for allowlist patterns.
 #[derive(Debug, Default)]
 pub struct PatternBuilder {
@@ -316,6 +418,7 @@ pub struct PatternBuilder {
     description: Option<String>,
     allow_websocket: bool,
     allowed_paths: Vec<String>,
+    port: Option<u16>,
 }
 
 impl PatternBuilder {
@@ -355,6 +458,19 @@ impl PatternBuilder {
         }
     }
 
+    /// Create a new pattern builder for an IP range in CIDR notation.
+    ///
+    /// A trailing port (`10.0.0.0/8:8080`) is taken as the port restriction.
+    pub fn cidr(range: impl Into<String>) -> Self {
+        let (range, port) = split_cidr_port(range.into());
+        Self {
+            pattern: range,
+            pattern_type: PatternType::CidrRange,
+            port,
+            ..Default::default()
+        }
+    }
+
     /// Add a description.
     pub fn description(mut self, desc: impl Into<String>) -> Self {
         self.description = Some(desc.into());
@@ -373,6 +489,14 @@ impl PatternBuilder {
         self
     }
 
+    /// Restrict to a specific port.
+    ///
+    /// URLs without an explicit port use their scheme's default (443 for https).
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = Some(port);
+        self
+    }
+
     /// Build the pattern.
     pub fn build(self) -> AllowlistPattern {
         AllowlistPattern {
@@ -381,6 +505,7 @@ impl PatternBuilder {
             description: self.description,
             allow_websocket: self.allow_websocket,
             allowed_paths: self.allowed_paths,
+            port: self.port,
         }
     }
 }
@@ -514,4 +639,59 @@ mod tests {
         assert!(allowlist.is_websocket_allowed("wss://ws.example.com/stream"));
         assert!(!allowlist.is_websocket_allowed("wss://api.example.com/stream"));
     }
+
+    #[test]
+    fn test_cidr_range() {
+        let allowlist = Allowlist::new()
+            .allow_cidr("10.0.0.0/8")
+            .allow_cidr("fd00::/8");
+
+        assert!(allowlist.is_allowed("http://10.1.2.3/status"));
+        assert!(allowlist.is_allowed("http://10.255.255.255:9000/"));
+        assert!(!allowlist.is_allowed("http://11.0.0.1/status"));
+        assert!(!allowlist.is_allowed("http://192.168.1.10/"));
+        assert!(!allowlist.is_allowed("http://internal.example.com/"));
+
+        assert!(allowlist.is_allowed("http://[fd12:3456::1]/status"));
+        assert!(!allowlist.is_allowed("http://[fe80::1]/status"));
+        assert!(!allowlist.is_allowed("http://[2001:db8::1]/"));
+    }
+
+    #[test]
+    fn test_port_restriction() {
+        let allowlist = Allowlist::new()
+            .add_pattern(PatternBuilder::cidr("10.0.0.0/8").port(8080).build())
+            .add_pattern(PatternBuilder::domain("api.example.com").port(443).build());
+
+        assert!(allowlist.is_allowed("http://10.0.0.5:8080/api"));
+        assert!(!allowlist.is_allowed("http://10.0.0.5:8081/api"));
+        assert!(!allowlist.is_allowed("http://10.0.0.5/api"));
+
+        // Default ports count as the URL's port
+        assert!(allowlist.is_allowed("https://api.example.com/users"));
+        assert!(!allowlist.is_allowed("http://api.example.com/users"));
+    }
+
+    #[test]
+    fn test_cidr_with_port_suffix() {
+        let allowlist = Allowlist::new()
+            .allow_cidr("10.0.0.0/8:8080")
+            .allow_cidr("fd00::/8:9000")
+            .add_pattern(PatternBuilder::cidr("[::1]:7000").build());
+
+        assert_eq!(allowlist.patterns()[0].pattern, "10.0.0.0/8");
+        assert_eq!(allowlist.patterns()[0].port, Some(8080));
+        assert!(allowlist.is_allowed("http://10.1.2.3:8080/status"));
+        assert!(!allowlist.is_allowed("http://10.1.2.3:8081/status"));
+        assert!(!allowlist.is_allowed("http://10.1.2.3/status"));
+
+        assert!(allowlist.is_allowed("http://[fd12::1]:9000/"));
+        assert!(!allowlist.is_allowed("http://[fd12::1]:9001/"));
+        assert!(allowlist.is_allowed("http://[::1]:7000/"));
+        assert!(!allowlist.is_allowed("http://[::1]:7001/"));
+
+        // Ranges without a port keep matching every port
+        assert_eq!(split_cidr_port("fd00::/8".to_string()), ("fd00::/8".to_string(), None));
+        assert_eq!(split_cidr_port("::1".to_string()), ("::1".to_string(), None));
+    }
 }
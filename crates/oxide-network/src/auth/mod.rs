@@ -7,9 +7,11 @@
 //! - Credential storage integration
 
 mod manager;
+mod oauth;
 mod provider;
 mod token;
 
 pub use manager::*;
+pub use oauth::*;
 pub use provider::*;
 pub use token::*;
@@ -0,0 +1,521 @@
+//! OAuth 2.0 authorization-code provider with PKCE.
+//!
+//! Desktop and mobile apps are public clients: they cannot keep a client
+//! secret, so the authorization code is bound to a one-time `code_verifier`
+//! (RFC 7636). The flow is:
+//!
+//! 1. [`OAuth2Provider::begin_pkce`] builds the authorize URL and a verifier.
+//! 2. The app opens the URL in a browser and captures the redirect.
+//! 3. [`OAuth2Provider::complete_pkce`] checks `state` and exchanges the code.
+
+use async_trait::async_trait;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+use url::Url;
+
+use crate::error::{AuthError, AuthState, NetworkError, NetworkResult};
+use crate::http::{HttpClient, HttpRequest};
+
+use super::{AuthCredentials, AuthProvider, AuthProviderType, OAuth2Config, TokenPair};
+
+/// Derive the S256 code challenge for a code verifier.
+pub fn pkce_challenge(code_verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()))
+}
+
+/// Generate `count` UUIDs worth of random bytes, base64url encoded.
+fn random_token(count: usize) -> String {
+    let bytes: Vec<u8> = (0..count)
+        .flat_map(|_| *uuid::Uuid::new_v4().as_bytes())
+        .collect();
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// Authorization URL to open in the user's browser.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthorizeUrl {
+    /// The full URL, including the PKCE challenge and state.
+    pub url: Url,
+    /// The `state` value embedded in the URL.
+    pub state: String,
+}
+
+impl std::fmt::Display for AuthorizeUrl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.url)
+    }
+}
+
+/// Secret half of a PKCE flow, kept by the app until the redirect arrives.
+#[derive(Clone, PartialEq, Eq)]
+pub struct PkceVerifier {
+    code_verifier: String,
+    state: String,
+}
+
+impl PkceVerifier {
+    /// Create a verifier from a known code verifier and state.
+    pub fn new(code_verifier: impl Into<String>, state: impl Into<String>) -> Self {
+        Self {
+            code_verifier: code_verifier.into(),
+            state: state.into(),
+        }
+    }
+
+    /// Generate a fresh verifier and state.
+    ///
+    /// The verifier is 43 characters of base64url, the minimum RFC 7636 allows.
+    pub fn generate() -> Self {
+        Self::new(random_token(2), random_token(1))
+    }
+
+    /// The code verifier sent with the token request.
+    pub fn code_verifier(&self) -> &str {
+        &self.code_verifier
+    }
+
+    /// The expected `state` value.
+    pub fn state(&self) -> &str {
+        &self.state
+    }
+
+    /// The S256 challenge sent with the authorization request.
+    pub fn challenge(&self) -> String {
+        pkce_challenge(&self.code_verifier)
+    }
+}
+
+impl std::fmt::Debug for PkceVerifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PkceVerifier")
+            .field("code_verifier", &"[redacted]")
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+/// Token endpoint response (RFC 6749 section 5.1).
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    token_type: Option<String>,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    #[serde(default)]
+    expires_in: Option<i64>,
+    #[serde(default)]
+    scope: Option<String>,
+}
+
+impl TokenResponse {
+    fn into_token_pair(self, previous_refresh: Option<String>) -> TokenPair {
+        let mut token = TokenPair::new(self.access_token)
+            .with_token_type(self.token_type.unwrap_or_else(|| "Bearer".to_string()));
+        // Servers may omit the refresh token on refresh; keep the old one
+        if let Some(refresh) = self.refresh_token.or(previous_refresh) {
+            token = token.with_refresh_token(refresh);
+        }
+        if let Some(expires_in) = self.expires_in {
+            token = token.with_expires_in(expires_in);
+        }
+        if let Some(scope) = self.scope {
+            token = token.with_scopes(scope.split_whitespace());
+        }
+        token
+    }
+}
+
+/// OAuth 2.0 authorization-code provider.
+#[derive(Debug)]
+pub struct OAuth2Provider {
+    id: String,
+    config: OAuth2Config,
+    client: HttpClient,
+    token: RwLock<Option<TokenPair>>,
+    state: RwLock<AuthState>,
+}
+
+impl OAuth2Provider {
+    /// Create a new OAuth2 provider with a default HTTP client.
+    pub fn new(id: impl Into<String>, config: OAuth2Config) -> NetworkResult<Self> {
+        Ok(Self::with_client(id, config, HttpClient::new()?))
+    }
+
+    /// Create a new OAuth2 provider using an existing HTTP client.
+    pub fn with_client(id: impl Into<String>, config: OAuth2Config, client: HttpClient) -> Self {
+        Self {
+            id: id.into(),
+            config,
+            client,
+            token: RwLock::new(None),
+            state: RwLock::new(AuthState::Unauthenticated),
+        }
+    }
+
+    /// Get the provider configuration.
+    pub fn config(&self) -> &OAuth2Config {
+        &self.config
+    }
+
+    /// Start a PKCE authorization-code flow.
+    pub fn begin_pkce(&self) -> NetworkResult<(AuthorizeUrl, PkceVerifier)> {
+        let verifier = PkceVerifier::generate();
+        let url = self.authorize_url(&verifier)?;
+        Ok((url, verifier))
+    }
+
+    /// Build the authorization URL for a given verifier.
+    pub fn authorize_url(&self, verifier: &PkceVerifier) -> NetworkResult<AuthorizeUrl> {
+        let mut url = Url::parse(&self.config.auth_url)?;
+        {
+            let mut query = url.query_pairs_mut();
+            query
+                .append_pair("response_type", "code")
+                .append_pair("client_id", &self.config.client_id)
+                .append_pair("redirect_uri", &self.config.redirect_uri)
+                .append_pair("state", verifier.state())
+                .append_pair("code_challenge", &verifier.challenge())
+                .append_pair("code_challenge_method", "S256");
+            if !self.config.scopes.is_empty() {
+                query.append_pair("scope", &self.config.scopes.join(" "));
+            }
+            let mut extra: Vec<_> = self.config.extra_auth_params.iter().collect();
+            extra.sort();
+            for (name, value) in extra {
+                query.append_pair(name, value);
+            }
+        }
+        Ok(AuthorizeUrl {
+            url,
+            state: verifier.state().to_string(),
+        })
+    }
+
+    /// Finish a PKCE flow with the `code` and `state` from the redirect.
+    ///
+    /// Fails with [`AuthError::OAuthStateMismatch`] before contacting the
+    /// token endpoint if `state` does not match the verifier.
+    pub async fn complete_pkce(
+        &self,
+        code: &str,
+        state: &str,
+        verifier: &PkceVerifier,
+    ) -> NetworkResult<TokenPair> {
+        if state != verifier.state() {
+            *self.state.write().await = AuthState::Failed("state mismatch".to_string());
+            return Err(AuthError::OAuthStateMismatch.into());
+        }
+        self.exchange_code(code, Some(verifier.code_verifier()))
+            .await
+    }
+
+    async fn exchange_code(
+        &self,
+        code: &str,
+        code_verifier: Option<&str>,
+    ) -> NetworkResult<TokenPair> {
+        *self.state.write().await = AuthState::Authenticating;
+
+        let mut form = HashMap::new();
+        form.insert("grant_type".to_string(), "authorization_code".to_string());
+        form.insert("code".to_string(), code.to_string());
+        form.insert("redirect_uri".to_string(), self.config.redirect_uri.clone());
+        if let Some(code_verifier) = code_verifier {
+            form.insert("code_verifier".to_string(), code_verifier.to_string());
+        }
+
+        match self.token_request(form).await {
+            Ok(response) => {
+                let token = response.into_token_pair(None);
+                *self.token.write().await = Some(token.clone());
+                *self.state.write().await = AuthState::Authenticated;
+                Ok(token)
+            }
+            Err(e) => {
+                *self.state.write().await = AuthState::Failed(e.to_string());
+                Err(AuthError::OAuthCodeExchangeFailed {
+                    message: e.to_string(),
+                }
+                .into())
+            }
+        }
+    }
+
+    /// POST a grant to the token endpoint.
+    async fn token_request(
+        &self,
+        mut form: HashMap<String, String>,
+    ) -> NetworkResult<TokenResponse> {
+        form.insert("client_id".to_string(), self.config.client_id.clone());
+        if let Some(secret) = &self.config.client_secret {
+            form.insert("client_secret".to_string(), secret.clone());
+        }
+
+        let request = HttpRequest::post(&self.config.token_url)?
+            .header("Accept", "application/json")
+            .form(form);
+        let response = self.client.execute(request).await?;
+        if !response.is_success() {
+            return Err(NetworkError::AuthenticationFailed {
+                message: format!(
+                    "token endpoint returned {}: {}",
+                    response.status,
+                    response.text_lossy()
+                ),
+            });
+        }
+        response.json()
+    }
+}
+
+#[async_trait]
+impl AuthProvider for OAuth2Provider {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn provider_type(&self) -> AuthProviderType {
+        AuthProviderType::OAuth2
+    }
+
+    async fn state(&self) -> AuthState {
+        let state = self.state.read().await.clone();
+        if state == AuthState::Authenticated {
+            let expired = self
+                .token
+                .read()
+                .await
+                .as_ref()
+                .map(|t| t.is_expired())
+                .unwrap_or(false);
+            if expired {
+                return AuthState::Expired;
+            }
+        }
+        state
+    }
+
+    async fn authenticate(&self, credentials: AuthCredentials) -> NetworkResult<TokenPair> {
+        match credentials {
+            AuthCredentials::OAuthCode {
+                code,
+                code_verifier,
+                ..
+            } => self.exchange_code(&code, code_verifier.as_deref()).await,
+            AuthCredentials::Token { token } => {
+                let token = TokenPair::new(token);
+                *self.token.write().await = Some(token.clone());
+                *self.state.write().await = AuthState::Authenticated;
+                Ok(token)
+            }
+            _ => Err(AuthError::InvalidCredentials.into()),
+        }
+    }
+
+    async fn refresh(&self) -> NetworkResult<TokenPair> {
+        let refresh_token = self
+            .token
+            .read()
+            .await
+            .as_ref()
+            .and_then(|t| t.refresh_token.clone())
+            .ok_or_else(|| NetworkError::RefreshFailed {
+                message: "No refresh token available".to_string(),
+            })?;
+
+        *self.state.write().await = AuthState::Refreshing;
+
+        let mut form = HashMap::new();
+        form.insert("grant_type".to_string(), "refresh_token".to_string());
+        form.insert("refresh_token".to_string(), refresh_token.clone());
+        if !self.config.scopes.is_empty() {
+            form.insert("scope".to_string(), self.config.scopes.join(" "));
+        }
+
+        match self.token_request(form).await {
+            Ok(response) => {
+                let token = response.into_token_pair(Some(refresh_token));
+                *self.token.write().await = Some(token.clone());
+                *self.state.write().await = AuthState::Authenticated;
+                Ok(token)
+            }
+            Err(e) => {
+                *self.state.write().await = AuthState::Expired;
+                Err(NetworkError::RefreshFailed {
+                    message: e.to_string(),
+                })
+            }
+        }
+    }
+
+    async fn logout(&self) -> NetworkResult<()> {
+        let token = self.token.write().await.take();
+        *self.state.write().await = AuthState::Unauthenticated;
+
+        // Best-effort revocation; the local session is gone either way
+        if let (Some(revoke_url), Some(token)) = (&self.config.revoke_url, token) {
+            let mut form = HashMap::new();
+            form.insert("token".to_string(), token.access_token);
+            form.insert("client_id".to_string(), self.config.client_id.clone());
+            if let Ok(request) = HttpRequest::post(revoke_url) {
+                let _ = self.client.execute(request.form(form)).await;
+            }
+        }
+        Ok(())
+    }
+
+    async fn apply_to_request(&self, mut request: HttpRequest) -> NetworkResult<HttpRequest> {
+        if let Some(token) = self.token.read().await.as_ref() {
+            request.headers.insert(
+                "Authorization".to_string(),
+                format!("{} {}", token.token_type, token.access_token),
+            );
+        }
+        Ok(request)
+    }
+
+    fn can_refresh(&self) -> bool {
+        self.token
+            .try_read()
+            .map(|t| t.as_ref().is_some_and(|t| t.refresh_token.is_some()))
+            .unwrap_or(true)
+    }
+
+    async fn current_token(&self) -> Option<TokenPair> {
+        self.token.read().await.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::AuthManager;
+    use crate::http::RetryConfig;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Token endpoint that answers every request with the given JSON bodies in order.
+    async fn token_server(
+        bodies: Vec<&'static str>,
+    ) -> (String, tokio::task::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/token", listener.local_addr().unwrap());
+        let handle = tokio::spawn(async move {
+            let mut requests = Vec::new();
+            for body in bodies {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = vec![0u8; 8192];
+                let n = socket.read(&mut buf).await.unwrap();
+                requests.push(String::from_utf8_lossy(&buf[..n]).to_string());
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\
+                     Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+            requests
+        });
+        (url, handle)
+    }
+
+    fn provider(token_url: &str) -> OAuth2Provider {
+        let config = OAuth2Config::new(
+            "desktop-app",
+            "https://auth.example.com/authorize",
+            token_url,
+            "http://127.0.0.1:8765/callback",
+        )
+        .with_scopes(["openid", "profile"]);
+        let client = HttpClient::builder()
+            .default_retry(RetryConfig::none())
+            .build()
+            .unwrap();
+        OAuth2Provider::with_client("oauth", config, client)
+    }
+
+    #[test]
+    fn test_s256_challenge() {
+        // RFC 7636 appendix B
+        let verifier = PkceVerifier::new("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk", "xyz");
+        assert_eq!(
+            verifier.challenge(),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+
+        let generated = PkceVerifier::generate();
+        assert_eq!(generated.code_verifier().len(), 43);
+        assert_ne!(
+            generated.code_verifier(),
+            PkceVerifier::generate().code_verifier()
+        );
+
+        let (url, verifier) = provider("https://auth.example.com/token")
+            .begin_pkce()
+            .unwrap();
+        let query: HashMap<_, _> = url.url.query_pairs().into_owned().collect();
+        assert_eq!(query["code_challenge"], verifier.challenge());
+        assert_eq!(query["code_challenge_method"], "S256");
+        assert_eq!(query["state"], verifier.state());
+        assert_eq!(query["scope"], "openid profile");
+    }
+
+    #[tokio::test]
+    async fn test_state_mismatch_rejected() {
+        // Nothing listens here; a mismatch must fail before any request is made
+        let provider = provider("http://127.0.0.1:9/token");
+        let (_, verifier) = provider.begin_pkce().unwrap();
+
+        let err = provider
+            .complete_pkce("code", "forged-state", &verifier)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("state mismatch"));
+        assert!(matches!(provider.state().await, AuthState::Failed(_)));
+        assert!(provider.current_token().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_pkce_exchange_and_managed_refresh() {
+        let (url, server) = token_server(vec![
+            r#"{"access_token":"a1","token_type":"Bearer","refresh_token":"r1","expires_in":3600}"#,
+            r#"{"access_token":"a2","expires_in":3600}"#,
+        ])
+        .await;
+        let provider = provider(&url);
+        let (authorize, verifier) = provider.begin_pkce().unwrap();
+
+        let token = provider
+            .complete_pkce("the-code", &authorize.state, &verifier)
+            .await
+            .unwrap();
+        assert_eq!(token.access_token, "a1");
+        assert!(provider.can_refresh());
+
+        let manager = AuthManager::new();
+        manager.register_provider(provider).await;
+        let mut changes = manager.subscribe_state_changes();
+
+        let refreshed = manager.refresh("oauth").await.unwrap();
+        assert_eq!(refreshed.access_token, "a2");
+        assert_eq!(refreshed.refresh_token.as_deref(), Some("r1"));
+        assert_eq!(changes.recv().await.unwrap().current, AuthState::Refreshing);
+        assert_eq!(
+            changes.recv().await.unwrap().current,
+            AuthState::Authenticated
+        );
+        assert_eq!(manager.state("oauth").await, Some(AuthState::Authenticated));
+
+        let requests = server.await.unwrap();
+        assert!(requests[0].contains("grant_type=authorization_code"));
+        assert!(requests[0].contains(&format!("code_verifier={}", verifier.code_verifier())));
+        assert!(requests[1].contains("grant_type=refresh_token"));
+        assert!(requests[1].contains("refresh_token=r1"));
+    }
+}
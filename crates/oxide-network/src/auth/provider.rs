@@ -3,7 +3,10 @@
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
+use crate::credentials::CredentialManager;
 use crate::error::{AuthError, AuthState, NetworkResult};
 use crate::http::HttpRequest;
 
@@ -304,6 +307,10 @@ pub struct ApiKeyProvider {
     id: String,
     config: ApiKeyConfig,
     api_key: tokio::sync::RwLock<Option<String>>,
+    /// Credential manager and key ID to read the key from on every request.
+    credentials: Option<(Arc<CredentialManager>, String)>,
+    /// Set by `logout`, stops reading the key from the credential manager.
+    logged_out: AtomicBool,
 }
 
 impl ApiKeyProvider {
@@ -313,6 +320,8 @@ impl ApiKeyProvider {
             id: id.into(),
             config,
             api_key: tokio::sync::RwLock::new(None),
+            credentials: None,
+            logged_out: AtomicBool::new(false),
         }
     }
 
@@ -322,8 +331,40 @@ impl ApiKeyProvider {
             id: id.into(),
             config,
             api_key: tokio::sync::RwLock::new(Some(key.into())),
+            credentials: None,
+            logged_out: AtomicBool::new(false),
         }
     }
+
+    /// Create a provider that reads its key from a credential manager on every request.
+    ///
+    /// Keys rotated by a [`CredentialManager::set_refresh_hook`] hook are picked
+    /// up automatically; requests wait for an in-flight rotation instead of
+    /// sending the stale key.
+    pub fn from_credentials(
+        id: impl Into<String>,
+        config: ApiKeyConfig,
+        manager: Arc<CredentialManager>,
+        key_id: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            config,
+            api_key: tokio::sync::RwLock::new(None),
+            credentials: Some((manager, key_id.into())),
+            logged_out: AtomicBool::new(false),
+        }
+    }
+
+    /// The credential manager backing this provider, unless logged out.
+    fn stored_key(&self) -> Option<(&CredentialManager, &str)> {
+        if self.logged_out.load(Ordering::SeqCst) {
+            return None;
+        }
+        self.credentials
+            .as_ref()
+            .map(|(manager, key_id)| (manager.as_ref(), key_id.as_str()))
+    }
 }
 
 #[async_trait]
@@ -338,6 +379,14 @@ impl AuthProvider for ApiKeyProvider {
 
     async fn state(&self) -> AuthState {
         if self.api_key.read().await.is_some() {
+            return AuthState::Authenticated;
+        }
+        // Peek rather than retrieve, so checking the state never rotates the key
+        let stored = match self.stored_key() {
+            Some((manager, key_id)) => manager.peek_api_key(key_id).await,
+            None => Ok(None),
+        };
+        if matches!(stored, Ok(Some(_))) {
             AuthState::Authenticated
         } else {
             AuthState::Unauthenticated
@@ -345,6 +394,7 @@ impl AuthProvider for ApiKeyProvider {
     }
 
     async fn authenticate(&self, credentials: AuthCredentials) -> NetworkResult<TokenPair> {
+        self.logged_out.store(false, Ordering::SeqCst);
         match credentials {
             AuthCredentials::ApiKey { key, .. } => {
                 *self.api_key.write().await = Some(key.clone());
@@ -364,16 +414,24 @@ impl AuthProvider for ApiKeyProvider {
     }
 
     async fn logout(&self) -> NetworkResult<()> {
+        self.logged_out.store(true, Ordering::SeqCst);
         *self.api_key.write().await = None;
         Ok(())
     }
 
     async fn apply_to_request(&self, mut request: HttpRequest) -> NetworkResult<HttpRequest> {
-        if let Some(key) = self.api_key.read().await.as_ref() {
+        // A key passed to `authenticate` wins over the credential manager
+        let authenticated = self.api_key.read().await.clone();
+        let api_key = match (authenticated, self.stored_key()) {
+            (Some(key), _) => Some(key),
+            (None, Some((manager, key_id))) => manager.retrieve_api_key(key_id).await?,
+            (None, None) => None,
+        };
+        if let Some(key) = api_key {
             let value = if let Some(prefix) = &self.config.key_prefix {
                 format!("{}{}", prefix, key)
             } else {
-                key.clone()
+                key
             };
             request.headers.insert(self.config.header_name.clone(), value);
         }
@@ -385,11 +443,11 @@ impl AuthProvider for ApiKeyProvider {
     }
 
     async fn current_token(&self) -> Option<TokenPair> {
-        self.api_key
-            .read()
-            .await
-            .as_ref()
-            .map(|k| TokenPair::new(k.clone()))
+        if let Some(key) = self.api_key.read().await.as_ref() {
+            return Some(TokenPair::new(key.clone()));
+        }
+        let (manager, key_id) = self.stored_key()?;
+        manager.peek_api_key(key_id).await.ok().flatten().map(TokenPair::new)
     }
 }
 
@@ -522,6 +580,62 @@ mod tests {
         assert_eq!(token.access_token, "my-secret-key");
     }
 
+    #[tokio::test]
+    async fn test_credentials_api_key_provider_logout() {
+        use crate::credentials::MemoryStore;
+
+        let manager = Arc::new(CredentialManager::with_store("test-app", MemoryStore::new()));
+        manager.store_api_key("upstream", "stored-key").await.unwrap();
+        let provider =
+            ApiKeyProvider::from_credentials("test", ApiKeyConfig::default(), manager, "upstream");
+
+        // Stored key counts before the first request
+        assert_eq!(provider.state().await, AuthState::Authenticated);
+
+        provider.logout().await.unwrap();
+        assert_eq!(provider.state().await, AuthState::Unauthenticated);
+
+        let request = HttpRequest::get("https://example.com").unwrap();
+        let request = provider.apply_to_request(request).await.unwrap();
+        assert!(!request.headers.contains_key("X-API-Key"));
+    }
+
+    #[tokio::test]
+    async fn test_credentials_api_key_provider_prefers_authenticated_key() {
+        use crate::credentials::{Credential, CredentialStore, CredentialType, MemoryStore};
+        use std::sync::atomic::AtomicUsize;
+
+        // An hour-long key with 30 seconds left, so any retrieve would rotate it
+        let store = MemoryStore::new();
+        let mut stored = Credential::api_key("stored-key")
+            .with_expiry(chrono::Utc::now() + chrono::Duration::seconds(30));
+        stored.stored_at = chrono::Utc::now() - chrono::Duration::hours(1);
+        store.store("test-app.apikey.upstream", &stored).await.unwrap();
+        let manager = Arc::new(CredentialManager::with_store("test-app", store));
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        manager.set_refresh_hook(CredentialType::ApiKey, move |_key_id| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            async { Ok(Credential::api_key("rotated-key")) }
+        });
+        let provider =
+            ApiKeyProvider::from_credentials("test", ApiKeyConfig::default(), manager, "upstream");
+
+        // Checking the state or token never runs the refresh hook
+        assert_eq!(provider.state().await, AuthState::Authenticated);
+        assert_eq!(provider.current_token().await.unwrap().access_token, "stored-key");
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        let token = provider
+            .authenticate(AuthCredentials::api_key("fresh-key"))
+            .await
+            .unwrap();
+        let request = HttpRequest::get("https://example.com").unwrap();
+        let request = provider.apply_to_request(request).await.unwrap();
+        assert_eq!(request.headers["X-API-Key"], token.access_token);
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+
     #[tokio::test]
     async fn test_basic_auth_provider() {
         let provider = BasicAuthProvider::new("test");
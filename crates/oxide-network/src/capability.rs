@@ -346,11 +346,14 @@ impl CapabilityValidator {
             crate::http::RequestBody::Form(f) => {
                 f.iter().map(|(k, v)| k.len() + v.len()).sum()
             }
-            crate::http::RequestBody::Multipart(fields) => {
-                fields.iter().map(|f| {
+            crate::http::RequestBody::Multipart(form) => {
+                form.fields().iter().map(|f| {
                     f.name.len() + match &f.value {
                         crate::http::MultipartValue::Text(t) => t.len(),
                         crate::http::MultipartValue::File { data, .. } => data.len(),
+                        crate::http::MultipartValue::Stream { reader, .. } => {
+                            usize::try_from(reader.len()).unwrap_or(usize::MAX)
+                        }
                     }
                 }).sum()
             }
@@ -6,12 +6,18 @@
 //! - Linux: libsecret/GNOME Keyring
 //!
 //! Fallback to encrypted file storage when platform keychain is unavailable.
+//!
+//! Short-lived credentials can be rotated automatically with
+//! [`CredentialManager::set_refresh_hook`].
 
 use async_trait::async_trait;
+use futures::future::BoxFuture;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::future::Future;
 use std::path::PathBuf;
-use tracing::debug;
+use std::sync::Arc;
+use tracing::{debug, warn};
 
 use crate::auth::TokenPair;
 use crate::error::NetworkResult;
@@ -101,10 +107,17 @@ impl Credential {
             .map(|exp| chrono::Utc::now() >= exp)
             .unwrap_or(false)
     }
+
+    /// Check if the credential expires within the given window.
+    pub fn expires_within(&self, window: chrono::Duration) -> bool {
+        self.expires_at
+            .map(|exp| chrono::Utc::now() + window >= exp)
+            .unwrap_or(false)
+    }
 }
 
 /// Types of credentials that can be stored.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum CredentialType {
     /// Password.
@@ -121,16 +134,41 @@ pub enum CredentialType {
     Secret,
 }
 
+/// Callback that issues a replacement for an expiring credential.
+///
+/// Receives the credential's identifier (API key ID, secret name or token
+/// provider ID).
+type RefreshHook =
+    Arc<dyn Fn(String) -> BoxFuture<'static, NetworkResult<Credential>> + Send + Sync>;
+
 /// Credential storage manager.
 ///
 /// Manages credential storage with automatic backend selection
 /// and provides a high-level API for common operations.
-#[derive(Debug)]
 pub struct CredentialManager {
     /// The storage backend.
     store: Box<dyn CredentialStore>,
     /// Application identifier for namespacing.
     app_id: String,
+    /// Refresh hooks by credential type.
+    refresh_hooks: std::sync::RwLock<HashMap<CredentialType, RefreshHook>>,
+    /// How long before expiry a credential is refreshed.
+    refresh_threshold: chrono::Duration,
+    /// Per-key locks so concurrent readers share a single refresh.
+    refresh_locks: std::sync::Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>,
+}
+
+impl std::fmt::Debug for CredentialManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let hooks: Vec<CredentialType> =
+            self.refresh_hooks.read().unwrap().keys().copied().collect();
+        f.debug_struct("CredentialManager")
+            .field("store", &self.store)
+            .field("app_id", &self.app_id)
+            .field("refresh_hooks", &hooks)
+            .field("refresh_threshold", &self.refresh_threshold)
+            .finish()
+    }
 }
 
 impl CredentialManager {
@@ -162,17 +200,49 @@ impl CredentialManager {
             Box::new(FileStore::new(&app_id))
         };
 
-        Self { store, app_id }
+        Self::from_parts(app_id, store)
     }
 
     /// Create a credential manager with a specific backend.
     pub fn with_store(app_id: impl Into<String>, store: impl CredentialStore + 'static) -> Self {
+        Self::from_parts(app_id.into(), Box::new(store))
+    }
+
+    fn from_parts(app_id: String, store: Box<dyn CredentialStore>) -> Self {
         Self {
-            store: Box::new(store),
-            app_id: app_id.into(),
+            store,
+            app_id,
+            refresh_hooks: std::sync::RwLock::new(HashMap::new()),
+            refresh_threshold: chrono::Duration::minutes(5),
+            refresh_locks: std::sync::Mutex::new(HashMap::new()),
         }
     }
 
+    /// Set how long before expiry a credential is refreshed (default: 5 minutes).
+    pub fn with_refresh_threshold(mut self, threshold: chrono::Duration) -> Self {
+        self.refresh_threshold = threshold;
+        self
+    }
+
+    /// Register a hook that issues replacements for credentials of one type.
+    ///
+    /// The hook runs when a credential of `credential_type` is read within the
+    /// refresh threshold of its expiry, and receives the credential's identifier.
+    /// Concurrent readers wait for the single in-flight refresh, and the store is
+    /// only updated once the hook succeeds. If the hook fails, a credential that
+    /// has not yet expired is still returned; an expired one yields the error.
+    pub fn set_refresh_hook<F, Fut>(&self, credential_type: CredentialType, hook: F)
+    where
+        F: Fn(String) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = NetworkResult<Credential>> + Send + 'static,
+    {
+        let hook: RefreshHook = Arc::new(move |id| Box::pin(hook(id)));
+        self.refresh_hooks
+            .write()
+            .unwrap()
+            .insert(credential_type, hook);
+    }
+
     /// Store a token pair.
     pub async fn store_tokens(&self, provider_id: &str, tokens: &TokenPair) -> NetworkResult<()> {
         let key = self.token_key(provider_id);
@@ -193,7 +263,7 @@ impl CredentialManager {
     pub async fn retrieve_tokens(&self, provider_id: &str) -> NetworkResult<Option<TokenPair>> {
         let key = self.token_key(provider_id);
 
-        match self.store.retrieve(&key).await? {
+        match self.retrieve_fresh(&key, provider_id).await? {
             Some(credential) => {
                 if credential.is_expired() {
                     // Auto-delete expired credentials
@@ -222,16 +292,39 @@ impl CredentialManager {
         self.store.store(&storage_key, &credential).await
     }
 
+    /// Store an API key that expires at the given time.
+    pub async fn store_api_key_with_expiry(
+        &self,
+        key_id: &str,
+        api_key: &str,
+        expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> NetworkResult<()> {
+        let storage_key = self.api_key_key(key_id);
+        let credential = Credential::api_key(api_key)
+            .with_metadata("key_id", key_id)
+            .with_expiry(expires_at);
+
+        self.store.store(&storage_key, &credential).await
+    }
+
     /// Retrieve an API key.
+    ///
+    /// Waits for a pending rotation if the key is about to expire.
     pub async fn retrieve_api_key(&self, key_id: &str) -> NetworkResult<Option<String>> {
         let storage_key = self.api_key_key(key_id);
 
-        match self.store.retrieve(&storage_key).await? {
+        match self.retrieve_fresh(&storage_key, key_id).await? {
             Some(credential) => Ok(Some(credential.value)),
             None => Ok(None),
         }
     }
 
+    /// Read an API key as stored, without running a refresh hook.
+    pub async fn peek_api_key(&self, key_id: &str) -> NetworkResult<Option<String>> {
+        let storage_key = self.api_key_key(key_id);
+        Ok(self.store.retrieve(&storage_key).await?.map(|credential| credential.value))
+    }
+
     /// Delete an API key.
     pub async fn delete_api_key(&self, key_id: &str) -> NetworkResult<()> {
         let storage_key = self.api_key_key(key_id);
@@ -248,7 +341,7 @@ impl CredentialManager {
     /// Retrieve a generic secret.
     pub async fn retrieve_secret(&self, name: &str) -> NetworkResult<Option<String>> {
         let key = self.secret_key(name);
-        match self.store.retrieve(&key).await? {
+        match self.retrieve_fresh(&key, name).await? {
             Some(credential) => Ok(Some(credential.value)),
             None => Ok(None),
         }
@@ -270,6 +363,77 @@ impl CredentialManager {
         self.store.clear().await
     }
 
+    /// Retrieve a credential, running its refresh hook first if it is expiring.
+    async fn retrieve_fresh(&self, key: &str, id: &str) -> NetworkResult<Option<Credential>> {
+        let Some(credential) = self.store.retrieve(key).await? else {
+            return Ok(None);
+        };
+        if !self.needs_refresh(&credential) {
+            return Ok(Some(credential));
+        }
+        let hook = self
+            .refresh_hooks
+            .read()
+            .unwrap()
+            .get(&credential.credential_type)
+            .cloned();
+        let Some(hook) = hook else {
+            return Ok(Some(credential));
+        };
+
+        let lock = self
+            .refresh_locks
+            .lock()
+            .unwrap()
+            .entry(key.to_string())
+            .or_default()
+            .clone();
+        let _guard = lock.lock().await;
+
+        // Another reader may have finished the refresh while we waited
+        let Some(credential) = self.store.retrieve(key).await? else {
+            return Ok(None);
+        };
+        if !self.needs_refresh(&credential) {
+            return Ok(Some(credential));
+        }
+
+        debug!(key = %key, "Credential expiring soon, running refresh hook");
+        match hook(id.to_string()).await {
+            Ok(mut refreshed) => {
+                for (name, value) in &credential.metadata {
+                    refreshed
+                        .metadata
+                        .entry(name.clone())
+                        .or_insert_with(|| value.clone());
+                }
+                self.store.store(key, &refreshed).await?;
+                Ok(Some(refreshed))
+            }
+            Err(e) if credential.is_expired() => Err(e),
+            Err(e) => {
+                warn!(
+                    key = %key,
+                    error = %e,
+                    "Credential refresh failed, using existing credential"
+                );
+                Ok(Some(credential))
+            }
+        }
+    }
+
+    /// Whether a credential is close enough to expiry to be refreshed.
+    ///
+    /// The threshold is capped at half the credential's lifetime, so a key that
+    /// lives shorter than the threshold is not refreshed again on every read.
+    fn needs_refresh(&self, credential: &Credential) -> bool {
+        let lifetime = credential
+            .expires_at
+            .map(|exp| exp - credential.stored_at)
+            .unwrap_or_else(chrono::Duration::zero);
+        credential.expires_within(self.refresh_threshold.min(lifetime / 2))
+    }
+
     /// Generate storage key for tokens.
     fn token_key(&self, provider_id: &str) -> String {
         format!("{}.token.{}", self.app_id, provider_id)
@@ -605,4 +769,116 @@ mod tests {
         assert_eq!(retrieved.access_token, "access123");
         assert_eq!(retrieved.refresh_token, Some("refresh456".to_string()));
     }
+
+    #[tokio::test]
+    async fn test_refresh_hook_runs_once_for_concurrent_requests() {
+        use crate::auth::{ApiKeyConfig, ApiKeyProvider, AuthManager};
+        use crate::http::{HttpClient, HttpRequest, RetryConfig};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::TcpListener;
+
+        // Echo back the API key each request arrived with
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                tokio::spawn(async move {
+                    let mut buf = vec![0u8; 4096];
+                    let n = socket.read(&mut buf).await.unwrap();
+                    let head = String::from_utf8_lossy(&buf[..n]).to_string();
+                    let key = head
+                        .lines()
+                        .filter_map(|line| line.split_once(':'))
+                        .find(|(name, _)| name.eq_ignore_ascii_case("x-api-key"))
+                        .map(|(_, value)| value.trim().to_string())
+                        .unwrap_or_default();
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        key.len(),
+                        key
+                    );
+                    socket.write_all(response.as_bytes()).await.unwrap();
+                });
+            }
+        });
+
+        // An hour-long key with 30 seconds left
+        let store = MemoryStore::new();
+        let mut old_key = Credential::api_key("old-key")
+            .with_metadata("key_id", "upstream")
+            .with_expiry(chrono::Utc::now() + chrono::Duration::seconds(30));
+        old_key.stored_at = chrono::Utc::now() - chrono::Duration::hours(1);
+        store.store("test-app.apikey.upstream", &old_key).await.unwrap();
+        let credentials = Arc::new(CredentialManager::with_store("test-app", store));
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        credentials.set_refresh_hook(CredentialType::ApiKey, move |_key_id| {
+            let counter = counter.clone();
+            async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+                // Slow rotation so every request arrives while it is in flight
+                tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+                let expires_at = chrono::Utc::now() + chrono::Duration::hours(1);
+                Ok(Credential::api_key("new-key").with_expiry(expires_at))
+            }
+        });
+
+        let auth = AuthManager::new();
+        auth.register_provider(ApiKeyProvider::from_credentials(
+            "upstream",
+            ApiKeyConfig::default(),
+            credentials.clone(),
+            "upstream",
+        ))
+        .await;
+        let client = HttpClient::builder()
+            .auth_manager(Arc::new(auth))
+            .default_retry(RetryConfig::none())
+            .build()
+            .unwrap();
+
+        let requests = (0..8).map(|_| {
+            let request = HttpRequest::get(format!("{}/data", base)).unwrap().with_auth();
+            client.execute(request)
+        });
+        for response in futures::future::join_all(requests).await {
+            assert_eq!(response.unwrap().body, b"new-key");
+        }
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        let stored = credentials.retrieve_api_key("upstream").await.unwrap();
+        assert_eq!(stored.as_deref(), Some("new-key"));
+    }
+
+    #[tokio::test]
+    async fn test_short_lived_credential_is_not_refreshed_repeatedly() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let store = MemoryStore::new();
+        let mut old_key = Credential::api_key("old-key")
+            .with_expiry(chrono::Utc::now() + chrono::Duration::seconds(30));
+        old_key.stored_at = chrono::Utc::now() - chrono::Duration::hours(1);
+        store.store("test-app.apikey.upstream", &old_key).await.unwrap();
+        let credentials = CredentialManager::with_store("test-app", store);
+
+        // Issued keys live shorter than the 5 minute threshold
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        credentials.set_refresh_hook(CredentialType::ApiKey, move |_key_id| {
+            let n = counter.fetch_add(1, Ordering::SeqCst) + 1;
+            async move {
+                let expires_at = chrono::Utc::now() + chrono::Duration::minutes(4);
+                Ok(Credential::api_key(format!("key-{}", n)).with_expiry(expires_at))
+            }
+        });
+
+        for _ in 0..3 {
+            let key = credentials.retrieve_api_key("upstream").await.unwrap();
+            assert_eq!(key.as_deref(), Some("key-1"));
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
 }
@@ -39,7 +39,7 @@ use std::collections::HashMap;
 use std::fmt;
 
 use crate::cors::CorsConfig;
-use crate::network_mode::{NetworkConfig, NetworkMode, TargetPlatform};
+use crate::network_mode::{ApiEndpoint, NetworkConfig, NetworkMode, TargetPlatform};
 use crate::proxy::ProxyConfig;
 
 /// Severity level for diagnostic issues.
@@ -85,6 +85,8 @@ pub enum IssueCategory {
     Configuration,
     /// Connectivity problem.
     Connectivity,
+    /// Insecure (`http://`, `ws://`) request from a page served over HTTPS.
+    MixedContent,
 }
 
 impl fmt::Display for IssueCategory {
@@ -97,6 +99,7 @@ impl fmt::Display for IssueCategory {
             IssueCategory::Performance => write!(f, "Performance"),
             IssueCategory::Configuration => write!(f, "Configuration"),
             IssueCategory::Connectivity => write!(f, "Connectivity"),
+            IssueCategory::MixedContent => write!(f, "Mixed Content"),
         }
     }
 }
@@ -396,6 +399,9 @@ impl NetworkDoctor {
         // Check security
         self.check_security(config, &mut report);
 
+        // Check for endpoints the browser will block as mixed content
+        self.check_mixed_content(config, &mut report);
+
         // Apply custom rules
         for rule in &self.custom_rules {
             if let Some(issue) = rule.check(config) {
@@ -711,6 +717,29 @@ impl NetworkDoctor {
             }
         }
     }
+
+    fn check_mixed_content(&self, config: &NetworkConfig, report: &mut DiagnosticReport) {
+        // Proxied and same-origin requests go to the page's own origin
+        if !config.target.is_browser_based()
+            || matches!(config.mode, NetworkMode::Proxied | NetworkMode::SameOrigin)
+        {
+            return;
+        }
+
+        let origin = if config.dev_mode {
+            &config.frontend_origin
+        } else {
+            &config.production_origin
+        };
+        let served_over_https = origin
+            .as_deref()
+            .map(|o| o.starts_with("https://"))
+            .unwrap_or(false);
+
+        for issue in quick_checks::check_mixed_content(&config.endpoints, served_over_https) {
+            report.add_issue(issue);
+        }
+    }
 }
 
 /// Trait for custom diagnostic rules.
@@ -769,6 +798,53 @@ pub mod quick_checks {
         issues
     }
 
+    /// Check endpoints a browser page would call directly for mixed content.
+    ///
+    /// When the page is served over HTTPS, browsers block `http://` and
+    /// `ws://` requests. Loopback hosts are exempt since browsers treat them
+    /// as secure.
+    pub fn check_mixed_content(
+        endpoints: &[ApiEndpoint],
+        served_over_https: bool,
+    ) -> Vec<DiagnosticIssue> {
+        if !served_over_https {
+            return Vec::new();
+        }
+
+        endpoints
+            .iter()
+            .filter(|endpoint| {
+                let Ok(url) = url::Url::parse(&endpoint.url) else {
+                    return false;
+                };
+                let loopback = match url.host() {
+                    Some(url::Host::Domain(host)) => host == "localhost",
+                    Some(url::Host::Ipv4(ip)) => ip == std::net::Ipv4Addr::LOCALHOST,
+                    Some(url::Host::Ipv6(ip)) => ip == std::net::Ipv6Addr::LOCALHOST,
+                    None => false,
+                };
+                matches!(url.scheme(), "http" | "ws") && !loopback
+            })
+            .map(|endpoint| {
+                DiagnosticIssue::new(
+                    "SEC003",
+                    Severity::Error,
+                    IssueCategory::MixedContent,
+                    format!(
+                        "Endpoint '{}' uses an insecure URL on an HTTPS page: {}",
+                        endpoint.name, endpoint.url
+                    ),
+                )
+                .with_details("Browsers block insecure requests from HTTPS pages as mixed content")
+                .with_fix(
+                    "Use https:// (or wss://) for the endpoint, or route it through the \
+                     dev proxy: oxide dev --proxy api=http://localhost:8000",
+                )
+                .with_config("network.endpoints")
+            })
+            .collect()
+    }
+
     /// Get a quick recommendation for a target platform.
     pub fn get_recommendation(target: TargetPlatform, dev_mode: bool) -> String {
         match (target, dev_mode) {
@@ -934,6 +1010,45 @@ mod tests {
         assert!(recommendation.contains("don't need CORS"));
     }
 
+    #[test]
+    fn test_mixed_content() {
+        let endpoints = vec![
+            ApiEndpoint::new("api", "http://api.example.com"),
+            ApiEndpoint::new("auth", "https://auth.example.com"),
+            ApiEndpoint::new("local", "http://localhost:8000"),
+            ApiEndpoint::new("loopback", "ws://127.0.0.1:9000/socket"),
+            ApiEndpoint::new("ipv6", "http://[::1]:8000"),
+            ApiEndpoint::new("lookalike", "http://localhost.evil.com"),
+            ApiEndpoint::new("userinfo", "http://localhost@api.example.com"),
+        ];
+
+        let issues = quick_checks::check_mixed_content(&endpoints, true);
+        assert_eq!(issues.len(), 3);
+        assert!(issues[1].message.contains("'lookalike'"));
+        assert!(issues[2].message.contains("'userinfo'"));
+        assert_eq!(issues[0].severity, Severity::Error);
+        assert_eq!(issues[0].category, IssueCategory::MixedContent);
+        assert!(issues[0].message.contains("'api'"));
+        assert!(issues[0].fix.as_deref().unwrap().contains("https://"));
+
+        assert!(quick_checks::check_mixed_content(&endpoints, false).is_empty());
+
+        let mut config = NetworkConfig::for_target(TargetPlatform::Web);
+        config.mode = NetworkMode::CrossOrigin;
+        config.allowed_origins = vec!["https://app.example.com".to_string()];
+        config.production_origin = Some("https://app.example.com".to_string());
+        config.add_endpoint(ApiEndpoint::new("api", "http://api.example.com"));
+
+        let report = NetworkDoctor::new().diagnose(&config);
+        assert_eq!(report.issues_by_category(IssueCategory::MixedContent).len(), 1);
+        assert!(report.has_errors());
+
+        // Behind a reverse proxy the browser never sees the backend URL
+        config.mode = NetworkMode::SameOrigin;
+        let report = NetworkDoctor::new().diagnose(&config);
+        assert!(report.issues_by_category(IssueCategory::MixedContent).is_empty());
+    }
+
     #[test]
     fn test_severity_ordering() {
         assert!(Severity::Info < Severity::Warning);
@@ -155,6 +155,24 @@ pub enum NetworkError {
         capability: String,
     },
 
+    /// Requests to a host are short-circuited after repeated failures.
+    #[error("Circuit open for '{host}': retry after {retry_after_secs}s")]
+    CircuitOpen {
+        /// The host whose circuit is open.
+        host: String,
+        /// Seconds until the circuit lets a probe request through.
+        retry_after_secs: u64,
+    },
+
+    /// No recorded response matches a replayed request.
+    #[error("No recorded response for {method} {url}")]
+    NoRecordedResponse {
+        /// The request method.
+        method: String,
+        /// The request URL.
+        url: String,
+    },
+
     /// Generic IO error.
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
@@ -298,6 +316,36 @@ impl From<AuthError> for NetworkError {
     }
 }
 
+/// WebSocket send errors.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum WsError {
+    /// The send queue is at capacity.
+    #[error("WebSocket send queue full ({capacity} messages)")]
+    QueueFull {
+        /// The configured queue length.
+        capacity: usize,
+    },
+
+    /// The connection is not in a state that accepts messages.
+    #[error("Cannot send in state: {state}")]
+    NotConnected {
+        /// The current connection state.
+        state: crate::websocket::WsConnectionState,
+    },
+
+    /// The connection's outgoing channel has closed.
+    #[error("WebSocket channel closed")]
+    ChannelClosed,
+}
+
+impl From<WsError> for NetworkError {
+    fn from(err: WsError) -> Self {
+        NetworkError::WebSocketError {
+            message: err.to_string(),
+        }
+    }
+}
+
 /// Auth state for tracking authentication lifecycle.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AuthState {
@@ -1,5 +1,6 @@
 //! HTTP client implementation.
 
+use futures::StreamExt;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -12,8 +13,12 @@ use crate::error::{NetworkError, NetworkResult};
 use crate::interceptor::{Interceptor, InterceptorChain};
 use crate::offline::OfflineDetector;
 
+use super::request::{short_stream, MultipartChunk};
 use super::{HttpRequest, HttpResponse, RequestBody, RetryConfig};
 
+/// Size of the reads used to stream multipart file parts.
+const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+
 /// Configuration for the HTTP client.
 #[derive(Debug, Clone)]
 pub struct HttpClientConfig {
@@ -21,6 +26,8 @@ pub struct HttpClientConfig {
     pub base_url: Option<String>,
     /// Default timeout for requests.
     pub default_timeout: Duration,
+    /// Default timeout for establishing connections.
+    pub connect_timeout: Option<Duration>,
     /// Default headers to add to all requests.
     pub default_headers: HashMap<String, String>,
     /// User agent string.
@@ -40,6 +47,7 @@ impl Default for HttpClientConfig {
         Self {
             base_url: None,
             default_timeout: Duration::from_secs(30),
+            connect_timeout: None,
             default_headers: HashMap::new(),
             user_agent: format!("OxideKit/{}", env!("CARGO_PKG_VERSION")),
             compression: true,
@@ -56,7 +64,7 @@ pub struct HttpClientBuilder {
     config: HttpClientConfig,
     allowlist: Option<Allowlist>,
     auth_manager: Option<Arc<AuthManager>>,
-    interceptors: Vec<Arc<dyn Interceptor>>,
+    interceptors: InterceptorChain,
     offline_detector: Option<Arc<OfflineDetector>>,
 }
 
@@ -78,6 +86,12 @@ impl HttpClientBuilder {
         self
     }
 
+    /// Set the default connect timeout.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.config.connect_timeout = Some(timeout);
+        self
+    }
+
     /// Add a default header.
     pub fn default_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
         self.config.default_headers.insert(name.into(), value.into());
@@ -110,7 +124,20 @@ impl HttpClientBuilder {
 
     /// Add an interceptor.
     pub fn interceptor(mut self, interceptor: impl Interceptor + 'static) -> Self {
-        self.interceptors.push(Arc::new(interceptor));
+        self.interceptors.add(interceptor);
+        self
+    }
+
+    /// Add an interceptor with an explicit priority.
+    ///
+    /// Higher priorities see requests first and responses last; see
+    /// [`InterceptorChain::add_with_priority`].
+    pub fn interceptor_with_priority(
+        mut self,
+        interceptor: impl Interceptor + 'static,
+        priority: i32,
+    ) -> Self {
+        self.interceptors.add_with_priority(interceptor, priority);
         self
     }
 
@@ -128,21 +155,15 @@ impl HttpClientBuilder {
 
     /// Build the HTTP client.
     pub fn build(self) -> NetworkResult<HttpClient> {
-        let inner_client = reqwest::Client::builder()
-            .timeout(self.config.default_timeout)
-            .user_agent(&self.config.user_agent)
-            .pool_idle_timeout(self.config.pool_idle_timeout)
-            .gzip(self.config.compression)
-            .brotli(self.config.compression)
-            .build()
-            .map_err(NetworkError::HttpError)?;
+        let inner_client = build_inner(&self.config, self.config.connect_timeout)?;
 
         Ok(HttpClient {
             inner: inner_client,
+            connect_clients: Arc::new(std::sync::Mutex::new(HashMap::new())),
             config: self.config,
             allowlist: self.allowlist.map(Arc::new),
             auth_manager: self.auth_manager,
-            interceptors: Arc::new(InterceptorChain::new(self.interceptors)),
+            interceptors: Arc::new(self.interceptors),
             offline_detector: self.offline_detector,
             active_requests: Arc::new(RwLock::new(0)),
         })
@@ -160,6 +181,8 @@ impl HttpClientBuilder {
 #[derive(Clone)]
 pub struct HttpClient {
     inner: reqwest::Client,
+    /// Clients for requests that override the connect timeout.
+    connect_clients: Arc<std::sync::Mutex<HashMap<Duration, reqwest::Client>>>,
     config: HttpClientConfig,
     allowlist: Option<Arc<Allowlist>>,
     auth_manager: Option<Arc<AuthManager>>,
@@ -180,7 +203,51 @@ impl HttpClient {
     }
 
     /// Execute an HTTP request.
-    pub async fn execute(&self, mut request: HttpRequest) -> NetworkResult<HttpResponse> {
+    pub async fn execute(&self, request: HttpRequest) -> NetworkResult<HttpResponse> {
+        self.execute_mode(request, false).await
+    }
+
+    /// Execute an HTTP request without buffering the response body.
+    ///
+    /// Returns as soon as the status and headers arrive; read the body with
+    /// [`HttpResponse::bytes_stream`]. Use this for large downloads and
+    /// event streams. Retries only cover failures before the body starts.
+    /// The client's default timeout only bounds the wait for headers; a
+    /// per-request timeout still applies to the whole transfer.
+    pub async fn execute_streaming(&self, request: HttpRequest) -> NetworkResult<HttpResponse> {
+        self.execute_mode(request, true).await
+    }
+
+    /// Run the full request pipeline, optionally streaming the body.
+    async fn execute_mode(
+        &self,
+        request: HttpRequest,
+        streaming: bool,
+    ) -> NetworkResult<HttpResponse> {
+        let mut request = self.prepare(request).await?;
+
+        // Run request interceptors; one may answer without touching the network
+        if let Some(short_circuited) = self.interceptors.intercept_request(&mut request).await? {
+            return self.interceptors.intercept_short_circuited(short_circuited).await;
+        }
+
+        // Execute with retry logic
+        let response = match self.execute_with_retry(&request, streaming).await {
+            Ok(response) => response,
+            Err(err) => {
+                self.interceptors.on_error(&request, &err).await;
+                return Err(err);
+            }
+        };
+
+        // Run response interceptors
+        let response = self.interceptors.intercept_response(response).await?;
+
+        Ok(response)
+    }
+
+    /// Check policy and apply client defaults and auth to a request.
+    async fn prepare(&self, mut request: HttpRequest) -> NetworkResult<HttpRequest> {
         // Check offline status
         if let Some(detector) = &self.offline_detector {
             if detector.is_offline() {
@@ -222,21 +289,21 @@ impl HttpClient {
             }
         }
 
-        // Run request interceptors
-        let request = self.interceptors.intercept_request(request).await?;
-
-        // Execute with retry logic
-        let response = self.execute_with_retry(request).await?;
-
-        // Run response interceptors
-        let response = self.interceptors.intercept_response(response).await?;
-
-        Ok(response)
+        Ok(request)
     }
 
     /// Execute a request with retry logic.
-    async fn execute_with_retry(&self, request: HttpRequest) -> NetworkResult<HttpResponse> {
-        let retry_config = if request.retry_config.max_retries > 0 {
+    ///
+    /// Requests with a body that can't be replayed get a single attempt, so
+    /// the caller sees the first failure rather than a drained stream.
+    async fn execute_with_retry(
+        &self,
+        request: &HttpRequest,
+        streaming: bool,
+    ) -> NetworkResult<HttpResponse> {
+        let retry_config = if !request.body.is_replayable() {
+            &RetryConfig::none()
+        } else if request.retry_config.max_retries > 0 {
             &request.retry_config
         } else {
             &self.config.default_retry
@@ -244,9 +311,10 @@ impl HttpClient {
 
         let mut _last_error: Option<NetworkError> = None;
         let mut attempt = 0;
+        let started = Instant::now();
 
         loop {
-            match self.execute_single(&request).await {
+            match self.execute_single(request, streaming).await {
                 Ok(response) => {
                     // Check if we should retry based on status code
                     if retry_config.should_retry_status(response.status)
@@ -256,6 +324,9 @@ impl HttpClient {
                             .retry_after()
                             .map(Duration::from_secs)
                             .unwrap_or_else(|| retry_config.delay_for_attempt(attempt));
+                        if !retry_config.within_budget(started.elapsed(), delay) {
+                            return Ok(response);
+                        }
 
                         warn!(
                             request_id = %request.id,
@@ -280,6 +351,9 @@ impl HttpClient {
                         .suggested_retry_delay()
                         .map(Duration::from_secs)
                         .unwrap_or_else(|| retry_config.delay_for_attempt(attempt));
+                    if !retry_config.within_budget(started.elapsed(), delay) {
+                        return Err(err);
+                    }
 
                     warn!(
                         request_id = %request.id,
@@ -298,7 +372,11 @@ impl HttpClient {
     }
 
     /// Execute a single request without retry.
-    async fn execute_single(&self, request: &HttpRequest) -> NetworkResult<HttpResponse> {
+    async fn execute_single(
+        &self,
+        request: &HttpRequest,
+        streaming: bool,
+    ) -> NetworkResult<HttpResponse> {
         // Track active requests
         {
             let mut count = self.active_requests.write().await;
@@ -309,7 +387,7 @@ impl HttpClient {
         }
 
         let start = Instant::now();
-        let result = self.do_execute(request).await;
+        let result = self.do_execute(request, streaming).await;
 
         // Decrement active requests
         {
@@ -350,18 +428,47 @@ impl HttpClient {
         }
     }
 
+    /// Get the underlying client for a request's connect timeout.
+    ///
+    /// Connect timeouts are a connection-pool setting in reqwest, so
+    /// overrides get their own (cached) client.
+    fn inner_for(&self, request: &HttpRequest) -> NetworkResult<reqwest::Client> {
+        let Some(connect_timeout) = request.connect_timeout else {
+            return Ok(self.inner.clone());
+        };
+        if self.config.connect_timeout == Some(connect_timeout) {
+            return Ok(self.inner.clone());
+        }
+
+        let mut clients = self.connect_clients.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(client) = clients.get(&connect_timeout) {
+            return Ok(client.clone());
+        }
+        let client = build_inner(&self.config, Some(connect_timeout))?;
+        clients.insert(connect_timeout, client.clone());
+        Ok(client)
+    }
+
     /// Internal request execution.
-    async fn do_execute(&self, request: &HttpRequest) -> NetworkResult<HttpResponse> {
+    async fn do_execute(
+        &self,
+        request: &HttpRequest,
+        streaming: bool,
+    ) -> NetworkResult<HttpResponse> {
         let method: reqwest::Method = request.method.into();
-        let mut builder = self.inner.request(method, request.url.clone());
+        let mut builder = self.inner_for(request)?.request(method, request.url.clone());
 
         // Add headers
         for (key, value) in &request.headers {
             builder = builder.header(key, value);
         }
 
-        // Set timeout
-        if let Some(timeout) = request.timeout {
+        // Set timeout. reqwest's timeout is a total-request deadline that also
+        // covers the body, which would cut off long-lived streams; without an
+        // explicit per-request timeout, streams only bound the wait for headers.
+        let timeout = request.timeout.unwrap_or(self.config.default_timeout);
+        let headers_deadline = streaming && request.timeout.is_none();
+        if !headers_deadline {
             builder = builder.timeout(timeout);
         }
 
@@ -372,40 +479,39 @@ impl HttpClient {
             RequestBody::Bytes(data) => builder.body(data.clone()),
             RequestBody::Text(text) => builder.body(text.clone()),
             RequestBody::Form(data) => builder.form(data),
-            RequestBody::Multipart(fields) => {
-                let mut form = reqwest::multipart::Form::new();
-                for field in fields {
-                    match &field.value {
-                        super::MultipartValue::Text(text) => {
-                            form = form.text(field.name.clone(), text.clone());
-                        }
-                        super::MultipartValue::File {
-                            filename,
-                            content_type,
-                            data,
-                        } => {
-                            let part = reqwest::multipart::Part::bytes(data.clone())
-                                .file_name(filename.clone())
-                                .mime_str(content_type)
-                                .map_err(|e| NetworkError::ConfigError {
-                                    message: e.to_string(),
-                                })?;
-                            form = form.part(field.name.clone(), part);
-                        }
-                    }
+            RequestBody::Multipart(form) => {
+                let has_content_type = request
+                    .headers
+                    .keys()
+                    .any(|key| key.eq_ignore_ascii_case("content-type"));
+                if !has_content_type {
+                    builder = builder.header("Content-Type", form.content_type());
+                }
+                if form.has_streams() {
+                    let length = form.content_length();
+                    let body = reqwest::Body::wrap_stream(multipart_stream(form.chunks()?));
+                    builder.header("Content-Length", length).body(body)
+                } else {
+                    builder.body(form.to_bytes()?)
                 }
-                builder.multipart(form)
             }
         };
 
         // Execute request
-        let response = builder.send().await.map_err(|e| {
+        let send = builder.send();
+        let sent = if headers_deadline {
+            tokio::time::timeout(timeout, send)
+                .await
+                .map_err(|_| NetworkError::Timeout {
+                    duration_secs: timeout.as_secs(),
+                })?
+        } else {
+            send.await
+        };
+        let response = sent.map_err(|e| {
             if e.is_timeout() {
                 NetworkError::Timeout {
-                    duration_secs: request
-                        .timeout
-                        .unwrap_or(self.config.default_timeout)
-                        .as_secs(),
+                    duration_secs: timeout.as_secs(),
                 }
             } else if e.is_connect() {
                 NetworkError::Offline
@@ -428,6 +534,22 @@ impl HttpClient {
             .map(|(k, v)| (k.as_str().to_string(), v.to_str().unwrap_or("").to_string()))
             .collect();
 
+        if streaming {
+            let stream = response
+                .bytes_stream()
+                .map(|chunk| chunk.map_err(NetworkError::HttpError))
+                .boxed();
+            let response = HttpResponse::new(
+                request.id,
+                status,
+                headers,
+                Vec::new(),
+                final_url,
+                Duration::ZERO, // Will be set by caller
+            );
+            return Ok(response.with_stream(stream));
+        }
+
         let body = response.bytes().await.map_err(NetworkError::HttpError)?;
 
         Ok(HttpResponse::new(
@@ -488,6 +610,23 @@ impl HttpClient {
     }
 }
 
+/// Build the underlying reqwest client from the configuration.
+fn build_inner(
+    config: &HttpClientConfig,
+    connect_timeout: Option<Duration>,
+) -> NetworkResult<reqwest::Client> {
+    // Deadlines are applied per request; see `do_execute`
+    let mut builder = reqwest::Client::builder()
+        .user_agent(&config.user_agent)
+        .pool_idle_timeout(config.pool_idle_timeout)
+        .gzip(config.compression)
+        .brotli(config.compression);
+    if let Some(timeout) = connect_timeout {
+        builder = builder.connect_timeout(timeout);
+    }
+    builder.build().map_err(NetworkError::HttpError)
+}
+
 impl std::fmt::Debug for HttpClient {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("HttpClient")
@@ -498,6 +637,42 @@ impl std::fmt::Debug for HttpClient {
     }
 }
 
+/// Stream a multipart body, reading file parts on the blocking pool.
+fn multipart_stream(
+    chunks: Vec<MultipartChunk>,
+) -> impl futures::Stream<Item = std::io::Result<Vec<u8>>> + Send + 'static {
+    futures::stream::iter(chunks).flat_map(|chunk| match chunk {
+        MultipartChunk::Bytes(bytes) => futures::stream::once(async move { Ok(bytes) }).boxed(),
+        MultipartChunk::Reader(reader, len) => read_stream(reader, len).boxed(),
+    })
+}
+
+/// Read exactly `len` bytes from a reader as a stream of chunks.
+fn read_stream(
+    reader: std::io::Take<Box<dyn std::io::Read + Send>>,
+    len: u64,
+) -> impl futures::Stream<Item = std::io::Result<Vec<u8>>> + Send + 'static {
+    futures::stream::try_unfold((reader, len), |(mut reader, remaining)| async move {
+        if remaining == 0 {
+            return Ok(None);
+        }
+        let size = STREAM_CHUNK_SIZE.min(usize::try_from(remaining).unwrap_or(usize::MAX));
+        let (chunk, reader) = tokio::task::spawn_blocking(move || {
+            let mut chunk = vec![0; size];
+            let read = std::io::Read::read(&mut reader, &mut chunk)?;
+            chunk.truncate(read);
+            Ok::<_, std::io::Error>((chunk, reader))
+        })
+        .await
+        .map_err(std::io::Error::other)??;
+        if chunk.is_empty() {
+            return Err(short_stream());
+        }
+        let remaining = remaining - chunk.len() as u64;
+        Ok(Some((chunk, (reader, remaining))))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -518,4 +693,233 @@ mod tests {
 
         assert!(client.config.base_url.is_some());
     }
+
+    #[tokio::test]
+    async fn test_streaming_response_arrives_incrementally() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (release_tx, release_rx) = tokio::sync::oneshot::channel::<()>();
+
+        // Sends one chunk, then holds the body open until released
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            let _ = socket.read(&mut buf).await.unwrap();
+            socket
+                .write_all(
+                    b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\n\
+                      Transfer-Encoding: chunked\r\n\r\n5\r\nfirst\r\n",
+                )
+                .await
+                .unwrap();
+            release_rx.await.unwrap();
+            socket.write_all(b"6\r\nsecond\r\n0\r\n\r\n").await.unwrap();
+        });
+
+        let client = HttpClient::new().unwrap();
+        let request = HttpRequest::get(format!("http://{}/events", addr)).unwrap().no_retry();
+        let response = tokio::time::timeout(
+            Duration::from_secs(5),
+            client.execute_streaming(request),
+        )
+        .await
+        .expect("returned before the body finished")
+        .unwrap();
+        assert_eq!(response.status, 200);
+        assert!(response.is_streaming());
+
+        let mut stream = Box::pin(response.bytes_stream());
+        let first = stream.next().await.unwrap().unwrap();
+        assert_eq!(&first[..], b"first");
+
+        release_tx.send(()).unwrap();
+        let second = stream.next().await.unwrap().unwrap();
+        assert_eq!(&second[..], b"second");
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_per_request_timeout_overrides_default() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        // Answers every request after 300ms
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 1024];
+                    let _ = socket.read(&mut buf).await;
+                    tokio::time::sleep(Duration::from_millis(300)).await;
+                    let _ = socket
+                        .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok")
+                        .await;
+                });
+            }
+        });
+
+        let client = HttpClient::builder()
+            .timeout(Duration::from_millis(100))
+            .default_retry(RetryConfig::none())
+            .build()
+            .unwrap();
+        let url = format!("http://{}/upload", addr);
+
+        let request = HttpRequest::get(&url).unwrap().no_retry();
+        assert!(matches!(
+            client.execute(request).await,
+            Err(NetworkError::Timeout { .. })
+        ));
+
+        let request = HttpRequest::get(&url)
+            .unwrap()
+            .no_retry()
+            .with_timeout(Duration::from_secs(5))
+            .with_connect_timeout(Duration::from_secs(1));
+        let response = client.execute(request).await.unwrap();
+        assert_eq!(response.text().unwrap(), "ok");
+    }
+
+    #[tokio::test]
+    async fn test_streaming_outlives_default_timeout() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        // Streams a chunk every 100ms for well past the client timeout
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            let _ = socket.read(&mut buf).await.unwrap();
+            socket
+                .write_all(b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n")
+                .await
+                .unwrap();
+            for _ in 0..8 {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                socket.write_all(b"1\r\nx\r\n").await.unwrap();
+            }
+            socket.write_all(b"0\r\n\r\n").await.unwrap();
+        });
+
+        let client = HttpClient::builder()
+            .timeout(Duration::from_millis(300))
+            .default_retry(RetryConfig::none())
+            .build()
+            .unwrap();
+        let request = HttpRequest::get(format!("http://{}/download", addr)).unwrap();
+        let response = client.execute_streaming(request).await.unwrap();
+
+        let mut stream = Box::pin(response.bytes_stream());
+        let mut received = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            received.extend_from_slice(&chunk.unwrap());
+        }
+        assert_eq!(received, b"xxxxxxxx");
+    }
+
+    #[tokio::test]
+    async fn test_multipart_stream_upload() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        // Echoes the request body back
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 8192];
+            let head_end = loop {
+                let read = socket.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..read]);
+                if let Some(pos) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+                    break pos + 4;
+                }
+            };
+            let head = String::from_utf8_lossy(&request[..head_end]).to_lowercase();
+            let length: usize = head
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length: "))
+                .unwrap()
+                .trim()
+                .parse()
+                .unwrap();
+            while request.len() < head_end + length {
+                let read = socket.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..read]);
+            }
+            let body = &request[head_end..];
+            let response = format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n", body.len());
+            socket.write_all(response.as_bytes()).await.unwrap();
+            socket.write_all(body).await.unwrap();
+        });
+
+        let data: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+        let form = crate::http::MultipartForm::new().text("kind", "blob").file_stream(
+            "upload",
+            "blob.bin",
+            "application/octet-stream",
+            std::io::Cursor::new(data.clone()),
+            data.len() as u64,
+        );
+        let length = form.content_length() as usize;
+        let tail = [data, format!("\r\n--{}--\r\n", form.boundary()).into_bytes()].concat();
+
+        let client = HttpClient::builder()
+            .default_retry(RetryConfig::none())
+            .build()
+            .unwrap();
+        let request = HttpRequest::post(format!("http://{}/upload", addr))
+            .unwrap()
+            .multipart(form);
+        let response = client.execute(request).await.unwrap();
+        assert_eq!(response.body.len(), length);
+        assert!(response.body.ends_with(&tail));
+    }
+
+    #[tokio::test]
+    async fn test_streamed_body_is_not_retried() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let connections = Arc::new(AtomicUsize::new(0));
+        let accepted = connections.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                accepted.fetch_add(1, Ordering::SeqCst);
+                let mut buf = [0u8; 8192];
+                let _ = socket.read(&mut buf).await;
+                let response = "HTTP/1.1 503 Service Unavailable\r\n\
+                                Content-Length: 0\r\nConnection: close\r\n\r\n";
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        let client = HttpClient::builder()
+            .default_retry(RetryConfig::with_max_retries(3).base(Duration::from_millis(1)))
+            .build()
+            .unwrap();
+        let form = crate::http::MultipartForm::new().file_stream(
+            "upload",
+            "blob.bin",
+            "application/octet-stream",
+            std::io::Cursor::new(b"abc".to_vec()),
+            3,
+        );
+        let request = HttpRequest::post(format!("http://{}/upload", addr))
+            .unwrap()
+            .multipart(form);
+        let response = client.execute(request).await.unwrap();
+        assert_eq!(response.status, 503);
+        assert_eq!(connections.load(Ordering::SeqCst), 1);
+    }
 }
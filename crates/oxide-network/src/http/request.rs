@@ -2,10 +2,15 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
+use std::io::Read;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use url::Url;
 use uuid::Uuid;
 
+use crate::error::{NetworkError, NetworkResult};
+
 /// HTTP methods supported by the client.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
@@ -66,7 +71,7 @@ pub enum RequestBody {
     /// Form data (URL encoded).
     Form(HashMap<String, String>),
     /// Multipart form data.
-    Multipart(Vec<MultipartField>),
+    Multipart(MultipartForm),
     /// Plain text.
     Text(String),
 }
@@ -77,6 +82,21 @@ impl Default for RequestBody {
     }
 }
 
+impl RequestBody {
+    /// Create a multipart/form-data body.
+    pub fn multipart(form: MultipartForm) -> Self {
+        RequestBody::Multipart(form)
+    }
+
+    /// Check if the body can be sent more than once.
+    ///
+    /// Streamed multipart parts are read by the first attempt, so requests
+    /// carrying them are never retried.
+    pub fn is_replayable(&self) -> bool {
+        !matches!(self, RequestBody::Multipart(form) if form.has_streams())
+    }
+}
+
 /// A field in a multipart form request.
 #[derive(Debug, Clone)]
 pub struct MultipartField {
@@ -100,6 +120,339 @@ pub enum MultipartValue {
         /// File content.
         data: Vec<u8>,
     },
+    /// File content read from a stream while the request is sent.
+    Stream {
+        /// Original filename.
+        filename: String,
+        /// MIME type.
+        content_type: String,
+        /// Source of the file content.
+        reader: MultipartReader,
+    },
+}
+
+/// A single-use source for a streamed multipart file part.
+///
+/// Clones share the source, so the content can only be sent once.
+#[derive(Clone)]
+pub struct MultipartReader {
+    len: u64,
+    source: Arc<Mutex<Option<Box<dyn Read + Send>>>>,
+}
+
+impl MultipartReader {
+    /// Get the declared content length.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Check if the declared content length is zero.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Take the source, limited to the declared length.
+    fn take(&self, filename: &str) -> NetworkResult<std::io::Take<Box<dyn Read + Send>>> {
+        let mut source = self.source.lock().unwrap_or_else(|e| e.into_inner());
+        source.take().map(|reader| reader.take(self.len)).ok_or_else(|| {
+            NetworkError::ConfigError {
+                message: format!("multipart stream '{}' was already consumed", filename),
+            }
+        })
+    }
+}
+
+impl fmt::Debug for MultipartReader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MultipartReader").field("len", &self.len).finish_non_exhaustive()
+    }
+}
+
+/// A piece of a serialized multipart body.
+pub(crate) enum MultipartChunk {
+    /// Bytes already in memory.
+    Bytes(Vec<u8>),
+    /// File content still to be read, and its declared length.
+    Reader(std::io::Take<Box<dyn Read + Send>>, u64),
+}
+
+/// A multipart/form-data body for file uploads.
+#[derive(Debug, Clone)]
+pub struct MultipartForm {
+    /// Boundary separating the parts.
+    boundary: String,
+    /// Form fields in order.
+    fields: Vec<MultipartField>,
+}
+
+impl Default for MultipartForm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MultipartForm {
+    /// Create an empty form with a random boundary.
+    pub fn new() -> Self {
+        Self {
+            boundary: format!("oxidekit-{}", Uuid::new_v4().simple()),
+            fields: Vec::new(),
+        }
+    }
+
+    /// Add a text field.
+    pub fn text(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.fields.push(MultipartField {
+            name: name.into(),
+            value: MultipartValue::Text(value.into()),
+        });
+        self
+    }
+
+    /// Add a file part from bytes.
+    pub fn file(
+        mut self,
+        name: impl Into<String>,
+        filename: impl Into<String>,
+        content_type: impl Into<String>,
+        data: Vec<u8>,
+    ) -> Self {
+        self.fields.push(MultipartField {
+            name: name.into(),
+            value: MultipartValue::File {
+                filename: filename.into(),
+                content_type: content_type.into(),
+                data,
+            },
+        });
+        self
+    }
+
+    /// Add a file part by reading it to the end.
+    ///
+    /// The whole file is buffered in memory; use
+    /// [`file_stream`](Self::file_stream) for large files.
+    pub fn file_from_reader(
+        self,
+        name: impl Into<String>,
+        filename: impl Into<String>,
+        content_type: impl Into<String>,
+        mut reader: impl Read,
+    ) -> std::io::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        Ok(self.file(name, filename, content_type, data))
+    }
+
+    /// Add a file part that is streamed from `reader` while the request is sent.
+    ///
+    /// Exactly `len` bytes are read. The reader is consumed by the first
+    /// attempt, so a failed request carrying it cannot be retried.
+    pub fn file_stream(
+        mut self,
+        name: impl Into<String>,
+        filename: impl Into<String>,
+        content_type: impl Into<String>,
+        reader: impl Read + Send + 'static,
+        len: u64,
+    ) -> Self {
+        let reader: Box<dyn Read + Send> = Box::new(reader);
+        self.fields.push(MultipartField {
+            name: name.into(),
+            value: MultipartValue::Stream {
+                filename: filename.into(),
+                content_type: content_type.into(),
+                reader: MultipartReader {
+                    len,
+                    source: Arc::new(Mutex::new(Some(reader))),
+                },
+            },
+        });
+        self
+    }
+
+    /// Get the boundary string.
+    pub fn boundary(&self) -> &str {
+        &self.boundary
+    }
+
+    /// Get the form fields.
+    pub fn fields(&self) -> &[MultipartField] {
+        &self.fields
+    }
+
+    /// Get the `Content-Type` header value, including the boundary.
+    pub fn content_type(&self) -> String {
+        format!("multipart/form-data; boundary={}", self.boundary)
+    }
+
+    /// Check if any file part is streamed from a reader.
+    pub fn has_streams(&self) -> bool {
+        self.fields
+            .iter()
+            .any(|field| matches!(field.value, MultipartValue::Stream { .. }))
+    }
+
+    /// Get the length of the serialized body.
+    pub fn content_length(&self) -> u64 {
+        let mut len = 0;
+        for piece in self.layout() {
+            len += match piece {
+                Piece::Bytes(bytes) => bytes.len() as u64,
+                Piece::Stream(_, reader) => reader.len(),
+            };
+        }
+        len
+    }
+
+    /// Serialize the form into a request body, reading any streamed parts.
+    ///
+    /// Fails if a part has an invalid content type or a streamed part ends
+    /// before its declared length.
+    pub fn to_bytes(&self) -> NetworkResult<Vec<u8>> {
+        let mut body = Vec::new();
+        for chunk in self.chunks()? {
+            match chunk {
+                MultipartChunk::Bytes(bytes) => body.extend_from_slice(&bytes),
+                MultipartChunk::Reader(mut reader, len) => {
+                    let start = body.len();
+                    reader.read_to_end(&mut body)?;
+                    if ((body.len() - start) as u64) < len {
+                        return Err(short_stream().into());
+                    }
+                }
+            }
+        }
+        Ok(body)
+    }
+
+    /// Validate the parts and split the body into bytes and streamed parts.
+    pub(crate) fn chunks(&self) -> NetworkResult<Vec<MultipartChunk>> {
+        for field in &self.fields {
+            if let MultipartValue::File { content_type, .. }
+            | MultipartValue::Stream { content_type, .. } = &field.value
+            {
+                if !is_valid_content_type(content_type) {
+                    return Err(NetworkError::ConfigError {
+                        message: format!("invalid multipart content type {:?}", content_type),
+                    });
+                }
+            }
+        }
+
+        let mut chunks: Vec<MultipartChunk> = Vec::new();
+        for piece in self.layout() {
+            match piece {
+                Piece::Bytes(bytes) => match chunks.last_mut() {
+                    Some(MultipartChunk::Bytes(last)) => last.extend_from_slice(&bytes),
+                    _ => chunks.push(MultipartChunk::Bytes(bytes)),
+                },
+                Piece::Stream(filename, reader) => {
+                    chunks.push(MultipartChunk::Reader(reader.take(filename)?, reader.len()));
+                }
+            }
+        }
+        Ok(chunks)
+    }
+
+    /// Lay out the body as bytes, with streamed parts left as readers.
+    fn layout(&self) -> Vec<Piece<'_>> {
+        let mut layout = Vec::new();
+        for field in &self.fields {
+            let mut head = format!("--{}\r\n", self.boundary);
+            let name = escape_disposition(&field.name);
+            match &field.value {
+                MultipartValue::Text(text) => {
+                    head.push_str(&format!(
+                        "Content-Disposition: form-data; name=\"{}\"\r\n\r\n",
+                        name
+                    ));
+                    head.push_str(text);
+                    layout.push(Piece::Bytes(head.into_bytes()));
+                }
+                MultipartValue::File {
+                    filename,
+                    content_type,
+                    data,
+                } => {
+                    head.push_str(&file_headers(&name, filename, content_type));
+                    let mut bytes = head.into_bytes();
+                    bytes.extend_from_slice(data);
+                    layout.push(Piece::Bytes(bytes));
+                }
+                MultipartValue::Stream {
+                    filename,
+                    content_type,
+                    reader,
+                } => {
+                    head.push_str(&file_headers(&name, filename, content_type));
+                    layout.push(Piece::Bytes(head.into_bytes()));
+                    layout.push(Piece::Stream(filename, reader));
+                }
+            }
+            layout.push(Piece::Bytes(b"\r\n".to_vec()));
+        }
+        layout.push(Piece::Bytes(format!("--{}--\r\n", self.boundary).into_bytes()));
+        layout
+    }
+}
+
+/// A piece of the body layout, before streamed parts are opened.
+enum Piece<'a> {
+    /// Bytes in memory.
+    Bytes(Vec<u8>),
+    /// A streamed part and its filename.
+    Stream(&'a str, &'a MultipartReader),
+}
+
+/// Error for a streamed part that ended before its declared length.
+pub(crate) fn short_stream() -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::UnexpectedEof,
+        "multipart stream ended before its declared length",
+    )
+}
+
+/// Format the part headers for a file.
+fn file_headers(name: &str, filename: &str, content_type: &str) -> String {
+    format!(
+        "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n\
+         Content-Type: {}\r\n\r\n",
+        name,
+        escape_disposition(filename),
+        content_type
+    )
+}
+
+/// Check that a content type is `type/subtype` with optional parameters.
+///
+/// Control characters are rejected so the value cannot inject part headers.
+fn is_valid_content_type(value: &str) -> bool {
+    let is_token = |s: &str| {
+        !s.is_empty()
+            && s.bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+    };
+    let essence = value.split(';').next().unwrap_or_default().trim();
+    let valid_essence = essence
+        .split_once('/')
+        .is_some_and(|(kind, subtype)| is_token(kind) && is_token(subtype));
+    valid_essence && !value.chars().any(|c| c.is_control() && c != '\t')
+}
+
+/// Escape a name or filename for a `Content-Disposition` header.
+fn escape_disposition(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c == '"' || c.is_control() {
+            for byte in c.to_string().bytes() {
+                escaped.push_str(&format!("%{:02X}", byte));
+            }
+        } else {
+            escaped.push(c);
+        }
+    }
+    escaped
 }
 
 /// HTTP request representation.
@@ -115,8 +468,10 @@ pub struct HttpRequest {
     pub headers: HashMap<String, String>,
     /// Request body.
     pub body: RequestBody,
-    /// Request timeout.
+    /// Request timeout, overriding the client default for each attempt.
     pub timeout: Option<Duration>,
+    /// Connect timeout, overriding the client default.
+    pub connect_timeout: Option<Duration>,
     /// Retry configuration.
     pub retry_config: RetryConfig,
     /// Whether to follow redirects.
@@ -168,6 +523,7 @@ impl HttpRequest {
             headers: HashMap::new(),
             body: RequestBody::None,
             timeout: None,
+            connect_timeout: None,
             retry_config: RetryConfig::default(),
             follow_redirects: true,
             max_redirects: 10,
@@ -224,6 +580,14 @@ impl HttpRequest {
         self
     }
 
+    /// Set multipart/form-data body.
+    pub fn multipart(mut self, form: MultipartForm) -> Self {
+        self.headers
+            .insert("Content-Type".to_string(), form.content_type());
+        self.body = RequestBody::multipart(form);
+        self
+    }
+
     /// Set raw bytes body.
     pub fn bytes(mut self, data: Vec<u8>, content_type: impl Into<String>) -> Self {
         self.body = RequestBody::Bytes(data);
@@ -233,11 +597,25 @@ impl HttpRequest {
     }
 
     /// Set request timeout.
-    pub fn timeout(mut self, timeout: Duration) -> Self {
+    pub fn timeout(self, timeout: Duration) -> Self {
+        self.with_timeout(timeout)
+    }
+
+    /// Override the client's default timeout for this request.
+    ///
+    /// The timeout applies to each attempt separately, so a retried request
+    /// may take up to `timeout` per attempt plus the backoff delays.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
         self.timeout = Some(timeout);
         self
     }
 
+    /// Override the client's connect timeout for this request.
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
     /// Set retry configuration.
     pub fn retry(mut self, config: RetryConfig) -> Self {
         self.retry_config = config;
@@ -321,6 +699,21 @@ impl HttpRequest {
     }
 }
 
+/// Randomization applied to retry delays.
+///
+/// Jitter spreads out retries from many clients so they don't hit a
+/// recovering backend in lockstep.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Jitter {
+    /// Use the backoff interval as-is.
+    #[default]
+    None,
+    /// Pick uniformly from `[0, interval]`.
+    Full,
+    /// Keep half the interval and randomize the other half.
+    Equal,
+}
+
 /// Configuration for automatic request retries.
 #[derive(Debug, Clone)]
 pub struct RetryConfig {
@@ -328,10 +721,14 @@ pub struct RetryConfig {
     pub max_retries: u32,
     /// Initial delay between retries.
     pub initial_delay: Duration,
-    /// Maximum delay between retries.
+    /// Maximum delay between retries (applied before jitter).
     pub max_delay: Duration,
     /// Multiplier for exponential backoff.
     pub backoff_multiplier: f64,
+    /// Randomization applied to each delay.
+    pub jitter: Jitter,
+    /// Give up once this much time has passed, even if attempts remain.
+    pub max_elapsed: Option<Duration>,
     /// Whether to retry on timeout errors.
     pub retry_on_timeout: bool,
     /// Whether to retry on network errors.
@@ -347,6 +744,8 @@ impl Default for RetryConfig {
             initial_delay: Duration::from_millis(100),
             max_delay: Duration::from_secs(10),
             backoff_multiplier: 2.0,
+            jitter: Jitter::None,
+            max_elapsed: None,
             retry_on_timeout: true,
             retry_on_network_error: true,
             retry_status_codes: vec![408, 429, 500, 502, 503, 504],
@@ -371,14 +770,84 @@ impl RetryConfig {
         }
     }
 
-    /// Calculate the delay for a given retry attempt (0-indexed).
-    pub fn delay_for_attempt(&self, attempt: u32) -> Duration {
+    /// Set the delay before the first retry (`initial_delay`).
+    pub fn base(mut self, base: Duration) -> Self {
+        self.initial_delay = base;
+        self
+    }
+
+    /// Set the exponential backoff multiplier (`backoff_multiplier`).
+    pub fn multiplier(mut self, multiplier: f64) -> Self {
+        self.backoff_multiplier = multiplier;
+        self
+    }
+
+    /// Cap the backoff interval before jitter (`max_delay`).
+    pub fn max_interval(mut self, max_interval: Duration) -> Self {
+        self.max_delay = max_interval;
+        self
+    }
+
+    /// Set the randomization applied to each delay.
+    pub fn jitter(mut self, jitter: Jitter) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Give up once `max_elapsed` has passed, even if attempts remain.
+    pub fn max_elapsed(mut self, max_elapsed: Duration) -> Self {
+        self.max_elapsed = Some(max_elapsed);
+        self
+    }
+
+    /// Calculate the backoff interval for a retry attempt (0-indexed), before jitter.
+    pub fn interval_for_attempt(&self, attempt: u32) -> Duration {
         let delay_ms = self.initial_delay.as_millis() as f64
             * self.backoff_multiplier.powi(attempt as i32);
         let delay = Duration::from_millis(delay_ms as u64);
         std::cmp::min(delay, self.max_delay)
     }
 
+    /// Calculate the delay for a given retry attempt (0-indexed), with jitter.
+    pub fn delay_for_attempt(&self, attempt: u32) -> Duration {
+        let interval = self.interval_for_attempt(attempt);
+        match self.jitter {
+            Jitter::None => interval,
+            Jitter::Full => interval.mul_f64(crate::offline::rand_factor()),
+            Jitter::Equal => {
+                let half = interval / 2;
+                half + half.mul_f64(crate::offline::rand_factor())
+            }
+        }
+    }
+
+    /// Check if waiting `delay` more keeps the retries within `max_elapsed`.
+    pub fn within_budget(&self, elapsed: Duration, delay: Duration) -> bool {
+        match self.max_elapsed {
+            Some(max_elapsed) => elapsed + delay <= max_elapsed,
+            None => true,
+        }
+    }
+
+    /// Compute the delays the client would wait between attempts.
+    ///
+    /// Jitter is sampled, so repeated calls differ unless `jitter` is
+    /// [`Jitter::None`]. The schedule stops early where `max_elapsed` would be
+    /// exceeded.
+    pub fn schedule(&self) -> Vec<Duration> {
+        let mut elapsed = Duration::ZERO;
+        let mut delays = Vec::new();
+        for attempt in 0..self.max_retries {
+            let delay = self.delay_for_attempt(attempt);
+            if !self.within_budget(elapsed, delay) {
+                break;
+            }
+            elapsed += delay;
+            delays.push(delay);
+        }
+        delays
+    }
+
     /// Check if a status code should trigger a retry.
     pub fn should_retry_status(&self, status: u16) -> bool {
         self.retry_status_codes.contains(&status)
@@ -417,6 +886,131 @@ mod tests {
         assert_eq!(delay2.as_millis(), 400);
     }
 
+    #[test]
+    fn test_retry_schedule_with_jitter_and_budget() {
+        let config = RetryConfig::with_max_retries(8)
+            .max_interval(Duration::from_millis(500))
+            .jitter(Jitter::Full);
+        assert_eq!(config.max_delay, Duration::from_millis(500));
+        let schedule = config.schedule();
+        assert_eq!(schedule.len(), 8);
+        for (attempt, delay) in schedule.iter().enumerate() {
+            let interval = config.interval_for_attempt(attempt as u32);
+            assert!(*delay <= interval, "{:?} exceeds {:?}", delay, interval);
+            assert!(interval <= Duration::from_millis(500));
+        }
+
+        let config = RetryConfig {
+            jitter: Jitter::Equal,
+            ..config
+        };
+        for (attempt, delay) in config.schedule().iter().enumerate() {
+            let interval = config.interval_for_attempt(attempt as u32);
+            assert!(*delay >= interval / 2 && *delay <= interval);
+        }
+
+        // 100 + 200 + 400 fits in a second; the 800ms retry would not
+        let config = RetryConfig::with_max_retries(10).max_elapsed(Duration::from_secs(1));
+        let millis: Vec<u128> = config.schedule().iter().map(|d| d.as_millis()).collect();
+        assert_eq!(millis, vec![100, 200, 400]);
+
+        // The builder names map onto the existing fields
+        let config = RetryConfig::with_max_retries(3)
+            .base(Duration::from_millis(50))
+            .multiplier(3.0);
+        assert_eq!(config.initial_delay, Duration::from_millis(50));
+        assert_eq!(config.backoff_multiplier, 3.0);
+        let millis: Vec<u128> = config.schedule().iter().map(|d| d.as_millis()).collect();
+        assert_eq!(millis, vec![50, 150, 450]);
+    }
+
+    #[test]
+    fn test_multipart_body() {
+        let form = MultipartForm::new()
+            .text("title", "Quarterly report")
+            .file_from_reader(
+                "attachment",
+                "report.csv",
+                "text/csv",
+                std::io::Cursor::new(b"a,b\n1,2".to_vec()),
+            )
+            .unwrap();
+        let boundary = form.boundary().to_string();
+
+        let request = HttpRequest::post("https://api.example.com/upload")
+            .unwrap()
+            .multipart(form);
+        assert_eq!(
+            request.headers.get("Content-Type"),
+            Some(&format!("multipart/form-data; boundary={}", boundary))
+        );
+
+        let RequestBody::Multipart(form) = &request.body else {
+            panic!("expected a multipart body");
+        };
+        let body = String::from_utf8(form.to_bytes().unwrap()).unwrap();
+        assert!(body.starts_with(&format!("--{}\r\n", boundary)));
+        assert!(body.contains(
+            "Content-Disposition: form-data; name=\"title\"\r\n\r\nQuarterly report\r\n"
+        ));
+        assert!(body.contains(
+            "Content-Disposition: form-data; name=\"attachment\"; filename=\"report.csv\"\r\n\
+             Content-Type: text/csv\r\n\r\na,b\n1,2\r\n"
+        ));
+        assert!(body.ends_with(&format!("--{}--\r\n", boundary)));
+    }
+
+    #[test]
+    fn test_multipart_rejects_header_injection() {
+        let form = MultipartForm::new().file("file", "a.txt", "text/plain\r\nX-Evil: 1", vec![1]);
+        assert!(matches!(form.to_bytes(), Err(NetworkError::ConfigError { .. })));
+        let form = MultipartForm::new().file("file", "a.txt", "not a mime", vec![1]);
+        assert!(form.to_bytes().is_err());
+
+        let form = MultipartForm::new().file(
+            "file",
+            "evil\"\r\nX-Evil: 1.txt",
+            "text/plain; charset=utf-8",
+            b"x".to_vec(),
+        );
+        let body = String::from_utf8(form.to_bytes().unwrap()).unwrap();
+        assert!(body.contains("filename=\"evil%22%0D%0AX-Evil: 1.txt\"\r\n"));
+        assert!(body.contains("Content-Type: text/plain; charset=utf-8\r\n\r\nx\r\n"));
+    }
+
+    #[test]
+    fn test_multipart_stream_part() {
+        let data = b"col\n1\n2\n".to_vec();
+        let streamed = MultipartForm::new().text("kind", "csv").file_stream(
+            "upload",
+            "data.csv",
+            "text/csv",
+            std::io::Cursor::new(data.clone()),
+            data.len() as u64,
+        );
+        let buffered = MultipartForm {
+            boundary: streamed.boundary().to_string(),
+            ..MultipartForm::new()
+        }
+        .text("kind", "csv")
+        .file("upload", "data.csv", "text/csv", data);
+
+        assert!(streamed.has_streams());
+        assert_eq!(streamed.content_length(), buffered.to_bytes().unwrap().len() as u64);
+        assert_eq!(streamed.to_bytes().unwrap(), buffered.to_bytes().unwrap());
+        // The reader is single-use
+        assert!(streamed.to_bytes().is_err());
+
+        let short = MultipartForm::new().file_stream(
+            "upload",
+            "data.csv",
+            "text/csv",
+            std::io::Cursor::new(b"abc".to_vec()),
+            10,
+        );
+        assert!(matches!(short.to_bytes(), Err(NetworkError::IoError(_))));
+    }
+
     #[test]
     fn test_json_body() {
         let data = serde_json::json!({
@@ -1,12 +1,30 @@
 //! HTTP response types.
 
+use bytes::Bytes;
+use futures::stream::{self, BoxStream, Stream, StreamExt};
 use serde::de::DeserializeOwned;
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use uuid::Uuid;
 
 use crate::error::{NetworkError, NetworkResult};
 
+/// Body chunks of a response that is still being received.
+pub type BodyStream = BoxStream<'static, NetworkResult<Bytes>>;
+
+/// Unread body of a streaming response.
+///
+/// Clones share the stream; whichever calls `bytes_stream` first consumes it.
+#[derive(Clone)]
+struct StreamingBody(Arc<Mutex<Option<BodyStream>>>);
+
+impl std::fmt::Debug for StreamingBody {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("StreamingBody")
+    }
+}
+
 /// HTTP response representation.
 #[derive(Debug, Clone)]
 pub struct HttpResponse {
@@ -26,6 +44,8 @@ pub struct HttpResponse {
     pub redirects: u32,
     /// Custom metadata from interceptors.
     pub metadata: HashMap<String, serde_json::Value>,
+    /// Body still being received (streaming responses only).
+    stream: Option<StreamingBody>,
 }
 
 impl HttpResponse {
@@ -47,6 +67,33 @@ impl HttpResponse {
             duration,
             redirects: 0,
             metadata: HashMap::new(),
+            stream: None,
+        }
+    }
+
+    /// Attach an unread body stream, making this a streaming response.
+    pub(crate) fn with_stream(mut self, stream: BodyStream) -> Self {
+        self.stream = Some(StreamingBody(Arc::new(Mutex::new(Some(stream)))));
+        self
+    }
+
+    /// Check if the body is streamed rather than buffered in `body`.
+    pub fn is_streaming(&self) -> bool {
+        self.stream.is_some()
+    }
+
+    /// Consume the response as a stream of body chunks.
+    ///
+    /// Streaming responses yield chunks as they arrive from the network;
+    /// buffered responses yield their whole body as a single chunk.
+    pub fn bytes_stream(self) -> impl Stream<Item = NetworkResult<Bytes>> + Send + 'static {
+        match self.stream {
+            Some(StreamingBody(shared)) => {
+                let taken = shared.lock().unwrap_or_else(|e| e.into_inner()).take();
+                taken.unwrap_or_else(|| stream::empty().boxed())
+            }
+            None if self.body.is_empty() => stream::empty().boxed(),
+            None => stream::once(async move { Ok(Bytes::from(self.body)) }).boxed(),
         }
     }
 
@@ -8,16 +8,27 @@
 //! - Error handling
 
 use async_trait::async_trait;
-use std::sync::Arc;
-use tracing::{debug, trace};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{debug, trace, warn};
 
-use crate::error::NetworkResult;
-use crate::http::{HttpRequest, HttpResponse};
+use crate::error::{NetworkError, NetworkResult};
+use crate::http::{HttpMethod, HttpRequest, HttpResponse};
+
+/// Decision returned by a request interceptor.
+#[derive(Debug)]
+pub enum Flow {
+    /// Pass the request on to the next interceptor and, eventually, the network.
+    Continue,
+    /// Stop the chain and answer with this response instead of calling the network.
+    ShortCircuit(HttpResponse),
+}
 
 /// Trait for request/response interceptors.
 ///
-/// Interceptors are called in order for requests (first registered = first called)
-/// and in reverse order for responses (last registered = first called).
+/// Interceptors are called in order for requests (highest priority first, then
+/// first registered = first called) and in reverse order for responses.
 #[async_trait]
 pub trait Interceptor: Send + Sync + std::fmt::Debug {
     /// Get the interceptor's name (for logging/debugging).
@@ -25,10 +36,12 @@ pub trait Interceptor: Send + Sync + std::fmt::Debug {
 
     /// Intercept and potentially modify a request before it's sent.
     ///
-    /// Return `Ok(request)` to continue with the (potentially modified) request,
+    /// Return `Ok(Flow::Continue)` to pass the (potentially modified) request on,
+    /// `Ok(Flow::ShortCircuit(response))` to answer it without calling the network,
     /// or `Err(error)` to abort the request.
-    async fn intercept_request(&self, request: HttpRequest) -> NetworkResult<HttpRequest> {
-        Ok(request)
+    async fn intercept_request(&self, request: &mut HttpRequest) -> NetworkResult<Flow> {
+        let _ = request;
+        Ok(Flow::Continue)
     }
 
     /// Intercept and potentially modify a response after it's received.
@@ -48,16 +61,30 @@ pub trait Interceptor: Send + Sync + std::fmt::Debug {
     }
 }
 
-/// Chain of interceptors.
+/// A response produced by a request interceptor instead of the network.
 #[derive(Debug)]
+pub struct ShortCircuited {
+    /// Position in the chain of the interceptor that answered.
+    pub position: usize,
+    /// The synthesized response.
+    pub response: HttpResponse,
+}
+
+/// Chain of interceptors.
+///
+/// Interceptors are kept sorted by priority (highest first); interceptors with
+/// equal priority keep their registration order.
+#[derive(Debug, Default)]
 pub struct InterceptorChain {
-    interceptors: Vec<Arc<dyn Interceptor>>,
+    interceptors: Vec<(i32, Arc<dyn Interceptor>)>,
 }
 
 impl InterceptorChain {
-    /// Create a new interceptor chain.
+    /// Create a new interceptor chain with all interceptors at priority 0.
     pub fn new(interceptors: Vec<Arc<dyn Interceptor>>) -> Self {
-        Self { interceptors }
+        Self {
+            interceptors: interceptors.into_iter().map(|i| (0, i)).collect(),
+        }
     }
 
     /// Create an empty interceptor chain.
@@ -67,30 +94,88 @@ impl InterceptorChain {
         }
     }
 
-    /// Add an interceptor to the chain.
+    /// Add an interceptor to the chain at priority 0.
     pub fn add(&mut self, interceptor: impl Interceptor + 'static) {
-        self.interceptors.push(Arc::new(interceptor));
+        self.add_with_priority(interceptor, 0);
+    }
+
+    /// Add an interceptor with an explicit priority.
+    ///
+    /// Higher priorities see requests first and responses last.
+    pub fn add_with_priority(&mut self, interceptor: impl Interceptor + 'static, priority: i32) {
+        let index = self
+            .interceptors
+            .iter()
+            .position(|(p, _)| *p < priority)
+            .unwrap_or(self.interceptors.len());
+        self.interceptors.insert(index, (priority, Arc::new(interceptor)));
     }
 
-    /// Run all request interceptors in order.
-    pub async fn intercept_request(&self, mut request: HttpRequest) -> NetworkResult<HttpRequest> {
-        for interceptor in &self.interceptors {
+    /// Builder-style variant of [`add_with_priority`](Self::add_with_priority).
+    pub fn with_priority(mut self, interceptor: impl Interceptor + 'static, priority: i32) -> Self {
+        self.add_with_priority(interceptor, priority);
+        self
+    }
+
+    /// Number of interceptors in the chain.
+    pub fn len(&self) -> usize {
+        self.interceptors.len()
+    }
+
+    /// Check whether the chain is empty.
+    pub fn is_empty(&self) -> bool {
+        self.interceptors.is_empty()
+    }
+
+    /// Run request interceptors in order until one short-circuits.
+    ///
+    /// Returns `Ok(Some(_))` when an interceptor answered the request itself; the
+    /// remaining interceptors are skipped and the network must not be called.
+    pub async fn intercept_request(
+        &self,
+        request: &mut HttpRequest,
+    ) -> NetworkResult<Option<ShortCircuited>> {
+        for (position, (_, interceptor)) in self.interceptors.iter().enumerate() {
             trace!(
                 interceptor = %interceptor.name(),
                 request_id = %request.id,
                 "Running request interceptor"
             );
-            request = interceptor.intercept_request(request).await?;
+            if let Flow::ShortCircuit(response) = interceptor.intercept_request(request).await? {
+                trace!(
+                    interceptor = %interceptor.name(),
+                    request_id = %request.id,
+                    "Request short-circuited"
+                );
+                return Ok(Some(ShortCircuited { position, response }));
+            }
         }
-        Ok(request)
+        Ok(None)
     }
 
     /// Run all response interceptors in reverse order.
-    pub async fn intercept_response(
+    pub async fn intercept_response(&self, response: HttpResponse) -> NetworkResult<HttpResponse> {
+        self.run_response_hooks(response, self.interceptors.len()).await
+    }
+
+    /// Run response interceptors for a short-circuited response.
+    ///
+    /// Only the interceptor that answered and those ahead of it saw the request,
+    /// so only they see the response, again in reverse order.
+    pub async fn intercept_short_circuited(
+        &self,
+        short_circuited: ShortCircuited,
+    ) -> NetworkResult<HttpResponse> {
+        self.run_response_hooks(short_circuited.response, short_circuited.position + 1)
+            .await
+    }
+
+    async fn run_response_hooks(
         &self,
         mut response: HttpResponse,
+        depth: usize,
     ) -> NetworkResult<HttpResponse> {
-        for interceptor in self.interceptors.iter().rev() {
+        for (_, interceptor) in self.interceptors[..depth].iter().rev() {
             trace!(
                 interceptor = %interceptor.name(),
                 request_id = %response.request_id,
@@ -103,7 +188,7 @@ impl InterceptorChain {
 
     /// Notify interceptors of an error.
     pub async fn on_error(&self, request: &HttpRequest, error: &crate::error::NetworkError) {
-        for interceptor in &self.interceptors {
+        for (_, interceptor) in &self.interceptors {
             interceptor.on_error(request, error).await;
         }
     }
@@ -163,7 +248,7 @@ impl Interceptor for LoggingInterceptor {
         &self.name
     }
 
-    async fn intercept_request(&self, request: HttpRequest) -> NetworkResult<HttpRequest> {
+    async fn intercept_request(&self, request: &mut HttpRequest) -> NetworkResult<Flow> {
         debug!(
             request_id = %request.id,
             method = %request.method,
@@ -184,7 +269,7 @@ impl Interceptor for LoggingInterceptor {
             }
         }
 
-        Ok(request)
+        Ok(Flow::Continue)
     }
 
     async fn intercept_response(&self, response: HttpResponse) -> NetworkResult<HttpResponse> {
@@ -265,11 +350,11 @@ impl Interceptor for HeaderInterceptor {
         &self.name
     }
 
-    async fn intercept_request(&self, mut request: HttpRequest) -> NetworkResult<HttpRequest> {
+    async fn intercept_request(&self, request: &mut HttpRequest) -> NetworkResult<Flow> {
         for (key, value) in &self.headers {
             request.headers.entry(key.clone()).or_insert_with(|| value.clone());
         }
-        Ok(request)
+        Ok(Flow::Continue)
     }
 }
 
@@ -425,20 +510,143 @@ impl Interceptor for ErrorTransformInterceptor {
     }
 }
 
-/// Caching interceptor for conditional requests.
+/// A cached response and its validators.
+#[derive(Debug, Clone)]
+struct CacheEntry {
+    status: u16,
+    headers: HashMap<String, String>,
+    body: Vec<u8>,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    /// When the entry stops being fresh; `None` means always revalidate.
+    fresh_until: Option<Instant>,
+}
+
+impl CacheEntry {
+    fn is_fresh(&self, now: Instant) -> bool {
+        self.fresh_until.map(|until| now < until).unwrap_or(false)
+    }
+}
+
+/// A GET request awaiting its response.
+#[derive(Debug)]
+struct PendingLookup {
+    key: String,
+    /// Whether this cache added the validators, so a 304 answers for its entry.
+    revalidating: bool,
+}
+
+/// Parsed `Cache-Control` response directives.
+#[derive(Debug, Default)]
+struct CacheDirectives {
+    no_store: bool,
+    no_cache: bool,
+    max_age: Option<u64>,
+}
+
+impl CacheDirectives {
+    fn parse(response: &HttpResponse) -> Self {
+        let mut directives = Self::default();
+        let Some(value) = response.header("cache-control") else {
+            return directives;
+        };
+        for directive in value.split(',') {
+            let directive = directive.trim().to_ascii_lowercase();
+            match directive.split_once('=') {
+                Some(("max-age", secs)) => {
+                    directives.max_age = secs.trim_matches('"').parse().ok();
+                }
+                _ if directive == "no-store" => directives.no_store = true,
+                _ if directive == "no-cache" => directives.no_cache = true,
+                _ => {}
+            }
+        }
+        directives
+    }
+
+    /// Freshness deadline for a response received at `now`.
+    fn fresh_until(&self, now: Instant) -> Option<Instant> {
+        if self.no_cache {
+            return None;
+        }
+        self.max_age
+            .filter(|&secs| secs > 0)
+            .map(|secs| now + Duration::from_secs(secs))
+    }
+}
+
+/// Caching interceptor for GET requests with HTTP conditional revalidation.
+///
+/// Fresh entries (within `Cache-Control: max-age`) are served without a
+/// network call. Stale entries are revalidated with `If-None-Match` and
+/// `If-Modified-Since`; a `304 Not Modified` is answered with the cached body
+/// and renews freshness. Responses marked `no-store` are never cached.
+///
+/// Entries are keyed by URL and `Authorization` header. Requests that carry
+/// their own conditional headers get the server's 304 unchanged.
 #[derive(Debug)]
 pub struct CacheInterceptor {
     name: String,
-    // Would include cache storage in real implementation
+    entries: Mutex<HashMap<String, CacheEntry>>,
+    /// GET requests awaiting their response.
+    pending: Mutex<HashMap<uuid::Uuid, PendingLookup>>,
 }
 
 impl CacheInterceptor {
+    /// Metadata key set to `true` on responses served from the cache.
+    pub const CACHE_HIT: &'static str = "cache_hit";
+
     /// Create a new cache interceptor.
     pub fn new() -> Self {
         Self {
             name: "cache".to_string(),
+            entries: Mutex::new(HashMap::new()),
+            pending: Mutex::new(HashMap::new()),
         }
     }
+
+    /// Number of cached responses.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// Check whether the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop all cached responses.
+    pub fn clear(&self) {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+
+    fn cache_key(request: &HttpRequest) -> Option<String> {
+        if request.method != HttpMethod::Get {
+            return None;
+        }
+        let authorization = request
+            .headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("authorization"))
+            .map(|(_, value)| value.as_str())
+            .unwrap_or_default();
+        Some(format!("{}\n{}", request.url, authorization))
+    }
+
+    fn cached_response(entry: &CacheEntry, template: &HttpResponse) -> HttpResponse {
+        let mut response = HttpResponse::new(
+            template.request_id,
+            entry.status,
+            entry.headers.clone(),
+            entry.body.clone(),
+            template.final_url.clone(),
+            template.duration,
+        );
+        response
+            .metadata
+            .insert(Self::CACHE_HIT.to_string(), serde_json::Value::Bool(true));
+        response
+    }
 }
 
 impl Default for CacheInterceptor {
@@ -453,27 +661,377 @@ impl Interceptor for CacheInterceptor {
         &self.name
     }
 
-    async fn intercept_request(&self, request: HttpRequest) -> NetworkResult<HttpRequest> {
-        // In a real implementation, would look up cached response
-        // and add If-None-Match/If-Modified-Since headers
-        trace!(
-            request_id = %request.id,
-            "Cache interceptor: would check for cached response"
-        );
-        Ok(request)
+    async fn intercept_request(&self, request: &mut HttpRequest) -> NetworkResult<Flow> {
+        let Some(key) = Self::cache_key(request) else {
+            return Ok(Flow::Continue);
+        };
+
+        let mut revalidating = false;
+        if let Some(entry) = self.entries.lock().unwrap_or_else(|e| e.into_inner()).get(&key) {
+            if entry.is_fresh(Instant::now()) {
+                trace!(request_id = %request.id, "Cache interceptor: serving fresh entry");
+                let template = HttpResponse::new(
+                    request.id,
+                    entry.status,
+                    HashMap::new(),
+                    Vec::new(),
+                    request.url.to_string(),
+                    Duration::ZERO,
+                );
+                return Ok(Flow::ShortCircuit(Self::cached_response(entry, &template)));
+            }
+
+            // A caller's own conditional request gets the server's answer as is
+            let conditional = request.headers.keys().any(|name| {
+                name.eq_ignore_ascii_case("if-none-match")
+                    || name.eq_ignore_ascii_case("if-modified-since")
+            });
+            if !conditional {
+                if let Some(etag) = &entry.etag {
+                    request
+                        .headers
+                        .insert("If-None-Match".to_string(), etag.clone());
+                    revalidating = true;
+                }
+                if let Some(last_modified) = &entry.last_modified {
+                    request
+                        .headers
+                        .insert("If-Modified-Since".to_string(), last_modified.clone());
+                    revalidating = true;
+                }
+            }
+        }
+
+        self.pending
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(request.id, PendingLookup { key, revalidating });
+        Ok(Flow::Continue)
     }
 
     async fn intercept_response(&self, response: HttpResponse) -> NetworkResult<HttpResponse> {
-        // In a real implementation, would cache the response
-        // based on Cache-Control headers
-        if response.status == 304 {
+        let pending = self
+            .pending
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&response.request_id);
+        let Some(PendingLookup { key, revalidating }) = pending else {
+            return Ok(response);
+        };
+
+        // Served by this cache; freshness was not renewed by the server
+        if response.metadata.contains_key(Self::CACHE_HIT) {
+            return Ok(response);
+        }
+
+        let now = Instant::now();
+        let directives = CacheDirectives::parse(&response);
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+
+        // A 304 has no body, so streaming requests are answered from the cache too
+        if response.status == 304 && revalidating {
+            if let Some(entry) = entries.get_mut(&key) {
+                trace!(
+                    request_id = %response.request_id,
+                    "Cache interceptor: revalidated, returning cached response"
+                );
+                entry.fresh_until = directives.fresh_until(now);
+                if let Some(etag) = response.etag() {
+                    entry.etag = Some(etag.to_string());
+                }
+                if let Some(last_modified) = response.last_modified() {
+                    entry.last_modified = Some(last_modified.to_string());
+                }
+                return Ok(Self::cached_response(entry, &response));
+            }
+            return Ok(response);
+        }
+
+        // Caching needs the full body; never buffer a streaming response
+        if response.is_streaming() {
             trace!(
                 request_id = %response.request_id,
-                "Cache interceptor: would return cached response for 304"
+                "Cache interceptor: skipping streaming response"
+            );
+            return Ok(response);
+        }
+
+        if directives.no_store {
+            entries.remove(&key);
+            return Ok(response);
+        }
+
+        if response.status == 200 {
+            let entry = CacheEntry {
+                status: response.status,
+                headers: response.headers.clone(),
+                body: response.body.clone(),
+                etag: response.etag().map(String::from),
+                last_modified: response.last_modified().map(String::from),
+                fresh_until: directives.fresh_until(now),
+            };
+            // Without validators or freshness there is nothing to reuse
+            if entry.etag.is_some() || entry.last_modified.is_some() || entry.fresh_until.is_some()
+            {
+                entries.insert(key, entry);
+            } else {
+                entries.remove(&key);
+            }
+        }
+        Ok(response)
+    }
+
+    async fn on_error(&self, request: &HttpRequest, _error: &NetworkError) {
+        self.pending
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&request.id);
+    }
+}
+
+/// State of a circuit breaker for one host.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests flow normally.
+    Closed,
+    /// Requests are rejected until the cooldown elapses.
+    Open,
+    /// One probe request is let through to test recovery.
+    HalfOpen,
+}
+
+/// How long an admitted request is remembered while awaiting its outcome.
+const PENDING_HORIZON: Duration = Duration::from_secs(600);
+
+/// Breaker bookkeeping for a single host.
+#[derive(Debug, Clone)]
+struct HostCircuit {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Instant,
+    /// When the current half-open probe was admitted.
+    probe_started: Option<Instant>,
+}
+
+impl HostCircuit {
+    fn new(now: Instant) -> Self {
+        Self {
+            state: CircuitState::Closed,
+            consecutive_failures: 0,
+            opened_at: now,
+            probe_started: None,
+        }
+    }
+}
+
+/// Circuit breaker that stops sending requests to a failing host.
+///
+/// After `failure_threshold` consecutive failures (server errors or transport
+/// errors) to a host, its circuit opens and requests fail fast with
+/// [`NetworkError::CircuitOpen`]. Once the cooldown elapses the circuit
+/// half-opens and lets a single probe through: success closes it, failure
+/// opens it again. A probe that never reports back (dropped request, later
+/// interceptor error) is considered lost after another cooldown.
+///
+/// Outcomes are recorded against the host the request was sent to, even if
+/// the response was redirected elsewhere.
+///
+/// Clones share state, so one breaker can guard several clients.
+#[derive(Clone)]
+pub struct CircuitBreakerInterceptor {
+    name: String,
+    failure_threshold: u32,
+    cooldown: Duration,
+    hosts: Arc<Mutex<HashMap<String, HostCircuit>>>,
+    /// Host and admission time of requests awaiting their outcome.
+    pending: Arc<Mutex<HashMap<uuid::Uuid, (String, Instant)>>>,
+    clock: Arc<dyn Fn() -> Instant + Send + Sync>,
+}
+
+impl std::fmt::Debug for CircuitBreakerInterceptor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CircuitBreakerInterceptor")
+            .field("name", &self.name)
+            .field("failure_threshold", &self.failure_threshold)
+            .field("cooldown", &self.cooldown)
+            .finish()
+    }
+}
+
+impl CircuitBreakerInterceptor {
+    /// Create a breaker that opens after `failure_threshold` consecutive failures.
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            name: "circuit-breaker".to_string(),
+            failure_threshold: failure_threshold.max(1),
+            cooldown,
+            hosts: Arc::new(Mutex::new(HashMap::new())),
+            pending: Arc::new(Mutex::new(HashMap::new())),
+            clock: Arc::new(Instant::now),
+        }
+    }
+
+    /// Use a custom time source (for tests).
+    pub fn with_clock(mut self, clock: impl Fn() -> Instant + Send + Sync + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Get the circuit state for a host.
+    pub fn state(&self, host: &str) -> CircuitState {
+        self.lock_hosts()
+            .get(host)
+            .map_or(CircuitState::Closed, |circuit| circuit.state)
+    }
+
+    /// Close all circuits and forget failure history.
+    pub fn reset(&self) {
+        self.lock_hosts().clear();
+    }
+
+    fn lock_hosts(&self) -> std::sync::MutexGuard<'_, HashMap<String, HostCircuit>> {
+        self.hosts.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn lock_pending(
+        &self,
+    ) -> std::sync::MutexGuard<'_, HashMap<uuid::Uuid, (String, Instant)>> {
+        self.pending.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Remember which host a request was admitted for.
+    fn track(&self, request_id: uuid::Uuid, host: &str) {
+        let now = (self.clock)();
+        // Requests that never report back are forgotten eventually
+        let horizon = self.cooldown.max(PENDING_HORIZON);
+        let mut pending = self.lock_pending();
+        pending.retain(|_, (_, admitted)| now.saturating_duration_since(*admitted) < horizon);
+        pending.insert(request_id, (host.to_string(), now));
+    }
+
+    /// Host a request was admitted for, if it is still tracked.
+    fn untrack(&self, request_id: &uuid::Uuid) -> Option<String> {
+        self.lock_pending().remove(request_id).map(|(host, _)| host)
+    }
+
+    /// Decide whether a request to `host` may proceed.
+    fn admit(&self, host: &str) -> NetworkResult<()> {
+        let now = (self.clock)();
+        let mut hosts = self.lock_hosts();
+        let Some(circuit) = hosts.get_mut(host) else {
+            return Ok(());
+        };
+
+        if circuit.state == CircuitState::Open {
+            let elapsed = now.saturating_duration_since(circuit.opened_at);
+            if elapsed < self.cooldown {
+                let remaining = self.cooldown - elapsed;
+                return Err(NetworkError::CircuitOpen {
+                    host: host.to_string(),
+                    retry_after_secs: remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0),
+                });
+            }
+            debug!(host = %host, "Circuit half-open, probing");
+            circuit.state = CircuitState::HalfOpen;
+            circuit.probe_started = None;
+        }
+
+        if circuit.state == CircuitState::HalfOpen {
+            if let Some(started) = circuit.probe_started {
+                let elapsed = now.saturating_duration_since(started);
+                if elapsed < self.cooldown {
+                    let remaining = self.cooldown - elapsed;
+                    return Err(NetworkError::CircuitOpen {
+                        host: host.to_string(),
+                        retry_after_secs: remaining.as_secs()
+                            + u64::from(remaining.subsec_nanos() > 0),
+                    });
+                }
+                debug!(host = %host, "Probe lost, admitting a new one");
+            }
+            circuit.probe_started = Some(now);
+        }
+        Ok(())
+    }
+
+    /// Record the outcome of a request to `host`.
+    fn record(&self, host: &str, success: bool) {
+        let now = (self.clock)();
+        let mut hosts = self.lock_hosts();
+        let circuit = hosts
+            .entry(host.to_string())
+            .or_insert_with(|| HostCircuit::new(now));
+        circuit.probe_started = None;
+
+        if success {
+            if circuit.state != CircuitState::Closed {
+                debug!(host = %host, "Circuit closed");
+            }
+            circuit.state = CircuitState::Closed;
+            circuit.consecutive_failures = 0;
+            return;
+        }
+
+        circuit.consecutive_failures += 1;
+        let trips = circuit.state == CircuitState::HalfOpen
+            || circuit.consecutive_failures >= self.failure_threshold;
+        if trips && circuit.state != CircuitState::Open {
+            warn!(
+                host = %host,
+                failures = circuit.consecutive_failures,
+                "Circuit opened"
             );
+            circuit.state = CircuitState::Open;
+            circuit.opened_at = now;
+        }
+    }
+}
+
+#[async_trait]
+impl Interceptor for CircuitBreakerInterceptor {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn intercept_request(&self, request: &mut HttpRequest) -> NetworkResult<Flow> {
+        if let Some(host) = request.url.host_str() {
+            self.admit(host)?;
+            self.track(request.id, host);
+        }
+        Ok(Flow::Continue)
+    }
+
+    async fn intercept_response(&self, response: HttpResponse) -> NetworkResult<HttpResponse> {
+        // Prefer the host the request went to; `final_url` may be a redirect target
+        let host = self.untrack(&response.request_id).or_else(|| {
+            url::Url::parse(&response.final_url)
+                .ok()
+                .and_then(|url| url.host_str().map(str::to_string))
+        });
+        if let Some(host) = host {
+            self.record(&host, !response.is_server_error());
         }
         Ok(response)
     }
+
+    async fn on_error(&self, request: &HttpRequest, error: &NetworkError) {
+        let is_failure = matches!(
+            error,
+            NetworkError::HttpError(_)
+                | NetworkError::Offline
+                | NetworkError::Timeout { .. }
+                | NetworkError::ServerError { .. }
+        );
+        self.untrack(&request.id);
+        if let Some(host) = request.url.host_str() {
+            if is_failure {
+                self.record(host, false);
+            } else {
+                // Client-side errors still prove the host is answering
+                self.record(host, true);
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -488,8 +1046,8 @@ mod tests {
             Arc::new(HeaderInterceptor::new().add_header("X-Custom", "value")),
         ]);
 
-        let request = HttpRequest::get("https://example.com").unwrap();
-        let request = chain.intercept_request(request).await.unwrap();
+        let mut request = HttpRequest::get("https://example.com").unwrap();
+        assert!(chain.intercept_request(&mut request).await.unwrap().is_none());
 
         assert_eq!(
             request.headers.get("X-Custom"),
@@ -503,8 +1061,8 @@ mod tests {
             .add_header("Authorization", "Bearer token")
             .add_header("X-Request-ID", "123");
 
-        let request = HttpRequest::get("https://example.com").unwrap();
-        let request = interceptor.intercept_request(request).await.unwrap();
+        let mut request = HttpRequest::get("https://example.com").unwrap();
+        interceptor.intercept_request(&mut request).await.unwrap();
 
         assert_eq!(
             request.headers.get("Authorization"),
@@ -536,4 +1094,252 @@ mod tests {
 
         assert_eq!(call_count.load(Ordering::SeqCst), 1);
     }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_lifecycle() {
+        let start = Instant::now();
+        let now = Arc::new(Mutex::new(start));
+        let clock = now.clone();
+        let breaker = CircuitBreakerInterceptor::new(3, Duration::from_secs(30))
+            .with_clock(move || *clock.lock().unwrap());
+        let advance = |secs| *now.lock().unwrap() = start + Duration::from_secs(secs);
+        let request = || HttpRequest::get("https://api.example.com/items").unwrap();
+        let response = |status| {
+            ResponseBuilder::new()
+                .status(status)
+                .url("https://api.example.com/items")
+                .build()
+        };
+
+        // Closed: failures below the threshold let requests through
+        for _ in 0..2 {
+            breaker.intercept_request(&mut request()).await.unwrap();
+            breaker.intercept_response(response(503)).await.unwrap();
+        }
+        assert_eq!(breaker.state("api.example.com"), CircuitState::Closed);
+        breaker.on_error(&request(), &NetworkError::Offline).await;
+
+        // Open: requests to the host fail fast, other hosts are unaffected
+        assert_eq!(breaker.state("api.example.com"), CircuitState::Open);
+        advance(10);
+        assert!(matches!(
+            breaker.intercept_request(&mut request()).await,
+            Err(NetworkError::CircuitOpen { retry_after_secs: 20, .. })
+        ));
+        let mut other = HttpRequest::get("https://cdn.example.com/a").unwrap();
+        assert!(breaker.intercept_request(&mut other).await.is_ok());
+
+        // Half-open: a single probe after the cooldown; its failure reopens
+        advance(30);
+        breaker.intercept_request(&mut request()).await.unwrap();
+        assert_eq!(breaker.state("api.example.com"), CircuitState::HalfOpen);
+        assert!(breaker.intercept_request(&mut request()).await.is_err());
+        breaker.intercept_response(response(500)).await.unwrap();
+        assert_eq!(breaker.state("api.example.com"), CircuitState::Open);
+
+        // A successful probe closes the circuit again
+        advance(60);
+        let shared = breaker.clone();
+        shared.intercept_request(&mut request()).await.unwrap();
+        shared.intercept_response(response(200)).await.unwrap();
+        assert_eq!(breaker.state("api.example.com"), CircuitState::Closed);
+        assert!(breaker.intercept_request(&mut request()).await.is_ok());
+    }
+
+    fn cached_get(url: &str) -> HttpRequest {
+        HttpRequest::get(url).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_cache_revalidation_304() {
+        let cache = CacheInterceptor::new();
+        let url = "https://api.example.com/users";
+
+        let mut request = cached_get(url);
+        cache.intercept_request(&mut request).await.unwrap();
+        assert!(!request.headers.contains_key("If-None-Match"));
+        let mut response = ResponseBuilder::new()
+            .header("ETag", "\"v1\"")
+            .header("Last-Modified", "Wed, 21 Oct 2015 07:28:00 GMT")
+            .body(b"[1,2,3]".to_vec())
+            .build();
+        response.request_id = request.id;
+        cache.intercept_response(response).await.unwrap();
+        assert_eq!(cache.len(), 1);
+
+        // No max-age, so the next request revalidates instead of hitting the cache
+        let mut request = cached_get(url);
+        let flow = cache.intercept_request(&mut request).await.unwrap();
+        assert!(matches!(flow, Flow::Continue));
+        assert_eq!(request.headers.get("If-None-Match").unwrap(), "\"v1\"");
+        assert_eq!(
+            request.headers.get("If-Modified-Since").unwrap(),
+            "Wed, 21 Oct 2015 07:28:00 GMT"
+        );
+
+        let mut not_modified = ResponseBuilder::new()
+            .status(304)
+            .header("Cache-Control", "max-age=60")
+            .build();
+        not_modified.request_id = request.id;
+        let response = cache.intercept_response(not_modified).await.unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, b"[1,2,3]");
+        assert_eq!(response.request_id, request.id);
+
+        // The 304 renewed freshness, so now the cache answers directly
+        let mut request = cached_get(url);
+        let Flow::ShortCircuit(hit) = cache.intercept_request(&mut request).await.unwrap() else {
+            panic!("expected a cache hit");
+        };
+        assert_eq!(hit.body, b"[1,2,3]");
+        assert_eq!(hit.metadata[CacheInterceptor::CACHE_HIT], true);
+    }
+
+    #[tokio::test]
+    async fn test_cache_conditional_and_authorized_requests() {
+        use futures::StreamExt;
+
+        let cache = CacheInterceptor::new();
+        let url = "https://api.example.com/profile";
+        let authorized = |token: &str| cached_get(url).header("Authorization", token);
+
+        let mut request = authorized("Bearer alice");
+        cache.intercept_request(&mut request).await.unwrap();
+        let mut response = ResponseBuilder::new()
+            .header("ETag", "\"alice\"")
+            .body(b"alice".to_vec())
+            .build();
+        response.request_id = request.id;
+        cache.intercept_response(response).await.unwrap();
+
+        // Another user's request neither revalidates nor reuses the entry
+        let mut request = authorized("Bearer bob");
+        cache.intercept_request(&mut request).await.unwrap();
+        assert!(!request.headers.contains_key("If-None-Match"));
+        cache.on_error(&request, &NetworkError::Offline).await;
+
+        // The caller's own validator gets the server's 304 unchanged
+        let mut request = authorized("Bearer alice").header("If-None-Match", "\"old\"");
+        cache.intercept_request(&mut request).await.unwrap();
+        assert_eq!(request.headers.get("If-None-Match").unwrap(), "\"old\"");
+        let mut not_modified = ResponseBuilder::new().status(304).build();
+        not_modified.request_id = request.id;
+        let response = cache.intercept_response(not_modified).await.unwrap();
+        assert_eq!(response.status, 304);
+
+        // A streaming request is answered from the cache on 304
+        let mut request = authorized("Bearer alice");
+        cache.intercept_request(&mut request).await.unwrap();
+        assert_eq!(request.headers.get("If-None-Match").unwrap(), "\"alice\"");
+        let mut not_modified = ResponseBuilder::new()
+            .status(304)
+            .build()
+            .with_stream(futures::stream::empty().boxed());
+        not_modified.request_id = request.id;
+        let response = cache.intercept_response(not_modified).await.unwrap();
+        assert_eq!(response.status, 200);
+        let chunks: Vec<_> = response.bytes_stream().collect().await;
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].as_ref().unwrap().as_ref(), b"alice");
+    }
+
+    #[tokio::test]
+    async fn test_cache_no_store() {
+        let cache = CacheInterceptor::new();
+        let url = "https://api.example.com/balance";
+
+        let mut request = cached_get(url);
+        cache.intercept_request(&mut request).await.unwrap();
+        let mut response = ResponseBuilder::new()
+            .header("ETag", "\"b1\"")
+            .header("Cache-Control", "no-store, max-age=600")
+            .body(b"42".to_vec())
+            .build();
+        response.request_id = request.id;
+        cache.intercept_response(response).await.unwrap();
+        assert!(cache.is_empty());
+
+        let mut request = cached_get(url);
+        let flow = cache.intercept_request(&mut request).await.unwrap();
+        assert!(matches!(flow, Flow::Continue));
+        assert!(!request.headers.contains_key("If-None-Match"));
+    }
+
+    #[tokio::test]
+    async fn test_short_circuit_runs_outer_response_hooks() {
+        let statuses = Arc::new(Mutex::new(Vec::new()));
+        let recorded = statuses.clone();
+        let metrics = MetricsInterceptor::new().with_callback(move |m| {
+            recorded.lock().unwrap().push(m.status);
+        });
+
+        // Registered after the cache, but its priority puts it outermost
+        let chain = InterceptorChain::empty()
+            .with_priority(CacheInterceptor::new(), 0)
+            .with_priority(HeaderInterceptor::new().add_header("X-Inner", "1"), -10)
+            .with_priority(metrics, 10);
+        assert_eq!(chain.len(), 3);
+
+        let url = "https://api.example.com/config";
+        let mut request = cached_get(url);
+        assert!(chain.intercept_request(&mut request).await.unwrap().is_none());
+        assert!(request.headers.contains_key("X-Inner"));
+        let mut response = ResponseBuilder::new()
+            .header("Cache-Control", "max-age=60")
+            .body(b"{}".to_vec())
+            .url(url)
+            .build();
+        response.request_id = request.id;
+        chain.intercept_response(response).await.unwrap();
+
+        // Fresh entry: the cache answers and interceptors behind it never run
+        let mut request = cached_get(url);
+        let short_circuited = chain.intercept_request(&mut request).await.unwrap().unwrap();
+        assert_eq!(short_circuited.position, 1);
+        assert!(!request.headers.contains_key("X-Inner"));
+        let response = chain.intercept_short_circuited(short_circuited).await.unwrap();
+        assert_eq!(response.body, b"{}");
+        assert_eq!(response.metadata[CacheInterceptor::CACHE_HIT], true);
+
+        assert_eq!(*statuses.lock().unwrap(), vec![Some(200), Some(200)]);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_lost_probe_and_redirect() {
+        let start = Instant::now();
+        let now = Arc::new(Mutex::new(start));
+        let clock = now.clone();
+        let breaker = CircuitBreakerInterceptor::new(1, Duration::from_secs(30))
+            .with_clock(move || *clock.lock().unwrap());
+        let advance = |secs| *now.lock().unwrap() = start + Duration::from_secs(secs);
+        let request = || HttpRequest::get("https://api.example.com/items").unwrap();
+
+        breaker.on_error(&request(), &NetworkError::Offline).await;
+        assert_eq!(breaker.state("api.example.com"), CircuitState::Open);
+
+        // The probe is admitted but its future is dropped before it reports back
+        advance(30);
+        breaker.intercept_request(&mut request()).await.unwrap();
+        advance(40);
+        assert!(matches!(
+            breaker.intercept_request(&mut request()).await,
+            Err(NetworkError::CircuitOpen { retry_after_secs: 20, .. })
+        ));
+
+        // After another cooldown the probe counts as lost and a new one goes out
+        advance(60);
+        let mut probe = request();
+        breaker.intercept_request(&mut probe).await.unwrap();
+
+        // Its redirected response still closes the original host's circuit
+        let mut response = ResponseBuilder::new()
+            .status(200)
+            .url("https://cdn.example.com/items")
+            .build();
+        response.request_id = probe.id;
+        breaker.intercept_response(response).await.unwrap();
+        assert_eq!(breaker.state("api.example.com"), CircuitState::Closed);
+        assert_eq!(breaker.state("cdn.example.com"), CircuitState::Closed);
+    }
 }
@@ -5,6 +5,7 @@
 //! - **HTTP Client**: Full-featured async HTTP client with retry logic, interceptors,
 //!   and automatic auth handling
 //! - **WebSocket**: Real-time communication with automatic reconnection and message queuing
+//! - **Server-Sent Events**: Event-stream client with `Last-Event-ID` resumption
 //! - **Authentication**: Pluggable auth providers (OAuth 2.0, JWT, API keys, Basic auth)
 //! - **Credential Storage**: Secure platform-native credential storage (Keychain, etc.)
 //! - **Capabilities**: Fine-grained permission system for network access
@@ -12,6 +13,7 @@
 //! - **Offline Support**: Network status detection, retry policies, and request queuing
 //! - **CORS Elimination**: Target-aware networking that eliminates CORS pain
 //! - **Dev Proxy**: First-class development proxy for web apps
+//! - **Record/Replay**: Cassette-based HTTP recording for offline integration tests
 //! - **Reverse Proxy**: Production reverse proxy config generation (Nginx, Caddy, Traefik)
 //! - **Diagnostics**: CORS doctor and network configuration diagnostics
 //!
@@ -130,7 +132,9 @@ pub mod interceptor;
 pub mod network_mode;
 pub mod offline;
 pub mod proxy;
+pub mod recording;
 pub mod reverse_proxy;
+pub mod sse;
 
 #[cfg(feature = "websocket")]
 pub mod websocket;
@@ -142,20 +146,22 @@ pub mod websocket;
 pub use allowlist::{Allowlist, AllowlistMode, AllowlistPattern, PatternBuilder, PatternType};
 pub use auth::{
     ApiKeyConfig, ApiKeyProvider, AuthCredentials, AuthManager, AuthManagerBuilder, AuthProvider,
-    AuthProviderType, BasicAuthProvider, JwtConfig, OAuth2Config, TokenPair,
+    AuthProviderType, AuthorizeUrl, BasicAuthProvider, JwtConfig, OAuth2Config, OAuth2Provider,
+    PkceVerifier, TokenPair,
 };
 pub use capability::{CapabilityValidator, CapabilityViolation, NetworkCapability};
 pub use cors::{BackendFramework, CorsConfig, CorsPreset, CorsDiagnostic};
 pub use credentials::{Credential, CredentialManager, CredentialStore, CredentialType};
 pub use diagnostics::{DiagnosticIssue, DiagnosticReport, NetworkDoctor, Severity, IssueCategory};
-pub use error::{AuthAction, AuthError, AuthState, NetworkError, NetworkResult};
+pub use error::{AuthAction, AuthError, AuthState, NetworkError, NetworkResult, WsError};
 pub use http::{
-    HttpClient, HttpClientBuilder, HttpClientConfig, HttpMethod, HttpRequest, HttpResponse,
-    RequestBody, ResponseBuilder, RetryConfig,
+    BodyStream, HttpClient, HttpClientBuilder, HttpClientConfig, HttpMethod, HttpRequest,
+    HttpResponse, Jitter, MultipartForm, RequestBody, ResponseBuilder, RetryConfig,
 };
 pub use interceptor::{
-    CacheInterceptor, ErrorTransformInterceptor, HeaderInterceptor, Interceptor, InterceptorChain,
-    LoggingInterceptor, MetricsInterceptor, RequestMetrics,
+    CacheInterceptor, CircuitBreakerInterceptor, CircuitState, ErrorTransformInterceptor, Flow,
+    HeaderInterceptor, Interceptor, InterceptorChain, LoggingInterceptor, MetricsInterceptor,
+    RequestMetrics, ShortCircuited,
 };
 pub use network_mode::{ApiEndpoint, NetworkConfig, NetworkConfigBuilder, NetworkMode, TargetPlatform};
 pub use offline::{
@@ -163,14 +169,19 @@ pub use offline::{
     RequestQueue, RetryPolicy,
 };
 pub use proxy::{DevProxy, ProxyConfig, ProxyLogEntry, ProxyLogLevel, ProxyTarget, PathRewrite};
+pub use recording::{Cassette, CassetteEntry, RecordingInterceptor, ReplayInterceptor};
 pub use reverse_proxy::{GeneratedConfig, ProxyServer, ReverseProxyConfig, SecurityHeaders, SslConfig, Upstream};
-pub use websocket::{WsClient, WsClientBuilder, WsClientConfig, WsConnectionState, WsEvent, WsMessage};
+pub use sse::{SseClient, SseEvent, SseParser};
+pub use websocket::{
+    QueuePolicy, WsClient, WsClientBuilder, WsClientConfig, WsConnectionState, WsEvent, WsMessage,
+};
 
 /// Convenient re-exports for common usage patterns.
 pub mod prelude {
     pub use crate::allowlist::{Allowlist, PatternBuilder};
     pub use crate::auth::{
-        ApiKeyConfig, ApiKeyProvider, AuthCredentials, AuthManager, AuthProvider, TokenPair,
+        ApiKeyConfig, ApiKeyProvider, AuthCredentials, AuthManager, AuthProvider, OAuth2Provider,
+        TokenPair,
     };
     pub use crate::capability::{CapabilityValidator, NetworkCapability};
     pub use crate::cors::{CorsConfig, CorsPreset, BackendFramework};
@@ -178,11 +189,12 @@ pub mod prelude {
     pub use crate::diagnostics::{NetworkDoctor, DiagnosticReport};
     pub use crate::error::{AuthState, NetworkError, NetworkResult};
     pub use crate::http::{HttpClient, HttpRequest, HttpResponse, RetryConfig};
-    pub use crate::interceptor::{HeaderInterceptor, Interceptor, LoggingInterceptor};
+    pub use crate::interceptor::{Flow, HeaderInterceptor, Interceptor, LoggingInterceptor};
     pub use crate::network_mode::{NetworkConfig, NetworkMode, TargetPlatform};
     pub use crate::offline::{NetworkStatus, OfflineDetector, RetryPolicy};
     pub use crate::proxy::{DevProxy, ProxyConfig, ProxyTarget};
     pub use crate::reverse_proxy::{ReverseProxyConfig, ProxyServer};
+    pub use crate::sse::{SseClient, SseEvent};
     pub use crate::websocket::{WsClient, WsClientBuilder, WsMessage};
 }
 
@@ -360,7 +360,7 @@ impl RetryPolicy {
 }
 
 /// Simple pseudo-random factor for jitter (0.0 to 1.0).
-fn rand_factor() -> f64 {
+pub(crate) fn rand_factor() -> f64 {
     use std::collections::hash_map::DefaultHasher;
     use std::hash::{Hash, Hasher};
     use std::time::SystemTime;
@@ -8,7 +8,7 @@
 //!
 //! - Path-based routing with prefix matching
 //! - Path rewriting (strip prefix when forwarding)
-//! - WebSocket proxying support
+//! - WebSocket proxying support (upgrade passthrough via [`DevProxy::tunnel_websocket`])
 //! - Request logging and debugging
 //! - Multiple backend targets
 //! - Custom header injection
@@ -47,7 +47,15 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tracing::debug;
+
+use crate::error::{NetworkError, NetworkResult};
+
+/// Maximum size of an HTTP message head read by the proxy.
+const MAX_HEAD_LEN: usize = 16 * 1024;
 
 /// A proxy target configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -587,6 +595,222 @@ impl DevProxy {
         headers.extend(target.headers.clone());
         headers
     }
+
+    /// Route a WebSocket handshake path to a target with `websocket` enabled.
+    pub fn route_websocket(&self, path: &str) -> Option<(&ProxyTarget, String)> {
+        self.route(path).filter(|(target, _)| target.websocket)
+    }
+
+    /// Proxy one WebSocket connection accepted on the frontend.
+    ///
+    /// Reads the upgrade request from `client`, forwards the handshake to the
+    /// matched target (with prefix stripping and [`PathRewrite`] rules applied
+    /// to the URL), relays the target's response, and on `101 Switching
+    /// Protocols` splices the two sockets until either side closes.
+    ///
+    /// Requests that are not upgrades are answered with `400`, unmatched
+    /// paths with `404` and unreachable targets with `502`. Only plain
+    /// `ws://`/`http://` targets are supported. Returns the log entry for
+    /// the connection once it ends.
+    pub async fn tunnel_websocket<S>(&self, mut client: S) -> NetworkResult<ProxyLogEntry>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let started = Instant::now();
+        let elapsed = || started.elapsed().as_millis() as u64;
+
+        let (request, leftover) = read_head(&mut client).await?;
+        let mut start_line = request.start_line.split_whitespace();
+        let method = start_line.next().unwrap_or_default().to_string();
+        let request_target = start_line.next().unwrap_or("/").to_string();
+        let (path, query) = match request_target.split_once('?') {
+            Some((path, query)) => (path, Some(query)),
+            None => (request_target.as_str(), None),
+        };
+
+        if method != "GET" || !is_websocket_upgrade(&request.header_map()) {
+            respond(&mut client, 400, "Bad Request").await?;
+            let entry = ProxyLogEntry::new(&method, &request_target, "", true);
+            return Ok(entry.complete(400, elapsed()));
+        }
+
+        let Some((target, mut url)) = self.route_websocket(path) else {
+            respond(&mut client, 404, "Not Found").await?;
+            let entry = ProxyLogEntry::new(&method, &request_target, "", true);
+            return Ok(entry.complete(404, elapsed()));
+        };
+        if let Some(query) = query {
+            url = format!("{}?{}", url, query);
+        }
+        let entry = ProxyLogEntry::new(&method, &request_target, &url, true);
+
+        let mut upstream = match connect_upstream(target, &url).await {
+            Ok(upstream) => upstream,
+            Err(e) => {
+                respond(&mut client, 502, "Bad Gateway").await?;
+                return Ok(entry.failed(e.to_string(), elapsed()));
+            }
+        };
+
+        // Forward the handshake with the rewritten path and target headers
+        let upstream_url = url::Url::parse(&url)?;
+        let mut forwarded = MessageHead {
+            start_line: format!("GET {} HTTP/1.1", &upstream_url[url::Position::BeforePath..]),
+            headers: request.headers.clone(),
+        };
+        if !target.preserve_host {
+            let host = &upstream_url[url::Position::BeforeHost..url::Position::AfterPort];
+            forwarded.set_header("Host", host);
+        }
+        for (name, value) in self.get_proxy_headers(target) {
+            forwarded.set_header(&name, &value);
+        }
+        upstream.write_all(&forwarded.to_bytes()).await?;
+        upstream.write_all(&leftover).await?;
+
+        // Relay the handshake response
+        let (response, leftover) = read_head(&mut upstream).await?;
+        client.write_all(&response.to_bytes()).await?;
+        client.write_all(&leftover).await?;
+        let status = response
+            .start_line
+            .split_whitespace()
+            .nth(1)
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(502);
+        if status != 101 {
+            return Ok(entry.complete(status, elapsed()));
+        }
+
+        debug!(url = %url, "WebSocket tunnel established");
+        if let Err(e) = tokio::io::copy_bidirectional(&mut client, &mut upstream).await {
+            // Peers often reset instead of closing cleanly; the tunnel is done either way
+            debug!(url = %url, error = %e, "WebSocket tunnel ended");
+        }
+        Ok(entry.complete(status, elapsed()))
+    }
+}
+
+/// Check whether request headers ask for a WebSocket upgrade.
+///
+/// Requires `Upgrade: websocket` and an `upgrade` token in `Connection`,
+/// both matched case-insensitively.
+pub fn is_websocket_upgrade(headers: &HashMap<String, String>) -> bool {
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    };
+    let upgrade = header("upgrade").is_some_and(|v| v.trim().eq_ignore_ascii_case("websocket"));
+    let connection = header("connection").is_some_and(|v| {
+        v.split(',')
+            .any(|token| token.trim().eq_ignore_ascii_case("upgrade"))
+    });
+    upgrade && connection
+}
+
+/// Start line and headers of an HTTP/1.1 message.
+#[derive(Debug, Clone)]
+struct MessageHead {
+    start_line: String,
+    headers: Vec<(String, String)>,
+}
+
+impl MessageHead {
+    fn parse(bytes: &[u8]) -> Option<Self> {
+        let text = std::str::from_utf8(bytes).ok()?;
+        let mut lines = text.split("\r\n");
+        let start_line = lines.next()?.to_string();
+        let headers = lines
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                let (name, value) = line.split_once(':')?;
+                Some((name.trim().to_string(), value.trim().to_string()))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self {
+            start_line,
+            headers,
+        })
+    }
+
+    fn header_map(&self) -> HashMap<String, String> {
+        self.headers.iter().cloned().collect()
+    }
+
+    /// Replace every header with this name (case-insensitive) by a single value.
+    fn set_header(&mut self, name: &str, value: &str) {
+        self.headers.retain(|(key, _)| !key.eq_ignore_ascii_case(name));
+        self.headers.push((name.to_string(), value.to_string()));
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut head = format!("{}\r\n", self.start_line);
+        for (name, value) in &self.headers {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        head.push_str("\r\n");
+        head.into_bytes()
+    }
+}
+
+/// Read a message head, returning it with any bytes read past its end.
+async fn read_head<S: AsyncRead + Unpin>(stream: &mut S) -> NetworkResult<(MessageHead, Vec<u8>)> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 2048];
+    loop {
+        if let Some(end) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            let head = MessageHead::parse(&buf[..end]).ok_or_else(|| NetworkError::ConfigError {
+                message: "Malformed HTTP message head".to_string(),
+            })?;
+            return Ok((head, buf[end + 4..].to_vec()));
+        }
+        if buf.len() > MAX_HEAD_LEN {
+            return Err(NetworkError::ConfigError {
+                message: "HTTP message head too large".to_string(),
+            });
+        }
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Err(NetworkError::WebSocketClosed {
+                reason: "Connection closed during handshake".to_string(),
+                code: None,
+            });
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+}
+
+/// Write a bodiless response and give up on the connection.
+async fn respond<S>(stream: &mut S, status: u16, reason: &str) -> NetworkResult<()>
+where
+    S: AsyncWrite + Unpin,
+{
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        status, reason
+    );
+    stream.write_all(response.as_bytes()).await?;
+    Ok(())
+}
+
+/// Open a TCP connection to a WebSocket target.
+async fn connect_upstream(target: &ProxyTarget, url: &str) -> NetworkResult<TcpStream> {
+    let url = url::Url::parse(url)?;
+    if !matches!(url.scheme(), "ws" | "http") {
+        return Err(NetworkError::ConfigError {
+            message: format!("Unsupported WebSocket target scheme '{}'", url.scheme()),
+        });
+    }
+    let host = url.host_str().unwrap_or_default().to_string();
+    let port = url.port_or_known_default().unwrap_or(80);
+    match tokio::time::timeout(target.timeout, TcpStream::connect((host.as_str(), port))).await {
+        Ok(stream) => Ok(stream?),
+        Err(_) => Err(NetworkError::Timeout {
+            duration_secs: target.timeout.as_secs(),
+        }),
+    }
 }
 
 #[cfg(test)]
@@ -737,4 +961,75 @@ mod tests {
         assert!(summary.contains("/api -> http://localhost:8000"));
         assert!(summary.contains("[WS]"));
     }
+
+    #[test]
+    fn test_websocket_upgrade_detection() {
+        let mut headers = HashMap::new();
+        headers.insert("Upgrade".to_string(), "WebSocket".to_string());
+        headers.insert("Connection".to_string(), "keep-alive, Upgrade".to_string());
+        assert!(is_websocket_upgrade(&headers));
+
+        headers.insert("Connection".to_string(), "keep-alive".to_string());
+        assert!(!is_websocket_upgrade(&headers));
+    }
+
+    #[tokio::test]
+    async fn test_websocket_tunnel_round_trip() {
+        use futures::{SinkExt, StreamExt};
+        use tokio::net::TcpListener;
+        use tokio_tungstenite::tungstenite::handshake::server::{Request, Response};
+        use tokio_tungstenite::tungstenite::Message;
+
+        // Echo server that reports the handshake path it saw
+        let echo = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let echo_addr = echo.local_addr().unwrap();
+        let (path_tx, path_rx) = tokio::sync::oneshot::channel();
+        tokio::spawn(async move {
+            let (stream, _) = echo.accept().await.unwrap();
+            // The error type is fixed by tungstenite's callback signature
+            #[allow(clippy::result_large_err)]
+            let callback = |request: &Request, response: Response| {
+                let _ = path_tx.send(request.uri().to_string());
+                Ok(response)
+            };
+            let mut ws = tokio_tungstenite::accept_hdr_async(stream, callback)
+                .await
+                .unwrap();
+            while let Some(Ok(message)) = ws.next().await {
+                if message.is_text() && ws.send(message).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        let config = ProxyConfig::new().add_target(
+            ProxyTarget::new("ws", format!("ws://{}", echo_addr))
+                .with_path("/ws")
+                .websocket(true)
+                .add_rewrite(PathRewrite::new("/live/*", "/echo/$1")),
+        );
+        let proxy = DevProxy::new(config);
+        let frontend = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let frontend_addr = frontend.local_addr().unwrap();
+        let tunnel = tokio::spawn(async move {
+            let (stream, _) = frontend.accept().await.unwrap();
+            proxy.tunnel_websocket(stream).await.unwrap()
+        });
+
+        let url = format!("ws://{}/ws/live/chat?room=1", frontend_addr);
+        let (mut ws, response) = tokio_tungstenite::connect_async(url).await.unwrap();
+        assert_eq!(response.status(), 101);
+        assert_eq!(path_rx.await.unwrap(), "/echo/chat?room=1");
+
+        ws.send(Message::Text("ping".into())).await.unwrap();
+        let reply = ws.next().await.unwrap().unwrap();
+        assert_eq!(reply.into_text().unwrap(), "ping");
+        ws.close(None).await.unwrap();
+        drop(ws);
+
+        let entry = tunnel.await.unwrap();
+        assert_eq!(entry.status, Some(101));
+        assert!(entry.websocket);
+        assert_eq!(entry.proxied_url, format!("ws://{}/echo/chat?room=1", echo_addr));
+    }
 }
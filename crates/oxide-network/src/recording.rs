@@ -0,0 +1,497 @@
+//! Record and replay HTTP traffic for offline tests.
+//!
+//! [`RecordingInterceptor`] writes every request/response pair that passes
+//! through a client to a JSON cassette file. [`ReplayInterceptor`] loads a
+//! cassette and answers matching requests without touching the network.
+//!
+//! Requests are matched on method, URL and a SHA-256 hash of the body, so
+//! a cassette recorded against a live server replays deterministically.
+//!
+//! ```rust,ignore
+//! // Record once against the real API
+//! let client = HttpClient::builder()
+//!     .interceptor(RecordingInterceptor::new("tests/cassettes/users.json"))
+//!     .build()?;
+//!
+//! // Replay in CI
+//! let client = HttpClient::builder()
+//!     .interceptor(ReplayInterceptor::from_file("tests/cassettes/users.json")?)
+//!     .build()?;
+//! ```
+
+use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::error::{NetworkError, NetworkResult};
+use crate::http::{HttpRequest, HttpResponse, MultipartValue, RequestBody};
+use crate::interceptor::{Flow, Interceptor};
+
+/// Placeholder written in place of redacted header values.
+const REDACTED: &str = "[REDACTED]";
+
+/// Build the replay key for a request: method, URL and body hash.
+pub fn request_key(request: &HttpRequest) -> String {
+    let digest = Sha256::digest(body_bytes(&request.body));
+    format!("{} {} {:x}", request.method, request.url, digest)
+}
+
+/// Stable byte representation of a request body for hashing.
+///
+/// Form fields are sorted and multipart boundaries are left out, since both
+/// vary between otherwise identical requests.
+fn body_bytes(body: &RequestBody) -> Vec<u8> {
+    match body {
+        RequestBody::None => Vec::new(),
+        RequestBody::Json(value) => serde_json::to_vec(value).unwrap_or_default(),
+        RequestBody::Bytes(bytes) => bytes.clone(),
+        RequestBody::Text(text) => text.as_bytes().to_vec(),
+        RequestBody::Form(data) => {
+            let mut pairs: Vec<_> = data.iter().collect();
+            pairs.sort();
+            pairs
+                .iter()
+                .map(|(name, value)| format!("{}={}", name, value))
+                .collect::<Vec<_>>()
+                .join("&")
+                .into_bytes()
+        }
+        RequestBody::Multipart(form) => {
+            let mut bytes = Vec::new();
+            for field in form.fields() {
+                bytes.extend_from_slice(field.name.as_bytes());
+                bytes.push(0);
+                match &field.value {
+                    MultipartValue::Text(text) => bytes.extend_from_slice(text.as_bytes()),
+                    MultipartValue::File { filename, data, .. } => {
+                        bytes.extend_from_slice(filename.as_bytes());
+                        bytes.push(0);
+                        bytes.extend_from_slice(data);
+                    }
+                    // Streamed content is read once, by the request itself
+                    MultipartValue::Stream { filename, reader, .. } => {
+                        bytes.extend_from_slice(filename.as_bytes());
+                        bytes.push(0);
+                        bytes.extend_from_slice(reader.len().to_string().as_bytes());
+                    }
+                }
+                bytes.push(0);
+            }
+            bytes
+        }
+    }
+}
+
+/// The body bytes as they go over the wire.
+///
+/// Streamed multipart parts are read once, by the request itself, so a body
+/// containing them is recorded as empty.
+fn sent_body(body: &RequestBody) -> Vec<u8> {
+    match body {
+        RequestBody::Form(data) => url::form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(data)
+            .finish()
+            .into_bytes(),
+        RequestBody::Multipart(form) if form.has_streams() => Vec::new(),
+        RequestBody::Multipart(form) => form.to_bytes().unwrap_or_default(),
+        _ => body_bytes(body),
+    }
+}
+
+/// A recorded request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    /// HTTP method.
+    pub method: String,
+    /// Request URL.
+    pub url: String,
+    /// Request headers, with redacted values replaced.
+    pub headers: HashMap<String, String>,
+    /// Base64-encoded request body, as sent.
+    pub body: String,
+}
+
+/// A recorded response.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    /// HTTP status code.
+    pub status: u16,
+    /// Response headers, with redacted values replaced.
+    pub headers: HashMap<String, String>,
+    /// Base64-encoded response body.
+    pub body: String,
+}
+
+/// One request/response pair in a cassette.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CassetteEntry {
+    /// Match key from [`request_key`].
+    pub key: String,
+    /// The request as sent.
+    pub request: RecordedRequest,
+    /// The response as received.
+    pub response: RecordedResponse,
+}
+
+/// A recorded sequence of HTTP exchanges.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Cassette {
+    /// Exchanges in the order they completed.
+    pub entries: Vec<CassetteEntry>,
+}
+
+impl Cassette {
+    /// Load a cassette from a JSON file.
+    pub fn load(path: impl AsRef<Path>) -> NetworkResult<Self> {
+        let data = std::fs::read(path)?;
+        Ok(serde_json::from_slice(&data)?)
+    }
+
+    /// Write the cassette to a JSON file, creating parent directories.
+    ///
+    /// The file is written next to its destination and renamed into place, so
+    /// readers never see a partially written cassette.
+    pub fn save(&self, path: impl AsRef<Path>) -> NetworkResult<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut temp = path.as_os_str().to_owned();
+        temp.push(".tmp");
+        std::fs::write(&temp, serde_json::to_vec_pretty(self)?)?;
+        std::fs::rename(&temp, path)?;
+        Ok(())
+    }
+}
+
+/// Interceptor that records traffic to a cassette file.
+///
+/// The file is rewritten after every completed exchange, one save at a time and
+/// off the async runtime. Streaming responses are not recorded since their
+/// body is never buffered.
+#[derive(Debug)]
+pub struct RecordingInterceptor {
+    name: String,
+    path: PathBuf,
+    redacted_headers: Vec<String>,
+    pending: Mutex<HashMap<Uuid, (String, RecordedRequest)>>,
+    cassette: Mutex<Cassette>,
+    /// Held while the cassette file is written.
+    saving: tokio::sync::Mutex<()>,
+}
+
+impl RecordingInterceptor {
+    /// Record to the given cassette path, redacting `Authorization`, `Cookie`
+    /// and `Set-Cookie` in requests and responses.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            name: "recording".to_string(),
+            path: path.into(),
+            redacted_headers: vec![
+                "authorization".to_string(),
+                "cookie".to_string(),
+                "set-cookie".to_string(),
+            ],
+            pending: Mutex::new(HashMap::new()),
+            cassette: Mutex::new(Cassette::default()),
+            saving: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Redact an additional header (case-insensitive).
+    pub fn redact_header(mut self, name: impl Into<String>) -> Self {
+        self.redacted_headers.push(name.into().to_ascii_lowercase());
+        self
+    }
+
+    /// Get the cassette path.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Get a copy of everything recorded so far.
+    pub fn cassette(&self) -> Cassette {
+        self.cassette.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    fn redact(&self, headers: &HashMap<String, String>) -> HashMap<String, String> {
+        headers
+            .iter()
+            .map(|(name, value)| {
+                if self.redacted_headers.contains(&name.to_ascii_lowercase()) {
+                    (name.clone(), REDACTED.to_string())
+                } else {
+                    (name.clone(), value.clone())
+                }
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Interceptor for RecordingInterceptor {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn intercept_request(&self, request: &mut HttpRequest) -> NetworkResult<Flow> {
+        let recorded = RecordedRequest {
+            method: request.method.to_string(),
+            url: request.url.to_string(),
+            headers: self.redact(&request.headers),
+            body: STANDARD.encode(sent_body(&request.body)),
+        };
+        self.pending
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(request.id, (request_key(request), recorded));
+        Ok(Flow::Continue)
+    }
+
+    async fn intercept_response(&self, response: HttpResponse) -> NetworkResult<HttpResponse> {
+        let pending = self
+            .pending
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&response.request_id);
+        let Some((key, request)) = pending else {
+            return Ok(response);
+        };
+        if response.is_streaming() {
+            return Ok(response);
+        }
+
+        let entry = CassetteEntry {
+            key,
+            request,
+            response: RecordedResponse {
+                status: response.status,
+                headers: self.redact(&response.headers),
+                body: STANDARD.encode(&response.body),
+            },
+        };
+        self.cassette
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entries
+            .push(entry);
+
+        // Each save snapshots the cassette once it holds the lock, so the last
+        // write to finish always has every entry
+        let _saving = self.saving.lock().await;
+        let cassette = self.cassette();
+        let path = self.path.clone();
+        tokio::task::spawn_blocking(move || cassette.save(path))
+            .await
+            .map_err(std::io::Error::other)??;
+        Ok(response)
+    }
+
+    async fn on_error(&self, request: &HttpRequest, _error: &NetworkError) {
+        self.pending
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&request.id);
+    }
+}
+
+/// Interceptor that serves responses from a cassette instead of the network.
+///
+/// Requests with no matching entry fail with
+/// [`NetworkError::NoRecordedResponse`]. When a key was recorded several
+/// times, the responses are served in order and the last one repeats.
+#[derive(Debug)]
+pub struct ReplayInterceptor {
+    name: String,
+    responses: HashMap<String, Vec<RecordedResponse>>,
+    served: Mutex<HashMap<String, usize>>,
+}
+
+impl ReplayInterceptor {
+    /// Replay the given cassette.
+    pub fn new(cassette: Cassette) -> Self {
+        let mut responses: HashMap<String, Vec<RecordedResponse>> = HashMap::new();
+        for entry in cassette.entries {
+            responses.entry(entry.key).or_default().push(entry.response);
+        }
+        Self {
+            name: "replay".to_string(),
+            responses,
+            served: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Load and replay a cassette file.
+    pub fn from_file(path: impl AsRef<Path>) -> NetworkResult<Self> {
+        Ok(Self::new(Cassette::load(path)?))
+    }
+}
+
+#[async_trait]
+impl Interceptor for ReplayInterceptor {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn intercept_request(&self, request: &mut HttpRequest) -> NetworkResult<Flow> {
+        let key = request_key(request);
+        let recorded = self.responses.get(&key).and_then(|responses| {
+            let mut served = self.served.lock().unwrap_or_else(|e| e.into_inner());
+            let index = served.entry(key).or_insert(0);
+            let response = responses.get(*index).or_else(|| responses.last());
+            *index += 1;
+            response
+        });
+        let Some(recorded) = recorded else {
+            return Err(NetworkError::NoRecordedResponse {
+                method: request.method.to_string(),
+                url: request.url.to_string(),
+            });
+        };
+
+        let body = STANDARD
+            .decode(&recorded.body)
+            .map_err(|e| NetworkError::ConfigError {
+                message: format!("Invalid cassette body: {}", e),
+            })?;
+        Ok(Flow::ShortCircuit(HttpResponse::new(
+            request.id,
+            recorded.status,
+            recorded.headers.clone(),
+            body,
+            request.url.to_string(),
+            Duration::ZERO,
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::{HttpClient, RetryConfig};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn test_record_then_replay_offline() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0u8; 4096];
+            let _ = socket.read(&mut buf).await.unwrap();
+            let body = r#"{"id":7,"name":"Ada"}"#;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\
+                 Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cassettes").join("users.json");
+        let request = || {
+            HttpRequest::post(format!("{}/users", base))
+                .unwrap()
+                .header("Authorization", "Bearer secret-token")
+                .json(&serde_json::json!({"name": "Ada"}))
+                .unwrap()
+        };
+
+        let recorder = HttpClient::builder()
+            .interceptor(RecordingInterceptor::new(&path))
+            .default_retry(RetryConfig::none())
+            .build()
+            .unwrap();
+        let recorded = recorder.execute(request()).await.unwrap();
+        assert_eq!(recorded.status, 200);
+        server.await.unwrap();
+
+        let file = std::fs::read_to_string(&path).unwrap();
+        assert!(!file.contains("secret-token"));
+        assert!(file.contains(REDACTED));
+
+        // The server is gone, so only the cassette can answer
+        let replayer = HttpClient::builder()
+            .interceptor(ReplayInterceptor::from_file(&path).unwrap())
+            .default_retry(RetryConfig::none())
+            .build()
+            .unwrap();
+        let replayed = replayer.execute(request()).await.unwrap();
+        assert_eq!(replayed.status, 200);
+        assert_eq!(replayed.body, recorded.body);
+        assert_eq!(replayed.content_type(), Some("application/json"));
+
+        let other = HttpRequest::post(format!("{}/users", base))
+            .unwrap()
+            .json(&serde_json::json!({"name": "Grace"}))
+            .unwrap();
+        let err = replayer.execute(other).await.unwrap_err();
+        assert!(matches!(err, NetworkError::NoRecordedResponse { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_records_sent_body_and_redacts_cookies() {
+        use crate::http::ResponseBuilder;
+
+        let dir = tempfile::tempdir().unwrap();
+        let recorder = RecordingInterceptor::new(dir.path().join("form.json"));
+
+        let form = HashMap::from([("user".to_string(), "ada lovelace".to_string())]);
+        let mut request = HttpRequest::post("https://api.example.com/login")
+            .unwrap()
+            .header("Cookie", "session=abc")
+            .form(form);
+        recorder.intercept_request(&mut request).await.unwrap();
+        let mut response = ResponseBuilder::new()
+            .header("Set-Cookie", "session=def; HttpOnly")
+            .body(b"ok".to_vec())
+            .build();
+        response.request_id = request.id;
+        recorder.intercept_response(response).await.unwrap();
+
+        let entry = &recorder.cassette().entries[0];
+        let body = STANDARD.decode(&entry.request.body).unwrap();
+        assert_eq!(body, b"user=ada+lovelace");
+        assert_eq!(entry.key, request_key(&request));
+        assert_eq!(entry.request.headers["Cookie"], REDACTED);
+        assert_eq!(entry.response.headers["Set-Cookie"], REDACTED);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_saves_keep_every_entry() {
+        use crate::http::ResponseBuilder;
+        use std::sync::Arc;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("concurrent.json");
+        let recorder = Arc::new(RecordingInterceptor::new(&path));
+
+        let exchanges = (0..16).map(|i| {
+            let recorder = recorder.clone();
+            tokio::spawn(async move {
+                let url = format!("https://api.example.com/items/{}", i);
+                let mut request = HttpRequest::get(url).unwrap();
+                recorder.intercept_request(&mut request).await.unwrap();
+                let mut response = ResponseBuilder::new().body(b"ok".to_vec()).build();
+                response.request_id = request.id;
+                recorder.intercept_response(response).await.unwrap();
+            })
+        });
+        for exchange in futures::future::join_all(exchanges).await {
+            exchange.unwrap();
+        }
+
+        assert_eq!(Cassette::load(&path).unwrap().entries.len(), 16);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+}
@@ -8,6 +8,8 @@
 //! - **Nginx**: Full configuration with upstream, location blocks, and WebSocket support
 //! - **Caddy**: Caddyfile format with reverse_proxy directives
 //! - **Traefik**: Docker labels or file-based configuration
+//! - **Apache**: `<VirtualHost>` with `mod_proxy` balancers and `ProxyPass` rules
+//! - **HAProxy**: `frontend`/`backend` sections with ACL routing and health checks
 //!
 //! # Why Same-Origin?
 //!
@@ -56,6 +58,10 @@ pub enum ProxyServer {
     Traefik,
     /// Cloudflare Workers.
     CloudflareWorkers,
+    /// Apache HTTP Server (mod_proxy).
+    Apache,
+    /// HAProxy load balancer.
+    HAProxy,
 }
 
 impl fmt::Display for ProxyServer {
@@ -65,6 +71,8 @@ impl fmt::Display for ProxyServer {
             ProxyServer::Caddy => write!(f, "caddy"),
             ProxyServer::Traefik => write!(f, "traefik"),
             ProxyServer::CloudflareWorkers => write!(f, "cloudflare-workers"),
+            ProxyServer::Apache => write!(f, "apache"),
+            ProxyServer::HAProxy => write!(f, "haproxy"),
         }
     }
 }
@@ -78,8 +86,11 @@ impl std::str::FromStr for ProxyServer {
             "caddy" => Ok(ProxyServer::Caddy),
             "traefik" => Ok(ProxyServer::Traefik),
             "cloudflare-workers" | "cloudflare" | "cf-workers" => Ok(ProxyServer::CloudflareWorkers),
+            "apache" | "httpd" => Ok(ProxyServer::Apache),
+            "haproxy" => Ok(ProxyServer::HAProxy),
             _ => Err(format!(
-                "Unknown proxy server '{}'. Supported: nginx, caddy, traefik, cloudflare-workers",
+                "Unknown proxy server '{}'. Supported: nginx, caddy, traefik, cloudflare-workers, \
+                 apache, haproxy",
                 s
             )),
         }
@@ -317,6 +328,8 @@ impl ReverseProxyConfig {
             ProxyServer::Caddy => self.generate_caddy(),
             ProxyServer::Traefik => self.generate_traefik(),
             ProxyServer::CloudflareWorkers => self.generate_cloudflare_workers(),
+            ProxyServer::Apache => self.generate_apache(),
+            ProxyServer::HAProxy => self.generate_haproxy(),
         }
     }
 
@@ -329,6 +342,8 @@ impl ReverseProxyConfig {
             ProxyServer::Caddy => "Caddyfile".to_string(),
             ProxyServer::Traefik => "traefik.yml".to_string(),
             ProxyServer::CloudflareWorkers => "worker.js".to_string(),
+            ProxyServer::Apache => "apache.conf".to_string(),
+            ProxyServer::HAProxy => "haproxy.cfg".to_string(),
         };
 
         GeneratedConfig {
@@ -766,6 +781,321 @@ impl ReverseProxyConfig {
         config
     }
 
+    fn generate_apache(&self) -> String {
+        let mut config = String::new();
+        let domain = self.extract_domain();
+        let server_name = self.server_name.as_ref().unwrap_or(&domain);
+
+        config.push_str("# OxideKit Generated Apache Configuration\n");
+        config.push_str("# Same-origin deployment eliminates CORS\n");
+        config.push_str("# Requires: mod_proxy, mod_proxy_http, mod_proxy_balancer,\n");
+        config.push_str("#           mod_lbmethod_byrequests, mod_proxy_wstunnel, mod_proxy_hcheck,\n");
+        config.push_str("#           mod_rewrite, mod_headers, mod_ssl\n\n");
+
+        // Balancers (upstreams sharing a name are load-balanced together)
+        for (name, members) in self.upstream_groups() {
+            config.push_str(&format!("<Proxy balancer://{}>\n", name));
+            for member in &members {
+                config.push_str(&format!(
+                    "    BalancerMember http://{} timeout={}",
+                    self.extract_host(&member.url),
+                    member.timeout_secs
+                ));
+                if let Some(ref path) = member.health_check {
+                    config.push_str(&format!(" hcmethod=GET hcuri={}", path));
+                }
+                config.push('\n');
+            }
+            config.push_str("    ProxySet lbmethod=byrequests\n");
+            config.push_str("</Proxy>\n\n");
+
+            if members[0].websocket {
+                config.push_str(&format!("<Proxy balancer://{}-ws>\n", name));
+                for member in &members {
+                    config.push_str(&format!(
+                        "    BalancerMember ws://{}\n",
+                        self.extract_host(&member.url)
+                    ));
+                }
+                config.push_str("</Proxy>\n\n");
+            }
+        }
+
+        if let Some(ref frontend) = self.frontend_upstream {
+            config.push_str("<Proxy balancer://frontend>\n");
+            config.push_str(&format!(
+                "    BalancerMember http://{}\n",
+                self.extract_host(frontend)
+            ));
+            config.push_str("</Proxy>\n\n");
+        }
+
+        // Virtual host
+        if self.ssl.enabled {
+            config.push_str("<VirtualHost *:443>\n");
+        } else {
+            config.push_str("<VirtualHost *:80>\n");
+        }
+        config.push_str(&format!("    ServerName {}\n\n", server_name));
+
+        if self.ssl.enabled {
+            config.push_str("    SSLEngine on\n");
+            if let (Some(ref cert), Some(ref key)) = (&self.ssl.cert_path, &self.ssl.key_path) {
+                config.push_str(&format!("    SSLCertificateFile {}\n", cert));
+                config.push_str(&format!("    SSLCertificateKeyFile {}\n", key));
+            } else if self.ssl.auto_https {
+                config.push_str("    # Certificates provisioned externally (mod_md or certbot)\n");
+            }
+            if let Some(ref min_tls) = self.ssl.min_tls_version {
+                let protocols: Vec<String> =
+                    min_tls.split_whitespace().map(|p| format!("+{}", p)).collect();
+                config.push_str(&format!("    SSLProtocol -all {}\n", protocols.join(" ")));
+            }
+            config.push('\n');
+        }
+
+        // Security headers
+        for (name, value) in self.security_header_values() {
+            config.push_str(&format!("    Header always set {} \"{}\"\n", name, value));
+        }
+        if self.ssl.hsts {
+            config.push_str(&format!(
+                "    Header always set Strict-Transport-Security \"max-age={}; includeSubDomains\"\n",
+                self.ssl.hsts_max_age
+            ));
+        }
+        config.push('\n');
+
+        // Compression and logging
+        if self.gzip {
+            config.push_str("    AddOutputFilterByType DEFLATE text/plain text/css application/json application/javascript text/xml application/xml\n\n");
+        }
+        if self.access_log {
+            config.push_str("    ErrorLog ${APACHE_LOG_DIR}/oxidekit-error.log\n");
+            config.push_str("    CustomLog ${APACHE_LOG_DIR}/oxidekit-access.log combined\n\n");
+        }
+
+        config.push_str("    ProxyRequests Off\n");
+        config.push_str("    ProxyPreserveHost On\n");
+        let scheme = if self.ssl.enabled { "https" } else { "http" };
+        config.push_str(&format!("    RequestHeader set X-Forwarded-Proto \"{}\"\n\n", scheme));
+
+        // WebSocket upgrades are rewritten to the ws:// balancer before ProxyPass applies.
+        // Apache uses the first matching rule, so longer prefixes go first.
+        let mut groups = self.upstream_groups();
+        groups.sort_by_key(|(_, members)| std::cmp::Reverse(members[0].path_prefix.len()));
+        if groups.iter().any(|(_, members)| members[0].websocket) {
+            config.push_str("    RewriteEngine On\n");
+            for (name, members) in &groups {
+                let upstream = members[0];
+                if !upstream.websocket {
+                    continue;
+                }
+                let kept = if upstream.strip_prefix { "" } else { upstream.path_prefix.as_str() };
+                config.push_str("    RewriteCond %{HTTP:Upgrade} =websocket [NC]\n");
+                config.push_str(&format!(
+                    "    RewriteRule ^{}/?(.*) balancer://{}-ws{}/$1 [P,L]\n",
+                    upstream.path_prefix, name, kept
+                ));
+            }
+            config.push('\n');
+        }
+
+        // API routes
+        for (name, members) in &groups {
+            let upstream = members[0];
+            let target = if upstream.strip_prefix {
+                format!("balancer://{}", name)
+            } else {
+                format!("balancer://{}{}", name, upstream.path_prefix)
+            };
+            config.push_str(&format!("    ProxyPass {} {}\n", upstream.path_prefix, target));
+            config.push_str(&format!("    ProxyPassReverse {} {}\n", upstream.path_prefix, target));
+
+            if !upstream.headers.is_empty() {
+                config.push_str(&format!("    <Location {}>\n", upstream.path_prefix));
+                for (key, value) in &upstream.headers {
+                    config.push_str(&format!("        RequestHeader set {} \"{}\"\n", key, value));
+                }
+                config.push_str("    </Location>\n");
+            }
+            config.push('\n');
+        }
+
+        // Frontend route (must come last so API prefixes match first)
+        if self.frontend_upstream.is_some() {
+            config.push_str("    ProxyPass / balancer://frontend/\n");
+            config.push_str("    ProxyPassReverse / balancer://frontend/\n");
+        }
+
+        config.push_str("</VirtualHost>\n");
+
+        // HTTP to HTTPS redirect
+        if self.ssl.enabled {
+            config.push_str("\n# HTTP to HTTPS redirect\n");
+            config.push_str("<VirtualHost *:80>\n");
+            config.push_str(&format!("    ServerName {}\n", server_name));
+            config.push_str(&format!("    Redirect permanent / https://{}/\n", server_name));
+            config.push_str("</VirtualHost>\n");
+        }
+
+        config
+    }
+
+    fn generate_haproxy(&self) -> String {
+        let mut config = String::new();
+        let domain = self.extract_domain();
+        let groups = self.upstream_groups();
+
+        config.push_str("# OxideKit Generated HAProxy Configuration\n");
+        config.push_str("# Same-origin deployment eliminates CORS\n\n");
+
+        config.push_str("global\n");
+        config.push_str("    log stdout format raw local0\n");
+        config.push_str("    maxconn 4096\n\n");
+
+        config.push_str("defaults\n");
+        config.push_str("    mode http\n");
+        config.push_str("    log global\n");
+        if self.access_log {
+            config.push_str("    option httplog\n");
+        }
+        config.push_str("    option forwardfor\n");
+        config.push_str("    timeout connect 5s\n");
+        config.push_str("    timeout client 60s\n");
+        config.push_str("    timeout server 60s\n\n");
+
+        // Frontend
+        config.push_str("frontend oxidekit\n");
+        config.push_str("    bind :80\n");
+        if self.ssl.enabled {
+            let default_cert = format!("/etc/haproxy/certs/{}.pem", domain);
+            let cert = match (&self.ssl.cert_path, &self.ssl.key_path) {
+                // HAProxy reads the key from the certificate's PEM file
+                (Some(cert), Some(key)) if cert != key => {
+                    config.push_str("    # HAProxy needs the certificate and key in one PEM:\n");
+                    config.push_str(&format!("    #   cat {} {} > {}\n", cert, key, default_cert));
+                    &default_cert
+                }
+                (Some(cert), _) => cert,
+                (None, _) => &default_cert,
+            };
+            config.push_str(&format!("    bind :443 ssl crt {}", cert));
+            if let Some(min_tls) =
+                self.ssl.min_tls_version.as_ref().and_then(|v| v.split_whitespace().next())
+            {
+                config.push_str(&format!(" ssl-min-ver {}", min_tls));
+            }
+            config.push('\n');
+            config.push_str("    http-request redirect scheme https code 301 unless { ssl_fc }\n");
+            config.push_str("    http-request set-header X-Forwarded-Proto https\n");
+        } else {
+            config.push_str("    http-request set-header X-Forwarded-Proto http\n");
+        }
+        config.push('\n');
+
+        // Security headers
+        for (name, value) in self.security_header_values() {
+            config.push_str(&format!("    http-response set-header {} \"{}\"\n", name, value));
+        }
+        if self.ssl.hsts {
+            config.push_str(&format!(
+                "    http-response set-header Strict-Transport-Security \"max-age={}; includeSubDomains\"\n",
+                self.ssl.hsts_max_age
+            ));
+        }
+        config.push('\n');
+
+        if self.gzip {
+            config.push_str("    compression algo gzip\n");
+            config.push_str("    compression type text/plain text/css application/json application/javascript text/xml application/xml\n\n");
+        }
+
+        // Routing; the first matching rule wins, so longer prefixes go first
+        let mut routes: Vec<_> = groups.iter().map(|(name, members)| (*name, members[0])).collect();
+        routes.sort_by_key(|(_, upstream)| std::cmp::Reverse(upstream.path_prefix.len()));
+        for (name, upstream) in &routes {
+            config.push_str(&format!("    acl is_{} path_beg {}\n", name, upstream.path_prefix));
+        }
+        for (name, _) in &routes {
+            config.push_str(&format!("    use_backend {} if is_{}\n", name, name));
+        }
+        if self.frontend_upstream.is_some() {
+            config.push_str("    default_backend frontend\n");
+        }
+        config.push('\n');
+
+        // Backends
+        for (name, members) in &groups {
+            let upstream = members[0];
+            config.push_str(&format!("backend {}\n", name));
+            config.push_str("    balance roundrobin\n");
+            if let Some(ref path) = upstream.health_check {
+                config.push_str(&format!("    option httpchk GET {}\n", path));
+            }
+            config.push_str(&format!("    timeout server {}s\n", upstream.timeout_secs));
+            if upstream.websocket {
+                config.push_str("    timeout tunnel 1h\n");
+            }
+            if upstream.strip_prefix {
+                config.push_str(&format!(
+                    "    http-request replace-path ^{}/?(.*) /\\1\n",
+                    upstream.path_prefix
+                ));
+            }
+            for (key, value) in &upstream.headers {
+                config.push_str(&format!("    http-request set-header {} \"{}\"\n", key, value));
+            }
+            for (i, member) in members.iter().enumerate() {
+                let check = if member.health_check.is_some() { " check" } else { "" };
+                config.push_str(&format!(
+                    "    server {}{} {}{}\n",
+                    name,
+                    i + 1,
+                    self.extract_host(&member.url),
+                    check
+                ));
+            }
+            config.push('\n');
+        }
+
+        if let Some(ref frontend) = self.frontend_upstream {
+            config.push_str("backend frontend\n");
+            config.push_str(&format!("    server frontend1 {}\n", self.extract_host(frontend)));
+        }
+
+        config
+    }
+
+    /// Group upstreams by name, preserving declaration order. Upstreams that share
+    /// a name are treated as members of one load-balanced pool.
+    fn upstream_groups(&self) -> Vec<(&str, Vec<&Upstream>)> {
+        let mut groups: Vec<(&str, Vec<&Upstream>)> = Vec::new();
+        for upstream in &self.upstreams {
+            match groups.iter_mut().find(|(name, _)| *name == upstream.name) {
+                Some((_, members)) => members.push(upstream),
+                None => groups.push((upstream.name.as_str(), vec![upstream])),
+            }
+        }
+        groups
+    }
+
+    /// Configured security headers as (name, value) pairs.
+    fn security_header_values(&self) -> Vec<(&'static str, &str)> {
+        let h = &self.security_headers;
+        [
+            ("X-Frame-Options", &h.x_frame_options),
+            ("X-Content-Type-Options", &h.x_content_type_options),
+            ("X-XSS-Protection", &h.x_xss_protection),
+            ("Referrer-Policy", &h.referrer_policy),
+            ("Content-Security-Policy", &h.content_security_policy),
+            ("Permissions-Policy", &h.permissions_policy),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| value.as_deref().map(|v| (name, v)))
+        .collect()
+    }
+
     fn extract_domain(&self) -> String {
         self.site_origin
             .trim_start_matches("https://")
@@ -839,6 +1169,8 @@ mod tests {
             "cloudflare-workers".parse::<ProxyServer>().unwrap(),
             ProxyServer::CloudflareWorkers
         );
+        assert_eq!("apache".parse::<ProxyServer>().unwrap(), ProxyServer::Apache);
+        assert_eq!("haproxy".parse::<ProxyServer>().unwrap(), ProxyServer::HAProxy);
         assert!("unknown".parse::<ProxyServer>().is_err());
     }
 
@@ -954,4 +1286,88 @@ mod tests {
         let config2 = ReverseProxyConfig::new("http://localhost:3000");
         assert_eq!(config2.extract_domain(), "localhost:3000");
     }
+
+    #[test]
+    fn test_apache_generation() {
+        let config = ReverseProxyConfig::new("https://app.example.com")
+            .add_upstream("api", "http://localhost:8000", "/api")
+            .add_upstream("api", "http://localhost:8002", "/api")
+            .with_upstream(Upstream::new("ws", "http://localhost:8001", "/ws").with_websocket(true))
+            .frontend_upstream("http://localhost:3000")
+            .with_ssl(SslConfig::manual("/etc/ssl/cert.pem", "/etc/ssl/key.pem"));
+
+        let apache = config.generate(ProxyServer::Apache);
+
+        assert!(apache.contains("<Proxy balancer://api>"));
+        assert!(apache.contains("BalancerMember http://localhost:8000"));
+        assert!(apache.contains("BalancerMember http://localhost:8002"));
+        assert!(apache.contains("ProxyPass /api balancer://api"));
+        assert!(apache.contains("ProxyPassReverse /api balancer://api"));
+        assert!(apache.contains("RewriteRule ^/ws/?(.*) balancer://ws-ws/$1 [P,L]"));
+        assert!(apache.contains("SSLCertificateFile /etc/ssl/cert.pem"));
+        assert!(apache.contains("Header always set X-Frame-Options \"SAMEORIGIN\""));
+        assert!(apache.contains("Header always set Strict-Transport-Security"));
+        assert!(apache.contains("ProxyPass / balancer://frontend/"));
+    }
+
+    #[test]
+    fn test_haproxy_generation() {
+        let config = ReverseProxyConfig::new("https://app.example.com")
+            .with_upstream(
+                Upstream::new("api", "http://localhost:8000", "/api").with_health_check("/health"),
+            )
+            .add_upstream("api", "http://localhost:8002", "/api")
+            .frontend_upstream("http://localhost:3000");
+
+        let haproxy = config.generate(ProxyServer::HAProxy);
+
+        assert!(haproxy.contains("backend api\n"));
+        assert!(haproxy.contains("acl is_api path_beg /api"));
+        assert!(haproxy.contains("use_backend api if is_api"));
+        assert!(haproxy.contains("option httpchk GET /health"));
+        assert!(haproxy.contains("server api1 localhost:8000 check"));
+        assert!(haproxy.contains("server api2 localhost:8002"));
+        assert!(haproxy.contains("http-response set-header X-Content-Type-Options \"nosniff\""));
+        assert!(haproxy.contains("default_backend frontend"));
+
+        let generated = config.generate_with_docs(ProxyServer::HAProxy);
+        assert_eq!(generated.filename, "haproxy.cfg");
+    }
+
+    #[test]
+    fn test_haproxy_ssl_key_and_prefix_order() {
+        let config = ReverseProxyConfig::new("https://app.example.com")
+            .with_ssl(SslConfig::manual("/certs/app.crt", "/certs/app.key"))
+            .with_upstream(Upstream::new("api", "http://localhost:8000", "/api"))
+            .with_upstream(Upstream::new("api_v2", "http://localhost:8001", "/api/v2"));
+
+        let haproxy = config.generate(ProxyServer::HAProxy);
+
+        let combined = "/etc/haproxy/certs/app.example.com.pem";
+        assert!(haproxy.contains(&format!("cat /certs/app.crt /certs/app.key > {}", combined)));
+        assert!(haproxy.contains(&format!("bind :443 ssl crt {}", combined)));
+        let v2 = haproxy.find("use_backend api_v2 if is_api_v2").unwrap();
+        let api = haproxy.find("use_backend api if is_api\n").unwrap();
+        assert!(v2 < api);
+    }
+
+    #[test]
+    fn test_apache_prefix_order() {
+        let config = ReverseProxyConfig::new("https://app.example.com")
+            .with_upstream(
+                Upstream::new("api", "http://localhost:8000", "/api").with_websocket(true),
+            )
+            .with_upstream(
+                Upstream::new("api_v2", "http://localhost:8001", "/api/v2").with_websocket(true),
+            );
+
+        let apache = config.generate(ProxyServer::Apache);
+
+        let v2 = apache.find("ProxyPass /api/v2 balancer://api_v2").unwrap();
+        let api = apache.find("ProxyPass /api balancer://api\n").unwrap();
+        assert!(v2 < api);
+        let v2 = apache.find("RewriteRule ^/api/v2/?").unwrap();
+        let api = apache.find("RewriteRule ^/api/?").unwrap();
+        assert!(v2 < api);
+    }
 }
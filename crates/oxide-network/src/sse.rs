@@ -0,0 +1,431 @@
+//! Server-Sent Events client for OxideKit.
+//!
+//! Connects to a `text/event-stream` endpoint over [`HttpClient`] and yields
+//! parsed events. Dropped connections are re-established according to a
+//! [`RetryPolicy`], resuming from the last seen event via `Last-Event-ID`.
+
+use futures::stream::{self, Stream, StreamExt};
+use serde::de::DeserializeOwned;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tracing::{debug, warn};
+
+use crate::allowlist::Allowlist;
+use crate::capability::{CapabilityValidator, NetworkCapability};
+use crate::error::{NetworkError, NetworkResult};
+use crate::http::{BodyStream, HttpClient, HttpRequest};
+use crate::offline::RetryPolicy;
+
+/// A single event received from an SSE stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SseEvent {
+    /// Last event ID at the time this event was dispatched.
+    pub id: Option<String>,
+    /// Event type (`None` means the default `message` type).
+    pub event: Option<String>,
+    /// Event payload; multiple `data:` lines are joined with newlines.
+    pub data: String,
+}
+
+impl SseEvent {
+    /// Get the event type, defaulting to `message`.
+    pub fn event_type(&self) -> &str {
+        self.event.as_deref().unwrap_or("message")
+    }
+
+    /// Deserialize the event data as JSON.
+    pub fn json<T: DeserializeOwned>(&self) -> NetworkResult<T> {
+        serde_json::from_str(&self.data).map_err(NetworkError::JsonError)
+    }
+}
+
+/// Incremental parser for the `text/event-stream` format.
+///
+/// Feed it body chunks as they arrive; complete events are returned once
+/// their terminating blank line has been seen. Comment lines (starting with
+/// `:`) and unknown fields are ignored.
+#[derive(Debug, Default)]
+pub struct SseParser {
+    /// Bytes of the current, incomplete line.
+    buffer: Vec<u8>,
+    /// Event type of the event being built.
+    event: Option<String>,
+    /// Data lines of the event being built.
+    data: Vec<String>,
+    /// Last event ID seen on the stream.
+    last_event_id: Option<String>,
+}
+
+impl SseParser {
+    /// Create a new parser.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the last event ID seen on the stream.
+    pub fn last_event_id(&self) -> Option<&str> {
+        self.last_event_id.as_deref()
+    }
+
+    /// Feed a chunk of the body and return any events it completed.
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        let mut events = Vec::new();
+        for &byte in chunk {
+            if byte != b'\n' {
+                self.buffer.push(byte);
+                continue;
+            }
+            let mut line = std::mem::take(&mut self.buffer);
+            if line.last() == Some(&b'\r') {
+                line.pop();
+            }
+            if let Some(event) = self.process_line(&String::from_utf8_lossy(&line)) {
+                events.push(event);
+            }
+        }
+        events
+    }
+
+    /// Apply one line, returning an event if it was the dispatching blank line.
+    fn process_line(&mut self, line: &str) -> Option<SseEvent> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        if line.starts_with(':') {
+            return None;
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+        match field {
+            "data" => self.data.push(value.to_string()),
+            "event" => self.event = Some(value.to_string()),
+            "id" if !value.contains('\0') => self.last_event_id = Some(value.to_string()),
+            _ => {}
+        }
+        None
+    }
+
+    /// Emit the buffered event, if it has any data.
+    fn dispatch(&mut self) -> Option<SseEvent> {
+        let event = self.event.take();
+        if self.data.is_empty() {
+            return None;
+        }
+        Some(SseEvent {
+            id: self.last_event_id.clone(),
+            event,
+            data: std::mem::take(&mut self.data).join("\n"),
+        })
+    }
+}
+
+/// Server-Sent Events client.
+///
+/// Requests go through the wrapped [`HttpClient`], so its interceptors, auth,
+/// allowlist and offline detection apply to every (re)connection.
+#[derive(Debug, Clone)]
+pub struct SseClient {
+    http: HttpClient,
+    url: String,
+    retry_policy: RetryPolicy,
+    allowlist: Option<Arc<Allowlist>>,
+    capability: Option<Arc<CapabilityValidator>>,
+    last_event_id: Arc<Mutex<Option<String>>>,
+}
+
+impl SseClient {
+    /// Create a client for an event-stream endpoint.
+    pub fn new(http: HttpClient, url: impl Into<String>) -> Self {
+        Self {
+            http,
+            url: url.into(),
+            retry_policy: RetryPolicy::default(),
+            allowlist: None,
+            capability: None,
+            last_event_id: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Set the reconnection policy.
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Set an allowlist for URL validation.
+    pub fn with_allowlist(mut self, allowlist: Arc<Allowlist>) -> Self {
+        self.allowlist = Some(allowlist);
+        self
+    }
+
+    /// Restrict connections to what a network capability permits.
+    pub fn with_capability(mut self, capability: NetworkCapability) -> Self {
+        self.capability = Some(Arc::new(CapabilityValidator::new(capability)));
+        self
+    }
+
+    /// Resume from a previously seen event ID.
+    pub fn with_last_event_id(self, id: impl Into<String>) -> Self {
+        *self.last_event_id.lock().unwrap_or_else(|e| e.into_inner()) = Some(id.into());
+        self
+    }
+
+    /// Get the ID of the last event received.
+    pub fn last_event_id(&self) -> Option<String> {
+        self.last_event_id.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Connect and stream events.
+    ///
+    /// When the connection drops the client waits per its [`RetryPolicy`]
+    /// and reconnects with `Last-Event-ID`. The stream ends after a
+    /// `204 No Content` response or once the policy gives up; a failed
+    /// connection is yielded as a final error.
+    pub fn events(&self) -> impl Stream<Item = NetworkResult<SseEvent>> + Send + 'static {
+        let state = SseStreamState {
+            client: self.clone(),
+            parser: SseParser::new(),
+            body: None,
+            pending: VecDeque::new(),
+            attempt: 0,
+            since: Instant::now(),
+            done: false,
+        };
+        stream::unfold(state, |mut state| async move {
+            let item = state.next_item().await?;
+            Some((item, state))
+        })
+    }
+
+    /// Build the request for one connection attempt.
+    fn build_request(&self) -> NetworkResult<HttpRequest> {
+        if let Some(allowlist) = &self.allowlist {
+            if !allowlist.is_allowed(&self.url) {
+                return Err(NetworkError::BlockedByAllowlist {
+                    url: self.url.clone(),
+                });
+            }
+        }
+
+        let mut request = HttpRequest::get(&self.url)?
+            .header("Accept", "text/event-stream")
+            .header("Cache-Control", "no-cache")
+            .no_retry();
+        if let Some(id) = self.last_event_id() {
+            request = request.header("Last-Event-ID", id);
+        }
+
+        if let Some(validator) = &self.capability {
+            validator
+                .validate_request(&request)
+                .map_err(|violation| NetworkError::MissingCapability {
+                    capability: format!("native.network ({})", violation),
+                })?;
+        }
+        Ok(request)
+    }
+}
+
+/// Connection state driving [`SseClient::events`].
+struct SseStreamState {
+    client: SseClient,
+    parser: SseParser,
+    body: Option<BodyStream>,
+    pending: VecDeque<SseEvent>,
+    /// Reconnection attempts since the last successful connection.
+    attempt: u32,
+    /// When the current run of reconnection attempts started.
+    since: Instant,
+    done: bool,
+}
+
+impl SseStreamState {
+    /// Produce the next event or error, or `None` when the stream is over.
+    async fn next_item(&mut self) -> Option<NetworkResult<SseEvent>> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Some(Ok(event));
+            }
+            if self.done {
+                return None;
+            }
+
+            let Some(body) = self.body.as_mut() else {
+                if let Err(err) = self.connect().await {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+                continue;
+            };
+
+            match body.next().await {
+                Some(Ok(chunk)) => {
+                    let events = self.parser.push(&chunk);
+                    if let Some(id) = self.parser.last_event_id() {
+                        *self.client.last_event_id.lock().unwrap_or_else(|e| e.into_inner()) =
+                            Some(id.to_string());
+                    }
+                    self.pending.extend(events);
+                }
+                Some(Err(err)) => {
+                    warn!(url = %self.client.url, error = %err, "SSE stream interrupted");
+                    self.disconnected();
+                }
+                None => {
+                    debug!(url = %self.client.url, "SSE stream closed by server");
+                    self.disconnected();
+                }
+            }
+        }
+    }
+
+    /// Drop the current body and schedule a reconnection.
+    fn disconnected(&mut self) {
+        self.body = None;
+        self.parser = SseParser {
+            last_event_id: self.parser.last_event_id.take(),
+            ..SseParser::default()
+        };
+        self.attempt = 1;
+        self.since = Instant::now();
+    }
+
+    /// Open a connection, retrying per the policy.
+    ///
+    /// Sets `done` when the server asks the client to stop reconnecting.
+    async fn connect(&mut self) -> NetworkResult<()> {
+        let policy = self.client.retry_policy.clone();
+        loop {
+            if self.attempt > 0 {
+                if !policy.should_retry(self.attempt - 1, self.since.elapsed()) {
+                    self.done = true;
+                    return Ok(());
+                }
+                let delay = policy.delay_for_attempt(self.attempt - 1);
+                debug!(url = %self.client.url, attempt = self.attempt, "Reconnecting SSE stream");
+                tokio::time::sleep(delay).await;
+            }
+
+            let request = self.client.build_request()?;
+            let error = match self.client.http.execute_streaming(request).await {
+                Ok(response) if response.status == 204 => {
+                    self.done = true;
+                    return Ok(());
+                }
+                Ok(response) => match response.into_result() {
+                    Ok(response) => {
+                        self.body = Some(response.bytes_stream().boxed());
+                        self.attempt = 0;
+                        return Ok(());
+                    }
+                    Err(err) => err,
+                },
+                Err(err) => err,
+            };
+
+            if !error.is_retryable()
+                || !policy.should_retry(self.attempt, self.since.elapsed())
+            {
+                return Err(error);
+            }
+            if self.attempt == 0 {
+                self.since = Instant::now();
+            }
+            self.attempt += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[test]
+    fn test_parse_multiline_data_and_comments() {
+        let mut parser = SseParser::new();
+        let events = parser.push(b": keep-alive\nevent: update\nid: 42\ndata: line one\n");
+        assert!(events.is_empty());
+
+        // Split mid-line to exercise buffering
+        let mut events = parser.push(b"data: line two\r\n\r");
+        events.extend(parser.push(b"\ndata: next\n\n"));
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            events[0],
+            SseEvent {
+                id: Some("42".to_string()),
+                event: Some("update".to_string()),
+                data: "line one\nline two".to_string(),
+            }
+        );
+        assert_eq!(events[1].event_type(), "message");
+        assert_eq!(events[1].id.as_deref(), Some("42"));
+
+        // A comment-only block dispatches nothing
+        assert!(parser.push(b":ping\n\n").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_sends_last_event_id() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (request_tx, mut request_rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+
+        tokio::spawn(async move {
+            let bodies: [&[u8]; 2] = [b"id: 7\ndata: first\n\n", b"data: second\n\n"];
+            for body in bodies {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = [0u8; 2048];
+                let n = socket.read(&mut buf).await.unwrap();
+                request_tx.send(String::from_utf8_lossy(&buf[..n]).to_lowercase()).unwrap();
+                socket
+                    .write_all(
+                        b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\n\
+                          Connection: close\r\n\r\n",
+                    )
+                    .await
+                    .unwrap();
+                socket.write_all(body).await.unwrap();
+            }
+        });
+
+        let policy = RetryPolicy {
+            max_retries: 1,
+            initial_delay: Duration::from_millis(10),
+            jitter: false,
+            ..RetryPolicy::default()
+        };
+        let client = SseClient::new(HttpClient::new().unwrap(), format!("http://{}/feed", addr))
+            .with_retry_policy(policy);
+        let mut events = Box::pin(client.events());
+
+        let first = events.next().await.unwrap().unwrap();
+        assert_eq!((first.id.as_deref(), first.data.as_str()), (Some("7"), "first"));
+        assert!(!request_rx.recv().await.unwrap().contains("last-event-id"));
+
+        let second = events.next().await.unwrap().unwrap();
+        assert_eq!(second.data, "second");
+        assert!(request_rx.recv().await.unwrap().contains("last-event-id: 7"));
+        assert_eq!(client.last_event_id().as_deref(), Some("7"));
+    }
+
+    #[tokio::test]
+    async fn test_allowlist_blocks_connection() {
+        let allowlist = Arc::new(Allowlist::new().allow_domain("api.example.com"));
+        let client = SseClient::new(HttpClient::new().unwrap(), "https://evil.com/feed")
+            .with_allowlist(allowlist);
+
+        let mut events = Box::pin(client.events());
+        assert!(matches!(
+            events.next().await,
+            Some(Err(NetworkError::BlockedByAllowlist { .. }))
+        ));
+        assert!(events.next().await.is_none());
+    }
+}
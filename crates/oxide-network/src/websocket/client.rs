@@ -7,7 +7,7 @@ use tokio::sync::{mpsc, RwLock, broadcast};
 use tracing::debug;
 
 use crate::allowlist::Allowlist;
-use crate::error::{NetworkError, NetworkResult};
+use crate::error::{NetworkError, NetworkResult, WsError};
 
 use super::{WsConnectionState, WsEvent, WsMessage};
 
@@ -30,8 +30,16 @@ pub struct WsClientConfig {
     pub heartbeat_interval: Option<Duration>,
     /// Connection timeout.
     pub connect_timeout: Duration,
-    /// Maximum message queue size during disconnection.
+    /// Maximum number of messages buffered for sending.
+    ///
+    /// Bounds both the queue held while disconnected and the outgoing channel
+    /// while connected.
     pub max_queue_size: usize,
+    /// What to do with queued messages once the connection is (re)established.
+    ///
+    /// Only recorded for now: `connect` doesn't open a transport yet, so the
+    /// queue is never drained outside tests.
+    pub queue_policy: QueuePolicy,
     /// Custom headers for the upgrade request.
     pub headers: Vec<(String, String)>,
     /// Subprotocols to request.
@@ -50,6 +58,7 @@ impl Default for WsClientConfig {
             heartbeat_interval: Some(Duration::from_secs(30)),
             connect_timeout: Duration::from_secs(10),
             max_queue_size: 100,
+            queue_policy: QueuePolicy::default(),
             headers: Vec::new(),
             subprotocols: Vec::new(),
         }
@@ -83,6 +92,18 @@ impl WsClientConfig {
         self
     }
 
+    /// Set the maximum number of buffered messages.
+    pub fn max_queue_size(mut self, len: usize) -> Self {
+        self.max_queue_size = len;
+        self
+    }
+
+    /// Set the reconnect queue policy.
+    pub fn queue_policy(mut self, policy: QueuePolicy) -> Self {
+        self.queue_policy = policy;
+        self
+    }
+
     /// Add a custom header.
     pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
         self.headers.push((name.into(), value.into()));
@@ -96,6 +117,16 @@ impl WsClientConfig {
     }
 }
 
+/// Handling of messages queued while disconnected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QueuePolicy {
+    /// Send queued messages in order after reconnecting.
+    #[default]
+    Replay,
+    /// Discard queued messages on reconnect (for state that goes stale).
+    Drop,
+}
+
 /// WebSocket client handle.
 ///
 /// This is the main interface for WebSocket communication.
@@ -114,7 +145,7 @@ pub struct WsClient {
 impl WsClient {
     /// Create a new WebSocket client (does not connect immediately).
     pub fn new(config: WsClientConfig) -> Self {
-        let (outgoing_tx, _outgoing_rx) = mpsc::channel(100);
+        let (outgoing_tx, _outgoing_rx) = mpsc::channel(config.max_queue_size.max(1));
         let (event_tx, _) = broadcast::channel(100);
 
         Self {
@@ -152,7 +183,8 @@ impl WsClient {
     /// Send a message.
     ///
     /// If not connected and queue is not full, the message will be queued
-    /// and sent when connection is established.
+    /// and sent when connection is established. While connected, waits for
+    /// room in the outgoing channel.
     pub async fn send(&self, message: WsMessage) -> NetworkResult<()> {
         let state = *self.state.read().await;
 
@@ -161,28 +193,51 @@ impl WsClient {
                 self.outgoing_tx
                     .send(message)
                     .await
-                    .map_err(|_| NetworkError::WebSocketError {
-                        message: "Channel closed".to_string(),
-                    })?;
+                    .map_err(|_| WsError::ChannelClosed)?;
             }
             WsConnectionState::Connecting | WsConnectionState::Reconnecting => {
-                // Queue the message
-                let mut queue = self.message_queue.write().await;
-                if queue.len() >= self.config.max_queue_size {
-                    return Err(NetworkError::WebSocketError {
-                        message: "Message queue full".to_string(),
-                    });
-                }
-                queue.push_back(message);
-                debug!("Message queued, queue size: {}", queue.len());
+                self.enqueue(message).await?;
             }
-            _ => {
-                return Err(NetworkError::WebSocketError {
-                    message: format!("Cannot send in state: {}", state),
-                });
+            _ => return Err(WsError::NotConnected { state }.into()),
+        }
+
+        Ok(())
+    }
+
+    /// Send a message without waiting for buffer space.
+    ///
+    /// Fails with [`WsError::QueueFull`] when the outgoing channel (while
+    /// connected) or the reconnect queue (while connecting) is at
+    /// `max_queue_size`, so callers can shed load instead of piling up memory.
+    pub async fn try_send(&self, message: WsMessage) -> Result<(), WsError> {
+        let state = *self.state.read().await;
+
+        match state {
+            WsConnectionState::Connected => {
+                self.outgoing_tx.try_send(message).map_err(|e| match e {
+                    mpsc::error::TrySendError::Full(_) => WsError::QueueFull {
+                        capacity: self.config.max_queue_size,
+                    },
+                    mpsc::error::TrySendError::Closed(_) => WsError::ChannelClosed,
+                })
+            }
+            WsConnectionState::Connecting | WsConnectionState::Reconnecting => {
+                self.enqueue(message).await
             }
+            _ => Err(WsError::NotConnected { state }),
         }
+    }
 
+    /// Add a message to the reconnect queue if there is room.
+    async fn enqueue(&self, message: WsMessage) -> Result<(), WsError> {
+        let mut queue = self.message_queue.write().await;
+        if queue.len() >= self.config.max_queue_size {
+            return Err(WsError::QueueFull {
+                capacity: self.config.max_queue_size,
+            });
+        }
+        queue.push_back(message);
+        debug!("Message queued, queue size: {}", queue.len());
         Ok(())
     }
 
@@ -250,6 +305,7 @@ impl WsClient {
         // 2. Writing outgoing messages
         // 3. Heartbeat/ping-pong
         // 4. Reconnection handling
+        // 5. Draining the reconnect queue per `queue_policy` once connected
 
         info!(url = %self.config.url, "WebSocket connection initiated");
 
@@ -273,9 +329,21 @@ impl WsClient {
     }
 
     /// Drain the message queue (internal use after reconnection).
+    ///
+    /// Returns the messages to send per the queue policy and emits
+    /// [`WsEvent::QueueDrained`]. Nothing calls this until `connect`
+    /// establishes a real connection.
     async fn drain_queue(&self) -> Vec<WsMessage> {
-        let mut queue = self.message_queue.write().await;
-        queue.drain(..).collect()
+        let queued: Vec<WsMessage> = self.message_queue.write().await.drain(..).collect();
+        let messages = match self.config.queue_policy {
+            QueuePolicy::Replay => queued,
+            QueuePolicy::Drop => {
+                debug!("Dropping {} queued messages on reconnect", queued.len());
+                Vec::new()
+            }
+        };
+        let _ = self.event_tx.send(WsEvent::QueueDrained);
+        messages
     }
 }
 
@@ -334,6 +402,18 @@ impl WsClientBuilder {
         self
     }
 
+    /// Set the maximum number of buffered messages.
+    pub fn max_queue_size(mut self, len: usize) -> Self {
+        self.config.max_queue_size = len;
+        self
+    }
+
+    /// Set the reconnect queue policy.
+    pub fn queue_policy(mut self, policy: QueuePolicy) -> Self {
+        self.config.queue_policy = policy;
+        self
+    }
+
     /// Add a custom header.
     pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
         self.config.headers.push((name.into(), value.into()));
@@ -393,4 +473,49 @@ mod tests {
         client.send_text("test").await.unwrap();
         assert_eq!(client.queue_size().await, 1);
     }
+
+    #[tokio::test]
+    async fn test_bounded_queue() {
+        let client = WsClientBuilder::new("wss://example.com/ws")
+            .max_queue_size(3)
+            .build();
+        let mut events = client.subscribe();
+
+        assert_eq!(
+            client.try_send(WsMessage::text("early")).await,
+            Err(WsError::NotConnected {
+                state: WsConnectionState::Disconnected
+            })
+        );
+
+        *client.state.write().await = WsConnectionState::Reconnecting;
+        for i in 0..3 {
+            client.try_send(WsMessage::text(format!("m{}", i))).await.unwrap();
+        }
+        assert_eq!(
+            client.try_send(WsMessage::text("overflow")).await,
+            Err(WsError::QueueFull { capacity: 3 })
+        );
+        assert!(client.send_text("overflow").await.is_err());
+        assert_eq!(client.queue_size().await, 3);
+
+        let replayed = client.drain_queue().await;
+        assert_eq!(replayed.len(), 3);
+        assert_eq!(replayed[0].as_text(), Some("m0"));
+        assert!(matches!(events.recv().await.unwrap(), WsEvent::QueueDrained));
+        client.try_send(WsMessage::text("room again")).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_drop_queue_policy() {
+        let client = WsClientBuilder::new("wss://example.com/ws")
+            .queue_policy(QueuePolicy::Drop)
+            .build();
+
+        *client.state.write().await = WsConnectionState::Connecting;
+        client.send_text("stale").await.unwrap();
+
+        assert!(client.drain_queue().await.is_empty());
+        assert_eq!(client.queue_size().await, 0);
+    }
 }
@@ -163,6 +163,8 @@ pub enum WsEvent {
         /// Attempt number.
         attempt: u32,
     },
+    /// Messages queued while disconnected have been flushed or dropped.
+    QueueDrained,
 }
 
 #[cfg(test)]
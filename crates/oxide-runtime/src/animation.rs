@@ -5,8 +5,10 @@
 
 use oxide_components::animation::{
     AnimationController, AnimationEvent, AnimationId, AnimatableValue,
-    Animation, Easing, Timeline, TransitionConfig, Transition,
+    Animation, Easing, GestureSpring, SpringParams, Timeline, TransitionConfig, Transition,
 };
+use oxide_components::animation::interpolate::{Point2D, Rect2D, Size2D};
+use crate::keyframes::{KeyframeTimeline, PropertyId};
 use oxide_components::theme::MotionTokens;
 use std::collections::HashMap;
 use std::time::Instant;
@@ -30,6 +32,15 @@ pub struct AnimationRuntime {
 
     /// Motion tokens from theme
     motion_tokens: Option<MotionTokens>,
+
+    /// Spring animations by element ID, then property
+    springs: HashMap<String, HashMap<String, Spring>>,
+
+    /// Keyframe timelines by element ID
+    keyframes: HashMap<String, KeyframeTimeline>,
+
+    /// Final values of finished `Once` timelines, held until replaced or cancelled
+    held_keyframes: HashMap<String, Vec<(PropertyId, f32)>>,
 }
 
 impl std::fmt::Debug for AnimationRuntime {
@@ -39,6 +50,9 @@ impl std::fmt::Debug for AnimationRuntime {
             .field("element_animations", &self.element_animations)
             .field("motion_tokens", &self.motion_tokens)
             .field("event_handlers_count", &self.event_handlers.len())
+            .field("springs", &self.springs)
+            .field("keyframes", &self.keyframes)
+            .field("held_keyframes", &self.held_keyframes)
             .finish()
     }
 }
@@ -59,6 +73,9 @@ impl AnimationRuntime {
             event_handlers: Vec::new(),
             element_animations: HashMap::new(),
             motion_tokens: None,
+            springs: HashMap::new(),
+            keyframes: HashMap::new(),
+            held_keyframes: HashMap::new(),
         }
     }
 
@@ -163,11 +180,128 @@ impl AnimationRuntime {
             }
         }
 
+        if let Some(held) = self.held_keyframes.get(element_id) {
+            for &(property, value) in held {
+                values.insert(property.to_string(), AnimatableValue::Float(value));
+            }
+        }
+
+        if let Some(timeline) = self.keyframes.get(element_id) {
+            for (property, value) in timeline.values() {
+                values.insert(property.to_string(), AnimatableValue::Float(value));
+            }
+        }
+
+        if let Some(springs) = self.springs.get(element_id) {
+            for (property, spring) in springs {
+                values.insert(property.clone(), spring.value());
+            }
+        }
+
         values
     }
 
+    /// Animate an element property toward `target` with a spring
+    ///
+    /// If the property already has a spring, it is retargeted and keeps its
+    /// current velocity. Returns `false` if the value type can't be sprung or
+    /// doesn't match the existing spring.
+    pub fn spring_to(
+        &mut self,
+        element_id: &str,
+        property: &str,
+        from: AnimatableValue,
+        target: AnimatableValue,
+        params: SpringParams,
+    ) -> bool {
+        let springs = self.springs.entry(element_id.to_string()).or_default();
+        if let Some(spring) = springs.get_mut(property) {
+            return spring.set_target(target);
+        }
+
+        match Spring::new(property, from, target) {
+            Some(spring) => {
+                springs.insert(property.to_string(), spring.params(params));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Spring an [`Animatable`] property from its current value toward `target`
+    pub fn spring_element<A: Animatable>(
+        &mut self,
+        element: &A,
+        property: &str,
+        target: AnimatableValue,
+        params: SpringParams,
+    ) -> bool {
+        let Some(from) = element.get_animatable_properties().remove(property) else {
+            return false;
+        };
+        self.spring_to(element.animation_id(), property, from, target, params)
+    }
+
+    /// Play a keyframe timeline on an element, replacing any previous one
+    pub fn play_keyframes(&mut self, element_id: &str, timeline: KeyframeTimeline) {
+        self.held_keyframes.remove(element_id);
+        self.keyframes.insert(element_id.to_string(), timeline);
+    }
+
+    /// Advance all keyframe timelines by `dt` seconds, dropping finished ones
+    ///
+    /// The final values of a finished timeline stay visible through
+    /// [`get_element_values`](Self::get_element_values) until it is replaced
+    /// or the element is cancelled. Returns the values of every timeline that
+    /// was playing, by element ID.
+    pub fn tick_keyframes(&mut self, dt: f32) -> HashMap<String, Vec<(PropertyId, f32)>> {
+        let mut updates = HashMap::new();
+        let held = &mut self.held_keyframes;
+        self.keyframes.retain(|element_id, timeline| {
+            let values = timeline.tick(dt);
+            if timeline.is_finished() {
+                held.insert(element_id.clone(), values.clone());
+            }
+            updates.insert(element_id.clone(), values);
+            !timeline.is_finished()
+        });
+        updates
+    }
+
+    /// Get the spring driving an element property
+    pub fn spring(&self, element_id: &str, property: &str) -> Option<&Spring> {
+        self.springs.get(element_id)?.get(property)
+    }
+
+    /// Advance all springs by `dt` seconds, dropping settled ones
+    ///
+    /// Returns the latest value of every spring that moved, by element ID.
+    pub fn tick_springs(&mut self, dt: f32) -> HashMap<String, HashMap<String, AnimatableValue>> {
+        let mut updates = HashMap::new();
+
+        for (element_id, springs) in &mut self.springs {
+            let mut values = HashMap::new();
+            springs.retain(|property, spring| {
+                if spring.is_settled() {
+                    return false;
+                }
+                values.insert(property.clone(), spring.tick(dt));
+                true
+            });
+            if !values.is_empty() {
+                updates.insert(element_id.clone(), values);
+            }
+        }
+        self.springs.retain(|_, springs| !springs.is_empty());
+
+        updates
+    }
+
     /// Cancel all animations for an element
     pub fn cancel_element(&mut self, element_id: &str) {
+        self.springs.remove(element_id);
+        self.keyframes.remove(element_id);
+        self.held_keyframes.remove(element_id);
         if let Some(ids) = self.element_animations.remove(element_id) {
             for id in ids {
                 self.controller.stop(id);
@@ -175,7 +309,7 @@ impl AnimationRuntime {
         }
     }
 
-    /// Update animations (call each frame)
+    /// Update animations and springs (call each frame)
     /// Returns events that occurred during this update
     pub fn update(&mut self) -> Vec<AnimationEvent> {
         if !self.enabled {
@@ -187,6 +321,8 @@ impl AnimationRuntime {
         self.last_update = now;
 
         let events = self.controller.update(dt);
+        self.tick_springs(dt);
+        self.tick_keyframes(dt);
 
         // Call event handlers
         for event in &events {
@@ -220,6 +356,8 @@ impl AnimationRuntime {
 
         self.last_update = Instant::now();
         let events = self.controller.update(dt);
+        self.tick_springs(dt);
+        self.tick_keyframes(dt);
 
         // Call event handlers
         for event in &events {
@@ -233,12 +371,20 @@ impl AnimationRuntime {
 
     /// Check if there are any active animations
     pub fn has_active_animations(&self) -> bool {
-        self.controller.has_active()
+        self.controller.has_active() || self.active_spring_count() > 0 || !self.keyframes.is_empty()
     }
 
     /// Get the count of active animations
     pub fn active_count(&self) -> usize {
-        self.controller.active_count()
+        self.controller.active_count() + self.active_spring_count() + self.keyframes.len()
+    }
+
+    fn active_spring_count(&self) -> usize {
+        self.springs
+            .values()
+            .flat_map(|springs| springs.values())
+            .filter(|spring| !spring.is_settled())
+            .count()
     }
 
     /// Get all current animation values
@@ -356,6 +502,144 @@ impl AnimationRuntime {
     }
 }
 
+/// Longest integration step; larger `tick` deltas are subdivided for stability
+const MAX_SPRING_STEP: f32 = 1.0 / 120.0;
+
+/// Physically-based spring driving one animatable property
+///
+/// Each numeric channel of the value (e.g. x/y of a point, RGBA of a color)
+/// is simulated independently with the same stiffness, damping, and mass.
+#[derive(Debug, Clone)]
+pub struct Spring {
+    property: String,
+    /// Current value, used as the template when rebuilding from channels
+    value: AnimatableValue,
+    channels: Vec<GestureSpring>,
+}
+
+impl Spring {
+    /// Create a spring from `from` toward `target`
+    ///
+    /// Returns `None` for value types without continuous channels (bools,
+    /// integers, transforms) or if `from` and `target` differ in type.
+    pub fn new(property: impl Into<String>, from: AnimatableValue, target: AnimatableValue) -> Option<Self> {
+        let start = channels_of(&from)?;
+        let end = channels_of(&target)?;
+        if std::mem::discriminant(&from) != std::mem::discriminant(&target) {
+            return None;
+        }
+
+        let params = SpringParams::default_spring();
+        Some(Self {
+            property: property.into(),
+            value: from,
+            channels: start
+                .into_iter()
+                .zip(end)
+                .map(|(position, target)| GestureSpring::new(position, target).params(params))
+                .collect(),
+        })
+    }
+
+    /// Set stiffness, damping, and mass
+    pub fn params(mut self, params: SpringParams) -> Self {
+        self.channels = self.channels.into_iter().map(|c| c.params(params)).collect();
+        self
+    }
+
+    /// Property this spring animates
+    pub fn property(&self) -> &str {
+        &self.property
+    }
+
+    /// Current value
+    pub fn value(&self) -> AnimatableValue {
+        self.value.clone()
+    }
+
+    /// Value the spring is moving toward
+    pub fn target(&self) -> AnimatableValue {
+        let targets: Vec<f32> = self.channels.iter().map(GestureSpring::target).collect();
+        with_channels(&self.value, &targets)
+    }
+
+    /// Current velocity of each channel (units per second)
+    pub fn velocity(&self) -> Vec<f32> {
+        self.channels.iter().map(GestureSpring::velocity).collect()
+    }
+
+    /// Move toward a new target, keeping the current velocity
+    ///
+    /// Returns `false` if the target's type doesn't match.
+    pub fn set_target(&mut self, target: AnimatableValue) -> bool {
+        if std::mem::discriminant(&target) != std::mem::discriminant(&self.value) {
+            return false;
+        }
+        let Some(targets) = channels_of(&target) else {
+            return false;
+        };
+
+        for (channel, target) in self.channels.iter_mut().zip(targets) {
+            channel.set_target(target);
+        }
+        true
+    }
+
+    /// Advance the simulation by `dt` seconds and return the new value
+    pub fn tick(&mut self, dt: f32) -> AnimatableValue {
+        let steps = (dt / MAX_SPRING_STEP).ceil().max(1.0);
+        let step = dt / steps;
+
+        for _ in 0..steps as usize {
+            for channel in &mut self.channels {
+                channel.update(step);
+            }
+        }
+
+        let positions: Vec<f32> = self.channels.iter().map(GestureSpring::position).collect();
+        self.value = with_channels(&self.value, &positions);
+        self.value.clone()
+    }
+
+    /// Check if every channel has come to rest at its target
+    pub fn is_settled(&self) -> bool {
+        self.channels.iter().all(GestureSpring::is_at_rest)
+    }
+
+    /// Write the current value to an element
+    pub fn apply_to<A: Animatable>(&self, element: &mut A) {
+        let mut values = HashMap::new();
+        values.insert(self.property.clone(), self.value.clone());
+        element.apply_animated_values(&values);
+    }
+}
+
+/// Split a value into independently animated channels
+fn channels_of(value: &AnimatableValue) -> Option<Vec<f32>> {
+    match value {
+        AnimatableValue::Float(v) | AnimatableValue::Radius(v) => Some(vec![*v]),
+        AnimatableValue::Point(p) => Some(vec![p.x, p.y]),
+        AnimatableValue::Size(s) => Some(vec![s.width, s.height]),
+        AnimatableValue::Rect(r) => Some(vec![r.x, r.y, r.width, r.height]),
+        AnimatableValue::Color(c) | AnimatableValue::Radii(c) => Some(c.to_vec()),
+        _ => None,
+    }
+}
+
+/// Rebuild a value of the same type as `template` from channels
+fn with_channels(template: &AnimatableValue, c: &[f32]) -> AnimatableValue {
+    match template {
+        AnimatableValue::Float(_) => AnimatableValue::Float(c[0]),
+        AnimatableValue::Radius(_) => AnimatableValue::Radius(c[0]),
+        AnimatableValue::Point(_) => AnimatableValue::Point(Point2D::new(c[0], c[1])),
+        AnimatableValue::Size(_) => AnimatableValue::Size(Size2D::new(c[0], c[1])),
+        AnimatableValue::Rect(_) => AnimatableValue::Rect(Rect2D::new(c[0], c[1], c[2], c[3])),
+        AnimatableValue::Color(_) => AnimatableValue::Color([c[0], c[1], c[2], c[3]]),
+        AnimatableValue::Radii(_) => AnimatableValue::Radii([c[0], c[1], c[2], c[3]]),
+        other => other.clone(),
+    }
+}
+
 /// Trait for elements that can be animated
 pub trait Animatable {
     /// Get the element ID for animation tracking
@@ -473,4 +757,139 @@ mod tests {
         runtime.update_with_dt(0.0);
         assert!(!runtime.has_active_animations());
     }
+
+    fn float(value: &AnimatableValue) -> f32 {
+        match value {
+            AnimatableValue::Float(v) => *v,
+            other => panic!("expected float, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_spring_converges() {
+        let mut spring = Spring::new(
+            properties::X,
+            AnimatableValue::Point(Point2D::new(0.0, 0.0)),
+            AnimatableValue::Point(Point2D::new(100.0, -40.0)),
+        )
+        .unwrap()
+        .params(SpringParams::snappy());
+
+        for _ in 0..600 {
+            spring.tick(1.0 / 60.0);
+            if spring.is_settled() {
+                break;
+            }
+        }
+
+        assert!(spring.is_settled());
+        assert!(matches!(
+            spring.value(),
+            AnimatableValue::Point(p) if p.x == 100.0 && p.y == -40.0
+        ));
+        assert!(Spring::new("x", AnimatableValue::Float(0.0), AnimatableValue::Bool(true)).is_none());
+        assert!(Spring::new("x", AnimatableValue::Bool(false), AnimatableValue::Bool(true)).is_none());
+    }
+
+    #[test]
+    fn test_spring_retarget_keeps_momentum() {
+        let mut spring = Spring::new("x", AnimatableValue::Float(0.0), AnimatableValue::Float(100.0)).unwrap();
+        for _ in 0..5 {
+            spring.tick(1.0 / 60.0);
+        }
+        let position = float(&spring.value());
+        let velocity = spring.velocity()[0];
+        assert!(velocity > 0.0);
+
+        // Reverse direction: the spring keeps moving forward before turning back
+        assert!(spring.set_target(AnimatableValue::Float(0.0)));
+        assert_eq!(spring.velocity()[0], velocity);
+        let next = float(&spring.tick(1.0 / 60.0));
+        assert!(next > position);
+
+        // A fresh spring at the same position heads straight back
+        let mut fresh = Spring::new("x", AnimatableValue::Float(position), AnimatableValue::Float(0.0)).unwrap();
+        assert!(float(&fresh.tick(1.0 / 60.0)) < position);
+
+        assert!(!spring.set_target(AnimatableValue::Color([0.0; 4])));
+    }
+
+    struct Panel {
+        opacity: f32,
+    }
+
+    impl Animatable for Panel {
+        fn animation_id(&self) -> &str {
+            "panel"
+        }
+
+        fn get_animatable_properties(&self) -> HashMap<String, AnimatableValue> {
+            HashMap::from([(properties::OPACITY.to_string(), AnimatableValue::Float(self.opacity))])
+        }
+
+        fn apply_animated_values(&mut self, values: &HashMap<String, AnimatableValue>) {
+            if let Some(AnimatableValue::Float(v)) = values.get(properties::OPACITY) {
+                self.opacity = *v;
+            }
+        }
+    }
+
+    #[test]
+    fn test_runtime_springs() {
+        let mut runtime = AnimationRuntime::new();
+        let mut panel = Panel { opacity: 0.0 };
+
+        assert!(runtime.spring_element(&panel, properties::OPACITY, AnimatableValue::Float(1.0), SpringParams::snappy()));
+        assert!(runtime.has_active_animations());
+
+        runtime.tick_springs(0.05);
+        runtime.spring("panel", properties::OPACITY).unwrap().apply_to(&mut panel);
+        assert!(panel.opacity > 0.0 && panel.opacity < 1.0);
+
+        for _ in 0..600 {
+            if runtime.tick_springs(1.0 / 60.0).is_empty() {
+                break;
+            }
+        }
+        assert!(!runtime.has_active_animations());
+    }
+
+    #[test]
+    fn test_runtime_keyframes() {
+        use crate::keyframes::LoopMode;
+
+        let mut runtime = AnimationRuntime::new();
+        runtime.play_keyframes(
+            "toast",
+            KeyframeTimeline::new()
+                .key(properties::OPACITY, 0.0, 0.0, Easing::Linear)
+                .key(properties::OPACITY, 0.2, 1.0, Easing::Linear),
+        );
+        runtime.play_keyframes(
+            "spinner",
+            KeyframeTimeline::new()
+                .key(properties::ROTATION, 0.0, 0.0, Easing::Linear)
+                .key(properties::ROTATION, 1.0, 6.0, Easing::Linear)
+                .loop_mode(LoopMode::Loop),
+        );
+
+        runtime.update_with_dt(0.1);
+        assert!(matches!(
+            runtime.get_element_values("toast").get(properties::OPACITY),
+            Some(AnimatableValue::Float(v)) if (v - 0.5).abs() < 1e-4
+        ));
+
+        let updates = runtime.tick_keyframes(0.5);
+        assert_eq!(updates["toast"], vec![(properties::OPACITY, 1.0)]);
+        assert_eq!(runtime.active_count(), 1);
+        assert!(matches!(
+            runtime.get_element_values("toast").get(properties::OPACITY),
+            Some(AnimatableValue::Float(v)) if (v - 1.0).abs() < 1e-4
+        ));
+
+        runtime.update_with_dt(0.1);
+        assert_eq!(runtime.get_element_values("toast").len(), 1);
+        runtime.cancel_element("toast");
+        assert!(runtime.get_element_values("toast").is_empty());
+    }
 }
@@ -2,8 +2,12 @@
 //!
 //! Provides event handling, hit testing, and event dispatch to components.
 
+use crate::gestures::{GestureConfig, GestureRecognizer, SwipeDirection};
+use crate::reactive::{ReactiveState, StateValue};
+use crate::shortcuts::{ShortcutMatch, ShortcutRegistry};
 use oxide_layout::{ComputedRect, LayoutTree, NodeId};
 use std::collections::HashMap;
+use std::time::Instant;
 
 /// Mouse button types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -40,10 +44,32 @@ pub enum UiEvent {
     KeyUp { key: String, modifiers: Modifiers },
     /// Text input while component has focus
     TextInput { text: String },
+    /// Pointer moved past the drag threshold while pressed (at the press position)
+    DragStart { x: f32, y: f32, button: MouseButton },
+    /// Pointer moved during a drag; `dx`/`dy` are relative to the drag start
+    DragMove { x: f32, y: f32, dx: f32, dy: f32 },
+    /// Pointer released after a drag
+    DragEnd { x: f32, y: f32, dx: f32, dy: f32 },
+    /// Pointer held without moving for the long-press duration
+    LongPress { x: f32, y: f32 },
+    /// Fast drag released in a direction (velocity in pixels per second)
+    Swipe { direction: SwipeDirection, velocity: f32 },
+}
+
+impl UiEvent {
+    /// Whether the event bubbles to ancestors after reaching its target
+    ///
+    /// Enter/leave and focus/blur only concern the target itself.
+    pub fn bubbles(&self) -> bool {
+        !matches!(
+            self,
+            UiEvent::MouseEnter { .. } | UiEvent::MouseLeave | UiEvent::Focus | UiEvent::Blur
+        )
+    }
 }
 
 /// Keyboard modifiers
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub struct Modifiers {
     pub shift: bool,
     pub ctrl: bool,
@@ -61,6 +87,8 @@ pub struct EventHandler {
     pub event_type: EventType,
     /// The action to perform (stored as string for now, will be evaluated)
     pub action: HandlerAction,
+    /// Run during the capture phase (root to target) instead of bubbling
+    pub capture: bool,
 }
 
 /// Types of events that can be handled
@@ -78,6 +106,11 @@ pub enum EventType {
     KeyDown,
     KeyUp,
     TextInput,
+    DragStart,
+    DragMove,
+    DragEnd,
+    LongPress,
+    Swipe,
 }
 
 /// Action to perform when event fires
@@ -93,10 +126,84 @@ pub enum HandlerAction {
     FunctionCall { name: String, args: Vec<ActionValue> },
     /// Navigate to a route
     Navigate { path: String },
+    /// Several actions run in order (`a; b`)
+    Sequence(Vec<HandlerAction>),
+    /// Run `then` if the condition holds, otherwise `otherwise`
+    Conditional {
+        condition: HandlerCondition,
+        then: Box<HandlerAction>,
+        otherwise: Option<Box<HandlerAction>>,
+    },
+    /// Stop the event from reaching further nodes (`stopPropagation()`)
+    StopPropagation,
+    /// Suppress the runtime's default behavior (`preventDefault()`)
+    PreventDefault,
     /// Raw expression (for complex cases)
     Raw(String),
 }
 
+/// Comparison used by a handler condition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConditionOp {
+    /// `if state.field`
+    Truthy,
+    /// `if !state.field`
+    Falsy,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// Condition on a state field, e.g. `state.count > 3`
+#[derive(Debug, Clone)]
+pub struct HandlerCondition {
+    /// State field to test
+    pub field: String,
+    /// Comparison to perform
+    pub op: ConditionOp,
+    /// Right-hand side (absent for truthiness checks)
+    pub value: Option<ActionValue>,
+}
+
+impl HandlerCondition {
+    /// Evaluate the condition against the current state
+    ///
+    /// Missing fields are treated as null.
+    pub fn evaluate(&self, state: &ReactiveState) -> bool {
+        let current = state.get(&self.field).cloned().unwrap_or(StateValue::Null);
+        let truthy = current.as_bool().unwrap_or(true);
+
+        let Some(value) = &self.value else {
+            return match self.op {
+                ConditionOp::Falsy => !truthy,
+                _ => truthy,
+            };
+        };
+
+        let ordering = match value {
+            ActionValue::Number(n) => current.as_number().and_then(|c| c.partial_cmp(n)),
+            ActionValue::Bool(b) => current.as_bool().map(|c| c.cmp(b)),
+            ActionValue::String(s) => Some(current.to_string_value().as_str().cmp(s.as_str())),
+        };
+
+        match (self.op, ordering) {
+            (ConditionOp::Truthy, _) => truthy,
+            (ConditionOp::Falsy, _) => !truthy,
+            (ConditionOp::Ne, None) => true,
+            (_, None) => false,
+            (ConditionOp::Eq, Some(o)) => o.is_eq(),
+            (ConditionOp::Ne, Some(o)) => o.is_ne(),
+            (ConditionOp::Lt, Some(o)) => o.is_lt(),
+            (ConditionOp::Le, Some(o)) => o.is_le(),
+            (ConditionOp::Gt, Some(o)) => o.is_gt(),
+            (ConditionOp::Ge, Some(o)) => o.is_ge(),
+        }
+    }
+}
+
 /// Mutation operations
 #[derive(Debug, Clone, Copy)]
 pub enum MutationOp {
@@ -116,6 +223,62 @@ pub enum ActionValue {
     Bool(bool),
 }
 
+/// Phase of event propagation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventPhase {
+    /// Travelling from the root down to the target's parent
+    Capture,
+    /// At the target node
+    Target,
+    /// Travelling from the target's parent back up to the root
+    Bubble,
+}
+
+/// Propagation state for one event, passed to each handler it reaches
+#[derive(Debug, Clone)]
+pub struct EventContext {
+    /// Node the event was dispatched to
+    pub target: NodeId,
+    /// Node whose handler is currently running
+    pub current_target: NodeId,
+    /// Current propagation phase
+    pub phase: EventPhase,
+    propagation_stopped: bool,
+    default_prevented: bool,
+}
+
+impl EventContext {
+    fn new(target: NodeId) -> Self {
+        Self {
+            target,
+            current_target: target,
+            phase: EventPhase::Target,
+            propagation_stopped: false,
+            default_prevented: false,
+        }
+    }
+
+    /// Stop the event after the current node's handlers have run
+    pub fn stop_propagation(&mut self) {
+        self.propagation_stopped = true;
+    }
+
+    /// Ask the runtime to skip its default behavior for this event
+    pub fn prevent_default(&mut self) {
+        self.default_prevented = true;
+    }
+
+    /// Check if a handler stopped propagation
+    pub fn is_propagation_stopped(&self) -> bool {
+        self.propagation_stopped
+    }
+
+    /// Check if a handler prevented the default behavior
+    pub fn is_default_prevented(&self) -> bool {
+        self.default_prevented
+    }
+}
+
 /// Interactive state for a node
 #[derive(Debug, Clone, Default)]
 pub struct InteractiveState {
@@ -151,6 +314,12 @@ pub struct EventManager {
     double_click_threshold_ms: u64,
     /// Double-click distance threshold in pixels
     double_click_distance: f32,
+    /// Drag, long-press, and swipe recognition
+    gestures: GestureRecognizer,
+    /// Registered keyboard shortcuts
+    pub shortcuts: ShortcutRegistry,
+    /// Shortcut actions triggered since the last `take_shortcut_actions`
+    shortcut_actions: Vec<String>,
 }
 
 impl Default for EventManager {
@@ -172,9 +341,22 @@ impl EventManager {
             last_click_pos: (0.0, 0.0),
             double_click_threshold_ms: 500,
             double_click_distance: 5.0,
+            gestures: GestureRecognizer::default(),
+            shortcuts: ShortcutRegistry::new(),
+            shortcut_actions: Vec::new(),
         }
     }
 
+    /// Set the gesture recognition thresholds
+    pub fn set_gesture_config(&mut self, config: GestureConfig) {
+        self.gestures.set_config(config);
+    }
+
+    /// Emit time-based gestures (long press); call once per frame
+    pub fn poll_gestures(&mut self) -> Vec<(NodeId, UiEvent)> {
+        self.gestures.poll(Instant::now())
+    }
+
     /// Register an event handler for a node
     pub fn register_handler(&mut self, node: NodeId, handler: EventHandler) {
         tracing::info!("Registering {:?} handler for node {:?}", handler.event_type, node);
@@ -196,7 +378,7 @@ impl EventManager {
         let old_position = self.mouse_position;
         self.mouse_position = (x, y);
 
-        let mut events = Vec::new();
+        let mut events = self.gestures.move_to(x, y, Instant::now());
 
         // Find node under mouse
         let hit_node = self.hit_test(x, y, tree, root);
@@ -238,44 +420,80 @@ impl EventManager {
             // Update pressed state
             self.pressed_node = Some(node);
             self.interactive_states.entry(node).or_default().pressed = true;
+            self.gestures.press(node, x, y, button, Instant::now());
 
+            // Focus moves in `default_actions`, unless a handler prevents it
             events.push((node, UiEvent::MouseDown { x, y, button }));
+        }
 
-            // Handle focus
-            if self.focused_node != Some(node) {
-                // Blur old focused node
-                if let Some(old_focus) = self.focused_node {
-                    if let Some(state) = self.interactive_states.get_mut(&old_focus) {
-                        state.focused = false;
-                    }
-                    events.push((old_focus, UiEvent::Blur));
-                }
+        events
+    }
+
+    /// Move focus to a node, returning the blur and focus events
+    pub fn focus(&mut self, node: NodeId) -> Vec<(NodeId, UiEvent)> {
+        let mut events = Vec::new();
+        if self.focused_node == Some(node) {
+            return events;
+        }
 
-                // Focus new node
-                self.focused_node = Some(node);
-                self.interactive_states.entry(node).or_default().focused = true;
-                events.push((node, UiEvent::Focus));
+        // Blur old focused node
+        if let Some(old_focus) = self.focused_node {
+            if let Some(state) = self.interactive_states.get_mut(&old_focus) {
+                state.focused = false;
             }
+            events.push((old_focus, UiEvent::Blur));
         }
 
+        // Focus new node
+        self.focused_node = Some(node);
+        self.interactive_states.entry(node).or_default().focused = true;
+        events.push((node, UiEvent::Focus));
         events
     }
 
+    /// Run the default behavior of dispatched events
+    ///
+    /// `contexts` are the results of [`dispatch_events`](Self::dispatch_events)
+    /// for `events`. Events whose handlers called `prevent_default` are
+    /// skipped. Returns the follow-up events (focus changes from mouse down).
+    pub fn default_actions(
+        &mut self,
+        events: &[(NodeId, UiEvent)],
+        contexts: &[EventContext],
+    ) -> Vec<(NodeId, UiEvent)> {
+        let mut follow_up = Vec::new();
+        for ((node, event), ctx) in events.iter().zip(contexts) {
+            if ctx.is_default_prevented() {
+                continue;
+            }
+            if let UiEvent::MouseDown { .. } = event {
+                follow_up.extend(self.focus(*node));
+            }
+        }
+        follow_up
+    }
+
     /// Handle mouse button up event
     pub fn on_mouse_up(&mut self, x: f32, y: f32, button: MouseButton, tree: &LayoutTree, root: NodeId) -> Vec<(NodeId, UiEvent)> {
         let mut events = Vec::new();
 
         let hit_node = self.hit_test(x, y, tree, root);
 
+        // A drag or long press replaces the click
+        let suppress_click = self.gestures.suppresses_click();
+        let gesture_events = self.gestures.release(x, y, Instant::now());
+        let suppress_click = suppress_click || !gesture_events.is_empty();
+
         // Clear pressed state
         if let Some(pressed) = self.pressed_node.take() {
             if let Some(state) = self.interactive_states.get_mut(&pressed) {
                 state.pressed = false;
             }
             events.push((pressed, UiEvent::MouseUp { x, y, button }));
+            events.extend(gesture_events);
 
             // If released over the same node that was pressed, it's a click
-            if hit_node == Some(pressed) {
+            if hit_node == Some(pressed) && !suppress_click {
                 // Check for double-click
                 let now = std::time::Instant::now();
                 let time_diff = now.duration_since(self.last_click_time).as_millis() as u64;
@@ -295,10 +513,28 @@ impl EventManager {
         events
     }
 
+    /// Take shortcut actions triggered by key presses
+    pub fn take_shortcut_actions(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.shortcut_actions)
+    }
+
     /// Handle keyboard events
+    ///
+    /// Keys that complete or continue a registered shortcut are consumed and
+    /// not delivered to the focused node.
     pub fn on_key_down(&mut self, key: String, modifiers: Modifiers) -> Vec<(NodeId, UiEvent)> {
         let mut events = Vec::new();
 
+        match self.shortcuts.match_key(&key, modifiers, Instant::now()) {
+            ShortcutMatch::Matched(action) => {
+                tracing::debug!("Shortcut triggered: {}", action);
+                self.shortcut_actions.push(action);
+                return events;
+            }
+            ShortcutMatch::Pending => return events,
+            ShortcutMatch::None => {}
+        }
+
         if let Some(focused) = self.focused_node {
             events.push((focused, UiEvent::KeyDown { key, modifiers }));
         }
@@ -329,39 +565,32 @@ impl EventManager {
     }
 
     /// Perform hit testing to find the node at a given position
-    /// Returns the best node for interaction (prefers nodes with handlers)
+    ///
+    /// The topmost node under the point wins (see [`LayoutTree::hit_test`]),
+    /// so an overlay blocks whatever it covers. The result is then resolved to
+    /// the nearest node (itself or an ancestor) with handlers, so hovering a
+    /// button's label targets the button.
     pub fn hit_test(&self, x: f32, y: f32, tree: &LayoutTree, root: NodeId) -> Option<NodeId> {
-        let mut hit_node: Option<NodeId> = None;
-        let mut hit_node_with_handler: Option<NodeId> = None;
-        let mut nodes_hit = Vec::new();
-
-        // Traverse tree to find all nodes containing the point
-        // Track both any hit node and specifically nodes with handlers
-        tree.traverse(root, |node, rect, _visual| {
-            if self.point_in_rect(x, y, &rect) {
-                let has_handler = self.handlers.contains_key(&node);
-                nodes_hit.push((node, has_handler));
-                hit_node = Some(node);
-                // Prefer nodes that have handlers (event bubbling simulation)
-                if has_handler {
-                    hit_node_with_handler = Some(node);
-                }
-            }
-        });
+        let topmost = tree.hit_test(x, y, root)?;
+        let result = self
+            .propagation_path(topmost, tree)
+            .into_iter()
+            .rev()
+            .find(|node| self.handlers.contains_key(node))
+            .unwrap_or(topmost);
 
-        let result = hit_node_with_handler.or(hit_node);
         tracing::info!(
-            "hit_test({:.0},{:.0}): {} nodes hit, result={:?}, with_handlers={}",
+            "hit_test({:.0},{:.0}): topmost={:?}, result={:?}",
             x, y,
-            nodes_hit.len(),
-            result,
-            nodes_hit.iter().filter(|(_, h)| *h).count()
+            topmost,
+            result
         );
 
-        result
+        Some(result)
     }
 
     /// Check if a point is inside a rectangle
+    #[cfg_attr(not(test), allow(dead_code))]
     fn point_in_rect(&self, x: f32, y: f32, rect: &ComputedRect) -> bool {
         x >= rect.x && x < rect.x + rect.width && y >= rect.y && y < rect.y + rect.height
     }
@@ -371,23 +600,87 @@ impl EventManager {
         self.handlers.get(&node)
     }
 
-    /// Dispatch events to their handlers
-    /// Returns a list of actions to execute
-    pub fn dispatch_events(&self, events: &[(NodeId, UiEvent)]) -> Vec<(NodeId, &EventHandler)> {
-        let mut actions = Vec::new();
+    /// Get the nodes an event travels through, from the root down to `target`
+    pub fn propagation_path(&self, target: NodeId, tree: &LayoutTree) -> Vec<NodeId> {
+        let mut path = vec![target];
+        while let Some(parent) = tree.parent(path[path.len() - 1]) {
+            path.push(parent);
+        }
+        path.reverse();
+        path
+    }
+
+    /// Dispatch events through the capture and bubble phases
+    ///
+    /// `run` is called for each matching handler in order: capture handlers
+    /// from the root down, handlers on the target, then bubbling handlers up
+    /// to the root. Once a handler stops propagation, the remaining handlers
+    /// on the current node still run but no further nodes are visited.
+    /// Without a layout tree only the target's handlers run.
+    /// Returns the final context of each event.
+    pub fn dispatch_events<F>(
+        &self,
+        events: &[(NodeId, UiEvent)],
+        tree: Option<&LayoutTree>,
+        mut run: F,
+    ) -> Vec<EventContext>
+    where
+        F: FnMut(&UiEvent, &EventHandler, &mut EventContext),
+    {
+        let mut contexts = Vec::with_capacity(events.len());
+
+        for (target, event) in events {
+            let event_type = event_to_type(event);
+            let path = match tree {
+                Some(tree) => self.propagation_path(*target, tree),
+                None => vec![*target],
+            };
+            let ancestors = &path[..path.len() - 1];
+            let mut ctx = EventContext::new(*target);
 
-        for (node, event) in events {
-            if let Some(handlers) = self.handlers.get(node) {
-                let event_type = event_to_type(event);
-                for handler in handlers {
-                    if handler.event_type == event_type {
-                        actions.push((*node, handler));
-                    }
+            let steps = ancestors
+                .iter()
+                .map(|node| (*node, EventPhase::Capture))
+                .chain(std::iter::once((*target, EventPhase::Target)))
+                .chain(
+                    ancestors
+                        .iter()
+                        .rev()
+                        .filter(|_| event.bubbles())
+                        .map(|node| (*node, EventPhase::Bubble)),
+                );
+
+            for (node, phase) in steps {
+                let Some(handlers) = self.handlers.get(&node) else {
+                    continue;
+                };
+                ctx.current_target = node;
+                ctx.phase = phase;
+
+                // On the target, capture handlers run before bubble handlers
+                let mut matching: Vec<&EventHandler> = handlers
+                    .iter()
+                    .filter(|h| h.event_type == event_type)
+                    .filter(|h| match phase {
+                        EventPhase::Capture => h.capture,
+                        EventPhase::Target => true,
+                        EventPhase::Bubble => !h.capture,
+                    })
+                    .collect();
+                matching.sort_by_key(|h| !h.capture);
+
+                for handler in matching {
+                    run(event, handler, &mut ctx);
+                }
+                if ctx.propagation_stopped {
+                    break;
                 }
             }
+
+            contexts.push(ctx);
         }
 
-        actions
+        contexts
     }
 }
 
@@ -406,6 +699,11 @@ fn event_to_type(event: &UiEvent) -> EventType {
         UiEvent::KeyDown { .. } => EventType::KeyDown,
         UiEvent::KeyUp { .. } => EventType::KeyUp,
         UiEvent::TextInput { .. } => EventType::TextInput,
+        UiEvent::DragStart { .. } => EventType::DragStart,
+        UiEvent::DragMove { .. } => EventType::DragMove,
+        UiEvent::DragEnd { .. } => EventType::DragEnd,
+        UiEvent::LongPress { .. } => EventType::LongPress,
+        UiEvent::Swipe { .. } => EventType::Swipe,
     }
 }
 
@@ -442,4 +740,363 @@ mod tests {
         assert!(!state.pressed);
         assert!(!state.disabled);
     }
+
+    #[test]
+    fn test_drag_suppresses_click() {
+        use oxide_layout::{AvailableSpace, Size, StyleBuilder};
+
+        let mut tree = LayoutTree::new();
+        let root = tree.new_node(StyleBuilder::new().size(400.0, 300.0).build());
+        tree.compute_layout(
+            root,
+            Size {
+                width: AvailableSpace::Definite(400.0),
+                height: AvailableSpace::Definite(300.0),
+            },
+        );
+
+        let mut manager = EventManager::new();
+        manager.register_handler(
+            root,
+            EventHandler {
+                event_type: EventType::DragEnd,
+                action: HandlerAction::Raw("dropped".into()),
+                capture: false,
+            },
+        );
+
+        manager.on_mouse_down(50.0, 50.0, MouseButton::Left, &tree, root);
+        let moved = manager.on_mouse_move(80.0, 60.0, &tree, root);
+        let released = manager.on_mouse_up(120.0, 70.0, MouseButton::Left, &tree, root);
+
+        assert!(moved
+            .iter()
+            .any(|(_, e)| matches!(e, UiEvent::DragStart { x, y, .. } if *x == 50.0 && *y == 50.0)));
+        assert!(moved
+            .iter()
+            .any(|(_, e)| matches!(e, UiEvent::DragMove { dx, dy, .. } if *dx == 30.0 && *dy == 10.0)));
+        assert!(released
+            .iter()
+            .any(|(_, e)| matches!(e, UiEvent::DragEnd { dx, .. } if *dx == 70.0)));
+        assert!(!released
+            .iter()
+            .any(|(_, e)| matches!(e, UiEvent::Click { .. } | UiEvent::DoubleClick { .. })));
+        let mut handled = 0;
+        manager.dispatch_events(&released, Some(&tree), |_, _, _| handled += 1);
+        assert_eq!(handled, 1);
+
+        // A plain press/release still clicks
+        manager.on_mouse_down(50.0, 50.0, MouseButton::Left, &tree, root);
+        let released = manager.on_mouse_up(51.0, 50.0, MouseButton::Left, &tree, root);
+        assert!(released
+            .iter()
+            .any(|(_, e)| matches!(e, UiEvent::Click { .. } | UiEvent::DoubleClick { .. })));
+    }
+
+    /// root > parent > child, with `(node, label, capture, stop)` click handlers
+    fn nested_tree(handlers: &[(usize, &str, bool, bool)]) -> (EventManager, LayoutTree, [NodeId; 3]) {
+        use oxide_layout::StyleBuilder;
+
+        let mut tree = LayoutTree::new();
+        let child = tree.new_node(StyleBuilder::new().size(10.0, 10.0).build());
+        let parent = tree.new_node_with_children(StyleBuilder::new().build(), &[child]);
+        let root = tree.new_node_with_children(StyleBuilder::new().build(), &[parent]);
+        let nodes = [root, parent, child];
+
+        let mut manager = EventManager::new();
+        for (index, label, capture, stop) in handlers {
+            let log = HandlerAction::Raw(label.to_string());
+            let action = if *stop {
+                HandlerAction::Sequence(vec![log, HandlerAction::StopPropagation])
+            } else {
+                log
+            };
+            manager.register_handler(
+                nodes[*index],
+                EventHandler {
+                    event_type: EventType::Click,
+                    action,
+                    capture: *capture,
+                },
+            );
+        }
+        (manager, tree, nodes)
+    }
+
+    /// Dispatch a click on `target`, returning handler labels in call order
+    fn click(manager: &EventManager, tree: &LayoutTree, target: NodeId) -> Vec<String> {
+        let event = UiEvent::Click { x: 0.0, y: 0.0, button: MouseButton::Left };
+        let mut calls = Vec::new();
+        manager.dispatch_events(&[(target, event)], Some(tree), |_, handler, ctx| {
+            let actions = match &handler.action {
+                HandlerAction::Sequence(actions) => actions.clone(),
+                action => vec![action.clone()],
+            };
+            for action in actions {
+                match action {
+                    HandlerAction::Raw(label) => calls.push(label),
+                    HandlerAction::StopPropagation => ctx.stop_propagation(),
+                    _ => {}
+                }
+            }
+        });
+        calls
+    }
+
+    #[test]
+    fn test_parent_handler_fires_on_bubble() {
+        let (manager, tree, [root, parent, child]) = nested_tree(&[
+            (0, "root", false, false),
+            (1, "parent", false, false),
+            (2, "child", false, false),
+        ]);
+
+        assert_eq!(manager.propagation_path(child, &tree), vec![root, parent, child]);
+        assert_eq!(click(&manager, &tree, child), ["child", "parent", "root"]);
+        assert_eq!(click(&manager, &tree, parent), ["parent", "root"]);
+    }
+
+    #[test]
+    fn test_capture_runs_before_bubble() {
+        let (manager, tree, [_, _, child]) = nested_tree(&[
+            (0, "root-bubble", false, false),
+            (0, "root-capture", true, false),
+            (1, "parent-capture", true, false),
+            (2, "child", false, false),
+        ]);
+
+        assert_eq!(
+            click(&manager, &tree, child),
+            ["root-capture", "parent-capture", "child", "root-bubble"]
+        );
+    }
+
+    #[test]
+    fn test_stop_propagation() {
+        let (manager, tree, [_, _, child]) = nested_tree(&[
+            (0, "root", false, false),
+            (1, "parent", false, true),
+            (1, "parent-2", false, false),
+            (2, "child", false, false),
+        ]);
+
+        // Other handlers on the stopping node still run; ancestors don't
+        assert_eq!(click(&manager, &tree, child), ["child", "parent", "parent-2"]);
+
+        let (manager, tree, [_, _, child]) = nested_tree(&[
+            (0, "root-capture", true, true),
+            (2, "child", false, false),
+        ]);
+        assert_eq!(click(&manager, &tree, child), ["root-capture"]);
+    }
+
+    #[test]
+    fn test_dispatch_without_layout_tree() {
+        let (manager, _, [_, parent, child]) = nested_tree(&[
+            (1, "parent", false, false),
+            (2, "child", false, false),
+        ]);
+        let event = UiEvent::Click { x: 0.0, y: 0.0, button: MouseButton::Left };
+
+        let mut calls = Vec::new();
+        manager.dispatch_events(&[(child, event.clone()), (parent, event)], None, |_, h, _| {
+            if let HandlerAction::Raw(label) = &h.action {
+                calls.push(label.clone());
+            }
+        });
+        assert_eq!(calls, ["child", "parent"]);
+    }
+
+    #[test]
+    fn test_prevent_default_keeps_focus() {
+        use oxide_layout::{NodeVisual, StyleBuilder};
+
+        let mut tree = LayoutTree::new();
+        let first = tree.new_visual_node(absolute(0.0, 0.0, 50.0, 50.0), NodeVisual::default());
+        let second = tree.new_visual_node(absolute(50.0, 0.0, 50.0, 50.0), NodeVisual::default());
+        let root = tree.new_node_with_children(
+            StyleBuilder::new().size(400.0, 300.0).build(),
+            &[first, second],
+        );
+        layout(&mut tree, root);
+
+        let mut manager = EventManager::new();
+        manager.register_handler(
+            second,
+            EventHandler {
+                event_type: EventType::MouseDown,
+                action: HandlerAction::PreventDefault,
+                capture: false,
+            },
+        );
+        let press = |manager: &mut EventManager, x: f32| {
+            let events = manager.on_mouse_down(x, 25.0, MouseButton::Left, &tree, root);
+            let contexts = manager.dispatch_events(&events, Some(&tree), |_, handler, ctx| {
+                if let HandlerAction::PreventDefault = handler.action {
+                    ctx.prevent_default();
+                }
+            });
+            let follow_up = manager.default_actions(&events, &contexts);
+            manager.on_mouse_up(x, 25.0, MouseButton::Left, &tree, root);
+            (contexts, follow_up)
+        };
+
+        let (contexts, follow_up) = press(&mut manager, 10.0);
+        assert!(!contexts[0].is_default_prevented());
+        assert!(matches!(follow_up.as_slice(), [(node, UiEvent::Focus)] if *node == first));
+        assert_eq!(manager.focused_node, Some(first));
+
+        // The handler on `second` prevents the focus change
+        let (contexts, follow_up) = press(&mut manager, 60.0);
+        assert!(contexts[0].is_default_prevented());
+        assert!(follow_up.is_empty());
+        assert_eq!(manager.focused_node, Some(first));
+        assert!(manager.get_state(first).focused);
+        assert!(!manager.get_state(second).focused);
+    }
+
+    #[test]
+    fn test_enter_and_leave_do_not_bubble() {
+        let (mut manager, tree, [root, _, child]) = nested_tree(&[]);
+        manager.register_handler(
+            root,
+            EventHandler {
+                event_type: EventType::MouseLeave,
+                action: HandlerAction::Raw("leave".into()),
+                capture: false,
+            },
+        );
+
+        let mut handled = 0;
+        let leave = [(child, UiEvent::MouseLeave)];
+        manager.dispatch_events(&leave, Some(&tree), |_, _, _| handled += 1);
+        assert_eq!(handled, 0);
+    }
+
+    fn absolute(x: f32, y: f32, width: f32, height: f32) -> oxide_layout::Style {
+        oxide_layout::StyleBuilder::new()
+            .position_absolute()
+            .left(x)
+            .top(y)
+            .size(width, height)
+            .build()
+    }
+
+    fn layout(tree: &mut LayoutTree, root: NodeId) {
+        use oxide_layout::{AvailableSpace, Size};
+
+        tree.compute_layout(
+            root,
+            Size {
+                width: AvailableSpace::Definite(400.0),
+                height: AvailableSpace::Definite(300.0),
+            },
+        );
+    }
+
+    fn click_handler() -> EventHandler {
+        EventHandler {
+            event_type: EventType::Click,
+            action: HandlerAction::Raw("click".into()),
+            capture: false,
+        }
+    }
+
+    #[test]
+    fn test_overlay_receives_click() {
+        use oxide_layout::{NodeVisual, StyleBuilder};
+
+        let mut tree = LayoutTree::new();
+        let label = tree.new_node(StyleBuilder::new().size(60.0, 20.0).build());
+        let button = tree.new_node_with_children(absolute(10.0, 10.0, 100.0, 40.0), &[label]);
+        let overlay = tree.new_visual_node(absolute(0.0, 0.0, 400.0, 300.0), NodeVisual::default());
+        let root = tree.new_node_with_children(StyleBuilder::new().size(400.0, 300.0).build(), &[button, overlay]);
+        layout(&mut tree, root);
+
+        let mut manager = EventManager::new();
+        manager.register_handler(button, click_handler());
+
+        // The overlay paints above the button, so it takes the click even without handlers
+        assert_eq!(manager.hit_test(20.0, 20.0, &tree, root), Some(overlay));
+        manager.on_mouse_down(20.0, 20.0, MouseButton::Left, &tree, root);
+        let events = manager.on_mouse_up(20.0, 20.0, MouseButton::Left, &tree, root);
+        assert!(events
+            .iter()
+            .any(|(node, e)| *node == overlay && matches!(e, UiEvent::Click { .. } | UiEvent::DoubleClick { .. })));
+        assert!(events.iter().all(|(node, _)| *node != button));
+
+        // Without the overlay, the button's label resolves to the button
+        let mut tree = LayoutTree::new();
+        let label = tree.new_node(StyleBuilder::new().size(60.0, 20.0).build());
+        let button = tree.new_node_with_children(absolute(10.0, 10.0, 100.0, 40.0), &[label]);
+        let root = tree.new_node_with_children(StyleBuilder::new().size(400.0, 300.0).build(), &[button]);
+        layout(&mut tree, root);
+        let mut manager = EventManager::new();
+        manager.register_handler(button, click_handler());
+        assert_eq!(manager.hit_test(20.0, 20.0, &tree, root), Some(button));
+    }
+
+    #[test]
+    fn test_clipped_content_is_not_hit() {
+        use oxide_layout::{NodeVisual, StyleBuilder};
+
+        let mut tree = LayoutTree::new();
+        let content = tree.new_node(StyleBuilder::new().size(100.0, 400.0).flex_shrink(0.0).build());
+        let scroll = tree.new_visual_node_with_children(
+            absolute(0.0, 0.0, 100.0, 100.0),
+            NodeVisual::default().with_clips_children(true),
+            &[content],
+        );
+        let root = tree.new_node_with_children(StyleBuilder::new().size(400.0, 300.0).build(), &[scroll]);
+        layout(&mut tree, root);
+
+        let mut manager = EventManager::new();
+        manager.register_handler(content, click_handler());
+
+        assert_eq!(manager.hit_test(50.0, 50.0, &tree, root), Some(content));
+        // Inside the content's bounds but outside the scroll viewport
+        assert_eq!(manager.hit_test(50.0, 200.0, &tree, root), Some(root));
+    }
+
+    #[test]
+    fn test_hit_test_respects_z_index() {
+        use oxide_layout::{NodeVisual, StyleBuilder};
+
+        let mut tree = LayoutTree::new();
+        let front = tree.new_visual_node(
+            absolute(0.0, 0.0, 100.0, 100.0),
+            NodeVisual::default().with_z_index(10),
+        );
+        let back = tree.new_visual_node(absolute(0.0, 0.0, 100.0, 100.0), NodeVisual::default());
+        let root_style = StyleBuilder::new().size(400.0, 300.0).build();
+        let root = tree.new_node_with_children(root_style, &[front, back]);
+        layout(&mut tree, root);
+
+        let mut manager = EventManager::new();
+        manager.register_handler(back, click_handler());
+
+        // `back` comes later in the tree, but `front` paints above it
+        assert_eq!(tree.hit_test(50.0, 50.0, root), Some(front));
+        assert_eq!(manager.hit_test(50.0, 50.0, &tree, root), Some(front));
+    }
+
+    #[test]
+    fn test_shortcut_consumes_key() {
+        let mut manager = EventManager::new();
+        manager.focused_node = Some(NodeId::from(1u64));
+        manager.shortcuts.register("Ctrl+K Ctrl+B", "toggle-sidebar").unwrap();
+        let ctrl = Modifiers {
+            ctrl: true,
+            ..Default::default()
+        };
+
+        assert!(manager.on_key_down("k".into(), ctrl).is_empty());
+        assert!(manager.on_key_down("b".into(), ctrl).is_empty());
+        assert_eq!(manager.take_shortcut_actions(), vec!["toggle-sidebar"]);
+        assert!(manager.take_shortcut_actions().is_empty());
+
+        // Unbound keys still reach the focused node
+        let events = manager.on_key_down("x".into(), ctrl);
+        assert!(matches!(events[..], [(_, UiEvent::KeyDown { .. })]));
+    }
 }
@@ -0,0 +1,253 @@
+//! Gesture recognition for OxideKit runtime
+//!
+//! Synthesizes drag, long-press, and swipe events from the raw pointer stream.
+//! Timestamps are passed in explicitly so recognition is deterministic.
+
+use crate::events::{MouseButton, UiEvent};
+use oxide_layout::NodeId;
+use std::time::{Duration, Instant};
+
+/// Thresholds used by the gesture recognizer
+#[derive(Debug, Clone)]
+pub struct GestureConfig {
+    /// Distance in pixels the pointer must move before a press becomes a drag
+    pub drag_threshold: f32,
+    /// How long a press must be held (without dragging) to become a long press
+    pub long_press_duration: Duration,
+    /// Minimum drag distance in pixels for a swipe
+    pub swipe_min_distance: f32,
+    /// Minimum average speed in pixels per second for a swipe
+    pub swipe_min_velocity: f32,
+}
+
+impl Default for GestureConfig {
+    fn default() -> Self {
+        Self {
+            drag_threshold: 4.0,
+            long_press_duration: Duration::from_millis(500),
+            swipe_min_distance: 50.0,
+            swipe_min_velocity: 300.0,
+        }
+    }
+}
+
+/// Direction of a swipe gesture
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SwipeDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+/// Pointer press being tracked
+#[derive(Debug, Clone)]
+struct Press {
+    node: NodeId,
+    button: MouseButton,
+    start: (f32, f32),
+    start_time: Instant,
+    dragging: bool,
+    long_pressed: bool,
+}
+
+impl Press {
+    fn delta(&self, x: f32, y: f32) -> (f32, f32) {
+        (x - self.start.0, y - self.start.1)
+    }
+}
+
+/// Recognizes gestures from a press/move/release sequence
+///
+/// Gesture events target the node that received the press, even when the
+/// pointer leaves it during a drag.
+#[derive(Debug, Clone, Default)]
+pub struct GestureRecognizer {
+    config: GestureConfig,
+    press: Option<Press>,
+}
+
+impl GestureRecognizer {
+    /// Create a recognizer with the given thresholds
+    pub fn new(config: GestureConfig) -> Self {
+        Self { config, press: None }
+    }
+
+    /// Get the current thresholds
+    pub fn config(&self) -> &GestureConfig {
+        &self.config
+    }
+
+    /// Replace the thresholds
+    pub fn set_config(&mut self, config: GestureConfig) {
+        self.config = config;
+    }
+
+    /// Start tracking a press on a node
+    pub fn press(&mut self, node: NodeId, x: f32, y: f32, button: MouseButton, now: Instant) {
+        self.press = Some(Press {
+            node,
+            button,
+            start: (x, y),
+            start_time: now,
+            dragging: false,
+            long_pressed: false,
+        });
+    }
+
+    /// Feed a pointer move, returning any drag events
+    pub fn move_to(&mut self, x: f32, y: f32, _now: Instant) -> Vec<(NodeId, UiEvent)> {
+        let mut events = Vec::new();
+        let Some(press) = self.press.as_mut() else {
+            return events;
+        };
+
+        let (dx, dy) = press.delta(x, y);
+        if !press.dragging {
+            if dx.hypot(dy) < self.config.drag_threshold {
+                return events;
+            }
+            press.dragging = true;
+            events.push((
+                press.node,
+                UiEvent::DragStart {
+                    x: press.start.0,
+                    y: press.start.1,
+                    button: press.button,
+                },
+            ));
+        }
+
+        events.push((press.node, UiEvent::DragMove { x, y, dx, dy }));
+        events
+    }
+
+    /// Check for a long press that has been held long enough
+    ///
+    /// Call this periodically (e.g. once per frame) while a press is active.
+    pub fn poll(&mut self, now: Instant) -> Vec<(NodeId, UiEvent)> {
+        let mut events = Vec::new();
+        if let Some(press) = self.press.as_mut() {
+            if !press.dragging
+                && !press.long_pressed
+                && now.duration_since(press.start_time) >= self.config.long_press_duration
+            {
+                press.long_pressed = true;
+                events.push((
+                    press.node,
+                    UiEvent::LongPress {
+                        x: press.start.0,
+                        y: press.start.1,
+                    },
+                ));
+            }
+        }
+        events
+    }
+
+    /// Finish the press, returning drag-end, swipe, or late long-press events
+    pub fn release(&mut self, x: f32, y: f32, now: Instant) -> Vec<(NodeId, UiEvent)> {
+        let mut events = self.poll(now);
+        let Some(press) = self.press.take() else {
+            return events;
+        };
+        if !press.dragging {
+            return events;
+        }
+
+        let (dx, dy) = press.delta(x, y);
+        events.push((press.node, UiEvent::DragEnd { x, y, dx, dy }));
+
+        let distance = dx.hypot(dy);
+        let elapsed = now.duration_since(press.start_time).as_secs_f32().max(0.001);
+        let velocity = distance / elapsed;
+        if distance >= self.config.swipe_min_distance && velocity >= self.config.swipe_min_velocity {
+            let direction = if dx.abs() >= dy.abs() {
+                if dx > 0.0 { SwipeDirection::Right } else { SwipeDirection::Left }
+            } else if dy > 0.0 {
+                SwipeDirection::Down
+            } else {
+                SwipeDirection::Up
+            };
+            events.push((press.node, UiEvent::Swipe { direction, velocity }));
+        }
+
+        events
+    }
+
+    /// Drop the current press without emitting events
+    pub fn cancel(&mut self) {
+        self.press = None;
+    }
+
+    /// Check if a press is being tracked
+    pub fn is_active(&self) -> bool {
+        self.press.is_some()
+    }
+
+    /// Check if the current press has turned into a gesture and should not click
+    pub fn suppresses_click(&self) -> bool {
+        self.press
+            .as_ref()
+            .is_some_and(|press| press.dragging || press.long_pressed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node() -> NodeId {
+        NodeId::from(1u64)
+    }
+
+    #[test]
+    fn test_small_move_is_not_a_drag() {
+        let mut recognizer = GestureRecognizer::default();
+        let t = Instant::now();
+
+        recognizer.press(node(), 10.0, 10.0, MouseButton::Left, t);
+        assert!(recognizer.move_to(12.0, 11.0, t).is_empty());
+        assert!(!recognizer.suppresses_click());
+        assert!(recognizer.release(12.0, 11.0, t).is_empty());
+    }
+
+    #[test]
+    fn test_long_press() {
+        let mut recognizer = GestureRecognizer::default();
+        let t = Instant::now();
+
+        recognizer.press(node(), 5.0, 5.0, MouseButton::Left, t);
+        assert!(recognizer.poll(t + Duration::from_millis(100)).is_empty());
+
+        let events = recognizer.poll(t + Duration::from_millis(600));
+        assert!(matches!(events[..], [(_, UiEvent::LongPress { .. })]));
+        assert!(recognizer.suppresses_click());
+
+        // Fires only once
+        assert!(recognizer.poll(t + Duration::from_millis(900)).is_empty());
+        assert!(recognizer.release(5.0, 5.0, t + Duration::from_millis(1000)).is_empty());
+    }
+
+    #[test]
+    fn test_swipe_direction() {
+        let mut recognizer = GestureRecognizer::default();
+        let t = Instant::now();
+
+        recognizer.press(node(), 200.0, 100.0, MouseButton::Left, t);
+        recognizer.move_to(150.0, 105.0, t + Duration::from_millis(50));
+        let events = recognizer.release(100.0, 110.0, t + Duration::from_millis(100));
+
+        assert!(matches!(events[0].1, UiEvent::DragEnd { dx, .. } if dx == -100.0));
+        assert!(matches!(
+            events[1].1,
+            UiEvent::Swipe { direction: SwipeDirection::Left, .. }
+        ));
+
+        // Same distance, too slow
+        recognizer.press(node(), 200.0, 100.0, MouseButton::Left, t);
+        recognizer.move_to(150.0, 100.0, t + Duration::from_millis(500));
+        let events = recognizer.release(100.0, 100.0, t + Duration::from_secs(2));
+        assert_eq!(events.len(), 1);
+    }
+}
@@ -0,0 +1,262 @@
+//! Input masks for OxideKit text inputs
+//!
+//! A mask formats a text input as the user types (`(555) 123-4567`,
+//! `$1,234.50`, `2024-03-01`) while the input also keeps the raw value
+//! (`5551234567`, `1234.50`, `20240301`).
+
+/// Formatting and validation applied to a text input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputMask {
+    /// Fixed pattern: `#` is a digit, `A` a letter, `*` a letter or digit;
+    /// any other character is a literal inserted automatically
+    Pattern(String),
+    /// Number with thousands grouping, a prefix symbol, and up to `decimals`
+    /// fractional digits
+    Currency { symbol: String, decimals: usize },
+    /// ISO 8601 date (`YYYY-MM-DD`) with month and day range checks
+    IsoDate,
+}
+
+impl InputMask {
+    /// Create a pattern mask like `(###) ###-####`
+    pub fn pattern(pattern: impl Into<String>) -> Self {
+        Self::Pattern(pattern.into())
+    }
+
+    /// Create a currency mask like `$1,234.56`
+    pub fn currency(symbol: impl Into<String>, decimals: usize) -> Self {
+        Self::Currency {
+            symbol: symbol.into(),
+            decimals,
+        }
+    }
+
+    fn pattern_str(&self) -> Option<&str> {
+        match self {
+            Self::Pattern(pattern) => Some(pattern),
+            Self::IsoDate => Some("####-##-##"),
+            Self::Currency { .. } => None,
+        }
+    }
+
+    /// Check if a raw value is allowed (possibly incomplete)
+    pub fn accepts(&self, raw: &str) -> bool {
+        if let Self::Currency { decimals, .. } = self {
+            return accepts_currency(raw, *decimals);
+        }
+
+        let pattern = self.pattern_str().unwrap_or_default();
+        let mut slots = pattern.chars().filter(|c| is_slot(*c));
+        let fits = raw
+            .chars()
+            .all(|c| slots.next().is_some_and(|slot| slot_accepts(slot, c)));
+        fits && (*self != Self::IsoDate || accepts_date(raw))
+    }
+
+    /// Format a raw value for display
+    pub fn format(&self, raw: &str) -> String {
+        self.layout(raw).0
+    }
+
+    /// Extract the raw value from a formatted (or unformatted) string
+    pub fn raw(&self, formatted: &str) -> String {
+        if let Self::Currency { symbol, .. } = self {
+            // Symbols like `kr.` or `Rs.` contain a `.` that isn't the decimal point
+            let trimmed = formatted.trim_start();
+            return trimmed
+                .strip_prefix(symbol.as_str())
+                .unwrap_or(trimmed)
+                .chars()
+                .filter(|c| c.is_ascii_digit() || *c == '.')
+                .collect();
+        }
+
+        let pattern = self.pattern_str().unwrap_or_default();
+        let mut chars = formatted.chars().peekable();
+        let mut raw = String::new();
+        for p in pattern.chars() {
+            let Some(&c) = chars.peek() else {
+                break;
+            };
+            if is_slot(p) {
+                raw.push(c);
+                chars.next();
+            } else if c == p {
+                // Literals are optional so unformatted input also parses
+                chars.next();
+            }
+        }
+        raw
+    }
+
+    /// Caret offset in the formatted text before raw character `raw_index`
+    pub fn caret_offset(&self, raw: &str, raw_index: usize) -> usize {
+        let (formatted, positions) = self.layout(raw);
+        positions
+            .get(raw_index)
+            .copied()
+            .unwrap_or_else(|| formatted.chars().count())
+    }
+
+    /// Number of raw characters before a caret offset in the formatted text
+    pub fn raw_index(&self, formatted: &str, offset: usize) -> usize {
+        let (_, positions) = self.layout(&self.raw(formatted));
+        positions.iter().filter(|p| **p < offset).count()
+    }
+
+    /// Format `raw`, returning the text and the offset of each raw character
+    fn layout(&self, raw: &str) -> (String, Vec<usize>) {
+        let mut text = String::new();
+        let mut positions = Vec::new();
+        let mut len = 0;
+        let mut push = |text: &mut String, c: char, is_raw: bool| {
+            if is_raw {
+                positions.push(len);
+            }
+            text.push(c);
+            len += 1;
+        };
+
+        match self {
+            Self::Currency { symbol, .. } => {
+                if raw.is_empty() {
+                    return (text, positions);
+                }
+                symbol.chars().for_each(|c| push(&mut text, c, false));
+
+                let (int, frac) = match raw.split_once('.') {
+                    Some((int, frac)) => (int, Some(frac)),
+                    None => (raw, None),
+                };
+                let digits = int.chars().count();
+                for (i, c) in int.chars().enumerate() {
+                    if i > 0 && (digits - i) % 3 == 0 {
+                        push(&mut text, ',', false);
+                    }
+                    push(&mut text, c, true);
+                }
+                if let Some(frac) = frac {
+                    push(&mut text, '.', true);
+                    frac.chars().for_each(|c| push(&mut text, c, true));
+                }
+            }
+            _ => {
+                let pattern = self.pattern_str().unwrap_or_default();
+                let mut chars = raw.chars().peekable();
+                for p in pattern.chars() {
+                    if chars.peek().is_none() {
+                        break;
+                    }
+                    if is_slot(p) {
+                        let c = chars.next().unwrap_or_default();
+                        push(&mut text, c, true);
+                    } else {
+                        push(&mut text, p, false);
+                    }
+                }
+            }
+        }
+
+        (text, positions)
+    }
+}
+
+fn is_slot(c: char) -> bool {
+    matches!(c, '#' | 'A' | '*')
+}
+
+fn slot_accepts(slot: char, c: char) -> bool {
+    match slot {
+        '#' => c.is_ascii_digit(),
+        'A' => c.is_alphabetic(),
+        _ => c.is_alphanumeric(),
+    }
+}
+
+fn accepts_currency(raw: &str, decimals: usize) -> bool {
+    let (int, frac) = match raw.split_once('.') {
+        Some((int, frac)) => (int, Some(frac)),
+        None => (raw, None),
+    };
+
+    let int_ok = int.chars().all(|c| c.is_ascii_digit()) && !raw.starts_with('.');
+    let frac_ok = match frac {
+        Some(frac) => decimals > 0 && frac.len() <= decimals && frac.chars().all(|c| c.is_ascii_digit()),
+        None => true,
+    };
+    int_ok && frac_ok
+}
+
+fn accepts_date(raw: &str) -> bool {
+    let digits: Vec<u32> = raw.chars().filter_map(|c| c.to_digit(10)).collect();
+    let field = |start: usize| match digits.get(start..start + 2) {
+        Some([a, b]) => Some(a * 10 + b),
+        _ => None,
+    };
+
+    let month_ok = match (digits.get(4), field(4)) {
+        (_, Some(month)) => (1..=12).contains(&month),
+        (Some(first), None) => *first <= 1,
+        _ => true,
+    };
+    let day_ok = match (digits.get(6), field(6)) {
+        (_, Some(day)) => (1..=31).contains(&day),
+        (Some(first), None) => *first <= 3,
+        _ => true,
+    };
+    month_ok && day_ok
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pattern_format_and_raw() {
+        let mask = InputMask::pattern("(###) ###-####");
+
+        assert_eq!(mask.format(""), "");
+        assert_eq!(mask.format("555"), "(555");
+        assert_eq!(mask.format("5551"), "(555) 1");
+        assert_eq!(mask.raw("(555) 1"), "5551");
+        // Unformatted input parses too
+        assert_eq!(mask.raw("5551234567"), "5551234567");
+
+        assert!(mask.accepts("555"));
+        assert!(!mask.accepts("55a"));
+        assert!(!mask.accepts("55512345678"));
+
+        // Caret after the 4th digit sits past the ") " literal
+        assert_eq!(mask.caret_offset("5551", 3), 6);
+        assert_eq!(mask.raw_index("(555) 1", 6), 3);
+    }
+
+    #[test]
+    fn test_currency_grouping() {
+        let mask = InputMask::currency("$", 2);
+
+        assert_eq!(mask.format("1234567.5"), "$1,234,567.5");
+        assert_eq!(mask.raw("$1,234,567.5"), "1234567.5");
+        assert!(mask.accepts("12.34"));
+        assert!(!mask.accepts("12.345"));
+        assert!(!mask.accepts("1.2.3"));
+        assert!(!InputMask::currency("$", 0).accepts("1."));
+
+        let mask = InputMask::currency("kr.", 2);
+        assert_eq!(mask.format("1234.5"), "kr.1,234.5");
+        assert_eq!(mask.raw("kr.1,234.5"), "1234.5");
+        assert_eq!(InputMask::currency("Rs.", 2).raw("Rs.99"), "99");
+    }
+
+    #[test]
+    fn test_iso_date_ranges() {
+        let mask = InputMask::IsoDate;
+
+        assert_eq!(mask.format("20240301"), "2024-03-01");
+        assert!(mask.accepts("202412"));
+        assert!(!mask.accepts("20242"));
+        assert!(!mask.accepts("202413"));
+        assert!(!mask.accepts("2024124"));
+        assert!(!mask.accepts("20241200"));
+    }
+}
@@ -0,0 +1,255 @@
+//! Keyframe timelines for OxideKit runtime
+//!
+//! A [`KeyframeTimeline`] drives several numeric properties at once, each with
+//! its own keyframes and per-segment easing, e.g. fade in and then slide:
+//!
+//! ```
+//! use oxide_components::animation::Easing;
+//! use oxide_runtime::{anim_properties as properties, KeyframeTimeline};
+//!
+//! let timeline = KeyframeTimeline::new()
+//!     .key(properties::OPACITY, 0.0, 0.0, Easing::Linear)
+//!     .key(properties::OPACITY, 0.3, 1.0, Easing::EaseOut)
+//!     .key(properties::X, 0.3, -20.0, Easing::Linear)
+//!     .key(properties::X, 0.6, 0.0, Easing::EaseOutCubic);
+//! ```
+
+use oxide_components::animation::Easing;
+
+/// Property identifier (see [`crate::animation::properties`])
+pub type PropertyId = &'static str;
+
+/// What happens when a timeline reaches its end
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LoopMode {
+    /// Play once and hold the final values
+    #[default]
+    Once,
+    /// Restart from the beginning
+    Loop,
+    /// Play forward, then backward, repeatedly
+    Alternate,
+}
+
+/// Value of one property at a point in time
+#[derive(Debug, Clone)]
+struct Key {
+    /// Seconds from the start of the timeline
+    time: f32,
+    value: f32,
+    /// Easing for the segment leading into this key
+    easing: Easing,
+}
+
+/// Keyframes for a single property
+#[derive(Debug, Clone)]
+struct Track {
+    property: PropertyId,
+    keys: Vec<Key>,
+}
+
+impl Track {
+    fn sample(&self, time: f32) -> f32 {
+        let first = &self.keys[0];
+        if time <= first.time {
+            return first.value;
+        }
+
+        for pair in self.keys.windows(2) {
+            let (from, to) = (&pair[0], &pair[1]);
+            if time <= to.time {
+                let span = to.time - from.time;
+                if span <= 0.0 {
+                    return to.value;
+                }
+                let t = to.easing.evaluate((time - from.time) / span);
+                return from.value + (to.value - from.value) * t;
+            }
+        }
+
+        self.keys[self.keys.len() - 1].value
+    }
+}
+
+/// Multi-property keyframe animation
+#[derive(Debug, Clone, Default)]
+pub struct KeyframeTimeline {
+    tracks: Vec<Track>,
+    loop_mode: LoopMode,
+    elapsed: f32,
+}
+
+impl KeyframeTimeline {
+    /// Create an empty timeline
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a keyframe: `property` reaches `value` at `time` seconds, eased
+    /// from the previous keyframe with `easing`
+    pub fn key(mut self, property: PropertyId, time: f32, value: f32, easing: Easing) -> Self {
+        let key = Key {
+            time: time.max(0.0),
+            value,
+            easing,
+        };
+
+        match self.tracks.iter_mut().find(|t| t.property == property) {
+            Some(track) => {
+                let index = track.keys.partition_point(|k| k.time <= key.time);
+                track.keys.insert(index, key);
+            }
+            None => self.tracks.push(Track {
+                property,
+                keys: vec![key],
+            }),
+        }
+        self
+    }
+
+    /// Set what happens at the end of the timeline
+    pub fn loop_mode(mut self, mode: LoopMode) -> Self {
+        self.loop_mode = mode;
+        self
+    }
+
+    /// Length of one pass in seconds (time of the last keyframe)
+    pub fn duration(&self) -> f32 {
+        self.tracks
+            .iter()
+            .filter_map(|t| t.keys.last())
+            .map(|k| k.time)
+            .fold(0.0, f32::max)
+    }
+
+    /// Total time played so far
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    /// Check if a non-looping timeline has reached its end
+    pub fn is_finished(&self) -> bool {
+        self.loop_mode == LoopMode::Once && self.elapsed >= self.duration()
+    }
+
+    /// Jump to a time in seconds
+    pub fn seek(&mut self, time: f32) {
+        self.elapsed = time.max(0.0);
+    }
+
+    /// Restart from the beginning
+    pub fn reset(&mut self) {
+        self.elapsed = 0.0;
+    }
+
+    /// Advance by `dt` seconds and return every property's new value
+    pub fn tick(&mut self, dt: f32) -> Vec<(PropertyId, f32)> {
+        self.elapsed += dt.max(0.0);
+        if self.is_finished() {
+            self.elapsed = self.duration();
+        }
+        self.values()
+    }
+
+    /// Current value of every property, in the order they were first keyed
+    pub fn values(&self) -> Vec<(PropertyId, f32)> {
+        let time = self.local_time();
+        self.tracks
+            .iter()
+            .map(|track| (track.property, track.sample(time)))
+            .collect()
+    }
+
+    /// Map elapsed time to a position within one pass
+    fn local_time(&self) -> f32 {
+        let duration = self.duration();
+        if duration <= 0.0 {
+            return 0.0;
+        }
+
+        match self.loop_mode {
+            LoopMode::Once => self.elapsed.min(duration),
+            LoopMode::Loop => self.elapsed % duration,
+            LoopMode::Alternate => {
+                let t = self.elapsed % (duration * 2.0);
+                if t > duration {
+                    duration * 2.0 - t
+                } else {
+                    t
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animation::properties;
+
+    fn fade_and_slide() -> KeyframeTimeline {
+        KeyframeTimeline::new()
+            .key(properties::OPACITY, 0.0, 0.0, Easing::Linear)
+            .key(properties::OPACITY, 1.0, 1.0, Easing::Linear)
+            .key(properties::X, 0.0, -100.0, Easing::Linear)
+            .key(properties::X, 1.0, 0.0, Easing::EaseIn)
+    }
+
+    fn value(values: &[(PropertyId, f32)], property: PropertyId) -> f32 {
+        values.iter().find(|(p, _)| *p == property).unwrap().1
+    }
+
+    #[test]
+    fn test_interpolation_and_completion() {
+        let mut timeline = fade_and_slide();
+        assert_eq!(timeline.duration(), 1.0);
+
+        let at_25 = timeline.tick(0.25);
+        assert!((value(&at_25, properties::OPACITY) - 0.25).abs() < 1e-4);
+        // Eased: slower than linear at the start
+        assert!(value(&at_25, properties::X) < -75.0);
+
+        let at_50 = timeline.tick(0.25);
+        assert!((value(&at_50, properties::OPACITY) - 0.5).abs() < 1e-4);
+        let expected_x = -100.0 + 100.0 * Easing::EaseIn.evaluate(0.5);
+        assert!((value(&at_50, properties::X) - expected_x).abs() < 1e-3);
+
+        let done = timeline.tick(2.0);
+        assert!(timeline.is_finished());
+        assert_eq!(value(&done, properties::OPACITY), 1.0);
+        assert_eq!(value(&done, properties::X), 0.0);
+        assert_eq!(timeline.elapsed(), 1.0);
+    }
+
+    #[test]
+    fn test_sequenced_segments() {
+        // Fade in, then slide
+        let mut timeline = KeyframeTimeline::new()
+            .key(properties::OPACITY, 0.0, 0.0, Easing::Linear)
+            .key(properties::OPACITY, 0.5, 1.0, Easing::Linear)
+            .key(properties::X, 0.5, 40.0, Easing::Linear)
+            .key(properties::X, 1.0, 0.0, Easing::Linear);
+
+        let values = timeline.tick(0.25);
+        assert_eq!(value(&values, properties::X), 40.0);
+
+        let values = timeline.tick(0.5);
+        assert_eq!(value(&values, properties::OPACITY), 1.0);
+        assert!((value(&values, properties::X) - 20.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_looping() {
+        let mut timeline = fade_and_slide().loop_mode(LoopMode::Loop);
+
+        timeline.tick(1.25);
+        assert!(!timeline.is_finished());
+        assert!((value(&timeline.values(), properties::OPACITY) - 0.25).abs() < 1e-4);
+
+        let mut alternate = fade_and_slide().loop_mode(LoopMode::Alternate);
+        let values = alternate.tick(1.25);
+        assert!((value(&values, properties::OPACITY) - 0.75).abs() < 1e-4);
+        let values = alternate.tick(1.0);
+        assert!((value(&values, properties::OPACITY) - 0.25).abs() < 1e-4);
+    }
+}
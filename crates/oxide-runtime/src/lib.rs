@@ -4,13 +4,21 @@
 
 pub mod animation;
 pub mod events;
+pub mod gestures;
+pub mod input_mask;
+pub mod keyframes;
 pub mod reactive;
+pub mod shortcuts;
 pub mod text_input;
 
-pub use animation::{AnimationRuntime, Animatable, properties as anim_properties};
+pub use animation::{AnimationRuntime, Animatable, Spring, properties as anim_properties};
 pub use events::{EventManager, UiEvent, MouseButton, Modifiers, EventHandler, EventType, HandlerAction};
+pub use gestures::{GestureConfig, GestureRecognizer, SwipeDirection};
+pub use input_mask::InputMask;
+pub use keyframes::{KeyframeTimeline, LoopMode, PropertyId};
 pub use reactive::{ReactiveState, StateValue, StateBinding};
-pub use text_input::{TextInputManager, TextInputState};
+pub use shortcuts::{KeyChord, Shortcut, ShortcutMatch, ShortcutParseError, ShortcutRegistry};
+pub use text_input::{Selection, TextInputChange, TextInputManager, TextInputState, TextPosition};
 
 // Re-export file picker for easy access
 pub use oxide_file_picker::{OpenDialog, SaveDialog, DirectoryDialog, FileFilter};
@@ -20,19 +28,37 @@ use oxide_compiler::{compile, ComponentIR, PropertyValue};
 use oxide_layout::{AvailableSpace, LayoutTree, NodeId, NodeVisual, Size, StyleBuilder};
 use oxide_render::{Color, PrimitiveRenderer, RenderContext};
 use oxide_text::{TextRenderer, TextSystem};
-use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::future::Future;
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, Mutex};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
 use wgpu::Surface;
 
+/// Identifier correlating a `CallFunction` command with its response
+pub type CallId = u64;
+
+/// How long a UI function call waits for its response by default
+pub const DEFAULT_CALL_TIMEOUT: Duration = Duration::from_secs(30);
+
 /// Command from UI to backend
 #[derive(Debug, Clone)]
 pub enum AppCommand {
-    /// Call a named function with arguments
-    CallFunction { name: String, args: Vec<events::ActionValue> },
+    /// Call a named function with arguments; answer with [`AppContext::respond`]
+    CallFunction {
+        id: CallId,
+        name: String,
+        args: Vec<events::ActionValue>,
+    },
     /// Navigate to a route
     Navigate { path: String },
+    /// A registered keyboard shortcut was triggered
+    Shortcut { action: String },
     /// Custom command
     Custom(String),
 }
@@ -44,6 +70,70 @@ pub struct StateUpdate {
     pub value: StateValue,
 }
 
+/// Where the response to a function call goes
+enum PendingCall {
+    /// Written to this state key (calls made from UI handlers), until the deadline
+    State { key: String, expires: Instant },
+    /// Resolves a `CallResponse` future; removed when the future is dropped
+    Future(Arc<Mutex<CallSlot>>),
+}
+
+impl PendingCall {
+    fn is_expired(&self, now: Instant) -> bool {
+        matches!(self, PendingCall::State { expires, .. } if *expires <= now)
+    }
+}
+
+type PendingCalls = Mutex<HashMap<CallId, PendingCall>>;
+
+#[derive(Default)]
+struct CallSlot {
+    value: Option<events::ActionValue>,
+    waker: Option<Waker>,
+}
+
+/// Future resolving to the backend's response to a function call
+///
+/// Dropping it (for example when a timeout around it fires) cancels the call,
+/// and a later response is ignored.
+pub struct CallResponse {
+    id: CallId,
+    slot: Arc<Mutex<CallSlot>>,
+    pending: Weak<PendingCalls>,
+}
+
+impl CallResponse {
+    /// Correlation id of the call
+    pub fn id(&self) -> CallId {
+        self.id
+    }
+}
+
+impl Future for CallResponse {
+    type Output = events::ActionValue;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut slot = self.slot.lock().unwrap();
+        match slot.value.take() {
+            Some(value) => Poll::Ready(value),
+            None => {
+                slot.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl Drop for CallResponse {
+    fn drop(&mut self) {
+        if let Some(pending) = self.pending.upgrade() {
+            if let Ok(mut calls) = pending.lock() {
+                calls.remove(&self.id);
+            }
+        }
+    }
+}
+
 /// Shared application context for communication between UI and backend
 pub struct AppContext {
     /// State updates to be applied to UI (backend writes, UI reads)
@@ -52,6 +142,14 @@ pub struct AppContext {
     pub commands: Arc<Mutex<Vec<AppCommand>>>,
     /// Shared state that can be read/written by both
     pub shared_state: Arc<Mutex<HashMap<String, String>>>,
+    /// Typed shared state, stored as JSON values
+    pub typed_state: Arc<Mutex<HashMap<String, serde_json::Value>>>,
+    /// Function calls awaiting a response
+    pending_calls: Arc<PendingCalls>,
+    /// Next call correlation id
+    next_call_id: Arc<AtomicU64>,
+    /// How long a UI function call waits for its response
+    call_timeout: Duration,
 }
 
 impl AppContext {
@@ -60,9 +158,21 @@ impl AppContext {
             state_updates: Arc::new(Mutex::new(Vec::new())),
             commands: Arc::new(Mutex::new(Vec::new())),
             shared_state: Arc::new(Mutex::new(HashMap::new())),
+            typed_state: Arc::new(Mutex::new(HashMap::new())),
+            pending_calls: Arc::new(Mutex::new(HashMap::new())),
+            next_call_id: Arc::new(AtomicU64::new(1)),
+            call_timeout: DEFAULT_CALL_TIMEOUT,
         }
     }
 
+    /// Set how long a UI function call waits for its response
+    ///
+    /// Responses arriving later are ignored.
+    pub fn with_call_timeout(mut self, timeout: Duration) -> Self {
+        self.call_timeout = timeout;
+        self
+    }
+
     /// Push a state update (called from backend)
     pub fn push_state_update(&self, key: impl Into<String>, value: StateValue) {
         if let Ok(mut updates) = self.state_updates.lock() {
@@ -109,6 +219,103 @@ impl AppContext {
     pub fn get_shared(&self, key: &str) -> Option<String> {
         self.shared_state.lock().ok()?.get(key).cloned()
     }
+
+    /// Set a typed shared state value
+    pub fn set_typed<T: Serialize>(&self, key: impl Into<String>, value: &T) -> Result<()> {
+        let value = serde_json::to_value(value)?;
+        if let Ok(mut state) = self.typed_state.lock() {
+            state.insert(key.into(), value);
+        }
+        Ok(())
+    }
+
+    /// Get a typed shared state value
+    ///
+    /// Returns `None` if the key is missing or the stored value does not
+    /// deserialize as `T`.
+    pub fn get_typed<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let value = self.typed_state.lock().ok()?.get(key).cloned()?;
+        serde_json::from_value(value).ok()
+    }
+
+    /// Remove a typed shared state value
+    pub fn remove_typed(&self, key: &str) -> bool {
+        self.typed_state
+            .lock()
+            .map(|mut state| state.remove(key).is_some())
+            .unwrap_or(false)
+    }
+
+    /// Call a backend function from the UI
+    ///
+    /// The response is applied as a state update to `<name>_result`, so
+    /// `Text { content: "{validate_result}" }` renders the result of `validate()`.
+    /// Responses arriving after the call timeout are ignored.
+    pub fn call_function(&self, name: impl Into<String>, args: Vec<events::ActionValue>) -> CallId {
+        let name = name.into();
+        let key = format!("{}_result", name);
+        let expires = Instant::now() + self.call_timeout;
+        self.send_call(name, args, Some(PendingCall::State { key, expires }))
+    }
+
+    /// Call a backend function without waiting for a response
+    pub fn send_function(&self, name: impl Into<String>, args: Vec<events::ActionValue>) -> CallId {
+        self.send_call(name.into(), args, None)
+    }
+
+    /// Call a backend function and await its response
+    pub fn call_and_await(&self, name: impl Into<String>, args: Vec<events::ActionValue>) -> CallResponse {
+        let slot = Arc::new(Mutex::new(CallSlot::default()));
+        let id = self.send_call(name.into(), args, Some(PendingCall::Future(Arc::clone(&slot))));
+        CallResponse {
+            id,
+            slot,
+            pending: Arc::downgrade(&self.pending_calls),
+        }
+    }
+
+    fn send_call(
+        &self,
+        name: String,
+        args: Vec<events::ActionValue>,
+        pending: Option<PendingCall>,
+    ) -> CallId {
+        let id = self.next_call_id.fetch_add(1, Ordering::Relaxed);
+        if let Ok(mut calls) = self.pending_calls.lock() {
+            let now = Instant::now();
+            calls.retain(|_, call| !call.is_expired(now));
+            if let Some(pending) = pending {
+                calls.insert(id, pending);
+            }
+        }
+        self.push_command(AppCommand::CallFunction { id, name, args });
+        id
+    }
+
+    /// Deliver the response to a `CallFunction` command (called from backend)
+    ///
+    /// Returns `false` if the id is unknown, was already answered, timed out,
+    /// or its caller stopped waiting.
+    pub fn respond(&self, id: CallId, value: events::ActionValue) -> bool {
+        let Some(pending) = self.pending_calls.lock().ok().and_then(|mut calls| calls.remove(&id)) else {
+            return false;
+        };
+        if pending.is_expired(Instant::now()) {
+            return false;
+        }
+
+        match pending {
+            PendingCall::State { key, .. } => self.push_state_update(key, value.into()),
+            PendingCall::Future(slot) => {
+                let mut slot = slot.lock().unwrap();
+                slot.value = Some(value);
+                if let Some(waker) = slot.waker.take() {
+                    waker.wake();
+                }
+            }
+        }
+        true
+    }
 }
 
 impl Default for AppContext {
@@ -123,6 +330,10 @@ impl Clone for AppContext {
             state_updates: Arc::clone(&self.state_updates),
             commands: Arc::clone(&self.commands),
             shared_state: Arc::clone(&self.shared_state),
+            typed_state: Arc::clone(&self.typed_state),
+            pending_calls: Arc::clone(&self.pending_calls),
+            next_call_id: Arc::clone(&self.next_call_id),
+            call_timeout: self.call_timeout,
         }
     }
 }
@@ -341,6 +552,9 @@ impl Application {
     }
 }
 
+/// State key holding the current route; changing it rebuilds the UI
+const ROUTE_STATE_KEY: &str = "view";
+
 /// Text element to render
 struct TextElement {
     node_id: NodeId,
@@ -440,6 +654,8 @@ struct AppState {
     reactive_state: ReactiveState,
     /// Last state version (for change detection)
     last_state_version: u64,
+    /// State keys changed since the UI was last refreshed
+    state_changes: Arc<Mutex<Vec<String>>>,
     /// Text input manager for handling text fields
     text_input_manager: TextInputManager,
     /// Current keyboard modifiers state
@@ -472,6 +688,13 @@ impl AppState {
             dev_overlay.log("DEV", "Dev overlay auto-enabled (inspector: true)");
         }
 
+        let mut reactive_state = ReactiveState::new();
+        let state_changes = Arc::new(Mutex::new(Vec::new()));
+        let changes = Arc::clone(&state_changes);
+        reactive_state.on_change(move |key, _| {
+            changes.lock().unwrap().push(key.to_string());
+        });
+
         Self {
             manifest,
             ui_ir,
@@ -491,8 +714,9 @@ impl AppState {
             node_count: 0,
             prev_node_count: 0,
             event_manager: EventManager::new(),
-            reactive_state: ReactiveState::new(),
+            reactive_state,
             last_state_version: 0,
+            state_changes,
             text_input_manager: TextInputManager::new(),
             keyboard_modifiers: KeyboardModifiers::default(),
             app_context,
@@ -516,7 +740,7 @@ impl AppState {
             let mut event_manager = EventManager::new();
 
             // Get current view from reactive state
-            let current_view = self.reactive_state.get("view")
+            let current_view = self.reactive_state.get(ROUTE_STATE_KEY)
                 .and_then(|v| v.as_string())
                 .map(|s| s.to_string());
             let view_ref = current_view.as_deref();
@@ -789,18 +1013,65 @@ impl AppState {
             return;
         };
 
-        let mut needs_rebuild = false;
         for update in updates {
             tracing::debug!("Applying state update: {} = {:?}", update.key, update.value);
             self.dev_overlay.log("UPDATE", format!("{} = {}", update.key, update.value.to_string_value()));
             self.reactive_state.set(&update.key, update.value);
-            needs_rebuild = true;
         }
 
-        // If state changed, rebuild UI to reflect text content changes
-        if needs_rebuild {
+        self.refresh_ui();
+    }
+
+    /// Bring the UI up to date with state keys changed since the last refresh
+    ///
+    /// Route changes rebuild the whole tree. Other changes only re-measure the
+    /// text elements bound to the changed keys and recompute layout, keeping
+    /// existing nodes and event handlers.
+    fn refresh_ui(&mut self) {
+        let changed = std::mem::take(&mut *self.state_changes.lock().unwrap());
+        if changed.is_empty() {
+            return;
+        }
+        self.last_state_version = self.reactive_state.version();
+
+        if changed.iter().any(|key| key == ROUTE_STATE_KEY) {
+            tracing::info!("Route changed, rebuilding UI (version {})", self.last_state_version);
             self.build_ui();
             self.compute_layout();
+            return;
+        }
+
+        let mut updated = 0;
+        for text_elem in &mut self.text_elements {
+            let Some(key) = text_elem.binding.as_deref().map(binding_key) else {
+                continue;
+            };
+            if !changed
+                .iter()
+                .any(|c| reactive::depends_on(key, c) || reactive::depends_on(c, key))
+            {
+                continue;
+            }
+
+            let content = self
+                .reactive_state
+                .get(key)
+                .map(StateValue::to_string_value)
+                .unwrap_or_default();
+            let (width, height) = match &mut self.text_system {
+                Some(text_system) => text_system.measure_text(&content, text_elem.size),
+                None => estimate_text_size(&content, text_elem.size),
+            };
+            if let Some(tree) = &mut self.layout_tree {
+                tree.set_style(text_elem.node_id, StyleBuilder::new().size(width, height).build());
+            }
+            text_elem.content = content;
+            updated += 1;
+        }
+
+        if updated > 0 {
+            tracing::debug!("Updated {} bound text element(s) in place", updated);
+            self.compute_layout();
         }
     }
 
@@ -899,10 +1170,9 @@ impl AppState {
                 let content = if let Some(var) = &text_elem.binding {
                     // Get value from reactive state
                     self.reactive_state
-                        .get(var)
-                        .and_then(|v| v.as_string())
-                        .unwrap_or(&text_elem.content)
-                        .to_string()
+                        .get(binding_key(var))
+                        .map(StateValue::to_string_value)
+                        .unwrap_or_else(|| text_elem.content.clone())
                 } else {
                     text_elem.content.clone()
                 };
@@ -1225,13 +1495,14 @@ fn render_debug_overlay(tree: &LayoutTree, root: NodeId, renderer: &mut Primitiv
         let w = rect.width * scale;
         let h = rect.height * scale;
 
-        // Get style for this node to check padding/margin
-        if let Some(style) = tree.get_style(node) {
+        // Use the computed padding/margin so percentages and auto are resolved
+        if tree.get_style(node).is_some() {
             // Draw margin area (outer)
-            let margin_top = resolve_length(&style.margin.top) * scale;
-            let margin_right = resolve_length(&style.margin.right) * scale;
-            let margin_bottom = resolve_length(&style.margin.bottom) * scale;
-            let margin_left = resolve_length(&style.margin.left) * scale;
+            let margin = tree.resolved_margin(node);
+            let margin_top = margin.top * scale;
+            let margin_right = margin.right * scale;
+            let margin_bottom = margin.bottom * scale;
+            let margin_left = margin.left * scale;
 
             if margin_top > 0.0 || margin_right > 0.0 || margin_bottom > 0.0 || margin_left > 0.0 {
                 // Draw margin as outer highlight
@@ -1246,10 +1517,11 @@ fn render_debug_overlay(tree: &LayoutTree, root: NodeId, renderer: &mut Primitiv
             }
 
             // Draw padding area (inner)
-            let pad_top = resolve_length_percent(&style.padding.top) * scale;
-            let pad_right = resolve_length_percent(&style.padding.right) * scale;
-            let pad_bottom = resolve_length_percent(&style.padding.bottom) * scale;
-            let pad_left = resolve_length_percent(&style.padding.left) * scale;
+            let padding = tree.resolved_padding(node);
+            let pad_top = padding.top * scale;
+            let pad_right = padding.right * scale;
+            let pad_bottom = padding.bottom * scale;
+            let pad_left = padding.left * scale;
 
             if pad_top > 0.0 || pad_right > 0.0 || pad_bottom > 0.0 || pad_left > 0.0 {
                 renderer.rect(x, y, w, pad_top, padding_color); // Top
@@ -1283,22 +1555,6 @@ fn render_debug_overlay(tree: &LayoutTree, root: NodeId, renderer: &mut Primitiv
     });
 }
 
-/// Helper to resolve LengthPercentageAuto to f32
-fn resolve_length(value: &oxide_layout::LengthPercentageAuto) -> f32 {
-    match value {
-        oxide_layout::LengthPercentageAuto::Length(l) => *l,
-        _ => 0.0,
-    }
-}
-
-/// Helper to resolve LengthPercentage to f32
-fn resolve_length_percent(value: &oxide_layout::LengthPercentage) -> f32 {
-    match value {
-        oxide_layout::LengthPercentage::Length(l) => *l,
-        _ => 0.0,
-    }
-}
-
 /// Convert hex color string to RGBA array
 fn hex_to_rgba(hex: &str) -> [f32; 4] {
     Color::from_hex(hex)
@@ -1417,8 +1673,18 @@ fn build_from_ir_with_measurement(
 /// Register event handlers for a node from IR
 fn register_handlers(node: NodeId, ir: &ComponentIR, event_manager: &mut EventManager) {
     for handler_ir in &ir.handlers {
+        // `click.capture` / `clickcapture` run during the capture phase
+        let event = handler_ir.event.to_lowercase();
+        let (event, capture) = match event
+            .strip_suffix(".capture")
+            .or_else(|| event.strip_suffix("capture"))
+        {
+            Some(event) => (event, true),
+            None => (event.as_str(), false),
+        };
+
         // Convert event string to EventType
-        let event_type = match handler_ir.event.to_lowercase().as_str() {
+        let event_type = match event {
             "click" => events::EventType::Click,
             "doubleclick" | "dblclick" => events::EventType::DoubleClick,
             "mousedown" => events::EventType::MouseDown,
@@ -1431,6 +1697,11 @@ fn register_handlers(node: NodeId, ir: &ComponentIR, event_manager: &mut EventMa
             "keydown" => events::EventType::KeyDown,
             "keyup" => events::EventType::KeyUp,
             "input" => events::EventType::TextInput,
+            "dragstart" => events::EventType::DragStart,
+            "dragmove" | "drag" => events::EventType::DragMove,
+            "dragend" | "drop" => events::EventType::DragEnd,
+            "longpress" => events::EventType::LongPress,
+            "swipe" => events::EventType::Swipe,
             _ => {
                 tracing::warn!("Unknown event type: {}", handler_ir.event);
                 continue;
@@ -1445,6 +1716,7 @@ fn register_handlers(node: NodeId, ir: &ComponentIR, event_manager: &mut EventMa
             events::EventHandler {
                 event_type,
                 action,
+                capture,
             },
         );
 
@@ -1458,9 +1730,180 @@ fn register_handlers(node: NodeId, ir: &ComponentIR, event_manager: &mut EventMa
 }
 
 /// Parse a handler expression string into a HandlerAction
+///
+/// Supports `;`-separated statements and `if <cond> { ... } else { ... }`.
 fn parse_handler_action(expr: &str) -> events::HandlerAction {
     let expr = expr.trim();
 
+    // Fast path: a single simple statement
+    if !expr.contains(';') && !is_conditional(expr) {
+        return parse_single_action(expr);
+    }
+
+    let mut actions: Vec<events::HandlerAction> = split_statements(expr)
+        .into_iter()
+        .map(|stmt| {
+            if is_conditional(stmt) {
+                parse_conditional(stmt)
+            } else {
+                parse_single_action(stmt)
+            }
+        })
+        .collect();
+
+    if actions.len() == 1 {
+        actions.remove(0)
+    } else {
+        events::HandlerAction::Sequence(actions)
+    }
+}
+
+fn is_conditional(stmt: &str) -> bool {
+    stmt.starts_with("if ") || stmt.starts_with("if(")
+}
+
+/// Split a handler body into top-level statements
+///
+/// Statements end at `;` or at a closing `}` not followed by `else`.
+fn split_statements(expr: &str) -> Vec<&str> {
+    let mut statements = Vec::new();
+    let mut depth = 0i32;
+    let mut quote: Option<char> = None;
+    let mut start = 0;
+
+    for (i, c) in expr.char_indices() {
+        if let Some(q) = quote {
+            if c == q {
+                quote = None;
+            }
+            continue;
+        }
+
+        match c {
+            '"' | '\'' => quote = Some(c),
+            '{' | '(' => depth += 1,
+            ')' => depth -= 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 && !expr[i + 1..].trim_start().starts_with("else") {
+                    statements.push(expr[start..=i].trim());
+                    start = i + 1;
+                }
+            }
+            ';' if depth == 0 => {
+                statements.push(expr[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    statements.push(expr[start..].trim());
+
+    statements.retain(|s| !s.is_empty());
+    statements
+}
+
+/// Parse `if <cond> { ... } [else { ... } | else if ...]`
+fn parse_conditional(stmt: &str) -> events::HandlerAction {
+    let raw = || events::HandlerAction::Raw(stmt.to_string());
+
+    let rest = stmt[2..].trim_start();
+    let (Some(open), Some(close)) = (rest.find('{'), matching_brace(rest)) else {
+        return raw();
+    };
+    let Some(condition) = parse_condition(&rest[..open]) else {
+        return raw();
+    };
+
+    let then = parse_handler_action(&rest[open + 1..close]);
+    let otherwise = match rest[close + 1..].trim().strip_prefix("else") {
+        None => None,
+        Some(tail) => {
+            let tail = tail.trim();
+            if is_conditional(tail) {
+                Some(parse_conditional(tail))
+            } else if let Some(body) = tail.strip_prefix('{').and_then(|t| t.strip_suffix('}')) {
+                Some(parse_handler_action(body))
+            } else {
+                return raw();
+            }
+        }
+    };
+
+    events::HandlerAction::Conditional {
+        condition,
+        then: Box::new(then),
+        otherwise: otherwise.map(Box::new),
+    }
+}
+
+/// Find the `}` closing the first `{` in `s`
+fn matching_brace(s: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Parse a condition like `state.open`, `!state.open` or `state.count >= 3`
+fn parse_condition(cond: &str) -> Option<events::HandlerCondition> {
+    let cond = cond.trim();
+    let cond = cond
+        .strip_prefix('(')
+        .and_then(|c| c.strip_suffix(')'))
+        .unwrap_or(cond)
+        .trim();
+
+    if let Some(negated) = cond.strip_prefix('!') {
+        let field = negated.trim().strip_prefix("state.")?;
+        return Some(events::HandlerCondition {
+            field: field.trim().to_string(),
+            op: events::ConditionOp::Falsy,
+            value: None,
+        });
+    }
+
+    let operators = [
+        ("==", events::ConditionOp::Eq),
+        ("!=", events::ConditionOp::Ne),
+        ("<=", events::ConditionOp::Le),
+        (">=", events::ConditionOp::Ge),
+        ("<", events::ConditionOp::Lt),
+        (">", events::ConditionOp::Gt),
+    ];
+    for (token, op) in operators {
+        if let Some(pos) = cond.find(token) {
+            let field = cond[..pos].trim().strip_prefix("state.")?;
+            return Some(events::HandlerCondition {
+                field: field.to_string(),
+                op,
+                value: Some(parse_action_value(cond[pos + token.len()..].trim())),
+            });
+        }
+    }
+
+    let field = cond.strip_prefix("state.")?;
+    Some(events::HandlerCondition {
+        field: field.to_string(),
+        op: events::ConditionOp::Truthy,
+        value: None,
+    })
+}
+
+/// Parse a single handler statement
+fn parse_single_action(expr: &str) -> events::HandlerAction {
+    let expr = expr.trim();
+
     // Check for state mutation patterns: state.field += value
     if expr.starts_with("state.") {
         if let Some(action) = parse_state_mutation(expr) {
@@ -1477,6 +1920,13 @@ fn parse_handler_action(expr: &str) -> events::HandlerAction {
         };
     }
 
+    // Propagation control: stopPropagation() / event.preventDefault()
+    match expr.trim_start_matches("event.").trim_end_matches("()") {
+        "stopPropagation" | "stop_propagation" => return events::HandlerAction::StopPropagation,
+        "preventDefault" | "prevent_default" => return events::HandlerAction::PreventDefault,
+        _ => {}
+    }
+
     // Check for function call pattern: funcName(args)
     if let Some(paren_pos) = expr.find('(') {
         if expr.ends_with(')') {
@@ -1527,8 +1977,14 @@ fn parse_state_mutation(expr: &str) -> Option<events::HandlerAction> {
     };
 
     let field = field.trim().to_string();
+    let value = parse_action_value(value_str);
 
-    let value = if let Ok(n) = value_str.parse::<f64>() {
+    Some(events::HandlerAction::StateMutation { field, op, value })
+}
+
+/// Parse a literal value (number, bool, or string)
+fn parse_action_value(value_str: &str) -> events::ActionValue {
+    if let Ok(n) = value_str.parse::<f64>() {
         events::ActionValue::Number(n)
     } else if value_str == "true" {
         events::ActionValue::Bool(true)
@@ -1536,9 +1992,17 @@ fn parse_state_mutation(expr: &str) -> Option<events::HandlerAction> {
         events::ActionValue::Bool(false)
     } else {
         events::ActionValue::String(value_str.trim_matches('"').to_string())
-    };
+    }
+}
 
-    Some(events::HandlerAction::StateMutation { field, op, value })
+/// Estimate text dimensions when no text system is available
+fn estimate_text_size(text: &str, font_size: f32) -> (f32, f32) {
+    (text.len() as f32 * font_size * 0.6, font_size * 1.2)
+}
+
+/// State key for a text binding (`{state.count}` reads `count`)
+fn binding_key(var: &str) -> &str {
+    var.strip_prefix("state.").unwrap_or(var)
 }
 
 /// Build layout tree from IR (fallback without measurement)
@@ -1595,8 +2059,7 @@ fn build_from_ir(
 
         // Estimate text dimensions (fallback)
         let measure_text = if binding.is_some() { "0.00".to_string() } else { content.clone() };
-        let estimated_width = measure_text.len() as f32 * font_size * 0.6;
-        let estimated_height = font_size * 1.2;
+        let (estimated_width, estimated_height) = estimate_text_size(&measure_text, font_size);
 
         let style = StyleBuilder::new()
             .size(estimated_width, estimated_height)
@@ -2009,6 +2472,11 @@ impl ApplicationHandler for AppState {
                 }
                 // Apply any pending state updates from backend
                 self.apply_state_updates();
+                // Emit long presses that have been held long enough
+                let gesture_events = self.event_manager.poll_gestures();
+                if !gesture_events.is_empty() {
+                    self.process_ui_events(&gesture_events);
+                }
                 self.render();
                 if let Some(window) = &self.window {
                     window.request_redraw();
@@ -2094,6 +2562,7 @@ impl ApplicationHandler for AppState {
                         modifiers.ctrl,
                         modifiers.meta,
                     );
+                    self.flush_text_input_changes();
                     if handled {
                         // Text input handled the key, request redraw
                         if let Some(window) = &self.window {
@@ -2108,6 +2577,12 @@ impl ApplicationHandler for AppState {
                     ElementState::Pressed => self.event_manager.on_key_down(key_str, modifiers),
                     ElementState::Released => self.event_manager.on_key_up(key_str, modifiers),
                 };
+                for action in self.event_manager.take_shortcut_actions() {
+                    self.dev_overlay.log("SHORTCUT", action.clone());
+                    if let Some(ctx) = &self.app_context {
+                        ctx.push_command(AppCommand::Shortcut { action });
+                    }
+                }
                 self.process_ui_events(&events);
             }
             WindowEvent::Ime(ime) => {
@@ -2115,6 +2590,7 @@ impl ApplicationHandler for AppState {
                 match ime {
                     winit::event::Ime::Commit(text) => {
                         self.text_input_manager.on_text_input(&text);
+                        self.flush_text_input_changes();
                         if let Some(window) = &self.window {
                             window.request_redraw();
                         }
@@ -2128,6 +2604,23 @@ impl ApplicationHandler for AppState {
 }
 
 impl AppState {
+    /// Drain text input changes, logging them to the dev overlay
+    ///
+    /// Only lengths and selection ranges are logged so password fields stay private.
+    fn flush_text_input_changes(&mut self) {
+        for change in self.text_input_manager.take_changes() {
+            self.dev_overlay.log(
+                "INPUT",
+                format!(
+                    "{:?} len={} sel={:?}",
+                    change.node,
+                    change.text.chars().count(),
+                    change.selection.range()
+                ),
+            );
+        }
+    }
+
     /// Process UI events and execute handlers
     fn process_ui_events(&mut self, events: &[(NodeId, UiEvent)]) {
         // Log events to dev overlay
@@ -2153,17 +2646,37 @@ impl AppState {
                 UiEvent::TextInput { text } => format!("TextInput({})", text),
                 UiEvent::Focus => "Focus".to_string(),
                 UiEvent::Blur => "Blur".to_string(),
+                UiEvent::DragStart { .. } => "DragStart".to_string(),
+                UiEvent::DragMove { .. } => continue, // Skip move events (too noisy)
+                UiEvent::DragEnd { dx, dy, .. } => format!("DragEnd({:.0},{:.0})", dx, dy),
+                UiEvent::LongPress { .. } => "LongPress".to_string(),
+                UiEvent::Swipe { direction, .. } => format!("Swipe({:?})", direction),
             };
             self.dev_overlay.log("EVENT", format!("{} {:?}", event_name, node));
         }
 
-        // Get handlers to execute
-        let actions = self.event_manager.dispatch_events(events);
+        // Run handlers through the capture and bubble phases. The event manager
+        // and layout tree are moved out while handlers run so they can mutate `self`.
         let mut state_changed = false;
+        let mut handled = 0;
+        let tree = self.layout_tree.take();
+        let event_manager = std::mem::take(&mut self.event_manager);
+        let contexts = event_manager.dispatch_events(events, tree.as_ref(), |_event, handler, ctx| {
+            handled += 1;
+            state_changed |= self.execute_action(&handler.action, ctx);
+        });
+        self.event_manager = event_manager;
+        self.layout_tree = tree;
+
+        // Default behavior (focus on mouse down) runs unless a handler prevented it
+        let follow_up = self.event_manager.default_actions(events, &contexts);
+        if !follow_up.is_empty() {
+            self.process_ui_events(&follow_up);
+        }
 
         // Log handler count or lack thereof for click events
-        if !actions.is_empty() {
-            self.dev_overlay.log("HANDLER", format!("{} handler(s) found", actions.len()));
+        if handled > 0 {
+            self.dev_overlay.log("HANDLER", format!("{} handler(s) run", handled));
         } else {
             // Check if any click events had no handlers
             for (node, event) in events {
@@ -2175,60 +2688,9 @@ impl AppState {
             }
         }
 
-        // Execute each action
-        for (_node, handler) in actions {
-            match &handler.action {
-                HandlerAction::StateMutation { field, op, value } => {
-                    tracing::debug!("State mutation: {} {:?} {:?}", field, op, value);
-                    self.dev_overlay.log("STATE", format!("{} {:?} {:?}", field, op, value));
-                    // Execute the mutation on reactive state
-                    if self.reactive_state.mutate(field, *op, value) {
-                        state_changed = true;
-                        tracing::info!(
-                            "State '{}' updated to: {:?}",
-                            field,
-                            self.reactive_state.get(field)
-                        );
-                    } else {
-                        tracing::warn!("Failed to mutate state '{}' with {:?}", field, op);
-                    }
-                }
-                HandlerAction::FunctionCall { name, args } => {
-                    tracing::debug!("Function call: {}({:?})", name, args);
-                    self.dev_overlay.log("CALL", format!("{}({:?})", name, args));
-                    // Push command to context for backend to handle
-                    if let Some(ctx) = &self.app_context {
-                        ctx.push_command(AppCommand::CallFunction {
-                            name: name.clone(),
-                            args: args.clone(),
-                        });
-                    }
-                }
-                HandlerAction::Navigate { path } => {
-                    tracing::info!("Navigate to: {}", path);
-                    self.dev_overlay.log("NAV", format!("-> {}", path));
-                    // Push navigation command to context
-                    if let Some(ctx) = &self.app_context {
-                        ctx.push_command(AppCommand::Navigate { path: path.clone() });
-                    }
-                    // Also update reactive state for view switching
-                    self.reactive_state.set("view", StateValue::String(path.clone()));
-                    state_changed = true;
-                }
-                HandlerAction::Raw(expr) => {
-                    tracing::debug!("Raw handler: {}", expr);
-                    self.dev_overlay.log("RAW", expr.clone());
-                }
-            }
-        }
-
-        // Check if state changed and UI needs rebuild
+        // Update the UI for changed state (rebuilding only on route changes)
         if state_changed && self.reactive_state.has_changed_since(self.last_state_version) {
-            self.last_state_version = self.reactive_state.version();
-            // Rebuild UI to reflect state changes (especially for route changes)
-            tracing::info!("State changed, rebuilding UI (version {})", self.last_state_version);
-            self.build_ui();
-            self.compute_layout();
+            self.refresh_ui();
         }
 
         // Request redraw if any events occurred
@@ -2239,6 +2701,73 @@ impl AppState {
         }
     }
 
+    /// Execute a handler action, returning whether state changed
+    fn execute_action(&mut self, action: &HandlerAction, event: &mut events::EventContext) -> bool {
+        let mut state_changed = false;
+        match action {
+            HandlerAction::StateMutation { field, op, value } => {
+                tracing::debug!("State mutation: {} {:?} {:?}", field, op, value);
+                self.dev_overlay.log("STATE", format!("{} {:?} {:?}", field, op, value));
+                // Execute the mutation on reactive state
+                if self.reactive_state.mutate(field, *op, value) {
+                    state_changed = true;
+                    tracing::info!(
+                        "State '{}' updated to: {:?}",
+                        field,
+                        self.reactive_state.get(field)
+                    );
+                } else {
+                    tracing::warn!("Failed to mutate state '{}' with {:?}", field, op);
+                }
+            }
+            HandlerAction::FunctionCall { name, args } => {
+                tracing::debug!("Function call: {}({:?})", name, args);
+                self.dev_overlay.log("CALL", format!("{}({:?})", name, args));
+                // Push command to context for backend to handle
+                if let Some(ctx) = &self.app_context {
+                    ctx.call_function(name.clone(), args.clone());
+                }
+            }
+            HandlerAction::Navigate { path } => {
+                tracing::info!("Navigate to: {}", path);
+                self.dev_overlay.log("NAV", format!("-> {}", path));
+                // Push navigation command to context
+                if let Some(ctx) = &self.app_context {
+                    ctx.push_command(AppCommand::Navigate { path: path.clone() });
+                }
+                // Also update reactive state for view switching
+                self.reactive_state.set(ROUTE_STATE_KEY, StateValue::String(path.clone()));
+                state_changed = true;
+            }
+            HandlerAction::StopPropagation => event.stop_propagation(),
+            HandlerAction::PreventDefault => event.prevent_default(),
+            HandlerAction::Raw(expr) => {
+                tracing::debug!("Raw handler: {}", expr);
+                self.dev_overlay.log("RAW", expr.clone());
+            }
+            HandlerAction::Sequence(actions) => {
+                for action in actions {
+                    state_changed |= self.execute_action(action, event);
+                }
+            }
+            HandlerAction::Conditional {
+                condition,
+                then,
+                otherwise,
+            } => {
+                let branch = if condition.evaluate(&self.reactive_state) {
+                    Some(then)
+                } else {
+                    otherwise.as_ref()
+                };
+                if let Some(branch) = branch {
+                    state_changed = self.execute_action(branch, event);
+                }
+            }
+        }
+        state_changed
+    }
+
     /// Initialize state from a JSON schema
     ///
     /// Call this to set up initial state values for the application.
@@ -2279,4 +2808,284 @@ mod tests {
         assert_eq!(manifest.app.name, "Test App");
         assert_eq!(manifest.window.width, 800);
     }
+
+    const COUNTER_UI: &str = r#"
+        app Counter {
+            Column {
+                Text {
+                    content: "{state.count}"
+                    size: 20
+                }
+                Container {
+                    on click => state.count = 7
+                    Text { content: "Set" }
+                }
+            }
+        }
+    "#;
+
+    fn counter_app() -> AppState {
+        let manifest: Manifest = toml::from_str(
+            r#"
+            [app]
+            id = "com.example.counter"
+            name = "Counter"
+            version = "0.1.0"
+
+            [window]
+            title = "Counter"
+            width = 400
+            height = 300
+        "#,
+        )
+        .unwrap();
+
+        let mut app = AppState::new(manifest, Some(compile(COUNTER_UI).unwrap()), None);
+        app.logical_size = (400.0, 300.0);
+        app.reactive_state.set("count", StateValue::number(0));
+        app.build_ui();
+        app.compute_layout();
+        app.refresh_ui();
+        app
+    }
+
+    /// Register a handler the UI build doesn't know about; a rebuild drops it
+    fn add_marker_handler(app: &mut AppState, node: NodeId) {
+        app.event_manager.register_handler(
+            node,
+            events::EventHandler {
+                event_type: events::EventType::DoubleClick,
+                action: events::HandlerAction::Raw("marker".into()),
+                capture: false,
+            },
+        );
+    }
+
+    fn has_marker_handler(app: &AppState, node: NodeId) -> bool {
+        app.event_manager
+            .get_handlers(node)
+            .is_some_and(|h| h.iter().any(|h| h.event_type == events::EventType::DoubleClick))
+    }
+
+    #[test]
+    fn test_text_state_change_keeps_handlers() {
+        let mut app = counter_app();
+        let button = *app.event_manager.handlers.keys().next().unwrap();
+        add_marker_handler(&mut app, button);
+
+        let click = UiEvent::Click { x: 0.0, y: 0.0, button: MouseButton::Left };
+        app.process_ui_events(&[(button, click)]);
+
+        assert_eq!(app.reactive_state.get("count"), Some(&StateValue::number(7)));
+        assert!(has_marker_handler(&app, button));
+
+        // The bound text was re-measured in place
+        let text = &app.text_elements[0];
+        assert_eq!(text.content, "7");
+        let width = app.layout_tree.as_ref().unwrap().get_layout(text.node_id).size.width;
+        assert_eq!(width, estimate_text_size("7", 20.0).0);
+
+        // Unrelated keys don't touch the tree either
+        app.reactive_state.set("unrelated", StateValue::bool(true));
+        app.refresh_ui();
+        assert!(has_marker_handler(&app, button));
+    }
+
+    #[test]
+    fn test_route_change_rebuilds() {
+        let mut app = counter_app();
+        let button = *app.event_manager.handlers.keys().next().unwrap();
+        add_marker_handler(&mut app, button);
+
+        app.reactive_state.set(ROUTE_STATE_KEY, StateValue::string("/settings"));
+        app.refresh_ui();
+        assert!(!has_marker_handler(&app, button));
+        assert_eq!(app.event_manager.handlers.len(), 1);
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Profile {
+        name: String,
+        age: u32,
+        admin: bool,
+    }
+
+    #[test]
+    fn test_typed_state_round_trip() {
+        let ctx = AppContext::new();
+        let profile = Profile {
+            name: "Ada".into(),
+            age: 36,
+            admin: true,
+        };
+
+        ctx.set_typed("profile", &profile).unwrap();
+        ctx.set_typed("count", &42_i64).unwrap();
+
+        // Shared across clones (UI and backend hold separate handles)
+        let backend = ctx.clone();
+        assert_eq!(backend.get_typed::<Profile>("profile"), Some(profile));
+        assert_eq!(backend.get_typed::<i64>("count"), Some(42));
+        assert_eq!(backend.get_typed::<f64>("count"), Some(42.0));
+
+        // Wrong type or missing key
+        assert_eq!(backend.get_typed::<bool>("count"), None);
+        assert_eq!(backend.get_typed::<i64>("missing"), None);
+
+        // String API is independent
+        assert_eq!(ctx.get_shared("count"), None);
+        assert!(ctx.remove_typed("count"));
+        assert_eq!(ctx.get_typed::<i64>("count"), None);
+    }
+
+    #[test]
+    fn test_call_and_await_round_trip() {
+        let ui = AppContext::new();
+        let backend = ui.clone();
+
+        let response = ui.call_and_await("validate", vec![events::ActionValue::String("ada@".into())]);
+        let id = response.id();
+
+        let worker = std::thread::spawn(move || {
+            for command in backend.take_commands() {
+                if let AppCommand::CallFunction { id, name, args } = command {
+                    assert_eq!(name, "validate");
+                    assert_eq!(args.len(), 1);
+                    assert!(backend.respond(id, events::ActionValue::Bool(false)));
+                    return id;
+                }
+            }
+            panic!("no call received");
+        });
+
+        assert_eq!(worker.join().unwrap(), id);
+        assert!(matches!(pollster::block_on(response), events::ActionValue::Bool(false)));
+        // Each call is answered once
+        assert!(!ui.respond(id, events::ActionValue::Bool(true)));
+    }
+
+    #[test]
+    fn test_ui_call_response_becomes_state_update() {
+        let ctx = AppContext::new();
+        let first = ctx.call_function("validate", vec![]);
+        let second = ctx.call_function("save", vec![]);
+        assert_ne!(first, second);
+
+        let ids: Vec<CallId> = ctx
+            .take_commands()
+            .into_iter()
+            .filter_map(|command| match command {
+                AppCommand::CallFunction { id, .. } => Some(id),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(ids, vec![first, second]);
+
+        assert!(ctx.respond(first, events::ActionValue::String("Invalid email".into())));
+        let updates = ctx.take_state_updates();
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].key, "validate_result");
+        assert_eq!(updates[0].value, StateValue::string("Invalid email"));
+        assert!(!ctx.respond(999, events::ActionValue::Bool(true)));
+    }
+
+    #[test]
+    fn test_unanswered_calls_are_released() {
+        let ctx = AppContext::new().with_call_timeout(Duration::from_millis(10));
+        let pending = |ctx: &AppContext| ctx.pending_calls.lock().unwrap().len();
+
+        // Fire-and-forget calls are never registered
+        let id = ctx.send_function("track", vec![]);
+        assert_eq!(pending(&ctx), 0);
+        assert!(!ctx.respond(id, events::ActionValue::Bool(true)));
+
+        // Dropping the future cancels the call
+        let response = ctx.call_and_await("validate", vec![]);
+        let id = response.id();
+        assert_eq!(pending(&ctx), 1);
+        drop(response);
+        assert_eq!(pending(&ctx), 0);
+        assert!(!ctx.respond(id, events::ActionValue::Bool(true)));
+
+        // UI calls expire after the timeout
+        let expired = ctx.call_function("save", vec![]);
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(!ctx.respond(expired, events::ActionValue::Bool(true)));
+        ctx.call_function("save", vec![]);
+        let stale = ctx.call_function("load", vec![]);
+        std::thread::sleep(Duration::from_millis(20));
+        ctx.send_function("track", vec![]);
+        assert_eq!(pending(&ctx), 0);
+        assert!(!ctx.respond(stale, events::ActionValue::Bool(true)));
+        assert!(ctx.take_state_updates().is_empty());
+    }
+
+    #[test]
+    fn test_parse_handler_sequence() {
+        let action = parse_handler_action(r#"state.count += 1; log("clicked")"#);
+        let events::HandlerAction::Sequence(actions) = action else {
+            panic!("expected sequence, got {:?}", action);
+        };
+
+        assert_eq!(actions.len(), 2);
+        assert!(matches!(
+            &actions[0],
+            events::HandlerAction::StateMutation { field, op: events::MutationOp::Add, .. } if field == "count"
+        ));
+        assert!(matches!(&actions[1], events::HandlerAction::FunctionCall { name, .. } if name == "log"));
+
+        // Single statements keep the fast path
+        assert!(matches!(
+            parse_handler_action("state.count += 1"),
+            events::HandlerAction::StateMutation { .. }
+        ));
+
+        let action = parse_handler_action("select(); event.stopPropagation()");
+        assert!(matches!(
+            action,
+            events::HandlerAction::Sequence(ref a) if matches!(a[1], events::HandlerAction::StopPropagation)
+        ));
+        assert!(matches!(
+            parse_handler_action("preventDefault()"),
+            events::HandlerAction::PreventDefault
+        ));
+    }
+
+    #[test]
+    fn test_parse_handler_conditional() {
+        let action = parse_handler_action(
+            "if state.open { state.open = false; state.closed += 1 } else { state.open = true } track()",
+        );
+        let events::HandlerAction::Sequence(actions) = &action else {
+            panic!("expected sequence, got {:?}", action);
+        };
+        assert!(matches!(&actions[1], events::HandlerAction::FunctionCall { name, .. } if name == "track"));
+
+        let events::HandlerAction::Conditional { condition, then, otherwise } = &actions[0] else {
+            panic!("expected conditional, got {:?}", actions[0]);
+        };
+        assert_eq!(condition.field, "open");
+        assert_eq!(condition.op, events::ConditionOp::Truthy);
+        assert!(matches!(**then, events::HandlerAction::Sequence(ref a) if a.len() == 2));
+        assert!(matches!(
+            otherwise.as_deref(),
+            Some(events::HandlerAction::StateMutation { .. })
+        ));
+
+        let mut state = ReactiveState::new();
+        assert!(!condition.evaluate(&state));
+        state.set("open", StateValue::bool(true));
+        assert!(condition.evaluate(&state));
+
+        let action = parse_handler_action("if (state.count >= 3) { navigate('/done') }");
+        let events::HandlerAction::Conditional { condition, then, otherwise } = action else {
+            panic!("expected conditional");
+        };
+        assert!(otherwise.is_none());
+        assert!(matches!(*then, events::HandlerAction::Navigate { .. }));
+        state.set("count", StateValue::number(2));
+        assert!(!condition.evaluate(&state));
+        state.set("count", StateValue::number(3));
+        assert!(condition.evaluate(&state));
+    }
 }
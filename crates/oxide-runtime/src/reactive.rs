@@ -6,6 +6,8 @@
 use crate::events::{ActionValue, MutationOp};
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
 
 /// Dynamic state value that can be stored and mutated
 #[derive(Debug, Clone, PartialEq)]
@@ -101,6 +103,31 @@ impl From<ActionValue> for StateValue {
     }
 }
 
+/// Function computing a derived value from the current state
+pub type ComputeFn = Arc<dyn Fn(&ReactiveState) -> StateValue + Send + Sync>;
+
+/// Derived value definition
+struct ComputedValue {
+    /// Keys this value is derived from
+    deps: Vec<String>,
+    compute: ComputeFn,
+}
+
+/// Error returned when a computed value would depend on itself
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyCycle {
+    /// Keys forming the cycle, starting and ending with the same key
+    pub path: Vec<String>,
+}
+
+impl std::fmt::Display for DependencyCycle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "computed dependency cycle: {}", self.path.join(" -> "))
+    }
+}
+
+impl std::error::Error for DependencyCycle {}
+
 /// Version counter for tracking state changes
 static VERSION: AtomicU64 = AtomicU64::new(0);
 
@@ -117,6 +144,14 @@ pub struct ReactiveState {
     subscribers: HashMap<String, Vec<SubscriberId>>,
     /// Global change listeners
     change_listeners: Vec<Box<dyn Fn(&str, &StateValue) + Send + Sync>>,
+    /// Channels receiving new values for a single key
+    watchers: HashMap<String, Vec<Sender<StateValue>>>,
+    /// Derived values by key
+    computed: HashMap<String, ComputedValue>,
+    /// Nesting depth of active `batch` calls
+    batch_depth: usize,
+    /// Keys changed inside the current batch, in order
+    batch_changes: Vec<String>,
 }
 
 impl std::fmt::Debug for ReactiveState {
@@ -126,10 +161,24 @@ impl std::fmt::Debug for ReactiveState {
             .field("version", &self.version)
             .field("subscribers", &self.subscribers)
             .field("change_listeners", &format!("[{} listeners]", self.change_listeners.len()))
+            .field("watchers", &self.watchers.keys().collect::<Vec<_>>())
+            .field("computed", &self.computed.keys().collect::<Vec<_>>())
             .finish()
     }
 }
 
+/// Ends a batch when dropped, including when the batch closure panics
+struct BatchGuard<'a>(&'a mut ReactiveState);
+
+impl Drop for BatchGuard<'_> {
+    fn drop(&mut self) {
+        if self.0.batch_depth == 1 {
+            self.0.flush_batch();
+        }
+        self.0.batch_depth -= 1;
+    }
+}
+
 /// Subscriber identifier
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct SubscriberId(u64);
@@ -160,6 +209,10 @@ impl ReactiveState {
             version: 0,
             subscribers: HashMap::new(),
             change_listeners: Vec::new(),
+            watchers: HashMap::new(),
+            computed: HashMap::new(),
+            batch_depth: 0,
+            batch_changes: Vec::new(),
         }
     }
 
@@ -176,12 +229,147 @@ impl ReactiveState {
         self.values.get(key)
     }
 
+    /// Get a value as a number
+    pub fn get_f64(&self, key: &str) -> Option<f64> {
+        self.get(key).and_then(StateValue::as_number)
+    }
+
+    /// Get a value as a boolean
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        self.get(key).and_then(StateValue::as_bool)
+    }
+
+    /// Get a value as a string (only for string values)
+    pub fn get_string(&self, key: &str) -> Option<&str> {
+        self.get(key).and_then(StateValue::as_string)
+    }
+
     /// Set a value by key
     pub fn set(&mut self, key: impl Into<String>, value: StateValue) {
         let key = key.into();
         self.values.insert(key.clone(), value.clone());
+        if self.batch_depth > 0 {
+            self.batch_changes.push(key);
+            return;
+        }
         self.version += 1;
         self.notify_change(&key, &value);
+        self.recompute_dependents(&key);
+    }
+
+    /// Apply several changes as one update
+    ///
+    /// Values are written immediately, but the version is bumped once and
+    /// listeners are notified once per changed key when the outermost batch
+    /// ends. Nested batches coalesce into the outer one. If `f` panics, the
+    /// batch still ends and the changes made so far are flushed.
+    pub fn batch<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R {
+        self.batch_depth += 1;
+        let guard = BatchGuard(self);
+        f(&mut *guard.0)
+    }
+
+    /// Check if a batch is in progress
+    pub fn is_batching(&self) -> bool {
+        self.batch_depth > 0
+    }
+
+    /// Finish the outermost batch
+    fn flush_batch(&mut self) {
+        // Recompute derived values while still batching so they join this update
+        let mut next = 0;
+        while next < self.batch_changes.len() {
+            let key = self.batch_changes[next].clone();
+            self.recompute_dependents(&key);
+            next += 1;
+        }
+
+        let changes = std::mem::take(&mut self.batch_changes);
+        if changes.is_empty() {
+            return;
+        }
+
+        self.version += 1;
+        let mut notified = std::collections::HashSet::new();
+        for key in changes {
+            if notified.insert(key.clone()) {
+                if let Some(value) = self.values.get(&key).cloned() {
+                    self.notify_change(&key, &value);
+                }
+            }
+        }
+    }
+
+    /// Define a value derived from other keys
+    ///
+    /// The value is computed immediately and recomputed whenever one of `deps`
+    /// changes. Computed values may depend on other computed values, but not
+    /// on themselves.
+    pub fn define_computed<F>(
+        &mut self,
+        key: impl Into<String>,
+        deps: impl IntoIterator<Item = impl Into<String>>,
+        compute: F,
+    ) -> Result<(), DependencyCycle>
+    where
+        F: Fn(&ReactiveState) -> StateValue + Send + Sync + 'static,
+    {
+        let key = key.into();
+        let deps: Vec<String> = deps.into_iter().map(Into::into).collect();
+
+        for dep in &deps {
+            if let Some(mut path) = self.dependency_path(dep, &key) {
+                path.insert(0, key.clone());
+                return Err(DependencyCycle { path });
+            }
+        }
+
+        let compute: ComputeFn = Arc::new(compute);
+        let value = compute(self);
+        self.computed.insert(key.clone(), ComputedValue { deps, compute });
+        self.set(key, value);
+        Ok(())
+    }
+
+    /// Remove a computed definition, keeping its last value
+    pub fn remove_computed(&mut self, key: &str) -> bool {
+        self.computed.remove(key).is_some()
+    }
+
+    /// Check if a key is a computed value
+    pub fn is_computed(&self, key: &str) -> bool {
+        self.computed.contains_key(key)
+    }
+
+    /// Find a chain of computed dependencies from `from` to `to`
+    fn dependency_path(&self, from: &str, to: &str) -> Option<Vec<String>> {
+        if from == to {
+            return Some(vec![from.to_string()]);
+        }
+
+        let computed = self.computed.get(from)?;
+        computed.deps.iter().find_map(|dep| {
+            let mut path = self.dependency_path(dep, to)?;
+            path.insert(0, from.to_string());
+            Some(path)
+        })
+    }
+
+    /// Recompute derived values that depend on a changed key
+    fn recompute_dependents(&mut self, changed: &str) {
+        let dependents: Vec<(String, ComputeFn)> = self
+            .computed
+            .iter()
+            .filter(|(_, c)| c.deps.iter().any(|dep| depends_on(dep, changed)))
+            .map(|(key, c)| (key.clone(), Arc::clone(&c.compute)))
+            .collect();
+
+        for (key, compute) in dependents {
+            let value = compute(self);
+            if self.values.get(&key) != Some(&value) {
+                self.set(key, value);
+            }
+        }
     }
 
     /// Apply a mutation operation to a field
@@ -236,6 +424,16 @@ impl ReactiveState {
         true
     }
 
+    /// Receive the new value of `key` each time it changes
+    ///
+    /// Only changes to that exact key are sent. The receiver can be moved to
+    /// another thread; dropping it ends the subscription.
+    pub fn watch(&mut self, key: impl Into<String>) -> Receiver<StateValue> {
+        let (tx, rx) = mpsc::channel();
+        self.watchers.entry(key.into()).or_default().push(tx);
+        rx
+    }
+
     /// Subscribe to changes on a specific key
     pub fn subscribe(&mut self, key: impl Into<String>, subscriber: SubscriberId) {
         self.subscribers
@@ -290,11 +488,18 @@ impl ReactiveState {
         self.change_listeners.push(Box::new(listener));
     }
 
-    /// Notify listeners of a change
-    fn notify_change(&self, key: &str, value: &StateValue) {
+    /// Notify listeners and key subscribers of a change
+    fn notify_change(&mut self, key: &str, value: &StateValue) {
         for listener in &self.change_listeners {
             listener(key, value);
         }
+
+        if let Some(watchers) = self.watchers.get_mut(key) {
+            watchers.retain(|tx| tx.send(value.clone()).is_ok());
+            if watchers.is_empty() {
+                self.watchers.remove(key);
+            }
+        }
     }
 
     /// Initialize state from a default schema
@@ -322,6 +527,14 @@ impl ReactiveState {
     }
 }
 
+/// Check if a dependency (possibly a dotted path) is affected by a changed key
+pub(crate) fn depends_on(dep: &str, changed: &str) -> bool {
+    dep == changed
+        || dep
+            .strip_prefix(changed)
+            .is_some_and(|rest| rest.starts_with('.'))
+}
+
 /// Convert JSON value to StateValue
 fn json_to_state_value(value: &serde_json::Value) -> StateValue {
     match value {
@@ -505,4 +718,181 @@ mod tests {
         state.unsubscribe("count", sub_id);
         assert_eq!(state.subscribers("count").len(), 0);
     }
+
+    #[test]
+    fn test_watch_key() {
+        let mut state = ReactiveState::new();
+        let rx = state.watch("count");
+
+        state.set("other", StateValue::number(1));
+        assert!(rx.try_recv().is_err());
+
+        state.set("count", StateValue::number(2));
+        state.mutate("count", MutationOp::Add, &ActionValue::Number(3.0));
+        assert_eq!(rx.try_recv(), Ok(StateValue::number(2)));
+        assert_eq!(rx.try_recv(), Ok(StateValue::number(5)));
+        assert!(rx.try_recv().is_err());
+
+        // Values arrive on other threads; dropping the receiver unsubscribes
+        drop(rx);
+        let rx = state.watch("count");
+        let handle = std::thread::spawn(move || rx.recv().unwrap());
+        state.set("count", StateValue::number(9));
+        assert_eq!(handle.join().unwrap(), StateValue::number(9));
+        state.set("count", StateValue::number(10));
+        assert!(state.watchers.is_empty());
+    }
+
+    #[test]
+    fn test_typed_getters() {
+        let mut state = ReactiveState::new();
+        state.set("count", StateValue::number(3));
+        state.set("open", StateValue::bool(true));
+        state.set("name", StateValue::string("Ada"));
+
+        assert_eq!(state.get_f64("count"), Some(3.0));
+        assert_eq!(state.get_bool("open"), Some(true));
+        assert_eq!(state.get_string("name"), Some("Ada"));
+        assert_eq!(state.get_string("count"), None);
+        assert_eq!(state.get_f64("missing"), None);
+    }
+
+    fn full_name(state: &ReactiveState) -> StateValue {
+        let first = state.get("first").and_then(|v| v.as_string()).unwrap_or("");
+        let last = state.get("last").and_then(|v| v.as_string()).unwrap_or("");
+        StateValue::string(format!("{} {}", first, last))
+    }
+
+    #[test]
+    fn test_computed_value() {
+        let mut state = ReactiveState::new();
+        state.set("first", StateValue::string("Ada"));
+        state.set("last", StateValue::string("Lovelace"));
+
+        state.define_computed("fullName", ["first", "last"], full_name).unwrap();
+        assert!(state.is_computed("fullName"));
+        assert_eq!(state.get("fullName"), Some(&StateValue::string("Ada Lovelace")));
+
+        let version = state.version();
+        state.set("last", StateValue::string("Byron"));
+        assert_eq!(state.get("fullName"), Some(&StateValue::string("Ada Byron")));
+        // One bump for the dependency, one for the computed value
+        assert_eq!(state.version(), version + 2);
+
+        // Unrelated keys don't trigger a recompute
+        let version = state.version();
+        state.set("other", StateValue::number(1));
+        assert_eq!(state.version(), version + 1);
+    }
+
+    #[test]
+    fn test_computed_chain() {
+        let mut state = ReactiveState::new();
+        state.set("count", StateValue::number(2));
+
+        state
+            .define_computed("double", ["count"], |s| {
+                StateValue::number(s.get("count").and_then(|v| v.as_number()).unwrap_or(0.0) * 2.0)
+            })
+            .unwrap();
+        state
+            .define_computed("label", ["double"], |s| {
+                StateValue::string(format!("x{}", s.get("double").unwrap().to_string_value()))
+            })
+            .unwrap();
+
+        state.mutate("count", MutationOp::Add, &ActionValue::Number(1.0));
+        assert_eq!(state.get("double"), Some(&StateValue::Number(6.0)));
+        assert_eq!(state.get("label"), Some(&StateValue::string("x6")));
+    }
+
+    #[test]
+    fn test_computed_cycle_detected() {
+        let mut state = ReactiveState::new();
+        state.define_computed("a", ["b"], |_| StateValue::Null).unwrap();
+        state.define_computed("b", ["c"], |_| StateValue::Null).unwrap();
+
+        let err = state.define_computed("c", ["a"], |_| StateValue::Null).unwrap_err();
+        assert_eq!(err.path, vec!["c", "a", "b", "c"]);
+        assert!(!state.is_computed("c"));
+
+        assert!(state.define_computed("d", ["d"], |_| StateValue::Null).is_err());
+    }
+
+    #[test]
+    fn test_batch_single_version_bump() {
+        let mut state = ReactiveState::new();
+        state.set("count", StateValue::number(0));
+        state.set("name", StateValue::string("a"));
+
+        let version = state.version();
+        state.batch(|s| {
+            s.mutate("count", MutationOp::Add, &ActionValue::Number(1.0));
+            s.mutate("count", MutationOp::Add, &ActionValue::Number(1.0));
+            s.mutate("name", MutationOp::Set, &ActionValue::String("b".into()));
+            assert!(s.is_batching());
+            assert_eq!(s.version(), version);
+        });
+
+        assert_eq!(state.version(), version + 1);
+        assert_eq!(state.get("count"), Some(&StateValue::Number(2.0)));
+        assert_eq!(state.get("name"), Some(&StateValue::string("b")));
+        assert!(!state.is_batching());
+
+        // Empty batch doesn't bump
+        state.batch(|_| {});
+        assert_eq!(state.version(), version + 1);
+    }
+
+    #[test]
+    fn test_nested_batch_coalesces() {
+        let notified = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut state = ReactiveState::new();
+        state.set("a", StateValue::number(1));
+        state
+            .define_computed("sum", ["a", "b"], |s| {
+                let a = s.get("a").and_then(|v| v.as_number()).unwrap_or(0.0);
+                let b = s.get("b").and_then(|v| v.as_number()).unwrap_or(0.0);
+                StateValue::number(a + b)
+            })
+            .unwrap();
+
+        let log = Arc::clone(&notified);
+        state.on_change(move |key, _| log.lock().unwrap().push(key.to_string()));
+
+        let version = state.version();
+        state.batch(|s| {
+            s.set("a", StateValue::number(2));
+            s.batch(|s| {
+                s.set("b", StateValue::number(3));
+                s.set("a", StateValue::number(4));
+            });
+            assert_eq!(s.version(), version);
+        });
+
+        assert_eq!(state.version(), version + 1);
+        assert_eq!(state.get("sum"), Some(&StateValue::Number(7.0)));
+        assert_eq!(*notified.lock().unwrap(), vec!["a", "b", "sum"]);
+    }
+
+    #[test]
+    fn test_batch_ends_when_closure_panics() {
+        let mut state = ReactiveState::new();
+        let version = state.version();
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            state.batch(|s| {
+                s.set("saved", StateValue::Bool(true));
+                panic!("failed halfway");
+            })
+        }));
+        assert!(result.is_err());
+
+        assert!(!state.is_batching());
+        assert_eq!(state.version(), version + 1);
+
+        // Later changes notify immediately again
+        state.set("after", StateValue::number(1));
+        assert_eq!(state.version(), version + 2);
+    }
 }
@@ -0,0 +1,324 @@
+//! Keyboard shortcuts for OxideKit runtime
+//!
+//! Maps key chords (`Ctrl+S`) and chord sequences (`Ctrl+K Ctrl+B`) to named
+//! actions. Sequences must be completed within a timeout or they reset.
+
+use crate::events::Modifiers;
+use std::time::{Duration, Instant};
+
+/// Default time allowed between the steps of a chord sequence
+pub const DEFAULT_SEQUENCE_TIMEOUT: Duration = Duration::from_millis(1000);
+
+/// A single key combined with modifiers (e.g. `Ctrl+Shift+P`)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    /// Normalized (lowercase) key name
+    pub key: String,
+    /// Required modifier state
+    pub modifiers: Modifiers,
+}
+
+impl KeyChord {
+    /// Create a chord from a key name and modifiers
+    pub fn new(key: &str, modifiers: Modifiers) -> Self {
+        Self {
+            key: normalize_key(key),
+            modifiers,
+        }
+    }
+
+    /// Parse a chord like `Ctrl+K` or `Cmd+Shift+P`
+    ///
+    /// `Mod` and `CmdOrCtrl` mean Cmd on macOS and Ctrl elsewhere.
+    pub fn parse(chord: &str) -> Result<Self, ShortcutParseError> {
+        let mut modifiers = Modifiers::default();
+        let mut key = None;
+
+        for part in chord.split('+').map(str::trim) {
+            match part.to_lowercase().as_str() {
+                "" => return Err(ShortcutParseError::new(chord, "empty key")),
+                "ctrl" | "control" => modifiers.ctrl = true,
+                "shift" => modifiers.shift = true,
+                "alt" | "option" => modifiers.alt = true,
+                "meta" | "cmd" | "command" | "super" | "win" => modifiers.meta = true,
+                "mod" | "cmdorctrl" => {
+                    if cfg!(target_os = "macos") {
+                        modifiers.meta = true;
+                    } else {
+                        modifiers.ctrl = true;
+                    }
+                }
+                _ if key.is_some() => {
+                    return Err(ShortcutParseError::new(chord, "more than one non-modifier key"))
+                }
+                _ => key = Some(part),
+            }
+        }
+
+        let key = key.ok_or_else(|| ShortcutParseError::new(chord, "missing key"))?;
+        Ok(Self::new(key, modifiers))
+    }
+}
+
+/// A shortcut made of one or more chords pressed in sequence
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Shortcut {
+    pub chords: Vec<KeyChord>,
+}
+
+impl Shortcut {
+    /// Parse a whitespace-separated chord sequence like `Ctrl+K Ctrl+B`
+    pub fn parse(shortcut: &str) -> Result<Self, ShortcutParseError> {
+        let chords = shortcut
+            .split_whitespace()
+            .map(KeyChord::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if chords.is_empty() {
+            return Err(ShortcutParseError::new(shortcut, "empty shortcut"));
+        }
+        Ok(Self { chords })
+    }
+}
+
+/// Error returned for malformed shortcut strings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShortcutParseError {
+    /// The shortcut string that failed to parse
+    pub shortcut: String,
+    /// What was wrong with it
+    pub reason: &'static str,
+}
+
+impl ShortcutParseError {
+    fn new(shortcut: &str, reason: &'static str) -> Self {
+        Self {
+            shortcut: shortcut.to_string(),
+            reason,
+        }
+    }
+}
+
+impl std::fmt::Display for ShortcutParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid shortcut '{}': {}", self.shortcut, self.reason)
+    }
+}
+
+impl std::error::Error for ShortcutParseError {}
+
+/// Result of feeding a key press to the registry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShortcutMatch {
+    /// A shortcut completed; contains its action name
+    Matched(String),
+    /// The key started or continued a sequence
+    Pending,
+    /// The key is not part of any shortcut
+    None,
+}
+
+/// Registry of shortcuts and the in-progress chord sequence
+#[derive(Debug, Clone)]
+pub struct ShortcutRegistry {
+    bindings: Vec<(Shortcut, String)>,
+    pending: Vec<KeyChord>,
+    last_key_time: Option<Instant>,
+    sequence_timeout: Duration,
+}
+
+impl Default for ShortcutRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ShortcutRegistry {
+    pub fn new() -> Self {
+        Self {
+            bindings: Vec::new(),
+            pending: Vec::new(),
+            last_key_time: None,
+            sequence_timeout: DEFAULT_SEQUENCE_TIMEOUT,
+        }
+    }
+
+    /// Set the time allowed between the steps of a sequence
+    pub fn with_sequence_timeout(mut self, timeout: Duration) -> Self {
+        self.sequence_timeout = timeout;
+        self
+    }
+
+    /// Bind a shortcut string to an action, replacing any existing binding
+    pub fn register(&mut self, shortcut: &str, action: impl Into<String>) -> Result<(), ShortcutParseError> {
+        let shortcut = Shortcut::parse(shortcut)?;
+        self.bindings.retain(|(existing, _)| *existing != shortcut);
+        self.bindings.push((shortcut, action.into()));
+        Ok(())
+    }
+
+    /// Remove all bindings for an action
+    pub fn unregister(&mut self, action: &str) {
+        self.bindings.retain(|(_, a)| a != action);
+    }
+
+    /// Number of registered shortcuts
+    pub fn len(&self) -> usize {
+        self.bindings.len()
+    }
+
+    /// Check if no shortcuts are registered
+    pub fn is_empty(&self) -> bool {
+        self.bindings.is_empty()
+    }
+
+    /// Check if a sequence is in progress
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Abandon any in-progress sequence
+    pub fn reset(&mut self) {
+        self.pending.clear();
+        self.last_key_time = None;
+    }
+
+    /// Feed a key press and report whether it completed a shortcut
+    pub fn match_key(&mut self, key: &str, modifiers: Modifiers, now: Instant) -> ShortcutMatch {
+        let chord = KeyChord::new(key, modifiers);
+
+        // Pressing a modifier on its own doesn't advance or break a sequence
+        if is_modifier_key(&chord.key) {
+            return if self.is_pending() { ShortcutMatch::Pending } else { ShortcutMatch::None };
+        }
+
+        let expired = self
+            .last_key_time
+            .is_some_and(|last| now.duration_since(last) > self.sequence_timeout);
+        if expired {
+            self.pending.clear();
+        }
+        self.last_key_time = Some(now);
+
+        self.pending.push(chord.clone());
+        let result = self.match_pending();
+        if result != ShortcutMatch::None || self.pending.len() == 1 {
+            return result;
+        }
+
+        // The sequence broke; the key may still start a new one
+        self.pending = vec![chord];
+        self.match_pending()
+    }
+
+    fn match_pending(&mut self) -> ShortcutMatch {
+        if let Some((_, action)) = self.bindings.iter().find(|(s, _)| s.chords == self.pending) {
+            let action = action.clone();
+            self.reset();
+            return ShortcutMatch::Matched(action);
+        }
+
+        let is_prefix = self
+            .bindings
+            .iter()
+            .any(|(s, _)| s.chords.len() > self.pending.len() && s.chords.starts_with(&self.pending));
+        if is_prefix {
+            ShortcutMatch::Pending
+        } else {
+            self.reset();
+            ShortcutMatch::None
+        }
+    }
+}
+
+fn normalize_key(key: &str) -> String {
+    let key = key.trim().to_lowercase();
+    match key.as_str() {
+        "esc" => "escape".into(),
+        "return" => "enter".into(),
+        "del" => "delete".into(),
+        "up" | "down" | "left" | "right" => format!("arrow{}", key),
+        "plus" => "+".into(),
+        _ => key,
+    }
+}
+
+fn is_modifier_key(key: &str) -> bool {
+    matches!(key, "control" | "shift" | "alt" | "super" | "meta")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctrl() -> Modifiers {
+        Modifiers {
+            ctrl: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_chord() {
+        let chord = KeyChord::parse("Ctrl+Shift+P").unwrap();
+        assert_eq!(chord.key, "p");
+        assert!(chord.modifiers.ctrl && chord.modifiers.shift && !chord.modifiers.alt);
+
+        assert_eq!(KeyChord::parse("Esc").unwrap().key, "escape");
+        assert!(KeyChord::parse("Ctrl+").is_err());
+        assert!(KeyChord::parse("Ctrl+A+B").is_err());
+        assert!(Shortcut::parse("  ").is_err());
+    }
+
+    #[test]
+    fn test_single_chord() {
+        let mut registry = ShortcutRegistry::new();
+        registry.register("Ctrl+S", "save").unwrap();
+        let now = Instant::now();
+
+        assert_eq!(registry.match_key("s", Modifiers::default(), now), ShortcutMatch::None);
+        assert_eq!(registry.match_key("Control", ctrl(), now), ShortcutMatch::None);
+        assert_eq!(registry.match_key("S", ctrl(), now), ShortcutMatch::Matched("save".into()));
+    }
+
+    #[test]
+    fn test_chord_sequence() {
+        let mut registry = ShortcutRegistry::new();
+        registry.register("Ctrl+K Ctrl+B", "toggle-sidebar").unwrap();
+        registry.register("Ctrl+B", "bold").unwrap();
+        let now = Instant::now();
+
+        assert_eq!(registry.match_key("k", ctrl(), now), ShortcutMatch::Pending);
+        assert!(registry.is_pending());
+        assert_eq!(
+            registry.match_key("b", ctrl(), now + Duration::from_millis(200)),
+            ShortcutMatch::Matched("toggle-sidebar".into())
+        );
+        assert!(!registry.is_pending());
+
+        // Without the prefix, the single chord matches
+        assert_eq!(registry.match_key("b", ctrl(), now), ShortcutMatch::Matched("bold".into()));
+
+        // A wrong second step falls back to matching the key on its own
+        registry.match_key("k", ctrl(), now);
+        assert_eq!(
+            registry.match_key("b", Modifiers::default(), now),
+            ShortcutMatch::None
+        );
+        assert!(!registry.is_pending());
+    }
+
+    #[test]
+    fn test_sequence_timeout_resets() {
+        let mut registry = ShortcutRegistry::new().with_sequence_timeout(Duration::from_millis(500));
+        registry.register("Ctrl+K Ctrl+B", "toggle-sidebar").unwrap();
+        registry.register("Ctrl+B", "bold").unwrap();
+        let now = Instant::now();
+
+        assert_eq!(registry.match_key("k", ctrl(), now), ShortcutMatch::Pending);
+        assert_eq!(
+            registry.match_key("b", ctrl(), now + Duration::from_millis(800)),
+            ShortcutMatch::Matched("bold".into())
+        );
+    }
+}
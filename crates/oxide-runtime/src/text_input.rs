@@ -1,10 +1,16 @@
 //! Text input integration for OxideKit runtime
 //!
-//! Provides simple single-line text input handling with cursor, selection, and clipboard support.
+//! Provides simple single-line text input handling with cursor, selection,
+//! clipboard, per-field undo/redo, and input mask support.
 
+use crate::input_mask::InputMask;
 use oxide_layout::NodeId;
 use oxide_text_edit::{ClipboardProvider, SystemClipboard};
 use std::collections::HashMap;
+use std::ops::Range;
+
+/// Maximum number of undo steps kept per field
+pub const MAX_UNDO_HISTORY: usize = 100;
 
 /// Simple text position for single-line inputs
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -71,6 +77,19 @@ impl Selection {
             (self.focus, self.anchor)
         }
     }
+
+    /// Get the selected character range (empty when collapsed)
+    pub fn range(&self) -> Range<usize> {
+        let (start, end) = self.ordered_positions();
+        start.offset..end.offset
+    }
+}
+
+/// Text and selection captured for undo/redo
+#[derive(Debug, Clone)]
+struct TextSnapshot {
+    text: String,
+    selection: Selection,
 }
 
 /// Text input field state
@@ -90,6 +109,14 @@ pub struct TextInputState {
     pub max_length: usize,
     /// Password mode (mask characters)
     pub password: bool,
+    /// Format applied as the user types; `text` holds the formatted value
+    pub mask: Option<InputMask>,
+    /// Snapshots to restore on undo (most recent last)
+    undo_stack: Vec<TextSnapshot>,
+    /// Snapshots to restore on redo (most recent last)
+    redo_stack: Vec<TextSnapshot>,
+    /// Whether the last edit was typing that further typing merges into
+    coalesce_typing: bool,
 }
 
 impl Default for TextInputState {
@@ -102,6 +129,10 @@ impl Default for TextInputState {
             placeholder: None,
             max_length: 0,
             password: false,
+            mask: None,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            coalesce_typing: false,
         }
     }
 }
@@ -121,6 +152,26 @@ impl TextInputState {
         }
     }
 
+    /// Apply (or remove) an input mask, reformatting the current text
+    pub fn set_mask(&mut self, mask: Option<InputMask>) {
+        if let Some(mask) = &mask {
+            let raw = mask.raw(&self.text);
+            self.text = mask.format(&raw);
+            let end = mask.caret_offset(&raw, raw.chars().count());
+            self.selection = Selection::new(TextPosition::new(end));
+        }
+        self.mask = mask;
+        self.clear_history();
+    }
+
+    /// Get the value without mask formatting (the text itself when unmasked)
+    pub fn raw_value(&self) -> String {
+        match &self.mask {
+            Some(mask) => mask.raw(&self.text),
+            None => self.text.clone(),
+        }
+    }
+
     /// Get the display text (masked if password mode)
     pub fn display_text(&self) -> String {
         if self.password {
@@ -133,11 +184,22 @@ impl TextInputState {
     }
 
     /// Insert text at cursor position
+    ///
+    /// Consecutive single-character inserts are undone together.
     pub fn insert(&mut self, text: &str) {
         if self.readonly {
             return;
         }
 
+        let typing = text.chars().count() == 1 && !self.selection.has_selection();
+        self.edit(typing, |input| input.insert_text(text));
+    }
+
+    fn insert_text(&mut self, text: &str) {
+        if let Some(mask) = self.mask.clone() {
+            return self.insert_masked(&mask, text);
+        }
+
         // Delete selection first if any
         if self.selection.has_selection() {
             self.delete_selection();
@@ -174,6 +236,14 @@ impl TextInputState {
             return;
         }
 
+        self.edit(false, Self::backspace_text);
+    }
+
+    fn backspace_text(&mut self) {
+        if let Some(mask) = self.mask.clone() {
+            return self.remove_masked(&mask, true);
+        }
+
         if self.selection.has_selection() {
             self.delete_selection();
         } else {
@@ -193,6 +263,14 @@ impl TextInputState {
             return;
         }
 
+        self.edit(false, Self::delete_text);
+    }
+
+    fn delete_text(&mut self) {
+        if let Some(mask) = self.mask.clone() {
+            return self.remove_masked(&mask, false);
+        }
+
         if self.selection.has_selection() {
             self.delete_selection();
         } else {
@@ -206,6 +284,71 @@ impl TextInputState {
         }
     }
 
+    /// Raw value and the raw range covered by the selection
+    fn masked_selection(&self, mask: &InputMask) -> (Vec<char>, usize, usize) {
+        let (start, end) = self.selection.ordered_positions();
+        (
+            mask.raw(&self.text).chars().collect(),
+            mask.raw_index(&self.text, start.offset),
+            mask.raw_index(&self.text, end.offset),
+        )
+    }
+
+    /// Replace the text with a formatted raw value and place the caret
+    fn set_masked(&mut self, mask: &InputMask, raw: &[char], raw_cursor: usize) {
+        let raw: String = raw.iter().collect();
+        self.text = mask.format(&raw);
+        self.selection = Selection::new(TextPosition::new(mask.caret_offset(&raw, raw_cursor)));
+    }
+
+    /// Insert through the mask, skipping characters it rejects
+    ///
+    /// `max_length` limits the formatted text, literals included.
+    fn insert_masked(&mut self, mask: &InputMask, text: &str) {
+        let (mut raw, start, end) = self.masked_selection(mask);
+        let had_selection = start != end;
+        raw.drain(start..end);
+
+        let mut cursor = start;
+        for c in text.chars() {
+            let mut candidate = raw.clone();
+            candidate.insert(cursor, c);
+            let candidate_raw: String = candidate.iter().collect();
+            let fits = self.max_length == 0
+                || mask.format(&candidate_raw).chars().count() <= self.max_length;
+            if fits && mask.accepts(&candidate_raw) {
+                raw = candidate;
+                cursor += 1;
+            }
+        }
+
+        // Nothing accepted: leave the text and caret alone
+        if cursor == start && !had_selection {
+            return;
+        }
+        self.set_masked(mask, &raw, cursor);
+    }
+
+    /// Backspace/delete one raw character (or the selection), skipping literals
+    fn remove_masked(&mut self, mask: &InputMask, backward: bool) {
+        let (mut raw, start, end) = self.masked_selection(mask);
+        let range = if start != end {
+            start..end
+        } else if backward && start > 0 {
+            start - 1..start
+        } else if !backward && start < raw.len() {
+            start..start + 1
+        } else {
+            return;
+        };
+
+        let cursor = range.start;
+        raw.drain(range);
+        if mask.accepts(&raw.iter().collect::<String>()) {
+            self.set_masked(mask, &raw, cursor);
+        }
+    }
+
     /// Delete selected text
     fn delete_selection(&mut self) {
         let (start, end) = self.selection.ordered_positions();
@@ -215,16 +358,99 @@ impl TextInputState {
         self.selection = Selection::new(start);
     }
 
+    /// Run an edit, recording an undo step if the text changed
+    fn edit(&mut self, typing: bool, f: impl FnOnce(&mut Self)) {
+        let before = self.snapshot();
+        f(self);
+        if self.text == before.text {
+            return;
+        }
+
+        if !(typing && self.coalesce_typing) {
+            self.undo_stack.push(before);
+            if self.undo_stack.len() > MAX_UNDO_HISTORY {
+                self.undo_stack.remove(0);
+            }
+        }
+        self.redo_stack.clear();
+        self.coalesce_typing = typing;
+    }
+
+    fn snapshot(&self) -> TextSnapshot {
+        TextSnapshot {
+            text: self.text.clone(),
+            selection: self.selection,
+        }
+    }
+
+    fn restore(&mut self, snapshot: TextSnapshot) {
+        self.text = snapshot.text;
+        self.selection = snapshot.selection;
+        self.coalesce_typing = false;
+    }
+
+    /// Revert the last edit, restoring the previous text and caret
+    ///
+    /// Returns `false` if there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        if self.readonly {
+            return false;
+        }
+        let Some(snapshot) = self.undo_stack.pop() else {
+            return false;
+        };
+        self.redo_stack.push(self.snapshot());
+        self.restore(snapshot);
+        true
+    }
+
+    /// Reapply the last undone edit
+    ///
+    /// Returns `false` if there is nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        if self.readonly {
+            return false;
+        }
+        let Some(snapshot) = self.redo_stack.pop() else {
+            return false;
+        };
+        self.undo_stack.push(self.snapshot());
+        self.restore(snapshot);
+        true
+    }
+
+    /// Check if there is an edit to undo
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    /// Check if there is an edit to redo
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// Forget the undo/redo history (e.g. after the text is set externally)
+    pub fn clear_history(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.coalesce_typing = false;
+    }
+
+    /// Move the cursor, optionally extending the selection
+    fn move_cursor(&mut self, new_pos: TextPosition, extend_selection: bool) {
+        if extend_selection {
+            self.selection.extend_to(new_pos);
+        } else {
+            self.selection = Selection::new(new_pos);
+        }
+        self.coalesce_typing = false;
+    }
+
     /// Move cursor left
     pub fn move_left(&mut self, extend_selection: bool) {
         let pos = self.selection.cursor();
         if pos.offset > 0 {
-            let new_pos = TextPosition::new(pos.offset - 1);
-            if extend_selection {
-                self.selection.extend_to(new_pos);
-            } else {
-                self.selection = Selection::new(new_pos);
-            }
+            self.move_cursor(TextPosition::new(pos.offset - 1), extend_selection);
         }
     }
 
@@ -233,34 +459,57 @@ impl TextInputState {
         let pos = self.selection.cursor();
         let char_count = self.text.chars().count();
         if pos.offset < char_count {
-            let new_pos = TextPosition::new(pos.offset + 1);
-            if extend_selection {
-                self.selection.extend_to(new_pos);
-            } else {
-                self.selection = Selection::new(new_pos);
-            }
+            self.move_cursor(TextPosition::new(pos.offset + 1), extend_selection);
         }
     }
 
+    /// Move cursor to the start of the previous word
+    ///
+    /// In password mode this jumps to the start so word boundaries aren't revealed.
+    pub fn move_word_left(&mut self, extend_selection: bool) {
+        if self.password {
+            return self.move_to_start(extend_selection);
+        }
+
+        let chars: Vec<char> = self.text.chars().collect();
+        let mut pos = self.selection.cursor().offset.min(chars.len());
+        while pos > 0 && !is_word_char(chars[pos - 1]) {
+            pos -= 1;
+        }
+        while pos > 0 && is_word_char(chars[pos - 1]) {
+            pos -= 1;
+        }
+        self.move_cursor(TextPosition::new(pos), extend_selection);
+    }
+
+    /// Move cursor to the end of the next word
+    ///
+    /// In password mode this jumps to the end so word boundaries aren't revealed.
+    pub fn move_word_right(&mut self, extend_selection: bool) {
+        if self.password {
+            return self.move_to_end(extend_selection);
+        }
+
+        let chars: Vec<char> = self.text.chars().collect();
+        let mut pos = self.selection.cursor().offset.min(chars.len());
+        while pos < chars.len() && !is_word_char(chars[pos]) {
+            pos += 1;
+        }
+        while pos < chars.len() && is_word_char(chars[pos]) {
+            pos += 1;
+        }
+        self.move_cursor(TextPosition::new(pos), extend_selection);
+    }
+
     /// Move cursor to start
     pub fn move_to_start(&mut self, extend_selection: bool) {
-        let new_pos = TextPosition::zero();
-        if extend_selection {
-            self.selection.extend_to(new_pos);
-        } else {
-            self.selection = Selection::new(new_pos);
-        }
+        self.move_cursor(TextPosition::zero(), extend_selection);
     }
 
     /// Move cursor to end
     pub fn move_to_end(&mut self, extend_selection: bool) {
         let char_count = self.text.chars().count();
-        let new_pos = TextPosition::new(char_count);
-        if extend_selection {
-            self.selection.extend_to(new_pos);
-        } else {
-            self.selection = Selection::new(new_pos);
-        }
+        self.move_cursor(TextPosition::new(char_count), extend_selection);
     }
 
     /// Select all text
@@ -270,6 +519,7 @@ impl TextInputState {
             TextPosition::zero(),
             TextPosition::new(char_count),
         );
+        self.coalesce_typing = false;
     }
 
     /// Get selected text
@@ -298,14 +548,33 @@ impl TextInputState {
     }
 }
 
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Text or selection change in a text input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextInputChange {
+    /// The input that changed
+    pub node: NodeId,
+    /// Text after the change
+    pub text: String,
+    /// Selection after the change
+    pub selection: Selection,
+}
+
 /// Text input manager for handling multiple text fields
 pub struct TextInputManager {
     /// Text input states by node ID
     inputs: HashMap<NodeId, TextInputState>,
     /// Currently focused input
     focused_input: Option<NodeId>,
-    /// Clipboard provider (system clipboard)
-    clipboard: Option<SystemClipboard>,
+    /// Clipboard provider (system clipboard by default)
+    clipboard: Option<Box<dyn ClipboardProvider>>,
+    /// Changes not yet taken by the runtime
+    changes: Vec<TextInputChange>,
+    /// Whether a change is already being tracked (avoids duplicate events)
+    tracking: bool,
 }
 
 impl std::fmt::Debug for TextInputManager {
@@ -314,6 +583,7 @@ impl std::fmt::Debug for TextInputManager {
             .field("inputs", &self.inputs)
             .field("focused_input", &self.focused_input)
             .field("clipboard", &self.clipboard.is_some())
+            .field("changes", &self.changes)
             .finish()
     }
 }
@@ -330,10 +600,53 @@ impl TextInputManager {
         Self {
             inputs: HashMap::new(),
             focused_input: None,
-            clipboard: SystemClipboard::new().ok(),
+            clipboard: SystemClipboard::new()
+                .ok()
+                .map(|c| Box::new(c) as Box<dyn ClipboardProvider>),
+            changes: Vec::new(),
+            tracking: false,
         }
     }
 
+    /// Use a specific clipboard provider instead of the system clipboard
+    pub fn with_clipboard(mut self, clipboard: impl ClipboardProvider + 'static) -> Self {
+        self.set_clipboard(clipboard);
+        self
+    }
+
+    /// Replace the clipboard provider
+    pub fn set_clipboard(&mut self, clipboard: impl ClipboardProvider + 'static) {
+        self.clipboard = Some(Box::new(clipboard));
+    }
+
+    /// Take the text/selection changes made since the last call
+    pub fn take_changes(&mut self) -> Vec<TextInputChange> {
+        std::mem::take(&mut self.changes)
+    }
+
+    /// Run `f` and queue a change if it altered the focused input
+    fn tracked<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R {
+        let Some(node) = self.focused_input.filter(|_| !self.tracking) else {
+            return f(self);
+        };
+        let before = self.inputs.get(&node).map(|input| input.snapshot());
+
+        self.tracking = true;
+        let result = f(self);
+        self.tracking = false;
+
+        if let (Some(before), Some(input)) = (before, self.inputs.get(&node)) {
+            if input.text != before.text || input.selection != before.selection {
+                self.changes.push(TextInputChange {
+                    node,
+                    text: input.text.clone(),
+                    selection: input.selection,
+                });
+            }
+        }
+        result
+    }
+
     /// Register a text input node
     pub fn register(&mut self, node: NodeId, initial_text: &str) {
         self.inputs.insert(node, TextInputState::with_text(initial_text));
@@ -393,15 +706,32 @@ impl TextInputManager {
         self.inputs.get_mut(&node)
     }
 
+    /// Apply (or remove) an input mask on a registered input
+    pub fn set_mask(&mut self, node: NodeId, mask: Option<InputMask>) {
+        if let Some(input) = self.inputs.get_mut(&node) {
+            input.set_mask(mask);
+        }
+    }
+
     /// Handle text input event
     pub fn on_text_input(&mut self, text: &str) {
-        if let Some(input) = self.focused_mut() {
-            input.insert(text);
-        }
+        self.tracked(|this| {
+            if let Some(input) = this.focused_mut() {
+                input.insert(text);
+            }
+        });
     }
 
     /// Handle key down event
+    ///
+    /// Ctrl+Arrow moves by word (Cmd+Arrow jumps to the start/end), Shift
+    /// extends the selection, and Ctrl/Cmd+Z, Ctrl/Cmd+Shift+Z and Ctrl+Y
+    /// undo and redo.
     pub fn on_key_down(&mut self, key: &str, shift: bool, ctrl: bool, meta: bool) -> bool {
+        self.tracked(|this| this.handle_key(key, shift, ctrl, meta))
+    }
+
+    fn handle_key(&mut self, key: &str, shift: bool, ctrl: bool, meta: bool) -> bool {
         let Some(input) = self.focused_mut() else {
             return false;
         };
@@ -418,11 +748,23 @@ impl TextInputManager {
                 true
             }
             "ArrowLeft" | "Left" => {
-                input.move_left(shift);
+                if meta {
+                    input.move_to_start(shift);
+                } else if ctrl {
+                    input.move_word_left(shift);
+                } else {
+                    input.move_left(shift);
+                }
                 true
             }
             "ArrowRight" | "Right" => {
-                input.move_right(shift);
+                if meta {
+                    input.move_to_end(shift);
+                } else if ctrl {
+                    input.move_word_right(shift);
+                } else {
+                    input.move_right(shift);
+                }
                 true
             }
             "Home" => {
@@ -449,6 +791,18 @@ impl TextInputManager {
                 self.paste();
                 true
             }
+            "z" | "Z" if cmd_or_ctrl => {
+                if shift {
+                    self.redo();
+                } else {
+                    self.undo();
+                }
+                true
+            }
+            "y" | "Y" if ctrl => {
+                self.redo();
+                true
+            }
             _ => false,
         }
     }
@@ -471,6 +825,10 @@ impl TextInputManager {
 
     /// Cut selected text to clipboard
     pub fn cut(&mut self) {
+        self.tracked(Self::cut_selection);
+    }
+
+    fn cut_selection(&mut self) {
         // First copy
         if let Some(input) = self.focused() {
             let text = input.selected_text().to_string();
@@ -489,13 +847,25 @@ impl TextInputManager {
         }
     }
 
-    /// Paste from clipboard
+    /// Paste from clipboard, replacing any selection
     pub fn paste(&mut self) {
         let text = self.clipboard.as_ref().and_then(|c| c.read_text().ok().flatten());
 
-        if let (Some(text), Some(input)) = (text, self.focused_mut()) {
-            input.insert(&text);
-        }
+        self.tracked(|this| {
+            if let (Some(text), Some(input)) = (text, this.focused_mut()) {
+                input.insert(&text);
+            }
+        });
+    }
+
+    /// Undo the last edit in the focused input
+    pub fn undo(&mut self) -> bool {
+        self.tracked(|this| this.focused_mut().is_some_and(TextInputState::undo))
+    }
+
+    /// Redo the last undone edit in the focused input
+    pub fn redo(&mut self) -> bool {
+        self.tracked(|this| this.focused_mut().is_some_and(TextInputState::redo))
     }
 
     /// Get all registered input nodes
@@ -513,6 +883,7 @@ impl TextInputManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use oxide_text_edit::MemoryClipboard;
 
     #[test]
     fn test_text_input_insert() {
@@ -585,4 +956,170 @@ mod tests {
         assert!(!manager.get(node1).unwrap().focused);
         assert!(manager.get(node2).unwrap().focused);
     }
+
+    fn focused_manager(text: &str) -> (TextInputManager, NodeId) {
+        let mut manager = TextInputManager::new().with_clipboard(MemoryClipboard::new());
+        let node = NodeId::new(1);
+        manager.register(node, text);
+        manager.focus(node);
+        (manager, node)
+    }
+
+    #[test]
+    fn test_select_all_and_delete() {
+        let (mut manager, node) = focused_manager("Hello World");
+
+        manager.on_key_down("a", false, true, false);
+        manager.on_key_down("Backspace", false, false, false);
+        assert_eq!(manager.get(node).unwrap().text, "");
+
+        let changes = manager.take_changes();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].selection.range(), 0..11);
+        assert_eq!(changes[1].text, "");
+        assert_eq!(changes[1].selection.range(), 0..0);
+        assert!(manager.take_changes().is_empty());
+    }
+
+    #[test]
+    fn test_paste_over_selection() {
+        let (mut manager, node) = focused_manager("Hello there");
+        manager.clipboard.as_ref().unwrap().write_text("World").unwrap();
+
+        // Ctrl+Shift+Left selects the last word
+        manager.on_key_down("End", false, false, false);
+        manager.on_key_down("ArrowLeft", true, true, false);
+        assert_eq!(manager.get(node).unwrap().selected_text(), "there");
+
+        manager.on_key_down("v", false, true, false);
+        let input = manager.get(node).unwrap();
+        assert_eq!(input.text, "Hello World");
+        assert_eq!(input.cursor_offset(), 11);
+    }
+
+    #[test]
+    fn test_cut_and_copy() {
+        let (mut manager, node) = focused_manager("copy me");
+
+        manager.on_key_down("a", false, true, false);
+        manager.on_key_down("c", false, true, false);
+        manager.on_key_down("x", false, true, false);
+        assert_eq!(manager.get(node).unwrap().text, "");
+
+        manager.on_key_down("v", false, true, false);
+        manager.on_key_down("v", false, true, false);
+        assert_eq!(manager.get(node).unwrap().text, "copy mecopy me");
+    }
+
+    #[test]
+    fn test_undo_restores_text_and_caret() {
+        let (mut manager, node) = focused_manager("Hello");
+        manager.on_key_down("End", false, false, false);
+
+        // Typing is undone as one step
+        for c in [",", " ", "y", "o", "u"] {
+            manager.on_text_input(c);
+        }
+        manager.on_key_down("ArrowLeft", false, false, false);
+        manager.on_key_down("Backspace", false, false, false);
+        assert_eq!(manager.get(node).unwrap().text, "Hello, yu");
+
+        assert!(manager.undo());
+        let input = manager.get(node).unwrap();
+        assert_eq!(input.text, "Hello, you");
+        assert_eq!(input.cursor_offset(), 9);
+
+        manager.on_key_down("z", false, true, false);
+        let input = manager.get(node).unwrap();
+        assert_eq!(input.text, "Hello");
+        assert_eq!(input.cursor_offset(), 5);
+        assert!(!manager.undo());
+
+        manager.on_key_down("z", true, true, false);
+        assert_eq!(manager.get(node).unwrap().text, "Hello, you");
+        manager.on_key_down("y", false, true, false);
+        assert_eq!(manager.get(node).unwrap().text, "Hello, yu");
+        assert!(!manager.get(node).unwrap().can_redo());
+    }
+
+    #[test]
+    fn test_phone_mask() {
+        let (mut manager, node) = focused_manager("");
+        manager.set_mask(node, Some(InputMask::pattern("(###) ###-####")));
+
+        for c in "555x1234-567".chars() {
+            manager.on_text_input(&c.to_string());
+        }
+        let input = manager.get(node).unwrap();
+        assert_eq!(input.text, "(555) 123-4567");
+        assert_eq!(input.raw_value(), "5551234567");
+
+        // Rejected characters don't move the caret; a full mask takes no more
+        let caret = input.cursor_offset();
+        manager.on_text_input("a");
+        manager.on_text_input("8");
+        assert_eq!(manager.get(node).unwrap().cursor_offset(), caret);
+        assert_eq!(manager.get(node).unwrap().raw_value(), "5551234567");
+
+        // Backspace removes digits, not literals
+        manager.on_key_down("Backspace", false, false, false);
+        manager.on_key_down("Backspace", false, false, false);
+        manager.on_key_down("Backspace", false, false, false);
+        manager.on_key_down("Backspace", false, false, false);
+        let input = manager.get(node).unwrap();
+        assert_eq!(input.text, "(555) 123");
+        assert_eq!(input.cursor_offset(), 9);
+    }
+
+    #[test]
+    fn test_currency_mask() {
+        let (mut manager, node) = focused_manager("");
+        manager.set_mask(node, Some(InputMask::currency("$", 2)));
+
+        for c in "1234567.891".chars() {
+            manager.on_text_input(&c.to_string());
+        }
+        let input = manager.get(node).unwrap();
+        assert_eq!(input.text, "$1,234,567.89");
+        assert_eq!(input.raw_value(), "1234567.89");
+
+        // Pasting over a selection reformats the whole value
+        manager.on_key_down("a", false, true, false);
+        manager.on_text_input("98765");
+        let input = manager.get(node).unwrap();
+        assert_eq!(input.text, "$98,765");
+        assert_eq!(input.cursor_offset(), 7);
+
+        // Existing text is reformatted when a mask is applied
+        let mut input = TextInputState::with_text("20240301");
+        input.set_mask(Some(InputMask::IsoDate));
+        assert_eq!(input.text, "2024-03-01");
+
+        // The length limit applies to the formatted text
+        let mut input = TextInputState::new();
+        input.max_length = 6;
+        input.set_mask(Some(InputMask::currency("kr.", 2)));
+        input.insert("12345");
+        assert_eq!(input.text, "kr.123");
+        assert_eq!(input.raw_value(), "123");
+    }
+
+    #[test]
+    fn test_word_movement() {
+        let mut input = TextInputState::with_text("one two_2, three");
+
+        input.move_word_right(false);
+        assert_eq!(input.cursor_offset(), 3);
+        input.move_word_right(true);
+        assert_eq!(input.selected_text(), " two_2");
+        input.move_to_end(false);
+        input.move_word_left(false);
+        assert_eq!(input.cursor_offset(), 11);
+        input.move_word_left(false);
+        assert_eq!(input.cursor_offset(), 4);
+
+        input.password = true;
+        input.move_word_right(false);
+        assert_eq!(input.cursor_offset(), 16);
+    }
 }
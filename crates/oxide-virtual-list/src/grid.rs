@@ -1,5 +1,6 @@
 //! Virtual grid component for 2D layouts.
 
+use crate::Rect;
 use serde::{Deserialize, Serialize};
 
 /// Column width configuration
@@ -19,21 +20,82 @@ impl Default for ColumnWidth {
     }
 }
 
+/// Order in which items fill grid cells
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum FillOrder {
+    /// Fill each row left to right before wrapping to the next row
+    #[default]
+    RowMajor,
+    /// Fill each column top to bottom before wrapping to the next column
+    ColumnMajor,
+}
+
 /// Responsive column configuration
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ResponsiveColumns {
-    /// Columns for different breakpoints
+    /// Columns for different breakpoints as `(min_width, columns)`
     pub breakpoints: Vec<(f32, usize)>,
+    /// Minimum item width; caps the column count to what fits (auto-fit)
+    #[serde(default)]
+    pub min_item_width: Option<f32>,
 }
 
 impl Default for ResponsiveColumns {
     fn default() -> Self {
         Self {
             breakpoints: vec![(0.0, 1), (600.0, 2), (900.0, 3), (1200.0, 4)],
+            min_item_width: None,
         }
     }
 }
 
+impl ResponsiveColumns {
+    /// Create from `(min_width, columns)` breakpoints
+    pub fn new(breakpoints: Vec<(f32, usize)>) -> Self {
+        Self {
+            breakpoints,
+            min_item_width: None,
+        }
+    }
+
+    /// Only use as many columns as fit items of at least `width`
+    ///
+    /// Like CSS `repeat(auto-fit, minmax(width, 1fr))`; without breakpoints
+    /// the column count is fully driven by the container width.
+    pub fn min_item_width(mut self, width: f32) -> Self {
+        self.min_item_width = Some(width);
+        self
+    }
+
+    /// Resolve the column count for a container width
+    pub fn resolve(&self, container_width: f32) -> usize {
+        self.resolve_with_gap(container_width, 0.0)
+    }
+
+    /// Resolve the column count for a container width with gaps between columns
+    pub fn resolve_with_gap(&self, container_width: f32, gap: f32) -> usize {
+        let breakpoint = self
+            .breakpoints
+            .iter()
+            .filter(|(min_width, _)| container_width >= *min_width)
+            .max_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|&(_, columns)| columns);
+
+        let fitting = self
+            .min_item_width
+            .filter(|&width| width > 0.0)
+            .map(|width| ((container_width + gap) / (width + gap)).floor() as usize);
+
+        let columns = match (breakpoint, fitting) {
+            (Some(columns), Some(fit)) => columns.min(fit),
+            (Some(columns), None) => columns,
+            (None, Some(fit)) => fit,
+            (None, None) => 1,
+        };
+        columns.max(1)
+    }
+}
+
 /// Grid item data
 #[derive(Debug, Clone)]
 pub struct GridItem<T> {
@@ -54,6 +116,69 @@ impl<T> GridItem<T> {
             row_span: 1,
         }
     }
+
+    /// Span multiple columns and rows
+    pub fn span(mut self, cols: usize, rows: usize) -> Self {
+        self.col_span = cols.max(1);
+        self.row_span = rows.max(1);
+        self
+    }
+}
+
+/// Computed cell placement for a grid item
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GridPlacement {
+    /// Item index
+    pub index: usize,
+    /// First column
+    pub column: usize,
+    /// First row
+    pub row: usize,
+    /// Columns spanned
+    pub col_span: usize,
+    /// Rows spanned
+    pub row_span: usize,
+}
+
+impl GridPlacement {
+    /// Last row covered by this item (inclusive)
+    pub fn last_row(&self) -> usize {
+        self.row + self.row_span - 1
+    }
+}
+
+/// Cell occupancy bitmap used for auto-placement
+#[derive(Debug)]
+struct Occupancy {
+    columns: usize,
+    cells: Vec<bool>,
+}
+
+impl Occupancy {
+    fn new(columns: usize) -> Self {
+        Self {
+            columns,
+            cells: Vec::new(),
+        }
+    }
+
+    fn is_free(&self, row: usize, column: usize, cols: usize, rows: usize) -> bool {
+        (row..row + rows).all(|r| {
+            (column..column + cols).all(|c| !self.cells.get(r * self.columns + c).unwrap_or(&false))
+        })
+    }
+
+    fn fill(&mut self, row: usize, column: usize, cols: usize, rows: usize) {
+        let needed = (row + rows) * self.columns;
+        if self.cells.len() < needed {
+            self.cells.resize(needed, false);
+        }
+        for r in row..row + rows {
+            for c in column..column + cols {
+                self.cells[r * self.columns + c] = true;
+            }
+        }
+    }
 }
 
 /// Grid layout configuration
@@ -67,6 +192,9 @@ pub struct GridLayout {
     pub row_height: f32,
     /// Gap between cells
     pub gap: f32,
+    /// Order in which items fill cells
+    #[serde(default)]
+    pub fill_order: FillOrder,
 }
 
 impl Default for GridLayout {
@@ -76,10 +204,23 @@ impl Default for GridLayout {
             column_width: ColumnWidth::Auto,
             row_height: 100.0,
             gap: 8.0,
+            fill_order: FillOrder::RowMajor,
         }
     }
 }
 
+impl GridLayout {
+    /// Set the fill order
+    ///
+    /// Column-major grids split items evenly into `columns` columns, so each
+    /// column holds `ceil(items / columns)` rows. Item spans are only honored
+    /// in row-major order.
+    pub fn fill_order(mut self, order: FillOrder) -> Self {
+        self.fill_order = order;
+        self
+    }
+}
+
 /// Virtual grid state
 #[derive(Debug, Clone, Default)]
 pub struct VirtualGridState {
@@ -102,6 +243,12 @@ pub struct VirtualGrid {
     pub total_items: usize,
     /// Overscan
     pub overscan: usize,
+    /// Requested `(cols, rows)` span per item when laid out with spans
+    spans: Vec<(usize, usize)>,
+    /// Cell placements when items span multiple cells
+    placements: Vec<GridPlacement>,
+    /// Largest row span among placed items
+    max_row_span: usize,
 }
 
 impl Default for VirtualGrid {
@@ -111,6 +258,9 @@ impl Default for VirtualGrid {
             responsive: None,
             total_items: 0,
             overscan: 2,
+            spans: Vec::new(),
+            placements: Vec::new(),
+            max_row_span: 1,
         }
     }
 }
@@ -127,14 +277,170 @@ impl VirtualGrid {
         self
     }
 
-    /// Set the total number of items
+    /// Set the total number of single-cell items
     pub fn items(mut self, count: usize) -> Self {
         self.total_items = count;
+        self.spans.clear();
+        self.placements.clear();
+        self.max_row_span = 1;
+        self
+    }
+
+    /// Derive the column count from the container width
+    pub fn responsive(mut self, responsive: ResponsiveColumns) -> Self {
+        self.responsive = Some(responsive);
         self
     }
 
+    /// Update the container width, returning whether the grid was relaid out
+    ///
+    /// With [`ResponsiveColumns`] the column count is re-resolved, and spanned
+    /// items are placed again when it changes.
+    pub fn set_container_width(&mut self, container_width: f32) -> bool {
+        let Some(responsive) = &self.responsive else {
+            return false;
+        };
+
+        let columns = responsive.resolve_with_gap(container_width, self.layout.gap);
+        if columns == self.layout.columns {
+            return false;
+        }
+        self.layout.columns = columns;
+        self.place();
+        true
+    }
+
+    /// Lay out items with their spans
+    ///
+    /// Items are auto-placed in order, each at the first free position after
+    /// the previous item, so spanned tiles never overlap their neighbors.
+    pub fn layout_items<T>(&mut self, items: &[GridItem<T>]) {
+        self.total_items = items.len();
+        self.spans = items.iter().map(|item| (item.col_span, item.row_span)).collect();
+        self.place();
+    }
+
+    /// Auto-place items from their stored spans
+    fn place(&mut self) {
+        let columns = self.layout.columns.max(1);
+        let mut occupancy = Occupancy::new(columns);
+        let (mut row, mut column) = (0, 0);
+
+        self.max_row_span = 1;
+        self.placements.clear();
+        for (index, &(col_span, row_span)) in self.spans.iter().enumerate() {
+            let cols = col_span.clamp(1, columns);
+            let rows = row_span.max(1);
+            loop {
+                if column + cols > columns {
+                    row += 1;
+                    column = 0;
+                    continue;
+                }
+                if occupancy.is_free(row, column, cols, rows) {
+                    break;
+                }
+                column += 1;
+            }
+
+            occupancy.fill(row, column, cols, rows);
+            self.placements.push(GridPlacement {
+                index,
+                column,
+                row,
+                col_span: cols,
+                row_span: rows,
+            });
+            self.max_row_span = self.max_row_span.max(rows);
+            column += cols;
+        }
+    }
+
+    /// Get the placement of an item
+    pub fn placement(&self, index: usize) -> Option<GridPlacement> {
+        if !self.placements.is_empty() && !self.is_column_major() {
+            return self.placements.get(index).copied();
+        }
+
+        let (column, row) = if self.is_column_major() {
+            let rows = self.column_length().max(1);
+            (index / rows, index % rows)
+        } else {
+            let columns = self.layout.columns.max(1);
+            (index % columns, index / columns)
+        };
+        (index < self.total_items).then_some(GridPlacement {
+            index,
+            column,
+            row,
+            col_span: 1,
+            row_span: 1,
+        })
+    }
+
+    /// Check if items fill columns before rows
+    fn is_column_major(&self) -> bool {
+        self.layout.fill_order == FillOrder::ColumnMajor
+    }
+
+    /// Number of rows in each column of a column-major grid
+    fn column_length(&self) -> usize {
+        self.total_items.div_ceil(self.layout.columns.max(1))
+    }
+
+    /// Get the bounds of an item for a container width
+    pub fn item_bounds(&self, index: usize, container_width: f32) -> Option<Rect> {
+        let placement = self.placement(index)?;
+        let columns = self.layout.columns.max(1) as f32;
+        let gap = self.layout.gap;
+        let cell_width = (container_width - gap * (columns - 1.0)) / columns;
+        let span = |count: usize, size: f32| count as f32 * size + (count - 1) as f32 * gap;
+
+        Some(Rect::new(
+            placement.column as f32 * (cell_width + gap),
+            placement.row as f32 * (self.layout.row_height + gap),
+            span(placement.col_span, cell_width),
+            span(placement.row_span, self.layout.row_height),
+        ))
+    }
+
+    /// Get the total number of rows
+    pub fn row_count(&self) -> usize {
+        if self.is_column_major() {
+            return self.column_length();
+        }
+        match self.placements.iter().map(GridPlacement::last_row).max() {
+            Some(last) => last + 1,
+            None => self.total_items.div_ceil(self.layout.columns.max(1)),
+        }
+    }
+
+    /// Get the total content height
+    pub fn content_height(&self) -> f32 {
+        let rows = self.row_count() as f32;
+        (rows * (self.layout.row_height + self.layout.gap) - self.layout.gap).max(0.0)
+    }
+
     /// Get visible range of items
+    ///
+    /// With spanned items the range covers every item whose bounds intersect
+    /// the viewport, including tiles whose first row is scrolled out.
+    ///
+    /// In column-major order the items in view are not contiguous, so this
+    /// only bounds them; render from [`visible_ranges`](Self::visible_ranges)
+    /// or [`visible_indices`](Self::visible_indices) instead.
     pub fn visible_range(&self, scroll_top: f32, viewport_height: f32) -> std::ops::Range<usize> {
+        if self.is_column_major() {
+            let ranges = self.visible_ranges(scroll_top, viewport_height);
+            return match (ranges.first(), ranges.last()) {
+                (Some(first), Some(last)) => first.start..last.end,
+                _ => 0..0,
+            };
+        }
+        if !self.placements.is_empty() {
+            return self.spanned_visible_range(scroll_top, viewport_height);
+        }
+
         let row_height = self.layout.row_height + self.layout.gap;
         let start_row = (scroll_top / row_height).floor() as usize;
         let visible_rows = (viewport_height / row_height).ceil() as usize + self.overscan * 2;
@@ -142,4 +448,234 @@ impl VirtualGrid {
         let end_idx = ((start_row + visible_rows) * self.layout.columns).min(self.total_items);
         start_idx..end_idx
     }
+
+    /// Get the exact runs of items in view, in ascending order
+    ///
+    /// Row-major grids have a single run; column-major grids have one run per
+    /// column that has items in the visible rows.
+    pub fn visible_ranges(
+        &self,
+        scroll_top: f32,
+        viewport_height: f32,
+    ) -> Vec<std::ops::Range<usize>> {
+        if !self.is_column_major() {
+            let range = self.visible_range(scroll_top, viewport_height);
+            return if range.is_empty() { Vec::new() } else { vec![range] };
+        }
+
+        let rows = self.visible_rows(scroll_top, viewport_height);
+        let column_length = self.column_length();
+        (0..self.layout.columns.max(1))
+            .map(|column| {
+                let first = column * column_length;
+                (first + rows.start).min(self.total_items)..(first + rows.end).min(self.total_items)
+            })
+            .filter(|range| !range.is_empty())
+            .collect()
+    }
+
+    /// Get the indices of the items in view, in ascending order
+    pub fn visible_indices(&self, scroll_top: f32, viewport_height: f32) -> Vec<usize> {
+        self.visible_ranges(scroll_top, viewport_height)
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    /// Rows of a column-major grid in view, including overscan
+    fn visible_rows(&self, scroll_top: f32, viewport_height: f32) -> std::ops::Range<usize> {
+        let row_height = self.layout.row_height + self.layout.gap;
+        let first = (scroll_top / row_height).floor().max(0.0) as usize;
+        let last = ((scroll_top + viewport_height) / row_height).ceil().max(0.0) as usize;
+        let rows = self.column_length();
+        let start = first.saturating_sub(self.overscan).min(rows);
+        start..(last + self.overscan).clamp(start, rows)
+    }
+
+    /// Visible range over explicit placements
+    fn spanned_visible_range(
+        &self,
+        scroll_top: f32,
+        viewport_height: f32,
+    ) -> std::ops::Range<usize> {
+        let row_height = self.layout.row_height + self.layout.gap;
+        let first_row = (scroll_top / row_height).floor().max(0.0) as usize;
+        let first_row = first_row.saturating_sub(self.overscan);
+        let last_row = ((scroll_top + viewport_height) / row_height).ceil().max(0.0) as usize;
+        let last_row = last_row + self.overscan;
+
+        // Placement rows never decrease, so only the tail of the rows before
+        // `first_row` can reach into the viewport
+        let reach = first_row.saturating_sub(self.max_row_span - 1);
+        let candidates = self.placements.partition_point(|p| p.row < reach);
+        let end = self.placements.partition_point(|p| p.row < last_row);
+        let start = self.placements[candidates..end]
+            .iter()
+            .position(|p| p.last_row() >= first_row)
+            .map_or(end, |offset| candidates + offset);
+        start..end
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 3-column grid with a 2x2 tile at index 4
+    fn dashboard() -> VirtualGrid {
+        let items: Vec<_> = (0..9)
+            .map(|i| if i == 4 { GridItem::new(i).span(2, 2) } else { GridItem::new(i) })
+            .collect();
+        let mut grid = VirtualGrid::new().columns(3);
+        grid.overscan = 0;
+        grid.layout.row_height = 100.0;
+        grid.layout.gap = 10.0;
+        grid.layout_items(&items);
+        grid
+    }
+
+    fn cell(grid: &VirtualGrid, index: usize) -> (usize, usize) {
+        let placement = grid.placement(index).unwrap();
+        (placement.row, placement.column)
+    }
+
+    #[test]
+    fn test_spanned_placement() {
+        let grid = dashboard();
+
+        assert_eq!(cell(&grid, 3), (1, 0));
+        assert_eq!(cell(&grid, 4), (1, 1));
+        assert_eq!(grid.placement(4).unwrap().last_row(), 2);
+        // Items flow around the tile instead of overlapping it
+        assert_eq!(cell(&grid, 5), (2, 0));
+        assert_eq!(cell(&grid, 6), (3, 0));
+        assert_eq!(cell(&grid, 8), (3, 2));
+        assert_eq!(grid.row_count(), 4);
+        assert_eq!(grid.content_height(), 430.0);
+    }
+
+    #[test]
+    fn test_spanned_bounds() {
+        let grid = dashboard();
+        let bounds = grid.item_bounds(4, 320.0).unwrap();
+        assert_eq!(bounds, Rect::new(110.0, 110.0, 210.0, 210.0));
+
+        let neighbor = grid.item_bounds(5, 320.0).unwrap();
+        assert!(!bounds.intersects(&neighbor));
+        for index in (0..9).filter(|&i| i != 4) {
+            assert!(!bounds.intersects(&grid.item_bounds(index, 320.0).unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_spanned_visible_range() {
+        let grid = dashboard();
+
+        // Viewport inside row 2 only: the tile's origin row is scrolled out
+        assert_eq!(grid.visible_range(230.0, 50.0), 4..6);
+        // Rows 0-1
+        assert_eq!(grid.visible_range(0.0, 200.0), 0..5);
+        // Last row only
+        assert_eq!(grid.visible_range(340.0, 80.0), 6..9);
+    }
+
+    #[test]
+    fn test_uniform_visible_range() {
+        let grid = VirtualGrid::new().columns(3).items(30);
+        assert_eq!(grid.placement(7).unwrap().row, 2);
+        assert_eq!(grid.visible_range(0.0, 200.0), 0..18);
+    }
+
+    #[test]
+    fn test_responsive_resolve_breakpoints() {
+        let responsive = ResponsiveColumns::default();
+        assert_eq!(responsive.resolve(320.0), 1);
+        assert_eq!(responsive.resolve(768.0), 2);
+        assert_eq!(responsive.resolve(1200.0), 4);
+
+        let unordered = ResponsiveColumns::new(vec![(1000.0, 5), (0.0, 2)]);
+        assert_eq!(unordered.resolve(999.0), 2);
+        assert_eq!(unordered.resolve(1000.0), 5);
+    }
+
+    #[test]
+    fn test_responsive_auto_fit() {
+        let auto_fit = ResponsiveColumns::new(Vec::new()).min_item_width(250.0);
+        assert_eq!(auto_fit.resolve(200.0), 1);
+        assert_eq!(auto_fit.resolve(768.0), 3);
+        assert_eq!(auto_fit.resolve_with_gap(768.0, 16.0), 2);
+
+        // Breakpoints are capped by how many items fit
+        let capped = ResponsiveColumns::default().min_item_width(400.0);
+        assert_eq!(capped.resolve(1200.0), 3);
+    }
+
+    #[test]
+    fn test_container_width_relayout() {
+        let items: Vec<_> = (0..6)
+            .map(|i| if i == 0 { GridItem::new(i).span(2, 1) } else { GridItem::new(i) })
+            .collect();
+        let mut grid = VirtualGrid::new().responsive(ResponsiveColumns::default());
+        grid.layout.gap = 0.0;
+        grid.layout_items(&items);
+
+        assert!(grid.set_container_width(320.0));
+        assert_eq!(grid.layout.columns, 1);
+        // The wide tile is clamped to the single column
+        assert_eq!(grid.placement(0).unwrap().col_span, 1);
+        assert_eq!(grid.row_count(), 6);
+
+        assert!(!grid.set_container_width(400.0));
+        assert!(grid.set_container_width(1200.0));
+        assert_eq!(grid.layout.columns, 4);
+        assert_eq!(grid.placement(0).unwrap().col_span, 2);
+        assert_eq!(grid.row_count(), 2);
+
+        assert!(!VirtualGrid::new().set_container_width(1200.0));
+    }
+
+    #[test]
+    fn test_column_major_bounds() {
+        let mut grid = VirtualGrid::new().columns(3).items(10);
+        grid.layout = grid.layout.clone().fill_order(FillOrder::ColumnMajor);
+        grid.layout.row_height = 100.0;
+        grid.layout.gap = 10.0;
+        grid.overscan = 0;
+
+        // Four rows per column: 0-3, 4-7, 8-9
+        assert_eq!(grid.row_count(), 4);
+        assert_eq!(cell(&grid, 3), (3, 0));
+        assert_eq!(cell(&grid, 4), (0, 1));
+        assert_eq!(cell(&grid, 9), (1, 2));
+        assert_eq!(grid.placement(10), None);
+
+        let rect = |index| grid.item_bounds(index, 320.0).unwrap();
+        assert_eq!(rect(0), Rect::new(0.0, 0.0, 100.0, 100.0));
+        assert_eq!(rect(3), Rect::new(0.0, 330.0, 100.0, 100.0));
+        assert_eq!(rect(5), Rect::new(110.0, 110.0, 100.0, 100.0));
+        assert_eq!(rect(8), Rect::new(220.0, 0.0, 100.0, 100.0));
+        assert_eq!(rect(9), Rect::new(220.0, 110.0, 100.0, 100.0));
+        assert_eq!(grid.content_height(), 430.0);
+    }
+
+    #[test]
+    fn test_column_major_visible_range() {
+        let mut grid = VirtualGrid::new().columns(3).items(10);
+        grid.layout = grid.layout.clone().fill_order(FillOrder::ColumnMajor);
+        grid.layout.row_height = 100.0;
+        grid.layout.gap = 10.0;
+        grid.overscan = 0;
+
+        // First row: the top item of every column
+        assert_eq!(grid.visible_range(0.0, 100.0), 0..9);
+        assert_eq!(grid.visible_indices(0.0, 100.0), vec![0, 4, 8]);
+        // Rows 2-3: the short last column has nothing there
+        assert_eq!(grid.visible_range(230.0, 200.0), 2..8);
+        assert_eq!(grid.visible_ranges(230.0, 200.0), vec![2..4, 6..8]);
+        assert_eq!(grid.visible_indices(230.0, 200.0), vec![2, 3, 6, 7]);
+        assert!(grid.visible_indices(1000.0, 100.0).is_empty());
+
+        grid.overscan = 1;
+        assert_eq!(grid.visible_indices(230.0, 50.0), vec![1, 2, 3, 5, 6, 7, 9]);
+    }
 }
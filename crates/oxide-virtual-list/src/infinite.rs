@@ -1,6 +1,7 @@
 //! Infinite scroll support for virtual lists.
 
 use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
 
 /// Loading state for infinite scroll
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -54,6 +55,19 @@ impl Default for InfiniteScrollConfig {
     }
 }
 
+impl InfiniteScrollConfig {
+    /// Set the debounce window for load-more triggers
+    pub fn debounce(mut self, delay: Duration) -> Self {
+        self.debounce_ms = delay.as_millis().min(u32::MAX as u128) as u32;
+        self
+    }
+
+    /// Get the debounce window
+    pub fn debounce_duration(&self) -> Duration {
+        Duration::from_millis(self.debounce_ms as u64)
+    }
+}
+
 /// State for infinite scroll
 #[derive(Debug, Clone, Default)]
 pub struct InfiniteScrollState {
@@ -63,6 +77,10 @@ pub struct InfiniteScrollState {
     pub items_loaded: usize,
     /// Whether more items are available
     pub has_more: bool,
+    /// Whether a load was requested and not yet finished or failed
+    pub load_requested: bool,
+    /// When the last load was requested
+    pub last_request: Option<Instant>,
 }
 
 /// Infinite scroll controller
@@ -110,6 +128,38 @@ impl InfiniteScroll {
         }
     }
 
+    /// Check if a load should be requested for a scroll event
+    ///
+    /// Fires at most once per debounce window, and never again until the
+    /// loading state returns to idle or error.
+    pub fn should_load_more(
+        &mut self,
+        visible_end: usize,
+        total_items: usize,
+        now: Instant,
+    ) -> bool {
+        if self.state.load_requested || !self.should_load(visible_end, total_items) {
+            return false;
+        }
+        if let Some(last) = self.state.last_request {
+            if now.saturating_duration_since(last) < self.config.debounce_duration() {
+                return false;
+            }
+        }
+
+        self.state.load_requested = true;
+        self.state.last_request = Some(now);
+        true
+    }
+
+    /// Set the loading state, re-arming the trigger on idle or error
+    pub fn set_loading_state(&mut self, loading_state: LoadingState) {
+        self.state.loading_state = loading_state;
+        if matches!(loading_state, LoadingState::Idle | LoadingState::Error) {
+            self.state.load_requested = false;
+        }
+    }
+
     /// Start loading
     pub fn start_loading(&mut self) {
         self.state.loading_state = LoadingState::Loading;
@@ -117,13 +167,68 @@ impl InfiniteScroll {
 
     /// Finish loading
     pub fn finish_loading(&mut self, new_items: usize, has_more: bool) {
-        self.state.loading_state = LoadingState::Idle;
+        self.set_loading_state(LoadingState::Idle);
         self.state.items_loaded += new_items;
         self.state.has_more = has_more;
     }
 
     /// Set error state
     pub fn set_error(&mut self) {
-        self.state.loading_state = LoadingState::Error;
+        self.set_loading_state(LoadingState::Error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scroll() -> InfiniteScroll {
+        let mut scroll = InfiniteScroll::new().trigger(LoadMoreTrigger::Threshold(5));
+        scroll.config = scroll.config.debounce(Duration::from_millis(200));
+        scroll.state.has_more = true;
+        scroll
+    }
+
+    #[test]
+    fn test_debounce_dedupes_triggers() {
+        let mut scroll = scroll();
+        let start = Instant::now();
+
+        let requests = [0, 40, 90]
+            .iter()
+            .filter(|&&ms| scroll.should_load_more(97, 100, start + Duration::from_millis(ms)))
+            .count();
+        assert_eq!(requests, 1);
+        assert!(scroll.state.load_requested);
+    }
+
+    #[test]
+    fn test_no_retrigger_until_idle() {
+        let mut scroll = scroll();
+        let start = Instant::now();
+
+        assert!(scroll.should_load_more(97, 100, start));
+        scroll.start_loading();
+        // Well past the debounce window but the load has not finished
+        assert!(!scroll.should_load_more(98, 100, start + Duration::from_secs(5)));
+
+        scroll.finish_loading(20, true);
+        assert!(scroll.should_load_more(117, 120, start + Duration::from_secs(6)));
+    }
+
+    #[test]
+    fn test_error_rearms_after_retry() {
+        let mut scroll = scroll();
+        let start = Instant::now();
+
+        assert!(scroll.should_load_more(97, 100, start));
+        scroll.set_error();
+        assert!(!scroll.state.load_requested);
+        assert!(!scroll.should_load_more(97, 100, start + Duration::from_secs(1)));
+
+        scroll.set_loading_state(LoadingState::Idle);
+        // Still inside the debounce window of the last request
+        assert!(!scroll.should_load_more(97, 100, start + Duration::from_millis(100)));
+        assert!(scroll.should_load_more(97, 100, start + Duration::from_secs(1)));
     }
 }
@@ -10,6 +10,7 @@
 //! - **MasonryGrid**: Pinterest-style waterfall layout
 //! - **Infinite scroll**: Load more items on demand
 //! - **Selection**: Single/multi selection with keyboard support
+//! - **Reorder**: Drag-to-reorder with sliding item offsets
 //! - **Scroll features**: Scroll to index, position restoration, sticky headers
 //!
 //! # Example
@@ -24,7 +25,7 @@
 //!     .overscan(5);
 //!
 //! // Calculate visible range for viewport
-//! let range = list.visible_range(0.0, 600.0);
+//! let range = list.visible_range_for(0.0, 600.0);
 //! ```
 
 mod grid;
@@ -32,24 +33,31 @@ mod infinite;
 mod list;
 mod masonry;
 mod measure;
+mod reorder;
 mod scroll;
 mod selection;
 
 pub use grid::{
-    ColumnWidth, GridItem, GridLayout, ResponsiveColumns, VirtualGrid, VirtualGridState,
+    ColumnWidth, FillOrder, GridItem, GridLayout, GridPlacement, ResponsiveColumns, VirtualGrid,
+    VirtualGridState,
 };
 pub use infinite::{
     InfiniteScroll, InfiniteScrollConfig, InfiniteScrollState, LoadMoreTrigger, LoadingState,
 };
 pub use list::{
-    ItemHeight, ListItem, SectionConfig, SectionHeader, Separator, SeparatorStyle, VirtualList,
-    VirtualListConfig, VirtualListState,
+    Anchor, ItemHeight, ListItem, NavOutcome, SectionConfig, SectionHeader, Separator,
+    SeparatorPlacement, SeparatorStyle, VirtualList, VirtualListConfig, VirtualListState,
 };
-pub use masonry::{MasonryColumn, MasonryConfig, MasonryGrid, MasonryItem, MasonryState};
-pub use measure::{ItemMeasureCache, ItemMeasurement, MeasureContext, MeasureStrategy};
+pub use masonry::{
+    BalanceStrategy, MasonryColumn, MasonryConfig, MasonryGrid, MasonryItem, MasonryState,
+};
+pub use measure::{
+    ItemMeasureCache, ItemMeasurement, LayoutInvalidation, MeasureContext, MeasureStrategy,
+};
+pub use reorder::{ReorderChange, ReorderController};
 pub use scroll::{
-    ScrollBehavior, ScrollConfig, ScrollController, ScrollDirection, ScrollEvent, ScrollPosition,
-    ScrollState, StickyConfig, StickyElement,
+    Easing, ScrollAlignment, ScrollBehavior, ScrollConfig, ScrollController, ScrollDirection,
+    ScrollEvent, ScrollPosition, ScrollRestoration, ScrollState, StickyConfig, StickyElement,
 };
 pub use selection::{
     KeyboardAction, MultiSelectMode, SelectionChange, SelectionConfig, SelectionController,
@@ -61,8 +69,10 @@ pub mod prelude {
     pub use crate::{
         // Grid
         ColumnWidth,
+        FillOrder,
         GridItem,
         GridLayout,
+        GridPlacement,
         ResponsiveColumns,
         VirtualGrid,
         VirtualGridState,
@@ -73,16 +83,20 @@ pub mod prelude {
         LoadMoreTrigger,
         LoadingState,
         // List
+        Anchor,
         ItemHeight,
         ListItem,
+        NavOutcome,
         SectionConfig,
         SectionHeader,
         Separator,
+        SeparatorPlacement,
         SeparatorStyle,
         VirtualList,
         VirtualListConfig,
         VirtualListState,
         // Masonry
+        BalanceStrategy,
         MasonryColumn,
         MasonryConfig,
         MasonryGrid,
@@ -91,15 +105,22 @@ pub mod prelude {
         // Measure
         ItemMeasureCache,
         ItemMeasurement,
+        LayoutInvalidation,
         MeasureContext,
         MeasureStrategy,
+        // Reorder
+        ReorderChange,
+        ReorderController,
         // Scroll
+        Easing,
+        ScrollAlignment,
         ScrollBehavior,
         ScrollConfig,
         ScrollController,
         ScrollDirection,
         ScrollEvent,
         ScrollPosition,
+        ScrollRestoration,
         ScrollState,
         StickyConfig,
         StickyElement,
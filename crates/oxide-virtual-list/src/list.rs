@@ -2,9 +2,13 @@
 //!
 //! Provides efficient rendering of large lists by only rendering visible items.
 
+use crate::measure::{ItemMeasureCache, ItemMeasurement, LayoutInvalidation, PrefixSums};
+use crate::scroll::ScrollAlignment;
+use crate::selection::{KeyboardAction, SelectionState};
 use crate::{Rect, Result, Size, VisibleRange, VirtualListError};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
 
 /// Item height configuration
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -24,6 +28,16 @@ impl Default for ItemHeight {
     }
 }
 
+/// Which edge of the content the viewport stays anchored to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum Anchor {
+    /// Content grows downward from the top
+    #[default]
+    Top,
+    /// Viewport follows the newest items at the bottom (chat logs)
+    Bottom,
+}
+
 /// Separator style between items
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum SeparatorStyle {
@@ -191,6 +205,40 @@ impl ListItem {
     }
 }
 
+/// A separator drawn below an item
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SeparatorPlacement {
+    /// Index of the item above the separator
+    pub index: usize,
+    /// Top edge of the separator (viewport coordinates, like item bounds)
+    pub y: f32,
+    /// Separator style
+    pub style: SeparatorStyle,
+}
+
+impl SeparatorPlacement {
+    /// Get the separator bounds within an item's horizontal extent
+    ///
+    /// Inset separators are shortened by their left and right insets.
+    pub fn bounds(&self, x: f32, width: f32) -> Rect {
+        match self.style {
+            SeparatorStyle::None => Rect::new(x, self.y, width, 0.0),
+            SeparatorStyle::Full { thickness, .. } => Rect::new(x, self.y, width, thickness),
+            SeparatorStyle::Inset {
+                thickness,
+                left_inset,
+                right_inset,
+                ..
+            } => Rect::new(
+                x + left_inset,
+                self.y,
+                (width - left_inset - right_inset).max(0.0),
+                thickness,
+            ),
+        }
+    }
+}
+
 /// Configuration for the virtual list
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VirtualListConfig {
@@ -206,6 +254,15 @@ pub struct VirtualListConfig {
     pub padding: f32,
     /// Whether pull-to-refresh is enabled
     pub pull_to_refresh: bool,
+    /// Items pinned above the scrolling content, in display order
+    #[serde(default)]
+    pub pinned: Vec<usize>,
+    /// Edge the viewport stays anchored to as items are added
+    #[serde(default)]
+    pub anchor: Anchor,
+    /// Maximum number of measured heights kept (variable height mode)
+    #[serde(default)]
+    pub measure_capacity: Option<usize>,
 }
 
 impl Default for VirtualListConfig {
@@ -217,10 +274,50 @@ impl Default for VirtualListConfig {
             smooth_scroll: true,
             padding: 0.0,
             pull_to_refresh: false,
+            pinned: Vec::new(),
+            anchor: Anchor::default(),
+            measure_capacity: None,
         }
     }
 }
 
+impl VirtualListConfig {
+    /// Pin items to the top of the viewport, outside the scrolling flow
+    pub fn pinned(mut self, indices: Vec<usize>) -> Self {
+        self.pinned.clear();
+        for index in indices {
+            if !self.pinned.contains(&index) {
+                self.pinned.push(index);
+            }
+        }
+        self
+    }
+
+    /// Set the edge the viewport stays anchored to
+    pub fn anchor(mut self, anchor: Anchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    /// Bound the number of measured heights kept
+    pub fn measure_capacity(mut self, capacity: usize) -> Self {
+        self.measure_capacity = Some(capacity);
+        self
+    }
+}
+
+/// Result of a keyboard navigation action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NavOutcome {
+    /// Newly focused item
+    pub focus: Option<usize>,
+    /// Item to scroll into view to keep the focus visible, and how to align it
+    pub scroll_to: Option<(usize, ScrollAlignment)>,
+}
+
+/// Distance from the end (in pixels) still treated as scrolled to the bottom
+const BOTTOM_TOLERANCE: f32 = 1.0;
+
 /// State of the virtual list
 #[derive(Debug, Clone)]
 pub struct VirtualListState {
@@ -235,9 +332,24 @@ pub struct VirtualListState {
     /// Currently visible range (with overscan)
     pub visible_range: VisibleRange,
     /// Measured item heights (for variable height mode)
+    ///
+    /// Mirrors [`measure_cache`](Self::measure_cache), so it holds at most
+    /// [`VirtualListConfig::measure_capacity`] entries.
     pub measured_heights: HashMap<usize, f32>,
-    /// Cached item positions (Y offsets)
+    /// Measurements driving variable height layout
+    ///
+    /// Bounded by [`VirtualListConfig::measure_capacity`]; evicted items fall
+    /// back to the estimated height.
+    pub measure_cache: ItemMeasureCache,
+    /// Cached item positions (Y offsets, fixed height mode only)
+    ///
+    /// Left empty for variable heights, where measurements would make a full
+    /// table stale; use [`offset_of`](Self::offset_of) in every mode.
     pub item_positions: Vec<f32>,
+    /// Offset of the first item (leading padding)
+    item_origin: f32,
+    /// Prefix sums of item extents (height plus separator)
+    item_extents: PrefixSums,
     /// Whether the list is currently scrolling
     pub is_scrolling: bool,
     /// Last scroll direction (1 = down, -1 = up, 0 = stopped)
@@ -246,6 +358,8 @@ pub struct VirtualListState {
     pub pull_progress: f32,
     /// Whether refresh is currently in progress
     pub is_refreshing: bool,
+    /// Indices of collapsed sections
+    pub collapsed_sections: HashSet<usize>,
 }
 
 impl Default for VirtualListState {
@@ -257,11 +371,71 @@ impl Default for VirtualListState {
             viewport_height: 0.0,
             visible_range: VisibleRange::empty(),
             measured_heights: HashMap::new(),
+            measure_cache: ItemMeasureCache::new(),
             item_positions: Vec::new(),
+            item_origin: 0.0,
+            item_extents: PrefixSums::default(),
             is_scrolling: false,
             scroll_direction: 0,
             pull_progress: 0.0,
             is_refreshing: false,
+            collapsed_sections: HashSet::new(),
+        }
+    }
+}
+
+impl VirtualListState {
+    /// Mark a section as collapsed or expanded, returning whether it changed
+    ///
+    /// Takes effect on the next layout; [`VirtualList::set_collapsed`] applies
+    /// it immediately.
+    pub fn set_collapsed(&mut self, section: usize, collapsed: bool) -> bool {
+        if collapsed {
+            self.collapsed_sections.insert(section)
+        } else {
+            self.collapsed_sections.remove(&section)
+        }
+    }
+
+    /// Check if a section is collapsed
+    pub fn is_collapsed(&self, section: usize) -> bool {
+        self.collapsed_sections.contains(&section)
+    }
+
+    /// Check if the viewport is scrolled to the end of the content
+    pub fn is_at_bottom(&self) -> bool {
+        self.scroll_offset >= self.content_height - self.viewport_height - BOTTOM_TOLERANCE
+    }
+
+    /// Find the item at a content Y offset
+    ///
+    /// Returns the item index and the offset from that item's top edge, or
+    /// `None` when the offset lies outside the items. Separator space below
+    /// an item counts as part of that item.
+    pub fn index_at_offset(&self, y: f32) -> Option<(usize, f32)> {
+        if y < self.item_origin
+            || y >= self.content_height
+            || self.item_extents.len() != self.total_items
+        {
+            return None;
+        }
+
+        let index = self.item_extents.count_within(f64::from(y - self.item_origin));
+        (index < self.total_items).then(|| (index, y - self.offset_of(index)))
+    }
+
+    /// Get the Y offset of an item in O(log n)
+    ///
+    /// Indices past the last item return the offset just after it.
+    pub fn offset_of(&self, index: usize) -> f32 {
+        (f64::from(self.item_origin) + self.item_extents.prefix(index)) as f32
+    }
+
+    /// Drop mirrored heights the measure cache has evicted
+    fn sync_measured_heights(&mut self) {
+        if self.measured_heights.len() > self.measure_cache.len() {
+            let cache = &self.measure_cache;
+            self.measured_heights.retain(|&index, _| cache.get(index).is_some());
         }
     }
 }
@@ -294,6 +468,7 @@ impl VirtualList {
     pub fn items(mut self, count: usize) -> Self {
         self.state.total_items = count;
         self.recalculate_layout();
+        self.follow_bottom();
         self
     }
 
@@ -314,6 +489,13 @@ impl VirtualList {
         self.item_height(ItemHeight::Variable { estimated })
     }
 
+    /// Bound the number of measured heights kept for variable height items
+    pub fn measure_capacity(mut self, capacity: usize) -> Self {
+        self.config = self.config.measure_capacity(capacity);
+        self.recalculate_layout();
+        self
+    }
+
     /// Set overscan (extra items rendered above/below viewport)
     pub fn overscan(mut self, count: usize) -> Self {
         self.config.overscan = count;
@@ -353,10 +535,25 @@ impl VirtualList {
         self
     }
 
+    /// Pin items to the top of the viewport (see [`VirtualListConfig::pinned`])
+    pub fn pinned(mut self, indices: Vec<usize>) -> Self {
+        self.config = self.config.pinned(indices);
+        self.recalculate_layout();
+        self
+    }
+
+    /// Anchor the viewport to the top or bottom of the content
+    pub fn anchor(mut self, anchor: Anchor) -> Self {
+        self.config.anchor = anchor;
+        self.follow_bottom();
+        self
+    }
+
     /// Set the viewport height
     pub fn viewport_height(mut self, height: f32) -> Self {
         self.state.viewport_height = height;
         self.update_visible_range();
+        self.follow_bottom();
         self
     }
 
@@ -375,10 +572,53 @@ impl VirtualList {
         &mut self.state
     }
 
+    /// Collapse or expand a section
+    ///
+    /// A collapsed section keeps its header but its items take no space and
+    /// are skipped by virtualization.
+    pub fn set_collapsed(&mut self, section: usize, collapsed: bool) {
+        if self.state.set_collapsed(section, collapsed) {
+            self.recalculate_layout();
+            self.set_scroll_offset(self.state.scroll_offset);
+        }
+    }
+
+    /// Check if an item belongs to a collapsed section
+    pub fn is_item_collapsed(&self, index: usize) -> bool {
+        self.collapsed_iter().any(|range| range.contains(&index))
+    }
+
     /// Update the total item count
+    ///
+    /// With [`Anchor::Bottom`] the viewport keeps following the end if it was
+    /// there, and otherwise keeps the item at the top of the viewport in place.
     pub fn set_item_count(&mut self, count: usize) {
-        self.state.total_items = count;
-        self.recalculate_layout();
+        self.preserve_anchor(0, |list| {
+            list.state.total_items = count;
+            list.recalculate_layout();
+        });
+    }
+
+    /// Add items after the current last item
+    pub fn append_items(&mut self, count: usize) {
+        self.set_item_count(self.state.total_items + count);
+    }
+
+    /// Add items before the current first item
+    ///
+    /// Measured heights and pinned indices shift with their items, and the
+    /// item at the top of the viewport stays in place.
+    pub fn prepend_items(&mut self, count: usize) {
+        self.preserve_anchor(count, |list| {
+            list.state.measure_cache.shift_indices(count);
+            list.state.measured_heights =
+                list.state.measured_heights.drain().map(|(i, h)| (i + count, h)).collect();
+            for index in &mut list.config.pinned {
+                *index += count;
+            }
+            list.state.total_items += count;
+            list.recalculate_layout();
+        });
     }
 
     /// Get the total content height
@@ -392,14 +632,21 @@ impl VirtualList {
     }
 
     /// Calculate visible range for given scroll position and viewport
+    ///
+    /// Pinned items take up the top of the viewport and are skipped by
+    /// [`visible_items`](Self::visible_items) even when inside the range.
     pub fn visible_range_for(&self, scroll_offset: f32, viewport_height: f32) -> VisibleRange {
+        let viewport_height = viewport_height - self.pinned_height();
         if self.state.total_items == 0 || viewport_height <= 0.0 {
             return VisibleRange::empty();
         }
 
+        let bottom = scroll_offset + viewport_height;
         let start = self.index_at_offset(scroll_offset);
-        let end = self.index_at_offset(scroll_offset + viewport_height);
-        let end = (end + 1).min(self.state.total_items);
+        let last = self.index_at_offset(bottom);
+        // The item at the bottom edge is only visible if it starts above it
+        let end = if self.offset_for_index(last) < bottom { last + 1 } else { last };
+        let end = end.max(start + 1).min(self.state.total_items);
 
         VisibleRange::new(start, end).with_overscan(self.config.overscan, self.state.total_items)
     }
@@ -421,7 +668,8 @@ impl VirtualList {
 
     /// Get the maximum scroll offset
     pub fn max_scroll_offset(&self) -> f32 {
-        (self.state.content_height - self.state.viewport_height).max(0.0)
+        let scroll_area = self.state.viewport_height - self.pinned_height();
+        (self.state.content_height - scroll_area).max(0.0)
     }
 
     /// Scroll by a delta amount
@@ -464,11 +712,50 @@ impl VirtualList {
     }
 
     /// Update measured height for a variable-height item
-    pub fn set_item_height(&mut self, index: usize, height: f32) {
-        if matches!(self.config.item_height, ItemHeight::Variable { .. }) {
-            self.state.measured_heights.insert(index, height);
-            self.recalculate_layout();
+    ///
+    /// Only the offset table entry for the item is updated, so measurements
+    /// landing on huge lists stay O(log n). Returns the resulting layout
+    /// change, if the height differs from what was laid out.
+    pub fn set_item_height(&mut self, index: usize, height: f32) -> Option<LayoutInvalidation> {
+        if !matches!(self.config.item_height, ItemHeight::Variable { .. }) {
+            return None;
+        }
+
+        let previous = self.get_item_height(index);
+        let evicted = self
+            .state
+            .measure_cache
+            .update(index, ItemMeasurement::from_height(height))
+            .and_then(|invalidation| invalidation.evicted);
+        self.state.measured_heights.insert(index, height);
+        self.state.sync_measured_heights();
+        let shift = self.get_item_height(index) - previous;
+        let update = |list: &mut Self| {
+            if list.is_pinned(index) || evicted.is_some_and(|(i, _)| list.is_pinned(i)) {
+                list.recalculate_layout();
+                return;
+            }
+            for (i, delta) in [(index, shift)].into_iter().chain(evicted) {
+                if i < list.state.item_extents.len() && !list.is_item_collapsed(i) {
+                    list.state.item_extents.add(i, f64::from(delta));
+                }
+            }
+            list.update_content_height();
+            list.update_visible_range();
+        };
+        if self.config.anchor == Anchor::Bottom {
+            self.preserve_anchor(0, update);
+        } else {
+            update(self);
         }
+
+        if shift == 0.0 && evicted.is_none() {
+            return None;
+        }
+        Some(LayoutInvalidation {
+            evicted,
+            ..LayoutInvalidation::new(index, shift)
+        })
     }
 
     /// Get the height for an item
@@ -476,91 +763,90 @@ impl VirtualList {
         match self.config.item_height {
             ItemHeight::Fixed(h) => h,
             ItemHeight::Variable { estimated } => {
-                *self.state.measured_heights.get(&index).unwrap_or(&estimated)
+                self.state.measure_cache.get(index).map_or(estimated, |m| m.height)
             }
         }
     }
 
     /// Get the Y offset for an item
     pub fn offset_for_index(&self, index: usize) -> f32 {
-        if index == 0 {
-            return self.config.padding;
-        }
+        let indexed = index.min(self.state.item_extents.len());
+        let mut offset = self.state.offset_of(indexed);
 
-        if !self.state.item_positions.is_empty() && index < self.state.item_positions.len() {
-            return self.state.item_positions[index];
+        // Items past the laid-out range are positioned by their estimated size
+        for i in indexed..index {
+            offset += self.get_item_height(i) + self.config.separator.height();
         }
+        offset
+    }
 
-        // Fallback calculation for fixed height
-        match self.config.item_height {
-            ItemHeight::Fixed(h) => {
-                let item_total_height = h + self.config.separator.height();
-                self.config.padding + (index as f32 * item_total_height)
-            }
-            ItemHeight::Variable { estimated } => {
-                // Calculate on the fly for variable heights
-                let mut offset = self.config.padding;
-                for i in 0..index {
-                    let height = self
-                        .state
-                        .measured_heights
-                        .get(&i)
-                        .copied()
-                        .unwrap_or(estimated);
-                    offset += height + self.config.separator.height();
-                }
-                offset
-            }
-        }
+    /// Get the Y offset for an item (alias of [`offset_for_index`](Self::offset_for_index))
+    pub fn offset_of(&self, index: usize) -> f32 {
+        self.offset_for_index(index)
     }
 
-    /// Find the item index at a given Y offset
+    /// Find the item index at a given Y offset in O(log n)
+    ///
+    /// Offsets before the first item map to 0 and offsets past the last item
+    /// are clamped to the last index.
     pub fn index_at_offset(&self, offset: f32) -> usize {
-        if self.state.total_items == 0 {
-            return 0;
-        }
-
-        let adjusted_offset = offset - self.config.padding;
-        if adjusted_offset <= 0.0 {
+        if self.state.total_items == 0 || offset <= self.state.item_origin {
             return 0;
         }
 
-        match self.config.item_height {
-            ItemHeight::Fixed(h) => {
-                let item_total_height = h + self.config.separator.height();
-                if item_total_height <= 0.0 {
-                    return 0;
-                }
-                let index = (adjusted_offset / item_total_height).floor() as usize;
-                index.min(self.state.total_items.saturating_sub(1))
-            }
-            ItemHeight::Variable { estimated } => {
-                // Binary search through cached positions
-                if !self.state.item_positions.is_empty() {
-                    match self
-                        .state
-                        .item_positions
-                        .binary_search_by(|pos| pos.partial_cmp(&offset).unwrap())
-                    {
-                        Ok(i) => i,
-                        Err(i) => i.saturating_sub(1).min(self.state.total_items - 1),
-                    }
-                } else {
-                    // Fallback for uncached
-                    let item_total_height = estimated + self.config.separator.height();
-                    let index = (adjusted_offset / item_total_height).floor() as usize;
-                    index.min(self.state.total_items.saturating_sub(1))
-                }
-            }
-        }
+        let adjusted = f64::from(offset - self.state.item_origin);
+        let index = self.state.item_extents.count_within(adjusted);
+        index.min(self.state.total_items - 1)
     }
 
     /// Get bounds for an item
+    ///
+    /// Scrolling items are placed below the pinned items; pinned items get
+    /// their slot in the pinned stack.
     pub fn item_bounds(&self, index: usize, viewport_width: f32) -> Rect {
-        let y = self.offset_for_index(index) - self.state.scroll_offset;
+        let width = viewport_width - 2.0 * self.config.padding;
+        if let Some(bounds) = self.pinned_bounds(index, width) {
+            return bounds;
+        }
+
+        let y = self.offset_for_index(index) - self.state.scroll_offset + self.pinned_height();
         let height = self.get_item_height(index);
 
-        Rect::new(self.config.padding, y, viewport_width - 2.0 * self.config.padding, height)
+        Rect::new(self.config.padding, y, width, height)
+    }
+
+    /// Get the separators to draw between items of a range
+    ///
+    /// There is no separator after the last item, after the last item of a
+    /// section (where the next header starts), or next to pinned and
+    /// collapsed items.
+    pub fn separators_for(&self, range: VisibleRange) -> Vec<SeparatorPlacement> {
+        let style = self.config.separator.style;
+        if style == SeparatorStyle::None {
+            return Vec::new();
+        }
+
+        let total = self.state.total_items;
+        let section_start = |index: usize| {
+            self.sections
+                .as_ref()
+                .is_some_and(|s| s.sections.iter().any(|header| header.start_index == index))
+        };
+        let flows = |index: usize| !self.is_pinned(index) && !self.is_item_collapsed(index);
+
+        range
+            .iter()
+            .filter(|&index| index + 1 < total && flows(index) && flows(index + 1))
+            .filter(|&index| !section_start(index + 1))
+            .map(|index| {
+                let bounds = self.item_bounds(index, 0.0);
+                SeparatorPlacement {
+                    index,
+                    y: bounds.y + bounds.height,
+                    style,
+                }
+            })
+            .collect()
     }
 
     /// Get visible items with their bounds
@@ -568,6 +854,7 @@ impl VirtualList {
         let range = self.state.visible_range;
         range
             .iter()
+            .filter(|&index| !self.is_pinned(index) && !self.is_item_collapsed(index))
             .map(|index| {
                 let bounds = self.item_bounds(index, viewport_width);
                 ListItem::new(index, bounds)
@@ -575,9 +862,147 @@ impl VirtualList {
             .collect()
     }
 
+    /// Check if an item is pinned above the scrolling content
+    pub fn is_pinned(&self, index: usize) -> bool {
+        index < self.state.total_items && self.config.pinned.contains(&index)
+    }
+
+    /// Get the height of the pinned item stack
+    pub fn pinned_height(&self) -> f32 {
+        self.config
+            .pinned
+            .iter()
+            .filter(|&&index| index < self.state.total_items)
+            .map(|&index| self.get_item_height(index) + self.config.separator.height())
+            .sum()
+    }
+
+    /// Get pinned items with their bounds, stacked at the top of the viewport
+    pub fn pinned_layout(&self, viewport_width: f32) -> Vec<ListItem> {
+        let width = viewport_width - 2.0 * self.config.padding;
+        self.config
+            .pinned
+            .iter()
+            .filter_map(|&index| Some(ListItem::new(index, self.pinned_bounds(index, width)?)))
+            .collect()
+    }
+
+    /// Bounds of a pinned item within the pinned stack
+    fn pinned_bounds(&self, index: usize, width: f32) -> Option<Rect> {
+        if !self.is_pinned(index) {
+            return None;
+        }
+
+        let mut y = 0.0;
+        for &pinned in self.config.pinned.iter().filter(|&&i| i < self.state.total_items) {
+            let height = self.get_item_height(pinned);
+            if pinned == index {
+                return Some(Rect::new(self.config.padding, y, width, height));
+            }
+            y += height + self.config.separator.height();
+        }
+        None
+    }
+
+    /// Handle a keyboard navigation action
+    ///
+    /// Moves the focus from `selection` and reports the scroll needed to keep
+    /// it fully visible; no scroll target is returned when it already is.
+    /// Selection-only actions keep the focus where it is.
+    pub fn on_keyboard(&self, action: KeyboardAction, selection: &SelectionState) -> NavOutcome {
+        let total = self.state.total_items;
+        if total == 0 {
+            return NavOutcome::default();
+        }
+
+        let last = total - 1;
+        let (focus, alignment) = match (action, selection.focus.map(|f| f.min(last))) {
+            (KeyboardAction::End, _) => (last, ScrollAlignment::End),
+            (KeyboardAction::Home, _) | (_, None) => (0, ScrollAlignment::Start),
+            (KeyboardAction::SelectNext | KeyboardAction::ExtendDown, Some(focus)) => {
+                ((focus + 1).min(last), ScrollAlignment::Nearest)
+            }
+            (KeyboardAction::SelectPrevious | KeyboardAction::ExtendUp, Some(focus)) => {
+                (focus.saturating_sub(1), ScrollAlignment::Nearest)
+            }
+            (KeyboardAction::PageDown, Some(focus)) => {
+                ((focus + self.page_size()).min(last), ScrollAlignment::End)
+            }
+            (KeyboardAction::PageUp, Some(focus)) => {
+                (focus.saturating_sub(self.page_size()), ScrollAlignment::Start)
+            }
+            (_, Some(focus)) => (focus, ScrollAlignment::Nearest),
+        };
+
+        // Land on the nearest item outside collapsed sections
+        let forward = !matches!(
+            action,
+            KeyboardAction::SelectPrevious
+                | KeyboardAction::ExtendUp
+                | KeyboardAction::PageUp
+                | KeyboardAction::End
+        );
+        let shown = |index: &usize| !self.is_item_collapsed(*index);
+        let mut ahead = (focus + 1..total).filter(shown);
+        let mut behind = (0..focus).rev().filter(shown);
+        let nearest = if shown(&focus) {
+            Some(focus)
+        } else if forward {
+            ahead.next().or_else(|| behind.next())
+        } else {
+            behind.next().or_else(|| ahead.next())
+        };
+        let Some(focus) = nearest else {
+            return NavOutcome::default();
+        };
+
+        NavOutcome {
+            focus: Some(focus),
+            scroll_to: (!self.is_fully_visible(focus)).then_some((focus, alignment)),
+        }
+    }
+
+    /// Number of items a page up/down moves by
+    ///
+    /// Derived from the scrolling viewport height and the average item extent.
+    fn page_size(&self) -> usize {
+        let total = self.state.total_items;
+        let collapsed = self.collapsed_ranges();
+        let pinned = self
+            .config
+            .pinned
+            .iter()
+            .filter(|&&i| i < total && !collapsed.iter().any(|range| range.contains(&i)))
+            .count();
+        let hidden = pinned + collapsed.iter().map(ExactSizeIterator::len).sum::<usize>();
+        let flow_items = total.saturating_sub(hidden);
+        let extent = self.offset_for_index(total) - self.state.item_origin;
+        if flow_items == 0 || extent <= 0.0 {
+            return 1;
+        }
+
+        let average = extent / flow_items as f32;
+        let area = self.state.viewport_height - self.pinned_height();
+        ((area / average).floor() as usize).max(1)
+    }
+
+    /// Check if an item lies entirely within the scrolling viewport
+    fn is_fully_visible(&self, index: usize) -> bool {
+        if self.is_pinned(index) {
+            return true;
+        }
+
+        let top = self.offset_for_index(index);
+        let bottom = top + self.get_item_height(index);
+        let scroll = self.state.scroll_offset;
+        let area = self.state.viewport_height - self.pinned_height();
+        top >= scroll && bottom <= scroll + area
+    }
+
     /// Check if an item is currently visible
     pub fn is_item_visible(&self, index: usize) -> bool {
-        self.state.visible_range.contains(index)
+        self.is_pinned(index)
+            || (self.state.visible_range.contains(index) && !self.is_item_collapsed(index))
     }
 
     /// Get sticky header that should be displayed (if any)
@@ -597,60 +1022,124 @@ impl VirtualList {
 
     /// Recalculate the layout (positions and content height)
     fn recalculate_layout(&mut self) {
-        if self.state.total_items == 0 {
-            self.state.content_height = self.config.padding * 2.0;
-            self.state.item_positions.clear();
-            return;
-        }
-
         let separator_height = self.config.separator.height();
-
+        self.state.item_origin = self.config.padding;
+        self.state.item_positions.clear();
+
+        // Pinned items are drawn above the content and items of collapsed
+        // sections are hidden, so neither takes space in the flow
+        let pinned = &self.config.pinned;
+        let collapsed = self.collapsed_ranges();
+        let hidden = |i: usize| {
+            pinned.contains(&i) || collapsed.iter().any(|range| range.contains(&i))
+        };
         match self.config.item_height {
             ItemHeight::Fixed(h) => {
-                // Simple calculation for fixed heights
                 let item_total_height = h + separator_height;
-                self.state.content_height = self.config.padding * 2.0
-                    + (self.state.total_items as f32 * item_total_height)
-                    - separator_height; // No separator after last item
+                self.state.item_extents = PrefixSums::build((0..self.state.total_items).map(|i| {
+                    if hidden(i) {
+                        0.0
+                    } else {
+                        item_total_height
+                    }
+                }));
 
                 // Cache positions for fast lookup
-                self.state.item_positions.clear();
                 self.state.item_positions.reserve(self.state.total_items);
-                for i in 0..self.state.total_items {
-                    self.state
-                        .item_positions
-                        .push(self.config.padding + (i as f32 * item_total_height));
+                if pinned.is_empty() && collapsed.is_empty() {
+                    for i in 0..self.state.total_items {
+                        self.state
+                            .item_positions
+                            .push(self.config.padding + (i as f32 * item_total_height));
+                    }
+                } else {
+                    for i in 0..self.state.total_items {
+                        let position = self.state.offset_of(i);
+                        self.state.item_positions.push(position);
+                    }
                 }
             }
             ItemHeight::Variable { estimated } => {
-                // Calculate positions incrementally
-                self.state.item_positions.clear();
-                self.state.item_positions.reserve(self.state.total_items);
-
-                let mut offset = self.config.padding;
-                for i in 0..self.state.total_items {
-                    self.state.item_positions.push(offset);
-                    let height = self
-                        .state
-                        .measured_heights
-                        .get(&i)
-                        .copied()
-                        .unwrap_or(estimated);
-                    offset += height + separator_height;
-                }
-
-                // Remove last separator
-                self.state.content_height = offset - separator_height + self.config.padding;
+                // Evicted measurements fall back to the list's estimate
+                self.state.measure_cache.set_default(ItemMeasurement::from_height(estimated));
+                self.state.measure_cache.set_capacity(self.config.measure_capacity);
+                self.state.sync_measured_heights();
+                let heights = &self.state.measure_cache;
+                self.state.item_extents = PrefixSums::build((0..self.state.total_items).map(|i| {
+                    if hidden(i) {
+                        0.0
+                    } else {
+                        heights.get(i).map_or(estimated, |m| m.height) + separator_height
+                    }
+                }));
             }
         }
 
+        self.update_content_height();
+        self.update_visible_range();
+    }
+
+    /// Recompute the content height from the offset table
+    fn update_content_height(&mut self) {
+        let end = self.state.offset_of(self.state.total_items);
+
+        // No separator after the last item
+        let separator = if end > self.state.item_origin {
+            self.config.separator.height()
+        } else {
+            0.0
+        };
+        self.state.content_height = end - separator + self.config.padding;
+
         // Add section header heights
         if let Some(ref sections) = self.sections {
             let total_header_height: f32 = sections.sections.iter().map(|s| s.height).sum();
             self.state.content_height += total_header_height;
         }
+    }
 
-        self.update_visible_range();
+    /// Item ranges of collapsed sections
+    pub fn collapsed_ranges(&self) -> Vec<Range<usize>> {
+        self.collapsed_iter().collect()
+    }
+
+    /// Iterate the item ranges of collapsed sections without allocating
+    fn collapsed_iter(&self) -> impl Iterator<Item = Range<usize>> + '_ {
+        let total = self.state.total_items;
+        self.sections
+            .iter()
+            .flat_map(|sections| sections.sections.iter().enumerate())
+            .filter(|(section, _)| self.state.is_collapsed(*section))
+            .map(move |(_, s)| s.start_index.min(total)..(s.start_index + s.item_count).min(total))
+    }
+
+    /// Apply a layout change while keeping the visible content in place
+    ///
+    /// A bottom-anchored list that was scrolled to the end stays there.
+    /// Otherwise the item at the top of the viewport (shifted by `shift`
+    /// indices) keeps its position rather than the absolute scroll offset.
+    fn preserve_anchor(&mut self, shift: usize, change: impl FnOnce(&mut Self)) {
+        let follow = self.config.anchor == Anchor::Bottom
+            && self.state.scroll_offset >= self.max_scroll_offset() - BOTTOM_TOLERANCE;
+        let anchor = self.state.index_at_offset(self.state.scroll_offset);
+
+        change(self);
+
+        if follow {
+            self.set_scroll_offset(self.max_scroll_offset());
+        } else if let Some((index, local)) = anchor {
+            let offset = self.offset_for_index(index + shift) + local;
+            if offset != self.state.scroll_offset {
+                self.set_scroll_offset(offset);
+            }
+        }
+    }
+
+    /// Jump to the end when anchored to the bottom
+    fn follow_bottom(&mut self) {
+        if self.config.anchor == Anchor::Bottom {
+            self.set_scroll_offset(self.max_scroll_offset());
+        }
     }
 
     /// Update the visible range based on current scroll position
@@ -916,4 +1405,524 @@ mod tests {
         assert!(list.is_item_visible(1));
         assert!(!list.is_item_visible(5));
     }
+
+    #[test]
+    fn test_state_index_at_offset_fixed() {
+        let list = VirtualList::new()
+            .items(10)
+            .fixed_height(50.0)
+            .viewport_height(200.0);
+        let state = list.state();
+
+        assert_eq!(state.index_at_offset(0.0), Some((0, 0.0)));
+        assert_eq!(state.index_at_offset(49.0), Some((0, 49.0)));
+        assert_eq!(state.index_at_offset(50.0), Some((1, 0.0)));
+        assert_eq!(state.index_at_offset(499.0), Some((9, 49.0)));
+        assert_eq!(state.index_at_offset(500.0), None);
+        assert_eq!(state.index_at_offset(-1.0), None);
+    }
+
+    #[test]
+    fn test_state_index_at_offset_variable() {
+        let mut list = VirtualList::new()
+            .items(5)
+            .variable_height(40.0)
+            .padding(10.0)
+            .viewport_height(200.0);
+        list.set_item_height(1, 100.0);
+        list.set_item_height(3, 15.0);
+        // Tops: 10, 50, 150, 190, 205; content ends at 245 + 10 padding
+        let state = list.state();
+
+        assert_eq!(state.index_at_offset(5.0), None);
+        assert_eq!(state.index_at_offset(10.0), Some((0, 0.0)));
+        assert_eq!(state.index_at_offset(49.5), Some((0, 39.5)));
+        assert_eq!(state.index_at_offset(50.0), Some((1, 0.0)));
+        assert_eq!(state.index_at_offset(149.0), Some((1, 99.0)));
+        assert_eq!(state.index_at_offset(150.0), Some((2, 0.0)));
+        assert_eq!(state.index_at_offset(200.0), Some((3, 10.0)));
+        assert_eq!(state.index_at_offset(205.0), Some((4, 0.0)));
+        assert_eq!(state.index_at_offset(255.0), None);
+
+        for index in 0..5 {
+            let offset = list.offset_for_index(index);
+            assert_eq!(state.index_at_offset(offset), Some((index, 0.0)));
+        }
+    }
+
+    #[test]
+    fn test_state_index_at_offset_empty() {
+        let state = VirtualListState::default();
+        assert_eq!(state.index_at_offset(0.0), None);
+    }
+
+    #[test]
+    fn test_offset_table_matches_brute_force() {
+        let count = 100_000;
+        let mut list = VirtualList::new()
+            .items(count)
+            .variable_height(40.0)
+            .separator(Separator::full(1.0, [0.0; 4]))
+            .padding(8.0)
+            .viewport_height(800.0);
+
+        let mut seed: u64 = 0x5eed;
+        let mut heights = Vec::with_capacity(count);
+        for i in 0..count {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let height = 10.0 + ((seed >> 33) % 190) as f32;
+            list.set_item_height(i, height);
+            heights.push(height);
+        }
+
+        // Brute-force tops, accumulated the same way the table does
+        let mut tops = Vec::with_capacity(count);
+        let mut offset = 8.0_f64;
+        for height in &heights {
+            tops.push(offset as f32);
+            offset += f64::from(*height) + 1.0;
+        }
+        assert_eq!(list.content_height(), (offset - 1.0 + 8.0) as f32);
+
+        for index in (0..count).step_by(997) {
+            assert_eq!(list.offset_of(index), tops[index]);
+        }
+
+        let brute_index = |y: f32| tops.partition_point(|&top| top <= y).max(1) - 1;
+        let mut y = 0.0;
+        while y < list.content_height() {
+            assert_eq!(list.index_at_offset(y), brute_index(y), "at {y}");
+            y += 7919.5;
+        }
+        for index in [0, 1, 4_999, 50_000, count - 1] {
+            assert_eq!(list.index_at_offset(tops[index]), index);
+        }
+
+        list.set_scroll_offset(tops[60_000] + 3.0);
+        let range = list.visible_range();
+        assert_eq!(range.start, 60_000 - list.config().overscan);
+        assert!(list.offset_of(range.end - 1) >= tops[60_000] + 800.0);
+    }
+
+    #[test]
+    fn test_measurement_updates_offsets_incrementally() {
+        let mut list = VirtualList::new()
+            .items(10)
+            .variable_height(50.0)
+            .viewport_height(100.0);
+        assert_eq!(list.content_height(), 500.0);
+
+        list.set_item_height(2, 80.0);
+        assert_eq!(list.offset_of(3), 180.0);
+        assert_eq!(list.state().offset_of(3), 180.0);
+        assert_eq!(list.content_height(), 530.0);
+        assert_eq!(list.index_at_offset(179.0), 2);
+        assert_eq!(list.index_at_offset(180.0), 3);
+
+        list.set_item_height(2, 50.0);
+        assert_eq!(list.offset_of(9), 450.0);
+        assert_eq!(list.content_height(), 500.0);
+    }
+
+    #[test]
+    fn test_item_positions_fixed_height_only() {
+        let fixed = VirtualList::new().items(3).fixed_height(40.0).padding(8.0);
+        assert_eq!(fixed.state().item_positions, vec![8.0, 48.0, 88.0]);
+
+        let mut variable = VirtualList::new().items(3).variable_height(40.0).padding(8.0);
+        variable.set_item_height(0, 60.0);
+        assert!(variable.state().item_positions.is_empty());
+        assert_eq!(variable.state().offset_of(1), 68.0);
+    }
+
+    #[test]
+    fn test_pinned_items() {
+        let mut list = VirtualList::new()
+            .items(100)
+            .fixed_height(50.0)
+            .overscan(0)
+            .pinned(vec![40, 2])
+            .viewport_height(300.0);
+
+        // Pinned items leave the flow
+        assert_eq!(list.content_height(), 4900.0);
+        assert_eq!(list.pinned_height(), 100.0);
+
+        let pinned = list.pinned_layout(400.0);
+        assert_eq!(pinned.len(), 2);
+        assert_eq!((pinned[0].index, pinned[0].bounds.y), (40, 0.0));
+        assert_eq!((pinned[1].index, pinned[1].bounds.y), (2, 50.0));
+
+        // The scrolling content starts below the pinned stack
+        let visible: Vec<_> =
+            list.visible_items(400.0).iter().map(|i| (i.index, i.bounds.y)).collect();
+        assert_eq!(visible, vec![(0, 100.0), (1, 150.0), (3, 200.0), (4, 250.0)]);
+
+        // Scrolling over the natural slot of a pinned item does not render it twice
+        list.scroll_to_index(39).unwrap();
+        assert!(list.visible_range().contains(40));
+        let indices: Vec<_> = list.visible_items(400.0).iter().map(|i| i.index).collect();
+        assert_eq!(indices, vec![39, 41, 42, 43]);
+        assert!(list.is_item_visible(2));
+        assert_eq!(list.item_bounds(40, 400.0).y, 0.0);
+
+        // The last items can still be scrolled fully into view
+        list.scroll_to_bottom();
+        assert_eq!(list.max_scroll_offset(), 4700.0);
+        assert_eq!(list.visible_items(400.0).last().unwrap().index, 99);
+    }
+
+    #[test]
+    fn test_pinned_variable_height() {
+        let mut list = VirtualList::new()
+            .items(10)
+            .variable_height(40.0)
+            .pinned(vec![5])
+            .viewport_height(200.0);
+        assert_eq!(list.content_height(), 360.0);
+
+        list.set_item_height(5, 70.0);
+        assert_eq!(list.pinned_height(), 70.0);
+        assert_eq!(list.content_height(), 360.0);
+        assert_eq!(list.offset_for_index(6), 200.0);
+
+        list.set_item_height(6, 60.0);
+        assert_eq!(list.content_height(), 380.0);
+    }
+
+    fn chat_list() -> VirtualList {
+        VirtualList::new()
+            .items(20)
+            .fixed_height(50.0)
+            .overscan(0)
+            .viewport_height(300.0)
+            .anchor(Anchor::Bottom)
+    }
+
+    #[test]
+    fn test_bottom_anchor_follows_appends() {
+        let mut list = chat_list();
+        assert_eq!(list.state().scroll_offset, 700.0);
+        assert!(list.state().is_at_bottom());
+
+        list.append_items(5);
+        assert_eq!(list.state().scroll_offset, 950.0);
+        assert_eq!(list.visible_range(), VisibleRange::new(19, 25));
+        assert!(list.state().is_at_bottom());
+    }
+
+    #[test]
+    fn test_bottom_anchor_keeps_position_when_scrolled_up() {
+        let mut list = chat_list();
+        list.set_scroll_offset(200.0);
+        assert!(!list.state().is_at_bottom());
+
+        list.append_items(5);
+        assert_eq!(list.state().scroll_offset, 200.0);
+        assert_eq!(list.visible_range().start, 4);
+
+        // Older messages loaded above keep the same message on screen
+        list.prepend_items(3);
+        assert_eq!(list.state().total_items, 28);
+        assert_eq!(list.state().scroll_offset, 350.0);
+        assert_eq!(list.visible_range().start, 7);
+    }
+
+    #[test]
+    fn test_bottom_anchor_variable_heights() {
+        let mut list = VirtualList::new()
+            .items(20)
+            .variable_height(50.0)
+            .overscan(0)
+            .viewport_height(300.0)
+            .anchor(Anchor::Bottom);
+
+        // Measuring the newest message keeps the bottom edge pinned
+        list.set_item_height(19, 120.0);
+        assert_eq!(list.state().scroll_offset, 770.0);
+        assert!(list.state().is_at_bottom());
+
+        // Scrolled up, a taller message above the anchor shifts scroll_top
+        list.set_scroll_offset(210.0);
+        list.set_item_height(1, 80.0);
+        assert_eq!(list.state().scroll_offset, 240.0);
+        assert_eq!(list.state().index_at_offset(240.0), Some((4, 10.0)));
+
+        list.set_item_count(22);
+        list.set_item_height(21, 90.0);
+        assert_eq!(list.state().scroll_offset, 240.0);
+    }
+
+    #[test]
+    fn test_top_anchor_unchanged_by_append() {
+        let mut list = VirtualList::new()
+            .items(20)
+            .fixed_height(50.0)
+            .viewport_height(300.0);
+        assert_eq!(list.state().scroll_offset, 0.0);
+        list.append_items(10);
+        assert_eq!(list.state().scroll_offset, 0.0);
+    }
+
+    fn focused(index: usize) -> SelectionState {
+        SelectionState {
+            focus: Some(index),
+            ..SelectionState::default()
+        }
+    }
+
+    #[test]
+    fn test_keyboard_arrows() {
+        let list = VirtualList::new()
+            .items(100)
+            .fixed_height(50.0)
+            .viewport_height(300.0);
+
+        let outcome = list.on_keyboard(KeyboardAction::SelectNext, &focused(2));
+        assert_eq!(outcome, NavOutcome { focus: Some(3), scroll_to: None });
+
+        let outcome = list.on_keyboard(KeyboardAction::SelectNext, &focused(5));
+        assert_eq!(outcome.scroll_to, Some((6, ScrollAlignment::Nearest)));
+
+        let outcome = list.on_keyboard(KeyboardAction::SelectPrevious, &focused(0));
+        assert_eq!(outcome.focus, Some(0));
+
+        // Nothing focused yet: start at the top
+        let outcome = list.on_keyboard(KeyboardAction::SelectNext, &SelectionState::new());
+        assert_eq!(outcome.focus, Some(0));
+
+        let outcome = list.on_keyboard(KeyboardAction::ToggleCurrent, &focused(40));
+        assert_eq!(outcome.scroll_to, Some((40, ScrollAlignment::Nearest)));
+
+        let empty = VirtualList::new().fixed_height(50.0).viewport_height(300.0);
+        assert_eq!(empty.on_keyboard(KeyboardAction::End, &focused(3)), NavOutcome::default());
+    }
+
+    #[test]
+    fn test_keyboard_page_down_clamps_at_end() {
+        let mut list = VirtualList::new()
+            .items(100)
+            .fixed_height(50.0)
+            .viewport_height(300.0);
+
+        // 300px viewport / 50px items = 6 items per page
+        let outcome = list.on_keyboard(KeyboardAction::PageDown, &focused(0));
+        assert_eq!(outcome.focus, Some(6));
+        assert_eq!(outcome.scroll_to, Some((6, ScrollAlignment::End)));
+
+        let outcome = list.on_keyboard(KeyboardAction::PageDown, &focused(96));
+        assert_eq!(outcome.focus, Some(99));
+        assert_eq!(outcome.scroll_to, Some((99, ScrollAlignment::End)));
+
+        // Already showing the last item: focus stays and no scroll is needed
+        list.scroll_to_bottom();
+        let outcome = list.on_keyboard(KeyboardAction::PageDown, &focused(99));
+        assert_eq!(outcome, NavOutcome { focus: Some(99), scroll_to: None });
+
+        let outcome = list.on_keyboard(KeyboardAction::PageUp, &focused(99));
+        assert_eq!(outcome.scroll_to, Some((93, ScrollAlignment::Start)));
+        list.set_scroll_offset(4400.0);
+        let outcome = list.on_keyboard(KeyboardAction::PageUp, &focused(99));
+        assert_eq!(outcome, NavOutcome { focus: Some(93), scroll_to: None });
+        let outcome = list.on_keyboard(KeyboardAction::PageUp, &focused(3));
+        assert_eq!(outcome.scroll_to, Some((0, ScrollAlignment::Start)));
+    }
+
+    #[test]
+    fn test_keyboard_home_end() {
+        let mut list = VirtualList::new()
+            .items(10)
+            .variable_height(40.0)
+            .viewport_height(200.0);
+        list.set_item_height(0, 240.0);
+
+        // Average extent is 60px, so a page is 3 items
+        let outcome = list.on_keyboard(KeyboardAction::PageDown, &focused(2));
+        assert_eq!(outcome.focus, Some(5));
+
+        let outcome = list.on_keyboard(KeyboardAction::End, &focused(2));
+        assert_eq!(outcome.scroll_to, Some((9, ScrollAlignment::End)));
+
+        list.scroll_to_bottom();
+        let outcome = list.on_keyboard(KeyboardAction::Home, &focused(9));
+        assert_eq!(outcome.scroll_to, Some((0, ScrollAlignment::Start)));
+        let outcome = list.on_keyboard(KeyboardAction::End, &SelectionState::new());
+        assert_eq!(outcome, NavOutcome { focus: Some(9), scroll_to: None });
+    }
+
+    #[test]
+    fn test_measurement_reports_invalidation() {
+        let mut list = VirtualList::new()
+            .items(100)
+            .variable_height(40.0)
+            .viewport_height(400.0);
+
+        let invalidation = list.set_item_height(50, 100.0).unwrap();
+        assert_eq!(invalidation.resized, VisibleRange::new(50, 51));
+        assert_eq!(invalidation.shift, 60.0);
+        assert_eq!(list.offset_for_index(51), 51.0 * 40.0 + 60.0);
+        assert_eq!(list.set_item_height(50, 100.0), None);
+
+        let mut fixed = VirtualList::new().items(10).fixed_height(40.0);
+        assert_eq!(fixed.set_item_height(5, 100.0), None);
+    }
+
+    #[test]
+    fn test_measure_capacity_bounds_heights() {
+        let mut list = VirtualList::new()
+            .items(100_000)
+            .variable_height(40.0)
+            .measure_capacity(100)
+            .viewport_height(400.0);
+
+        for index in 0..10_000 {
+            list.set_item_height(index, 60.0);
+        }
+        assert_eq!(list.state().measured_heights.len(), 100);
+        assert_eq!(list.state().measure_cache.len(), 100);
+        assert_eq!(list.get_item_height(0), 40.0);
+        assert_eq!(list.get_item_height(9_999), 60.0);
+        assert_eq!(list.offset_for_index(10_000), 9_900.0 * 40.0 + 100.0 * 60.0);
+
+        // Measuring another item evicts the oldest and reports its shift back
+        let invalidation = list.set_item_height(20_000, 100.0).unwrap();
+        assert_eq!(invalidation.evicted, Some((9_900, -20.0)));
+        assert_eq!(list.get_item_height(9_900), 40.0);
+        assert_eq!(list.offset_for_index(20_001), 20_001.0 * 40.0 + 99.0 * 20.0 + 60.0);
+
+        // Prepending keeps the surviving measurements with their items
+        list.prepend_items(5);
+        assert_eq!(list.get_item_height(20_005), 100.0);
+        assert_eq!(list.state().measured_heights.len(), 100);
+        assert_eq!(list.state().measured_heights.get(&20_005), Some(&100.0));
+        assert!(!list.state().measured_heights.contains_key(&9_900));
+    }
+
+    fn sectioned_list() -> VirtualList {
+        let sections = SectionConfig::new()
+            .add_section(SectionHeader::new("a", "Section A", 40.0).with_items(0, 5))
+            .add_section(SectionHeader::new("b", "Section B", 40.0).with_items(5, 20));
+
+        VirtualList::new()
+            .items(25)
+            .fixed_height(50.0)
+            .sections(sections)
+            .viewport_height(300.0)
+    }
+
+    #[test]
+    fn test_collapse_first_section() {
+        let mut list = sectioned_list();
+        assert_eq!(list.content_height(), 25.0 * 50.0 + 80.0);
+
+        list.set_collapsed(0, true);
+        assert!(list.state().is_collapsed(0));
+        // Only the header of the collapsed section remains
+        assert_eq!(list.content_height(), 20.0 * 50.0 + 80.0);
+        assert!(list.is_item_collapsed(4));
+        assert!(!list.is_item_collapsed(5));
+
+        let indices: Vec<_> = list.visible_items(400.0).iter().map(|item| item.index).collect();
+        assert_eq!(indices.first(), Some(&5));
+        assert!(!list.is_item_visible(0));
+
+        list.scroll_to_index(10).unwrap();
+        assert_eq!(list.state().scroll_offset, 250.0);
+        assert_eq!(list.index_at_offset(250.0), 10);
+
+        list.set_collapsed(0, false);
+        assert_eq!(list.content_height(), 25.0 * 50.0 + 80.0);
+        assert_eq!(list.offset_for_index(10), 500.0);
+    }
+
+    #[test]
+    fn test_collapse_clamps_scroll_and_measurements() {
+        let sections = SectionConfig::new()
+            .add_section(SectionHeader::new("a", "A", 0.0).with_items(0, 10))
+            .add_section(SectionHeader::new("b", "B", 0.0).with_items(10, 10));
+        let mut list = VirtualList::new()
+            .items(20)
+            .variable_height(50.0)
+            .sections(sections)
+            .viewport_height(300.0);
+        list.set_scroll_offset(700.0);
+
+        list.set_collapsed(1, true);
+        assert_eq!(list.content_height(), 500.0);
+        assert_eq!(list.state().scroll_offset, 200.0);
+
+        // Measuring a hidden item does not move anything
+        list.set_item_height(15, 200.0);
+        assert_eq!(list.content_height(), 500.0);
+        list.set_collapsed(1, false);
+        assert_eq!(list.content_height(), 1150.0);
+    }
+
+    #[test]
+    fn test_keyboard_skips_collapsed_sections() {
+        let sections = SectionConfig::new()
+            .add_section(SectionHeader::new("a", "A", 40.0).with_items(0, 3))
+            .add_section(SectionHeader::new("b", "B", 40.0).with_items(3, 3))
+            .add_section(SectionHeader::new("c", "C", 40.0).with_items(6, 3));
+        let mut list = VirtualList::new()
+            .items(9)
+            .fixed_height(50.0)
+            .sections(sections)
+            .viewport_height(600.0);
+        list.set_collapsed(1, true);
+
+        let next = list.on_keyboard(KeyboardAction::SelectNext, &focused(2));
+        assert_eq!(next.focus, Some(6));
+        let previous = list.on_keyboard(KeyboardAction::SelectPrevious, &focused(6));
+        assert_eq!(previous.focus, Some(2));
+
+        list.set_collapsed(2, true);
+        assert_eq!(list.on_keyboard(KeyboardAction::End, &focused(0)).focus, Some(2));
+        assert_eq!(list.on_keyboard(KeyboardAction::SelectNext, &focused(2)).focus, Some(2));
+
+        list.set_collapsed(0, true);
+        assert_eq!(list.on_keyboard(KeyboardAction::Home, &focused(0)), NavOutcome::default());
+    }
+
+    #[test]
+    fn test_separators_for_range() {
+        let list = VirtualList::new()
+            .items(10)
+            .fixed_height(50.0)
+            .separator(Separator::full(1.0, [0.5, 0.5, 0.5, 1.0]))
+            .viewport_height(300.0);
+
+        let separators = list.separators_for(VisibleRange::new(0, 5));
+        assert_eq!(separators.len(), 5);
+        assert_eq!(separators[0].y, 50.0);
+        assert_eq!(separators[4].y, 4.0 * 51.0 + 50.0);
+
+        // No trailing separator after the last item
+        let separators = list.separators_for(VisibleRange::new(5, 10));
+        assert_eq!(separators.len(), 4);
+        assert_eq!(separators.last().unwrap().index, 8);
+
+        let plain = VirtualList::new().items(10).fixed_height(50.0);
+        assert!(plain.separators_for(VisibleRange::new(0, 5)).is_empty());
+    }
+
+    #[test]
+    fn test_separators_around_sections_and_insets() {
+        let sections = SectionConfig::new()
+            .add_section(SectionHeader::new("a", "A", 30.0).with_items(0, 3))
+            .add_section(SectionHeader::new("b", "B", 30.0).with_items(3, 3));
+        let list = VirtualList::new()
+            .items(6)
+            .fixed_height(50.0)
+            .separator(Separator::inset(1.0, [0.0; 4], 16.0, 8.0))
+            .sections(sections)
+            .viewport_height(300.0);
+
+        let indices: Vec<_> =
+            list.separators_for(VisibleRange::new(0, 6)).iter().map(|s| s.index).collect();
+        assert_eq!(indices, vec![0, 1, 3, 4]);
+
+        let separator = list.separators_for(VisibleRange::new(0, 1))[0];
+        assert_eq!(separator.bounds(0.0, 400.0), Rect::new(16.0, 50.0, 376.0, 1.0));
+    }
 }
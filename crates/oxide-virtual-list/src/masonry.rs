@@ -2,6 +2,20 @@
 
 use serde::{Deserialize, Serialize};
 
+/// Smallest reduction (in pixels) of the column gap worth another rebalancing pass
+const MIN_IMPROVEMENT: f32 = 0.01;
+
+/// Strategy for assigning items to columns
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum BalanceStrategy {
+    /// Place each item in the currently shortest column
+    #[default]
+    ShortestColumn,
+    /// Look at all known heights up front and minimize the spread between
+    /// the tallest and shortest column
+    MinimizeHeightVariance,
+}
+
 /// Configuration for masonry layout
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MasonryConfig {
@@ -11,6 +25,9 @@ pub struct MasonryConfig {
     pub gap: f32,
     /// Minimum column width
     pub min_column_width: f32,
+    /// Column assignment strategy for batch layout
+    #[serde(default)]
+    pub balance_strategy: BalanceStrategy,
 }
 
 impl Default for MasonryConfig {
@@ -19,10 +36,19 @@ impl Default for MasonryConfig {
             columns: 3,
             gap: 8.0,
             min_column_width: 200.0,
+            balance_strategy: BalanceStrategy::default(),
         }
     }
 }
 
+impl MasonryConfig {
+    /// Set the column assignment strategy
+    pub fn balance_strategy(mut self, strategy: BalanceStrategy) -> Self {
+        self.balance_strategy = strategy;
+        self
+    }
+}
+
 /// Column state in masonry layout
 #[derive(Debug, Clone, Default)]
 pub struct MasonryColumn {
@@ -68,6 +94,25 @@ pub struct MasonryState {
     pub scroll_top: f32,
 }
 
+impl MasonryState {
+    /// Get the total height of each column (including the gap after its last item)
+    pub fn column_heights(&self) -> Vec<f32> {
+        self.columns.iter().map(|c| c.height).collect()
+    }
+
+    /// Get the difference between the tallest and shortest column
+    pub fn height_spread(&self) -> f32 {
+        let heights = self.column_heights();
+        let max = heights.iter().copied().fold(f32::MIN, f32::max);
+        let min = heights.iter().copied().fold(f32::MAX, f32::min);
+        if heights.is_empty() {
+            0.0
+        } else {
+            max - min
+        }
+    }
+}
+
 /// Masonry grid component
 #[derive(Debug, Clone)]
 pub struct MasonryGrid {
@@ -140,9 +185,239 @@ impl MasonryGrid {
         (col, y)
     }
 
+    /// Lay out a batch of items with known heights
+    ///
+    /// Resets the columns and returns the `(column, y)` position of each item
+    /// using the configured [`BalanceStrategy`].
+    pub fn layout_items(&mut self, heights: &[f32]) -> Vec<(usize, f32)> {
+        self.init_columns();
+        if self.state.columns.is_empty() {
+            return Vec::new();
+        }
+
+        match self.config.balance_strategy {
+            BalanceStrategy::ShortestColumn => heights
+                .iter()
+                .enumerate()
+                .map(|(index, &height)| self.add_item(index, height))
+                .collect(),
+            BalanceStrategy::MinimizeHeightVariance => {
+                let assignment = self.balanced_assignment(heights);
+                // Stack each column in index order so reading order is kept
+                let mut positions = Vec::with_capacity(heights.len());
+                for (index, &col) in assignment.iter().enumerate() {
+                    let column = &mut self.state.columns[col];
+                    positions.push((col, column.height));
+                    column.items.push(index);
+                    column.height += heights[index] + self.config.gap;
+                }
+                self.state.total_height = self
+                    .state
+                    .columns
+                    .iter()
+                    .map(|c| c.height)
+                    .fold(0.0f32, f32::max);
+                positions
+            }
+        }
+    }
+
+    /// Lay out a batch of items from their aspect ratios (width / height)
+    pub fn layout_aspect_ratios(
+        &mut self,
+        ratios: &[f32],
+        container_width: f32,
+    ) -> Vec<(usize, f32)> {
+        let width = self.column_width(container_width);
+        let heights: Vec<f32> = ratios
+            .iter()
+            .map(|&ratio| if ratio > 0.0 { width / ratio } else { width })
+            .collect();
+        self.layout_items(&heights)
+    }
+
+    /// Assign items to columns minimizing the max-min column height
+    ///
+    /// Seeds with largest-first placement into the shortest column, then
+    /// repeatedly moves or swaps items between the tallest and shortest
+    /// columns while that narrows the gap between them.
+    fn balanced_assignment(&self, heights: &[f32]) -> Vec<usize> {
+        let columns = self.state.columns.len();
+        let gap = self.config.gap;
+        let extent = |index: usize| heights[index] + gap;
+
+        let mut order: Vec<usize> = (0..heights.len()).collect();
+        order.sort_by(|&a, &b| heights[b].total_cmp(&heights[a]));
+
+        let mut assignment = vec![0; heights.len()];
+        let mut members: Vec<Vec<usize>> = vec![Vec::new(); columns];
+        let mut totals = vec![0.0f32; columns];
+        for index in order {
+            let col = argmin(&totals);
+            assignment[index] = col;
+            members[col].push(index);
+            totals[col] += extent(index);
+        }
+
+        for _ in 0..heights.len() {
+            let (high, low) = (argmax(&totals), argmin(&totals));
+            let diff = totals[high] - totals[low];
+            let ideal = diff / 2.0;
+
+            // Pick the move or swap whose net shift is closest to half the gap
+            let mut best: Option<(f32, usize, Option<usize>)> = None;
+            let mut consider = |shift: f32, a: usize, b: Option<usize>| {
+                if shift <= 0.0 || shift >= diff {
+                    return;
+                }
+                let score = (shift - ideal).abs();
+                match best {
+                    Some((s, ..)) if s <= score => {}
+                    _ => best = Some((score, a, b)),
+                }
+            };
+
+            let mut low_members: Vec<(f32, usize)> =
+                members[low].iter().map(|&b| (extent(b), b)).collect();
+            low_members.sort_by(|x, y| x.0.total_cmp(&y.0));
+            for &a in &members[high] {
+                consider(extent(a), a, None);
+
+                let wanted = extent(a) - ideal;
+                let at = low_members.partition_point(|&(e, _)| e < wanted);
+                let near = at.saturating_sub(1)..(at + 1).min(low_members.len());
+                for &(e, b) in &low_members[near] {
+                    consider(extent(a) - e, a, Some(b));
+                }
+            }
+
+            // Stop once the gap can no longer be narrowed meaningfully
+            let Some((score, a, swap)) = best else {
+                break;
+            };
+            if ideal - score < MIN_IMPROVEMENT {
+                break;
+            }
+
+            members[high].retain(|&i| i != a);
+            members[low].push(a);
+            assignment[a] = low;
+            totals[high] -= extent(a);
+            totals[low] += extent(a);
+            if let Some(b) = swap {
+                members[low].retain(|&i| i != b);
+                members[high].push(b);
+                assignment[b] = high;
+                totals[low] -= extent(b);
+                totals[high] += extent(b);
+            }
+        }
+
+        assignment
+    }
+
     /// Calculate column width for container
     pub fn column_width(&self, container_width: f32) -> f32 {
         let total_gap = self.config.gap * (self.config.columns - 1) as f32;
         (container_width - total_gap) / self.config.columns as f32
     }
 }
+
+fn argmin(values: &[f32]) -> usize {
+    values
+        .iter()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(i, _)| i)
+        .unwrap_or(0)
+}
+
+fn argmax(values: &[f32]) -> usize {
+    values
+        .iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(i, _)| i)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic aspect ratios between 0.5 (tall) and 2.0 (wide)
+    fn aspect_ratios(count: usize) -> Vec<f32> {
+        let mut seed: u64 = 0xa5;
+        (0..count)
+            .map(|_| {
+                seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                0.5 + ((seed >> 33) % 1500) as f32 / 1000.0
+            })
+            .collect()
+    }
+
+    fn variance(values: &[f32]) -> f32 {
+        let mean = values.iter().sum::<f32>() / values.len() as f32;
+        values.iter().map(|v| (v - mean).powi(2)).sum::<f32>() / values.len() as f32
+    }
+
+    fn grid(strategy: BalanceStrategy) -> MasonryGrid {
+        let mut grid = MasonryGrid::new().columns(4).gap(8.0);
+        grid.config = grid.config.balance_strategy(strategy);
+        grid
+    }
+
+    #[test]
+    fn test_balanced_variance_is_lower() {
+        let ratios = aspect_ratios(200);
+
+        let mut greedy = grid(BalanceStrategy::ShortestColumn);
+        greedy.layout_aspect_ratios(&ratios, 1000.0);
+        let mut balanced = grid(BalanceStrategy::MinimizeHeightVariance);
+        balanced.layout_aspect_ratios(&ratios, 1000.0);
+
+        let greedy_variance = variance(&greedy.state.column_heights());
+        let balanced_variance = variance(&balanced.state.column_heights());
+        assert!(balanced_variance < greedy_variance, "{balanced_variance} >= {greedy_variance}");
+        assert!(balanced.state.height_spread() <= greedy.state.height_spread());
+        assert!(balanced.state.height_spread() < 1.0);
+
+        // Both layouts hold the same content
+        let total = |g: &MasonryGrid| g.state.column_heights().iter().sum::<f32>();
+        assert!((total(&greedy) - total(&balanced)).abs() < 1.0);
+        let tallest = balanced.state.column_heights().into_iter().fold(0.0, f32::max);
+        assert_eq!(balanced.state.total_height, tallest);
+    }
+
+    #[test]
+    fn test_balanced_columns_keep_index_order() {
+        let heights: Vec<f32> = aspect_ratios(50).iter().map(|r| 240.0 / r).collect();
+        let mut balanced = grid(BalanceStrategy::MinimizeHeightVariance);
+        let positions = balanced.layout_items(&heights);
+        assert_eq!(positions.len(), 50);
+
+        for (col, column) in balanced.state.columns.iter().enumerate() {
+            assert!(column.items.windows(2).all(|w| w[0] < w[1]));
+            let mut y = 0.0;
+            for &index in &column.items {
+                assert_eq!(positions[index], (col, y));
+                y += heights[index] + 8.0;
+            }
+            assert_eq!(column.height, y);
+        }
+    }
+
+    #[test]
+    fn test_shortest_column_matches_add_item() {
+        let heights = [100.0, 50.0, 80.0, 30.0, 60.0];
+        let mut batch = grid(BalanceStrategy::ShortestColumn);
+        let positions = batch.layout_items(&heights);
+
+        let mut incremental = grid(BalanceStrategy::ShortestColumn);
+        incremental.init_columns();
+        for (index, &height) in heights.iter().enumerate() {
+            assert_eq!(incremental.add_item(index, height), positions[index]);
+        }
+        assert_eq!(batch.state.column_heights(), vec![108.0, 58.0, 88.0, 38.0 + 68.0]);
+    }
+}
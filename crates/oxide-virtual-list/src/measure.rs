@@ -1,7 +1,8 @@
 //! Item measurement and caching for variable height items.
 
+use crate::VisibleRange;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Strategy for measuring item sizes
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -15,6 +16,52 @@ pub enum MeasureStrategy {
     PreMeasured,
     /// Estimate first, measure later
     Estimate,
+    /// Measurements arrive asynchronously and are reported when ready
+    ///
+    /// Layout uses estimates until [`ItemMeasureCache::update`] reports the
+    /// real size, which yields a [`LayoutInvalidation`] for the change.
+    Deferred,
+}
+
+/// Layout change caused by an item's measured size differing from before
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LayoutInvalidation {
+    /// Items whose size changed and need a re-layout
+    pub resized: VisibleRange,
+    /// Distance items after the resized ones moved by
+    pub shift: f32,
+    /// Item evicted from a bounded cache to make room, with its height change
+    ///
+    /// The evicted item falls back to the estimate, which moves the items
+    /// after it as well.
+    pub evicted: Option<(usize, f32)>,
+}
+
+impl LayoutInvalidation {
+    /// Create an invalidation for a single resized item
+    pub fn new(index: usize, shift: f32) -> Self {
+        Self {
+            resized: VisibleRange::new(index, index + 1),
+            shift,
+            evicted: None,
+        }
+    }
+
+    /// Check if an item needs a re-layout, not just a translation
+    pub fn needs_layout(&self, index: usize) -> bool {
+        self.resized.contains(index) || self.evicted.is_some_and(|(evicted, _)| evicted == index)
+    }
+
+    /// Get how far an item moved
+    pub fn offset_shift(&self, index: usize) -> f32 {
+        let mut shift = if index >= self.resized.end { self.shift } else { 0.0 };
+        if let Some((evicted, delta)) = self.evicted {
+            if index > evicted {
+                shift += delta;
+            }
+        }
+        shift
+    }
 }
 
 /// Context for measuring items
@@ -26,6 +73,8 @@ pub struct MeasureContext {
     pub container_height: f32,
     /// Device pixel ratio
     pub device_pixel_ratio: f32,
+    /// How items are measured
+    pub strategy: MeasureStrategy,
 }
 
 impl Default for MeasureContext {
@@ -34,10 +83,19 @@ impl Default for MeasureContext {
             container_width: 0.0,
             container_height: 0.0,
             device_pixel_ratio: 1.0,
+            strategy: MeasureStrategy::default(),
         }
     }
 }
 
+impl MeasureContext {
+    /// Set the measure strategy
+    pub fn strategy(mut self, strategy: MeasureStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+}
+
 /// Measured dimensions for an item
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct ItemMeasurement {
@@ -62,7 +120,77 @@ impl ItemMeasurement {
     }
 }
 
+/// Fenwick tree over item heights for O(log n) prefix sums
+///
+/// Sums are accumulated in `f64` so offsets deep into huge lists stay exact
+/// enough for pixel positioning.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PrefixSums {
+    tree: Vec<f64>,
+}
+
+impl PrefixSums {
+    /// Build the tree from item heights in O(n)
+    pub(crate) fn build(heights: impl Iterator<Item = f32>) -> Self {
+        let mut tree: Vec<f64> = heights.map(f64::from).collect();
+        for i in 0..tree.len() {
+            let parent = i | (i + 1);
+            if parent < tree.len() {
+                tree[parent] += tree[i];
+            }
+        }
+        Self { tree }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    /// Add `delta` to the height of item `index`
+    pub(crate) fn add(&mut self, index: usize, delta: f64) {
+        let mut i = index;
+        while i < self.tree.len() {
+            self.tree[i] += delta;
+            i |= i + 1;
+        }
+    }
+
+    /// Sum of the first `count` heights
+    pub(crate) fn prefix(&self, count: usize) -> f64 {
+        let mut sum = 0.0;
+        let mut i = count.min(self.tree.len());
+        while i > 0 {
+            sum += self.tree[i - 1];
+            i &= i - 1;
+        }
+        sum
+    }
+
+    /// Number of leading items whose cumulative height is at most `target`
+    pub(crate) fn count_within(&self, target: f64) -> usize {
+        let mut count = 0;
+        let mut remaining = target;
+        let mut step = self.tree.len().checked_next_power_of_two().unwrap_or(0);
+        while step > 0 {
+            let next = count + step;
+            if next <= self.tree.len() && self.tree[next - 1] <= remaining {
+                remaining -= self.tree[next - 1];
+                count = next;
+            }
+            step >>= 1;
+        }
+        count
+    }
+}
+
 /// Cache for item measurements
+///
+/// Once the item count is known via [`set_item_count`](Self::set_item_count),
+/// cumulative offsets are kept in a prefix-sum index that is updated
+/// incrementally as measurements change.
+///
+/// A cache created with [`with_capacity`](Self::with_capacity) evicts the
+/// least recently used measurements; evicted items fall back to the estimate.
 #[derive(Debug, Clone, Default)]
 pub struct ItemMeasureCache {
     /// Cached measurements by item index
@@ -71,6 +199,16 @@ pub struct ItemMeasureCache {
     default_measurement: Option<ItemMeasurement>,
     /// Estimated item height
     estimated_height: f32,
+    /// Prefix sums of item heights
+    offsets: PrefixSums,
+    /// Maximum number of cached measurements
+    capacity: Option<usize>,
+    /// Last access tick per cached item (bounded caches only)
+    last_used: HashMap<usize, u64>,
+    /// Cached items ordered by last access tick
+    recency: BTreeMap<u64, usize>,
+    /// Access counter
+    clock: u64,
 }
 
 impl ItemMeasureCache {
@@ -80,6 +218,15 @@ impl ItemMeasureCache {
             measurements: HashMap::new(),
             default_measurement: None,
             estimated_height: 48.0,
+            ..Self::default()
+        }
+    }
+
+    /// Create a cache holding at most `capacity` measurements
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity: Some(capacity),
+            ..Self::new()
         }
     }
 
@@ -89,12 +236,36 @@ impl ItemMeasureCache {
             measurements: HashMap::new(),
             default_measurement: Some(ItemMeasurement::from_height(height)),
             estimated_height: height,
+            ..Self::default()
         }
     }
 
     /// Set default measurement
     pub fn set_default(&mut self, measurement: ItemMeasurement) {
         self.default_measurement = Some(measurement);
+        self.rebuild_offsets();
+    }
+
+    /// Get the maximum number of cached measurements
+    pub fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+
+    /// Set the maximum number of cached measurements, evicting if needed
+    pub fn set_capacity(&mut self, capacity: Option<usize>) {
+        if self.capacity.is_none() {
+            for &index in self.measurements.keys() {
+                self.clock += 1;
+                self.last_used.insert(index, self.clock);
+                self.recency.insert(self.clock, index);
+            }
+        }
+        self.capacity = capacity;
+        if capacity.is_none() {
+            self.last_used.clear();
+            self.recency.clear();
+        }
+        self.evict();
     }
 
     /// Get measurement for item
@@ -102,6 +273,13 @@ impl ItemMeasureCache {
         self.measurements.get(&index)
     }
 
+    /// Get measurement for item, marking it as recently used
+    pub fn access(&mut self, index: usize) -> Option<ItemMeasurement> {
+        let measurement = self.measurements.get(&index).copied()?;
+        self.touch(index);
+        Some(measurement)
+    }
+
     /// Get measurement or default
     pub fn get_or_default(&self, index: usize) -> ItemMeasurement {
         self.measurements
@@ -113,17 +291,127 @@ impl ItemMeasureCache {
 
     /// Set measurement for item
     pub fn set(&mut self, index: usize, measurement: ItemMeasurement) {
-        self.measurements.insert(index, measurement);
+        self.insert(index, measurement);
+    }
+
+    /// Get an item's measurement, measuring it now if the strategy allows
+    ///
+    /// `measure` runs only for uncached items. With [`MeasureStrategy::Fixed`]
+    /// and [`MeasureStrategy::Deferred`] it never runs: uncached items keep
+    /// the estimate until a size is reported via [`update`](Self::update).
+    pub fn measure(
+        &mut self,
+        context: &MeasureContext,
+        index: usize,
+        measure: impl FnOnce(&MeasureContext) -> ItemMeasurement,
+    ) -> ItemMeasurement {
+        if let Some(measurement) = self.access(index) {
+            return measurement;
+        }
+        match context.strategy {
+            MeasureStrategy::Fixed | MeasureStrategy::Deferred => self.get_or_default(index),
+            _ => {
+                let measurement = measure(context);
+                self.set(index, measurement);
+                measurement
+            }
+        }
+    }
+
+    /// Report a measurement that became available after layout
+    ///
+    /// Offsets after the item are patched in O(log n). The invalidation also
+    /// covers any item evicted to make room. Returns `None` when no height
+    /// differs from what the layout already assumed.
+    pub fn update(
+        &mut self,
+        index: usize,
+        measurement: ItemMeasurement,
+    ) -> Option<LayoutInvalidation> {
+        let previous = self.get_or_default(index).height;
+        let evicted = self.insert(index, measurement).filter(|&(evicted, _)| evicted != index);
+        let shift = self.get_or_default(index).height - previous;
+        let evicted = evicted.filter(|&(_, delta)| delta != 0.0);
+        if shift == 0.0 && evicted.is_none() {
+            return None;
+        }
+        Some(LayoutInvalidation {
+            evicted,
+            ..LayoutInvalidation::new(index, shift)
+        })
     }
 
     /// Remove measurement for item
     pub fn remove(&mut self, index: usize) -> Option<ItemMeasurement> {
-        self.measurements.remove(&index)
+        let removed = self.measurements.remove(&index)?;
+        if let Some(tick) = self.last_used.remove(&index) {
+            self.recency.remove(&tick);
+        }
+        self.adjust_offset(index, removed.height);
+        Some(removed)
     }
 
     /// Clear all measurements
     pub fn clear(&mut self) {
         self.measurements.clear();
+        self.last_used.clear();
+        self.recency.clear();
+        self.rebuild_offsets();
+    }
+
+    /// Move every measurement `by` items later, as when items are prepended
+    pub(crate) fn shift_indices(&mut self, by: usize) {
+        self.measurements = self.measurements.drain().map(|(i, m)| (i + by, m)).collect();
+        self.last_used = self.last_used.drain().map(|(i, tick)| (i + by, tick)).collect();
+        for index in self.recency.values_mut() {
+            *index += by;
+        }
+        self.rebuild_offsets();
+    }
+
+    /// Set the number of items covered by the offset index
+    pub fn set_item_count(&mut self, count: usize) {
+        self.offsets = PrefixSums::build((0..count).map(|i| self.get_or_default(i).height));
+    }
+
+    /// Get the number of items covered by the offset index
+    pub fn item_count(&self) -> usize {
+        self.offsets.len()
+    }
+
+    /// Get the Y offset of an item (sum of the heights before it)
+    pub fn offset_for_index(&self, index: usize) -> f32 {
+        let indexed = index.min(self.offsets.len());
+        let tail = self.total_height_linear(indexed..index);
+        (self.offsets.prefix(indexed) + f64::from(tail)) as f32
+    }
+
+    /// Find the item containing a Y offset
+    ///
+    /// Returns `None` for offsets before the first item or past the last.
+    pub fn index_at_offset(&self, offset: f32) -> Option<usize> {
+        if offset < 0.0 {
+            return None;
+        }
+        let index = self.offsets.count_within(f64::from(offset));
+        (index < self.offsets.len()).then_some(index)
+    }
+
+    /// Get the items overlapping a viewport, using estimates for unmeasured items
+    pub fn visible_range(&self, scroll_offset: f32, viewport_height: f32) -> VisibleRange {
+        let count = self.offsets.len();
+        let top = scroll_offset.max(0.0);
+        let bottom = scroll_offset + viewport_height;
+        if count == 0 || bottom <= top {
+            return VisibleRange::empty();
+        }
+
+        let start = self.offsets.count_within(f64::from(top)).min(count);
+        let mut end = self.offsets.count_within(f64::from(bottom));
+        if end < count && self.offsets.prefix(end) < f64::from(bottom) {
+            end += 1;
+        }
+        VisibleRange::new(start, end.max(start))
     }
 
     /// Get number of cached measurements
@@ -138,6 +426,16 @@ impl ItemMeasureCache {
 
     /// Calculate total height for range
     pub fn total_height(&self, range: std::ops::Range<usize>) -> f32 {
+        if range.start >= range.end {
+            return 0.0;
+        }
+        if range.end <= self.offsets.len() {
+            return (self.offsets.prefix(range.end) - self.offsets.prefix(range.start)) as f32;
+        }
+        self.total_height_linear(range)
+    }
+
+    fn total_height_linear(&self, range: std::ops::Range<usize>) -> f32 {
         range
             .map(|i| self.get_or_default(i).height)
             .sum()
@@ -153,6 +451,296 @@ impl ItemMeasureCache {
         if !self.measurements.is_empty() {
             let sum: f32 = self.measurements.values().map(|m| m.height).sum();
             self.estimated_height = sum / self.measurements.len() as f32;
+            self.rebuild_offsets();
         }
     }
+
+    /// Mark a cached item as most recently used
+    fn touch(&mut self, index: usize) {
+        if self.capacity.is_none() {
+            return;
+        }
+        self.clock += 1;
+        if let Some(tick) = self.last_used.insert(index, self.clock) {
+            self.recency.remove(&tick);
+        }
+        self.recency.insert(self.clock, index);
+    }
+
+    /// Store a measurement, returning the last item evicted and its height change
+    fn insert(&mut self, index: usize, measurement: ItemMeasurement) -> Option<(usize, f32)> {
+        let previous = self.get_or_default(index).height;
+        self.measurements.insert(index, measurement);
+        self.adjust_offset(index, previous);
+        self.touch(index);
+        self.evict()
+    }
+
+    /// Evict least recently used measurements beyond the capacity
+    fn evict(&mut self) -> Option<(usize, f32)> {
+        let capacity = self.capacity?;
+        let mut last = None;
+        while self.measurements.len() > capacity {
+            let Some((_, index)) = self.recency.pop_first() else {
+                break;
+            };
+            self.last_used.remove(&index);
+            if let Some(evicted) = self.measurements.remove(&index) {
+                self.adjust_offset(index, evicted.height);
+                last = Some((index, self.get_or_default(index).height - evicted.height));
+            }
+        }
+        last
+    }
+
+    /// Apply a height change for one item to the offset index
+    fn adjust_offset(&mut self, index: usize, previous: f32) {
+        if index < self.offsets.len() {
+            let delta = self.get_or_default(index).height - previous;
+            self.offsets.add(index, f64::from(delta));
+        }
+    }
+
+    /// Rebuild the offset index after the default height changed
+    fn rebuild_offsets(&mut self) {
+        self.set_item_count(self.offsets.len());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic pseudo-random heights between 10 and 200
+    fn random_heights(count: usize, mut seed: u64) -> Vec<(usize, f32)> {
+        (0..count)
+            .map(|i| {
+                seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                (i, 10.0 + (seed >> 33) as f32 % 190.0)
+            })
+            .collect()
+    }
+
+    fn naive_offset(cache: &ItemMeasureCache, index: usize) -> f32 {
+        (0..index).map(|i| cache.get_or_default(i).height).sum()
+    }
+
+    fn naive_index(cache: &ItemMeasureCache, offset: f32) -> Option<usize> {
+        let mut top = 0.0;
+        for i in 0..cache.item_count() {
+            let height = cache.get_or_default(i).height;
+            if offset >= top && offset < top + height {
+                return Some(i);
+            }
+            top += height;
+        }
+        None
+    }
+
+    fn assert_close(a: f32, b: f32) {
+        assert!((a - b).abs() < 0.01, "{a} != {b}");
+    }
+
+    #[test]
+    fn test_offsets_match_naive() {
+        let mut cache = ItemMeasureCache::with_estimated_height(40.0);
+        cache.set_item_count(500);
+        // Measure every third item, leaving the rest estimated
+        for (i, height) in random_heights(500, 7).into_iter().step_by(3) {
+            cache.set(i, ItemMeasurement::from_height(height));
+        }
+
+        for index in (0..=500).step_by(7) {
+            assert_close(cache.offset_for_index(index), naive_offset(&cache, index));
+        }
+        let naive_range = naive_offset(&cache, 250) - naive_offset(&cache, 100);
+        assert_close(cache.total_height(100..250), naive_range);
+    }
+
+    #[test]
+    fn test_index_at_offset_matches_naive() {
+        let mut cache = ItemMeasureCache::with_estimated_height(25.0);
+        cache.set_item_count(300);
+        for (i, height) in random_heights(300, 42).into_iter().filter(|(i, _)| i % 2 == 0) {
+            cache.set(i, ItemMeasurement::from_height(height.round()));
+        }
+
+        let total = cache.total_height(0..300);
+        let mut offset = 0.0;
+        while offset < total {
+            assert_eq!(cache.index_at_offset(offset), naive_index(&cache, offset), "at {offset}");
+            offset += 13.0;
+        }
+
+        // Item boundaries belong to the item that starts there
+        for index in [0, 1, 99, 299] {
+            let top = cache.offset_for_index(index);
+            assert_eq!(cache.index_at_offset(top), Some(index));
+        }
+        assert_eq!(cache.index_at_offset(-1.0), None);
+        assert_eq!(cache.index_at_offset(total), None);
+    }
+
+    #[test]
+    fn test_incremental_updates() {
+        let mut cache = ItemMeasureCache::with_estimated_height(50.0);
+        cache.set_item_count(100);
+        assert_eq!(cache.offset_for_index(10), 500.0);
+
+        cache.set(3, ItemMeasurement::from_height(80.0));
+        assert_eq!(cache.offset_for_index(10), 530.0);
+        cache.set(3, ItemMeasurement::from_height(20.0));
+        assert_eq!(cache.offset_for_index(10), 470.0);
+        assert_eq!(cache.index_at_offset(169.0), Some(3));
+        assert_eq!(cache.index_at_offset(170.0), Some(4));
+
+        cache.remove(3);
+        assert_eq!(cache.offset_for_index(10), 500.0);
+
+        cache.set_default(ItemMeasurement::from_height(10.0));
+        assert_eq!(cache.offset_for_index(10), 100.0);
+
+        // Measurements beyond the indexed range fall back to a linear sum
+        cache.set(150, ItemMeasurement::from_height(30.0));
+        assert_eq!(cache.offset_for_index(152), 1540.0);
+        assert_eq!(cache.total_height(0..152), 1540.0);
+    }
+
+    #[test]
+    fn test_random_updates_match_naive() {
+        let mut cache = ItemMeasureCache::new();
+        cache.set_item_count(200);
+        for (step, (i, height)) in random_heights(400, 3).into_iter().enumerate() {
+            let index = (i * 37) % 200;
+            if step % 5 == 0 {
+                cache.remove(index);
+            } else {
+                cache.set(index, ItemMeasurement::from_height(height));
+            }
+        }
+
+        for index in 0..=200 {
+            assert_close(cache.offset_for_index(index), naive_offset(&cache, index));
+        }
+    }
+
+    #[test]
+    fn test_capacity_respected_under_many_measurements() {
+        let count = 1_000_000;
+        let mut cache = ItemMeasureCache::with_capacity(1_000);
+        cache.set_item_count(count);
+        for index in 0..count {
+            cache.set(index, ItemMeasurement::from_height(20.0 + (index % 7) as f32));
+        }
+
+        assert_eq!(cache.len(), 1_000);
+        // The most recent measurements survive, older ones fall back to the estimate
+        assert_eq!(cache.get(count - 1).unwrap().height, 20.0 + ((count - 1) % 7) as f32);
+        assert!(cache.get(0).is_none());
+        assert_eq!(cache.get_or_default(0).height, 48.0);
+
+        let recent: f32 = (count - 1_000..count).map(|i| 20.0 + (i % 7) as f32).sum();
+        let expected = (count - 1_000) as f32 * 48.0 + recent;
+        assert!((cache.total_height(0..count) - expected).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_eviction_keeps_offsets_consistent() {
+        let mut cache = ItemMeasureCache::with_estimated_height(50.0);
+        cache.set_capacity(Some(2));
+        cache.set_item_count(10);
+
+        cache.set(1, ItemMeasurement::from_height(80.0));
+        cache.set(2, ItemMeasurement::from_height(10.0));
+        // Touch 1 so 2 is the least recently used
+        assert_eq!(cache.access(1).unwrap().height, 80.0);
+        cache.set(3, ItemMeasurement::from_height(30.0));
+
+        assert!(cache.get(2).is_none());
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.offset_for_index(5), naive_offset(&cache, 5));
+        assert_eq!(cache.offset_for_index(5), 50.0 + 80.0 + 50.0 + 30.0 + 50.0);
+
+        // Re-measuring an evicted item updates the offsets again
+        cache.set(2, ItemMeasurement::from_height(10.0));
+        assert!(cache.get(1).is_none());
+        assert_eq!(cache.offset_for_index(5), naive_offset(&cache, 5));
+        assert_eq!(cache.index_at_offset(105.0), Some(2));
+        assert_eq!(cache.index_at_offset(110.0), Some(3));
+    }
+
+    #[test]
+    fn test_deferred_measurement_shifts_later_offsets() {
+        let mut cache = ItemMeasureCache::with_estimated_height(50.0);
+        cache.set_item_count(1000);
+        assert_eq!(cache.visible_range(0.0, 300.0), VisibleRange::new(0, 6));
+        let before: Vec<f32> = (0..1000).map(|i| cache.offset_for_index(i)).collect();
+
+        // The image for item 500 loaded and is taller than the estimate
+        let invalidation = cache.update(500, ItemMeasurement::new(320.0, 170.0)).unwrap();
+        assert_eq!(invalidation.resized, VisibleRange::new(500, 501));
+        assert_eq!(invalidation.shift, 120.0);
+        assert!(invalidation.needs_layout(500));
+        assert!(!invalidation.needs_layout(501));
+
+        for (index, &offset) in before.iter().enumerate() {
+            let expected = offset + invalidation.offset_shift(index);
+            assert_eq!(cache.offset_for_index(index), expected, "item {index}");
+        }
+        assert_eq!(cache.offset_for_index(501), 501.0 * 50.0 + 120.0);
+        assert_eq!(cache.visible_range(25_000.0, 100.0), VisibleRange::new(500, 501));
+
+        // Reporting the same height again changes nothing
+        assert_eq!(cache.update(500, ItemMeasurement::from_height(170.0)), None);
+        let invalidation = cache.update(10, ItemMeasurement::from_height(20.0)).unwrap();
+        assert_eq!(invalidation.shift, -30.0);
+        assert_eq!(invalidation.offset_shift(5), 0.0);
+        assert_eq!(cache.offset_for_index(501), 501.0 * 50.0 + 90.0);
+    }
+
+    #[test]
+    fn test_update_reports_evicted_item() {
+        let mut cache = ItemMeasureCache::with_capacity(1);
+        cache.set_item_count(1000);
+        cache.update(10, ItemMeasurement::from_height(100.0)).unwrap();
+        let before: Vec<f32> = (0..1000).map(|i| cache.offset_for_index(i)).collect();
+
+        // Measuring item 500 evicts item 10, which falls back to the estimate
+        let invalidation = cache.update(500, ItemMeasurement::from_height(170.0)).unwrap();
+        assert!(cache.get(10).is_none());
+        assert_eq!(invalidation.shift, 122.0);
+        assert_eq!(invalidation.evicted, Some((10, -52.0)));
+        assert!(invalidation.needs_layout(10));
+        assert!(invalidation.needs_layout(500));
+        assert_eq!(invalidation.offset_shift(200), -52.0);
+
+        for (index, &offset) in before.iter().enumerate() {
+            let expected = offset + invalidation.offset_shift(index);
+            assert_eq!(cache.offset_for_index(index), expected, "item {index}");
+        }
+    }
+
+    #[test]
+    fn test_deferred_strategy_uses_estimates_until_reported() {
+        let context = MeasureContext::default().strategy(MeasureStrategy::Deferred);
+        let mut cache = ItemMeasureCache::with_estimated_height(50.0);
+        cache.set_item_count(100);
+
+        for index in cache.visible_range(0.0, 300.0).iter() {
+            let measurement = cache.measure(&context, index, |_| panic!("measured item {index}"));
+            assert_eq!(measurement.height, 50.0);
+        }
+        assert!(cache.is_empty());
+        assert_eq!(cache.visible_range(0.0, 300.0), VisibleRange::new(0, 6));
+
+        // Item 2's size arrives later and pushes item 5 out of the viewport
+        assert!(cache.update(2, ItemMeasurement::from_height(100.0)).is_some());
+        assert_eq!(cache.measure(&context, 2, |_| unreachable!()).height, 100.0);
+        assert_eq!(cache.visible_range(0.0, 300.0), VisibleRange::new(0, 5));
+
+        // Synchronous strategies measure uncached items right away
+        let context = context.strategy(MeasureStrategy::OnRender);
+        let measurement = cache.measure(&context, 7, |_| ItemMeasurement::from_height(80.0));
+        assert_eq!(cache.get(7), Some(&measurement));
+    }
 }
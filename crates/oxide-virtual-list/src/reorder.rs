@@ -0,0 +1,295 @@
+//! Drag-to-reorder support for virtual lists.
+//!
+//! Offsets are computed per index on demand from the list layout, so items
+//! that scroll into the virtualized window mid-drag get the right translation
+//! without any per-item bookkeeping.
+
+use crate::{list::VirtualList, Result, VirtualListError, VisibleRange};
+use serde::{Deserialize, Serialize};
+
+/// Reorder event emitted when a dragged item is dropped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReorderChange {
+    /// Original index of the dragged item
+    pub from: usize,
+    /// Index the item ends up at after the move
+    pub to: usize,
+}
+
+impl ReorderChange {
+    /// Apply the move to a data source
+    pub fn apply<T>(&self, items: &mut Vec<T>) {
+        if self.from < items.len() && self.to < items.len() {
+            let item = items.remove(self.from);
+            items.insert(self.to, item);
+        }
+    }
+}
+
+/// In-progress drag state
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct DragState {
+    /// Index of the dragged item
+    source: usize,
+    /// Content Y offset of the dragged item before the drag
+    origin: f32,
+    /// Space the dragged item occupies (height plus separator)
+    extent: f32,
+    /// Distance from the item top to the grab point
+    grab_offset: f32,
+    /// Current content Y offset of the dragged item's top edge
+    top: f32,
+    /// Current insertion index
+    target: usize,
+}
+
+/// Controller for drag-to-reorder interactions
+///
+/// Drag positions are viewport-relative; the list's current scroll offset is
+/// applied on every update so auto-scrolling while dragging works.
+#[derive(Debug, Clone, Default)]
+pub struct ReorderController {
+    drag: Option<DragState>,
+}
+
+impl ReorderController {
+    /// Create a new reorder controller
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start dragging the item at `index`, grabbed at viewport position `y`
+    pub fn start(&mut self, list: &VirtualList, index: usize, y: f32) -> Result<()> {
+        let total = list.state().total_items;
+        if index >= total {
+            return Err(VirtualListError::InvalidIndex { index, total });
+        }
+
+        let origin = list.offset_for_index(index);
+        let top = y + list.state().scroll_offset;
+        self.drag = Some(DragState {
+            source: index,
+            origin,
+            extent: list.get_item_height(index) + list.config().separator.height(),
+            grab_offset: top - origin,
+            top: origin,
+            target: index,
+        });
+        Ok(())
+    }
+
+    /// Move the drag to viewport position `y`, returning the insertion index
+    pub fn update(&mut self, list: &VirtualList, y: f32) -> Option<usize> {
+        let total = list.state().total_items;
+        let drag = self.drag.as_mut()?;
+
+        drag.top = y + list.state().scroll_offset - drag.grab_offset;
+        let height = list.get_item_height(drag.source);
+        let center = drag.top + height / 2.0;
+
+        // Count the items whose midpoint lies above the dragged item's center
+        let hovered = list.index_at_offset(center);
+        let midpoint = list.offset_for_index(hovered) + list.get_item_height(hovered) / 2.0;
+        let mut before = hovered + usize::from(midpoint < center);
+        if drag.source < before {
+            before -= 1;
+        }
+
+        drag.target = before.min(total.saturating_sub(1));
+        Some(drag.target)
+    }
+
+    /// Finish the drag, returning the move if the item changed position
+    pub fn drop(&mut self) -> Option<ReorderChange> {
+        let drag = self.drag.take()?;
+        (drag.source != drag.target).then_some(ReorderChange {
+            from: drag.source,
+            to: drag.target,
+        })
+    }
+
+    /// Abort the drag without emitting a change
+    pub fn cancel(&mut self) {
+        self.drag = None;
+    }
+
+    /// Check if a drag is in progress
+    pub fn is_dragging(&self) -> bool {
+        self.drag.is_some()
+    }
+
+    /// Get the index of the dragged item
+    pub fn source(&self) -> Option<usize> {
+        self.drag.map(|d| d.source)
+    }
+
+    /// Get the current insertion index
+    pub fn target(&self) -> Option<usize> {
+        self.drag.map(|d| d.target)
+    }
+
+    /// Get the Y translation for an item
+    ///
+    /// The dragged item follows the pointer; items between the source and
+    /// the insertion index slide by the dragged item's extent to make room.
+    pub fn offset_for(&self, index: usize) -> f32 {
+        let Some(drag) = self.drag else {
+            return 0.0;
+        };
+
+        if index == drag.source {
+            drag.top - drag.origin
+        } else if drag.source < index && index <= drag.target {
+            -drag.extent
+        } else if drag.target <= index && index < drag.source {
+            drag.extent
+        } else {
+            0.0
+        }
+    }
+
+    /// Get translations for every item in a range
+    pub fn offsets(&self, range: VisibleRange) -> Vec<(usize, f32)> {
+        range.iter().map(|index| (index, self.offset_for(index))).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixed_list() -> VirtualList {
+        VirtualList::new()
+            .items(100)
+            .fixed_height(50.0)
+            .viewport_height(300.0)
+    }
+
+    #[test]
+    fn test_insertion_index_moving_down() {
+        let list = fixed_list();
+        let mut reorder = ReorderController::new();
+        reorder.start(&list, 2, 125.0).unwrap();
+
+        // Center still inside the original slot
+        assert_eq!(reorder.update(&list, 140.0), Some(2));
+        // Center passes the midpoint of item 3 (175)
+        assert_eq!(reorder.update(&list, 170.0), Some(2));
+        assert_eq!(reorder.update(&list, 180.0), Some(3));
+        // Center past the midpoint of item 5 (275)
+        assert_eq!(reorder.update(&list, 280.0), Some(5));
+    }
+
+    #[test]
+    fn test_insertion_index_moving_up() {
+        let list = fixed_list();
+        let mut reorder = ReorderController::new();
+        reorder.start(&list, 4, 225.0).unwrap();
+
+        assert_eq!(reorder.update(&list, 180.0), Some(4));
+        assert_eq!(reorder.update(&list, 170.0), Some(3));
+        assert_eq!(reorder.update(&list, 20.0), Some(0));
+        assert_eq!(reorder.update(&list, -500.0), Some(0));
+    }
+
+    #[test]
+    fn test_offsets_make_room() {
+        let list = fixed_list();
+        let mut reorder = ReorderController::new();
+        reorder.start(&list, 1, 75.0).unwrap();
+        reorder.update(&list, 180.0);
+        assert_eq!(reorder.target(), Some(3));
+
+        let offsets = reorder.offsets(VisibleRange::new(0, 5));
+        assert_eq!(
+            offsets,
+            vec![(0, 0.0), (1, 105.0), (2, -50.0), (3, -50.0), (4, 0.0)]
+        );
+
+        reorder.update(&list, 75.0);
+        assert_eq!(reorder.offset_for(2), 0.0);
+
+        let mut reorder = ReorderController::new();
+        reorder.start(&list, 4, 225.0).unwrap();
+        reorder.update(&list, 70.0);
+        assert_eq!(reorder.target(), Some(1));
+        assert_eq!(reorder.offset_for(0), 0.0);
+        assert_eq!(reorder.offset_for(1), 50.0);
+        assert_eq!(reorder.offset_for(3), 50.0);
+        assert_eq!(reorder.offset_for(4), -155.0);
+        assert_eq!(reorder.offset_for(5), 0.0);
+    }
+
+    #[test]
+    fn test_variable_heights() {
+        let mut list = VirtualList::new()
+            .items(10)
+            .variable_height(40.0)
+            .viewport_height(400.0);
+        list.set_item_height(1, 100.0);
+        // Positions: 0 -> 0, 1 -> 40, 2 -> 140, 3 -> 180
+
+        let mut reorder = ReorderController::new();
+        reorder.start(&list, 0, 20.0).unwrap();
+
+        // Center must pass the midpoint of the tall item (90)
+        assert_eq!(reorder.update(&list, 85.0), Some(0));
+        assert_eq!(reorder.update(&list, 95.0), Some(1));
+        assert_eq!(reorder.offset_for(1), -40.0);
+        assert_eq!(reorder.offset_for(2), 0.0);
+
+        // Past the midpoint of item 2 (160)
+        assert_eq!(reorder.update(&list, 165.0), Some(2));
+
+        let mut reorder = ReorderController::new();
+        reorder.start(&list, 1, 90.0).unwrap();
+        assert_eq!(reorder.update(&list, 120.0), Some(1));
+        // The tall item's center sits 50px below its top
+        assert_eq!(reorder.update(&list, 165.0), Some(2));
+        assert_eq!(reorder.offset_for(2), -100.0);
+    }
+
+    #[test]
+    fn test_scroll_during_drag() {
+        let mut list = fixed_list();
+        let mut reorder = ReorderController::new();
+        reorder.start(&list, 2, 125.0).unwrap();
+
+        // Auto-scroll brings items far below the original window into view
+        list.set_scroll_offset(1000.0);
+        assert!(!list.visible_range().contains(2));
+        assert_eq!(reorder.update(&list, 130.0), Some(22));
+        assert_eq!(reorder.offset_for(22), -50.0);
+        assert_eq!(reorder.offset_for(23), 0.0);
+
+        let change = reorder.drop().unwrap();
+        assert_eq!(change, ReorderChange { from: 2, to: 22 });
+        assert!(!reorder.is_dragging());
+    }
+
+    #[test]
+    fn test_drop_and_cancel() {
+        let list = fixed_list();
+        let mut reorder = ReorderController::new();
+
+        reorder.start(&list, 3, 175.0).unwrap();
+        assert_eq!(reorder.drop(), None);
+
+        reorder.start(&list, 3, 175.0).unwrap();
+        reorder.update(&list, 25.0);
+        reorder.cancel();
+        assert_eq!(reorder.drop(), None);
+        assert_eq!(reorder.offset_for(0), 0.0);
+
+        assert!(reorder.start(&list, 100, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_apply_change() {
+        let mut items = vec!['a', 'b', 'c', 'd'];
+        ReorderChange { from: 0, to: 2 }.apply(&mut items);
+        assert_eq!(items, vec!['b', 'c', 'a', 'd']);
+        ReorderChange { from: 3, to: 0 }.apply(&mut items);
+        assert_eq!(items, vec!['d', 'b', 'c', 'a']);
+    }
+}
@@ -1,6 +1,19 @@
 //! Scroll management for virtual lists.
 
+use crate::{list::VirtualList, Result, VirtualListError};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::time::Duration;
+
+/// Frame rate the per-frame momentum friction is expressed at
+const FRICTION_FRAME_RATE: f32 = 60.0;
+
+/// Speed in pixels per second below which momentum stops
+const MIN_FLING_VELOCITY: f32 = 10.0;
+
+/// Items on each side of the top visible item kept as restoration fallbacks
+const RESTORATION_NEIGHBORS: usize = 16;
 
 /// Scroll direction
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -26,6 +39,50 @@ pub enum ScrollBehavior {
     Instant,
 }
 
+/// Where to place an item when scrolling it into view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum ScrollAlignment {
+    /// Align the item's top with the top of the viewport
+    Start,
+    /// Center the item in the viewport
+    Center,
+    /// Align the item's bottom with the bottom of the viewport
+    End,
+    /// Scroll the least amount needed to fully reveal the item
+    #[default]
+    Nearest,
+}
+
+/// Easing curve for animated scrolling
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum Easing {
+    /// Constant speed
+    Linear,
+    /// Fast start, slowing towards the target
+    #[default]
+    EaseOutCubic,
+    /// Slow start and end
+    EaseInOutCubic,
+}
+
+impl Easing {
+    /// Map linear progress in `0..=1` to eased progress
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseOutCubic => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOutCubic => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+        }
+    }
+}
+
 /// Scroll position
 #[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
 pub struct ScrollPosition {
@@ -60,6 +117,36 @@ pub struct ScrollEvent {
     pub direction: ScrollDirection,
 }
 
+/// In-flight animated scroll
+#[derive(Debug, Clone, Copy)]
+struct ScrollAnimation {
+    from: ScrollPosition,
+    to: ScrollPosition,
+    duration: Duration,
+    elapsed: Duration,
+    easing: Easing,
+}
+
+impl ScrollAnimation {
+    /// Linear progress through the animation
+    fn progress(&self) -> f32 {
+        if self.duration.is_zero() {
+            1.0
+        } else {
+            (self.elapsed.as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+        }
+    }
+
+    /// Position at the current progress
+    fn position(&self) -> ScrollPosition {
+        let eased = self.easing.apply(self.progress());
+        ScrollPosition::new(
+            self.from.top + (self.to.top - self.from.top) * eased,
+            self.from.left + (self.to.left - self.from.left) * eased,
+        )
+    }
+}
+
 /// State for scroll management
 #[derive(Debug, Clone, Default)]
 pub struct ScrollState {
@@ -73,6 +160,114 @@ pub struct ScrollState {
     pub is_momentum: bool,
 }
 
+impl ScrollState {
+    /// Capture the scroll position relative to the top visible item
+    ///
+    /// The item is identified by `key_fn` so the position survives reloads
+    /// that insert, remove or resize items. Nearby items are captured too, to
+    /// fall back on if the top item is gone. Returns `None` for empty lists.
+    pub fn save_restoration<K, F>(
+        &self,
+        list: &VirtualList,
+        key_fn: F,
+    ) -> Option<ScrollRestoration<K>>
+    where
+        F: Fn(usize) -> K,
+    {
+        let total = list.state().total_items;
+        if total == 0 {
+            return None;
+        }
+
+        let top = self.position.top;
+        let first = list.index_at_offset(top);
+        let neighbors = (1..=RESTORATION_NEIGHBORS)
+            .flat_map(|distance| [first.checked_add(distance), first.checked_sub(distance)])
+            .flatten()
+            .filter(|&index| index < total);
+        let anchors = std::iter::once(first)
+            .chain(neighbors)
+            .filter(|&index| !list.is_pinned(index))
+            .map(|index| (key_fn(index), top - list.offset_for_index(index)))
+            .collect();
+
+        Some(ScrollRestoration {
+            anchors,
+            top,
+            left: self.position.left,
+        })
+    }
+
+    /// Re-derive the scroll position from a saved restoration
+    ///
+    /// Scrolls so the saved item sits at the same offset as before, snapping
+    /// to the nearest surviving neighbor if it was removed. Falls back to the
+    /// saved offset when none of them survive.
+    pub fn restore<K, F>(
+        &mut self,
+        list: &VirtualList,
+        restoration: &ScrollRestoration<K>,
+        key_fn: F,
+    ) -> ScrollPosition
+    where
+        K: Eq + Hash,
+        F: Fn(usize) -> K,
+    {
+        let ranks: HashMap<&K, usize> = restoration
+            .anchors
+            .iter()
+            .enumerate()
+            .map(|(rank, (key, _))| (key, rank))
+            .collect();
+
+        let (mut best_rank, mut best_index) = (usize::MAX, None);
+        for index in (0..list.state().total_items).filter(|&i| !list.is_pinned(i)) {
+            let Some(&rank) = ranks.get(&key_fn(index)) else {
+                continue;
+            };
+            if rank < best_rank {
+                (best_rank, best_index) = (rank, Some(index));
+                if rank == 0 {
+                    break;
+                }
+            }
+        }
+
+        let top = match best_index {
+            Some(index) => list.offset_for_index(index) + restoration.anchors[best_rank].1,
+            None => restoration.top,
+        };
+        self.position = ScrollPosition::new(
+            top.clamp(0.0, list.max_scroll_offset()),
+            restoration.left,
+        );
+        self.position
+    }
+}
+
+/// Scroll position saved relative to stable item keys
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScrollRestoration<K> {
+    /// Item keys with the scroll offset from each item's top, nearest first
+    anchors: Vec<(K, f32)>,
+    /// Scroll offset at the time of saving
+    top: f32,
+    /// Horizontal offset at the time of saving
+    left: f32,
+}
+
+impl<K> ScrollRestoration<K> {
+    /// Get the key of the item that was at the top of the viewport
+    pub fn key(&self) -> Option<&K> {
+        self.anchors.first().map(|(key, _)| key)
+    }
+
+    /// Get the scroll offset into the top item
+    pub fn offset(&self) -> f32 {
+        self.anchors.first().map_or(0.0, |&(_, offset)| offset)
+    }
+}
+
 /// Configuration for sticky elements
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StickyConfig {
@@ -92,7 +287,10 @@ impl Default for StickyConfig {
 }
 
 /// Sticky element
+///
+/// Build with [`StickyElement::new`]; more fields may be added later.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct StickyElement {
     /// Item index
     pub index: usize,
@@ -100,6 +298,31 @@ pub struct StickyElement {
     pub config: StickyConfig,
     /// Original position
     pub original_y: f32,
+    /// Height covered while pinned
+    pub height: f32,
+}
+
+impl StickyElement {
+    /// Create a new sticky element
+    pub fn new(index: usize, original_y: f32, height: f32) -> Self {
+        Self {
+            index,
+            config: StickyConfig::default(),
+            original_y,
+            height,
+        }
+    }
+
+    /// Set the sticky config
+    pub fn with_config(mut self, config: StickyConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Get the viewport space this element covers once pinned
+    pub fn pinned_extent(&self) -> f32 {
+        self.config.offset + self.height
+    }
 }
 
 /// Scroll configuration
@@ -111,12 +334,19 @@ pub struct ScrollConfig {
     pub behavior: ScrollBehavior,
     /// Enable momentum scrolling
     pub momentum: bool,
-    /// Friction for momentum
+    /// Friction for momentum, as velocity kept per frame at 60fps
     pub friction: f32,
+    /// Maximum fling speed in pixels per second
+    #[serde(default = "default_max_velocity")]
+    pub max_velocity: f32,
     /// Bounce on edges
     pub bounce: bool,
 }
 
+fn default_max_velocity() -> f32 {
+    8000.0
+}
+
 impl Default for ScrollConfig {
     fn default() -> Self {
         Self {
@@ -124,6 +354,7 @@ impl Default for ScrollConfig {
             behavior: ScrollBehavior::Smooth,
             momentum: true,
             friction: 0.95,
+            max_velocity: default_max_velocity(),
             bounce: false,
         }
     }
@@ -140,6 +371,12 @@ pub struct ScrollController {
     pub content_height: f32,
     /// Viewport height
     pub viewport_height: f32,
+    /// Sticky elements pinned to the top while scrolled past
+    pub sticky: Vec<StickyElement>,
+    /// Scroll events emitted by momentum and animation ticks
+    events: Vec<ScrollEvent>,
+    /// In-flight animated scroll
+    animation: Option<ScrollAnimation>,
 }
 
 impl Default for ScrollController {
@@ -149,6 +386,9 @@ impl Default for ScrollController {
             state: ScrollState::default(),
             content_height: 0.0,
             viewport_height: 0.0,
+            sticky: Vec::new(),
+            events: Vec::new(),
+            animation: None,
         }
     }
 }
@@ -197,4 +437,446 @@ impl ScrollController {
     pub fn at_bottom(&self) -> bool {
         self.state.position.top >= self.max_scroll()
     }
+
+    /// Start momentum scrolling with a vertical velocity in pixels per second
+    ///
+    /// Positive velocities scroll towards the end of the content. The speed is
+    /// capped at [`ScrollConfig::max_velocity`]; flings are ignored when
+    /// momentum is disabled.
+    pub fn apply_fling(&mut self, velocity: f32) {
+        self.animation = None;
+        if !self.config.momentum || velocity.abs() < MIN_FLING_VELOCITY {
+            self.stop_momentum();
+            return;
+        }
+
+        let max = self.config.max_velocity.abs();
+        self.state.velocity.top = velocity.clamp(-max, max);
+        self.state.is_momentum = true;
+        self.state.is_scrolling = true;
+    }
+
+    /// Animate the scroll position to `position` over `duration`
+    ///
+    /// The animation is driven by [`tick`](Self::tick) and replaces any
+    /// in-flight animation or momentum, starting from the current position.
+    pub fn animate_to(&mut self, position: ScrollPosition, duration: Duration, easing: Easing) {
+        self.stop_momentum();
+        let to = ScrollPosition::new(
+            position.top.clamp(0.0, self.max_scroll()),
+            position.left.max(0.0),
+        );
+        self.animation = Some(ScrollAnimation {
+            from: self.state.position,
+            to,
+            duration,
+            elapsed: Duration::ZERO,
+            easing,
+        });
+        self.state.is_scrolling = true;
+    }
+
+    /// Check if an animated scroll is in flight
+    pub fn is_animating(&self) -> bool {
+        self.animation.is_some()
+    }
+
+    /// Stop an in-flight animated scroll where it is
+    pub fn cancel_animation(&mut self) {
+        if self.animation.take().is_some() {
+            self.state.is_scrolling = false;
+        }
+    }
+
+    /// Scroll by a user drag, cancelling any animation or momentum
+    pub fn drag_by(&mut self, delta: f32) {
+        self.cancel_animation();
+        self.stop_momentum();
+
+        let previous = self.state.position;
+        self.scroll_to(ScrollPosition::new(previous.top + delta, previous.left));
+        self.emit(previous);
+    }
+
+    /// Advance animated or momentum scrolling by `dt`
+    ///
+    /// Animations interpolate towards their target with their easing curve.
+    /// Momentum moves by the current velocity, then decays it by the
+    /// configured friction; it stops once the speed drops below a threshold
+    /// or the scroll reaches a content edge.
+    pub fn tick(&mut self, dt: Duration) -> ScrollPosition {
+        let previous = self.state.position;
+        if let Some(animation) = &mut self.animation {
+            animation.elapsed += dt;
+            let (position, done) = (animation.position(), animation.progress() >= 1.0);
+            self.scroll_to(position);
+            if done {
+                self.cancel_animation();
+            }
+        } else if self.state.is_momentum {
+            self.step_momentum(dt);
+        } else {
+            return previous;
+        }
+
+        self.emit(previous);
+        self.state.position
+    }
+
+    /// Move by the momentum velocity and apply friction
+    fn step_momentum(&mut self, dt: Duration) {
+        let seconds = dt.as_secs_f32();
+        let previous = self.state.position;
+        let velocity = self.state.velocity.top;
+        self.scroll_to(ScrollPosition::new(
+            previous.top + velocity * seconds,
+            previous.left,
+        ));
+
+        let decayed = velocity * self.config.friction.powf(seconds * FRICTION_FRAME_RATE);
+        let hit_edge = (velocity < 0.0 && self.at_top()) || (velocity > 0.0 && self.at_bottom());
+        if hit_edge || decayed.abs() < MIN_FLING_VELOCITY {
+            self.stop_momentum();
+        } else {
+            self.state.velocity.top = decayed;
+        }
+    }
+
+    /// Record a scroll event if the position moved
+    fn emit(&mut self, previous: ScrollPosition) {
+        let position = self.state.position;
+        if position != previous {
+            self.events.push(ScrollEvent {
+                position,
+                previous,
+                delta: ScrollPosition::new(
+                    position.top - previous.top,
+                    position.left - previous.left,
+                ),
+                direction: ScrollDirection::Vertical,
+            });
+        }
+    }
+
+    /// Stop any momentum scrolling in place
+    pub fn stop_momentum(&mut self) {
+        self.state.velocity = ScrollPosition::default();
+        self.state.is_momentum = false;
+        self.state.is_scrolling = false;
+    }
+
+    /// Take the scroll events emitted since the last call
+    pub fn take_events(&mut self) -> Vec<ScrollEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Register a sticky element
+    pub fn add_sticky(&mut self, element: StickyElement) {
+        self.sticky.push(element);
+    }
+
+    /// Scroll a list item into view with the given alignment
+    ///
+    /// Sticky elements before the item are pinned once it is revealed, so
+    /// their height is kept clear above it. The horizontal offset is kept.
+    pub fn scroll_to_index(
+        &mut self,
+        list: &VirtualList,
+        index: usize,
+        alignment: ScrollAlignment,
+    ) -> Result<ScrollPosition> {
+        let total = list.state().total_items;
+        if index >= total {
+            return Err(VirtualListError::InvalidIndex { index, total });
+        }
+
+        let item_top = list.offset_for_index(index);
+        let item_bottom = item_top + list.get_item_height(index);
+        let inset = self
+            .sticky
+            .iter()
+            .filter(|s| s.index < index)
+            .map(StickyElement::pinned_extent)
+            .fold(0.0, f32::max);
+
+        let current = self.state.position.top;
+        let start = item_top - inset;
+        let end = item_bottom - self.viewport_height;
+        let top = match alignment {
+            ScrollAlignment::Start => start,
+            ScrollAlignment::End => end,
+            ScrollAlignment::Center => {
+                let visible = self.viewport_height - inset;
+                (item_top + item_bottom) / 2.0 - inset - visible / 2.0
+            }
+            ScrollAlignment::Nearest => {
+                if item_top < current + inset || end > start {
+                    // Above the viewport, or too tall to fit: show its top
+                    start
+                } else if item_bottom > current + self.viewport_height {
+                    end
+                } else {
+                    current
+                }
+            }
+        };
+
+        self.scroll_to(ScrollPosition::new(top, self.state.position.left));
+        Ok(self.state.position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> (VirtualList, ScrollController) {
+        let list = VirtualList::new()
+            .items(100)
+            .fixed_height(50.0)
+            .viewport_height(300.0);
+        let mut controller = ScrollController::new();
+        controller.set_dimensions(list.content_height(), 300.0);
+        (list, controller)
+    }
+
+    fn reveal(
+        controller: &mut ScrollController,
+        list: &VirtualList,
+        index: usize,
+        alignment: ScrollAlignment,
+    ) -> f32 {
+        controller.scroll_to_index(list, index, alignment).unwrap().top
+    }
+
+    #[test]
+    fn test_scroll_to_index_alignments() {
+        let (list, mut controller) = setup();
+
+        assert_eq!(reveal(&mut controller, &list, 20, ScrollAlignment::Start), 1000.0);
+        assert_eq!(reveal(&mut controller, &list, 20, ScrollAlignment::End), 750.0);
+        assert_eq!(reveal(&mut controller, &list, 20, ScrollAlignment::Center), 875.0);
+
+        // Clamped to the scrollable range
+        assert_eq!(reveal(&mut controller, &list, 99, ScrollAlignment::Start), 4700.0);
+        assert_eq!(reveal(&mut controller, &list, 1, ScrollAlignment::End), 0.0);
+        assert!(controller.scroll_to_index(&list, 100, ScrollAlignment::Start).is_err());
+    }
+
+    #[test]
+    fn test_scroll_to_index_nearest() {
+        let (list, mut controller) = setup();
+
+        // Below the viewport: align to the bottom
+        assert_eq!(reveal(&mut controller, &list, 20, ScrollAlignment::Nearest), 750.0);
+        // Already fully visible: no-op
+        controller.scroll_to(ScrollPosition::from_top(900.0));
+        assert_eq!(reveal(&mut controller, &list, 20, ScrollAlignment::Nearest), 900.0);
+        assert_eq!(reveal(&mut controller, &list, 23, ScrollAlignment::Nearest), 900.0);
+        // Partially cut off at the top: align to the top
+        controller.scroll_to(ScrollPosition::from_top(1020.0));
+        assert_eq!(reveal(&mut controller, &list, 20, ScrollAlignment::Nearest), 1000.0);
+    }
+
+    #[test]
+    fn test_scroll_to_index_sticky_header() {
+        let (list, mut controller) = setup();
+        controller.add_sticky(StickyElement::new(5, 250.0, 40.0));
+
+        assert_eq!(reveal(&mut controller, &list, 20, ScrollAlignment::Start), 960.0);
+        assert_eq!(reveal(&mut controller, &list, 20, ScrollAlignment::End), 750.0);
+        // Centered in the 260px left below the header
+        assert_eq!(reveal(&mut controller, &list, 20, ScrollAlignment::Center), 855.0);
+
+        // Visible but hidden behind the pinned header
+        controller.scroll_to(ScrollPosition::from_top(980.0));
+        assert_eq!(reveal(&mut controller, &list, 20, ScrollAlignment::Nearest), 960.0);
+        assert_eq!(reveal(&mut controller, &list, 21, ScrollAlignment::Nearest), 960.0);
+
+        // Items before the header are not covered by it
+        assert_eq!(reveal(&mut controller, &list, 2, ScrollAlignment::Start), 100.0);
+
+        let offset = StickyConfig { offset: 8.0, ..StickyConfig::default() };
+        assert_eq!(StickyElement::new(5, 250.0, 40.0).with_config(offset).pinned_extent(), 48.0);
+    }
+
+    fn settle(controller: &mut ScrollController, max_ticks: usize) -> usize {
+        let frame = Duration::from_millis(16);
+        (1..=max_ticks)
+            .find(|_| {
+                controller.tick(frame);
+                !controller.state.is_momentum
+            })
+            .expect("momentum did not settle")
+    }
+
+    #[test]
+    fn test_fling_settles_within_bounds() {
+        let (_, mut controller) = setup();
+        controller.apply_fling(5000.0);
+        assert!(controller.state.is_momentum);
+
+        let ticks = settle(&mut controller, 600);
+        assert!(ticks > 10);
+        let top = controller.state.position.top;
+        assert!(top > 1000.0 && top < controller.max_scroll(), "settled at {top}");
+        assert_eq!(controller.state.velocity.top, 0.0);
+
+        let events = controller.take_events();
+        assert_eq!(events.len(), ticks);
+        assert!(events.iter().all(|e| e.delta.top > 0.0));
+        assert_eq!(events.last().unwrap().position.top, top);
+        assert!(controller.take_events().is_empty());
+
+        // Settled: further ticks are no-ops
+        assert_eq!(controller.tick(Duration::from_millis(16)).top, top);
+        assert!(controller.take_events().is_empty());
+    }
+
+    #[test]
+    fn test_fling_clamps_at_edges() {
+        let (_, mut controller) = setup();
+        controller.scroll_to(ScrollPosition::from_top(4500.0));
+        controller.apply_fling(1.0e6);
+        assert_eq!(controller.state.velocity.top, 8000.0);
+
+        settle(&mut controller, 10);
+        assert_eq!(controller.state.position.top, controller.max_scroll());
+        assert!(controller.at_bottom());
+
+        controller.apply_fling(-3000.0);
+        settle(&mut controller, 600);
+        assert!(controller.state.position.top >= 0.0);
+        assert!(controller.state.position.top < controller.max_scroll());
+
+        // Flinging into the top edge stops immediately without moving
+        controller.scroll_to_top();
+        controller.take_events();
+        controller.apply_fling(-3000.0);
+        assert_eq!(settle(&mut controller, 1), 1);
+        assert_eq!(controller.state.position.top, 0.0);
+        assert!(controller.take_events().is_empty());
+    }
+
+    #[test]
+    fn test_fling_disabled_or_too_slow() {
+        let (_, mut controller) = setup();
+        controller.apply_fling(5.0);
+        assert!(!controller.state.is_momentum);
+
+        controller.config.momentum = false;
+        controller.apply_fling(5000.0);
+        assert!(!controller.state.is_momentum);
+        assert_eq!(controller.tick(Duration::from_millis(16)).top, 0.0);
+    }
+
+    #[test]
+    fn test_restore_after_reload() {
+        let mut list = VirtualList::new()
+            .items(100)
+            .variable_height(50.0)
+            .viewport_height(300.0);
+        let mut keys: Vec<u32> = (0..100).collect();
+
+        let mut scroll = ScrollState {
+            position: ScrollPosition::from_top(1020.0),
+            ..ScrollState::default()
+        };
+        let saved = scroll.save_restoration(&list, |i| keys[i]).unwrap();
+        assert_eq!(saved.key(), Some(&20));
+        assert_eq!(saved.offset(), 20.0);
+
+        // Ten items inserted at the top and an earlier item grows
+        keys.splice(0..0, 1000..1010);
+        list.set_item_count(110);
+        list.set_item_height(3, 150.0);
+        let position = scroll.restore(&list, &saved, |i| keys[i]);
+        assert_eq!(position.top, 30.0 * 50.0 + 100.0 + 20.0);
+        assert_eq!(list.index_at_offset(position.top), 30);
+    }
+
+    #[test]
+    fn test_restore_snaps_to_surviving_neighbor() {
+        let list = VirtualList::new()
+            .items(100)
+            .fixed_height(50.0)
+            .viewport_height(300.0);
+        let mut keys: Vec<u32> = (0..100).collect();
+
+        let mut scroll = ScrollState {
+            position: ScrollPosition::new(1020.0, 8.0),
+            ..ScrollState::default()
+        };
+        let saved = scroll.save_restoration(&list, |i| keys[i]).unwrap();
+
+        // The top item and the one after it are removed
+        keys.retain(|&k| k != 20 && k != 21);
+        let list = VirtualList::new()
+            .items(98)
+            .fixed_height(50.0)
+            .viewport_height(300.0);
+        let position = scroll.restore(&list, &saved, |i| keys[i]);
+        // Item 19 survives and keeps its place just above the viewport
+        assert_eq!(position, ScrollPosition::new(19.0 * 50.0 + 70.0, 8.0));
+
+        // Nothing nearby survives: keep the raw offset, clamped
+        let short = VirtualList::new()
+            .items(10)
+            .fixed_height(50.0)
+            .viewport_height(300.0);
+        let position = scroll.restore(&short, &saved, |i| 500 + i as u32);
+        assert_eq!(position.top, short.max_scroll_offset());
+
+        let empty = VirtualList::new().fixed_height(50.0);
+        assert!(scroll.save_restoration(&empty, |i| i).is_none());
+    }
+
+    #[test]
+    fn test_animate_to_reaches_target() {
+        let (_, mut controller) = setup();
+        let frame = Duration::from_millis(16);
+        let duration = Duration::from_millis(300);
+        controller.animate_to(ScrollPosition::from_top(1000.0), duration, Easing::EaseOutCubic);
+        assert!(controller.is_animating());
+
+        let first = controller.tick(frame).top;
+        // Ease-out covers more ground early than a linear ramp
+        assert!(first > 1000.0 * 16.0 / 300.0);
+        for _ in 0..17 {
+            controller.tick(frame);
+        }
+        // 288ms in: not there yet
+        assert!(controller.is_animating());
+        controller.tick(frame);
+        assert!(!controller.is_animating());
+        assert!((controller.state.position.top - 1000.0).abs() < 1e-3);
+
+        let events = controller.take_events();
+        assert_eq!(events.len(), 19);
+        assert!(events.windows(2).all(|w| w[1].position.top >= w[0].position.top));
+        assert!(!controller.state.is_scrolling);
+    }
+
+    #[test]
+    fn test_animation_is_cancelled() {
+        let (_, mut controller) = setup();
+        let frame = Duration::from_millis(50);
+        let duration = Duration::from_millis(200);
+        controller.animate_to(ScrollPosition::from_top(2000.0), duration, Easing::Linear);
+        assert_eq!(controller.tick(frame).top, 500.0);
+
+        // A new animation starts from where the old one was
+        controller.animate_to(ScrollPosition::from_top(100.0), duration, Easing::Linear);
+        assert_eq!(controller.tick(frame).top, 400.0);
+
+        // A user drag takes over
+        controller.drag_by(-50.0);
+        assert!(!controller.is_animating());
+        assert_eq!(controller.tick(frame).top, 350.0);
+
+        // Targets are clamped to the content
+        controller.animate_to(ScrollPosition::from_top(1.0e6), Duration::ZERO, Easing::Linear);
+        assert_eq!(controller.tick(frame).top, controller.max_scroll());
+        assert_eq!(Easing::EaseInOutCubic.apply(0.5), 0.5);
+    }
 }
@@ -1,7 +1,9 @@
 //! Selection management for virtual lists.
 
+use crate::Rect;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
+use std::ops::Range;
 
 /// Multi-select mode
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -34,6 +36,14 @@ pub enum KeyboardAction {
     ClearSelection,
     /// Toggle current selection
     ToggleCurrent,
+    /// Move focus one page down
+    PageDown,
+    /// Move focus one page up
+    PageUp,
+    /// Move focus to the first item
+    Home,
+    /// Move focus to the last item
+    End,
 }
 
 /// Selection range
@@ -84,7 +94,7 @@ impl SelectionRange {
 }
 
 /// Selection change event
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct SelectionChange {
     /// Newly selected indices
     pub added: Vec<usize>,
@@ -92,6 +102,13 @@ pub struct SelectionChange {
     pub removed: Vec<usize>,
 }
 
+impl SelectionChange {
+    /// Check if the selection did not change
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
 /// Selection configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SelectionConfig {
@@ -162,6 +179,8 @@ pub struct SelectionController {
     pub state: SelectionState,
     /// Total items
     pub total_items: usize,
+    /// Item ranges of collapsed sections, skipped by navigation and ranges
+    collapsed: Vec<Range<usize>>,
 }
 
 impl Default for SelectionController {
@@ -170,6 +189,7 @@ impl Default for SelectionController {
             config: SelectionConfig::default(),
             state: SelectionState::default(),
             total_items: 0,
+            collapsed: Vec::new(),
         }
     }
 }
@@ -187,6 +207,45 @@ impl SelectionController {
         self.state.selected.retain(|&i| i < total);
     }
 
+    /// Set the item ranges hidden by collapsed sections
+    ///
+    /// Pass [`VirtualList::collapsed_ranges`](crate::VirtualList::collapsed_ranges)
+    /// after collapsing or expanding. Selections inside the ranges are dropped.
+    pub fn set_collapsed(&mut self, ranges: impl IntoIterator<Item = Range<usize>>) {
+        self.collapsed = ranges.into_iter().collect();
+        let collapsed = &self.collapsed;
+        self.state
+            .selected
+            .retain(|i| !collapsed.iter().any(|range| range.contains(i)));
+    }
+
+    /// Check if an item belongs to a collapsed section
+    pub fn is_item_collapsed(&self, index: usize) -> bool {
+        self.collapsed.iter().any(|range| range.contains(&index))
+    }
+
+    /// Select the next item outside collapsed sections
+    ///
+    /// Starts from the first item without a focus. Stays put at the end.
+    pub fn select_next(&mut self) -> SelectionChange {
+        let start = self.state.focus.map_or(0, |focus| focus + 1);
+        match (start..self.total_items).find(|&i| !self.is_item_collapsed(i)) {
+            Some(index) => self.select(index),
+            None => SelectionChange::default(),
+        }
+    }
+
+    /// Select the previous item outside collapsed sections
+    ///
+    /// Stays put at the start.
+    pub fn select_previous(&mut self) -> SelectionChange {
+        let end = self.state.focus.unwrap_or(0).min(self.total_items);
+        match (0..end).rev().find(|&i| !self.is_item_collapsed(i)) {
+            Some(index) => self.select(index),
+            None => SelectionChange::default(),
+        }
+    }
+
     /// Select single item (replacing existing selection)
     pub fn select(&mut self, index: usize) -> SelectionChange {
         let removed: Vec<_> = self.state.selected.drain().collect();
@@ -220,10 +279,11 @@ impl SelectionController {
     pub fn select_range(&mut self, index: usize) -> SelectionChange {
         let anchor = self.state.anchor.unwrap_or(index);
         let range = SelectionRange::new(anchor, index);
-        let added: Vec<_> = range.iter().filter(|i| !self.state.selected.contains(i)).collect();
-        for i in range.iter() {
-            self.state.selected.insert(i);
-        }
+        let added: Vec<_> = range
+            .iter()
+            .filter(|&i| !self.state.selected.contains(&i) && !self.is_item_collapsed(i))
+            .collect();
+        self.state.selected.extend(added.iter().copied());
         self.state.focus = Some(index);
         SelectionChange {
             added,
@@ -231,12 +291,40 @@ impl SelectionController {
         }
     }
 
+    /// Select from the anchor to `index` inclusive (Shift+click)
+    ///
+    /// Replaces the selection with the range while keeping the anchor, so
+    /// repeated Shift+clicks pivot around the last plain click. The change
+    /// lists only the indices that actually toggled, in ascending order.
+    pub fn select_range_to(&mut self, index: usize) -> SelectionChange {
+        if self.config.multi_select == MultiSelectMode::None || !self.config.range_selection {
+            return self.select(index);
+        }
+
+        let anchor = *self.state.anchor.get_or_insert(index);
+        let range = SelectionRange::new(anchor, index);
+        let mut removed: Vec<_> = self
+            .state
+            .selected
+            .iter()
+            .copied()
+            .filter(|&i| !range.contains(i))
+            .collect();
+        removed.sort_unstable();
+        for i in &removed {
+            self.state.selected.remove(i);
+        }
+
+        let added = self.select_range(index).added;
+        SelectionChange { added, removed }
+    }
+
     /// Select all
     pub fn select_all(&mut self) -> SelectionChange {
         let added: Vec<_> = (0..self.total_items)
-            .filter(|i| !self.state.selected.contains(i))
+            .filter(|&i| !self.state.selected.contains(&i) && !self.is_item_collapsed(i))
             .collect();
-        self.state.selected = (0..self.total_items).collect();
+        self.state.selected.extend(added.iter().copied());
         SelectionChange {
             added,
             removed: vec![],
@@ -251,4 +339,244 @@ impl SelectionController {
             removed,
         }
     }
+
+    /// Select items whose bounds intersect a marquee, replacing the selection
+    ///
+    /// Without multi-select only the first intersecting item is selected.
+    pub fn select_in_rect(
+        &mut self,
+        rect: Rect,
+        item_rects: impl IntoIterator<Item = (usize, Rect)>,
+    ) -> SelectionChange {
+        let hits = self.marquee_hits(rect, item_rects);
+        let removed: Vec<_> = self
+            .state
+            .selected
+            .iter()
+            .copied()
+            .filter(|i| !hits.contains(i))
+            .collect();
+        let added: Vec<_> = hits
+            .iter()
+            .copied()
+            .filter(|i| !self.state.selected.contains(i))
+            .collect();
+        self.state.selected = hits.iter().copied().collect();
+        self.focus_marquee(&hits);
+        SelectionChange { added, removed }
+    }
+
+    /// Add items whose bounds intersect a marquee to the selection (Shift+drag)
+    ///
+    /// Falls back to [`select_in_rect`](Self::select_in_rect) without multi-select.
+    pub fn extend_in_rect(
+        &mut self,
+        rect: Rect,
+        item_rects: impl IntoIterator<Item = (usize, Rect)>,
+    ) -> SelectionChange {
+        if self.config.multi_select == MultiSelectMode::None {
+            return self.select_in_rect(rect, item_rects);
+        }
+
+        let hits = self.marquee_hits(rect, item_rects);
+        let added: Vec<_> = hits
+            .iter()
+            .copied()
+            .filter(|i| !self.state.selected.contains(i))
+            .collect();
+        self.state.selected.extend(added.iter().copied());
+        self.focus_marquee(&hits);
+        SelectionChange {
+            added,
+            removed: vec![],
+        }
+    }
+
+    /// Collect the sorted indices hit by a marquee
+    fn marquee_hits(
+        &self,
+        rect: Rect,
+        item_rects: impl IntoIterator<Item = (usize, Rect)>,
+    ) -> BTreeSet<usize> {
+        let mut hits: BTreeSet<usize> = item_rects
+            .into_iter()
+            .filter(|(_, bounds)| rect.intersects(bounds))
+            .map(|(index, _)| index)
+            .collect();
+        if self.config.multi_select == MultiSelectMode::None {
+            hits = hits.into_iter().take(1).collect();
+        }
+        hits
+    }
+
+    /// Anchor at the first marquee hit and focus the last
+    fn focus_marquee(&mut self, hits: &BTreeSet<usize>) {
+        if let (Some(&first), Some(&last)) = (hits.first(), hits.last()) {
+            self.state.anchor = Some(first);
+            self.state.focus = Some(last);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Item rects for a 3x3 grid of 100x100 cells with a 10px gap
+    fn grid_rects() -> Vec<(usize, Rect)> {
+        (0..9)
+            .map(|i| {
+                let (row, col) = (i / 3, i % 3);
+                (i, Rect::new(col as f32 * 110.0, row as f32 * 110.0, 100.0, 100.0))
+            })
+            .collect()
+    }
+
+    fn multi_controller() -> SelectionController {
+        let mut controller = SelectionController::new();
+        controller.config.multi_select = MultiSelectMode::Modifier;
+        controller.set_total(9);
+        controller
+    }
+
+    #[test]
+    fn test_select_in_rect_contained() {
+        let mut controller = multi_controller();
+        let change = controller.select_in_rect(Rect::new(-5.0, -5.0, 220.0, 220.0), grid_rects());
+
+        assert_eq!(controller.state.to_vec(), vec![0, 1, 3, 4]);
+        let mut added = change.added;
+        added.sort();
+        assert_eq!(added, vec![0, 1, 3, 4]);
+        assert_eq!(controller.state.anchor, Some(0));
+        assert_eq!(controller.state.focus, Some(4));
+    }
+
+    #[test]
+    fn test_select_in_rect_partial_overlap() {
+        let mut controller = multi_controller();
+        // Clips the corners of items 4, 5, 7 and 8; misses the gap columns
+        controller.select_in_rect(Rect::new(150.0, 150.0, 120.0, 120.0), grid_rects());
+        assert_eq!(controller.state.to_vec(), vec![4, 5, 7, 8]);
+
+        // A marquee entirely inside the gap selects nothing
+        let change = controller.select_in_rect(Rect::new(101.0, 0.0, 8.0, 300.0), grid_rects());
+        assert!(controller.state.is_empty());
+        assert_eq!(change.removed.len(), 4);
+    }
+
+    #[test]
+    fn test_select_in_rect_replaces_selection() {
+        let mut controller = multi_controller();
+        controller.select(8);
+        let change = controller.select_in_rect(Rect::new(0.0, 0.0, 50.0, 50.0), grid_rects());
+
+        assert_eq!(controller.state.to_vec(), vec![0]);
+        assert_eq!(change.added, vec![0]);
+        assert_eq!(change.removed, vec![8]);
+    }
+
+    #[test]
+    fn test_extend_in_rect_is_additive() {
+        let mut controller = multi_controller();
+        controller.select(8);
+        let change = controller.extend_in_rect(Rect::new(0.0, 0.0, 150.0, 50.0), grid_rects());
+
+        assert_eq!(controller.state.to_vec(), vec![0, 1, 8]);
+        let mut added = change.added;
+        added.sort();
+        assert_eq!(added, vec![0, 1]);
+        assert!(change.removed.is_empty());
+    }
+
+    #[test]
+    fn test_marquee_without_multi_select() {
+        let mut controller = SelectionController::new();
+        controller.set_total(9);
+        controller.select(8);
+
+        controller.extend_in_rect(Rect::new(0.0, 0.0, 330.0, 330.0), grid_rects());
+        assert_eq!(controller.state.to_vec(), vec![0]);
+        assert_eq!(controller.state.focus, Some(0));
+    }
+
+    #[test]
+    fn test_select_range_to_from_anchor() {
+        let mut controller = multi_controller();
+        controller.set_total(20);
+
+        controller.select(5);
+        assert_eq!(controller.state.anchor, Some(5));
+        let change = controller.select_range_to(10);
+        assert_eq!(controller.state.to_vec(), vec![5, 6, 7, 8, 9, 10]);
+        assert_eq!(change.added, vec![6, 7, 8, 9, 10]);
+        assert!(change.removed.is_empty());
+
+        // Shift-extending past the anchor pivots around it
+        let change = controller.select_range_to(3);
+        assert_eq!(controller.state.to_vec(), vec![3, 4, 5]);
+        assert_eq!(change.added, vec![3, 4]);
+        assert_eq!(change.removed, vec![6, 7, 8, 9, 10]);
+        assert_eq!(controller.state.anchor, Some(5));
+        assert_eq!(controller.state.focus, Some(3));
+
+        assert!(controller.select_range_to(3).is_empty());
+    }
+
+    #[test]
+    fn test_select_range_to_resets_with_plain_select() {
+        let mut controller = multi_controller();
+        controller.set_total(20);
+
+        controller.select(5);
+        controller.select_range_to(8);
+        let change = controller.select(12);
+        assert_eq!(controller.state.anchor, Some(12));
+        assert_eq!(change.added, vec![12]);
+
+        let change = controller.select_range_to(14);
+        assert_eq!(controller.state.to_vec(), vec![12, 13, 14]);
+        assert!(change.removed.is_empty());
+    }
+
+    #[test]
+    fn test_select_range_to_without_multi_select() {
+        let mut controller = SelectionController::new();
+        controller.set_total(20);
+
+        controller.select(5);
+        controller.select_range_to(10);
+        assert_eq!(controller.state.to_vec(), vec![10]);
+    }
+
+    #[test]
+    fn test_navigation_and_ranges_skip_collapsed() {
+        let mut controller = multi_controller();
+        controller.set_total(10);
+        controller.select(2);
+        controller.set_collapsed(std::iter::once(3..6));
+
+        controller.select_next();
+        assert_eq!(controller.state.focus, Some(6));
+        controller.select_previous();
+        assert_eq!(controller.state.focus, Some(2));
+
+        let change = controller.select_range_to(8);
+        assert_eq!(controller.state.to_vec(), vec![2, 6, 7, 8]);
+        assert_eq!(change.added, vec![6, 7, 8]);
+
+        controller.select(1);
+        controller.select_range(7);
+        assert_eq!(controller.state.to_vec(), vec![1, 2, 6, 7]);
+
+        controller.select_all();
+        assert_eq!(controller.state.to_vec(), vec![0, 1, 2, 6, 7, 8, 9]);
+
+        // Collapsing drops selections that became hidden
+        controller.set_collapsed(std::iter::once(0..2));
+        assert_eq!(controller.state.to_vec(), vec![2, 6, 7, 8, 9]);
+        controller.select(2);
+        controller.select_previous();
+        assert_eq!(controller.state.focus, Some(2));
+    }
 }